@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::LitStr;
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let directive_name = match name_attribute(&input.attrs)? {
+        Some(name) => name,
+        None => lower_camel_case(&input.ident.to_string()),
+    };
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`FromDirective` can only be derived for structs",
+        ));
+    };
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.as_ref().unwrap();
+                    let argument_name = match name_attribute(&field.attrs)? {
+                        Some(name) => name,
+                        None => lower_camel_case(&ident.unraw().to_string()),
+                    };
+                    Ok(quote!(#ident: args.get(#argument_name)?))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(Self { #(#fields,)* })
+        }
+        Fields::Unit => quote!(Self),
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`FromDirective` cannot be derived for tuple structs, \
+                 since directive arguments are named",
+            ))
+        }
+    };
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::apollo_compiler::schema::FromDirective
+            for #ident #ty_generics #where_clause
+        {
+            const NAME: &'static str = #directive_name;
+
+            fn from_directive(
+                args: &::apollo_compiler::schema::DirectiveArguments<'_>,
+            ) -> ::core::result::Result<Self, ::apollo_compiler::schema::DirectiveExtractionError>
+            {
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+/// The value of `#[directive(name = "…")]`, if any
+fn name_attribute(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let mut name = None;
+    for attr in attrs {
+        if !attr.path().is_ident("directive") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"…\"`"))
+            }
+        })?;
+    }
+    Ok(name)
+}
+
+/// `CacheControl` or `cache_control` to `cacheControl`, following GraphQL naming conventions
+fn lower_camel_case(rust_name: &str) -> String {
+    let mut graphql_name = String::with_capacity(rust_name.len());
+    let mut upper_next = false;
+    for c in rust_name.chars() {
+        if c == '_' && !graphql_name.is_empty() {
+            upper_next = true;
+        } else if graphql_name.is_empty() {
+            graphql_name.extend(c.to_lowercase());
+        } else if upper_next {
+            graphql_name.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            graphql_name.push(c);
+        }
+    }
+    graphql_name
+}
@@ -0,0 +1,291 @@
+#![doc = include_str!("../README.md")]
+
+use apollo_compiler::ast;
+use apollo_compiler::Schema;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use std::path::Path;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse_macro_input;
+use syn::Attribute;
+use syn::DeriveInput;
+use syn::Ident;
+use syn::LitStr;
+use syn::Token;
+use syn::Visibility;
+
+mod from_directive;
+
+/// Name of the source file in diagnostics and in the parsed document
+const PATH: &str = "graphql!";
+
+/// Parse a GraphQL document at compile time, and expand to an expression
+/// of type [`apollo_compiler::ast::Document`].
+///
+/// Syntax errors are reported as compile errors.
+/// The document is given as a string literal, normal or raw:
+///
+/// ```
+/// use apollo_compiler_macros::graphql;
+///
+/// let document = graphql!(r#"
+///     query GetUser($id: ID!) {
+///         user(id: $id) { ...UserFields }
+///     }
+///     fragment UserFields on User { name }
+/// "#);
+/// assert_eq!(document.definitions.len(), 2);
+/// ```
+///
+/// The document is parsed again the first time the expression is evaluated,
+/// then cached: later evaluations clone the cached document,
+/// which shares its nodes.
+///
+/// With a `schema` path relative to the directory of the crate’s `Cargo.toml`,
+/// the document is also validated as an executable document against that schema.
+/// Validation errors, such as typos in field names, are reported as compile errors:
+///
+/// ```compile_fail
+/// use apollo_compiler_macros::graphql;
+///
+/// let document = graphql!(schema = "tests/schema.graphql", "{ user(id: 1) { nmae } }");
+/// ```
+///
+/// The crate is rebuilt when the schema file changes.
+///
+/// Instead of an expression, the macro can declare a `static` item
+/// of type `LazyLock<apollo_compiler::ast::Document>`:
+///
+/// ```
+/// use apollo_compiler_macros::graphql;
+///
+/// graphql! {
+///     /// Fields shared by user queries
+///     pub static USER_FIELDS = "fragment UserFields on User { name }";
+/// }
+///
+/// assert_eq!(USER_FIELDS.definitions.len(), 1);
+/// ```
+#[proc_macro]
+pub fn graphql(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+    expand(input, Output::Document).unwrap_or_else(|error| error.to_compile_error().into())
+}
+
+/// Parse and validate a GraphQL executable document against a schema at compile time,
+/// and expand to an expression of type
+/// [`Valid<apollo_compiler::ExecutableDocument>`][apollo_compiler::validation::Valid].
+///
+/// Takes the same input as [`graphql!`], except that the `schema` path is required:
+///
+/// ```
+/// use apollo_compiler_macros::graphql_executable;
+///
+/// let document = graphql_executable!(schema = "tests/schema.graphql", r#"
+///     query GetUser($id: ID!) {
+///         user(id: $id) { name }
+///     }
+/// "#);
+/// assert!(document.operations.get(Some("GetUser")).is_ok());
+///
+/// graphql_executable! {
+///     schema = "tests/schema.graphql",
+///     static LIST_USERS = "query ListUsers { users { id } }";
+/// }
+/// assert!(LIST_USERS.operations.get(Some("ListUsers")).is_ok());
+/// ```
+///
+/// The schema is read when compiling, and parsed again at runtime together with the document
+/// the first time it is needed. Neither is validated again at runtime.
+#[proc_macro]
+pub fn graphql_executable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+    expand(input, Output::Executable).unwrap_or_else(|error| error.to_compile_error().into())
+}
+
+/// Implement [`apollo_compiler::schema::FromDirective`] for a struct
+/// whose fields mirror the arguments of a directive.
+///
+/// Each field is converted with [`DirectiveArguments::get`], so its type must implement
+/// [`FromArgumentValue`]. Use `Option<T>` for nullable arguments.
+/// By default, the directive name is the struct name and argument names are field names,
+/// both converted to `lowerCamelCase`. Override them with `#[directive(name = "…")]`:
+///
+/// ```
+/// use apollo_compiler::coord;
+/// use apollo_compiler::Schema;
+/// use apollo_compiler_macros::FromDirective;
+///
+/// #[derive(FromDirective)]
+/// struct CacheControl {
+///     max_age: i32,
+///     #[directive(name = "scope")]
+///     cache_scope: Option<String>,
+/// }
+///
+/// #[derive(FromDirective)]
+/// #[directive(name = "internal")]
+/// struct Hidden;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     directive @cacheControl(maxAge: Int = 0, scope: String) on FIELD_DEFINITION
+///     directive @internal on FIELD_DEFINITION
+///     type Query {
+///         user: String @cacheControl(maxAge: 60) @internal
+///     }
+/// "#, "schema.graphql").unwrap();
+///
+/// let user = coord!(Query.user).into();
+/// let cache = schema.extract_directive::<CacheControl>(&user).unwrap().unwrap();
+/// assert_eq!(cache.max_age, 60);
+/// assert_eq!(cache.cache_scope, None);
+/// assert!(schema.extract_directive::<Hidden>(&user).unwrap().is_some());
+/// ```
+///
+/// [`DirectiveArguments::get`]: apollo_compiler::schema::DirectiveArguments::get
+/// [`FromArgumentValue`]: apollo_compiler::schema::FromArgumentValue
+#[proc_macro_derive(FromDirective, attributes(directive))]
+pub fn derive_from_directive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_directive::expand(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Output {
+    /// `ast::Document`
+    Document,
+    /// `Valid<ExecutableDocument>`
+    Executable,
+}
+
+/// `[schema = "path",] "document"` or `[schema = "path",] #[attrs] vis static NAME = "document";`
+struct Input {
+    schema: Option<LitStr>,
+    item: Option<Item>,
+    source: LitStr,
+}
+
+/// The `static` item declared by the constant form
+struct Item {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut schema = None;
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let key: Ident = input.parse()?;
+            if key != "schema" {
+                return Err(syn::Error::new(key.span(), "expected `schema`"));
+            }
+            input.parse::<Token![=]>()?;
+            schema = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        }
+        let mut item = None;
+        if !input.peek(LitStr) {
+            if !(input.peek(Token![#]) || input.peek(Token![pub]) || input.peek(Token![static])) {
+                return Err(input.error("expected a GraphQL document as a string literal"));
+            }
+            let attrs = input.call(Attribute::parse_outer)?;
+            let vis = input.parse()?;
+            input.parse::<Token![static]>()?;
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            item = Some(Item { attrs, vis, name });
+        }
+        let source = input.parse()?;
+        if item.is_some() {
+            input.parse::<Option<Token![;]>>()?;
+        }
+        Ok(Self {
+            schema,
+            item,
+            source,
+        })
+    }
+}
+
+fn expand(input: Input, output: Output) -> syn::Result<TokenStream> {
+    let Input {
+        schema,
+        item,
+        source,
+    } = input;
+    let source_text = source.value();
+    let document = ast::Document::parse(source_text.as_str(), PATH)
+        .map_err(|with_errors| syn::Error::new(source.span(), with_errors.errors))?;
+
+    let mut track_schema = quote!();
+    let mut init = quote! {
+        // Checked at compile time: there are no errors and `partial` is complete
+        ::apollo_compiler::ast::Document::parse(#source_text, #PATH)
+            .unwrap_or_else(|invalid| invalid.partial)
+    };
+    let mut ty = quote!(::apollo_compiler::ast::Document);
+    match &schema {
+        Some(schema_path) => {
+            let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+            let full_path = Path::new(&manifest_dir).join(schema_path.value());
+            let schema_source = std::fs::read_to_string(&full_path).map_err(|e| {
+                syn::Error::new(
+                    schema_path.span(),
+                    format!("could not read schema `{}`: {e}", full_path.display()),
+                )
+            })?;
+            let valid_schema = Schema::parse_and_validate(schema_source, schema_path.value())
+                .map_err(|with_errors| syn::Error::new(schema_path.span(), with_errors.errors))?;
+            document
+                .to_executable_validate(&valid_schema)
+                .map_err(|with_errors| syn::Error::new(source.span(), with_errors.errors))?;
+            let full_path = full_path.to_string_lossy();
+            // Make Cargo track the schema file
+            track_schema = quote! {
+                const _: &[u8] = ::core::include_bytes!(#full_path);
+            };
+            if output == Output::Executable {
+                init = quote! {{
+                    // Both checked at compile time
+                    let schema = ::apollo_compiler::validation::Valid::assume_valid(
+                        ::apollo_compiler::Schema::parse(::core::include_str!(#full_path), #schema_path)
+                            .unwrap_or_else(|invalid| invalid.partial),
+                    );
+                    ::apollo_compiler::validation::Valid::assume_valid(
+                        ::apollo_compiler::ExecutableDocument::parse(&schema, #source_text, #PATH)
+                            .unwrap_or_else(|invalid| invalid.partial),
+                    )
+                }};
+                ty = quote!(
+                    ::apollo_compiler::validation::Valid<::apollo_compiler::ExecutableDocument>
+                );
+            }
+        }
+        None if output == Output::Executable => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected `schema = \"path/to/schema.graphql\"`",
+            ))
+        }
+        None => {}
+    }
+
+    Ok(match item {
+        Some(Item { attrs, vis, name }) => quote! {
+            #track_schema
+            #(#attrs)*
+            #vis static #name: ::std::sync::LazyLock<#ty> = ::std::sync::LazyLock::new(|| #init);
+        },
+        None => quote! {{
+            #track_schema
+            static DOCUMENT: ::std::sync::LazyLock<#ty> = ::std::sync::LazyLock::new(|| #init);
+            ::core::clone::Clone::clone(&*DOCUMENT)
+        }},
+    }
+    .into())
+}
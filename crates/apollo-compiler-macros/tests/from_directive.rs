@@ -0,0 +1,92 @@
+use apollo_compiler::coord;
+use apollo_compiler::schema::DirectiveExtractionError;
+use apollo_compiler::Name;
+use apollo_compiler::Schema;
+use apollo_compiler_macros::FromDirective;
+
+#[derive(Debug, PartialEq, FromDirective)]
+struct Auth {
+    requires: Name,
+    scopes: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq, FromDirective)]
+#[directive(name = "key")]
+struct EntityKey {
+    fields: String,
+    #[directive(name = "resolvable")]
+    is_resolvable: bool,
+    r#type: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromDirective)]
+struct Shareable;
+
+const SCHEMA: &str = r#"
+    directive @auth(requires: Role = USER, scopes: [String!]) repeatable on OBJECT | FIELD_DEFINITION
+    directive @key(fields: String!, resolvable: Boolean = true, type: String) on OBJECT
+    directive @shareable on FIELD_DEFINITION
+    enum Role { USER ADMIN }
+    type Query @auth(scopes: "read") @key(fields: "id", type: "entity") {
+        id: ID
+        users: [String] @auth(requires: ADMIN) @shareable
+    }
+"#;
+
+#[test]
+fn derived_names_and_arguments() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = coord!(Query).into();
+    let users = coord!(Query.users).into();
+
+    assert_eq!(
+        schema.extract_directive::<Auth>(&query).unwrap(),
+        Some(Auth {
+            requires: Name::new("USER").unwrap(),
+            scopes: Some(vec!["read".to_owned()]),
+        })
+    );
+    assert_eq!(
+        schema.extract_directive::<Auth>(&users).unwrap(),
+        Some(Auth {
+            requires: Name::new("ADMIN").unwrap(),
+            scopes: None,
+        })
+    );
+    assert_eq!(
+        schema.extract_directive::<EntityKey>(&query).unwrap(),
+        Some(EntityKey {
+            fields: "id".to_owned(),
+            is_resolvable: true,
+            r#type: Some("entity".to_owned()),
+        })
+    );
+    assert_eq!(
+        schema.extract_directive::<Shareable>(&users).unwrap(),
+        Some(Shareable)
+    );
+    assert_eq!(schema.extract_directive::<Shareable>(&query).unwrap(), None);
+}
+
+#[test]
+fn derived_errors() {
+    #[derive(Debug, FromDirective)]
+    #[directive(name = "key")]
+    struct WrongType {
+        #[allow(dead_code)]
+        fields: i32,
+    }
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let err = schema
+        .extract_directive::<WrongType>(&coord!(Query).into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        DirectiveExtractionError::InvalidArgument {
+            directive: Name::new("key").unwrap(),
+            argument: "fields".to_owned(),
+            expected: "an integer",
+        }
+    );
+}
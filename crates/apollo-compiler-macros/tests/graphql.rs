@@ -0,0 +1,107 @@
+use apollo_compiler::ast;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+use apollo_compiler_macros::graphql;
+use apollo_compiler_macros::graphql_executable;
+
+graphql! {
+    /// A shared fragment
+    static USER_FIELDS = "fragment UserFields on User { id name }";
+}
+
+graphql_executable! {
+    schema = "tests/schema.graphql",
+    pub(crate) static GET_USER = r#"
+        query GetUser($id: ID!) {
+            user(id: $id) { name role }
+        }
+    "#;
+}
+
+#[test]
+fn string_literal() {
+    let document = graphql!("type Query { a: String } # \"comment\"\n# caf\u{e9}\nscalar S");
+    let expected = "type Query { a: String } # \"comment\"\n# caf\u{e9}\nscalar S";
+    let expected = ast::Document::parse(expected, "expected.graphql").unwrap();
+    assert_eq!(document.to_string(), expected.to_string());
+
+    let document = graphql!(
+        r#"
+        """
+        Block
+        """
+        type Query { a: String }
+        "#
+    );
+    assert_eq!(
+        document.definitions[0]
+            .as_object_type_definition()
+            .unwrap()
+            .description
+            .as_deref(),
+        Some("Block")
+    );
+}
+
+#[test]
+fn rust_escapes_are_not_graphql_text() {
+    let document = graphql!("type Query { a(arg: String = \"line\\nbreak\"): String }");
+    let field = &document.definitions[0]
+        .as_object_type_definition()
+        .unwrap()
+        .fields[0];
+    assert_eq!(
+        field.arguments[0].default_value.as_ref().unwrap().as_str(),
+        Some("line\nbreak")
+    );
+    assert_eq!(
+        document.to_string(),
+        "type Query {\n  a(arg: String = \"line\\nbreak\"): String\n}\n"
+    );
+}
+
+#[test]
+fn cached_between_evaluations() {
+    let documents: Vec<ast::Document> = (0..2).map(|_| graphql!("{ a }")).collect();
+    assert!(Node::ptr_eq(
+        documents[0].definitions[0]
+            .as_operation_definition()
+            .unwrap(),
+        documents[1].definitions[0]
+            .as_operation_definition()
+            .unwrap(),
+    ));
+}
+
+#[test]
+fn static_item() {
+    assert_eq!(USER_FIELDS.definitions.len(), 1);
+    let operation = GET_USER.operations.get(Some("GetUser")).unwrap();
+    assert_eq!(operation.variables[0].name, "id");
+}
+
+#[test]
+fn validated_against_schema() {
+    let document = graphql!(
+        schema = "tests/schema.graphql",
+        r#"
+        query GetUser($id: ID!) {
+            user(id: $id) { name role }
+        }
+    "#
+    );
+    let schema_source = include_str!("schema.graphql");
+    let schema = Schema::parse_and_validate(schema_source, "schema.graphql").unwrap();
+    let executable = document.to_executable_validate(&schema).unwrap();
+    assert!(executable.operations.get(Some("GetUser")).is_ok());
+
+    let document = graphql!(schema = "tests/schema.graphql", "{ users { id } }");
+    assert!(document.to_executable_validate(&schema).is_ok());
+}
+
+#[test]
+fn executable_document() {
+    let document = graphql_executable!(schema = "tests/schema.graphql", "{ users { id } }");
+    let operation = document.operations.anonymous.as_ref().unwrap();
+    assert_eq!(operation.selection_set.selections.len(), 1);
+}
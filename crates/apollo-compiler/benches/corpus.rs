@@ -0,0 +1,153 @@
+use apollo_compiler::ast;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use criterion::*;
+
+#[path = "../../apollo-parser/benches/corpus/mod.rs"]
+mod corpus;
+
+/// A representative pair of schema and executable document.
+///
+/// In addition to built-in pairs, setting the `APOLLO_BENCH_CORPUS` environment variable
+/// to a directory path also benchmarks documents from that directory,
+/// so that users can measure performance on their own schema and operations.
+/// The directory must contain a `schema.graphql` file.
+/// Every other `.graphql` file is an executable document for that schema.
+///
+/// ```bash
+/// env APOLLO_BENCH_CORPUS=path/to/graphql/files cargo bench --package apollo-compiler --bench corpus
+/// ```
+struct Document {
+    name: String,
+    schema: String,
+    executable: String,
+}
+
+/// All built-in pairs, followed by those found in `APOLLO_BENCH_CORPUS` if set
+fn all() -> Vec<Document> {
+    let mut documents = vec![supergraph(), deep_query(100), many_fragments(500)];
+    documents.extend(from_env());
+    documents
+}
+
+/// A large supergraph schema with a small query
+fn supergraph() -> Document {
+    Document {
+        name: "supergraph".into(),
+        schema: include_str!("testdata/supergraph.graphql").into(),
+        executable: include_str!("testdata/supergraph_query.graphql").into(),
+    }
+}
+
+const RECURSIVE_SCHEMA: &str = r#"
+    type Query { node(id: ID!): Node }
+    interface Node { id: ID! }
+    type Item implements Node {
+        id: ID!
+        name: String
+        children(first: Int = 10, filter: Filter): [Item!]!
+    }
+    input Filter { name: String, tags: [String!] }
+"#;
+
+/// A query with `depth` levels of nested selection sets with arguments
+fn deep_query(depth: usize) -> Document {
+    let mut executable = String::from("query Deep { node(id: 1) { ... on Item");
+    for _ in 0..depth {
+        executable.push_str(r#" { name children(first: 2, filter: { tags: ["a", "b"] })"#);
+    }
+    executable.push_str(" { id }");
+    for _ in 0..depth {
+        executable.push_str(" }");
+    }
+    executable.push_str(" } }");
+    Document {
+        name: format!("deep_query_{depth}"),
+        schema: RECURSIVE_SCHEMA.into(),
+        executable,
+    }
+}
+
+/// A query spreading `count` fragments, each with a few fields and nested selections
+fn many_fragments(count: usize) -> Document {
+    let mut executable =
+        String::from("query ManyFragments($condition: Boolean!) {\n  node(id: 1) {\n");
+    for i in 0..count {
+        executable.push_str(&format!("    ...Fragment{i}\n"));
+    }
+    executable.push_str("  }\n}\n");
+    for i in 0..count {
+        executable.push_str(&format!(
+            "\nfragment Fragment{i} on Node {{\n  \
+             id\n  \
+             ... on Item @include(if: $condition) {{ children{i}: children(first: {i}) {{ name }} }}\n\
+             }}\n"
+        ));
+    }
+    Document {
+        name: format!("many_fragments_{count}"),
+        schema: RECURSIVE_SCHEMA.into(),
+        executable,
+    }
+}
+
+/// Pairs each executable document found by [`corpus::from_env`] with its `schema.graphql`
+fn from_env() -> Vec<Document> {
+    let mut documents = corpus::from_env();
+    if documents.is_empty() {
+        return Vec::new();
+    }
+    let schema = documents
+        .iter()
+        .position(|document| document.name == "schema")
+        .expect("APOLLO_BENCH_CORPUS must contain a schema.graphql file");
+    let schema = documents.remove(schema).source;
+    documents
+        .into_iter()
+        .map(|document| Document {
+            name: document.name,
+            schema: schema.clone(),
+            executable: document.source,
+        })
+        .collect()
+}
+
+fn bench_corpus(c: &mut Criterion) {
+    for document in all() {
+        let mut group = c.benchmark_group(&document.name);
+        group.throughput(Throughput::Bytes(
+            (document.schema.len() + document.executable.len()) as u64,
+        ));
+        group.bench_function("parse_ast", |b| {
+            b.iter(|| {
+                let schema = ast::Document::parse(&document.schema, "schema.graphql").unwrap();
+                let executable =
+                    ast::Document::parse(&document.executable, "query.graphql").unwrap();
+                black_box((schema, executable))
+            })
+        });
+        group.bench_function("parse_and_validate", |b| {
+            b.iter(|| {
+                let schema =
+                    Schema::parse_and_validate(&document.schema, "schema.graphql").unwrap();
+                let executable = ExecutableDocument::parse_and_validate(
+                    &schema,
+                    &document.executable,
+                    "query.graphql",
+                )
+                .unwrap();
+                black_box((schema, executable))
+            })
+        });
+        let schema = Schema::parse_and_validate(&document.schema, "schema.graphql").unwrap();
+        let executable =
+            ExecutableDocument::parse(&schema, &document.executable, "query.graphql").unwrap();
+        group.bench_function("validate_executable", |b| {
+            b.iter(|| black_box(executable.clone().validate(&schema).unwrap()))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_corpus);
+criterion_main!(benches);
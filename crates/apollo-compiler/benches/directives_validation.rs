@@ -74,10 +74,90 @@ fn bench_many_invalid_directives_query(c: &mut Criterion) {
     });
 }
 
+/// A supergraph-like schema where every type and field has one `@join__*` application
+/// per subgraph, so directive lists are long and looked up by name often.
+/// Non-repeatable `@shareable` is applied `shareable` times to each type and field.
+fn directive_heavy_supergraph(
+    subgraphs: usize,
+    types: usize,
+    fields: usize,
+    shareable: usize,
+) -> String {
+    let mut sdl = String::from(
+        r#"
+        directive @join__type(graph: String!, key: String) repeatable on OBJECT
+        directive @join__field(graph: String!, external: Boolean) repeatable on FIELD_DEFINITION
+        directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+        directive @shareable on OBJECT | FIELD_DEFINITION
+        directive @inaccessible on OBJECT | FIELD_DEFINITION
+        type Query { t0: T0 }
+        "#,
+    );
+    let join_type = (0..subgraphs)
+        .map(|g| format!(r#" @join__type(graph: "G{g}", key: "id")"#))
+        .collect::<String>();
+    let join_field = (0..subgraphs)
+        .map(|g| format!(r#" @join__field(graph: "G{g}")"#))
+        .collect::<String>();
+    let shareable = " @shareable".repeat(shareable);
+    for t in 0..types {
+        sdl.push_str(&format!("type T{t}{join_type}{shareable} {{\n"));
+        for f in 0..fields {
+            sdl.push_str(&format!(
+                "  f{f}: Int{join_field} @tag(name: \"x\"){shareable}\n"
+            ));
+        }
+        sdl.push_str("}\n");
+    }
+    sdl
+}
+
+fn bench_supergraph_directive_lookups(c: &mut Criterion) {
+    let sdl = directive_heavy_supergraph(30, 50, 20, 1);
+    let schema = Schema::parse_and_validate(&sdl, "supergraph.graphql").unwrap();
+
+    c.bench_function("supergraph_directive_lookups", move |b| {
+        b.iter(|| {
+            let mut count = 0;
+            for ty in schema.types.values() {
+                let Some(object) = ty.as_object() else {
+                    continue;
+                };
+                count += usize::from(object.directives.has("inaccessible"));
+                for field in object.fields.values() {
+                    count += usize::from(field.directives.has("inaccessible"));
+                    count += usize::from(field.directives.get("shareable").is_some());
+                }
+            }
+            black_box(count);
+        });
+    });
+}
+
+fn bench_supergraph_dedup_directives(c: &mut Criterion) {
+    // Each non-repeatable `@shareable` is applied once per subgraph
+    let sdl = directive_heavy_supergraph(10, 50, 20, 10);
+
+    c.bench_function("supergraph_dedup_directives", move |b| {
+        b.iter(|| {
+            let result = Schema::builder()
+                .dedup_non_repeatable_directives()
+                .parse(&sdl, "supergraph.graphql")
+                .build()
+                .unwrap()
+                .validate()
+                .unwrap();
+            black_box(result);
+        });
+    });
+}
+
 criterion_group!(
     directives,
     bench_many_identical_directives,
     bench_many_identical_directives_query,
     bench_many_invalid_directives_query,
+    bench_supergraph_directive_lookups,
+    bench_supergraph_dedup_directives,
 );
 criterion_main!(directives);
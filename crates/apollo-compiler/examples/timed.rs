@@ -1,6 +1,8 @@
 //! Parse and validate a schema and executable document provided as files.
 //! Print the time taken by each step.
 
+#![allow(clippy::result_large_err)] // `WithErrors` is returned as-is by the timed closures
+
 use apollo_compiler::ExecutableDocument;
 use apollo_compiler::Schema;
 use std::process::ExitCode;
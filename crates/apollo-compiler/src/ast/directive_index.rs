@@ -0,0 +1,73 @@
+use crate::collections::HashMap;
+use crate::Name;
+use std::sync::OnceLock;
+
+/// Below this many directives, a linear scan is faster than hashing the name
+const MIN_INDEXED_LEN: usize = 8;
+
+/// A lazily-built index from directive name to the position of its first application,
+/// shared by [`ast::DirectiveList`][crate::ast::DirectiveList]
+/// and [`schema::DirectiveList`][crate::schema::DirectiveList].
+///
+/// The owning list must call [`invalidate`][Self::invalidate] whenever it is mutated.
+/// Since the list is only ever mutated through `&mut self`, which cannot coexist with
+/// a shared borrow of the index, the index is never stale when read.
+///
+/// This is a cache: it is ignored by `Clone`, `PartialEq`, and `Hash`.
+#[derive(Default)]
+pub(crate) struct DirectiveIndex {
+    first_positions: OnceLock<Box<HashMap<Name, usize>>>,
+}
+
+impl DirectiveIndex {
+    pub(crate) const fn new() -> Self {
+        Self {
+            first_positions: OnceLock::new(),
+        }
+    }
+
+    /// Returns the position in `items` of the first item named `name`
+    pub(crate) fn first_position<T>(
+        &self,
+        items: &[T],
+        name: &str,
+        name_of: impl Fn(&T) -> &Name,
+    ) -> Option<usize> {
+        if items.len() < MIN_INDEXED_LEN {
+            return items.iter().position(|item| name_of(item) == name);
+        }
+        let first_positions = self.first_positions.get_or_init(|| {
+            let mut first_positions = HashMap::default();
+            for (position, item) in items.iter().enumerate() {
+                first_positions
+                    .entry(name_of(item).clone())
+                    .or_insert(position);
+            }
+            Box::new(first_positions)
+        });
+        first_positions.get(name).copied()
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        self.first_positions.take();
+    }
+}
+
+impl Clone for DirectiveIndex {
+    fn clone(&self) -> Self {
+        // Rebuilt on demand
+        Self::new()
+    }
+}
+
+impl PartialEq for DirectiveIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for DirectiveIndex {}
+
+impl std::hash::Hash for DirectiveIndex {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
@@ -1,8 +1,10 @@
 use crate::ast;
 use crate::ast::Document;
+use crate::parser::ExtensionContext;
 use crate::parser::FileId;
-use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
+use crate::parser::SyntaxExtension;
+use crate::validation::DiagnosticList;
 use crate::Node;
 use apollo_parser::cst;
 use apollo_parser::cst::CstNode;
@@ -10,13 +12,27 @@ use apollo_parser::SyntaxNode;
 use apollo_parser::S;
 
 impl Document {
-    pub(crate) fn from_cst(document: cst::Document, file_id: FileId, sources: SourceMap) -> Self {
+    pub(crate) fn from_cst(
+        document: cst::Document,
+        file_id: FileId,
+        extension: Option<&dyn SyntaxExtension>,
+        errors: &mut DiagnosticList,
+    ) -> Self {
+        let mut definitions = Vec::new();
+        for def in document.definitions() {
+            if let cst::Definition::CustomDefinition(def) = &def {
+                // Only parsed when an extension is configured
+                if let Some(extension) = extension {
+                    let mut cx = ExtensionContext { file_id, errors };
+                    definitions.extend(extension.convert_definition(def, &mut cx))
+                }
+            } else {
+                definitions.extend(def.convert(file_id))
+            }
+        }
         Self {
-            sources,
-            definitions: document
-                .definitions()
-                .filter_map(|def| def.convert(file_id))
-                .collect(),
+            sources: errors.sources.clone(),
+            definitions,
         }
     }
 }
@@ -27,14 +43,28 @@ pub(crate) trait Convert {
     fn convert(&self, file_id: FileId) -> Option<Self::Target>;
 }
 
-fn with_location<T>(file_id: FileId, syntax_node: &SyntaxNode, node: T) -> Node<T> {
+pub(crate) fn with_location<T>(file_id: FileId, syntax_node: &SyntaxNode, node: T) -> Node<T> {
     Node::new_parsed(node, SourceSpan::new(file_id, syntax_node))
 }
 
+pub(crate) fn leading_comments(file_id: FileId, node: &impl CstNode) -> Vec<Node<str>> {
+    node.leading_comments()
+        .iter()
+        .map(|token| {
+            let text = token.text();
+            let location = SourceSpan {
+                file_id,
+                text_range: token.text_range(),
+            };
+            Node::new_str_parsed(text.strip_prefix('#').unwrap_or(text), location)
+        })
+        .collect()
+}
+
 /// Convert and collect, silently skipping entries with conversion errors
 /// as they have corresponding parse errors in `SyntaxTree::errors`
 #[inline]
-fn collect<CstType, AstType>(
+pub(crate) fn collect<CstType, AstType>(
     file_id: FileId,
     iter: impl IntoIterator<Item = CstType>,
 ) -> Vec<Node<AstType>>
@@ -111,6 +141,8 @@ impl Convert for cst::Definition {
             C::UnionTypeExtension(def) => A::UnionTypeExtension(r!(def)),
             C::EnumTypeExtension(def) => A::EnumTypeExtension(r!(def)),
             C::InputObjectTypeExtension(def) => A::InputObjectTypeExtension(r!(def)),
+            // Converted by `parser::SyntaxExtension` in `Document::from_cst`
+            C::CustomDefinition(_) => return None,
         })
     }
 }
@@ -125,12 +157,13 @@ impl Convert for cst::OperationDefinition {
             ast::OperationType::Query
         };
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             operation_type,
             name: self.name().convert(file_id)?,
             variables: collect_opt(file_id, self.variable_definitions(), |x| {
                 x.variable_definitions()
             }),
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             selection_set: self
@@ -147,9 +180,10 @@ impl Convert for cst::FragmentDefinition {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.fragment_name()?.name()?.convert(file_id)?,
             type_condition: self.type_condition()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             selection_set: self.selection_set().convert(file_id)??,
@@ -171,6 +205,7 @@ impl Convert for cst::DirectiveDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             arguments: collect_opt(file_id, self.arguments_definition(), |x| {
                 x.input_value_definitions()
@@ -194,7 +229,8 @@ impl Convert for cst::SchemaDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            leading_comments: leading_comments(file_id, self),
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             // This may represent a syntactically invalid thing: a schema without any root
@@ -215,8 +251,9 @@ impl Convert for cst::ScalarTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -229,9 +266,10 @@ impl Convert for cst::ObjectTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             fields: collect_opt(file_id, self.fields_definition(), |x| x.field_definitions()),
@@ -245,9 +283,10 @@ impl Convert for cst::InterfaceTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             fields: collect_opt(file_id, self.fields_definition(), |x| x.field_definitions()),
@@ -261,8 +300,9 @@ impl Convert for cst::UnionTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             members: self
@@ -283,8 +323,9 @@ impl Convert for cst::EnumTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             values: collect_opt(file_id, self.enum_values_definition(), |x| {
@@ -300,8 +341,9 @@ impl Convert for cst::InputObjectTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             fields: collect_opt(file_id, self.input_fields_definition(), |x| {
@@ -316,7 +358,8 @@ impl Convert for cst::SchemaExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            leading_comments: leading_comments(file_id, self),
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             root_operations: self
@@ -332,8 +375,9 @@ impl Convert for cst::ScalarTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -345,9 +389,10 @@ impl Convert for cst::ObjectTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             fields: collect_opt(file_id, self.fields_definition(), |x| x.field_definitions()),
@@ -360,9 +405,10 @@ impl Convert for cst::InterfaceTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             fields: collect_opt(file_id, self.fields_definition(), |x| x.field_definitions()),
@@ -375,8 +421,9 @@ impl Convert for cst::UnionTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             members: self
@@ -396,8 +443,9 @@ impl Convert for cst::EnumTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             values: collect_opt(file_id, self.enum_values_definition(), |x| {
@@ -412,8 +460,9 @@ impl Convert for cst::InputObjectTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             fields: collect_opt(file_id, self.input_fields_definition(), |x| {
@@ -533,7 +582,7 @@ impl Convert for cst::VariableDefinition {
             name: self.variable()?.name()?.convert(file_id)?,
             ty: with_location(file_id, ty.syntax(), ty.convert(file_id)?),
             default_value,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -568,12 +617,13 @@ impl Convert for cst::FieldDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             arguments: collect_opt(file_id, self.arguments_definition(), |x| {
                 x.input_value_definitions()
             }),
             ty: self.ty()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -608,10 +658,11 @@ impl Convert for cst::InputValueDefinition {
         let ty = &self.ty()?;
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             name: self.name()?.convert(file_id)?,
             ty: with_location(file_id, ty.syntax(), ty.convert(file_id)?),
             default_value,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -624,8 +675,9 @@ impl Convert for cst::EnumValueDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
+            leading_comments: leading_comments(file_id, self),
             value: self.enum_value()?.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -677,7 +729,9 @@ impl Convert for cst::Field {
             alias: self.alias().convert(file_id)?,
             name: self.name()?.convert(file_id)?,
             arguments: collect_opt(file_id, self.arguments(), |x| x.arguments()),
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            #[cfg(feature = "experimental-client-controlled-nullability")]
+            nullability: self.nullability().convert(file_id)?,
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             // Use an empty Vec for a field without sub-selections
@@ -686,13 +740,44 @@ impl Convert for cst::Field {
     }
 }
 
+impl Convert for cst::Nullability {
+    type Target = ast::Nullability;
+
+    fn convert(&self, file_id: FileId) -> Option<Self::Target> {
+        let list = if let Some(list) = self.list_nullability() {
+            // Use an empty Nullability for `[]`
+            let items = list.nullability().convert(file_id)?.unwrap_or_default();
+            Some(Box::new(items))
+        } else {
+            None
+        };
+        Some(Self::Target {
+            list,
+            designator: self.nullability_designator().convert(file_id)?,
+        })
+    }
+}
+
+impl Convert for cst::NullabilityDesignator {
+    type Target = ast::NullabilityDesignator;
+
+    fn convert(&self, _file_id: FileId) -> Option<Self::Target> {
+        let token = self.syntax().first_token()?;
+        match token.kind() {
+            S![!] => Some(ast::NullabilityDesignator::Required),
+            S![?] => Some(ast::NullabilityDesignator::Optional),
+            _ => None,
+        }
+    }
+}
+
 impl Convert for cst::FragmentSpread {
     type Target = ast::FragmentSpread;
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             fragment_name: self.fragment_name()?.name()?.convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
         })
@@ -705,7 +790,7 @@ impl Convert for cst::InlineFragment {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             type_condition: self.type_condition().convert(file_id)?,
-            directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
+            directives: ast::DirectiveList::from(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
             selection_set: self.selection_set().convert(file_id)??,
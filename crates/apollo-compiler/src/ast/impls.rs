@@ -1,3 +1,4 @@
+use super::directive_index::DirectiveIndex;
 use super::*;
 use crate::name;
 use crate::parser::Parser;
@@ -39,6 +40,10 @@ impl Document {
     }
 
     /// Validate as an executable document, as much as possible without a schema
+    ///
+    /// This checks that the document contains only executable definitions
+    /// and that operation and fragment names are unique,
+    /// then the rules listed in [`ExecutableDocument::validate_standalone`].
     pub fn validate_standalone_executable(&self) -> Result<(), DiagnosticList> {
         let mut errors = DiagnosticList::new(self.sources.clone());
         let type_system_definitions_are_errors = true;
@@ -47,6 +52,7 @@ impl Document {
             self,
             &mut errors,
             type_system_definitions_are_errors,
+            false,
         );
         crate::executable::validation::validate_standalone_executable(&mut errors, &executable);
         errors.into_result()
@@ -79,7 +85,7 @@ impl Document {
         schema: &Valid<Schema>,
     ) -> Result<ExecutableDocument, WithErrors<ExecutableDocument>> {
         let mut errors = DiagnosticList::new(self.sources.clone());
-        let document = self.to_executable_inner(schema, &mut errors);
+        let document = self.to_executable_inner(schema, &mut errors, false);
         errors.into_result_with(document)
     }
 
@@ -90,7 +96,7 @@ impl Document {
         schema: &Valid<Schema>,
     ) -> Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>> {
         let mut errors = DiagnosticList::new(self.sources.clone());
-        let document = self.to_executable_inner(schema, &mut errors);
+        let document = self.to_executable_inner(schema, &mut errors, false);
         crate::executable::validation::validate_executable_document(&mut errors, schema, &document);
         errors.into_valid_result(document)
     }
@@ -99,6 +105,7 @@ impl Document {
         &self,
         schema: &Valid<Schema>,
         errors: &mut DiagnosticList,
+        keep_invalid_selections: bool,
     ) -> ExecutableDocument {
         let type_system_definitions_are_errors = true;
         crate::executable::from_ast::document_from_ast(
@@ -106,6 +113,7 @@ impl Document {
             self,
             errors,
             type_system_definitions_are_errors,
+            keep_invalid_selections,
         )
     }
 
@@ -125,6 +133,7 @@ impl Document {
             self,
             &mut errors,
             type_system_definitions_are_errors,
+            false,
         );
         crate::schema::validation::validate_schema(&mut errors, &mut schema);
         crate::executable::validation::validate_executable_document(
@@ -161,6 +170,55 @@ impl Default for Document {
     }
 }
 
+/// `leading_comments` is ignored for comparison and hashing, like other ignored tokens.
+/// Other fields are listed exhaustively, so that adding one without listing it here fails to compile.
+macro_rules! eq_and_hash_ignoring_comments {
+    ($($Struct: ident { $($field: ident),* })*) => {$(
+        impl PartialEq for $Struct {
+            fn eq(&self, other: &Self) -> bool {
+                let Self {
+                    leading_comments: _,
+                    $($field,)*
+                } = self;
+                $(*$field == other.$field)&&*
+            }
+        }
+
+        impl hash::Hash for $Struct {
+            fn hash<H: hash::Hasher>(&self, state: &mut H) {
+                let Self {
+                    leading_comments: _,
+                    $($field,)*
+                } = self;
+                $($field.hash(state);)*
+            }
+        }
+    )*};
+}
+
+eq_and_hash_ignoring_comments! {
+    OperationDefinition { operation_type, name, variables, directives, selection_set }
+    FragmentDefinition { name, type_condition, directives, selection_set }
+    DirectiveDefinition { description, name, arguments, repeatable, locations }
+    SchemaDefinition { description, directives, root_operations }
+    ScalarTypeDefinition { description, name, directives }
+    ObjectTypeDefinition { description, name, implements_interfaces, directives, fields }
+    InterfaceTypeDefinition { description, name, implements_interfaces, directives, fields }
+    UnionTypeDefinition { description, name, directives, members }
+    EnumTypeDefinition { description, name, directives, values }
+    InputObjectTypeDefinition { description, name, directives, fields }
+    SchemaExtension { directives, root_operations }
+    ScalarTypeExtension { name, directives }
+    ObjectTypeExtension { name, implements_interfaces, directives, fields }
+    InterfaceTypeExtension { name, implements_interfaces, directives, fields }
+    UnionTypeExtension { name, directives, members }
+    EnumTypeExtension { name, directives, values }
+    InputObjectTypeExtension { name, directives, fields }
+    FieldDefinition { description, name, arguments, ty, directives }
+    InputValueDefinition { description, name, ty, default_value, directives }
+    EnumValueDefinition { description, value, directives }
+}
+
 impl fmt::Debug for Document {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Skip two not-useful indentation levels
@@ -268,7 +326,7 @@ impl Definition {
     }
 
     pub fn directives(&self) -> &DirectiveList {
-        static EMPTY: DirectiveList = DirectiveList(Vec::new());
+        static EMPTY: DirectiveList = DirectiveList::new();
         match self {
             Self::DirectiveDefinition(_) => &EMPTY,
             Self::OperationDefinition(def) => &def.directives,
@@ -528,8 +586,11 @@ impl InputObjectTypeExtension {
 }
 
 impl DirectiveList {
-    pub fn new() -> Self {
-        Self(Vec::new())
+    pub const fn new() -> Self {
+        Self {
+            directives: Vec::new(),
+            index: DirectiveIndex::new(),
+        }
     }
 
     /// Returns an iterator of directives with the given name.
@@ -540,7 +601,10 @@ impl DirectiveList {
         &'def self,
         name: &'name str,
     ) -> impl Iterator<Item = &'def Node<Directive>> + 'name {
-        self.0.iter().filter(move |dir| dir.name == name)
+        let first = self.first_position(name).unwrap_or(self.directives.len());
+        self.directives[first..]
+            .iter()
+            .filter(move |dir| dir.name == name)
     }
 
     /// Returns the first directive with the given name, if any.
@@ -548,25 +612,59 @@ impl DirectiveList {
     /// This method is best for non-repeatable directives.
     /// See also [`get_all`][Self::get_all] for repeatable directives.
     pub fn get(&self, name: &str) -> Option<&Node<Directive>> {
-        self.get_all(name).next()
+        Some(&self.directives[self.first_position(name)?])
     }
 
     /// Returns whether there is a directive with the given name
     pub fn has(&self, name: &str) -> bool {
-        self.get(name).is_some()
+        self.first_position(name).is_some()
+    }
+
+    fn first_position(&self, name: &str) -> Option<usize> {
+        self.index
+            .first_position(&self.directives, name, |dir| &dir.name)
     }
 
     /// Accepts either [`Node<Directive>`] or [`Directive`].
     pub fn push(&mut self, directive: impl Into<Node<Directive>>) {
-        self.0.push(directive.into());
+        self.index.invalidate();
+        self.directives.push(directive.into());
+    }
+
+    /// Removes applications of a non-repeatable directive that are identical
+    /// (same name and same arguments) to an earlier application in this list,
+    /// such as when the same directive is copied from multiple subgraphs.
+    ///
+    /// Directives that are repeatable or not defined in `schema` are kept.
+    /// Non-identical applications of a non-repeatable directive are also kept,
+    /// to be reported by validation.
+    pub fn dedup_non_repeatable(&mut self, schema: &Schema) {
+        dedup_non_repeatable(&mut self.directives, &schema.directive_definitions);
+        self.index.invalidate();
     }
 
     serialize_method!();
 }
 
+pub(crate) fn dedup_non_repeatable<T: PartialEq + AsRef<Directive>>(
+    directives: &mut Vec<T>,
+    definitions: &crate::collections::IndexMap<Name, Node<DirectiveDefinition>>,
+) {
+    let mut kept = Vec::with_capacity(directives.len());
+    for directive in std::mem::take(directives) {
+        let repeatable = definitions
+            .get(&directive.as_ref().name)
+            .is_none_or(|def| def.repeatable);
+        if repeatable || !kept.contains(&directive) {
+            kept.push(directive)
+        }
+    }
+    *directives = kept
+}
+
 impl std::fmt::Debug for DirectiveList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+        self.directives.fmt(f)
     }
 }
 
@@ -574,13 +672,29 @@ impl std::ops::Deref for DirectiveList {
     type Target = Vec<Node<Directive>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.directives
     }
 }
 
 impl std::ops::DerefMut for DirectiveList {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        self.index.invalidate();
+        &mut self.directives
+    }
+}
+
+impl From<Vec<Node<Directive>>> for DirectiveList {
+    fn from(directives: Vec<Node<Directive>>) -> Self {
+        Self {
+            directives,
+            index: DirectiveIndex::new(),
+        }
+    }
+}
+
+impl From<DirectiveList> for Vec<Node<Directive>> {
+    fn from(list: DirectiveList) -> Self {
+        list.directives
     }
 }
 
@@ -590,7 +704,7 @@ impl IntoIterator for DirectiveList {
     type IntoIter = std::vec::IntoIter<Node<Directive>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.directives.into_iter()
     }
 }
 
@@ -600,7 +714,7 @@ impl<'a> IntoIterator for &'a DirectiveList {
     type IntoIter = std::slice::Iter<'a, Node<Directive>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter()
+        self.directives.iter()
     }
 }
 
@@ -610,19 +724,20 @@ impl<'a> IntoIterator for &'a mut DirectiveList {
     type IntoIter = std::slice::IterMut<'a, Node<Directive>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter_mut()
+        self.index.invalidate();
+        self.directives.iter_mut()
     }
 }
 
 impl FromIterator<Node<Directive>> for DirectiveList {
     fn from_iter<T: IntoIterator<Item = Node<Directive>>>(iter: T) -> Self {
-        Self(iter.into_iter().collect())
+        Self::from(iter.into_iter().collect::<Vec<_>>())
     }
 }
 
 impl FromIterator<Directive> for DirectiveList {
     fn from_iter<T: IntoIterator<Item = Directive>>(iter: T) -> Self {
-        Self(iter.into_iter().map(Node::new).collect())
+        Self::from(iter.into_iter().map(Node::new).collect::<Vec<_>>())
     }
 }
 
@@ -661,6 +776,18 @@ impl Directive {
         Argument::specified_argument_by_name(&self.arguments, name)
     }
 
+    /// Returns the arguments of this directive application with default values filled in:
+    /// [`arguments`][Self::arguments] as specified, followed by arguments that are not specified
+    /// but have a default value in `schema`’s directive definition, in definition order.
+    ///
+    /// If the directive is not defined in `schema`, returns the specified arguments.
+    pub fn arguments_with_defaults(&self, schema: &Schema) -> Vec<Node<Argument>> {
+        match schema.directive_definitions.get(&self.name) {
+            Some(definition) => Argument::with_defaults(&self.arguments, &definition.arguments),
+            None => self.arguments.clone(),
+        }
+    }
+
     serialize_method!();
 }
 
@@ -692,6 +819,24 @@ impl Argument {
             .iter()
             .find_map(|arg| (arg.name == name).then_some(&arg.value))
     }
+
+    pub(crate) fn with_defaults(
+        arguments: &[Node<Self>],
+        definitions: &[Node<InputValueDefinition>],
+    ) -> Vec<Node<Self>> {
+        let mut effective = arguments.to_vec();
+        effective.extend(definitions.iter().filter_map(|definition| {
+            let default_value = definition.default_value.as_ref()?;
+            if Self::specified_argument_by_name(arguments, &definition.name).is_some() {
+                return None;
+            }
+            Some(Node::new(Self {
+                name: definition.name.clone(),
+                value: default_value.clone(),
+            }))
+        }));
+        effective
+    }
 }
 
 impl OperationType {
@@ -965,6 +1110,19 @@ impl Selection {
 }
 
 impl Field {
+    /// Create a field selection without alias, arguments, directives, or sub-selections
+    pub fn new(name: Name) -> Self {
+        Self {
+            alias: None,
+            name,
+            arguments: Vec::new(),
+            #[cfg(feature = "experimental-client-controlled-nullability")]
+            nullability: None,
+            directives: DirectiveList::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
     /// Get the name that will be used for this field selection in [response `data`].
     ///
     /// For example, in this operation, the response name is `sourceField`:
@@ -985,6 +1143,69 @@ impl Field {
     serialize_method!();
 }
 
+impl fmt::Debug for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Skip experimental nullability when unset,
+        // so that output is the same with or without the crate feature
+        let mut debug = f.debug_struct("Field");
+        debug
+            .field("alias", &self.alias)
+            .field("name", &self.name)
+            .field("arguments", &self.arguments);
+        #[cfg(feature = "experimental-client-controlled-nullability")]
+        if self.nullability.is_some() {
+            debug.field("nullability", &self.nullability);
+        }
+        debug
+            .field("directives", &self.directives)
+            .field("selection_set", &self.selection_set)
+            .finish()
+    }
+}
+
+impl Nullability {
+    /// Returns the type of a field with definition type `ty`
+    /// when this nullability is applied to the field selection.
+    ///
+    /// `!` makes the field or list item non-null, and `?` makes it nullable.
+    /// List nullability applies to the outermost list types of `ty`
+    /// if it is not nested as deep as them.
+    /// Returns `None` if list nullability is nested deeper than list types of `ty`.
+    ///
+    /// # Example
+    /// ```
+    /// use apollo_compiler::ast::Nullability;
+    /// use apollo_compiler::ast::NullabilityDesignator;
+    /// use apollo_compiler::ty;
+    ///
+    /// // `[!]?`
+    /// let nullability = Nullability {
+    ///     list: Some(Box::new(Nullability {
+    ///         list: None,
+    ///         designator: Some(NullabilityDesignator::Required),
+    ///     })),
+    ///     designator: Some(NullabilityDesignator::Optional),
+    /// };
+    /// assert_eq!(nullability.apply_to(&ty!([Int]!)), Some(ty!([Int!])));
+    /// assert_eq!(nullability.apply_to(&ty!(Int)), None);
+    /// ```
+    pub fn apply_to(&self, ty: &Type) -> Option<Type> {
+        let ty = match (&self.list, ty) {
+            (None, _) => ty.clone(),
+            (Some(items), Type::List(inner)) => Type::List(Box::new(items.apply_to(inner)?)),
+            (Some(items), Type::NonNullList(inner)) => {
+                Type::NonNullList(Box::new(items.apply_to(inner)?))
+            }
+            (Some(_), Type::Named(_) | Type::NonNullNamed(_)) => return None,
+        };
+        Some(match self.designator {
+            Some(NullabilityDesignator::Required) => ty.non_null(),
+            Some(NullabilityDesignator::Optional) => ty.nullable(),
+            None => ty,
+        })
+    }
+}
+
 impl FragmentSpread {
     serialize_method!();
 }
@@ -1591,6 +1812,18 @@ impl From<bool> for Value {
     }
 }
 
+impl<T: Into<Node<Value>>> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Node<Value>>, const N: usize> From<[T; N]> for Value {
+    fn from(value: [T; N]) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
 /// The Rust unit value a.k.a empty tuple converts to [`Value::Null`].
 impl From<()> for Node<Value> {
     fn from(value: ()) -> Self {
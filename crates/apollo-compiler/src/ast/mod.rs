@@ -19,6 +19,20 @@
 //! Start with [`Document::parse`], or [`Parser`][crate::parser::Parser]
 //! to change the parser configuration.
 //!
+//! ## Comments
+//!
+//! Like other ignored tokens, most `#` comments are not preserved.
+//! The exception is the block of comment lines just before a definition
+//! or a field, argument, input field, or enum value definition,
+//! which is kept in its `leading_comments` field without the `#` prefix.
+//! This follows the same rules as
+//! [`CstNode::leading_comments`][apollo_parser::cst::CstNode::leading_comments]:
+//! comments separated from the definition by a blank line,
+//! or at the end of a line after other tokens, are not included.
+//! Serialization outputs them back, unless indentation is disabled.
+//! Like other ignored tokens, comments do not affect equality or hashing of nodes.
+//! A [`Schema`][crate::Schema] does not keep them.
+//!
 //! ## Structural sharing and mutation
 //!
 //! Nodes inside documents are wrapped in [`Node`], a reference-counted smart pointer.
@@ -55,11 +69,20 @@ use crate::parser::SourceMap;
 use crate::Name;
 use crate::Node;
 
+pub(crate) mod directive_index;
 pub(crate) mod from_cst;
 pub(crate) mod impls;
+mod provenance;
 pub(crate) mod serialize;
+mod value_builder;
 
+pub use self::provenance::ProvenanceEntry;
+pub use self::provenance::ProvenanceMap;
 pub use self::serialize::Serialize;
+pub use self::value_builder::ListValueBuilder;
+pub use self::value_builder::ObjectValueBuilder;
+pub use self::value_builder::ValueBuildError;
+pub use self::value_builder::ValueBuilder;
 
 /// AST for a GraphQL [_Document_](https://spec.graphql.org/draft/#Document)
 /// that can contain executable definitions, type system (schema) definitions, or both.
@@ -114,8 +137,9 @@ pub enum Definition {
 
 /// Executable AST for an
 /// [_OperationDefinition_](https://spec.graphql.org/draft/#OperationDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct OperationDefinition {
+    pub leading_comments: Vec<Node<str>>,
     pub operation_type: OperationType,
     pub name: Option<Name>,
     pub variables: Vec<Node<VariableDefinition>>,
@@ -125,8 +149,9 @@ pub struct OperationDefinition {
 
 /// Executable AST for a
 /// [_FragmentDefinition_](https://spec.graphql.org/draft/#FragmentDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct FragmentDefinition {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub type_condition: NamedType,
     pub directives: DirectiveList,
@@ -135,9 +160,10 @@ pub struct FragmentDefinition {
 
 /// Type system AST for a `directive @foo`
 /// [_DirectiveDefinition_](https://spec.graphql.org/draft/#DirectiveDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct DirectiveDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub arguments: Vec<Node<InputValueDefinition>>,
     pub repeatable: bool,
@@ -146,27 +172,30 @@ pub struct DirectiveDefinition {
 
 /// Type system AST for a `schema`
 /// [_SchemaDefinition_](https://spec.graphql.org/draft/#SchemaDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct SchemaDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub directives: DirectiveList,
     pub root_operations: Vec<Node<(OperationType, NamedType)>>,
 }
 
 /// Type system AST for a `scalar FooS`
 /// [_ScalarTypeDefinition_](https://spec.graphql.org/draft/#ScalarTypeDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct ScalarTypeDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
 }
 
 /// Type system AST for a `type FooO`
 /// [_ObjectTypeDefinition_](https://spec.graphql.org/draft/#ObjectTypeDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct ObjectTypeDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub implements_interfaces: Vec<Name>,
     pub directives: DirectiveList,
@@ -175,9 +204,10 @@ pub struct ObjectTypeDefinition {
 
 /// Type system AST for an `interface FooI`
 /// [_InterfaceTypeDefinition_](https://spec.graphql.org/draft/#InterfaceTypeDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct InterfaceTypeDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub implements_interfaces: Vec<Name>,
     pub directives: DirectiveList,
@@ -186,9 +216,10 @@ pub struct InterfaceTypeDefinition {
 
 /// Type system AST for a `union FooU`
 /// [_UnionTypeDefinition_](https://spec.graphql.org/draft/#UnionTypeDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct UnionTypeDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
     pub members: Vec<NamedType>,
@@ -196,9 +227,10 @@ pub struct UnionTypeDefinition {
 
 /// Type system AST for an `enum FooE`
 /// [_EnumTypeDefinition_](https://spec.graphql.org/draft/#EnumTypeDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct EnumTypeDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
     pub values: Vec<Node<EnumValueDefinition>>,
@@ -206,9 +238,10 @@ pub struct EnumTypeDefinition {
 
 /// Type system AST for an `input FooIn`
 /// [_InputObjectTypeDefinition_](https://spec.graphql.org/draft/#InputObjectTypeDefinition).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct InputObjectTypeDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
     pub fields: Vec<Node<InputValueDefinition>>,
@@ -216,24 +249,27 @@ pub struct InputObjectTypeDefinition {
 
 /// Type system AST for an `extend schema`
 /// [_SchemaExtension_](https://spec.graphql.org/draft/#SchemaExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct SchemaExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub directives: DirectiveList,
     pub root_operations: Vec<Node<(OperationType, NamedType)>>,
 }
 
 /// Type system AST for an `extend scalar FooS`
 /// [_ScalarTypeExtension_](https://spec.graphql.org/draft/#ScalarTypeExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct ScalarTypeExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
 }
 
 /// Type system AST for an `extend type FooO`
 /// [_ObjectTypeExtension_](https://spec.graphql.org/draft/#ObjectTypeExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct ObjectTypeExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub implements_interfaces: Vec<Name>,
     pub directives: DirectiveList,
@@ -242,8 +278,9 @@ pub struct ObjectTypeExtension {
 
 /// Type system AST for an `extend interface FooI`
 /// [_InterfaceTypeExtension_](https://spec.graphql.org/draft/#InterfaceTypeExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct InterfaceTypeExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub implements_interfaces: Vec<Name>,
     pub directives: DirectiveList,
@@ -252,8 +289,9 @@ pub struct InterfaceTypeExtension {
 
 /// Type system AST for an `extend union FooU`
 /// [_UnionTypeExtension_](https://spec.graphql.org/draft/#UnionTypeExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct UnionTypeExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
     pub members: Vec<NamedType>,
@@ -261,8 +299,9 @@ pub struct UnionTypeExtension {
 
 /// Type system AST for an `extend enum FooE`
 /// [_EnumTypeExtension_](https://spec.graphql.org/draft/#EnumTypeExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct EnumTypeExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
     pub values: Vec<Node<EnumValueDefinition>>,
@@ -270,8 +309,9 @@ pub struct EnumTypeExtension {
 
 /// Type system AST for an `extend input FooIn`
 /// [_InputObjectTypeExtension_](https://spec.graphql.org/draft/#InputObjectTypeExtension).
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct InputObjectTypeExtension {
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub directives: DirectiveList,
     pub fields: Vec<Node<InputValueDefinition>>,
@@ -287,8 +327,15 @@ pub struct Argument {
 
 /// AST for the list of [_Directives_](https://spec.graphql.org/draft/#Directives)
 /// applied to some context.
+///
+/// Dereferences to a `Vec` for iteration and mutation.
+/// [`get`][Self::get] and [`has`][Self::has] use a name index, built on first use
+/// for long lists such as in directive-heavy supergraph schemas.
 #[derive(Clone, Eq, PartialEq, Hash, Default)]
-pub struct DirectiveList(pub Vec<Node<Directive>>);
+pub struct DirectiveList {
+    directives: Vec<Node<Directive>>,
+    index: directive_index::DirectiveIndex,
+}
 
 /// AST for a [_Directive_](https://spec.graphql.org/draft/#Directive) application.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -299,7 +346,8 @@ pub struct Directive {
 
 /// AST for the [_OperationType_](https://spec.graphql.org/draft/#OperationType)
 /// of an [`OperationDefinition`] or [`RootOperationDefinition`][SchemaDefinition::root_operations].
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OperationType {
     Query,
     Mutation,
@@ -361,9 +409,10 @@ pub enum Type {
 
 /// Type system AST for a [_FieldDefinition_](https://spec.graphql.org/draft/#FieldDefinition)
 /// in an object type or interface type defintion or extension.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct FieldDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub arguments: Vec<Node<InputValueDefinition>>,
     pub ty: Type,
@@ -373,9 +422,10 @@ pub struct FieldDefinition {
 /// Type system AST for an
 /// [_InputValueDefinition_](https://spec.graphql.org/draft/#InputValueDefinition),
 /// a input type field definition or an argument definition.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct InputValueDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub name: Name,
     pub ty: Node<Type>,
     pub default_value: Option<Node<Value>>,
@@ -385,9 +435,10 @@ pub struct InputValueDefinition {
 /// Type system AST for an
 /// [_EnumValueDefinition_](https://spec.graphql.org/draft/#EnumValueDefinition)
 /// in an enum type definition or extension.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq)]
 pub struct EnumValueDefinition {
     pub description: Option<Node<str>>,
+    pub leading_comments: Vec<Node<str>>,
     pub value: Name,
     pub directives: DirectiveList,
 }
@@ -403,15 +454,41 @@ pub enum Selection {
 
 /// Executable AST for a [_Field_](https://spec.graphql.org/draft/#Field) selection
 /// in a selection set.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct Field {
     pub alias: Option<Name>,
     pub name: Name,
     pub arguments: Vec<Node<Argument>>,
+    /// Experimental client-controlled nullability, written after arguments.
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    pub nullability: Option<Nullability>,
     pub directives: DirectiveList,
     pub selection_set: Vec<Selection>,
 }
 
+/// Executable AST for the experimental client-controlled nullability of a [`Field`] selection,
+/// such as `!`, `?`, or `[!]?` in `{ field[!]? }`.
+///
+/// This is from a proposal that is not part of the GraphQL specification yet.
+/// The `experimental-client-controlled-nullability` crate feature enables
+/// parsing it and adds the `nullability` field of [`Field`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Nullability {
+    /// Nullability of list items, written in `[…]`.
+    /// `Some` with an empty [`Nullability`] for `[]`.
+    pub list: Option<Box<Nullability>>,
+    pub designator: Option<NullabilityDesignator>,
+}
+
+/// A `!` or `?` designator in [`Nullability`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NullabilityDesignator {
+    /// `!`: the field or list item is non-null
+    Required,
+    /// `?`: the field or list item is nullable
+    Optional,
+}
+
 /// Executable AST for a
 /// [_FragmentSpread_](https://spec.graphql.org/draft/#FragmentSpread) selection
 /// in a selection set.
@@ -0,0 +1,358 @@
+use super::*;
+use crate::execution::GraphQLError;
+use crate::parser::LineColumn;
+use crate::parser::SourceSpan;
+use crate::ExecutableDocument;
+use std::path::Path;
+
+/// Maps locations in serialized GraphQL text back to the source locations
+/// of the nodes it was serialized from.
+///
+/// A document produced by a transform (such as [splitting introspection][crate::execution::SchemaIntrospectionSplit]
+/// or a user-defined normalization) is made of nodes that keep the location
+/// where they were originally parsed.
+/// Once such a document is serialized, for example to be sent to another server,
+/// errors about that text are reported with locations in that text, which the user never saw.
+/// This map translates them back to the user’s original text.
+///
+/// Created by [`Serialize::to_string_with_provenance`].
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::ast;
+/// use apollo_compiler::execution::GraphQLError;
+/// use apollo_compiler::parser::LineColumn;
+///
+/// let input = "query Q {\n  a\n  b { c }\n}\n";
+/// let mut document = ast::Document::parse(input, "original.graphql").unwrap();
+/// // Some transform: remove the `a` field
+/// if let ast::Definition::OperationDefinition(operation) = &mut document.definitions[0] {
+///     operation.make_mut().selection_set.remove(0);
+/// }
+///
+/// let (derived, provenance) = document
+///     .serialize()
+///     .no_indent()
+///     .to_string_with_provenance("derived.graphql");
+/// assert_eq!(derived, "query Q { b { c } }");
+///
+/// // Another server reports an error about `c` in the derived text
+/// let mut error = GraphQLError::builder("oops")
+///     .line_column(LineColumn { line: 1, column: 15 })
+///     .build();
+/// provenance.remap_graphql_error(&mut error, &document.sources);
+/// assert_eq!(error.locations, [LineColumn { line: 3, column: 7 }]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProvenanceMap {
+    derived_sources: SourceMap,
+    entries: Vec<ProvenanceEntry>,
+}
+
+/// A node in serialized text and where it was originally parsed.
+/// Part of a [`ProvenanceMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvenanceEntry {
+    /// The location of the node in the serialized text
+    pub derived: SourceSpan,
+    /// The location of the node in the document it was parsed from
+    pub original: SourceSpan,
+}
+
+impl ProvenanceMap {
+    fn new(original: &Document, derived_text: &str, path: &Path) -> Self {
+        let derived = Document::parse(derived_text, path).unwrap_or_else(|invalid| invalid.partial);
+        let mut original_locations = Vec::new();
+        let mut derived_locations = Vec::new();
+        document_locations(original, &mut original_locations);
+        document_locations(&derived, &mut derived_locations);
+        // Serialization round-trips (see tests/snapshot_tests.rs),
+        // so both traversals visit the same nodes in the same order
+        debug_assert_eq!(original_locations.len(), derived_locations.len());
+        let entries = original_locations
+            .into_iter()
+            .zip(derived_locations)
+            .filter_map(|(original, derived)| {
+                Some(ProvenanceEntry {
+                    derived: derived?,
+                    original: original?,
+                })
+            })
+            .collect();
+        Self {
+            derived_sources: derived.sources,
+            entries,
+        }
+    }
+
+    /// Returns every mapped node, with parents before their children
+    pub fn entries(&self) -> &[ProvenanceEntry] {
+        &self.entries
+    }
+
+    /// Returns the original location of the innermost node
+    /// whose serialization contains this byte offset in the serialized text
+    pub fn original_span_at(&self, offset: usize) -> Option<SourceSpan> {
+        self.innermost(|entry| {
+            (entry.derived.offset()..entry.derived.end_offset()).contains(&offset)
+        })
+    }
+
+    /// Returns the original location of the innermost node
+    /// whose serialization contains this line and column in the serialized text
+    pub fn original_span_at_line_column(&self, location: LineColumn) -> Option<SourceSpan> {
+        let key = |lc: LineColumn| (lc.line, lc.column);
+        self.innermost(|entry| {
+            entry
+                .derived
+                .line_column_range(&self.derived_sources)
+                .is_some_and(|range| {
+                    key(range.start) <= key(location) && key(location) < key(range.end)
+                })
+        })
+    }
+
+    /// Replace locations of an error about the serialized text
+    /// with the corresponding locations in the original text,
+    /// given the source map of the original document or schema.
+    ///
+    /// Locations that do not correspond to any node with an original location are removed.
+    pub fn remap_graphql_error(&self, error: &mut GraphQLError, original_sources: &SourceMap) {
+        error.locations = error
+            .locations
+            .iter()
+            .filter_map(|&location| {
+                self.original_span_at_line_column(location)?
+                    .line_column(original_sources)
+            })
+            .collect()
+    }
+
+    fn innermost(&self, contains: impl Fn(&ProvenanceEntry) -> bool) -> Option<SourceSpan> {
+        self.entries
+            .iter()
+            .rev() // children are after their parents: prefer them on ties
+            .filter(|entry| contains(entry))
+            .min_by_key(|entry| entry.derived.node_len())
+            .map(|entry| entry.original)
+    }
+}
+
+impl Serialize<'_, Document> {
+    /// Serialize to a string, and return it together with a map from locations in that string
+    /// to the original locations of the serialized nodes.
+    ///
+    /// `path` is used as a file name when parsing the serialized text to build the map.
+    pub fn to_string_with_provenance(&self, path: impl AsRef<Path>) -> (String, ProvenanceMap) {
+        let text = self.to_string();
+        let map = ProvenanceMap::new(self.node, &text, path.as_ref());
+        (text, map)
+    }
+}
+
+impl Serialize<'_, ExecutableDocument> {
+    /// Serialize to a string, and return it together with a map from locations in that string
+    /// to the original locations of the serialized nodes.
+    ///
+    /// `path` is used as a file name when parsing the serialized text to build the map.
+    pub fn to_string_with_provenance(&self, path: impl AsRef<Path>) -> (String, ProvenanceMap) {
+        let text = self.to_string();
+        let map = ProvenanceMap::new(&self.node.to_ast(), &text, path.as_ref());
+        (text, map)
+    }
+}
+
+fn document_locations(document: &Document, out: &mut Vec<Option<SourceSpan>>) {
+    for definition in &document.definitions {
+        out.push(definition.location());
+        if let Some(name) = definition.name() {
+            out.push(name.location())
+        }
+        match definition {
+            Definition::OperationDefinition(def) => {
+                for var in &def.variables {
+                    out.push(var.location());
+                    out.push(var.name.location());
+                    out.push(var.ty.location());
+                    if let Some(value) = &var.default_value {
+                        value_locations(value, out)
+                    }
+                    directives_locations(&var.directives, out);
+                }
+                directives_locations(&def.directives, out);
+                selections_locations(&def.selection_set, out);
+            }
+            Definition::FragmentDefinition(def) => {
+                out.push(def.type_condition.location());
+                directives_locations(&def.directives, out);
+                selections_locations(&def.selection_set, out);
+            }
+            Definition::DirectiveDefinition(def) => input_values_locations(&def.arguments, out),
+            Definition::SchemaDefinition(def) => {
+                directives_locations(&def.directives, out);
+                root_operations_locations(&def.root_operations, out);
+            }
+            Definition::SchemaExtension(def) => {
+                directives_locations(&def.directives, out);
+                root_operations_locations(&def.root_operations, out);
+            }
+            Definition::ScalarTypeDefinition(def) => directives_locations(&def.directives, out),
+            Definition::ScalarTypeExtension(def) => directives_locations(&def.directives, out),
+            Definition::ObjectTypeDefinition(def) => {
+                names_locations(&def.implements_interfaces, out);
+                directives_locations(&def.directives, out);
+                fields_locations(&def.fields, out);
+            }
+            Definition::ObjectTypeExtension(def) => {
+                names_locations(&def.implements_interfaces, out);
+                directives_locations(&def.directives, out);
+                fields_locations(&def.fields, out);
+            }
+            Definition::InterfaceTypeDefinition(def) => {
+                names_locations(&def.implements_interfaces, out);
+                directives_locations(&def.directives, out);
+                fields_locations(&def.fields, out);
+            }
+            Definition::InterfaceTypeExtension(def) => {
+                names_locations(&def.implements_interfaces, out);
+                directives_locations(&def.directives, out);
+                fields_locations(&def.fields, out);
+            }
+            Definition::UnionTypeDefinition(def) => {
+                directives_locations(&def.directives, out);
+                names_locations(&def.members, out);
+            }
+            Definition::UnionTypeExtension(def) => {
+                directives_locations(&def.directives, out);
+                names_locations(&def.members, out);
+            }
+            Definition::EnumTypeDefinition(def) => {
+                directives_locations(&def.directives, out);
+                enum_values_locations(&def.values, out);
+            }
+            Definition::EnumTypeExtension(def) => {
+                directives_locations(&def.directives, out);
+                enum_values_locations(&def.values, out);
+            }
+            Definition::InputObjectTypeDefinition(def) => {
+                directives_locations(&def.directives, out);
+                input_values_locations(&def.fields, out);
+            }
+            Definition::InputObjectTypeExtension(def) => {
+                directives_locations(&def.directives, out);
+                input_values_locations(&def.fields, out);
+            }
+        }
+    }
+}
+
+fn selections_locations(selections: &[Selection], out: &mut Vec<Option<SourceSpan>>) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                out.push(field.location());
+                if let Some(alias) = &field.alias {
+                    out.push(alias.location())
+                }
+                out.push(field.name.location());
+                arguments_locations(&field.arguments, out);
+                directives_locations(&field.directives, out);
+                selections_locations(&field.selection_set, out);
+            }
+            Selection::FragmentSpread(spread) => {
+                out.push(spread.location());
+                out.push(spread.fragment_name.location());
+                directives_locations(&spread.directives, out);
+            }
+            Selection::InlineFragment(inline) => {
+                out.push(inline.location());
+                if let Some(type_condition) = &inline.type_condition {
+                    out.push(type_condition.location())
+                }
+                directives_locations(&inline.directives, out);
+                selections_locations(&inline.selection_set, out);
+            }
+        }
+    }
+}
+
+fn directives_locations(directives: &DirectiveList, out: &mut Vec<Option<SourceSpan>>) {
+    for directive in directives {
+        out.push(directive.location());
+        out.push(directive.name.location());
+        arguments_locations(&directive.arguments, out);
+    }
+}
+
+fn arguments_locations(arguments: &[Node<Argument>], out: &mut Vec<Option<SourceSpan>>) {
+    for argument in arguments {
+        out.push(argument.location());
+        out.push(argument.name.location());
+        value_locations(&argument.value, out);
+    }
+}
+
+fn value_locations(value: &Node<Value>, out: &mut Vec<Option<SourceSpan>>) {
+    out.push(value.location());
+    match value.as_ref() {
+        Value::List(items) => {
+            for item in items {
+                value_locations(item, out)
+            }
+        }
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                out.push(name.location());
+                value_locations(value, out)
+            }
+        }
+        _ => {}
+    }
+}
+
+fn fields_locations(fields: &[Node<FieldDefinition>], out: &mut Vec<Option<SourceSpan>>) {
+    for field in fields {
+        out.push(field.location());
+        out.push(field.name.location());
+        input_values_locations(&field.arguments, out);
+        directives_locations(&field.directives, out);
+    }
+}
+
+fn input_values_locations(
+    input_values: &[Node<InputValueDefinition>],
+    out: &mut Vec<Option<SourceSpan>>,
+) {
+    for input_value in input_values {
+        out.push(input_value.location());
+        out.push(input_value.name.location());
+        out.push(input_value.ty.location());
+        if let Some(value) = &input_value.default_value {
+            value_locations(value, out)
+        }
+        directives_locations(&input_value.directives, out);
+    }
+}
+
+fn enum_values_locations(values: &[Node<EnumValueDefinition>], out: &mut Vec<Option<SourceSpan>>) {
+    for value in values {
+        out.push(value.location());
+        out.push(value.value.location());
+        directives_locations(&value.directives, out);
+    }
+}
+
+fn root_operations_locations(
+    root_operations: &[Node<(OperationType, NamedType)>],
+    out: &mut Vec<Option<SourceSpan>>,
+) {
+    for root_operation in root_operations {
+        out.push(root_operation.location());
+        out.push(root_operation.1.location());
+    }
+}
+
+fn names_locations(names: &[Name], out: &mut Vec<Option<SourceSpan>>) {
+    out.extend(names.iter().map(|name| name.location()))
+}
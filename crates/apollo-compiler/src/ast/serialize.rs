@@ -14,8 +14,12 @@ pub struct Serialize<'a, T> {
 
 #[derive(Debug, Clone)]
 pub(crate) struct Config<'a> {
-    indent_prefix: Option<&'a str>,
-    initial_indent_level: usize,
+    pub(crate) indent_prefix: Option<&'a str>,
+    pub(crate) initial_indent_level: usize,
+    /// Only used for `Schema`
+    pub(crate) origin_comments: bool,
+    /// Only used for `Schema`
+    pub(crate) canonical: bool,
 }
 
 pub(crate) struct State<'config, 'fmt, 'fmt2> {
@@ -53,6 +57,8 @@ impl Default for Config<'_> {
         Self {
             indent_prefix: Some("  "),
             initial_indent_level: 0,
+            origin_comments: false,
+            canonical: false,
         }
     }
 }
@@ -122,6 +128,14 @@ impl State<'_, '_, '_> {
         Ok(())
     }
 
+    pub(crate) fn origin_comments_enabled(&self) -> bool {
+        self.config.origin_comments
+    }
+
+    pub(crate) fn canonical_enabled(&self) -> bool {
+        self.config.canonical
+    }
+
     pub(crate) fn newlines_enabled(&self) -> bool {
         self.config.indent_prefix.is_some()
     }
@@ -194,6 +208,7 @@ impl OperationDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         // Deconstruct to get a warning if we forget to serialize something
         let Self {
+            leading_comments,
             operation_type,
             name,
             variables,
@@ -207,6 +222,7 @@ impl OperationDefinition {
             && name.is_none()
             && variables.is_empty()
             && directives.is_empty();
+        serialize_leading_comments(state, leading_comments)?;
         if !shorthand {
             state.write(operation_type.name())?;
             if let Some(name) = &name {
@@ -230,11 +246,13 @@ impl OperationDefinition {
 impl FragmentDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             name,
             type_condition,
             directives,
             selection_set,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         display!(state, "fragment {} on {}", name, type_condition)?;
         directives.serialize_impl(state)?;
         state.write(" ")?;
@@ -246,11 +264,13 @@ impl DirectiveDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             arguments,
             repeatable,
             locations,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("directive @")?;
         state.write(name)?;
@@ -281,10 +301,11 @@ fn serialize_arguments_definition(
                 arg.serialize_impl(state)
             })
         };
-        if arguments
-            .iter()
-            .any(|arg| arg.description.is_some() || !arg.directives.is_empty())
-        {
+        if arguments.iter().any(|arg| {
+            arg.description.is_some()
+                || !arg.leading_comments.is_empty()
+                || !arg.directives.is_empty()
+        }) {
             serialize_arguments(state)?
         } else {
             state.on_single_line(serialize_arguments)?
@@ -297,9 +318,11 @@ impl SchemaDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             directives,
             root_operations,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("schema")?;
         directives.serialize_impl(state)?;
@@ -315,9 +338,11 @@ impl ScalarTypeDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             directives,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("scalar ")?;
         state.write(name)?;
@@ -329,11 +354,13 @@ impl ObjectTypeDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             implements_interfaces,
             directives,
             fields,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("type ")?;
         serialize_object_type_like(state, name, implements_interfaces, directives, fields)
@@ -369,11 +396,13 @@ impl InterfaceTypeDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             implements_interfaces,
             directives,
             fields,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("interface ")?;
         serialize_object_type_like(state, name, implements_interfaces, directives, fields)
@@ -384,10 +413,12 @@ impl UnionTypeDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             directives,
             members,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("union ")?;
         serialize_union(state, name, directives, members)
@@ -417,10 +448,12 @@ impl EnumTypeDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             directives,
             values,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("enum ")?;
         state.write(name)?;
@@ -439,10 +472,12 @@ impl InputObjectTypeDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             directives,
             fields,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write("input ")?;
         state.write(name)?;
@@ -458,9 +493,11 @@ impl InputObjectTypeDefinition {
 impl SchemaExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             directives,
             root_operations,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend schema")?;
         directives.serialize_impl(state)?;
         if !root_operations.is_empty() {
@@ -476,7 +513,12 @@ impl SchemaExtension {
 
 impl ScalarTypeExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
-        let Self { name, directives } = self;
+        let Self {
+            leading_comments,
+            name,
+            directives,
+        } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend scalar ")?;
         state.write(name)?;
         directives.serialize_impl(state)
@@ -486,11 +528,13 @@ impl ScalarTypeExtension {
 impl ObjectTypeExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             name,
             implements_interfaces,
             directives,
             fields,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend type ")?;
         serialize_object_type_like(state, name, implements_interfaces, directives, fields)
     }
@@ -499,11 +543,13 @@ impl ObjectTypeExtension {
 impl InterfaceTypeExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             name,
             implements_interfaces,
             directives,
             fields,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend interface ")?;
         serialize_object_type_like(state, name, implements_interfaces, directives, fields)
     }
@@ -512,10 +558,12 @@ impl InterfaceTypeExtension {
 impl UnionTypeExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             name,
             directives,
             members,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend union ")?;
         serialize_union(state, name, directives, members)
     }
@@ -524,10 +572,12 @@ impl UnionTypeExtension {
 impl EnumTypeExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             name,
             directives,
             values,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend enum ")?;
         state.write(name)?;
         directives.serialize_impl(state)?;
@@ -544,10 +594,12 @@ impl EnumTypeExtension {
 impl InputObjectTypeExtension {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
+            leading_comments,
             name,
             directives,
             fields,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         state.write("extend input ")?;
         state.write(name)?;
         directives.serialize_impl(state)?;
@@ -602,11 +654,13 @@ impl FieldDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             arguments,
             ty,
             directives,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write(name)?;
         serialize_arguments_definition(state, arguments)?;
@@ -620,11 +674,13 @@ impl InputValueDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             name,
             ty,
             default_value,
             directives,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write(name)?;
         state.write(": ")?;
@@ -641,9 +697,11 @@ impl EnumValueDefinition {
     fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         let Self {
             description,
+            leading_comments,
             value,
             directives,
         } = self;
+        serialize_leading_comments(state, leading_comments)?;
         serialize_description(state, description)?;
         state.write(value)?;
         directives.serialize_impl(state)
@@ -666,6 +724,8 @@ impl Field {
             alias,
             name,
             arguments,
+            #[cfg(feature = "experimental-client-controlled-nullability")]
+            nullability,
             directives,
             selection_set,
         } = self;
@@ -675,6 +735,10 @@ impl Field {
         }
         state.write(name)?;
         serialize_arguments(state, arguments)?;
+        #[cfg(feature = "experimental-client-controlled-nullability")]
+        if let Some(nullability) = nullability {
+            display!(state, nullability)?;
+        }
         directives.serialize_impl(state)?;
         if !selection_set.is_empty() {
             state.write(" ")?;
@@ -943,6 +1007,18 @@ fn can_be_block_string(value: &str) -> bool {
     common_indent == 0
 }
 
+/// Comments can only be kept when newlines are enabled
+pub(crate) fn serialize_leading_comments(state: &mut State, comments: &[Node<str>]) -> fmt::Result {
+    if state.newlines_enabled() {
+        for comment in comments {
+            state.write("#")?;
+            state.write(comment)?;
+            state.require_new_line()?;
+        }
+    }
+    Ok(())
+}
+
 fn serialize_description(state: &mut State, description: &Option<Node<str>>) -> fmt::Result {
     if let Some(description) = description {
         let is_description = true;
@@ -963,6 +1039,28 @@ impl fmt::Display for Type {
     }
 }
 
+impl fmt::Display for Nullability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { list, designator } = self;
+        if let Some(items) = list {
+            std::write!(f, "[{items}]")?;
+        }
+        if let Some(designator) = designator {
+            designator.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for NullabilityDesignator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NullabilityDesignator::Required => f.write_str("!"),
+            NullabilityDesignator::Optional => f.write_str("?"),
+        }
+    }
+}
+
 impl fmt::Display for OperationType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.name().fmt(f)
@@ -0,0 +1,534 @@
+use crate::ast::Type;
+use crate::ast::Value;
+use crate::schema::ExtendedType;
+use crate::schema::InputObjectType;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// Builds an [`ast::Value`][Value] checked against the type it is expected to have,
+/// returned by [`Value::build`].
+///
+/// Each value, list item, and input object field is checked as soon as it is added,
+/// with the rules of [values validation](https://spec.graphql.org/draft/#sec-Values)
+/// and input coercion. The first mistake is returned as a [`ValueBuildError`]
+/// with the path of the offending value,
+/// instead of being reported later when validating the document that contains it.
+///
+/// Variables are accepted for any type:
+/// they are checked against variable definitions when validating the document.
+/// Custom scalars accept any value.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::ast::Value;
+/// use apollo_compiler::ty;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { users(filter: UserFilter!): [String] }
+///     input UserFilter { ids: [Int!], role: Role, name: NameFilter }
+///     input NameFilter { prefix: String! }
+///     enum Role { ADMIN, GUEST }
+/// "#, "schema.graphql").unwrap();
+///
+/// let filter = Value::build(&schema, &ty!(UserFilter!))
+///     .object(|o| {
+///         o.field("ids", [1, 2, 3])
+///             .field("role", Value::Enum(apollo_compiler::name!("ADMIN")))
+///             .object("name", |n| n.field("prefix", "Al"))
+///     })
+///     .unwrap();
+/// assert_eq!(
+///     filter.serialize().no_indent().to_string(),
+///     r#"{ids: [1, 2, 3], role: ADMIN, name: {prefix: "Al"}}"#,
+/// );
+///
+/// let error = Value::build(&schema, &ty!(UserFilter!))
+///     .object(|o| o.field("ids", [1, 2, 3]).object("name", |n| n.field("prefix", 4)))
+///     .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "value.name.prefix: expected a value of type `String!`, found an integer",
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValueBuilder<'a> {
+    schema: &'a Schema,
+    ty: &'a Type,
+    path: Path,
+}
+
+/// Builds an input object value field by field, for [`ValueBuilder::object`].
+///
+/// After the first error, further fields are ignored.
+#[derive(Debug, Clone)]
+pub struct ObjectValueBuilder<'a> {
+    schema: &'a Schema,
+    ty: &'a Type,
+    /// `None` for a custom scalar, which accepts any field
+    definition: Option<&'a Node<InputObjectType>>,
+    path: Path,
+    fields: Vec<(Name, Node<Value>)>,
+    error: Option<ValueBuildError>,
+}
+
+/// Builds a list value item by item, for [`ValueBuilder::list`].
+///
+/// After the first error, further items are ignored.
+#[derive(Debug, Clone)]
+pub struct ListValueBuilder<'a> {
+    schema: &'a Schema,
+    item_type: &'a Type,
+    path: Path,
+    items: Vec<Node<Value>>,
+    error: Option<ValueBuildError>,
+}
+
+/// A value that does not have its expected type, returned by [`ValueBuilder`] methods.
+///
+/// `path` is the location of the value in the built value, such as `value.filter.ids[2]`,
+/// where `value` is the root.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ValueBuildError {
+    #[error("{path}: type `{ty}` is not defined in the schema")]
+    UndefinedType { path: String, ty: Name },
+    #[error("{path}: `{ty}` is not an input type")]
+    NotInputType { path: String, ty: Name },
+    #[error("{path}: expected a value of type `{ty}`, found {found}")]
+    TypeMismatch {
+        path: String,
+        ty: Type,
+        /// The kind of value found, such as `a string`
+        found: &'static str,
+    },
+    #[error("{path}: null value for non-null type `{ty}`")]
+    NullValue { path: String, ty: Type },
+    #[error("{path}: {value} is out of range for type `{ty}`")]
+    OutOfRange {
+        path: String,
+        ty: Name,
+        value: String,
+    },
+    #[error("{path}: enum `{ty}` has no value `{value}`")]
+    UndefinedEnumValue { path: String, ty: Name, value: Name },
+    #[error("{path}: input object `{ty}` has no field `{field}`")]
+    UndefinedField {
+        path: String,
+        ty: Name,
+        field: String,
+    },
+    #[error("{path}: `{field}` is not a valid GraphQL name")]
+    InvalidFieldName { path: String, field: String },
+    #[error("{path}: field `{field}` of input object `{ty}` is set more than once")]
+    DuplicateField { path: String, ty: Name, field: Name },
+    #[error("{path}: missing value for required field `{field}` of input object `{ty}`")]
+    MissingField { path: String, ty: Name, field: Name },
+    #[error("{path}: oneOf input object `{ty}` must have exactly one field, which is not null")]
+    OneOf { path: String, ty: Name },
+}
+
+#[derive(Debug, Clone, Default)]
+struct Path(Vec<PathElement>);
+
+#[derive(Debug, Clone)]
+enum PathElement {
+    Field(Name),
+    Index(usize),
+}
+
+impl Value {
+    /// Returns a builder for a value of type `ty`,
+    /// checking nested values against the types defined in `schema`.
+    ///
+    /// See [`ValueBuilder`].
+    pub fn build<'a>(schema: &'a Schema, ty: &'a Type) -> ValueBuilder<'a> {
+        ValueBuilder {
+            schema,
+            ty,
+            path: Path::default(),
+        }
+    }
+}
+
+impl<'a> ValueBuilder<'a> {
+    /// Returns `value` after checking it against the expected type, including nested values.
+    pub fn value(self, value: impl Into<Value>) -> Result<Node<Value>, ValueBuildError> {
+        let value = value.into();
+        check_value(self.schema, self.ty, &value, &self.path)?;
+        Ok(Node::new(value))
+    }
+
+    /// Returns `null` if the expected type is nullable
+    pub fn null(self) -> Result<Node<Value>, ValueBuildError> {
+        self.value(Value::Null)
+    }
+
+    /// Builds an input object value with `build`, then checks for missing required fields.
+    ///
+    /// If the expected type is a list, this builds a single item
+    /// which [input coercion](https://spec.graphql.org/draft/#sec-List.Input-Coercion)
+    /// turns into a list.
+    pub fn object(
+        self,
+        build: impl FnOnce(ObjectValueBuilder<'a>) -> ObjectValueBuilder<'a>,
+    ) -> Result<Node<Value>, ValueBuildError> {
+        let ty = self.ty.item_type();
+        let definition = match input_type(self.schema, ty, &self.path)? {
+            ExtendedType::InputObject(definition) => Some(definition),
+            ExtendedType::Scalar(scalar) if !scalar.is_built_in() => None,
+            _ => return Err(mismatch(ty, "an input object", &self.path)),
+        };
+        let builder = build(ObjectValueBuilder {
+            schema: self.schema,
+            ty,
+            definition,
+            path: self.path,
+            fields: Vec::new(),
+            error: None,
+        });
+        builder.finish()
+    }
+
+    /// Builds a list value with `build`.
+    ///
+    /// The expected type must be a list or a custom scalar.
+    pub fn list(
+        self,
+        build: impl FnOnce(ListValueBuilder<'a>) -> ListValueBuilder<'a>,
+    ) -> Result<Node<Value>, ValueBuildError> {
+        let item_type = if self.ty.is_list() {
+            self.ty.item_type()
+        } else {
+            match input_type(self.schema, self.ty, &self.path)? {
+                // A custom scalar accepts anything, including items of its own type
+                ExtendedType::Scalar(scalar) if !scalar.is_built_in() => self.ty,
+                _ => return Err(mismatch(self.ty, "a list", &self.path)),
+            }
+        };
+        let builder = build(ListValueBuilder {
+            schema: self.schema,
+            item_type,
+            path: self.path,
+            items: Vec::new(),
+            error: None,
+        });
+        match builder.error {
+            Some(error) => Err(error),
+            None => Ok(Node::new(Value::List(builder.items))),
+        }
+    }
+}
+
+impl<'a> ObjectValueBuilder<'a> {
+    /// Adds a field after checking `value` against the type of the field definition
+    pub fn field(self, name: &str, value: impl Into<Value>) -> Self {
+        self.add(name, |builder| builder.value(value))
+    }
+
+    /// Adds a field with an input object value built by `build`
+    pub fn object(
+        self,
+        name: &str,
+        build: impl FnOnce(ObjectValueBuilder<'a>) -> ObjectValueBuilder<'a>,
+    ) -> Self {
+        self.add(name, |builder| builder.object(build))
+    }
+
+    /// Adds a field with a list value built by `build`
+    pub fn list(
+        self,
+        name: &str,
+        build: impl FnOnce(ListValueBuilder<'a>) -> ListValueBuilder<'a>,
+    ) -> Self {
+        self.add(name, |builder| builder.list(build))
+    }
+
+    fn add(
+        mut self,
+        name: &str,
+        value: impl FnOnce(ValueBuilder<'a>) -> Result<Node<Value>, ValueBuildError>,
+    ) -> Self {
+        if self.error.is_none() {
+            if let Err(error) = self.try_add(name, value) {
+                self.error = Some(error)
+            }
+        }
+        self
+    }
+
+    fn try_add(
+        &mut self,
+        name: &str,
+        value: impl FnOnce(ValueBuilder<'a>) -> Result<Node<Value>, ValueBuildError>,
+    ) -> Result<(), ValueBuildError> {
+        let (name, ty) = match self.definition {
+            Some(definition) => {
+                let Some((name, field)) = definition.fields.get_key_value(name) else {
+                    return Err(ValueBuildError::UndefinedField {
+                        path: self.path.to_string(),
+                        ty: definition.name.clone(),
+                        field: name.to_owned(),
+                    });
+                };
+                (name.clone(), &*field.ty)
+            }
+            None => {
+                let name = Name::new(name).map_err(|_| ValueBuildError::InvalidFieldName {
+                    path: self.path.to_string(),
+                    field: name.to_owned(),
+                })?;
+                (name, self.ty)
+            }
+        };
+        if self.fields.iter().any(|(existing, _)| *existing == name) {
+            return Err(ValueBuildError::DuplicateField {
+                path: self.path.to_string(),
+                ty: self.ty.inner_named_type().clone(),
+                field: name,
+            });
+        }
+        let value = value(ValueBuilder {
+            schema: self.schema,
+            ty,
+            path: self.path.field(&name),
+        })?;
+        self.fields.push((name, value));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Node<Value>, ValueBuildError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if let Some(definition) = self.definition {
+            check_object_fields(definition, &self.fields, &self.path)?;
+        }
+        Ok(Node::new(Value::Object(self.fields)))
+    }
+}
+
+impl<'a> ListValueBuilder<'a> {
+    /// Adds an item after checking `value` against the item type
+    pub fn item(self, value: impl Into<Value>) -> Self {
+        self.add(|builder| builder.value(value))
+    }
+
+    /// Adds an input object item built by `build`
+    pub fn object(
+        self,
+        build: impl FnOnce(ObjectValueBuilder<'a>) -> ObjectValueBuilder<'a>,
+    ) -> Self {
+        self.add(|builder| builder.object(build))
+    }
+
+    /// Adds a nested list item built by `build`
+    pub fn list(self, build: impl FnOnce(ListValueBuilder<'a>) -> ListValueBuilder<'a>) -> Self {
+        self.add(|builder| builder.list(build))
+    }
+
+    fn add(
+        mut self,
+        value: impl FnOnce(ValueBuilder<'a>) -> Result<Node<Value>, ValueBuildError>,
+    ) -> Self {
+        if self.error.is_none() {
+            let builder = ValueBuilder {
+                schema: self.schema,
+                ty: self.item_type,
+                path: self.path.index(self.items.len()),
+            };
+            match value(builder) {
+                Ok(value) => self.items.push(value),
+                Err(error) => self.error = Some(error),
+            }
+        }
+        self
+    }
+}
+
+/// Returns the definition of the named type of `ty`, which must be an input type
+fn input_type<'a>(
+    schema: &'a Schema,
+    ty: &Type,
+    path: &Path,
+) -> Result<&'a ExtendedType, ValueBuildError> {
+    let name = ty.inner_named_type();
+    let Some(definition) = schema.types.get(name) else {
+        return Err(ValueBuildError::UndefinedType {
+            path: path.to_string(),
+            ty: name.clone(),
+        });
+    };
+    if !definition.is_input_type() {
+        return Err(ValueBuildError::NotInputType {
+            path: path.to_string(),
+            ty: name.clone(),
+        });
+    }
+    Ok(definition)
+}
+
+fn mismatch(ty: &Type, found: &'static str, path: &Path) -> ValueBuildError {
+    ValueBuildError::TypeMismatch {
+        path: path.to_string(),
+        ty: ty.clone(),
+        found,
+    }
+}
+
+fn check_value(
+    schema: &Schema,
+    ty: &Type,
+    value: &Value,
+    path: &Path,
+) -> Result<(), ValueBuildError> {
+    match value {
+        // Checked against variable definitions when validating the document
+        Value::Variable(_) => return Ok(()),
+        Value::Null if ty.is_non_null() => {
+            return Err(ValueBuildError::NullValue {
+                path: path.to_string(),
+                ty: ty.clone(),
+            })
+        }
+        Value::Null => return Ok(()),
+        _ => {}
+    }
+    let definition = input_type(schema, ty, path)?;
+    if let ExtendedType::Scalar(scalar) = definition {
+        if !scalar.is_built_in() {
+            return Ok(());
+        }
+    }
+    if ty.is_list() {
+        return match value {
+            Value::List(items) => items.iter().enumerate().try_for_each(|(index, item)| {
+                check_value(schema, ty.item_type(), item, &path.index(index))
+            }),
+            // Input coercion turns a single item into a list
+            _ => check_value(schema, ty.item_type(), value, path),
+        };
+    }
+    let out_of_range = |value: &str| ValueBuildError::OutOfRange {
+        path: path.to_string(),
+        ty: ty.inner_named_type().clone(),
+        value: value.to_owned(),
+    };
+    match (value, definition) {
+        (Value::Int(int), ExtendedType::Scalar(scalar)) => match scalar.name.as_str() {
+            "Int" => int
+                .try_to_i32()
+                .map(drop)
+                .map_err(|_| out_of_range(int.as_str())),
+            "Float" => int
+                .try_to_f64()
+                .map(drop)
+                .map_err(|_| out_of_range(int.as_str())),
+            "ID" => Ok(()),
+            _ => Err(mismatch(ty, value.describe(), path)),
+        },
+        (Value::Float(float), ExtendedType::Scalar(scalar)) if scalar.name == "Float" => float
+            .try_to_f64()
+            .map(drop)
+            .map_err(|_| out_of_range(float.as_str())),
+        (Value::String(_), ExtendedType::Scalar(scalar))
+            if matches!(scalar.name.as_str(), "String" | "ID") =>
+        {
+            Ok(())
+        }
+        (Value::Boolean(_), ExtendedType::Scalar(scalar)) if scalar.name == "Boolean" => Ok(()),
+        (Value::Enum(name), ExtendedType::Enum(definition)) => {
+            if definition.values.contains_key(name) {
+                Ok(())
+            } else {
+                Err(ValueBuildError::UndefinedEnumValue {
+                    path: path.to_string(),
+                    ty: definition.name.clone(),
+                    value: name.clone(),
+                })
+            }
+        }
+        (Value::Object(fields), ExtendedType::InputObject(definition)) => {
+            for (index, (name, value)) in fields.iter().enumerate() {
+                let Some(field) = definition.fields.get(name) else {
+                    return Err(ValueBuildError::UndefinedField {
+                        path: path.to_string(),
+                        ty: definition.name.clone(),
+                        field: name.to_string(),
+                    });
+                };
+                if fields[..index].iter().any(|(previous, _)| previous == name) {
+                    return Err(ValueBuildError::DuplicateField {
+                        path: path.to_string(),
+                        ty: definition.name.clone(),
+                        field: name.clone(),
+                    });
+                }
+                check_value(schema, &field.ty, value, &path.field(name))?;
+            }
+            check_object_fields(definition, fields, path)
+        }
+        _ => Err(mismatch(ty, value.describe(), path)),
+    }
+}
+
+/// Checks required fields and [oneOf](https://spec.graphql.org/draft/#sec-OneOf-Input-Objects)
+/// input objects, after each field was checked individually
+fn check_object_fields(
+    definition: &InputObjectType,
+    fields: &[(Name, Node<Value>)],
+    path: &Path,
+) -> Result<(), ValueBuildError> {
+    for (name, field) in &definition.fields {
+        let is_set = fields.iter().any(|(set, _)| set == name);
+        if !is_set && field.ty.is_non_null() && field.default_value.is_none() {
+            return Err(ValueBuildError::MissingField {
+                path: path.to_string(),
+                ty: definition.name.clone(),
+                field: name.clone(),
+            });
+        }
+    }
+    if definition.directives.get("oneOf").is_some() {
+        match fields {
+            [(_, value)] if !value.is_null() => {}
+            _ => {
+                return Err(ValueBuildError::OneOf {
+                    path: path.to_string(),
+                    ty: definition.name.clone(),
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Path {
+    fn field(&self, name: &Name) -> Self {
+        let mut path = self.clone();
+        path.0.push(PathElement::Field(name.clone()));
+        path
+    }
+
+    fn index(&self, index: usize) -> Self {
+        let mut path = self.clone();
+        path.0.push(PathElement::Index(index));
+        path
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value")?;
+        for element in &self.0 {
+            match element {
+                PathElement::Field(name) => write!(f, ".{name}")?,
+                PathElement::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
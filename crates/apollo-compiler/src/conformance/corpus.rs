@@ -0,0 +1,183 @@
+// Generated by `conformance_corpus` in tests/snapshot_tests.rs, do not edit.
+// To update: env UPDATE_EXPECT=1 cargo test --package apollo-compiler
+
+use super::Case;
+use super::Expected::Accepted;
+use super::Expected::Rejected;
+use super::Mode::Executable;
+use super::Mode::Mixed;
+use super::Mode::TypeSystem;
+
+macro_rules! case {
+    ($name: literal, $mode: expr, $expected: expr) => {
+        Case {
+            name: $name,
+            input: include_str!(concat!("../../test_data/", $name, ".graphql")),
+            mode: $mode,
+            expected: $expected,
+            serialized: include_str!(concat!("../../test_data/serializer/", $name, ".graphql")),
+        }
+    };
+}
+
+pub(super) const VALIDATION: &[Case] = &[
+    case!("ok/0001_annonymous_operation_definition", Mixed, Accepted),
+    case!("ok/0002_multiple_named_operation_definitions", Mixed, Accepted),
+    case!("ok/0003_schema_definition_with_custom_operation_types", Mixed, Accepted),
+    case!("ok/0004_schema_with_custom_scalars", Mixed, Accepted),
+    case!("ok/0005_schema_with_valid_enum_definitions", Mixed, Accepted),
+    case!("ok/0006_schema_with_valid_union", Mixed, Accepted),
+    case!("ok/0007_schema_with_interface_definition", Mixed, Accepted),
+    case!("ok/0008_schema_with_directive_definition", Mixed, Accepted),
+    case!("ok/0009_schema_with_input_object", Mixed, Accepted),
+    case!("ok/0010_operation_with_defined_fields", Mixed, Accepted),
+    case!("ok/0011_fragment_spreads_in_fragment_definitions", Mixed, Accepted),
+    case!("ok/0012_introspection_query", Mixed, Accepted),
+    case!("ok/0013_operation_with_used_variable_in_fragment", Mixed, Accepted),
+    case!("ok/0014_float_values", Mixed, Accepted),
+    case!("ok/0015_supergraph", Mixed, Accepted),
+    case!("ok/0016_same_variables_in_multiple_operations", Mixed, Accepted),
+    case!("ok/0017_variables_are_input_types", Mixed, Accepted),
+    case!("ok/0018_non_clashing_names", Mixed, Accepted),
+    case!("ok/0019_extensions", Mixed, Accepted),
+    case!("ok/0020_merge_identical_fields", Mixed, Accepted),
+    case!("ok/0021_merge_identical_fields_with_arguments", Mixed, Accepted),
+    case!("ok/0022_merge_differing_fields_and_args", Mixed, Accepted),
+    case!("ok/0024_used_variables_in_directives", Mixed, Accepted),
+    case!("ok/0025_unique_directives", Mixed, Accepted),
+    case!("ok/0026_type_introspection", Mixed, Accepted),
+    case!("ok/0027_typename_introspection_in_object", Mixed, Accepted),
+    case!("ok/0028_typename_introspection_in_union", Mixed, Accepted),
+    case!("ok/0029_used_variable_in_list_and_input", Mixed, Accepted),
+    case!("ok/0030_cyclical_nullable_input_objects", Mixed, Accepted),
+    case!("ok/0031_fragment_spread_possible", Mixed, Accepted),
+    case!("ok/0032_valid_of_correct_type", Mixed, Accepted),
+    case!("ok/0033_valid_variable_usage", Mixed, Accepted),
+    case!("ok/0034_built_in_directive_redefinition", Mixed, Accepted),
+    case!("ok/0035_implicit_schema_definition_with_query_type", Mixed, Accepted),
+    case!("ok/0036_implicit_schema_definition_with_several_default_types", Mixed, Accepted),
+    case!("ok/0037_implicit_schema_extension_with_directive", Mixed, Accepted),
+    case!("ok/0038_argument_default", Mixed, Accepted),
+    case!("ok/0039_string_literals", Mixed, Accepted),
+    case!("ok/0040_field_merging_issue_755", Mixed, Accepted),
+    case!("ok/0041_unquoted_string_for_custom_scalar", Mixed, Accepted),
+    case!("ok/0042_used_variable_in_operation_directive", Mixed, Accepted),
+    case!("ok/0115_interface_definition_with_extension_defines_field", Mixed, Accepted),
+    case!("ok/0116_interface_without_implementations", Mixed, Accepted),
+    case!("diagnostics/0001_duplicate_operatoin_names", Mixed, Rejected),
+    case!("diagnostics/0002_multiple_anonymous_operations", Mixed, Rejected),
+    case!("diagnostics/0003_anonymous_and_named_operation", Mixed, Rejected),
+    case!("diagnostics/0004_subscription_with_multiple_root_fields", Mixed, Rejected),
+    case!("diagnostics/0005_subscription_with_multiple_root_fields_in_fragment_spreads", Mixed, Rejected),
+    case!("diagnostics/0006_subscription_with_multiple_root_fields_in_inline_fragments", Mixed, Rejected),
+    case!("diagnostics/0007_operation_with_undefined_variables", Mixed, Rejected),
+    case!("diagnostics/0008_operation_with_undefined_variables_in_inline_fragment", Mixed, Rejected),
+    case!("diagnostics/0009_operation_with_undefined_variables_in_fragment", Mixed, Rejected),
+    case!("diagnostics/0010_operation_with_unused_variable", Mixed, Rejected),
+    case!("diagnostics/0011_syntactic_errors_from_parser", Mixed, Rejected),
+    case!("diagnostics/0012_schema_definition_with_missing_query_operation_type", Mixed, Rejected),
+    case!("diagnostics/0013_schema_definition_with_duplicate_root_type_operations", Mixed, Rejected),
+    case!("diagnostics/0014_subscription_operation_without_subscription_schema_operation_type", Mixed, Rejected),
+    case!("diagnostics/0015_mutation_operation_without_mutation_schema_definition", Mixed, Rejected),
+    case!("diagnostics/0016_schema_with_built_in_scalar_definition", Mixed, Rejected),
+    case!("diagnostics/0017_schema_with_unspecified_scalar", Mixed, Rejected),
+    case!("diagnostics/0018_schema_with_specified_scalar_missing_values", Mixed, Rejected),
+    case!("diagnostics/0019_enum_definition_with_duplicate_values", Mixed, Rejected),
+    case!("diagnostics/0020_enum_values_with_uncapitalised_values", Mixed, Rejected),
+    case!("diagnostics/0021_union_definition_with_duplicate_members", Mixed, Rejected),
+    case!("diagnostics/0022_duplicate_interface_definitions", Mixed, Rejected),
+    case!("diagnostics/0023_interface_definition_with_cyclic_implements_interfaces", Mixed, Rejected),
+    case!("diagnostics/0024_interface_definition_with_duplicate_fields", Mixed, Rejected),
+    case!("diagnostics/0025_interface_definition_with_missing_transitive_fields", Mixed, Rejected),
+    case!("diagnostics/0026_interface_definition_with_missing_implemetns_interface", Mixed, Rejected),
+    case!("diagnostics/0027_interface_definition_with_undefined_implements_interface", Mixed, Rejected),
+    case!("diagnostics/0028_interface_definition_with_missing_fields_implements_intrefaces_undefined_interfaces", Mixed, Rejected),
+    case!("diagnostics/0029_directive_definitions_with_duplicate_names", Mixed, Rejected),
+    case!("diagnostics/0030_schema_with_duplicate_input_objects", Mixed, Rejected),
+    case!("diagnostics/0031_input_object_with_duplicate_fields", Mixed, Rejected),
+    case!("diagnostics/0032_duplicate_object_type_definition", Mixed, Rejected),
+    case!("diagnostics/0033_object_type_definition_with_duplicate_fields", Mixed, Rejected),
+    case!("diagnostics/0034_object_type_definition_with_missing_transitive_fields", Mixed, Rejected),
+    case!("diagnostics/0035_object_type_definition_with_missing_implements_interfaces_definition", Mixed, Rejected),
+    case!("diagnostics/0036_object_type_with_non_output_field_types", Mixed, Rejected),
+    case!("diagnostics/0037_interface_with_non_output_fields", Mixed, Rejected),
+    case!("diagnostics/0038_object_type_with_undefined_field_type", Mixed, Rejected),
+    case!("diagnostics/0039_interface_with_undefined_field_type", Mixed, Rejected),
+    case!("diagnostics/0040_operation_with_undefined_fields_on_reference_type", Mixed, Rejected),
+    case!("diagnostics/0041_subscription_operation_with_undefined_fields", Mixed, Rejected),
+    case!("diagnostics/0042_mutation_operation_with_undefined_fields", Mixed, Rejected),
+    case!("diagnostics/0043_undefined_union_member", Mixed, Rejected),
+    case!("diagnostics/0044_union_member_not_of_object_type", Mixed, Rejected),
+    case!("diagnostics/0045_operation_with_unused_variable_in_fragment", Mixed, Rejected),
+    case!("diagnostics/0046_duplicate_directive_arguments", Mixed, Rejected),
+    case!("diagnostics/0047_duplicate_field_arguments", Mixed, Rejected),
+    case!("diagnostics/0048_duplicate_field_argument_definition_names", Mixed, Rejected),
+    case!("diagnostics/0049_duplicate_directive_argument_definition_names", Mixed, Rejected),
+    case!("diagnostics/0050_directives_in_invalid_locations", Mixed, Rejected),
+    case!("diagnostics/0051_subscription_operation_with_root_introspection_field", Mixed, Rejected),
+    case!("diagnostics/0052_undefined_directive", Mixed, Rejected),
+    case!("diagnostics/0053_argument_name_is_not_defined", Mixed, Rejected),
+    case!("diagnostics/0054_argument_not_provided", Mixed, Rejected),
+    case!("diagnostics/0055_duplicate_variable_definitions", Mixed, Rejected),
+    case!("diagnostics/0056_variables_are_input_types", Mixed, Rejected),
+    case!("diagnostics/0057_duplicate_type_names", Mixed, Rejected),
+    case!("diagnostics/0058_fragment_definitions_with_duplicate_names", Mixed, Rejected),
+    case!("diagnostics/0059_root_operation_object_type", Mixed, Rejected),
+    case!("diagnostics/0060_root_operation_definition_undefined_operation_type", Mixed, Rejected),
+    case!("diagnostics/0061_root_operation_with_default_undefined_query", Mixed, Rejected),
+    case!("diagnostics/0062_root_operation_with_list", Mixed, Rejected),
+    case!("diagnostics/0063_extension_orphan", Mixed, Rejected),
+    case!("diagnostics/0064_extension_wrong_type", Mixed, Rejected),
+    case!("diagnostics/0065_subselection_of_enum", Mixed, Rejected),
+    case!("diagnostics/0066_subselection_of_scalar", Mixed, Rejected),
+    case!("diagnostics/0067_subselection_of_interface", Mixed, Rejected),
+    case!("diagnostics/0068_subselection_of_union", Mixed, Rejected),
+    case!("diagnostics/0069_subselection_of_object", Mixed, Rejected),
+    case!("diagnostics/0070_self_referential_directive_definition", Mixed, Rejected),
+    case!("diagnostics/0074_merge_identical_fields", Mixed, Rejected),
+    case!("diagnostics/0075_merge_conflicting_args", Mixed, Rejected),
+    case!("diagnostics/0076_merge_differing_responses", Mixed, Rejected),
+    case!("diagnostics/0077_merge_conflict_deep", Mixed, Rejected),
+    case!("diagnostics/0078_merge_conflict_nested_fragments", Mixed, Rejected),
+    case!("diagnostics/0079_directive_is_unique", Mixed, Rejected),
+    case!("diagnostics/0080_directive_is_unique_with_extensions", Mixed, Rejected),
+    case!("diagnostics/0081_directive_is_unique_type_system", Mixed, Rejected),
+    case!("diagnostics/0082_introspection_types_in_mutation", Mixed, Rejected),
+    case!("diagnostics/0083_type_introspection_from_disallowed_type", Mixed, Rejected),
+    case!("diagnostics/0084_circular_non_nullable_input_objects", Mixed, Rejected),
+    case!("diagnostics/0085_fragment_spread_target_defined", Mixed, Rejected),
+    case!("diagnostics/0086_unused_fragment", Mixed, Rejected),
+    case!("diagnostics/0087_fragment_type_condition_on_composite_types", Mixed, Rejected),
+    case!("diagnostics/0088_fragment_selection_set", Mixed, Rejected),
+    case!("diagnostics/0089_fragment_type_condition_on_non_existent_types", Mixed, Rejected),
+    case!("diagnostics/0090_fragment_spread_impossible", Mixed, Rejected),
+    case!("diagnostics/0091_recursive_interface_definition", Mixed, Rejected),
+    case!("diagnostics/0092_recursive_fragment_spread", Mixed, Rejected),
+    case!("diagnostics/0093_fragment_validation_with_recursive_type_system", Mixed, Rejected),
+    case!("diagnostics/0094_object_type_extensions", Mixed, Rejected),
+    case!("diagnostics/0095_interface_implementation_declared_once", Mixed, Rejected),
+    case!("diagnostics/0096_schema_extensions", Mixed, Rejected),
+    case!("diagnostics/0097_enum_extensions", Mixed, Rejected),
+    case!("diagnostics/0098_interface_extensions", Mixed, Rejected),
+    case!("diagnostics/0099_input_object_extensions", Mixed, Rejected),
+    case!("diagnostics/0100_union_extensions", Mixed, Rejected),
+    case!("diagnostics/0101_mismatched_variable_usage", Mixed, Rejected),
+    case!("diagnostics/0102_invalid_string_values", Mixed, Rejected),
+    case!("diagnostics/0103_invalid_directive_on_input_value", Mixed, Rejected),
+    case!("diagnostics/0104_invalid_type_in_arg", Mixed, Rejected),
+    case!("diagnostics/0105_executable_definition_in_type_system_document", TypeSystem, Rejected),
+    case!("diagnostics/0106_type_system_definition_in_executable_document", Executable, Rejected),
+    case!("diagnostics/0107_built_in_directive_double_redefinition", Mixed, Rejected),
+    case!("diagnostics/0108_implicit_schema_extension", Mixed, Rejected),
+    case!("diagnostics/0109_null_in_list_issue_738", Mixed, Rejected),
+    case!("diagnostics/0110_list_usage_in_non_list_type", Mixed, Rejected),
+    case!("diagnostics/0111_const_value", Mixed, Rejected),
+    case!("diagnostics/0112_anonymous_subscription_with_multiple_root_fields", Mixed, Rejected),
+    case!("diagnostics/0113_partially_overlapping_fragments", Mixed, Rejected),
+    case!("diagnostics/0114_interface_definition_with_missing_fields", Mixed, Rejected),
+    case!("diagnostics/0115_object_definition_with_missing_fields", Mixed, Rejected),
+    case!("diagnostics/0116_enum_definition_with_missing_values", Mixed, Rejected),
+    case!("diagnostics/0117_union_definition_with_missing_members", Mixed, Rejected),
+    case!("diagnostics/0118_input_object_definition_with_missing_values", Mixed, Rejected),
+    case!("diagnostics/0119_reserved_names", Mixed, Rejected),
+];
@@ -0,0 +1,137 @@
+//! The validation and serialization test corpus of apollo-compiler,
+//! with the expected outcome of each case.
+//!
+//! This lets other GraphQL implementations and tools built on apollo-compiler
+//! (bindings, alternate validators or serializers, …)
+//! run the same conformance checks without copying test files.
+//! Enabled by the `conformance` Cargo feature,
+//! which also enables the lexer and parser corpus in [`apollo_parser::conformance`].
+//!
+//! ## Example
+//!
+//! A serializer other than [`ast::Document`]’s `Display` implementation
+//! must produce documents that parse back to the same AST:
+//!
+//! ```
+//! use apollo_compiler::conformance;
+//!
+//! let mismatches = conformance::round_trip(|document| {
+//!     document.serialize().indent_prefix("\t").to_string()
+//! });
+//! assert!(mismatches.is_empty(), "{mismatches:?}");
+//! ```
+
+use crate::ast;
+use std::fmt;
+
+// Generated, kept on one line per case
+#[rustfmt::skip]
+mod corpus;
+
+pub use apollo_parser::conformance::Expected;
+
+/// A test case of the conformance suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Case {
+    /// Path of the test file in `apollo-compiler/test_data` without extension,
+    /// such as `ok/0001_annonymous_operation_definition`
+    pub name: &'static str,
+    /// GraphQL source text
+    pub input: &'static str,
+    /// How `input` is validated
+    pub mode: Mode,
+    /// Whether a conforming implementation finds `input` valid in this `mode`
+    pub expected: Expected,
+    /// `input` parsed as an [`ast::Document`] (keeping partial results of syntax errors)
+    /// and serialized with its `Display` implementation
+    pub serialized: &'static str,
+}
+
+/// How the input of a test case is validated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// As a schema, like [`Schema::parse_and_validate`][crate::Schema::parse_and_validate]
+    TypeSystem,
+    /// As an executable document without a schema, like
+    /// [`ast::Document::validate_standalone_executable`]
+    Executable,
+    /// As a schema together with executable definitions against that schema, like
+    /// [`parse_mixed_validate`][crate::parse_mixed_validate]
+    Mixed,
+}
+
+/// A test case whose validation outcome differs from the expected one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mismatch {
+    /// The test case
+    pub case: &'static Case,
+    /// The outcome returned by the implementation under test
+    pub actual: Expected,
+}
+
+/// A test case whose serialization does not parse back to the same AST
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundTripMismatch {
+    /// The test case
+    pub case: &'static Case,
+    /// Output of the serializer under test
+    pub serialized: String,
+}
+
+/// Test cases for validation: `Accepted` when there are no syntax errors
+/// and no validation errors
+pub fn validation_cases() -> &'static [Case] {
+    corpus::VALIDATION
+}
+
+/// Runs `check` on each validation case,
+/// and returns the cases for which it does not return the expected outcome.
+pub fn run(mut check: impl FnMut(&Case) -> Expected) -> Vec<Mismatch> {
+    validation_cases()
+        .iter()
+        .filter_map(|case| {
+            let actual = check(case);
+            (actual != case.expected).then_some(Mismatch { case, actual })
+        })
+        .collect()
+}
+
+/// Parses the input of each validation case, serializes it with `serialize`, and parses it again.
+/// Returns the cases for which the two ASTs differ.
+///
+/// Comments are part of the AST, so serializers must keep them.
+/// Syntax errors are ignored: the partial AST of a document with errors is used.
+pub fn round_trip(mut serialize: impl FnMut(&ast::Document) -> String) -> Vec<RoundTripMismatch> {
+    validation_cases()
+        .iter()
+        .filter_map(|case| {
+            let original = ast::Document::parse(case.input, case.name)
+                .unwrap_or_else(|invalid| invalid.partial);
+            let serialized = serialize(&original);
+            let round_tripped = ast::Document::parse(&serialized, case.name)
+                .unwrap_or_else(|invalid| invalid.partial);
+            (round_tripped != original).then_some(RoundTripMismatch { case, serialized })
+        })
+        .collect()
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {:?}, got {:?}",
+            self.case.name, self.case.expected, self.actual
+        )
+    }
+}
+
+impl fmt::Display for RoundTripMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: serialization does not round-trip:\n{}",
+            self.case.name, self.serialized
+        )
+    }
+}
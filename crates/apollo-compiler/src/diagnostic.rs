@@ -67,6 +67,7 @@
 //!     }
 //! }
 //! ```
+use crate::collections::HashMap;
 use crate::execution::GraphQLError;
 use crate::parser::FileId;
 use crate::parser::LineColumn;
@@ -113,6 +114,7 @@ pub struct CliReport<'s> {
     sources: &'s SourceMap,
     colors: ColorGenerator,
     report: ariadne::ReportBuilder<'static, AriadneSpan>,
+    label_locations: Vec<SourceSpan>,
 }
 
 /// Indicate when to use ANSI colors for printing.
@@ -221,6 +223,7 @@ impl<'s> CliReport<'s> {
             sources,
             colors: ColorGenerator::new(),
             report: report.with_config(config),
+            label_locations: Vec::new(),
         }
     }
 
@@ -242,6 +245,7 @@ impl<'s> CliReport<'s> {
 
     /// Add a label at a given location. If the location is `None`, the message is discarded.
     pub fn with_label_opt(&mut self, location: Option<SourceSpan>, message: impl ToString) {
+        self.label_locations.extend(location);
         if let Some(span) = location.and_then(to_span) {
             self.report.add_label(
                 ariadne::Label::new(span)
@@ -251,12 +255,17 @@ impl<'s> CliReport<'s> {
         }
     }
 
+    /// Returns the locations of labels added so far, in the order they were added
+    pub fn label_locations(&self) -> &[SourceSpan] {
+        &self.label_locations
+    }
+
     /// Write the report to a [`Write`].
     ///
     /// [`Write`]: std::io::Write
     pub fn write(self, w: impl std::io::Write) -> std::io::Result<()> {
         let report = self.report.finish();
-        report.write(Cache(self.sources), w)
+        report.write(Cache::new(self.sources), w)
     }
 
     /// Write the report to a [`fmt::Formatter`].
@@ -278,11 +287,19 @@ impl<'s> CliReport<'s> {
     }
 }
 
-struct Cache<'a>(&'a SourceMap);
+/// Text of detached source files is loaded for the duration of one report
+struct Cache<'a>(&'a SourceMap, HashMap<FileId, ariadne::Source>);
+
+impl<'a> Cache<'a> {
+    fn new(sources: &'a SourceMap) -> Self {
+        Self(sources, HashMap::default())
+    }
+}
 
 impl ariadne::Cache<FileId> for Cache<'_> {
     type Storage = String;
 
+    #[allow(refining_impl_trait)]
     fn fetch(&mut self, file_id: &FileId) -> Result<&ariadne::Source, Box<dyn fmt::Debug + '_>> {
         struct NotFound(FileId);
         impl fmt::Debug for NotFound {
@@ -291,7 +308,13 @@ impl ariadne::Cache<FileId> for Cache<'_> {
             }
         }
         if let Some(source_file) = self.0.get(file_id) {
-            Ok(source_file.ariadne())
+            if let Some(source) = source_file.ariadne() {
+                return Ok(source);
+            }
+            Ok(self
+                .1
+                .entry(*file_id)
+                .or_insert_with(|| source_file.new_ariadne(source_file.source_text().into_owned())))
         } else if *file_id == FileId::NONE {
             static EMPTY: OnceLock<ariadne::Source> = OnceLock::new();
             Ok(EMPTY.get_or_init(|| ariadne::Source::from(String::new())))
@@ -300,6 +323,7 @@ impl ariadne::Cache<FileId> for Cache<'_> {
         }
     }
 
+    #[allow(refining_impl_trait)]
     fn display<'a>(&self, file_id: &'a FileId) -> Option<Box<dyn fmt::Display + 'a>> {
         if *file_id != FileId::NONE {
             struct Path(Arc<SourceFile>);
@@ -330,6 +354,22 @@ impl<T: ToCliReport> Diagnostic<'_, T> {
         self.error.location()?.line_column_range(self.sources)
     }
 
+    /// Returns every location this diagnostic refers to:
+    /// its main location first, then the locations of its labels without duplicates.
+    ///
+    /// For example, a name collision has the location of the duplicate definition
+    /// followed by the location of the previous definition, possibly in another file.
+    pub fn locations(&self) -> Vec<SourceSpan> {
+        let report = self.to_report(Color::Never);
+        let mut locations: Vec<SourceSpan> = self.error.location().into_iter().collect();
+        for location in report.label_locations() {
+            if !locations.contains(location) {
+                locations.push(*location)
+            }
+        }
+        locations
+    }
+
     /// Get a [`serde`]-serializable version of the current diagnostic. The shape is compatible
     /// with the JSON error shape described in [the GraphQL spec].
     ///
@@ -347,6 +387,12 @@ impl<T: ToCliReport> Diagnostic<'_, T> {
     }
 }
 
+impl<T: ToCliReport + ToString> From<Diagnostic<'_, T>> for GraphQLError {
+    fn from(diagnostic: Diagnostic<'_, T>) -> Self {
+        diagnostic.to_json()
+    }
+}
+
 impl<T: ToCliReport> fmt::Debug for Diagnostic<'_, T> {
     /// Pretty-format the diagnostic, with colors for the CLI.
     ///
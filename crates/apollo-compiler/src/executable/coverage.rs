@@ -0,0 +1,216 @@
+use crate::collections::IndexMap;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::parser::SourceSpan;
+use crate::ExecutableDocument;
+use crate::Schema;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// How often each field and field argument of a schema is used by a set of executable documents,
+/// such as the operations of a test suite, to measure API test coverage.
+///
+/// Counts are static: each field selection and each argument in a document counts once,
+/// regardless of how many operations use the fragment it is in.
+/// A field selected through an interface counts for the interface field,
+/// not for the fields of implementing object types.
+/// Built-in types, such as introspection types, are not included.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::executable::SchemaCoverage;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { user(id: ID!, fresh: Boolean): User }
+///     type User { name: String email: String }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "{ user(id: 1) { name } }",
+///     "test.graphql",
+/// ).unwrap();
+///
+/// let mut coverage = SchemaCoverage::new(&schema);
+/// coverage.add_document(&document);
+/// assert_eq!((coverage.covered(), coverage.total()), (3, 5));
+/// let uncovered: Vec<_> = coverage.uncovered().map(|c| c.to_string()).collect();
+/// assert_eq!(uncovered, ["Query.user(fresh:)", "User.email"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaCoverage {
+    /// Use counts of field and field argument coordinates, in the order of
+    /// [`Schema::coordinates`], with the location of their definition
+    counts: IndexMap<SchemaCoordinate, (usize, Option<SourceSpan>)>,
+    sources: crate::parser::SourceMap,
+}
+
+impl SchemaCoverage {
+    /// Start measuring coverage of every field and field argument of `schema`
+    pub fn new(schema: &Schema) -> Self {
+        let counts = schema
+            .coordinates()
+            .filter_map(|(coordinate, definition)| {
+                let location = match (&coordinate, definition) {
+                    (SchemaCoordinate::TypeAttribute(_), SchemaCoordinateLookup::Field(def)) => {
+                        def.location()
+                    }
+                    (SchemaCoordinate::FieldArgument(_), SchemaCoordinateLookup::Argument(def)) => {
+                        def.location()
+                    }
+                    // Types, input fields, enum values, and directives
+                    _ => return None,
+                };
+                Some((coordinate, (0, location)))
+            })
+            .collect();
+        Self {
+            counts,
+            sources: schema.sources.clone(),
+        }
+    }
+
+    /// Count the fields and arguments used by operations and fragments of `document`
+    pub fn add_document(&mut self, document: &ExecutableDocument) {
+        for operation in document.operations.iter() {
+            self.add_selection_set(&operation.selection_set)
+        }
+        for fragment in document.fragments.values() {
+            self.add_selection_set(&fragment.selection_set)
+        }
+    }
+
+    fn add_selection_set(&mut self, selection_set: &SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    let coordinate = TypeAttributeCoordinate {
+                        ty: selection_set.ty.clone(),
+                        attribute: field.name.clone(),
+                    };
+                    self.increment(coordinate.into());
+                    for argument in &field.arguments {
+                        let coordinate = FieldArgumentCoordinate {
+                            ty: selection_set.ty.clone(),
+                            field: field.name.clone(),
+                            argument: argument.name.clone(),
+                        };
+                        self.increment(coordinate.into());
+                    }
+                    self.add_selection_set(&field.selection_set)
+                }
+                Selection::InlineFragment(inline) => self.add_selection_set(&inline.selection_set),
+                // The fragment definition is counted separately
+                Selection::FragmentSpread(_) | Selection::Invalid(_) => {}
+            }
+        }
+    }
+
+    fn increment(&mut self, coordinate: SchemaCoordinate) {
+        // Meta-fields like `__typename` are not included
+        if let Some((count, _)) = self.counts.get_mut(&coordinate) {
+            *count += 1
+        }
+    }
+
+    /// Returns each field and field argument coordinate with how often it is used
+    pub fn counts(&self) -> impl Iterator<Item = (&SchemaCoordinate, usize)> {
+        self.counts
+            .iter()
+            .map(|(coordinate, (count, _))| (coordinate, *count))
+    }
+
+    /// Returns the number of coordinates used at least once
+    pub fn covered(&self) -> usize {
+        self.counts().filter(|(_, count)| *count > 0).count()
+    }
+
+    /// Returns the number of field and field argument coordinates in the schema
+    pub fn total(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the coordinates that are never used
+    pub fn uncovered(&self) -> impl Iterator<Item = &SchemaCoordinate> {
+        self.counts()
+            .filter(|(_, count)| *count == 0)
+            .map(|(coordinate, _)| coordinate)
+    }
+
+    /// Returns a JSON report with totals and the count for each coordinate:
+    ///
+    /// ```json
+    /// {"covered": 3, "total": 5, "coordinates": {"Query.user": 1, "User.email": 0}}
+    /// ```
+    pub fn to_json(&self) -> JsonValue {
+        let coordinates: JsonMap = self
+            .counts()
+            .map(|(coordinate, count)| (coordinate.to_string().into(), count.into()))
+            .collect();
+        let mut report = JsonMap::new();
+        report.insert("covered", self.covered().into());
+        report.insert("total", self.total().into());
+        report.insert("coordinates", coordinates.into());
+        report.into()
+    }
+
+    /// Returns a report in the [lcov] tracefile format used by code coverage tools,
+    /// with one record per schema source file.
+    ///
+    /// Each coordinate is reported as a function at the line of its definition.
+    /// Each line with definitions is reported with the smallest count among them,
+    /// so a line is covered only if everything defined on it is.
+    /// Definitions without a source location, such as those added programmatically,
+    /// are not reported.
+    ///
+    /// [lcov]: https://manpages.debian.org/unstable/lcov/geninfo.1.en.html#TRACEFILE_FORMAT
+    pub fn to_lcov(&self) -> String {
+        // File path => (coordinates with their line and count, line => count)
+        #[allow(clippy::type_complexity)]
+        let mut files: IndexMap<_, (Vec<(String, usize, usize)>, BTreeMap<usize, usize>)> =
+            IndexMap::default();
+        for (coordinate, (count, location)) in &self.counts {
+            let Some(location) = location else { continue };
+            let (Some(file), Some(line_column)) = (
+                self.sources.get(&location.file_id()),
+                location.line_column(&self.sources),
+            ) else {
+                continue;
+            };
+            let (functions, lines) = files.entry(file.path()).or_default();
+            functions.push((coordinate.to_string(), line_column.line, *count));
+            let line_count = lines.entry(line_column.line).or_insert(*count);
+            *line_count = (*line_count).min(*count);
+        }
+        let mut lcov = String::new();
+        for (path, (functions, lines)) in files {
+            writeln!(lcov, "TN:").unwrap();
+            writeln!(lcov, "SF:{}", path.display()).unwrap();
+            for (name, line, _) in &functions {
+                writeln!(lcov, "FN:{line},{name}").unwrap();
+            }
+            for (name, _, count) in &functions {
+                writeln!(lcov, "FNDA:{count},{name}").unwrap();
+            }
+            let functions_hit = functions.iter().filter(|(_, _, count)| *count > 0).count();
+            writeln!(lcov, "FNF:{}", functions.len()).unwrap();
+            writeln!(lcov, "FNH:{functions_hit}").unwrap();
+            for (line, count) in &lines {
+                writeln!(lcov, "DA:{line},{count}").unwrap();
+            }
+            let lines_hit = lines.values().filter(|count| **count > 0).count();
+            writeln!(lcov, "LF:{}", lines.len()).unwrap();
+            writeln!(lcov, "LH:{lines_hit}").unwrap();
+            writeln!(lcov, "end_of_record").unwrap();
+        }
+        lcov
+    }
+}
@@ -0,0 +1,97 @@
+use crate::ast::DirectiveList;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::ExecutableDocument;
+use crate::Schema;
+
+impl ExecutableDocument {
+    /// Add arguments that are not specified but have a default value in the schema
+    /// to every field selection and directive application of this document,
+    /// so that their [`arguments`][crate::executable::Field::arguments]
+    /// are the effective argument list.
+    ///
+    /// Added arguments come after those specified, in definition order.
+    /// Field arguments use the field’s [`definition`][crate::executable::Field::definition],
+    /// and directive arguments use `schema`’s directive definitions.
+    /// Directives not defined in `schema` are left unchanged.
+    ///
+    /// This can be done as part of parsing with
+    /// [`Parser::materialize_default_arguments`][crate::parser::Parser::materialize_default_arguments].
+    /// To get the effective arguments of one field or directive without changing the document,
+    /// use [`Field::arguments_with_defaults`][crate::executable::Field::arguments_with_defaults]
+    /// or [`Directive::arguments_with_defaults`][crate::executable::Directive::arguments_with_defaults].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(r#"
+    ///     type Query { users(first: Int = 10, after: String): [String] @deprecated }
+    /// "#, "schema.graphql").unwrap();
+    /// let mut document = ExecutableDocument::parse_and_validate(
+    ///     &schema, "{ users(after: \"x\") }", "query.graphql",
+    /// ).unwrap().into_inner();
+    /// document.materialize_default_arguments(&schema);
+    /// assert_eq!(
+    ///     document.serialize().no_indent().to_string(),
+    ///     r#"{ users(after: "x", first: 10) }"#,
+    /// );
+    /// ```
+    pub fn materialize_default_arguments(&mut self, schema: &Schema) {
+        for operation in self
+            .operations
+            .anonymous
+            .iter_mut()
+            .chain(self.operations.named.values_mut())
+        {
+            let operation = operation.make_mut();
+            for variable in &mut operation.variables {
+                if !variable.directives.is_empty() {
+                    directives(schema, &mut variable.make_mut().directives)
+                }
+            }
+            directives(schema, &mut operation.directives);
+            selection_set(schema, &mut operation.selection_set)
+        }
+        for fragment in self.fragments.values_mut() {
+            let fragment = fragment.make_mut();
+            directives(schema, &mut fragment.directives);
+            selection_set(schema, &mut fragment.selection_set)
+        }
+    }
+}
+
+fn selection_set(schema: &Schema, set: &mut SelectionSet) {
+    for selection in &mut set.selections {
+        match selection {
+            Selection::Field(field) => {
+                let field = field.make_mut();
+                field.arguments = field.arguments_with_defaults();
+                directives(schema, &mut field.directives);
+                selection_set(schema, &mut field.selection_set)
+            }
+            Selection::FragmentSpread(spread) => {
+                if !spread.directives.is_empty() {
+                    directives(schema, &mut spread.make_mut().directives)
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                let inline = inline.make_mut();
+                directives(schema, &mut inline.directives);
+                selection_set(schema, &mut inline.selection_set)
+            }
+            Selection::Invalid(_) => {}
+        }
+    }
+}
+
+fn directives(schema: &Schema, directives: &mut DirectiveList) {
+    for directive in directives.iter_mut() {
+        let arguments = directive.arguments_with_defaults(schema);
+        if arguments.len() != directive.arguments.len() {
+            directive.make_mut().arguments = arguments
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::collections::IndexSet;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::parser::SourceSpan;
+use crate::schema::ExtendedType;
+use crate::schema::NamedType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Schema;
+
+/// A selection on a field of interface or union type that has type conditions
+/// for some, but not all, of the object types it may return.
+///
+/// Returned by [`check_exhaustiveness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonExhaustiveSelection {
+    /// The schema coordinate of the selected field, such as `Query.search`
+    pub coordinate: TypeAttributeCoordinate,
+    /// The location of the field selection in the executable document
+    pub location: Option<SourceSpan>,
+    /// The interface or union type of the field
+    pub abstract_type: NamedType,
+    /// Possible object types of the field that no type condition applies to,
+    /// in schema order
+    pub missing_types: Vec<NamedType>,
+}
+
+/// Find selections of abstract-typed fields that do not handle every possible runtime type.
+///
+/// For each field selection of interface or union type, this looks at the inline fragments
+/// and fragment spreads in its selection set. Those whose type condition is
+/// the field’s own type (or that have no type condition) select fields shared by all
+/// possible types, so they are looked into but do not cover any type by themselves.
+/// Other type conditions cover every object type they apply to.
+/// Selections with at least one such type condition are reported
+/// if some possible object types of the field are still not covered.
+/// Selections with no type conditions at all handle every type the same way
+/// and are not reported.
+///
+/// Each field selection is analyzed once, in operations or in the fragment definition
+/// it is written in, so fields in a fragment used many times are reported once.
+/// `@skip` and `@include` directives are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::executable::check_exhaustiveness;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { search: [Result] }
+///     union Result = User | Post | Comment
+///     type User { name: String }
+///     type Post { title: String }
+///     type Comment { text: String }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(&schema, r#"
+///     { search { ... on User { name } ... on Post { title } } }
+/// "#, "query.graphql").unwrap();
+///
+/// let report = check_exhaustiveness(&schema, &document);
+/// assert_eq!(report.len(), 1);
+/// assert_eq!(report[0].coordinate.to_string(), "Query.search");
+/// assert_eq!(report[0].missing_types, ["Comment"]);
+/// ```
+pub fn check_exhaustiveness(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+) -> Vec<NonExhaustiveSelection> {
+    let mut report = Vec::new();
+    let mut stack: Vec<&SelectionSet> = document
+        .operations
+        .iter()
+        .map(|operation| &operation.selection_set)
+        .chain(
+            document
+                .fragments
+                .values()
+                .map(|fragment| &fragment.selection_set),
+        )
+        .collect();
+    while let Some(selection_set) = stack.pop() {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    if field.selection_set.is_empty() {
+                        continue;
+                    }
+                    stack.push(&field.selection_set);
+                    let abstract_type = field.ty().inner_named_type();
+                    if !is_abstract(schema, abstract_type) {
+                        continue;
+                    }
+                    let mut coverage = Coverage::default();
+                    coverage.visit(schema, document, abstract_type, &field.selection_set);
+                    if !coverage.narrowed {
+                        continue;
+                    }
+                    let missing_types: Vec<_> = possible_types(schema, abstract_type)
+                        .filter(|ty| !coverage.covered.contains(*ty))
+                        .cloned()
+                        .collect();
+                    if !missing_types.is_empty() {
+                        report.push(NonExhaustiveSelection {
+                            coordinate: TypeAttributeCoordinate {
+                                ty: selection_set.ty.clone(),
+                                attribute: field.name.clone(),
+                            },
+                            location: field.location(),
+                            abstract_type: abstract_type.clone(),
+                            missing_types,
+                        })
+                    }
+                }
+                Selection::InlineFragment(inline) => stack.push(&inline.selection_set),
+                // Fields in fragment definitions are analyzed separately
+                Selection::FragmentSpread(_) => {}
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+    report.sort_by_key(|item| item.location.map(|loc| (loc.file_id(), loc.offset())));
+    report
+}
+
+#[derive(Default)]
+struct Coverage<'a> {
+    covered: IndexSet<&'a NamedType>,
+    /// Whether any type condition narrows down the abstract type
+    narrowed: bool,
+}
+
+impl<'a> Coverage<'a> {
+    fn visit(
+        &mut self,
+        schema: &'a Schema,
+        document: &'a ExecutableDocument,
+        abstract_type: &NamedType,
+        selection_set: &'a SelectionSet,
+    ) {
+        for selection in &selection_set.selections {
+            let (type_condition, nested) = match selection {
+                Selection::Field(_) | Selection::Invalid(_) => continue,
+                Selection::InlineFragment(inline) => {
+                    (inline.type_condition.as_ref(), &inline.selection_set)
+                }
+                Selection::FragmentSpread(spread) => {
+                    // Validation ensures that `Valid<ExecutableDocument>` does not contain
+                    // fragment cycles nor undefined fragments
+                    let Some(def) = document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    (Some(def.type_condition()), &def.selection_set)
+                }
+            };
+            match type_condition {
+                Some(type_condition) if type_condition != abstract_type => {
+                    self.narrowed = true;
+                    self.covered.extend(
+                        possible_types(schema, type_condition)
+                            .filter(|ty| schema.is_subtype(abstract_type, ty)),
+                    )
+                }
+                _ => self.visit(schema, document, abstract_type, nested),
+            }
+        }
+    }
+}
+
+fn is_abstract(schema: &Schema, name: &str) -> bool {
+    matches!(
+        schema.types.get(name),
+        Some(ExtendedType::Interface(_) | ExtendedType::Union(_))
+    )
+}
+
+/// Object types that `ty` applies to, in schema order
+fn possible_types<'a>(
+    schema: &'a Schema,
+    ty: &'a NamedType,
+) -> impl Iterator<Item = &'a NamedType> {
+    schema.types.iter().filter_map(move |(name, def)| {
+        (def.is_object() && (name == ty || schema.is_subtype(ty, name))).then_some(name)
+    })
+}
@@ -0,0 +1,227 @@
+use crate::collections::IndexMap;
+use crate::executable::DirectiveList;
+use crate::executable::Fragment;
+use crate::executable::FragmentSpread;
+use crate::executable::NamedType;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+
+/// Options for [`extract_fragments`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FragmentExtraction {
+    /// The minimum number of selections in a repeated selection set to extract it,
+    /// counting nested selections. Defaults to 3.
+    pub min_selections: usize,
+    /// The minimum number of times a selection set must be repeated to extract it.
+    /// Defaults to 2.
+    pub min_occurrences: usize,
+}
+
+impl Default for FragmentExtraction {
+    fn default() -> Self {
+        Self {
+            min_selections: 3,
+            min_occurrences: 2,
+        }
+    }
+}
+
+/// Factor selection sets that are repeated in `document` into new named fragments,
+/// the inverse of inlining fragments.
+///
+/// This shrinks machine-generated operations, such as from query planning or code generation,
+/// and makes them easier to read. Selection sets of fields and inline fragments are candidates:
+/// two selection sets are identical if they have the same type and serialize the same.
+/// The repeated selection set that saves the most selections is extracted first,
+/// until none is repeated [enough][FragmentExtraction::min_occurrences].
+///
+/// New fragments are named after their type condition, like `UserFields`,
+/// with a number appended if that name is taken.
+/// An inline fragment without directives whose selections are extracted
+/// is replaced by a spread of the new fragment.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::executable::extract_fragments;
+/// use apollo_compiler::executable::FragmentExtraction;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { me: User, friends: [User] }
+///      type User { id: ID!, name: String, avatar(size: Int): String }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let source = "{
+///   me { id name avatar(size: 64) }
+///   friends { id name avatar(size: 64) }
+/// }";
+/// let document = ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+///
+/// let extracted = extract_fragments(&document, &FragmentExtraction::default());
+/// let expected = "\
+/// {
+///   me {
+///     ...UserFields
+///   }
+///   friends {
+///     ...UserFields
+///   }
+/// }
+///
+/// fragment UserFields on User {
+///   id
+///   name
+///   avatar(size: 64)
+/// }
+/// ";
+/// assert_eq!(extracted.to_string(), expected);
+/// extracted.validate(&schema).unwrap();
+/// ```
+pub fn extract_fragments(
+    document: &ExecutableDocument,
+    options: &FragmentExtraction,
+) -> ExecutableDocument {
+    let mut document = document.clone();
+    loop {
+        let mut candidates = IndexMap::default();
+        for operation in document.operations.iter() {
+            count(&operation.selection_set, &mut candidates);
+        }
+        for fragment in document.fragments.values() {
+            count(&fragment.selection_set, &mut candidates);
+        }
+        let best = candidates
+            .into_values()
+            .filter(|candidate| {
+                candidate.occurrences >= options.min_occurrences.max(2)
+                    && candidate.size >= options.min_selections.max(1)
+            })
+            // Prefer the first of equal candidates
+            .rev()
+            .max_by_key(|candidate| (candidate.occurrences - 1) * candidate.size);
+        let Some(best) = best else {
+            return document;
+        };
+
+        let name = fragment_name(&document, &best.selection_set.ty);
+        let key = Key::new(&best.selection_set);
+        let operations = &mut document.operations;
+        for operation in operations
+            .anonymous
+            .iter_mut()
+            .chain(operations.named.values_mut())
+        {
+            replace(&mut operation.make_mut().selection_set, &key, &name)
+        }
+        for fragment in document.fragments.values_mut() {
+            replace(&mut fragment.make_mut().selection_set, &key, &name)
+        }
+        document.fragments.insert(
+            name.clone(),
+            Node::new(Fragment {
+                name,
+                directives: DirectiveList::new(),
+                selection_set: best.selection_set,
+            }),
+        );
+    }
+}
+
+/// A selection set that occurs in a document
+struct Candidate {
+    selection_set: SelectionSet,
+    occurrences: usize,
+    /// The number of selections, including nested ones
+    size: usize,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct Key {
+    ty: NamedType,
+    serialized: String,
+}
+
+impl Key {
+    fn new(selection_set: &SelectionSet) -> Self {
+        Self {
+            ty: selection_set.ty.clone(),
+            serialized: selection_set.serialize().no_indent().to_string(),
+        }
+    }
+}
+
+/// Counts occurrences of the selection sets nested in `selection_set`, and returns its size
+fn count(selection_set: &SelectionSet, candidates: &mut IndexMap<Key, Candidate>) -> usize {
+    let mut size = 0;
+    for selection in &selection_set.selections {
+        size += 1;
+        let nested = match selection {
+            Selection::Field(field) => &field.selection_set,
+            Selection::InlineFragment(inline) => &inline.selection_set,
+            Selection::FragmentSpread(_) | Selection::Invalid(_) => continue,
+        };
+        if nested.is_empty() {
+            continue;
+        }
+        let nested_size = count(nested, candidates);
+        size += nested_size;
+        candidates
+            .entry(Key::new(nested))
+            .or_insert_with(|| Candidate {
+                selection_set: nested.clone(),
+                occurrences: 0,
+                size: nested_size,
+            })
+            .occurrences += 1;
+    }
+    size
+}
+
+/// Replaces the selection sets nested in `selection_set` that match `key`
+/// with a spread of the fragment named `name`
+fn replace(selection_set: &mut SelectionSet, key: &Key, name: &Name) {
+    for selection in &mut selection_set.selections {
+        let nested = match selection {
+            Selection::Field(field) if !field.selection_set.is_empty() => {
+                &mut field.make_mut().selection_set
+            }
+            Selection::InlineFragment(inline) => {
+                if inline.directives.is_empty()
+                    && inline.type_condition.is_some()
+                    && Key::new(&inline.selection_set) == *key
+                {
+                    *selection = FragmentSpread::new(name.clone()).into();
+                    continue;
+                }
+                &mut inline.make_mut().selection_set
+            }
+            _ => continue,
+        };
+        if Key::new(nested) == *key {
+            nested.selections = vec![FragmentSpread::new(name.clone()).into()];
+        } else {
+            replace(nested, key, name)
+        }
+    }
+}
+
+/// Returns an unused fragment name for type `ty`
+fn fragment_name(document: &ExecutableDocument, ty: &NamedType) -> Name {
+    let base = format!("{ty}Fields");
+    (1..)
+        .map(|i| {
+            if i == 1 {
+                base.clone()
+            } else {
+                format!("{base}{i}")
+            }
+        })
+        .find(|name| !document.fragments.contains_key(name.as_str()))
+        .and_then(|name| Name::new(&name).ok())
+        .expect("a fragment name based on a type name is valid")
+}
@@ -4,6 +4,7 @@ use crate::ty;
 pub(crate) struct BuildErrors<'a> {
     pub(crate) errors: &'a mut DiagnosticList,
     pub(crate) path: SelectionPath,
+    pub(crate) keep_invalid_selections: bool,
 }
 
 pub(crate) fn document_from_ast(
@@ -11,6 +12,7 @@ pub(crate) fn document_from_ast(
     document: &ast::Document,
     errors: &mut DiagnosticList,
     type_system_definitions_are_errors: bool,
+    keep_invalid_selections: bool,
 ) -> ExecutableDocument {
     let mut operations = OperationMap::default();
     let mut multiple_anonymous = false;
@@ -22,6 +24,7 @@ pub(crate) fn document_from_ast(
             // overwritten:
             root: ExecutableDefinitionName::AnonymousOperation(ast::OperationType::Query),
         },
+        keep_invalid_selections,
     };
     for definition in &document.definitions {
         debug_assert!(errors.path.nested_fields.is_empty());
@@ -45,6 +48,7 @@ pub(crate) fn document_from_ast(
                             errors.errors.push(
                                 operation.location(),
                                 BuildError::UndefinedRootOperation {
+                                    name: Some(name.clone()),
                                     operation_type: operation.operation_type.name(),
                                 },
                             )
@@ -83,6 +87,7 @@ pub(crate) fn document_from_ast(
                         errors.errors.push(
                             operation.location(),
                             BuildError::UndefinedRootOperation {
+                                name: None,
                                 operation_type: operation.operation_type.name(),
                             },
                         )
@@ -192,6 +197,7 @@ impl SelectionSet {
                     } else {
                         Ok(Node::new(ast::FieldDefinition {
                             description: None,
+                            leading_comments: Vec::new(),
                             name: ast.name.clone(),
                             arguments: Vec::new(),
                             ty: ty!(UNKNOWN),
@@ -210,16 +216,19 @@ impl SelectionSet {
                                 .as_ref()
                                 .and_then(|schema| schema.types.get(type_name))
                             {
-                                Some(schema::ExtendedType::Scalar(_)) if !leaf => {
-                                    errors.errors.push(
+                                Some(schema::ExtendedType::Scalar(_)) if !leaf => self
+                                    .push_invalid(
+                                        errors,
+                                        selection,
                                         ast.location(),
                                         BuildError::SubselectionOnScalarType {
                                             type_name: type_name.clone(),
                                             path: errors.path.clone(),
                                         },
-                                    )
-                                }
-                                Some(schema::ExtendedType::Enum(_)) if !leaf => errors.errors.push(
+                                    ),
+                                Some(schema::ExtendedType::Enum(_)) if !leaf => self.push_invalid(
+                                    errors,
+                                    selection,
                                     ast.location(),
                                     BuildError::SubselectionOnEnumType {
                                         type_name: type_name.clone(),
@@ -231,6 +240,7 @@ impl SelectionSet {
                                         Field::new(ast.name.clone(), field_def)
                                             .with_opt_alias(ast.alias.clone())
                                             .with_arguments(ast.arguments.iter().cloned())
+                                            .with_ast_nullability(ast)
                                             .with_directives(ast.directives.iter().cloned())
                                             .with_ast_selections(
                                                 schema,
@@ -241,16 +251,17 @@ impl SelectionSet {
                                 ),
                             }
                         }
-                        Err(schema::FieldLookupError::NoSuchField(type_name, _)) => {
-                            errors.errors.push(
+                        Err(schema::FieldLookupError::NoSuchField(type_name, _)) => self
+                            .push_invalid(
+                                errors,
+                                selection,
                                 ast.name.location(),
                                 BuildError::UndefinedField {
                                     type_name: type_name.clone(),
                                     field_name: ast.name.clone(),
                                     path: errors.path.clone(),
                                 },
-                            )
-                        }
+                            ),
                         Err(schema::FieldLookupError::NoSuchType) => {
                             // `self.ty` is the name of a type not definied in the schema.
                             // It can come from:
@@ -276,7 +287,9 @@ impl SelectionSet {
                         (Some(type_condition), Some(schema))
                             if !schema.types.contains_key(type_condition) =>
                         {
-                            errors.errors.push(
+                            self.push_invalid(
+                                errors,
+                                selection,
                                 type_condition.location(),
                                 BuildError::UndefinedTypeInInlineFragmentTypeCondition {
                                     type_name: type_condition.clone(),
@@ -296,9 +309,42 @@ impl SelectionSet {
             }
         }
     }
+
+    /// Record `error`, or if configured to keep `selection` as [`Selection::Invalid`]
+    /// for validation to record it instead
+    fn push_invalid(
+        &mut self,
+        errors: &mut BuildErrors,
+        selection: &ast::Selection,
+        location: Option<SourceSpan>,
+        error: BuildError,
+    ) {
+        if errors.keep_invalid_selections {
+            let invalid = InvalidSelection {
+                ast: selection.clone(),
+                error,
+                error_location: location,
+            };
+            self.push(Node::new_opt_location(invalid, selection.location()))
+        } else {
+            errors.errors.push(location, error)
+        }
+    }
 }
 
 impl Field {
+    #[cfg_attr(
+        not(feature = "experimental-client-controlled-nullability"),
+        allow(unused_mut, unused_variables)
+    )]
+    fn with_ast_nullability(mut self, ast: &ast::Field) -> Self {
+        #[cfg(feature = "experimental-client-controlled-nullability")]
+        {
+            self.nullability = ast.nullability.clone();
+        }
+        self
+    }
+
     fn with_ast_selections(
         mut self,
         schema: Option<&Schema>,
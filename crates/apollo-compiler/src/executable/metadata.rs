@@ -0,0 +1,207 @@
+use crate::collections::HashSet;
+use crate::collections::IndexSet;
+use crate::executable::DirectiveList;
+use crate::executable::Operation;
+use crate::executable::OperationType;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::schema::NamedType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A compact summary of an operation, returned by [`metadata`].
+///
+/// Serializes to JSON such as:
+///
+/// ```json
+/// {
+///   "operationType": "query",
+///   "name": "GetUser",
+///   "rootFields": ["user"],
+///   "referencedTypes": ["ID", "Query", "User"],
+///   "directives": ["include"],
+///   "depth": 2,
+///   "variables": [{ "name": "id", "type": "ID!" }]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationMetadata {
+    pub operation_type: OperationType,
+    /// `None` for an anonymous operation
+    pub name: Option<Name>,
+    /// Names of fields selected on the root operation type, including through fragments,
+    /// without duplicates and in order of first selection
+    pub root_fields: Vec<Name>,
+    /// Output types of selected fields, type conditions, and input types of variables,
+    /// in schema order (where built-in scalars come first)
+    pub referenced_types: Vec<NamedType>,
+    /// Names of directives applied anywhere in the operation or the fragments it uses,
+    /// without duplicates and in order of first use
+    pub directives: Vec<Name>,
+    /// Maximum nesting level of fields, where root fields have depth 1.
+    /// Fragments and inline fragments do not add a level.
+    pub depth: usize,
+    pub variables: Vec<VariableMetadata>,
+}
+
+/// A variable definition in [`OperationMetadata`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariableMetadata {
+    pub name: Name,
+    /// The variable type in GraphQL syntax, such as `[String!]`
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Summarize an operation for logs or traces:
+/// operation type and name, root fields, referenced types, directives used, depth,
+/// and variable definitions.
+///
+/// This walks the operation and the fragments it uses once,
+/// so it is cheap enough to run for every request.
+/// Each fragment is visited at most once per nesting depth.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::executable;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { user(id: ID!): User }
+///     type User { name: String, friends: [User] }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(&schema, r#"
+///     query GetUser($id: ID!) { user(id: $id) { friends { name @skip(if: true) } } }
+/// "#, "query.graphql").unwrap();
+///
+/// let operation = document.operations.get(Some("GetUser")).unwrap();
+/// let metadata = executable::metadata(operation, &document, &schema);
+/// assert_eq!(metadata.root_fields, ["user"]);
+/// assert_eq!(metadata.referenced_types, ["String", "ID", "Query", "User"]);
+/// assert_eq!(metadata.directives, ["skip"]);
+/// assert_eq!(metadata.depth, 3);
+/// assert_eq!(metadata.variables[0].ty, "ID!");
+/// ```
+pub fn metadata(
+    operation: &Operation,
+    document: &Valid<ExecutableDocument>,
+    schema: &Valid<Schema>,
+) -> OperationMetadata {
+    let mut collector = Collector {
+        document,
+        types: HashSet::default(),
+        directives: IndexSet::default(),
+        visited_fragments: HashSet::default(),
+    };
+    collector.directives(&operation.directives);
+    for variable in &operation.variables {
+        collector.types.insert(variable.ty.inner_named_type());
+        collector.directives(&variable.directives);
+    }
+    let mut root_fields = IndexSet::default();
+    collect_root_fields(document, &operation.selection_set, &mut root_fields);
+    let depth = collector.selection_set(&operation.selection_set, 0);
+    OperationMetadata {
+        operation_type: operation.operation_type,
+        name: operation.name.clone(),
+        root_fields: root_fields.into_iter().cloned().collect(),
+        referenced_types: schema
+            .types
+            .keys()
+            .filter(|name| collector.types.contains(name))
+            .cloned()
+            .collect(),
+        directives: collector.directives.into_iter().cloned().collect(),
+        depth,
+        variables: operation
+            .variables
+            .iter()
+            .map(|variable| VariableMetadata {
+                name: variable.name.clone(),
+                ty: variable.ty.to_string(),
+            })
+            .collect(),
+    }
+}
+
+struct Collector<'a> {
+    document: &'a ExecutableDocument,
+    types: HashSet<&'a NamedType>,
+    directives: IndexSet<&'a Name>,
+    /// Fragment names with the depth they were visited at
+    visited_fragments: HashSet<(&'a Name, usize)>,
+}
+
+impl<'a> Collector<'a> {
+    fn directives(&mut self, directives: &'a DirectiveList) {
+        self.directives
+            .extend(directives.iter().map(|directive| &directive.name))
+    }
+
+    /// Returns the maximum field depth within `selection_set`,
+    /// whose parent field is at `depth`
+    fn selection_set(&mut self, selection_set: &'a SelectionSet, depth: usize) -> usize {
+        self.types.insert(&selection_set.ty);
+        let mut max_depth = depth;
+        for selection in &selection_set.selections {
+            let nested_depth = match selection {
+                Selection::Field(field) => {
+                    self.directives(&field.directives);
+                    self.types.insert(field.ty().inner_named_type());
+                    self.selection_set(&field.selection_set, depth + 1)
+                }
+                Selection::InlineFragment(inline) => {
+                    self.directives(&inline.directives);
+                    self.selection_set(&inline.selection_set, depth)
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.directives);
+                    let Some(fragment) = self.document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if !self.visited_fragments.insert((&fragment.name, depth)) {
+                        // Already visited at this depth: same result
+                        continue;
+                    }
+                    self.directives(&fragment.directives);
+                    self.selection_set(&fragment.selection_set, depth)
+                }
+                Selection::Invalid(_) => continue,
+            };
+            max_depth = max_depth.max(nested_depth)
+        }
+        max_depth
+    }
+}
+
+fn collect_root_fields<'a>(
+    document: &'a ExecutableDocument,
+    selection_set: &'a SelectionSet,
+    root_fields: &mut IndexSet<&'a Name>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                root_fields.insert(&field.name);
+            }
+            Selection::InlineFragment(inline) => {
+                collect_root_fields(document, &inline.selection_set, root_fields)
+            }
+            Selection::FragmentSpread(spread) => {
+                // Validation ensures that `Valid<ExecutableDocument>` does not contain
+                // fragment cycles
+                if let Some(fragment) = document.fragments.get(&spread.fragment_name) {
+                    collect_root_fields(document, &fragment.selection_set, root_fields)
+                }
+            }
+            Selection::Invalid(_) => {}
+        }
+    }
+}
@@ -5,6 +5,8 @@ use crate::ast;
 use crate::collections::IndexMap;
 use crate::coordinate::FieldArgumentCoordinate;
 use crate::coordinate::TypeAttributeCoordinate;
+use crate::execution::InputCoercionError;
+use crate::execution::JsonMap;
 use crate::parser::Parser;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
@@ -15,19 +17,48 @@ use crate::validation::WithErrors;
 use crate::Node;
 use crate::Schema;
 use indexmap::map::Entry;
+use std::borrow::Cow;
 use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
+mod coverage;
+mod default_arguments;
+mod exhaustiveness;
+mod extract;
 pub(crate) mod from_ast;
+mod metadata;
+mod persisted;
 mod serialize;
+mod strip;
+mod tables;
 pub(crate) mod validation;
 
+pub use self::coverage::SchemaCoverage;
+pub use self::exhaustiveness::check_exhaustiveness;
+pub use self::exhaustiveness::NonExhaustiveSelection;
+pub use self::extract::extract_fragments;
+pub use self::extract::FragmentExtraction;
+pub use self::metadata::metadata;
+pub use self::metadata::OperationMetadata;
+pub use self::metadata::VariableMetadata;
+pub(crate) use self::persisted::collect_fragments;
+pub use self::persisted::deduplicate_operations;
+pub use self::persisted::OperationOccurrence;
+pub use self::persisted::PersistedOperation;
+pub use self::strip::strip_directives;
+pub use self::strip::strip_selections_with_directives;
+pub use self::strip::Stripped;
+pub use self::strip::StrippedDocument;
+pub use self::tables::ExecutableTables;
+pub use self::tables::SelectionView;
 pub use crate::ast::Argument;
 use crate::ast::ArgumentByNameError;
 pub use crate::ast::Directive;
 pub use crate::ast::DirectiveList;
 pub use crate::ast::NamedType;
+pub use crate::ast::Nullability;
+pub use crate::ast::NullabilityDesignator;
 pub use crate::ast::OperationType;
 pub use crate::ast::Type;
 pub use crate::ast::Value;
@@ -35,6 +66,14 @@ pub use crate::ast::VariableDefinition;
 pub use crate::Name;
 
 /// Executable definitions, annotated with type information
+///
+/// # Ordering
+///
+/// Named operations and fragment definitions are in source order,
+/// which serialization follows after the anonymous operation if any.
+/// Selections within a selection set are always in source order.
+/// Use [`sort_by_name`][Self::sort_by_name] or sort the `IndexMap`s directly
+/// to get a different order.
 #[derive(Debug, Clone, Default)]
 pub struct ExecutableDocument {
     /// If this document was originally parsed from a source file,
@@ -70,7 +109,7 @@ pub struct FieldSet {
     pub selection_set: SelectionSet,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Operation {
     pub operation_type: OperationType,
     pub name: Option<Name>,
@@ -79,7 +118,7 @@ pub struct Operation {
     pub selection_set: SelectionSet,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Fragment {
     pub name: Name,
     pub directives: DirectiveList,
@@ -97,15 +136,21 @@ pub enum Selection {
     Field(Node<Field>),
     FragmentSpread(Node<FragmentSpread>),
     InlineFragment(Node<InlineFragment>),
+    /// Only present in documents built with
+    /// [`Parser::keep_invalid_selections`][crate::parser::Parser::keep_invalid_selections]
+    Invalid(Node<InvalidSelection>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Field {
     /// The definition of this field in an object type or interface type definition in the schema
     pub definition: Node<schema::FieldDefinition>,
     pub alias: Option<Name>,
     pub name: Name,
     pub arguments: Vec<Node<Argument>>,
+    /// Experimental client-controlled nullability, see [`Nullability`]
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    pub nullability: Option<Nullability>,
     pub directives: DirectiveList,
     pub selection_set: SelectionSet,
 }
@@ -123,6 +168,23 @@ pub struct InlineFragment {
     pub selection_set: SelectionSet,
 }
 
+/// A selection that could not be converted from AST,
+/// such as a field that is not defined on its parent type
+/// or an inline fragment whose type condition is not defined in the schema.
+///
+/// Building a document only keeps these with
+/// [`Parser::keep_invalid_selections`][crate::parser::Parser::keep_invalid_selections],
+/// so that tools like formatters and linters can still see everything that was written.
+/// Validating a document that contains any of them reports the corresponding error.
+/// Execution and other analyses skip them.
+#[derive(Debug, Clone)]
+pub struct InvalidSelection {
+    /// The selection as written in the source text, including any nested selections
+    pub ast: ast::Selection,
+    pub(crate) error: BuildError,
+    pub(crate) error_location: Option<SourceSpan>,
+}
+
 /// Errors that can occur during conversion from AST to executable document or
 /// validation of an executable document.
 #[derive(thiserror::Error, Debug, Clone)]
@@ -147,7 +209,10 @@ pub(crate) enum BuildError {
     FragmentNameCollision { name_at_previous_location: Name },
 
     #[error("`{operation_type}` root operation type is not defined")]
-    UndefinedRootOperation { operation_type: &'static str },
+    UndefinedRootOperation {
+        name: Option<Name>,
+        operation_type: &'static str,
+    },
 
     #[error(
         "type condition `{type_name}` of fragment `{fragment_name}` \
@@ -257,15 +322,20 @@ fn subscription_name_or_anonymous(name: &Option<Name>) -> impl std::fmt::Display
     }
 }
 
+/// The path to a selection in an executable document, used in diagnostics:
+/// the top-level definition that contains it, then the names of nested fields.
+///
+/// Displays like `query GetUser → user → avatar`.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct SelectionPath {
-    pub(crate) root: ExecutableDefinitionName,
-    pub(crate) nested_fields: Vec<Name>,
+pub struct SelectionPath {
+    pub root: ExecutableDefinitionName,
+    /// Names (not aliases) of the fields selected from `root` to the selection
+    pub nested_fields: Vec<Name>,
 }
 
 /// Designates by name a top-level definition in an executable document
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum ExecutableDefinitionName {
+pub enum ExecutableDefinitionName {
     AnonymousOperation(ast::OperationType),
     NamedOperation(ast::OperationType, Name),
     Fragment(Name),
@@ -328,6 +398,63 @@ impl ExecutableDocument {
         errors.into_valid_result(self)
     }
 
+    /// Like [`validate`][Self::validate], with configuration for directives
+    /// that the schema does not define. See [`ExecutableValidationOptions`].
+    ///
+    /// On success, also returns warnings for unknown directives
+    /// if [`UnknownDirectives::Warn`] is configured.
+    /// If the document has other errors, unknown directives are reported with them.
+    ///
+    /// [`ExecutableValidationOptions`]: crate::validation::ExecutableValidationOptions
+    /// [`UnknownDirectives::Warn`]: crate::validation::UnknownDirectives::Warn
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn validate_with_options(
+        self,
+        schema: &Valid<Schema>,
+        options: &crate::validation::ExecutableValidationOptions,
+    ) -> Result<(Valid<Self>, DiagnosticList), WithErrors<Self>> {
+        crate::validation::options::validate_with_options(self, schema, options)
+    }
+
+    /// Validate as much as possible without a schema,
+    /// such as in a client-side linter that does not have access to one.
+    ///
+    /// This checks the rules of the [GraphQL specification][spec] that do not depend on
+    /// type definitions:
+    ///
+    /// * Subscription operations have a single root field, which is not an introspection field
+    /// * Fields selected into the same response key from the same type
+    ///   have the same name and arguments
+    /// * Arguments, input object fields, and variables are unique
+    /// * Fragment spreads refer to a defined fragment, fragments are used,
+    ///   and fragment spreads do not form cycles
+    /// * Variables used are defined, and variables defined are used
+    /// * Built-in directives such as `@skip` and `@include` are used in a valid location,
+    ///   only once, and with valid arguments. Other directives are not checked.
+    ///
+    /// Without a schema the type of fields is unknown, so for example fields of an inline fragment
+    /// with a type condition may conflict with fields of its parent selection set
+    /// without this being reported.
+    ///
+    /// To parse source text without a schema,
+    /// use [`ast::Document::validate_standalone_executable`] instead.
+    ///
+    /// [spec]: https://spec.graphql.org/October2021/#sec-Validation
+    pub fn validate_standalone(&self) -> Result<(), DiagnosticList> {
+        let mut errors = DiagnosticList::new(self.sources.clone());
+        validation::validate_standalone_executable(&mut errors, self);
+        errors.into_result()
+    }
+
+    /// Sort named operations and fragment definitions by name.
+    ///
+    /// Serialization then outputs the anonymous operation if any,
+    /// named operations, and fragments in this order.
+    pub fn sort_by_name(&mut self) {
+        self.operations.named.sort_keys();
+        self.fragments.sort_keys();
+    }
+
     serialize_method!();
 }
 
@@ -503,6 +630,7 @@ impl Operation {
                         // and continue with the parent selection set
                         stack.pop();
                     }
+                    Some(Selection::Invalid(_)) => {}
                 }
             }
             None
@@ -552,6 +680,7 @@ impl Operation {
                         // and continue with the parent selection set
                         stack.pop();
                     }
+                    Some(Selection::Invalid(_)) => {}
                 }
             }
             None
@@ -634,6 +763,11 @@ impl Selection {
             Self::Field(sel) => &sel.directives,
             Self::FragmentSpread(sel) => &sel.directives,
             Self::InlineFragment(sel) => &sel.directives,
+            Self::Invalid(sel) => match &sel.ast {
+                ast::Selection::Field(sel) => &sel.directives,
+                ast::Selection::FragmentSpread(sel) => &sel.directives,
+                ast::Selection::InlineFragment(sel) => &sel.directives,
+            },
         }
     }
 
@@ -661,6 +795,14 @@ impl Selection {
         }
     }
 
+    pub fn as_invalid(&self) -> Option<&Node<InvalidSelection>> {
+        if let Self::Invalid(invalid) = self {
+            Some(invalid)
+        } else {
+            None
+        }
+    }
+
     serialize_method!();
 }
 
@@ -682,6 +824,34 @@ impl From<Node<FragmentSpread>> for Selection {
     }
 }
 
+impl InvalidSelection {
+    /// The message of the error reported when validating this selection
+    pub fn message(&self) -> String {
+        self.error.to_string()
+    }
+}
+
+/// The error is derived from `ast` and is ignored for comparison
+impl PartialEq for InvalidSelection {
+    fn eq(&self, other: &Self) -> bool {
+        self.ast == other.ast
+    }
+}
+
+impl Eq for InvalidSelection {}
+
+impl std::hash::Hash for InvalidSelection {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.ast.hash(state)
+    }
+}
+
+impl From<Node<InvalidSelection>> for Selection {
+    fn from(node: Node<InvalidSelection>) -> Self {
+        Self::Invalid(node)
+    }
+}
+
 impl From<Field> for Selection {
     fn from(value: Field) -> Self {
         Self::Field(Node::new(value))
@@ -711,6 +881,8 @@ impl Field {
             alias: None,
             name,
             arguments: Vec::new(),
+            #[cfg(feature = "experimental-client-controlled-nullability")]
+            nullability: None,
             directives: DirectiveList::new(),
             selection_set,
         }
@@ -722,7 +894,19 @@ impl Field {
     }
 
     pub fn with_opt_alias(mut self, alias: Option<Name>) -> Self {
-        self.alias = alias.map(Into::into);
+        self.alias = alias;
+        self
+    }
+
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    pub fn with_nullability(mut self, nullability: Nullability) -> Self {
+        self.nullability = Some(nullability);
+        self
+    }
+
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    pub fn with_opt_nullability(mut self, nullability: Option<Nullability>) -> Self {
+        self.nullability = nullability;
         self
     }
 
@@ -772,6 +956,33 @@ impl Field {
         &self.definition.ty
     }
 
+    /// The type of this field with its experimental client-controlled
+    /// [`nullability`][Self::nullability] applied, if any.
+    ///
+    /// Returns `None` if list nullability is nested deeper than list types of [`ty()`][Self::ty].
+    /// Validation reports this as an error.
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    pub fn ty_with_nullability(&self) -> Option<Type> {
+        match &self.nullability {
+            Some(nullability) => nullability.apply_to(self.ty()),
+            None => Some(self.ty().clone()),
+        }
+    }
+
+    /// The type of values of this field in a response, with nullability applied if any.
+    /// Ignores invalid list nullability and avoids cloning without nullability.
+    pub(crate) fn response_ty(&self) -> Cow<'_, Type> {
+        #[cfg(feature = "experimental-client-controlled-nullability")]
+        if let Some(ty) = self
+            .nullability
+            .as_ref()
+            .and_then(|nullability| nullability.apply_to(self.ty()))
+        {
+            return Cow::Owned(ty);
+        }
+        Cow::Borrowed(self.ty())
+    }
+
     /// Look up in `schema` the definition of the inner type of this field.
     ///
     /// The inner type is [`ty()`][Self::ty] after unwrapping non-null and list markers.
@@ -799,9 +1010,94 @@ impl Field {
         Argument::specified_argument_by_name(&self.arguments, name)
     }
 
+    /// Returns the arguments of this field selection with default values filled in:
+    /// [`arguments`][Self::arguments] as specified, followed by arguments that are not specified
+    /// but have a default value in the field definition, in definition order.
+    ///
+    /// Unlike [`effective_arguments`][Self::effective_arguments],
+    /// this does not need variable values: values are not coerced and variables are kept.
+    pub fn arguments_with_defaults(&self) -> Vec<Node<Argument>> {
+        Argument::with_defaults(&self.arguments, &self.definition.arguments)
+    }
+
+    /// Returns the values of all arguments of this field selection, as execution passes them
+    /// to a resolver.
+    ///
+    /// Values specified in the field selection are merged with the `variables` they use,
+    /// including in nested lists and input objects.
+    /// Arguments and input object fields that are not specified, or that use a variable
+    /// that is not in `variables`, get their default value from the schema if there is one.
+    /// Otherwise they are absent from the result, unlike those that are explicitly `null`.
+    /// [`CoercedValue`][crate::execution::CoercedValue] makes this distinction explicit.
+    ///
+    /// `variables` are typically the result of
+    /// [`coerce_variable_values`][crate::execution::coerce_variable_values],
+    /// which also applies the default values of variable definitions.
+    /// Returns an error if a required argument or input object field
+    /// ends up null or absent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::execution::coerce_variable_values;
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(r#"
+    ///     type Query { search(filter: Filter, limit: Int = 10): [String] }
+    ///     input Filter { text: String, tags: [String], exact: Boolean = false }
+    /// "#, "schema.graphql").unwrap();
+    /// let document = ExecutableDocument::parse_and_validate(&schema, r#"
+    ///     query($text: String, $tag: String) { search(filter: { text: $text, tags: [$tag, "b"] }) }
+    /// "#, "query.graphql").unwrap();
+    /// let operation = document.operations.get(None).unwrap();
+    /// let variables = serde_json::from_str(r#"{ "tag": "a" }"#).unwrap();
+    /// let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+    ///
+    /// let field = operation.selection_set.fields().next().unwrap();
+    /// let arguments = field.effective_arguments(&schema, &variables).unwrap();
+    /// assert_eq!(
+    ///     serde_json::to_string(&arguments).unwrap(),
+    ///     r#"{"filter":{"tags":["a","b"],"exact":false},"limit":10}"#,
+    /// );
+    /// ```
+    pub fn effective_arguments(
+        &self,
+        schema: &Valid<Schema>,
+        variables: &Valid<JsonMap>,
+    ) -> Result<JsonMap, InputCoercionError> {
+        crate::execution::input_coercion::coerce_argument_values(
+            schema,
+            variables,
+            &self.definition,
+            self,
+        )
+    }
+
     serialize_method!();
 }
 
+impl fmt::Debug for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Skip experimental nullability when unset,
+        // so that output is the same with or without the crate feature
+        let mut debug = f.debug_struct("Field");
+        debug
+            .field("definition", &self.definition)
+            .field("alias", &self.alias)
+            .field("name", &self.name)
+            .field("arguments", &self.arguments);
+        #[cfg(feature = "experimental-client-controlled-nullability")]
+        if self.nullability.is_some() {
+            debug.field("nullability", &self.nullability);
+        }
+        debug
+            .field("directives", &self.directives)
+            .field("selection_set", &self.selection_set)
+            .finish()
+    }
+}
+
 impl InlineFragment {
     pub fn with_type_condition(type_condition: NamedType) -> Self {
         let selection_set = SelectionSet::new(type_condition.clone());
@@ -918,6 +1214,15 @@ impl FieldSet {
     serialize_method!();
 }
 
+impl ExecutableDefinitionName {
+    pub(crate) fn of_operation(operation: &Operation) -> Self {
+        match &operation.name {
+            Some(name) => Self::NamedOperation(operation.operation_type, name.clone()),
+            None => Self::AnonymousOperation(operation.operation_type),
+        }
+    }
+}
+
 impl fmt::Display for SelectionPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.root {
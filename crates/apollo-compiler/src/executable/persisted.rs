@@ -0,0 +1,295 @@
+use crate::ast;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::executable::Fragment;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// An entry of a persisted query manifest, as returned by [`deduplicate_operations`]
+#[derive(Debug, Clone)]
+pub struct PersistedOperation {
+    /// A document with this single operation and the fragments it uses,
+    /// where literal values of field arguments were replaced with variables
+    pub document: Valid<ExecutableDocument>,
+    /// The name of the operation, or `None` for an anonymous operation
+    pub operation_name: Option<Name>,
+    /// Every input operation that is identical to this one
+    /// after replacing literal values, in input order
+    pub occurrences: Vec<OperationOccurrence>,
+}
+
+/// An input operation of [`deduplicate_operations`], mapped to a [`PersistedOperation`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationOccurrence {
+    /// The index of the document containing this operation, in the slice given to
+    /// [`deduplicate_operations`]
+    pub document_index: usize,
+    /// The replaced literal values, keyed by the name of the new variable.
+    /// A client sending the persisted operation instead of this one
+    /// adds these to its variables.
+    pub variables: JsonMap,
+}
+
+/// Deduplicate operations that differ only in literal values of field arguments,
+/// such as when generating a persisted query manifest for a large client codebase.
+///
+/// Each operation of each document is rewritten so that field arguments with a literal value
+/// (without any variable in it) take a new variable instead.
+/// Variables are named after the argument, with a numeric suffix if that name is taken.
+/// A literal repeated for arguments of the same name and type uses the same variable.
+/// Operations whose rewritten documents are identical (including the operation name
+/// and the fragments used) are then grouped into one [`PersistedOperation`],
+/// in order of first occurrence.
+///
+/// Directive arguments such as in `@include(if: true)` are not rewritten
+/// since they change which fields are selected.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::executable::deduplicate_operations;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { user(id: ID!): User } type User { name: String avatar(size: Int): String }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let documents = [
+///     ExecutableDocument::parse_and_validate(
+///         &schema, r#"query User { user(id: "1") { name avatar(size: 64) } }"#, "a.graphql",
+///     ).unwrap(),
+///     ExecutableDocument::parse_and_validate(
+///         &schema, r#"query User { user(id: "2") { name avatar(size: 32) } }"#, "b.graphql",
+///     ).unwrap(),
+/// ];
+///
+/// let persisted = deduplicate_operations(&schema, &documents);
+/// assert_eq!(persisted.len(), 1);
+/// assert_eq!(
+///     persisted[0].document.serialize().no_indent().to_string(),
+///     "query User($id: ID!, $size: Int) { user(id: $id) { name avatar(size: $size) } }",
+/// );
+/// assert_eq!(persisted[0].occurrences[1].document_index, 1);
+/// assert_eq!(
+///     serde_json::to_string(&persisted[0].occurrences[1].variables).unwrap(),
+///     r#"{"id":"2","size":32}"#,
+/// );
+/// ```
+pub fn deduplicate_operations(
+    schema: &Valid<Schema>,
+    documents: &[Valid<ExecutableDocument>],
+) -> Vec<PersistedOperation> {
+    let mut persisted = IndexMap::<String, PersistedOperation>::default();
+    for (document_index, document) in documents.iter().enumerate() {
+        for operation in document.operations.iter() {
+            let (rewritten, variables) = parameterize(schema, document, operation);
+            let key = rewritten.serialize().to_string();
+            let occurrence = OperationOccurrence {
+                document_index,
+                variables,
+            };
+            persisted
+                .entry(key)
+                .or_insert_with(|| PersistedOperation {
+                    document: rewritten,
+                    operation_name: operation.name.clone(),
+                    occurrences: Vec::new(),
+                })
+                .occurrences
+                .push(occurrence)
+        }
+    }
+    persisted.into_values().collect()
+}
+
+/// Returns a document with the operation and the fragments it uses,
+/// with literal field arguments replaced by variables, and the values of those variables.
+///
+/// If the rewritten document is somehow not valid, returns it without rewriting.
+fn parameterize(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+) -> (Valid<ExecutableDocument>, JsonMap) {
+    let mut used_fragments = IndexMap::default();
+    collect_fragments(document, &operation.selection_set, &mut used_fragments);
+    used_fragments.sort_keys();
+
+    let mut parameterizer = Parameterizer {
+        taken_names: operation.variables.iter().map(|v| v.name.clone()).collect(),
+        new_variables: Vec::new(),
+    };
+    let mut new_operation = operation.clone();
+    let mut new_fragments = used_fragments.clone();
+    {
+        let new_operation = new_operation.make_mut();
+        parameterizer.selection_set(&mut new_operation.selection_set);
+        for fragment in new_fragments.values_mut() {
+            parameterizer.selection_set(&mut fragment.make_mut().selection_set)
+        }
+        new_operation
+            .variables
+            .extend(parameterizer.new_variables.iter().map(|new| {
+                Node::new(ast::VariableDefinition {
+                    name: new.name.clone(),
+                    ty: Node::new(new.ty.clone()),
+                    default_value: None,
+                    directives: Default::default(),
+                })
+            }));
+    }
+    let variables = parameterizer
+        .new_variables
+        .into_iter()
+        .map(|new| (new.name.as_str().into(), new.json))
+        .collect();
+
+    let build = |operation: Node<Operation>, fragments| {
+        let mut new_document = ExecutableDocument::new();
+        new_document.sources = document.sources.clone();
+        new_document.operations.insert(operation);
+        new_document.fragments = fragments;
+        new_document
+    };
+    match build(new_operation, new_fragments).validate(schema) {
+        Ok(valid) => (valid, variables),
+        Err(_) => {
+            let unchanged = build(operation.clone(), used_fragments);
+            (Valid::assume_valid(unchanged), JsonMap::new())
+        }
+    }
+}
+
+pub(crate) fn collect_fragments(
+    document: &ExecutableDocument,
+    selection_set: &SelectionSet,
+    used: &mut IndexMap<Name, Node<Fragment>>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => collect_fragments(document, &field.selection_set, used),
+            Selection::InlineFragment(inline) => {
+                collect_fragments(document, &inline.selection_set, used)
+            }
+            Selection::FragmentSpread(spread) => {
+                if used.contains_key(&spread.fragment_name) {
+                    continue;
+                }
+                if let Some(fragment) = document.fragments.get(&spread.fragment_name) {
+                    used.insert(spread.fragment_name.clone(), fragment.clone());
+                    collect_fragments(document, &fragment.selection_set, used)
+                }
+            }
+            Selection::Invalid(_) => {}
+        }
+    }
+}
+
+struct Parameterizer {
+    taken_names: HashSet<Name>,
+    new_variables: Vec<NewVariable>,
+}
+
+struct NewVariable {
+    name: Name,
+    argument_name: Name,
+    ty: Type,
+    value: ast::Value,
+    json: JsonValue,
+}
+
+impl Parameterizer {
+    fn selection_set(&mut self, selection_set: &mut SelectionSet) {
+        for selection in &mut selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    let field = field.make_mut();
+                    for argument in &mut field.arguments {
+                        let Some(definition) = field.definition.argument_by_name(&argument.name)
+                        else {
+                            continue;
+                        };
+                        let Some(json) = literal_to_json(&argument.value) else {
+                            continue;
+                        };
+                        let name = self.variable_for(
+                            &argument.name,
+                            &definition.ty,
+                            &argument.value,
+                            json,
+                        );
+                        argument.make_mut().value = Node::new(ast::Value::Variable(name));
+                    }
+                    self.selection_set(&mut field.selection_set)
+                }
+                Selection::InlineFragment(inline) => {
+                    self.selection_set(&mut inline.make_mut().selection_set)
+                }
+                Selection::FragmentSpread(_) | Selection::Invalid(_) => {}
+            }
+        }
+    }
+
+    fn variable_for(
+        &mut self,
+        argument_name: &Name,
+        ty: &Type,
+        value: &ast::Value,
+        json: JsonValue,
+    ) -> Name {
+        let existing = self.new_variables.iter().find(|new| {
+            new.argument_name == *argument_name && new.ty == *ty && new.value == *value
+        });
+        if let Some(existing) = existing {
+            return existing.name.clone();
+        }
+        let mut name = argument_name.clone();
+        let mut suffix = 0;
+        while self.taken_names.contains(&name) {
+            suffix += 1;
+            name = Name::new(&format!("{argument_name}_{suffix}")).unwrap()
+        }
+        self.taken_names.insert(name.clone());
+        self.new_variables.push(NewVariable {
+            name: name.clone(),
+            argument_name: argument_name.clone(),
+            ty: ty.clone(),
+            value: value.clone(),
+            json,
+        });
+        name
+    }
+}
+
+/// Returns `None` if the value contains a variable
+fn literal_to_json(value: &ast::Value) -> Option<JsonValue> {
+    Some(match value {
+        ast::Value::Variable(_) => return None,
+        ast::Value::Null => JsonValue::Null,
+        ast::Value::Enum(value) => value.as_str().into(),
+        ast::Value::String(value) => value.as_str().into(),
+        ast::Value::Boolean(value) => (*value).into(),
+        ast::Value::Int(value) => JsonValue::Number(value.as_str().parse().ok()?),
+        ast::Value::Float(value) => JsonValue::Number(value.as_str().parse().ok()?),
+        ast::Value::List(items) => items
+            .iter()
+            .map(|item| literal_to_json(item))
+            .collect::<Option<_>>()?,
+        ast::Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| Some((name.as_str().into(), literal_to_json(value)?)))
+                .collect::<Option<_>>()?,
+        ),
+    })
+}
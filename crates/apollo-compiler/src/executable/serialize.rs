@@ -28,6 +28,7 @@ impl ExecutableDocument {
 impl Operation {
     fn to_ast(&self, location: Option<SourceSpan>) -> ast::Definition {
         let def = ast::OperationDefinition {
+            leading_comments: Vec::new(),
             operation_type: self.operation_type,
             name: self.name.clone(),
             variables: self.variables.clone(),
@@ -45,6 +46,7 @@ impl Operation {
 impl Fragment {
     fn to_ast(&self, location: Option<SourceSpan>) -> ast::Definition {
         let def = ast::FragmentDefinition {
+            leading_comments: Vec::new(),
             name: self.name.clone(),
             type_condition: self.selection_set.ty.clone(),
             directives: self.directives.clone(),
@@ -83,6 +85,7 @@ impl Selection {
             Selection::InlineFragment(inline_fragment) => ast::Selection::InlineFragment(
                 inline_fragment.same_location(inline_fragment.to_ast()),
             ),
+            Selection::Invalid(invalid) => invalid.ast.clone(),
         }
     }
 
@@ -97,6 +100,8 @@ impl Field {
             alias: self.alias.clone(),
             name: self.name.clone(),
             arguments: self.arguments.clone(),
+            #[cfg(feature = "experimental-client-controlled-nullability")]
+            nullability: self.nullability.clone(),
             directives: self.directives.clone(),
             selection_set: self.selection_set.to_ast(),
         }
@@ -0,0 +1,361 @@
+use crate::ast;
+use crate::collections::HashSet;
+use crate::executable::DirectiveList;
+use crate::executable::ExecutableDefinitionName;
+use crate::executable::Fragment;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionPath;
+use crate::executable::SelectionSet;
+use crate::executable::VariableDefinition;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+
+/// The result of [`strip_directives`] or [`strip_selections_with_directives`]:
+/// a cleaned document to send to a server, and the pieces that were removed from it
+/// for local processing.
+#[derive(Debug, Clone)]
+pub struct StrippedDocument {
+    /// The input document without the removed pieces
+    pub document: ExecutableDocument,
+    /// Removed applications of the listed directives,
+    /// except those on selections that were removed entirely
+    pub directives: Vec<Stripped<Node<ast::Directive>>>,
+    /// Removed selections, which have one of the listed directives
+    pub selections: Vec<Stripped<Selection>>,
+    /// Fragment definitions that were used before stripping and no longer are
+    pub fragments: Vec<Node<Fragment>>,
+    /// Variable definitions that were used before stripping and no longer are
+    pub variables: Vec<Stripped<Node<VariableDefinition>>>,
+}
+
+/// A piece removed by [`strip_directives`] or [`strip_selections_with_directives`],
+/// and where it was in the input document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stripped<T> {
+    /// The path to the selection set or definition that contained `node`.
+    /// For a directive on a field, this includes the name of that field.
+    pub path: SelectionPath,
+    pub node: T,
+}
+
+/// Remove applications of the directives named in `names` from `document`,
+/// such as client-only directives before sending an operation to a server.
+///
+/// Directives are removed from operations, variable definitions, fragment definitions,
+/// and selections. Variable definitions that are no longer used are also removed.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::executable::strip_directives;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { feed(offset: Int): [String] }
+///      directive @connection(key: String!) on FIELD",
+///     "schema.graphql",
+/// ).unwrap();
+/// let source = r#"{ feed(offset: 10) @connection(key: "feed") }"#;
+/// let document = ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+///
+/// let stripped = strip_directives(&document, &["connection"]);
+/// assert_eq!(stripped.document.serialize().no_indent().to_string(), "{ feed(offset: 10) }");
+/// assert_eq!(stripped.directives.len(), 1);
+/// assert_eq!(stripped.directives[0].path.to_string(), "query → feed");
+/// ```
+pub fn strip_directives(document: &ExecutableDocument, names: &[&str]) -> StrippedDocument {
+    strip(document, names, false)
+}
+
+/// Remove fields, fragment spreads, and inline fragments
+/// that have one of the directives named in `names` from `document`,
+/// like Apollo Client does for fields resolved locally with `@client`.
+///
+/// Applications of these directives elsewhere are removed like in [`strip_directives`].
+/// A field or inline fragment whose selections are all removed is removed too,
+/// as are fragment definitions and variable definitions that are no longer used.
+/// An operation whose selections are all removed is kept with an empty selection set,
+/// which is not valid to send to a server.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::executable::strip_selections_with_directives;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { user: User } type User { name: String isSelected: Boolean }
+///      directive @client on FIELD",
+///     "schema.graphql",
+/// ).unwrap();
+/// let source = "{ user { name isSelected @client } }";
+/// let document = ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+///
+/// let stripped = strip_selections_with_directives(&document, &["client"]);
+/// assert_eq!(stripped.document.serialize().no_indent().to_string(), "{ user { name } }");
+/// assert_eq!(stripped.selections.len(), 1);
+/// assert_eq!(stripped.selections[0].path.to_string(), "query → user");
+/// ```
+pub fn strip_selections_with_directives(
+    document: &ExecutableDocument,
+    names: &[&str],
+) -> StrippedDocument {
+    strip(document, names, true)
+}
+
+fn strip(
+    document: &ExecutableDocument,
+    names: &[&str],
+    remove_selections: bool,
+) -> StrippedDocument {
+    let mut stripper = Stripper {
+        names,
+        remove_selections,
+        directives: Vec::new(),
+        selections: Vec::new(),
+    };
+    let mut new_document = document.clone();
+    for operation in operations_mut(&mut new_document) {
+        let operation = operation.make_mut();
+        let path = &mut SelectionPath {
+            root: ExecutableDefinitionName::of_operation(operation),
+            nested_fields: Vec::new(),
+        };
+        stripper.directives(path, &mut operation.directives);
+        for variable in &mut operation.variables {
+            stripper.directives(path, &mut variable.make_mut().directives);
+        }
+        stripper.selection_set(path, &mut operation.selection_set);
+    }
+    for fragment in new_document.fragments.values_mut() {
+        let fragment = fragment.make_mut();
+        let path = &mut SelectionPath {
+            root: ExecutableDefinitionName::Fragment(fragment.name.clone()),
+            nested_fields: Vec::new(),
+        };
+        stripper.directives(path, &mut fragment.directives);
+        stripper.selection_set(path, &mut fragment.selection_set);
+    }
+
+    // Remove definitions that are only unused because of stripping
+    let used_before = used_fragments(document);
+    let used_after = used_fragments(&new_document);
+    let mut fragments = Vec::new();
+    new_document.fragments.retain(|name, fragment| {
+        let keep = used_after.contains(name) || !used_before.contains(name);
+        if !keep {
+            fragments.push(fragment.clone());
+        }
+        keep
+    });
+
+    let unused_variables: Vec<HashSet<Name>> = document
+        .operations
+        .iter()
+        .zip(new_document.operations.iter())
+        .map(|(before, after)| {
+            let used_after = used_variables(&new_document, after);
+            used_variables(document, before)
+                .difference(&used_after)
+                .cloned()
+                .collect()
+        })
+        .collect();
+    let mut variables = Vec::new();
+    for (operation, unused) in operations_mut(&mut new_document).zip(unused_variables) {
+        if unused.is_empty() {
+            continue;
+        }
+        let operation = operation.make_mut();
+        let path = SelectionPath {
+            root: ExecutableDefinitionName::of_operation(operation),
+            nested_fields: Vec::new(),
+        };
+        operation.variables.retain(|variable| {
+            let keep = !unused.contains(&variable.name);
+            if !keep {
+                variables.push(Stripped {
+                    path: path.clone(),
+                    node: variable.clone(),
+                });
+            }
+            keep
+        });
+    }
+
+    StrippedDocument {
+        document: new_document,
+        directives: stripper.directives,
+        selections: stripper.selections,
+        fragments,
+        variables,
+    }
+}
+
+struct Stripper<'a> {
+    names: &'a [&'a str],
+    remove_selections: bool,
+    directives: Vec<Stripped<Node<ast::Directive>>>,
+    selections: Vec<Stripped<Selection>>,
+}
+
+impl Stripper<'_> {
+    fn is_listed(&self, directive: &ast::Directive) -> bool {
+        self.names.contains(&directive.name.as_str())
+    }
+
+    fn directives(&mut self, path: &SelectionPath, directives: &mut DirectiveList) {
+        if !directives.iter().any(|directive| self.is_listed(directive)) {
+            // Avoid invalidating the name index of unchanged lists
+            return;
+        }
+        directives.retain(|directive| {
+            let keep = !self.is_listed(directive);
+            if !keep {
+                self.directives.push(Stripped {
+                    path: path.clone(),
+                    node: directive.clone(),
+                })
+            }
+            keep
+        })
+    }
+
+    fn selection_set(&mut self, path: &mut SelectionPath, selection_set: &mut SelectionSet) {
+        selection_set.selections.retain_mut(|selection| {
+            if self.remove_selections
+                && selection
+                    .directives()
+                    .iter()
+                    .any(|directive| self.is_listed(directive))
+            {
+                self.selections.push(Stripped {
+                    path: path.clone(),
+                    node: selection.clone(),
+                });
+                return false;
+            }
+            match selection {
+                Selection::Field(field) => {
+                    let field = field.make_mut();
+                    path.nested_fields.push(field.name.clone());
+                    self.directives(path, &mut field.directives);
+                    let had_selections = !field.selection_set.selections.is_empty();
+                    self.selection_set(path, &mut field.selection_set);
+                    path.nested_fields.pop();
+                    !had_selections || !field.selection_set.selections.is_empty()
+                }
+                Selection::InlineFragment(inline) => {
+                    let inline = inline.make_mut();
+                    self.directives(path, &mut inline.directives);
+                    self.selection_set(path, &mut inline.selection_set);
+                    !inline.selection_set.selections.is_empty()
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.directives(path, &mut spread.make_mut().directives);
+                    true
+                }
+                Selection::Invalid(_) => true,
+            }
+        })
+    }
+}
+
+fn operations_mut(document: &mut ExecutableDocument) -> impl Iterator<Item = &mut Node<Operation>> {
+    document
+        .operations
+        .anonymous
+        .iter_mut()
+        .chain(document.operations.named.values_mut())
+}
+
+/// Returns the names of fragments used by operations of `document`,
+/// directly or through other fragments
+fn used_fragments(document: &ExecutableDocument) -> HashSet<Name> {
+    let mut used = HashSet::default();
+    let mut stack: Vec<_> = document
+        .operations
+        .iter()
+        .map(|operation| &operation.selection_set)
+        .collect();
+    while let Some(selection_set) = stack.pop() {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => stack.push(&field.selection_set),
+                Selection::InlineFragment(inline) => stack.push(&inline.selection_set),
+                Selection::FragmentSpread(spread) => {
+                    if used.insert(spread.fragment_name.clone()) {
+                        if let Some(fragment) = document.fragments.get(&spread.fragment_name) {
+                            stack.push(&fragment.selection_set)
+                        }
+                    }
+                }
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+    used
+}
+
+/// Returns the names of variables used in `operation`, including in the fragments it uses
+fn used_variables(document: &ExecutableDocument, operation: &Operation) -> HashSet<Name> {
+    let mut used = HashSet::default();
+    let mut visited_fragments = HashSet::default();
+    variables_in_directives(&mut used, &operation.directives);
+    let mut stack = vec![&operation.selection_set];
+    while let Some(selection_set) = stack.pop() {
+        for selection in &selection_set.selections {
+            variables_in_directives(&mut used, selection.directives());
+            match selection {
+                Selection::Field(field) => {
+                    for argument in &field.arguments {
+                        variables_in_value(&mut used, &argument.value)
+                    }
+                    stack.push(&field.selection_set)
+                }
+                Selection::InlineFragment(inline) => stack.push(&inline.selection_set),
+                Selection::FragmentSpread(spread) => {
+                    if visited_fragments.insert(&spread.fragment_name) {
+                        if let Some(fragment) = document.fragments.get(&spread.fragment_name) {
+                            variables_in_directives(&mut used, &fragment.directives);
+                            stack.push(&fragment.selection_set)
+                        }
+                    }
+                }
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+    used
+}
+
+fn variables_in_directives(used: &mut HashSet<Name>, directives: &DirectiveList) {
+    for directive in directives.iter() {
+        for argument in &directive.arguments {
+            variables_in_value(used, &argument.value)
+        }
+    }
+}
+
+fn variables_in_value(used: &mut HashSet<Name>, value: &ast::Value) {
+    match value {
+        ast::Value::Variable(name) => {
+            used.insert(name.clone());
+        }
+        ast::Value::List(list) => {
+            for item in list {
+                variables_in_value(used, item)
+            }
+        }
+        ast::Value::Object(fields) => {
+            for (_, value) in fields {
+                variables_in_value(used, value)
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,214 @@
+use crate::executable::OperationType;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::schema::FieldView;
+use crate::schema::SchemaTables;
+use crate::ExecutableDocument;
+use crate::Name;
+
+/// A flat copy of the field selections of an [`ExecutableDocument`],
+/// stored as parallel arrays indexed by row number like [`SchemaTables`].
+///
+/// * The definition table has one row per operation then one per fragment definition,
+///   in document order.
+/// * The selection table has one row per field selection.
+///   Fields inside inline fragments are attributed to the enclosing field (or definition),
+///   and fragment spreads are not expanded:
+///   fields of a fragment definition are recorded once, under that definition.
+///
+/// Field selections are linked to the field table of a [`SchemaTables`]
+/// built from the schema the document was validated against.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::executable::ExecutableTables;
+/// use apollo_compiler::schema::SchemaTables;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { user(id: ID!): User }
+///     type User { name: String }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(&schema, r#"
+///     { user(id: 1) { username: name } }
+/// "#, "query.graphql").unwrap();
+///
+/// let schema_tables = SchemaTables::new(&schema);
+/// let tables = ExecutableTables::new(&document, &schema_tables);
+/// let rows: Vec<_> = tables
+///     .selections()
+///     .map(|selection| {
+///         let field = selection.schema_field().unwrap();
+///         format!("{} {}.{}", selection.response_key(), field.parent().name(), field.name())
+///     })
+///     .collect();
+/// assert_eq!(rows, ["user Query.user", "username User.name"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExecutableTables<'schema> {
+    schema: &'schema SchemaTables,
+
+    definition_names: Vec<Option<Name>>,
+    definition_operation_types: Vec<Option<OperationType>>,
+
+    selection_definitions: Vec<u32>,
+    selection_parents: Vec<u32>,
+    selection_response_keys: Vec<Name>,
+    selection_schema_fields: Vec<u32>,
+}
+
+/// A row of the selection table of [`ExecutableTables`]
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionView<'a> {
+    tables: &'a ExecutableTables<'a>,
+    index: usize,
+}
+
+impl<'schema> ExecutableTables<'schema> {
+    /// Row index used for a field selection without a parent field,
+    /// and for a field not defined in the schema such as `__typename`.
+    pub const NO_INDEX: u32 = SchemaTables::NO_INDEX;
+
+    /// Copy the field selections of `document` into flat tables
+    pub fn new(document: &ExecutableDocument, schema: &'schema SchemaTables) -> Self {
+        let mut tables = Self {
+            schema,
+            definition_names: Vec::new(),
+            definition_operation_types: Vec::new(),
+            selection_definitions: Vec::new(),
+            selection_parents: Vec::new(),
+            selection_response_keys: Vec::new(),
+            selection_schema_fields: Vec::new(),
+        };
+        for operation in document.operations.iter() {
+            let definition = tables.definition_names.len() as u32;
+            tables.definition_names.push(operation.name.clone());
+            tables
+                .definition_operation_types
+                .push(Some(operation.operation_type));
+            tables.push_selection_set(definition, Self::NO_INDEX, &operation.selection_set)
+        }
+        for fragment in document.fragments.values() {
+            let definition = tables.definition_names.len() as u32;
+            tables.definition_names.push(Some(fragment.name.clone()));
+            tables.definition_operation_types.push(None);
+            tables.push_selection_set(definition, Self::NO_INDEX, &fragment.selection_set)
+        }
+        tables
+    }
+
+    fn push_selection_set(&mut self, definition: u32, parent: u32, selection_set: &SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    let index = self.selection_response_keys.len() as u32;
+                    self.selection_definitions.push(definition);
+                    self.selection_parents.push(parent);
+                    self.selection_response_keys
+                        .push(field.response_key().clone());
+                    let schema_field = self
+                        .schema
+                        .field_by_name(&selection_set.ty, &field.name)
+                        .map_or(Self::NO_INDEX, |field| field.index() as u32);
+                    self.selection_schema_fields.push(schema_field);
+                    self.push_selection_set(definition, index, &field.selection_set)
+                }
+                Selection::InlineFragment(inline) => {
+                    self.push_selection_set(definition, parent, &inline.selection_set)
+                }
+                Selection::FragmentSpread(_) | Selection::Invalid(_) => {}
+            }
+        }
+    }
+
+    /// The schema tables that field selections are linked to
+    pub fn schema(&self) -> &'schema SchemaTables {
+        self.schema
+    }
+
+    /// Returns a row of the selection table. Panics if `index` is out of bounds.
+    pub fn selection(&self, index: usize) -> SelectionView<'_> {
+        assert!(
+            index < self.selection_response_keys.len(),
+            "selection index out of bounds"
+        );
+        SelectionView {
+            tables: self,
+            index,
+        }
+    }
+
+    /// Iterate over rows of the selection table
+    pub fn selections(&self) -> impl ExactSizeIterator<Item = SelectionView<'_>> {
+        (0..self.selection_response_keys.len()).map(|index| SelectionView {
+            tables: self,
+            index,
+        })
+    }
+
+    /// The name column of the definition table: `None` for an anonymous operation
+    pub fn definition_names(&self) -> &[Option<Name>] {
+        &self.definition_names
+    }
+
+    /// The operation type column of the definition table: `None` for a fragment definition
+    pub fn definition_operation_types(&self) -> &[Option<OperationType>] {
+        &self.definition_operation_types
+    }
+
+    /// The column of the selection table with the definition table row
+    /// of the operation or fragment each field selection is in
+    pub fn selection_definitions(&self) -> &[u32] {
+        &self.selection_definitions
+    }
+
+    /// The column of the selection table with the selection table row of each parent field,
+    /// or [`NO_INDEX`][Self::NO_INDEX] for fields at the top level of a definition
+    pub fn selection_parents(&self) -> &[u32] {
+        &self.selection_parents
+    }
+
+    /// The response key (alias or field name) column of the selection table
+    pub fn selection_response_keys(&self) -> &[Name] {
+        &self.selection_response_keys
+    }
+
+    /// The column of the selection table with the schema field table row
+    /// of each selected field, or [`NO_INDEX`][Self::NO_INDEX]
+    pub fn selection_schema_fields(&self) -> &[u32] {
+        &self.selection_schema_fields
+    }
+}
+
+impl<'a> SelectionView<'a> {
+    /// The row index in the selection table
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn response_key(&self) -> &'a Name {
+        &self.tables.selection_response_keys[self.index]
+    }
+
+    /// The name of the operation or fragment this field selection is in,
+    /// or `None` for an anonymous operation
+    pub fn definition_name(&self) -> Option<&'a Name> {
+        self.tables.definition_names[self.tables.selection_definitions[self.index] as usize]
+            .as_ref()
+    }
+
+    /// The field selection whose selection set contains this one
+    pub fn parent(&self) -> Option<SelectionView<'a>> {
+        let parent = self.tables.selection_parents[self.index];
+        (parent != ExecutableTables::NO_INDEX).then(|| self.tables.selection(parent as usize))
+    }
+
+    /// The definition of the selected field in the schema tables,
+    /// or `None` for `__typename` and other introspection meta-fields
+    pub fn schema_field(&self) -> Option<FieldView<'a>> {
+        let field = self.tables.selection_schema_fields[self.index];
+        (field != ExecutableTables::NO_INDEX).then(|| self.tables.schema.field(field as usize))
+    }
+}
@@ -1,20 +1,48 @@
 use super::FieldSet;
+use super::Operation;
 use crate::validation::fragment::validate_fragment_used;
-use crate::validation::operation::validate_operation_definitions;
+use crate::validation::operation::validate_operation;
+use crate::validation::operation::validate_subscription;
 use crate::validation::selection::FieldsInSetCanMerge;
 use crate::validation::DiagnosticList;
 use crate::validation::ExecutableValidationContext;
 use crate::validation::Valid;
 use crate::ExecutableDocument;
+use crate::Node;
 use crate::Schema;
 
+/// A part of validation that only depends on one operation and the fragments it uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum OperationStep {
+    Definition,
+    Selections,
+}
+
+/// Called for each [`OperationStep`] of each operation with a function that runs it,
+/// so that [`Validator`][crate::validation::Validator] can reuse earlier diagnostics instead
+pub(crate) type RunOperationStep<'a> = dyn FnMut(
+        &ExecutableDocument,
+        &Node<Operation>,
+        OperationStep,
+        &mut DiagnosticList,
+        &mut dyn FnMut(&mut DiagnosticList),
+    ) + 'a;
+
 pub(crate) fn validate_executable_document(
     errors: &mut DiagnosticList,
     schema: &Schema,
     document: &ExecutableDocument,
 ) {
     validate_with_or_without_schema(errors, Some(schema), document);
-    validate_with_schema(errors, schema, document);
+}
+
+pub(crate) fn validate_executable_document_with(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+    run_step: &mut RunOperationStep<'_>,
+) {
+    validate_with_or_without_schema_with(errors, Some(schema), document, run_step);
 }
 
 pub(crate) fn validate_standalone_executable(
@@ -24,29 +52,49 @@ pub(crate) fn validate_standalone_executable(
     validate_with_or_without_schema(errors, None, document);
 }
 
-fn validate_with_schema(
+pub(crate) fn validate_with_or_without_schema(
     errors: &mut DiagnosticList,
-    schema: &Schema,
+    schema: Option<&Schema>,
     document: &ExecutableDocument,
 ) {
-    let alloc = typed_arena::Arena::new();
-    let mut fields_in_set_can_merge = FieldsInSetCanMerge::new(&alloc, schema, document);
-    for operation in document.operations.iter() {
-        crate::validation::operation::validate_subscription(document, operation, errors);
-        fields_in_set_can_merge.validate_operation(operation, errors);
-    }
+    validate_with_or_without_schema_with(errors, schema, document, &mut |_, _, _, errors, run| {
+        run(errors)
+    });
 }
 
-pub(crate) fn validate_with_or_without_schema(
+fn validate_with_or_without_schema_with(
     errors: &mut DiagnosticList,
     schema: Option<&Schema>,
     document: &ExecutableDocument,
+    run_step: &mut RunOperationStep<'_>,
 ) {
     let context = ExecutableValidationContext::new(schema);
-    validate_operation_definitions(errors, document, &context);
+    for operation in document.operations.iter() {
+        run_step(
+            document,
+            operation,
+            OperationStep::Definition,
+            errors,
+            &mut |errors| validate_operation(errors, document, operation, &context),
+        );
+    }
     for def in document.fragments.values() {
         validate_fragment_used(errors, document, def);
     }
+    let alloc = typed_arena::Arena::new();
+    let mut fields_in_set_can_merge = FieldsInSetCanMerge::new(&alloc, schema, document);
+    for operation in document.operations.iter() {
+        run_step(
+            document,
+            operation,
+            OperationStep::Selections,
+            errors,
+            &mut |errors| {
+                validate_subscription(document, operation, errors);
+                fields_in_set_can_merge.validate_operation(operation, errors);
+            },
+        );
+    }
 }
 
 pub(crate) fn validate_field_set(
@@ -0,0 +1,315 @@
+use crate::ast::OperationType;
+use crate::collections::IndexMap;
+use crate::executable::collect_fragments;
+use crate::executable::Operation;
+use crate::execution::CostPolicy;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::schema::canonical::sha256_hex;
+use crate::schema::ExtendedType;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json_bytes::serde_json;
+
+/// The version of the [`OperationArchive`] format written by this version of apollo-compiler
+pub const OPERATION_ARCHIVE_VERSION: u32 = 1;
+
+/// Pre-computed analysis of every operation a client may send,
+/// for a gateway to load at startup instead of parsing, validating,
+/// and analyzing each operation again in every process.
+///
+/// Build an archive with [`OperationArchive::build`], store it with
+/// [`to_json`][Self::to_json], and read it back with [`from_json`][Self::from_json].
+/// The archive records the [content hash][Schema::content_hash] of the schema
+/// it was built against: check it with [`matches_schema`][Self::matches_schema]
+/// before trusting its contents.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::CostPolicy;
+/// use apollo_compiler::execution::OperationArchive;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { users(first: Int!): [User] } type User { name: String }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let source = "query Users($first: Int!) { users(first: $first) { name } }";
+/// let document = ExecutableDocument::parse_and_validate(&schema, source, "users.graphql").unwrap();
+///
+/// let archive = OperationArchive::build(&schema, &[document], &CostPolicy::default());
+/// let stored = archive.to_json();
+///
+/// // In another process
+/// let archive = OperationArchive::from_json(&stored).unwrap();
+/// assert!(archive.matches_schema(&schema));
+/// let operation = archive.get(&archive.operations[0].signature).unwrap();
+/// assert_eq!(operation.name.as_deref(), Some("Users"));
+/// assert_eq!(operation.estimated_cost, 10);
+/// let document = operation.to_document(&schema).unwrap();
+/// assert_eq!(document.operations.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationArchive {
+    /// The format version, [`OPERATION_ARCHIVE_VERSION`] for archives built by this crate
+    pub version: u32,
+    /// The [content hash][Schema::content_hash] of the schema the archive was built against
+    pub schema_hash: String,
+    /// Archived operations, in input order, without duplicates
+    pub operations: Vec<ArchivedOperation>,
+}
+
+/// One operation in an [`OperationArchive`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedOperation {
+    /// The name of the operation, or `None` for an anonymous operation
+    pub name: Option<String>,
+    pub operation_type: OperationType,
+    /// A document with this single operation and the fragments it uses sorted by name,
+    /// serialized on a single line
+    pub document: String,
+    /// The lowercase hexadecimal SHA-256 hash of `document`,
+    /// as used by automatic persisted queries
+    pub signature: String,
+    /// A [JSON Schema] of the variables object accepted by the operation,
+    /// as returned by [`variables_json_schema`]
+    ///
+    /// [JSON Schema]: https://json-schema.org/
+    pub variables_schema: JsonValue,
+    /// The static cost of the operation, as computed by [`CostPolicy::estimate`]
+    pub estimated_cost: u64,
+}
+
+/// Error type of [`OperationArchive::from_json`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OperationArchiveError {
+    #[error("invalid operation archive: {0}")]
+    Json(String),
+    #[error("unsupported operation archive version {0}, expected {OPERATION_ARCHIVE_VERSION}")]
+    UnsupportedVersion(u32),
+}
+
+impl OperationArchive {
+    /// Archive every operation of `documents`, which should be valid against `schema`.
+    ///
+    /// Operations with the same normalized document are archived once.
+    pub fn build(
+        schema: &Valid<Schema>,
+        documents: &[Valid<ExecutableDocument>],
+        cost: &CostPolicy,
+    ) -> Self {
+        let mut operations = IndexMap::<String, ArchivedOperation>::default();
+        for document in documents {
+            for operation in document.operations.iter() {
+                let single = single_operation_document(document, operation);
+                let text = single.serialize().no_indent().to_string();
+                let signature = sha256_hex(&text);
+                if operations.contains_key(&signature) {
+                    continue;
+                }
+                let archived = ArchivedOperation {
+                    name: operation.name.as_ref().map(|name| name.to_string()),
+                    operation_type: operation.operation_type,
+                    document: text,
+                    signature: signature.clone(),
+                    variables_schema: variables_json_schema(schema, operation),
+                    estimated_cost: cost.estimate(&single, operation),
+                };
+                operations.insert(signature, archived);
+            }
+        }
+        Self {
+            version: OPERATION_ARCHIVE_VERSION,
+            schema_hash: schema.content_hash(),
+            operations: operations.into_values().collect(),
+        }
+    }
+
+    /// Serialize to compact JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("JSON serialization should not fail")
+    }
+
+    /// Read an archive serialized by [`to_json`][Self::to_json]
+    pub fn from_json(json: &str) -> Result<Self, OperationArchiveError> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+        let Version { version } = serde_json::from_str(json)
+            .map_err(|err| OperationArchiveError::Json(err.to_string()))?;
+        if version != OPERATION_ARCHIVE_VERSION {
+            return Err(OperationArchiveError::UnsupportedVersion(version));
+        }
+        serde_json::from_str(json).map_err(|err| OperationArchiveError::Json(err.to_string()))
+    }
+
+    /// Returns whether the archive was built against a schema with the same content hash
+    pub fn matches_schema(&self, schema: &Schema) -> bool {
+        self.schema_hash == schema.content_hash()
+    }
+
+    /// Returns the archived operation with the given [signature][ArchivedOperation::signature]
+    pub fn get(&self, signature: &str) -> Option<&ArchivedOperation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.signature == signature)
+    }
+}
+
+impl ArchivedOperation {
+    /// Parse and validate the archived document against `schema`
+    #[allow(clippy::result_large_err)] // Typically called once per operation at startup
+    pub fn to_document(
+        &self,
+        schema: &Valid<Schema>,
+    ) -> Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>> {
+        let path = match &self.name {
+            Some(name) => format!("{name}.graphql"),
+            None => "operation.graphql".to_owned(),
+        };
+        ExecutableDocument::parse_and_validate(schema, &self.document, path)
+    }
+}
+
+/// Returns a [JSON Schema] (draft 2020-12) of the variables object accepted by `operation`.
+///
+/// Variables of non-null types without a default value are required.
+/// Input object types are in `$defs` and referenced with `$ref`,
+/// which supports recursive input types.
+/// Values of custom scalars are not constrained.
+///
+/// [JSON Schema]: https://json-schema.org/
+pub fn variables_json_schema(schema: &Schema, operation: &Operation) -> JsonValue {
+    let mut defs = JsonMap::new();
+    let mut properties = JsonMap::new();
+    let mut required = Vec::new();
+    for variable in &operation.variables {
+        properties.insert(
+            variable.name.as_str(),
+            type_json_schema(schema, &variable.ty, &mut defs),
+        );
+        if variable.ty.is_non_null() && variable.default_value.is_none() {
+            required.push(JsonValue::from(variable.name.as_str()));
+        }
+    }
+    let mut object = object([
+        (
+            "$schema",
+            "https://json-schema.org/draft/2020-12/schema".into(),
+        ),
+        ("type", "object".into()),
+        ("properties", properties.into()),
+        ("required", required.into()),
+        ("additionalProperties", false.into()),
+    ]);
+    if !defs.is_empty() {
+        object.as_object_mut().unwrap().insert("$defs", defs.into());
+    }
+    object
+}
+
+/// Builds a JSON object, keeping the order of `entries`
+fn object<const N: usize>(entries: [(&str, JsonValue); N]) -> JsonValue {
+    JsonValue::Object(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.into(), value))
+            .collect(),
+    )
+}
+
+fn type_json_schema(schema: &Schema, ty: &Type, defs: &mut JsonMap) -> JsonValue {
+    let non_null = match ty {
+        Type::Named(name) | Type::NonNullNamed(name) => named_type_json_schema(schema, name, defs),
+        Type::List(item) | Type::NonNullList(item) => {
+            // Input coercion accepts a single item for a list
+            let item = type_json_schema(schema, item, defs);
+            let array = object([("type", "array".into()), ("items", item.clone())]);
+            object([("anyOf", vec![array, item].into())])
+        }
+    };
+    if ty.is_non_null() {
+        non_null
+    } else {
+        let null = object([("type", "null".into())]);
+        object([("anyOf", vec![non_null, null].into())])
+    }
+}
+
+fn named_type_json_schema(schema: &Schema, name: &str, defs: &mut JsonMap) -> JsonValue {
+    match schema.types.get(name) {
+        Some(ExtendedType::Scalar(def)) if def.is_built_in() => match name {
+            "Int" => object([
+                ("type", "integer".into()),
+                ("minimum", i32::MIN.into()),
+                ("maximum", i32::MAX.into()),
+            ]),
+            "Float" => object([("type", "number".into())]),
+            "String" => object([("type", "string".into())]),
+            "Boolean" => object([("type", "boolean".into())]),
+            "ID" => object([("type", vec!["string", "integer"].into())]),
+            _ => object([]),
+        },
+        Some(ExtendedType::Enum(def)) => {
+            let values: Vec<_> = def
+                .values
+                .keys()
+                .map(|value| JsonValue::from(value.as_str()))
+                .collect();
+            object([("enum", values.into())])
+        }
+        Some(ExtendedType::InputObject(def)) => {
+            if !defs.contains_key(name) {
+                // Insert a placeholder first in case of recursion
+                defs.insert(name, JsonValue::Null);
+                let mut properties = JsonMap::new();
+                let mut required = Vec::new();
+                for (field_name, field) in &def.fields {
+                    properties.insert(
+                        field_name.as_str(),
+                        type_json_schema(schema, &field.ty, defs),
+                    );
+                    if field.is_required() {
+                        required.push(JsonValue::from(field_name.as_str()));
+                    }
+                }
+                let def = object([
+                    ("type", "object".into()),
+                    ("properties", properties.into()),
+                    ("required", required.into()),
+                    ("additionalProperties", false.into()),
+                ]);
+                defs.insert(name, def);
+            }
+            object([("$ref", format!("#/$defs/{name}").into())])
+        }
+        // Custom scalar, or not an input type in an invalid document
+        _ => object([]),
+    }
+}
+
+/// Returns a document with `operation` and the fragments it uses, sorted by name
+fn single_operation_document(
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+) -> Valid<ExecutableDocument> {
+    let mut fragments = IndexMap::default();
+    collect_fragments(document, &operation.selection_set, &mut fragments);
+    fragments.sort_keys();
+    let mut single = ExecutableDocument::new();
+    single.sources = document.sources.clone();
+    single.operations.insert(operation.clone());
+    single.fragments = fragments;
+    Valid::assume_valid(single)
+}
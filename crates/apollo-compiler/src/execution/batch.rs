@@ -0,0 +1,225 @@
+use crate::collections::HashMap;
+use crate::executable::Operation;
+use crate::execution::coerce_variable_values;
+use crate::execution::execute_with_json_data;
+use crate::execution::introspection_execute::ImplementersCache;
+use crate::execution::GraphQLError;
+use crate::execution::JsonMap;
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use crate::execution::SchemaIntrospectionQuery;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+/// A GraphQL request, as found in the body of an HTTP request.
+///
+/// A batched HTTP request has a JSON array of these.
+///
+/// ```
+/// use apollo_compiler::execution::Request;
+///
+/// let batch: Vec<Request> = serde_json::from_str(r#"[
+///     { "query": "{ a }" },
+///     { "query": "query Q($x: Int) { b(x: $x) }", "operationName": "Q", "variables": { "x": 1 } }
+/// ]"#).unwrap();
+/// assert_eq!(batch[1].operation_name.as_deref(), Some("Q"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+    /// Absent and `null` are both deserialized as an empty map
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub variables: JsonMap,
+}
+
+fn null_as_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<JsonMap, D::Error> {
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Execute a batch of requests against static JSON data, with up to `max_concurrency` threads.
+///
+/// Each request is parsed, validated, and has its variables coerced,
+/// then is executed as with [`execute_with_json_data`] with the same `root_value`.
+/// Schema introspection is executed as with [`SchemaIntrospectionQuery::split_and_execute`].
+/// Invalid requests get a response with [request errors] without affecting other requests.
+///
+/// Requests in a batch share work: identical query strings are parsed and validated once,
+/// and data about the schema needed by introspection is computed at most once.
+///
+/// Responses are returned in the same order as requests.
+/// With `max_concurrency` of 0 or 1, requests are executed in order on the current thread.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::execute_batch_with_json_data;
+/// use apollo_compiler::execution::Request;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int b: Int }", "schema.graphql").unwrap();
+/// let root_value = serde_json::from_str(r#"{ "a": 1, "b": 2 }"#).unwrap();
+/// let requests: Vec<Request> = serde_json::from_str(r#"[
+///     { "query": "{ a }" },
+///     { "query": "{ c }" },
+///     { "query": "{ b __typename }" }
+/// ]"#).unwrap();
+///
+/// let responses = execute_batch_with_json_data(&schema, &requests, &root_value, 4);
+/// assert_eq!(
+///     serde_json::to_string(&responses[0]).unwrap(),
+///     r#"{"data":{"a":1}}"#,
+/// );
+/// assert!(!responses[1].errors.is_empty());
+/// assert_eq!(
+///     serde_json::to_string(&responses[2]).unwrap(),
+///     r#"{"data":{"b":2,"__typename":"Query"}}"#,
+/// );
+/// ```
+///
+/// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+pub fn execute_batch_with_json_data(
+    schema: &Valid<Schema>,
+    requests: &[Request],
+    root_value: &JsonMap,
+    max_concurrency: usize,
+) -> Vec<Response> {
+    let cache = BatchCache::new(schema, requests.iter().map(|r| r.query.as_str()));
+    execute_batch(requests, max_concurrency, |request| {
+        cache.execute_request(
+            &request.query,
+            request.operation_name.as_deref(),
+            &request.variables,
+            |document, operation, variable_values| {
+                execute_with_json_data(schema, document, operation, variable_values, root_value)
+            },
+        )
+    })
+}
+
+/// Run `execute_one` for each request on up to `max_concurrency` scoped threads,
+/// and return responses in request order.
+pub(crate) fn execute_batch(
+    requests: &[Request],
+    max_concurrency: usize,
+    execute_one: impl Fn(&Request) -> Response + Sync,
+) -> Vec<Response> {
+    let workers = max_concurrency.min(requests.len());
+    if workers <= 1 {
+        return requests.iter().map(execute_one).collect();
+    }
+    let next_index = AtomicUsize::new(0);
+    let responses: Vec<OnceLock<Response>> = requests.iter().map(|_| OnceLock::new()).collect();
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(request) = requests.get(index) else {
+                    break;
+                };
+                let _ = responses[index].set(execute_one(request));
+            });
+        }
+    });
+    responses
+        .into_iter()
+        .map(|response| response.into_inner().expect("every request was executed"))
+        .collect()
+}
+
+/// Work shared by requests of a batch
+pub(crate) struct BatchCache<'a> {
+    schema: &'a Valid<Schema>,
+    /// Keyed by query string, initialized by the first request that needs it
+    documents: HashMap<&'a str, OnceLock<Result<Valid<ExecutableDocument>, DiagnosticList>>>,
+    implementers_map: ImplementersCache,
+}
+
+impl<'a> BatchCache<'a> {
+    pub(crate) fn new(
+        schema: &'a Valid<Schema>,
+        queries: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self {
+            schema,
+            documents: queries
+                .into_iter()
+                .map(|query| (query, OnceLock::new()))
+                .collect(),
+            implementers_map: OnceLock::new(),
+        }
+    }
+
+    /// Parse and validate the query (or reuse a previous result for the same query),
+    /// find the operation, coerce variables, and execute schema introspection.
+    /// `execute_operation` is called for the rest of the operation, if any.
+    ///
+    /// `query` must be one of the queries this cache was created with.
+    pub(crate) fn execute_request(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variable_values: &JsonMap,
+        execute_operation: impl FnOnce(
+            &Valid<ExecutableDocument>,
+            &Node<Operation>,
+            &Valid<JsonMap>,
+        ) -> Response,
+    ) -> Response {
+        let document = self.documents[query].get_or_init(|| {
+            ExecutableDocument::parse_and_validate(self.schema, query, "request.graphql")
+                .map_err(|with_errors| with_errors.errors)
+        });
+        let document = match document {
+            Ok(document) => document,
+            Err(errors) => {
+                return Response {
+                    errors: errors.to_graphql_errors(),
+                    data: ResponseData::Absent,
+                    extensions: Default::default(),
+                }
+            }
+        };
+        let Ok(operation) = document.operations.get(operation_name) else {
+            let message = match operation_name {
+                Some(name) => format!("operation `{name}` not found in the request"),
+                None => "operation name is required in a request with many operations".into(),
+            };
+            return Response::from_request_error(GraphQLError::new(
+                message,
+                None,
+                &document.sources,
+            ));
+        };
+        let variable_values = match coerce_variable_values(self.schema, operation, variable_values)
+        {
+            Ok(variable_values) => variable_values,
+            Err(err) => return err.into_response(&document.sources),
+        };
+        SchemaIntrospectionQuery::split_and_execute_with_cache(
+            self.schema,
+            document,
+            operation,
+            &variable_values,
+            &self.implementers_map,
+            |document| {
+                let operation = document
+                    .operations
+                    .get(operation.name.as_deref())
+                    .expect("split document keeps the operation");
+                execute_operation(document, operation, &variable_values)
+            },
+        )
+    }
+}
@@ -0,0 +1,286 @@
+use crate::collections::IndexMap;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use crate::schema::FieldDefinition;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configuration for computing the cost of an operation
+/// and reporting it in the `cost` entry of [`Response::extensions`].
+///
+/// The cost of a field is its weight, plus the cost of its sub-selections for composite types.
+/// For list types this is multiplied by the number of list items.
+/// The weight of a field is given by a `@cost(weight: Int!)` directive
+/// on its schema definition if present, or by [`object_cost`][Self::object_cost]
+/// or [`leaf_cost`][Self::leaf_cost] otherwise.
+///
+/// The static (estimated) cost only looks at the operation and schema.
+/// Since list sizes are not known yet, it uses a `@listSize(assumedSize: Int!)` directive
+/// on the schema definition of a list field if present,
+/// or [`assumed_list_size`][Self::assumed_list_size] otherwise.
+/// Every field is counted regardless of `@skip`, `@include`, or fragment type conditions,
+/// so the estimate is an upper bound as long as lists are no longer than assumed.
+///
+/// The actual cost looks at response data after execution
+/// and only counts fields and list items that are present.
+///
+/// ```
+/// use apollo_compiler::execution::CostPolicy;
+/// use apollo_compiler::execution::Response;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { users: [User] } type User { name: String }",
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let document =
+///     ExecutableDocument::parse_and_validate(&schema, "{ users { name } }", "query.graphql")
+///         .unwrap();
+/// let operation = document.operations.get(None).unwrap();
+/// let mut response: Response = serde_json::from_str(
+///     r#"{"data": {"users": [{"name": "Alice"}, {"name": "Bob"}]}}"#,
+/// )
+/// .unwrap();
+///
+/// let cost = CostPolicy::default().add_to_response(&schema, &document, operation, &mut response);
+/// assert_eq!(cost.estimated, 10);
+/// assert_eq!(cost.actual, 2);
+/// assert_eq!(
+///     serde_json::to_string(&response.extensions).unwrap(),
+///     r#"{"cost":{"estimated":10,"actual":2}}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostPolicy {
+    /// Weight of a field of object, interface, or union type. Defaults to 1.
+    pub object_cost: u64,
+
+    /// Weight of a field of scalar or enum type. Defaults to 0.
+    pub leaf_cost: u64,
+
+    /// Number of items assumed in each list for the estimated cost,
+    /// when a field has no `@listSize` directive. Defaults to 10.
+    pub assumed_list_size: u64,
+}
+
+/// The cost of an operation as computed by [`CostPolicy`],
+/// serialized as the `cost` entry of [`Response::extensions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryCost {
+    /// Static cost computed before execution
+    pub estimated: u64,
+
+    /// Cost computed from response data after execution
+    pub actual: u64,
+}
+
+impl Default for CostPolicy {
+    fn default() -> Self {
+        Self {
+            object_cost: 1,
+            leaf_cost: 0,
+            assumed_list_size: 10,
+        }
+    }
+}
+
+impl CostPolicy {
+    /// Compute the static cost of an operation, before executing it
+    pub fn estimate(&self, document: &Valid<ExecutableDocument>, operation: &Operation) -> u64 {
+        let mut cost = 0_u64;
+        for fields in group_fields(document, None, None, [&operation.selection_set]).values() {
+            cost = cost.saturating_add(self.estimate_field(document, fields));
+        }
+        cost
+    }
+
+    fn estimate_field(&self, document: &Valid<ExecutableDocument>, fields: &[&Field]) -> u64 {
+        let definition = &fields[0].definition;
+        let mut cost = self.weight(definition, !fields[0].selection_set.selections.is_empty());
+        let selection_sets = fields.iter().map(|field| &field.selection_set);
+        for nested in group_fields(document, None, None, selection_sets).values() {
+            cost = cost.saturating_add(self.estimate_field(document, nested));
+        }
+        let mut ty = &definition.ty;
+        while ty.is_list() {
+            cost = cost.saturating_mul(self.list_size(definition));
+            ty = ty.item_type();
+        }
+        cost
+    }
+
+    /// Compute the cost of an operation from the data in its response, after executing it
+    pub fn actual(
+        &self,
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+        operation: &Operation,
+        data: &ResponseData,
+    ) -> u64 {
+        match data {
+            ResponseData::Object(object) => {
+                self.actual_object(schema, document, [&operation.selection_set], object)
+            }
+            ResponseData::Null | ResponseData::Absent => 0,
+        }
+    }
+
+    fn actual_object<'a>(
+        &self,
+        schema: &Valid<Schema>,
+        document: &'a Valid<ExecutableDocument>,
+        selection_sets: impl IntoIterator<Item = &'a SelectionSet>,
+        object: &JsonMap,
+    ) -> u64 {
+        let runtime_type = object.get("__typename").and_then(|name| name.as_str());
+        let mut cost = 0_u64;
+        for (response_key, fields) in
+            group_fields(document, Some(schema), runtime_type, selection_sets)
+        {
+            if let Some(value) = object.get(response_key.as_str()) {
+                cost = cost.saturating_add(self.actual_value(schema, document, &fields, value));
+            }
+        }
+        cost
+    }
+
+    fn actual_value(
+        &self,
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+        fields: &[&Field],
+        value: &JsonValue,
+    ) -> u64 {
+        let definition = &fields[0].definition;
+        match value {
+            JsonValue::Null => 0,
+            JsonValue::Array(items) => items.iter().fold(0_u64, |cost, item| {
+                cost.saturating_add(self.actual_value(schema, document, fields, item))
+            }),
+            JsonValue::Object(object) => {
+                let selection_sets = fields.iter().map(|field| &field.selection_set);
+                self.weight(definition, true)
+                    .saturating_add(self.actual_object(schema, document, selection_sets, object))
+            }
+            JsonValue::Bool(_) | JsonValue::Number(_) | JsonValue::String(_) => {
+                self.weight(definition, false)
+            }
+        }
+    }
+
+    /// Compute both the static and actual cost of an operation,
+    /// and insert them in the `cost` entry of [`Response::extensions`].
+    pub fn add_to_response(
+        &self,
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+        operation: &Operation,
+        response: &mut Response,
+    ) -> QueryCost {
+        let cost = QueryCost {
+            estimated: self.estimate(document, operation),
+            actual: self.actual(schema, document, operation, &response.data),
+        };
+        cost.insert_into(response);
+        cost
+    }
+
+    fn weight(&self, definition: &FieldDefinition, is_composite: bool) -> u64 {
+        directive_argument(definition, "cost", "weight").unwrap_or(if is_composite {
+            self.object_cost
+        } else {
+            self.leaf_cost
+        })
+    }
+
+    fn list_size(&self, definition: &FieldDefinition) -> u64 {
+        directive_argument(definition, "listSize", "assumedSize").unwrap_or(self.assumed_list_size)
+    }
+}
+
+impl QueryCost {
+    /// Insert this cost in the `cost` entry of [`Response::extensions`],
+    /// replacing any existing entry.
+    pub fn insert_into(&self, response: &mut Response) {
+        let mut cost = JsonMap::new();
+        cost.insert("estimated", self.estimated.into());
+        cost.insert("actual", self.actual.into());
+        response.extensions.insert("cost", cost.into());
+    }
+}
+
+fn directive_argument(
+    definition: &FieldDefinition,
+    directive: &str,
+    argument: &str,
+) -> Option<u64> {
+    let value = definition
+        .directives
+        .get(directive)?
+        .specified_argument_by_name(argument)?;
+    u64::try_from(value.to_i32()?).ok()
+}
+
+/// Group fields by response key like [`CollectFields()`], without looking at variables.
+///
+/// Fragments are only filtered by type condition when `runtime_type` is known.
+///
+/// [`CollectFields()`]: https://spec.graphql.org/October2021/#CollectFields()
+pub(crate) fn group_fields<'a>(
+    document: &'a Valid<ExecutableDocument>,
+    schema: Option<&Valid<Schema>>,
+    runtime_type: Option<&str>,
+    selection_sets: impl IntoIterator<Item = &'a SelectionSet>,
+) -> IndexMap<&'a Name, Vec<&'a Field>> {
+    let applies = |type_condition: &str| match (schema, runtime_type) {
+        (Some(schema), Some(runtime_type)) => {
+            type_condition == runtime_type || schema.is_subtype(type_condition, runtime_type)
+        }
+        _ => true,
+    };
+    let mut grouped = IndexMap::<_, Vec<_>>::default();
+    // Validation ensures that `Valid<ExecutableDocument>` does not contain fragment cycles
+    let mut stack: Vec<_> = selection_sets
+        .into_iter()
+        .flat_map(|set| &set.selections)
+        .collect();
+    stack.reverse();
+    while let Some(selection) = stack.pop() {
+        match selection {
+            Selection::Field(field) => grouped
+                .entry(field.response_key())
+                .or_default()
+                .push(field.as_ref()),
+            Selection::InlineFragment(inline) => {
+                if inline
+                    .type_condition
+                    .as_ref()
+                    .is_none_or(|condition| applies(condition))
+                {
+                    stack.extend(inline.selection_set.selections.iter().rev())
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(def) = document.fragments.get(&spread.fragment_name) {
+                    if applies(def.type_condition()) {
+                        stack.extend(def.selection_set.selections.iter().rev())
+                    }
+                }
+            }
+            Selection::Invalid(_) => {}
+        }
+    }
+    grouped
+}
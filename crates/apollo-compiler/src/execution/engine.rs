@@ -10,7 +10,9 @@ use crate::execution::result_coercion::complete_value;
 use crate::execution::GraphQLError;
 use crate::execution::JsonMap;
 use crate::execution::JsonValue;
+use crate::execution::NumberPrecision;
 use crate::execution::ResponseDataPathElement;
+use crate::execution::ResponsePath;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::schema::ExtendedType;
@@ -29,7 +31,6 @@ pub(crate) enum ExecutionMode {
     /// Allowed to resolve fields in any order, including in parellel
     Normal,
     /// Top-level fields of a mutation operation must be executed in order
-    #[allow(unused)]
     Sequential,
 }
 
@@ -55,6 +56,7 @@ pub(crate) fn execute_selection_set<'a>(
     errors: &mut Vec<GraphQLError>,
     path: LinkedPath<'_>,
     mode: ExecutionMode,
+    precision: NumberPrecision,
     object_type: &ObjectType,
     object_value: &ObjectValue<'_>,
     selections: impl IntoIterator<Item = &'a Selection>,
@@ -102,6 +104,7 @@ pub(crate) fn execute_selection_set<'a>(
                 errors,
                 Some(&field_path),
                 mode,
+                precision,
                 object_value,
                 field_def,
                 fields,
@@ -114,7 +117,7 @@ pub(crate) fn execute_selection_set<'a>(
 
 /// <https://spec.graphql.org/October2021/#CollectFields()>
 #[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
-fn collect_fields<'a>(
+pub(crate) fn collect_fields<'a>(
     schema: &Schema,
     document: &'a ExecutableDocument,
     variable_values: &Valid<JsonMap>,
@@ -171,12 +174,13 @@ fn collect_fields<'a>(
                     grouped_fields,
                 )
             }
+            Selection::Invalid(_) => {}
         }
     }
 }
 
 /// <https://spec.graphql.org/October2021/#DoesFragmentTypeApply()>
-fn does_fragment_type_apply(
+pub(crate) fn does_fragment_type_apply(
     schema: &Schema,
     object_type: &ObjectType,
     fragment_type: &Name,
@@ -192,7 +196,7 @@ fn does_fragment_type_apply(
     }
 }
 
-fn eval_if_arg(
+pub(crate) fn eval_if_arg(
     selection: &Selection,
     directive_name: &str,
     variable_values: &Valid<JsonMap>,
@@ -218,22 +222,20 @@ fn execute_field(
     errors: &mut Vec<GraphQLError>,
     path: LinkedPath<'_>,
     mode: ExecutionMode,
+    precision: NumberPrecision,
     object_value: &ObjectValue<'_>,
     field_def: &FieldDefinition,
     fields: &[&Field],
 ) -> Result<JsonValue, PropagateNull> {
     let field = fields[0];
-    let argument_values = match coerce_argument_values(
-        schema,
-        document,
-        variable_values,
-        errors,
-        path,
-        field_def,
-        field,
-    ) {
+    // Client-controlled nullability changes where nulls propagate to
+    let ty = field.response_ty();
+    let argument_values = match coerce_argument_values(schema, variable_values, field_def, field) {
         Ok(argument_values) => argument_values,
-        Err(PropagateNull) => return try_nullify(&field_def.ty, Err(PropagateNull)),
+        Err(err) => {
+            errors.push(err.into_field_error(path, &document.sources));
+            return try_nullify(&ty, Err(PropagateNull));
+        }
     };
     let resolved_result = object_value.resolve_field(&field.name, &argument_values);
     let completed_result = match resolved_result {
@@ -244,7 +246,8 @@ fn execute_field(
             errors,
             path,
             mode,
-            field.ty(),
+            precision,
+            &ty,
             resolved,
             fields,
         ),
@@ -258,7 +261,7 @@ fn execute_field(
             Err(PropagateNull)
         }
     };
-    try_nullify(&field_def.ty, completed_result)
+    try_nullify(&ty, completed_result)
 }
 
 /// Try to insert a propagated null if possible, or keep propagating it.
@@ -280,8 +283,8 @@ pub(crate) fn try_nullify(
     }
 }
 
-pub(crate) fn path_to_vec(mut link: LinkedPath<'_>) -> Vec<ResponseDataPathElement> {
-    let mut path = Vec::new();
+pub(crate) fn path_to_vec(mut link: LinkedPath<'_>) -> ResponsePath {
+    let mut path = ResponsePath::new();
     while let Some(node) = link {
         path.push(node.element.clone());
         link = node.next;
@@ -0,0 +1,198 @@
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use std::fmt;
+
+/// A media type for GraphQL responses over HTTP,
+/// as defined by the [GraphQL over HTTP] specification
+///
+/// [GraphQL over HTTP]: https://graphql.github.io/graphql-over-http/draft/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseMediaType {
+    /// `application/graphql-response+json`: the status code reflects whether execution started
+    GraphQLResponseJson,
+    /// `application/json`, for legacy clients: the status code is 200 for any GraphQL response
+    Json,
+}
+
+/// The status code and headers recommended by the [GraphQL over HTTP] specification
+/// for an HTTP response, returned by [`Response::http_head`] or [`HttpRequestRejection::http_head`].
+///
+/// [GraphQL over HTTP]: https://graphql.github.io/graphql-over-http/draft/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponseHead {
+    pub status: u16,
+    /// Header names and values, such as `Content-Type`
+    pub headers: Vec<(&'static str, String)>,
+}
+
+/// A reason to reject an HTTP request before it becomes a GraphQL request,
+/// so there is no GraphQL [`Response`] to send
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpRequestRejection {
+    /// The body or query string could not be decoded, or does not have a string `query`.
+    /// Responds with 400 Bad Request.
+    MalformedRequest,
+    /// A mutation was requested with the `GET` method.
+    /// Responds with 405 Method Not Allowed and `Allow: POST`.
+    MutationOverGet,
+    /// The `Content-Type` of a `POST` request is not supported.
+    /// Responds with 415 Unsupported Media Type.
+    UnsupportedMediaType,
+    /// The `Accept` header does not allow any supported media type
+    /// (see [`ResponseMediaType::from_accept`]).
+    /// Responds with 406 Not Acceptable.
+    NotAcceptable,
+}
+
+impl ResponseMediaType {
+    /// The media type without parameters
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::GraphQLResponseJson => "application/graphql-response+json",
+            Self::Json => "application/json",
+        }
+    }
+
+    /// The value of the `Content-Type` response header
+    pub fn content_type(self) -> String {
+        format!("{}; charset=utf-8", self.as_str())
+    }
+
+    /// Select a media type for the response from the `Accept` request header, if any.
+    ///
+    /// The supported media type with the highest quality (`q` parameter) is selected,
+    /// with `application/graphql-response+json` preferred in case of a tie
+    /// or for wildcards like `*/*`.
+    /// Without an `Accept` header, `application/json` is selected for compatibility
+    /// with legacy clients.
+    ///
+    /// Returns `None` if no supported media type is acceptable: respond with
+    /// [`HttpRequestRejection::NotAcceptable`].
+    pub fn from_accept(accept: Option<&str>) -> Option<Self> {
+        let Some(accept) = accept else {
+            return Some(Self::Json);
+        };
+        let mut best: Option<(f32, Self)> = None;
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media_range = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+            let mut quality = 1.0;
+            for parameter in parts {
+                if let Some((name, value)) = parameter.split_once('=') {
+                    if name.trim().eq_ignore_ascii_case("q") {
+                        quality = value.trim().parse().unwrap_or(0.0);
+                    }
+                }
+            }
+            let media_type = match media_range.as_str() {
+                "application/graphql-response+json" | "application/*" | "*/*" => {
+                    Self::GraphQLResponseJson
+                }
+                "application/json" => Self::Json,
+                _ => continue,
+            };
+            if quality <= 0.0 {
+                continue;
+            }
+            let is_better = match best {
+                None => true,
+                Some((best_quality, best_type)) => {
+                    quality > best_quality
+                        || (quality == best_quality
+                            && media_type == Self::GraphQLResponseJson
+                            && best_type == Self::Json)
+                }
+            };
+            if is_better {
+                best = Some((quality, media_type))
+            }
+        }
+        best.map(|(_, media_type)| media_type)
+    }
+}
+
+impl fmt::Display for ResponseMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Response {
+    /// Returns the status code and headers recommended by the [GraphQL over HTTP] specification
+    /// for sending this response with the given media type.
+    ///
+    /// * With `application/json`, the status code is 200 for every response,
+    ///   including [request errors] where `data` is absent.
+    /// * With `application/graphql-response+json`, the status code is 200 if `data` is present,
+    ///   even if it is `null` because of a field error.
+    ///   If `data` is absent, execution did not start because of a request error
+    ///   (such as a syntax or validation error, or invalid variables)
+    ///   and the status code is 400.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::execution::GraphQLError;
+    /// use apollo_compiler::execution::Response;
+    /// use apollo_compiler::execution::ResponseMediaType;
+    ///
+    /// let response = Response::from_request_error(GraphQLError::new(
+    ///     "syntax error", None, &Default::default(),
+    /// ));
+    /// let accept = "application/graphql-response+json, application/json;q=0.9";
+    /// let media_type = ResponseMediaType::from_accept(Some(accept)).unwrap();
+    /// let head = response.http_head(media_type);
+    /// assert_eq!(head.status, 400);
+    /// assert_eq!(
+    ///     head.headers,
+    ///     [("Content-Type", "application/graphql-response+json; charset=utf-8".to_owned())],
+    /// );
+    ///
+    /// let head = response.http_head(ResponseMediaType::Json);
+    /// assert_eq!(head.status, 200);
+    /// ```
+    ///
+    /// [GraphQL over HTTP]: https://graphql.github.io/graphql-over-http/draft/
+    /// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+    pub fn http_head(&self, media_type: ResponseMediaType) -> HttpResponseHead {
+        let status = match (media_type, &self.data) {
+            (ResponseMediaType::Json, _) => 200,
+            (
+                ResponseMediaType::GraphQLResponseJson,
+                ResponseData::Object(_) | ResponseData::Null,
+            ) => 200,
+            (ResponseMediaType::GraphQLResponseJson, ResponseData::Absent) => 400,
+        };
+        HttpResponseHead {
+            status,
+            headers: vec![("Content-Type", media_type.content_type())],
+        }
+    }
+}
+
+impl HttpRequestRejection {
+    /// Returns the status code and headers recommended by the [GraphQL over HTTP] specification
+    /// for rejecting a request
+    ///
+    /// [GraphQL over HTTP]: https://graphql.github.io/graphql-over-http/draft/
+    pub fn http_head(self) -> HttpResponseHead {
+        match self {
+            Self::MalformedRequest => HttpResponseHead {
+                status: 400,
+                headers: Vec::new(),
+            },
+            Self::MutationOverGet => HttpResponseHead {
+                status: 405,
+                headers: vec![("Allow", "POST".to_owned())],
+            },
+            Self::UnsupportedMediaType => HttpResponseHead {
+                status: 415,
+                headers: Vec::new(),
+            },
+            Self::NotAcceptable => HttpResponseHead {
+                status: 406,
+                headers: Vec::new(),
+            },
+        }
+    }
+}
@@ -4,18 +4,18 @@ use crate::collections::HashMap;
 use crate::executable::Field;
 use crate::executable::Operation;
 use crate::execution::engine::LinkedPath;
-use crate::execution::engine::PropagateNull;
 use crate::execution::GraphQLError;
 use crate::execution::JsonMap;
 use crate::execution::JsonValue;
 use crate::execution::Response;
+use crate::execution::ScalarRegistry;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::schema::ExtendedType;
 use crate::schema::FieldDefinition;
+use crate::schema::InputObjectType;
 use crate::validation::SuspectedValidationBug;
 use crate::validation::Valid;
-use crate::ExecutableDocument;
 use crate::Node;
 use crate::Schema;
 
@@ -29,23 +29,117 @@ pub enum InputCoercionError {
     },
 }
 
+/// The value of a variable, argument, or input object field in coerced values,
+/// distinguishing an explicit `null` from a value that was not provided.
+///
+/// In maps returned by [`coerce_variable_values`] and
+/// [`Field::effective_arguments`][crate::executable::Field::effective_arguments],
+/// a nullable input that was not provided and has no default value is a missing key,
+/// whereas one explicitly set to `null` is present with a [`JsonValue::Null`] value.
+/// This enum makes that distinction explicit, for example to implement partial updates
+/// where an absent input field is left unchanged but a `null` one is cleared.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::CoercedValue;
+///
+/// let arguments = serde_json::from_str(r#"{ "patch": { "email": null, "name": "Alice" } }"#).unwrap();
+/// let patch = CoercedValue::get(&arguments, "patch");
+/// assert_eq!(patch.field("email"), CoercedValue::Null);
+/// assert_eq!(patch.field("name").value().unwrap(), "Alice");
+/// assert_eq!(patch.field("age"), CoercedValue::Absent);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoercedValue<'a> {
+    /// Not provided, and there is no default value
+    Absent,
+    /// Explicitly `null`, or defaulting to `null`
+    Null,
+    /// A non-null value
+    Value(&'a JsonValue),
+}
+
+impl<'a> CoercedValue<'a> {
+    /// Look up `key` in a map of coerced values
+    pub fn get(map: &'a JsonMap, key: &str) -> Self {
+        match map.get(key) {
+            None => Self::Absent,
+            Some(JsonValue::Null) => Self::Null,
+            Some(value) => Self::Value(value),
+        }
+    }
+
+    /// Look up an input object field of this value.
+    ///
+    /// Returns [`Absent`][Self::Absent] if this value is not an input object.
+    pub fn field(&self, key: &str) -> Self {
+        match self {
+            Self::Value(JsonValue::Object(object)) => Self::get(object, key),
+            _ => Self::Absent,
+        }
+    }
+
+    /// Returns the value if it is neither absent nor `null`
+    pub fn value(&self) -> Option<&'a JsonValue> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::Absent | Self::Null => None,
+        }
+    }
+
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+}
+
 /// Coerce the values of variables from a GraphQL request to the types expected by the operation.
 ///
 /// If type coercion fails, a request error is returned and the request must not be executed.
 ///
+/// Nullable variables and input object fields that are not provided and have no default value
+/// are absent from the result, unlike those provided as `null`.
+/// See [`CoercedValue`].
+///
 /// This is [CoerceVariableValues()](https://spec.graphql.org/October2021/#CoerceVariableValues())
 /// in the GraphQL specification.
+///
+/// Values of custom scalars are accepted as-is.
+/// To validate them, use [`coerce_variable_values_with_scalars`] instead.
 pub fn coerce_variable_values(
     schema: &Valid<Schema>,
     operation: &Operation,
     values: &JsonMap,
+) -> Result<Valid<JsonMap>, InputCoercionError> {
+    coerce_variable_values_with_scalars(schema, operation, values, &ScalarRegistry::new())
+}
+
+/// Like [`coerce_variable_values`], but values of custom scalars that have a spec
+/// in `scalars` are validated and coerced by that spec.
+pub fn coerce_variable_values_with_scalars(
+    schema: &Valid<Schema>,
+    operation: &Operation,
+    values: &JsonMap,
+    scalars: &ScalarRegistry,
 ) -> Result<Valid<JsonMap>, InputCoercionError> {
     let mut coerced_values = JsonMap::new();
     for variable_def in &operation.variables {
         let name = variable_def.name.as_str();
         if let Some((key, value)) = values.get_key_value(name) {
-            let value =
-                coerce_variable_value(schema, "variable", "", "", name, &variable_def.ty, value)?;
+            let value = coerce_variable_value(
+                schema,
+                scalars,
+                "variable",
+                "",
+                "",
+                name,
+                &variable_def.ty,
+                value,
+            )?;
             coerced_values.insert(key.clone(), value);
         } else if let Some(default) = &variable_def.default_value {
             let value = graphql_value_to_json("variable default value", "", "", name, default)?;
@@ -67,8 +161,9 @@ pub fn coerce_variable_values(
 }
 
 #[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
-fn coerce_variable_value(
+pub(crate) fn coerce_variable_value(
     schema: &Valid<Schema>,
+    scalars: &ScalarRegistry,
     kind: &str,
     parent: &str,
     sep: &str,
@@ -95,7 +190,9 @@ fn coerce_variable_value(
                 // If not an array, treat the value as an array of size one:
                 .unwrap_or(std::slice::from_ref(value))
                 .iter()
-                .map(|item| coerce_variable_value(schema, kind, parent, sep, name, inner, item))
+                .map(|item| {
+                    coerce_variable_value(schema, scalars, kind, parent, sep, name, inner, item)
+                })
                 .collect();
         }
         Type::Named(ty_name) | Type::NonNullNamed(ty_name) => ty_name,
@@ -149,8 +246,14 @@ fn coerce_variable_value(
             }
             _ => {
                 // Custom scalar
-                // TODO: have a hook for coercion of custom scalars?
-                return Ok(value.clone());
+                return scalars.coerce_input(ty_name, value).map_err(|message| {
+                    InputCoercionError::ValueError {
+                        message: format!(
+                            "Could not coerce {kind} {parent}{sep}{name} to type {ty_name}: {message}"
+                        ),
+                        location: None,
+                    }
+                });
             }
         },
         ExtendedType::Enum(ty_def) => {
@@ -181,6 +284,7 @@ fn coerce_variable_value(
                     if let Some(field_value) = object.get_mut(field_name.as_str()) {
                         *field_value = coerce_variable_value(
                             schema,
+                            scalars,
                             "input field",
                             ty_name,
                             ".",
@@ -206,6 +310,7 @@ fn coerce_variable_value(
                         // Field not required
                     }
                 }
+                check_one_of(schema, ty_def, &object, None)?;
                 return Ok(object.into());
             }
         }
@@ -269,103 +374,72 @@ fn graphql_value_to_json(
 /// <https://spec.graphql.org/October2021/#sec-Coercing-Field-Arguments>
 pub(crate) fn coerce_argument_values(
     schema: &Schema,
-    document: &Valid<ExecutableDocument>,
     variable_values: &Valid<JsonMap>,
-    errors: &mut Vec<GraphQLError>,
-    path: LinkedPath<'_>,
     field_def: &FieldDefinition,
     field: &Field,
-) -> Result<JsonMap, PropagateNull> {
+) -> Result<JsonMap, InputCoercionError> {
     let mut coerced_values = JsonMap::new();
     for arg_def in &field_def.arguments {
         let arg_name = &arg_def.name;
-        if let Some(arg) = field.arguments.iter().find(|arg| arg.name == *arg_name) {
-            if let Value::Variable(var_name) = arg.value.as_ref() {
-                if let Some(var_value) = variable_values.get(var_name.as_str()) {
-                    if var_value.is_null() && arg_def.ty.is_non_null() {
-                        errors.push(GraphQLError::field_error(
-                            format!("null value for non-nullable argument {arg_name}"),
-                            path,
-                            arg_def.location(),
-                            &document.sources,
-                        ));
-                        return Err(PropagateNull);
-                    } else {
-                        coerced_values.insert(arg_name.as_str(), var_value.clone());
-                        continue;
-                    }
-                }
-            } else if arg.value.is_null() && arg_def.ty.is_non_null() {
-                errors.push(GraphQLError::field_error(
-                    format!("null value for non-nullable argument {arg_name}"),
-                    path,
-                    arg_def.location(),
-                    &document.sources,
-                ));
-                return Err(PropagateNull);
-            } else {
-                let coerced_value = coerce_argument_value(
-                    schema,
-                    document,
-                    variable_values,
-                    errors,
-                    path,
-                    "argument",
-                    "",
-                    "",
-                    arg_name,
-                    &arg_def.ty,
-                    &arg.value,
-                )?;
-                coerced_values.insert(arg_name.as_str(), coerced_value);
-                continue;
-            }
-        }
-        if let Some(default) = &arg_def.default_value {
-            let value =
-                graphql_value_to_json("argument", "", "", arg_name, default).map_err(|err| {
-                    errors.push(err.into_field_error(path, &document.sources));
-                    PropagateNull
-                })?;
+        let arg_value = field
+            .arguments
+            .iter()
+            .find(|arg| arg.name == *arg_name)
+            .and_then(|arg| provided_value(&arg.value, variable_values));
+        if let Some(value) = arg_value {
+            let coerced_value = coerce_argument_value(
+                schema,
+                variable_values,
+                "argument",
+                "",
+                "",
+                arg_name,
+                &arg_def.ty,
+                value,
+            )?;
+            coerced_values.insert(arg_name.as_str(), coerced_value);
+        } else if let Some(default) = &arg_def.default_value {
+            let value = graphql_value_to_json("argument", "", "", arg_name, default)?;
             coerced_values.insert(arg_def.name.as_str(), value);
-            continue;
-        }
-        if arg_def.ty.is_non_null() {
-            errors.push(GraphQLError::field_error(
-                format!("missing value for required argument {arg_name}"),
-                path,
-                arg_def.location(),
-                &document.sources,
-            ));
-            return Err(PropagateNull);
+        } else if arg_def.ty.is_non_null() {
+            return Err(InputCoercionError::ValueError {
+                message: format!("missing value for required argument {arg_name}"),
+                location: arg_def.location(),
+            });
         }
     }
     Ok(coerced_values)
 }
 
+/// Returns `None` if `value` is a variable that was not provided,
+/// which makes an argument or input object field absent rather than null.
+fn provided_value<'a>(
+    value: &'a Node<Value>,
+    variable_values: &JsonMap,
+) -> Option<&'a Node<Value>> {
+    match value.as_variable() {
+        Some(var_name) if !variable_values.contains_key(var_name.as_str()) => None,
+        _ => Some(value),
+    }
+}
+
 #[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
 fn coerce_argument_value(
     schema: &Schema,
-    document: &Valid<ExecutableDocument>,
     variable_values: &Valid<JsonMap>,
-    errors: &mut Vec<GraphQLError>,
-    path: LinkedPath<'_>,
     kind: &str,
     parent: &str,
     sep: &str,
     name: &str,
     ty: &Type,
     value: &Node<Value>,
-) -> Result<JsonValue, PropagateNull> {
+) -> Result<JsonValue, InputCoercionError> {
     if value.is_null() {
         if ty.is_non_null() {
-            errors.push(GraphQLError::field_error(
-                format!("null value for non-null {kind} {parent}{sep}{name}"),
-                path,
-                value.location(),
-                &document.sources,
-            ));
-            return Err(PropagateNull);
+            return Err(InputCoercionError::ValueError {
+                message: format!("null value for non-null {kind} {parent}{sep}{name}"),
+                location: value.location(),
+            });
         } else {
             return Ok(JsonValue::Null);
         }
@@ -373,24 +447,18 @@ fn coerce_argument_value(
     if let Some(var_name) = value.as_variable() {
         if let Some(var_value) = variable_values.get(var_name.as_str()) {
             if var_value.is_null() && ty.is_non_null() {
-                errors.push(GraphQLError::field_error(
-                    format!("null variable value for non-null {kind} {parent}{sep}{name}"),
-                    path,
-                    value.location(),
-                    &document.sources,
-                ));
-                return Err(PropagateNull);
+                return Err(InputCoercionError::ValueError {
+                    message: format!("null variable value for non-null {kind} {parent}{sep}{name}"),
+                    location: value.location(),
+                });
             } else {
                 return Ok(var_value.clone());
             }
         } else if ty.is_non_null() {
-            errors.push(GraphQLError::field_error(
-                format!("missing variable for non-null {kind} {parent}{sep}{name}"),
-                path,
-                value.location(),
-                &document.sources,
-            ));
-            return Err(PropagateNull);
+            return Err(InputCoercionError::ValueError {
+                message: format!("missing variable for non-null {kind} {parent}{sep}{name}"),
+                location: value.location(),
+            });
         } else {
             return Ok(JsonValue::Null);
         }
@@ -406,10 +474,7 @@ fn coerce_argument_value(
                 .map(|item| {
                     coerce_argument_value(
                         schema,
-                        document,
                         variable_values,
-                        errors,
-                        path,
                         kind,
                         parent,
                         sep,
@@ -423,14 +488,10 @@ fn coerce_argument_value(
         Type::Named(ty_name) | Type::NonNullNamed(ty_name) => ty_name,
     };
     let Some(ty_def) = schema.types.get(ty_name) else {
-        errors.push(
-            SuspectedValidationBug {
-                message: format!("Undefined type {ty_name} for {kind} {parent}{sep}{name}"),
-                location: value.location(),
-            }
-            .into_field_error(&document.sources, path),
-        );
-        return Err(PropagateNull);
+        Err(SuspectedValidationBug {
+            message: format!("Undefined type {ty_name} for {kind} {parent}{sep}{name}"),
+            location: value.location(),
+        })?
     };
     match ty_def {
         ExtendedType::InputObject(ty_def) => {
@@ -440,25 +501,22 @@ fn coerce_argument_value(
                     .iter()
                     .find(|(key, _value)| !ty_def.fields.contains_key(key))
                 {
-                    errors.push(GraphQLError::field_error(
-                        format!("Input object has key {key} not in type {ty_name}",),
-                        path,
-                        value.location(),
-                        &document.sources,
-                    ));
-                    return Err(PropagateNull);
+                    return Err(InputCoercionError::ValueError {
+                        message: format!("Input object has key {key} not in type {ty_name}",),
+                        location: value.location(),
+                    });
                 }
                 #[allow(clippy::map_identity)] // `map` converts `&(k, v)` to `(&k, &v)`
                 let object: HashMap<_, _> = object.iter().map(|(k, v)| (k, v)).collect();
                 let mut coerced_object = JsonMap::new();
                 for (field_name, field_def) in &ty_def.fields {
-                    if let Some(field_value) = object.get(field_name) {
+                    let field_value = object
+                        .get(field_name)
+                        .and_then(|field_value| provided_value(field_value, variable_values));
+                    if let Some(field_value) = field_value {
                         let coerced_value = coerce_argument_value(
                             schema,
-                            document,
                             variable_values,
-                            errors,
-                            path,
                             "input field",
                             ty_name,
                             ".",
@@ -468,45 +526,64 @@ fn coerce_argument_value(
                         )?;
                         coerced_object.insert(field_name.as_str(), coerced_value);
                     } else if let Some(default) = &field_def.default_value {
-                        let default =
-                            graphql_value_to_json("input field", ty_name, ".", field_name, default)
-                                .map_err(|err| {
-                                    errors.push(err.into_field_error(path, &document.sources));
-                                    PropagateNull
-                                })?;
+                        let default = graphql_value_to_json(
+                            "input field",
+                            ty_name,
+                            ".",
+                            field_name,
+                            default,
+                        )?;
                         coerced_object.insert(field_name.as_str(), default);
                     } else if field_def.ty.is_non_null() {
-                        errors.push(GraphQLError::field_error(
-                            format!(
+                        return Err(InputCoercionError::ValueError {
+                            message: format!(
                                 "Missing value for non-null input object field {ty_name}.{field_name}"
                             ),
-                            path,
-                            value.location(),
-                            &document.sources,
-                        ));
-                        return Err(PropagateNull);
+                            location: value.location(),
+                        });
                     } else {
                         // Field not required
                     }
                 }
+                check_one_of(schema, ty_def, &coerced_object, value.location())?;
                 return Ok(coerced_object.into());
             }
         }
         _ => {
             // For scalar and enums, rely and validation and just convert between Rust types
-            return graphql_value_to_json(kind, parent, sep, name, value).map_err(|err| {
-                errors.push(err.into_field_error(path, &document.sources));
-                PropagateNull
-            });
+            return graphql_value_to_json(kind, parent, sep, name, value);
         }
     }
-    errors.push(GraphQLError::field_error(
-        format!("Could not coerce {kind} {parent}{sep}{name}: {value} to type {ty_name}"),
-        path,
-        value.location(),
-        &document.sources,
-    ));
-    Err(PropagateNull)
+    Err(InputCoercionError::ValueError {
+        message: format!("Could not coerce {kind} {parent}{sep}{name}: {value} to type {ty_name}"),
+        location: value.location(),
+    })
+}
+
+/// A oneOf input object value must have exactly one field, which is not null.
+/// This is checked after coercion, as a field set to a variable may be null at runtime.
+///
+/// <https://spec.graphql.org/draft/#sec-OneOf-Input-Objects.Input-Coercion>
+fn check_one_of(
+    schema: &Schema,
+    ty_def: &InputObjectType,
+    object: &JsonMap,
+    location: Option<SourceSpan>,
+) -> Result<(), InputCoercionError> {
+    if !schema.is_one_of(ty_def) {
+        return Ok(());
+    }
+    let mut values = object.values();
+    match (values.next(), values.next()) {
+        (Some(value), None) if !value.is_null() => Ok(()),
+        _ => Err(InputCoercionError::ValueError {
+            message: format!(
+                "OneOf input object {} must specify exactly one non-null field",
+                ty_def.name
+            ),
+            location,
+        }),
+    }
 }
 
 impl From<SuspectedValidationBug> for InputCoercionError {
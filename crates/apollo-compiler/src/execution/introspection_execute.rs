@@ -1,12 +1,16 @@
 use crate::collections::HashMap;
 use crate::executable::Operation;
 use crate::executable::OperationType;
+use crate::execution::coerce_variable_values;
 use crate::execution::engine::execute_selection_set;
 use crate::execution::engine::ExecutionMode;
+use crate::execution::introspection_max_depth::check_introspection_max_depth;
 use crate::execution::resolver::ResolvedValue;
 use crate::execution::GraphQLError;
 use crate::execution::JsonMap;
+use crate::execution::NumberPrecision;
 use crate::execution::Response;
+use crate::execution::ResponseData;
 use crate::execution::SchemaIntrospectionError;
 use crate::execution::SchemaIntrospectionSplit;
 use crate::schema;
@@ -19,6 +23,9 @@ use crate::Schema;
 use std::borrow::Cow;
 use std::sync::OnceLock;
 
+/// Lazily computed [`Schema::implementers_map`], shared by introspection queries of a batch
+pub(crate) type ImplementersCache = OnceLock<HashMap<Name, Implementers>>;
+
 /// A document with a single query that only has [schema introspection] fields.
 /// Obtained from [`SchemaIntrospectionSplit::split`].
 ///
@@ -69,10 +76,28 @@ impl SchemaIntrospectionQuery {
         operation: &Node<Operation>,
         variable_values: &Valid<JsonMap>,
         execute_non_introspection_parts: impl FnOnce(&Valid<ExecutableDocument>) -> Response,
+    ) -> Response {
+        Self::split_and_execute_with_cache(
+            schema,
+            document,
+            operation,
+            variable_values,
+            &OnceLock::new(),
+            execute_non_introspection_parts,
+        )
+    }
+
+    pub(crate) fn split_and_execute_with_cache(
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+        operation: &Node<Operation>,
+        variable_values: &Valid<JsonMap>,
+        implementers_map: &ImplementersCache,
+        execute_non_introspection_parts: impl FnOnce(&Valid<ExecutableDocument>) -> Response,
     ) -> Response {
         match SchemaIntrospectionSplit::split(schema, document, operation) {
             Ok(SchemaIntrospectionSplit::Only(introspection_query)) => {
-                introspection_query.execute(schema, variable_values)
+                introspection_query.execute_with_cache(schema, variable_values, implementers_map)
             }
             Ok(SchemaIntrospectionSplit::None) => execute_non_introspection_parts(document),
             Ok(SchemaIntrospectionSplit::Both {
@@ -81,7 +106,11 @@ impl SchemaIntrospectionQuery {
             }) => {
                 let non_introspection_response =
                     execute_non_introspection_parts(&filtered_operation);
-                let introspection_response = introspection_query.execute(schema, variable_values);
+                let introspection_response = introspection_query.execute_with_cache(
+                    schema,
+                    variable_values,
+                    implementers_map,
+                );
                 non_introspection_response.merge(introspection_response)
             }
             Err(err) => err.into_response(&document.sources),
@@ -93,8 +122,23 @@ impl SchemaIntrospectionQuery {
     ///
     /// [request error]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
     pub fn execute(&self, schema: &Valid<Schema>, variable_values: &Valid<JsonMap>) -> Response {
+        self.execute_with_cache(schema, variable_values, &OnceLock::new())
+    }
+
+    fn execute_with_cache(
+        &self,
+        schema: &Valid<Schema>,
+        variable_values: &Valid<JsonMap>,
+        implementers_map: &ImplementersCache,
+    ) -> Response {
         let operation = self.0.operations.get(None).unwrap();
-        execute_introspection_only_query(schema, &self.0, operation, variable_values)
+        execute_introspection_only_query_with_cache(
+            schema,
+            &self.0,
+            operation,
+            variable_values,
+            implementers_map,
+        )
     }
 }
 
@@ -107,6 +151,102 @@ pub fn execute_introspection_only_query(
     document: &Valid<ExecutableDocument>,
     operation: &Node<Operation>,
     variable_values: &Valid<JsonMap>,
+) -> Response {
+    execute_introspection_only_query_with_cache(
+        schema,
+        document,
+        operation,
+        variable_values,
+        &OnceLock::new(),
+    )
+}
+
+/// Parse, validate, and execute a query made only of introspection meta-fields,
+/// from source text to response.
+///
+/// This is a convenience for servers that answer introspection themselves
+/// but do not otherwise parse requests, such as a gateway or a schema registry:
+///
+/// 1. Parse `query_text` and validate it against `schema`
+/// 2. Select the operation named `operation_name`, or the only operation if `None`
+/// 3. Check that it is a query whose [root fields][Operation::root_fields] are all
+///    `__schema`, `__type`, or `__typename`, and that its introspection lists are not
+///    [nested too deeply][check_introspection_max_depth]
+/// 4. Coerce `variables`
+/// 5. Execute it as with [`execute_introspection_only_query`]
+///
+/// If any of steps 1 to 4 fail, the response has [request errors] and no `data` entry.
+/// To also execute other fields of an operation, use [`crate::execution::request::execute`].
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::execute_introspection;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let query = "query Q($name: String!) { __type(name: $name) { kind } }";
+/// let variables = serde_json::from_str(r#"{ "name": "Query" }"#).unwrap();
+///
+/// let response = execute_introspection(&schema, query, Some("Q"), &variables);
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"data":{"__type":{"kind":"OBJECT"}}}"#,
+/// );
+///
+/// let response = execute_introspection(&schema, "{ a }", None, &Default::default());
+/// assert!(response.data.is_absent());
+/// ```
+///
+/// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+pub fn execute_introspection(
+    schema: &Valid<Schema>,
+    query_text: impl Into<String>,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+) -> Response {
+    let document =
+        match ExecutableDocument::parse_and_validate(schema, query_text, "request.graphql") {
+            Ok(document) => document,
+            Err(with_errors) => {
+                return Response {
+                    errors: with_errors.errors.to_graphql_errors(),
+                    data: ResponseData::Absent,
+                    extensions: Default::default(),
+                }
+            }
+        };
+    let Ok(operation) = document.operations.get(operation_name) else {
+        let message = match operation_name {
+            Some(name) => format!("operation `{name}` not found in the request"),
+            None => "operation name is required in a request with many operations".into(),
+        };
+        return Response::from_request_error(GraphQLError::new(message, None, &document.sources));
+    };
+    if !operation.is_introspection(&document) {
+        return Response::from_request_error(GraphQLError::new(
+            "operation selects fields other than introspection meta-fields",
+            operation.location(),
+            &document.sources,
+        ));
+    }
+    if let Err(err) = check_introspection_max_depth(&document, operation) {
+        return SchemaIntrospectionError::DeeplyNestedIntrospectionList(err)
+            .into_response(&document.sources);
+    }
+    let variable_values = match coerce_variable_values(schema, operation, variables) {
+        Ok(variable_values) => variable_values,
+        Err(err) => return err.into_response(&document.sources),
+    };
+    execute_introspection_only_query(schema, &document, operation, &variable_values)
+}
+
+fn execute_introspection_only_query_with_cache(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+    implementers_map: &ImplementersCache,
 ) -> Response {
     if operation.operation_type != OperationType::Query {
         return Response::from_request_error(GraphQLError::new(
@@ -128,7 +268,6 @@ pub fn execute_introspection_only_query(
             &document.sources,
         ));
     };
-    let implementers_map = &OnceLock::new();
     let initial_value = &IntrospectionRootResolver(SchemaWithCache {
         schema,
         implementers_map,
@@ -143,6 +282,7 @@ pub fn execute_introspection_only_query(
         &mut errors,
         path,
         ExecutionMode::Normal,
+        NumberPrecision::Preserve,
         object_type_def,
         initial_value,
         &operation.selection_set.selections,
@@ -157,7 +297,7 @@ pub fn execute_introspection_only_query(
 #[derive(Clone, Copy)]
 struct SchemaWithCache<'a> {
     schema: &'a Schema,
-    implementers_map: &'a OnceLock<HashMap<Name, Implementers>>,
+    implementers_map: &'a ImplementersCache,
 }
 
 impl<'a> SchemaWithCache<'a> {
@@ -432,6 +572,13 @@ impl_resolver! {
             .and_then(|arg| arg.as_str())
         ))
     }
+
+    fn isOneOf(&self_) {
+        let schema::ExtendedType::InputObject(def) = self_.def else {
+            return Ok(ResolvedValue::null())
+        };
+        Ok(ResolvedValue::leaf(self_.schema.is_one_of(def)))
+    }
 }
 
 // Only used for non-null and list types
@@ -469,6 +616,7 @@ impl_resolver! {
     fn enumValues() { Ok(ResolvedValue::null()) }
     fn inputFields() { Ok(ResolvedValue::null()) }
     fn specifiedByURL() { Ok(ResolvedValue::null()) }
+    fn isOneOf() { Ok(ResolvedValue::null()) }
 }
 
 impl_resolver! {
@@ -605,11 +753,15 @@ impl_resolver! {
     }
 }
 
-/// Although it should be non-null, the `includeDeprecated: Boolean = false` argument is nullable
+/// Although it should be non-null, the `includeDeprecated: Boolean = false` argument is nullable.
+///
+/// With [`SpecEdition::October2021`][crate::schema::SpecEdition::October2021],
+/// `inputFields` and `args` have no such argument and always include everything.
 fn include_deprecated(args: &JsonMap) -> bool {
-    match &args["includeDeprecated"] {
-        serde_json_bytes::Value::Bool(b) => *b,
-        serde_json_bytes::Value::Null => false,
-        _ => unreachable!(),
+    match args.get("includeDeprecated") {
+        Some(serde_json_bytes::Value::Bool(b)) => *b,
+        Some(serde_json_bytes::Value::Null) => false,
+        None => true,
+        Some(_) => unreachable!(),
     }
 }
@@ -0,0 +1,218 @@
+use crate::execution::FromIntrospectionError;
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use std::fmt;
+
+/// A common reason why a [schema introspection] request to a server failed,
+/// as classified by [`Response::introspection_failure`]
+/// or [`FromIntrospectionError::introspection_failure`].
+///
+/// Tools fetching a schema can use [`hint`][Self::hint] to print an actionable message
+/// instead of raw GraphQL errors.
+///
+/// ```
+/// use apollo_compiler::execution::IntrospectionFailure;
+/// use apollo_compiler::execution::Response;
+///
+/// let response: Response = serde_json::from_str(r#"{
+///     "errors": [{"message": "Cannot query field \"isRepeatable\" on type \"__Directive\"."}]
+/// }"#).unwrap();
+/// let failure = response.introspection_failure().unwrap();
+/// assert_eq!(
+///     failure,
+///     IntrospectionFailure::UnsupportedFields {
+///         coordinates: vec!["__Directive.isRepeatable".into()],
+///     },
+/// );
+/// assert_eq!(
+///     failure.hint(),
+///     "The server does not support `__Directive.isRepeatable`, \
+///      which may have been added in a later version of the GraphQL specification. \
+///      Remove it from the introspection query.",
+/// );
+/// ```
+///
+/// [schema introspection]: https://spec.graphql.org/October2021/#sec-Schema-Introspection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntrospectionFailure {
+    /// The server does not allow introspection, such as in production environments
+    Disabled {
+        /// The error message returned by the server
+        message: String,
+    },
+
+    /// The introspection query selects fields or arguments of introspection types
+    /// that the server does not support, typically because they were added
+    /// in a version of the GraphQL specification more recent than the server.
+    UnsupportedFields {
+        /// Schema coordinates such as `__Directive.isRepeatable`
+        /// or `__Type.inputFields(includeDeprecated:)`
+        coordinates: Vec<String>,
+    },
+
+    /// The response has data, but also errors: the schema may be incomplete
+    PartialSchema {
+        /// Error messages returned by the server
+        errors: Vec<String>,
+    },
+
+    /// The response has errors and no data, for another reason
+    Other {
+        /// Error messages returned by the server
+        errors: Vec<String>,
+    },
+}
+
+impl Response {
+    /// If this is the response to a [schema introspection] request and has errors,
+    /// classify them into a common failure mode.
+    ///
+    /// Returns `None` if the response has no errors.
+    ///
+    /// [schema introspection]: https://spec.graphql.org/October2021/#sec-Schema-Introspection
+    pub fn introspection_failure(&self) -> Option<IntrospectionFailure> {
+        let has_data = matches!(self.data, ResponseData::Object(_));
+        IntrospectionFailure::classify(
+            self.errors.iter().map(|error| error.message.as_str()),
+            has_data,
+        )
+    }
+}
+
+impl FromIntrospectionError {
+    /// If this error is from a GraphQL response with errors and no data,
+    /// classify them into a common failure mode.
+    ///
+    /// Returns `None` for other kinds of errors.
+    pub fn introspection_failure(&self) -> Option<IntrospectionFailure> {
+        match self {
+            Self::ResponseErrors(messages) => {
+                IntrospectionFailure::classify(messages.iter().map(String::as_str), false)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl IntrospectionFailure {
+    fn classify<'a>(
+        messages: impl Iterator<Item = &'a str> + Clone,
+        has_data: bool,
+    ) -> Option<Self> {
+        let errors = || messages.clone().map(str::to_owned).collect::<Vec<_>>();
+        messages.clone().next()?;
+        let coordinates: Vec<_> = messages
+            .clone()
+            .filter_map(unsupported_coordinate)
+            .collect();
+        if !coordinates.is_empty() {
+            return Some(Self::UnsupportedFields { coordinates });
+        }
+        if let Some(message) = messages.clone().find(|message| is_disabled(message)) {
+            return Some(Self::Disabled {
+                message: message.to_owned(),
+            });
+        }
+        if has_data {
+            Some(Self::PartialSchema { errors: errors() })
+        } else {
+            Some(Self::Other { errors: errors() })
+        }
+    }
+
+    /// Returns a suggestion of what to do about this failure, meant for end users
+    pub fn hint(&self) -> String {
+        match self {
+            Self::Disabled { .. } => "Introspection is disabled on this server. \
+                 Enable it, or get the schema from another source such as a schema file."
+                .to_owned(),
+            Self::UnsupportedFields { coordinates } => {
+                let them = if coordinates.len() == 1 { "it" } else { "them" };
+                let coordinates = coordinates
+                    .iter()
+                    .map(|coordinate| format!("`{coordinate}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "The server does not support {coordinates}, \
+                     which may have been added in a later version of the GraphQL specification. \
+                     Remove {them} from the introspection query."
+                )
+            }
+            Self::PartialSchema { .. } => "The server returned errors together with data, \
+                 so the schema may be incomplete. Check the errors and the server logs."
+                .to_owned(),
+            Self::Other { .. } => "Check the errors returned by the server.".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for IntrospectionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled { message } => write!(f, "introspection is disabled: {message}"),
+            Self::UnsupportedFields { coordinates } => write!(
+                f,
+                "introspection query uses unsupported fields: {}",
+                coordinates.join(", ")
+            ),
+            Self::PartialSchema { errors } => {
+                write!(
+                    f,
+                    "introspection response is partial: {}",
+                    errors.join("; ")
+                )
+            }
+            Self::Other { errors } => {
+                write!(
+                    f,
+                    "introspection response has errors: {}",
+                    errors.join("; ")
+                )
+            }
+        }
+    }
+}
+
+fn is_disabled(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("introspection")
+        && [
+            "disabled",
+            "not allowed",
+            "not enabled",
+            "not permitted",
+            "forbidden",
+        ]
+        .iter()
+        .any(|phrase| message.contains(phrase))
+}
+
+/// Recognizes messages from graphql-js and from apollo-compiler validation
+/// about undefined fields or arguments of introspection types
+fn unsupported_coordinate(message: &str) -> Option<String> {
+    let (type_name, coordinate) = if message.starts_with("Cannot query field") {
+        // Cannot query field "isRepeatable" on type "__Directive".
+        let [field, type_name] = quoted(message, '"')?;
+        (type_name, format!("{type_name}.{field}"))
+    } else if message.starts_with("Unknown argument") {
+        // Unknown argument "includeDeprecated" on field "__Type.inputFields".
+        let [argument, field] = quoted(message, '"')?;
+        (field, format!("{field}({argument}:)"))
+    } else if message.starts_with("type `") && message.contains("does not have a field") {
+        let [type_name, field] = quoted(message, '`')?;
+        (type_name, format!("{type_name}.{field}"))
+    } else if message.starts_with("the argument `") && message.contains("is not supported by") {
+        let [argument, field] = quoted(message, '`')?;
+        (field, format!("{field}({argument}:)"))
+    } else {
+        return None;
+    };
+    type_name.starts_with("__").then_some(coordinate)
+}
+
+/// The first two substrings delimited by `quote`
+fn quoted(message: &str, quote: char) -> Option<[&str; 2]> {
+    let mut parts = message.split(quote).skip(1).step_by(2);
+    Some([parts.next()?, parts.next()?])
+}
@@ -51,6 +51,7 @@ fn check_selection_set(
                 }
                 check_selection_set(document, depth, &field.selection_set)?
             }
+            Selection::Invalid(_) => {}
         }
     }
     Ok(())
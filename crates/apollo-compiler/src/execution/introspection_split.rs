@@ -195,6 +195,7 @@ fn check_non_query<'doc>(
                         fragments_to_visit.insert(name);
                     }
                 }
+                Selection::Invalid(_) => {}
             }
         }
         Ok(())
@@ -362,6 +363,7 @@ fn collect_field_kinds<'doc>(
                     }
                 }
             }
+            Selection::Invalid(_) => {}
         }
     }
     Ok(top_level_field_kinds)
@@ -478,6 +480,7 @@ fn split_selection_set<'doc>(
                 );
                 if_defined(&mut new_documents.other, &mut new_selection_sets.other);
             }
+            Selection::Invalid(_) => {}
         }
     }
     new_selection_sets
@@ -515,6 +518,7 @@ impl<'doc> DocumentBuilder<'doc> {
                         self.visit_selection_set(&fragment_def.selection_set);
                     };
                 }
+                Selection::Invalid(_) => {}
             }
         }
     }
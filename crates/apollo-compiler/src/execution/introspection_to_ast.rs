@@ -0,0 +1,548 @@
+use crate::ast;
+use crate::ast::Definition;
+use crate::ast::DirectiveLocation;
+use crate::ast::OperationType;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use serde::Deserialize;
+
+/// Tried to convert an introspection response that does not have the expected shape.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("invalid introspection response: {message}")]
+pub struct InvalidIntrospectionError {
+    pub message: String,
+}
+
+/// Error type of [`Schema::from_introspection_json`]
+#[derive(Debug, thiserror::Error)]
+pub enum FromIntrospectionError {
+    /// The input is not JSON
+    #[error("malformed introspection JSON: {0}")]
+    Json(String),
+    /// The input is a GraphQL response with errors and no data,
+    /// such as when introspection is disabled on the server
+    #[error("introspection response has errors: {}", .0.join("; "))]
+    ResponseErrors(Vec<String>),
+    /// The JSON does not have the shape of a schema introspection response
+    #[error(transparent)]
+    Invalid(#[from] InvalidIntrospectionError),
+    /// The introspected schema was converted but is not valid
+    #[error("introspected schema is invalid:\n{}", .0.errors)]
+    Validation(Box<WithErrors<Schema>>),
+}
+
+/// Convert the `data` of a response to a [schema introspection] query
+/// into a type system document, such as to print SDL for a schema fetched from a remote server.
+///
+/// The query must select `__schema` with the fields of the
+/// [standard introspection query] that are relevant to SDL:
+/// type and directive definitions with their fields, arguments, descriptions,
+/// and deprecation status.
+///
+/// Built-in scalars, introspection types, and built-in directives are omitted.
+/// A `schema` definition is only included if root operation types
+/// do not have their default names.
+///
+/// ```
+/// use apollo_compiler::execution::introspection_response_to_ast;
+/// use apollo_compiler::execution::JsonMap;
+///
+/// let data: JsonMap = serde_json::from_str(r#"{
+///     "__schema": {
+///         "queryType": {"name": "Query"},
+///         "types": [{
+///             "kind": "OBJECT",
+///             "name": "Query",
+///             "fields": [{
+///                 "name": "hello",
+///                 "args": [],
+///                 "type": {"kind": "SCALAR", "name": "String"}
+///             }]
+///         }],
+///         "directives": []
+///     }
+/// }"#).unwrap();
+/// let document = introspection_response_to_ast(&data).unwrap();
+/// assert_eq!(document.to_string(), "type Query {\n  hello: String\n}\n");
+/// ```
+///
+/// [schema introspection]: https://spec.graphql.org/October2021/#sec-Schema-Introspection
+/// [standard introspection query]: https://github.com/graphql/graphql-js/blob/v16.9.0/src/utilities/getIntrospectionQuery.ts
+pub fn introspection_response_to_ast(
+    data: &JsonMap,
+) -> Result<ast::Document, InvalidIntrospectionError> {
+    let schema = data
+        .get("__schema")
+        .ok_or_else(|| InvalidIntrospectionError::new("missing `__schema`"))?;
+    let schema: IntrospectionSchema = serde_json_bytes::from_value(schema.clone())
+        .map_err(|e| InvalidIntrospectionError::new(e.to_string()))?;
+    schema.to_ast()
+}
+
+/// Implementation of [`Schema::from_introspection_json`]
+pub(crate) fn schema_from_introspection_json(
+    json: &[u8],
+) -> Result<Valid<Schema>, FromIntrospectionError> {
+    let json: JsonValue = serde_json_bytes::serde_json::from_slice(json)
+        .map_err(|e| FromIntrospectionError::Json(e.to_string()))?;
+    let JsonValue::Object(mut object) = json else {
+        return Err(InvalidIntrospectionError::new("expected a JSON object").into());
+    };
+    if !object.contains_key("__schema") {
+        // A full GraphQL response rather than its `data`
+        match object.remove("data") {
+            Some(JsonValue::Object(data)) => object = data,
+            Some(JsonValue::Null) | None => {
+                if let Some(JsonValue::Array(errors)) = object.get("errors") {
+                    let messages = errors
+                        .iter()
+                        .map(
+                            |error| match error.get("message").and_then(|m| m.as_str()) {
+                                Some(message) => message.to_owned(),
+                                None => error.to_string(),
+                            },
+                        )
+                        .collect();
+                    return Err(FromIntrospectionError::ResponseErrors(messages));
+                }
+            }
+            Some(_) => return Err(InvalidIntrospectionError::new("`data` is not an object").into()),
+        }
+    }
+    introspection_response_to_ast(&object)?
+        .to_schema_validate()
+        .map_err(|e| FromIntrospectionError::Validation(Box::new(e)))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    #[serde(default)]
+    description: Option<String>,
+    query_type: Option<NamedTypeRef>,
+    #[serde(default)]
+    mutation_type: Option<NamedTypeRef>,
+    #[serde(default)]
+    subscription_type: Option<NamedTypeRef>,
+    types: Vec<FullType>,
+    #[serde(default)]
+    directives: Vec<Directive>,
+}
+
+#[derive(Deserialize)]
+struct NamedTypeRef {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FullType {
+    kind: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    // `specifiedByUrl` was used by graphql-js before v16
+    #[serde(default, rename = "specifiedByURL", alias = "specifiedByUrl")]
+    specified_by_url: Option<String>,
+    #[serde(default)]
+    fields: Option<Vec<Field>>,
+    #[serde(default)]
+    input_fields: Option<Vec<InputValue>>,
+    #[serde(default)]
+    interfaces: Option<Vec<NamedTypeRef>>,
+    #[serde(default)]
+    enum_values: Option<Vec<EnumValue>>,
+    #[serde(default)]
+    possible_types: Option<Vec<NamedTypeRef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Field {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    #[serde(default)]
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InputValue {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    default_value: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    #[serde(default)]
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnumValue {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    #[serde(default)]
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Directive {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    is_repeatable: bool,
+    locations: Vec<String>,
+    #[serde(default)]
+    args: Vec<InputValue>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    of_type: Option<Box<TypeRef>>,
+}
+
+const BUILT_IN_SCALARS: [&str; 5] = ["Int", "Float", "String", "Boolean", "ID"];
+const BUILT_IN_DIRECTIVES: [&str; 4] = ["skip", "include", "deprecated", "specifiedBy"];
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+impl InvalidIntrospectionError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl IntrospectionSchema {
+    fn to_ast(&self) -> Result<ast::Document, InvalidIntrospectionError> {
+        let mut document = ast::Document::new();
+        let root_operations = [
+            (OperationType::Query, &self.query_type),
+            (OperationType::Mutation, &self.mutation_type),
+            (OperationType::Subscription, &self.subscription_type),
+        ];
+        let has_default_names = root_operations.iter().all(|(operation_type, ty)| {
+            ty.as_ref()
+                .is_none_or(|ty| ty.name == operation_type.default_type_name().as_str())
+        });
+        if !has_default_names || self.description.is_some() {
+            let root_operations = root_operations
+                .into_iter()
+                .filter_map(|(operation_type, ty)| {
+                    ty.as_ref()
+                        .map(|ty| Ok(Node::new((operation_type, name(&ty.name)?))))
+                })
+                .collect::<Result<_, _>>()?;
+            document
+                .definitions
+                .push(Definition::SchemaDefinition(Node::new(
+                    ast::SchemaDefinition {
+                        description: description(&self.description),
+                        leading_comments: Vec::new(),
+                        directives: Default::default(),
+                        root_operations,
+                    },
+                )))
+        }
+        for directive in &self.directives {
+            if BUILT_IN_DIRECTIVES.contains(&directive.name.as_str()) {
+                continue;
+            }
+            document.definitions.push(directive.to_ast()?)
+        }
+        for ty in &self.types {
+            if ty.name.starts_with("__")
+                || (ty.kind == "SCALAR" && BUILT_IN_SCALARS.contains(&ty.name.as_str()))
+            {
+                continue;
+            }
+            document.definitions.push(ty.to_ast()?)
+        }
+        Ok(document)
+    }
+}
+
+impl Directive {
+    fn to_ast(&self) -> Result<Definition, InvalidIntrospectionError> {
+        Ok(Definition::DirectiveDefinition(Node::new(
+            ast::DirectiveDefinition {
+                description: description(&self.description),
+                leading_comments: Vec::new(),
+                name: name(&self.name)?,
+                arguments: input_values(&self.args)?,
+                repeatable: self.is_repeatable,
+                locations: self
+                    .locations
+                    .iter()
+                    .map(|location| directive_location(location))
+                    .collect::<Result<_, _>>()?,
+            },
+        )))
+    }
+}
+
+impl FullType {
+    fn to_ast(&self) -> Result<Definition, InvalidIntrospectionError> {
+        let description = description(&self.description);
+        let name = name(&self.name)?;
+        let names = |list: &Option<Vec<NamedTypeRef>>| {
+            list.iter()
+                .flatten()
+                .map(|ty| self::name(&ty.name))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let fields = || {
+            self.fields
+                .iter()
+                .flatten()
+                .map(|field| field.to_ast())
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match self.kind.as_str() {
+            "SCALAR" => {
+                let mut directives = ast::DirectiveList::new();
+                if let Some(url) = &self.specified_by_url {
+                    directives.push(ast::Directive {
+                        name: crate::name!("specifiedBy"),
+                        arguments: vec![Node::new(ast::Argument {
+                            name: crate::name!("url"),
+                            value: Node::new(ast::Value::String(url.clone())),
+                        })],
+                    })
+                }
+                Definition::ScalarTypeDefinition(Node::new(ast::ScalarTypeDefinition {
+                    description,
+                    leading_comments: Vec::new(),
+                    name,
+                    directives,
+                }))
+            }
+            "OBJECT" => Definition::ObjectTypeDefinition(Node::new(ast::ObjectTypeDefinition {
+                description,
+                leading_comments: Vec::new(),
+                name,
+                implements_interfaces: names(&self.interfaces)?,
+                directives: Default::default(),
+                fields: fields()?,
+            })),
+            "INTERFACE" => {
+                Definition::InterfaceTypeDefinition(Node::new(ast::InterfaceTypeDefinition {
+                    description,
+                    leading_comments: Vec::new(),
+                    name,
+                    implements_interfaces: names(&self.interfaces)?,
+                    directives: Default::default(),
+                    fields: fields()?,
+                }))
+            }
+            "UNION" => Definition::UnionTypeDefinition(Node::new(ast::UnionTypeDefinition {
+                description,
+                leading_comments: Vec::new(),
+                name,
+                directives: Default::default(),
+                members: names(&self.possible_types)?,
+            })),
+            "ENUM" => Definition::EnumTypeDefinition(Node::new(ast::EnumTypeDefinition {
+                description,
+                leading_comments: Vec::new(),
+                name,
+                directives: Default::default(),
+                values: self
+                    .enum_values
+                    .iter()
+                    .flatten()
+                    .map(|value| {
+                        Ok(Node::new(ast::EnumValueDefinition {
+                            description: self::description(&value.description),
+                            leading_comments: Vec::new(),
+                            value: self::name(&value.name)?,
+                            directives: deprecated(value.is_deprecated, &value.deprecation_reason),
+                        }))
+                    })
+                    .collect::<Result<_, _>>()?,
+            })),
+            "INPUT_OBJECT" => {
+                Definition::InputObjectTypeDefinition(Node::new(ast::InputObjectTypeDefinition {
+                    description,
+                    leading_comments: Vec::new(),
+                    name,
+                    directives: Default::default(),
+                    fields: input_values(self.input_fields.as_deref().unwrap_or_default())?,
+                }))
+            }
+            kind => {
+                return Err(InvalidIntrospectionError::new(format!(
+                    "unexpected kind `{kind}` for named type `{}`",
+                    self.name
+                )))
+            }
+        })
+    }
+}
+
+impl Field {
+    fn to_ast(&self) -> Result<Node<ast::FieldDefinition>, InvalidIntrospectionError> {
+        Ok(Node::new(ast::FieldDefinition {
+            description: description(&self.description),
+            leading_comments: Vec::new(),
+            name: name(&self.name)?,
+            arguments: input_values(&self.args)?,
+            ty: self.ty.to_ast()?,
+            directives: deprecated(self.is_deprecated, &self.deprecation_reason),
+        }))
+    }
+}
+
+impl TypeRef {
+    fn to_ast(&self) -> Result<ast::Type, InvalidIntrospectionError> {
+        let of_type = || {
+            self.of_type.as_deref().ok_or_else(|| {
+                InvalidIntrospectionError::new(format!("missing `ofType` for {}", self.kind))
+            })
+        };
+        match self.kind.as_str() {
+            "NON_NULL" => {
+                let inner = of_type()?.to_ast()?;
+                if inner.is_non_null() {
+                    return Err(InvalidIntrospectionError::new("nested `NON_NULL` types"));
+                }
+                Ok(inner.non_null())
+            }
+            "LIST" => Ok(of_type()?.to_ast()?.list()),
+            _ => {
+                let name = self.name.as_deref().ok_or_else(|| {
+                    InvalidIntrospectionError::new(format!("missing `name` for {}", self.kind))
+                })?;
+                Ok(ast::Type::Named(self::name(name)?))
+            }
+        }
+    }
+}
+
+fn input_values(
+    values: &[InputValue],
+) -> Result<Vec<Node<ast::InputValueDefinition>>, InvalidIntrospectionError> {
+    values
+        .iter()
+        .map(|value| {
+            let ty = value.ty.to_ast()?;
+            let default_value = value
+                .default_value
+                .as_deref()
+                .map(|default_value| parse_value(&ty, default_value))
+                .transpose()?;
+            Ok(Node::new(ast::InputValueDefinition {
+                description: description(&value.description),
+                leading_comments: Vec::new(),
+                name: name(&value.name)?,
+                ty: Node::new(ty),
+                default_value,
+                directives: deprecated(value.is_deprecated, &value.deprecation_reason),
+            }))
+        })
+        .collect()
+}
+
+/// `defaultValue` is a string in GraphQL syntax. Parse it as part of an input field definition.
+fn parse_value(
+    ty: &ast::Type,
+    source: &str,
+) -> Result<Node<ast::Value>, InvalidIntrospectionError> {
+    let error = || InvalidIntrospectionError::new(format!("invalid default value `{source}`"));
+    let document = ast::Document::parse(
+        format!("input Input {{ field: {ty} = {source} }}"),
+        "defaultValue",
+    )
+    .map_err(|_| error())?;
+    match document.definitions.as_slice() {
+        [Definition::InputObjectTypeDefinition(def)] => match def.fields.as_slice() {
+            [field] => field.default_value.clone().ok_or_else(error),
+            _ => Err(error()),
+        },
+        _ => Err(error()),
+    }
+}
+
+fn deprecated(is_deprecated: bool, reason: &Option<String>) -> ast::DirectiveList {
+    let mut directives = ast::DirectiveList::new();
+    if is_deprecated {
+        directives.push(ast::Directive {
+            name: crate::name!("deprecated"),
+            arguments: reason
+                .iter()
+                .filter(|reason| *reason != DEFAULT_DEPRECATION_REASON)
+                .map(|reason| {
+                    Node::new(ast::Argument {
+                        name: crate::name!("reason"),
+                        value: Node::new(ast::Value::String(reason.clone())),
+                    })
+                })
+                .collect(),
+        })
+    }
+    directives
+}
+
+fn description(description: &Option<String>) -> Option<Node<str>> {
+    description.as_deref().map(Node::new_str)
+}
+
+fn name(name: &str) -> Result<Name, InvalidIntrospectionError> {
+    Name::new(name).map_err(|e| InvalidIntrospectionError::new(e.to_string()))
+}
+
+fn directive_location(location: &str) -> Result<DirectiveLocation, InvalidIntrospectionError> {
+    [
+        DirectiveLocation::Query,
+        DirectiveLocation::Mutation,
+        DirectiveLocation::Subscription,
+        DirectiveLocation::Field,
+        DirectiveLocation::FragmentDefinition,
+        DirectiveLocation::FragmentSpread,
+        DirectiveLocation::InlineFragment,
+        DirectiveLocation::VariableDefinition,
+        DirectiveLocation::Schema,
+        DirectiveLocation::Scalar,
+        DirectiveLocation::Object,
+        DirectiveLocation::FieldDefinition,
+        DirectiveLocation::ArgumentDefinition,
+        DirectiveLocation::Interface,
+        DirectiveLocation::Union,
+        DirectiveLocation::Enum,
+        DirectiveLocation::EnumValue,
+        DirectiveLocation::InputObject,
+        DirectiveLocation::InputFieldDefinition,
+    ]
+    .into_iter()
+    .find(|candidate| candidate.name() == location)
+    .ok_or_else(|| {
+        InvalidIntrospectionError::new(format!("unknown directive location `{location}`"))
+    })
+}
@@ -0,0 +1,277 @@
+use crate::executable::Operation;
+use crate::executable::OperationType;
+use crate::execution::engine::execute_selection_set;
+use crate::execution::engine::ExecutionMode;
+use crate::execution::resolver::ResolvedValue;
+use crate::execution::resolver::Resolver;
+use crate::execution::resolver::ResolverError;
+use crate::execution::GraphQLError;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::execution::MetaFieldRegistry;
+use crate::execution::NumberPrecision;
+use crate::execution::Response;
+use crate::schema::ExtendedType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+
+/// Execute a query or mutation against static JSON data instead of field resolvers,
+/// for example to serve fixtures or mocked responses in tests.
+///
+/// `root_value` is an object of the operation’s root type.
+/// Each field is resolved by looking up the field name (not the alias) in its parent object,
+/// ignoring arguments. A missing key resolves to null.
+/// Objects returned by fields of interface or union type need a `__typename` entry
+/// with the name of their concrete object type, or a field error is raised.
+/// Values are then completed like in execution:
+/// leaf values go through result coercion, nulls propagate,
+/// and errors are returned in the response.
+///
+/// Schema introspection fields are not handled here:
+/// use [`SchemaIntrospectionQuery::split_and_execute`][crate::execution::SchemaIntrospectionQuery::split_and_execute]
+/// to execute them separately.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::coerce_variable_values;
+/// use apollo_compiler::execution::execute_with_json_data;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { search: [Result] }
+///     union Result = User | Post
+///     type User { name: String }
+///     type Post { title: String }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "{ search { ... on User { name } ... on Post { heading: title } } }",
+///     "query.graphql",
+/// ).unwrap();
+/// let operation = document.operations.get(None).unwrap();
+/// let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+/// let root_value = serde_json::from_str(r#"{ "search": [
+///     { "__typename": "User", "name": "Alice", "email": "alice@example.com" },
+///     { "__typename": "Post", "title": "Hello" }
+/// ] }"#).unwrap();
+///
+/// let response = execute_with_json_data(&schema, &document, operation, &variables, &root_value);
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"data":{"search":[{"name":"Alice"},{"heading":"Hello"}]}}"#,
+/// );
+/// ```
+pub fn execute_with_json_data(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+    root_value: &JsonMap,
+) -> Response {
+    execute_with_json_data_and_meta_fields(
+        schema,
+        document,
+        operation,
+        variable_values,
+        root_value,
+        &MetaFieldRegistry::default(),
+        NumberPrecision::Preserve,
+    )
+}
+
+/// Like [`execute_with_json_data`], consulting registered meta-fields first
+pub(crate) fn execute_with_json_data_and_meta_fields(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+    root_value: &JsonMap,
+    meta_fields: &MetaFieldRegistry,
+    precision: NumberPrecision,
+) -> Response {
+    let mode = match operation.operation_type {
+        OperationType::Query => ExecutionMode::Normal,
+        OperationType::Mutation => ExecutionMode::Sequential,
+        OperationType::Subscription => {
+            return Response::from_request_error(GraphQLError::new(
+                "execute_with_json_data called with a subscription",
+                operation.location(),
+                &document.sources,
+            ))
+        }
+    };
+    let object_type_name = operation.object_type();
+    let Some(object_type_def) = schema.get_object(object_type_name) else {
+        return Response::from_request_error(GraphQLError::new(
+            "Undefined root operation type",
+            object_type_name.location(),
+            &document.sources,
+        ));
+    };
+    let initial_value = meta_fields.wrap(
+        schema,
+        Box::new(JsonObjectResolver {
+            schema,
+            type_name: object_type_name,
+            object: root_value,
+        }),
+    );
+
+    let mut errors = Vec::new();
+    let path = None;
+    let data = execute_selection_set(
+        schema,
+        document,
+        variable_values,
+        &mut errors,
+        path,
+        mode,
+        precision,
+        object_type_def,
+        &*initial_value,
+        &operation.selection_set.selections,
+    );
+    Response {
+        data: data.into(),
+        errors,
+        extensions: Default::default(),
+    }
+}
+
+#[derive(Clone, Copy)]
+struct JsonObjectResolver<'a> {
+    schema: &'a Schema,
+    type_name: &'a str,
+    object: &'a JsonMap,
+}
+
+/// Like [`JsonObjectResolver`], for a JSON object not borrowed from elsewhere
+struct OwnedJsonObjectResolver<'a> {
+    schema: &'a Schema,
+    type_name: String,
+    object: JsonMap,
+}
+
+impl Resolver for JsonObjectResolver<'_> {
+    fn type_name(&self) -> &str {
+        self.type_name
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        _arguments: &'a JsonMap,
+    ) -> Result<ResolvedValue<'a>, ResolverError> {
+        self.field(field_name)
+    }
+}
+
+impl Resolver for OwnedJsonObjectResolver<'_> {
+    fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        _arguments: &'a JsonMap,
+    ) -> Result<ResolvedValue<'a>, ResolverError> {
+        JsonObjectResolver {
+            schema: self.schema,
+            type_name: &self.type_name,
+            object: &self.object,
+        }
+        .field(field_name)
+    }
+}
+
+impl<'a> JsonObjectResolver<'a> {
+    fn field(self, field_name: &str) -> Result<ResolvedValue<'a>, ResolverError> {
+        let Some(value) = self.object.get(field_name) else {
+            return Ok(ResolvedValue::null());
+        };
+        let field_type = match self.schema.type_field(self.type_name, field_name) {
+            Ok(def) => def.ty.inner_named_type(),
+            // Undefined field: validation should have caught this
+            Err(_) => return Ok(ResolvedValue::leaf(value.clone())),
+        };
+        self.resolve_value(field_type, value)
+    }
+
+    fn resolve_value(
+        self,
+        field_type: &'a str,
+        value: &'a JsonValue,
+    ) -> Result<ResolvedValue<'a>, ResolverError> {
+        match value {
+            JsonValue::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.resolve_value(field_type, item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ResolvedValue::list(items))
+            }
+            JsonValue::Object(object) => match object_type_name(self.schema, field_type, object)? {
+                Some(type_name) => Ok(ResolvedValue::object(JsonObjectResolver {
+                    schema: self.schema,
+                    type_name,
+                    object,
+                })),
+                None => Ok(ResolvedValue::leaf(value.clone())),
+            },
+            _ => Ok(ResolvedValue::leaf(value.clone())),
+        }
+    }
+}
+
+/// Convert an owned JSON value of the given type to a resolved value,
+/// with the same rules as [`execute_with_json_data`]
+pub(crate) fn resolve_owned_json<'a>(
+    schema: &'a Schema,
+    field_type: &str,
+    value: JsonValue,
+) -> Result<ResolvedValue<'a>, ResolverError> {
+    match value {
+        JsonValue::Array(items) => {
+            let items = items
+                .into_iter()
+                .map(|item| resolve_owned_json(schema, field_type, item))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(ResolvedValue::list(items))
+        }
+        JsonValue::Object(object) => match object_type_name(schema, field_type, &object)? {
+            Some(type_name) => Ok(ResolvedValue::object(OwnedJsonObjectResolver {
+                schema,
+                type_name: type_name.to_owned(),
+                object,
+            })),
+            None => Ok(ResolvedValue::leaf(object)),
+        },
+        _ => Ok(ResolvedValue::leaf(value)),
+    }
+}
+
+/// The name of the concrete object type of a JSON object of type `field_type`,
+/// or `None` for a custom scalar
+fn object_type_name<'a>(
+    schema: &Schema,
+    field_type: &'a str,
+    object: &'a JsonMap,
+) -> Result<Option<&'a str>, ResolverError> {
+    match schema.types.get(field_type) {
+        Some(ExtendedType::Object(_)) => Ok(Some(field_type)),
+        Some(ExtendedType::Interface(_) | ExtendedType::Union(_)) => object
+            .get("__typename")
+            .and_then(|name| name.as_str())
+            .map(Some)
+            .ok_or_else(|| ResolverError {
+                message: format!("missing __typename in JSON object of abstract type {field_type}"),
+            }),
+        // Custom scalar
+        _ => Ok(None),
+    }
+}
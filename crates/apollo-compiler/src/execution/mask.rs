@@ -0,0 +1,205 @@
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::engine::does_fragment_type_apply;
+use crate::execution::engine::eval_if_arg;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::schema::ObjectType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+
+/// Filter JSON data down to the fields selected by an operation,
+/// for example to turn rich internal objects into the `data` of a response.
+///
+/// The result has the same shape that executing the operation would produce:
+/// each object only has keys for the fields collected from its selection set,
+/// in selection order, named after their alias if any.
+/// The value of a field is looked up in `data` by its response key,
+/// or by its field name if that key is missing.
+/// Fields that are missing from `data` are also missing from the result.
+///
+/// Fragments apply based on the runtime object type, like in execution.
+/// For fields of interface or union type this is taken from a `__typename` entry in `data`.
+/// If that is missing, only fragments without a type condition (or whose type condition
+/// is the field’s own type) apply.
+/// A selected `__typename` is filled from the runtime object type when known.
+/// `@skip` and `@include` directives are evaluated with `variable_values`.
+///
+/// Leaf values are copied as-is, without [result coercion].
+///
+/// [result coercion]: https://spec.graphql.org/October2021/#sec-Value-Completion
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::coerce_variable_values;
+/// use apollo_compiler::execution::mask;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { me: User }
+///     type User { id: ID, name: String, email: String }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "{ me { id fullName: name } }",
+///     "query.graphql",
+/// ).unwrap();
+/// let operation = document.operations.get(None).unwrap();
+/// let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+/// let data = serde_json::from_str(
+///     r#"{ "me": { "id": "1", "name": "Alice", "email": "alice@example.com" } }"#
+/// ).unwrap();
+///
+/// let masked = mask(&schema, &document, operation, &variables, &data);
+/// assert_eq!(
+///     serde_json::to_string(&masked).unwrap(),
+///     r#"{"me":{"id":"1","fullName":"Alice"}}"#,
+/// );
+/// ```
+pub fn mask(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Operation,
+    variable_values: &Valid<JsonMap>,
+    data: &JsonValue,
+) -> JsonValue {
+    let masker = Masker {
+        schema,
+        document,
+        variable_values,
+    };
+    masker.mask_value(&[&operation.selection_set], data)
+}
+
+struct Masker<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    variable_values: &'a Valid<JsonMap>,
+}
+
+impl<'a> Masker<'a> {
+    fn mask_value(&self, selection_sets: &[&'a SelectionSet], value: &JsonValue) -> JsonValue {
+        match value {
+            JsonValue::Array(items) => items
+                .iter()
+                .map(|item| self.mask_value(selection_sets, item))
+                .collect(),
+            JsonValue::Object(object) => self.mask_object(selection_sets, object).into(),
+            // Null, or a leaf value where an object was expected: nothing to filter
+            _ => value.clone(),
+        }
+    }
+
+    fn mask_object(&self, selection_sets: &[&'a SelectionSet], object: &JsonMap) -> JsonMap {
+        let parent_type = &selection_sets[0].ty;
+        let object_type = self.schema.get_object(parent_type).or_else(|| {
+            let typename = object.get("__typename")?.as_str()?;
+            self.schema
+                .get_object(typename)
+                .filter(|def| self.schema.is_subtype(parent_type, &def.name))
+        });
+        let mut grouped_fields = IndexMap::default();
+        let mut visited_fragments = HashSet::default();
+        for selection_set in selection_sets {
+            self.collect_fields(
+                parent_type,
+                object_type.map(|def| &**def),
+                &selection_set.selections,
+                &mut visited_fragments,
+                &mut grouped_fields,
+            )
+        }
+        let mut masked = JsonMap::new();
+        for (response_key, fields) in grouped_fields {
+            let field = fields[0];
+            if field.name == "__typename" {
+                if let Some(def) = object_type {
+                    masked.insert(response_key.as_str(), def.name.as_str().into());
+                }
+                continue;
+            }
+            let Some(value) = object
+                .get(response_key.as_str())
+                .or_else(|| object.get(field.name.as_str()))
+            else {
+                continue;
+            };
+            let value = if field.selection_set.selections.is_empty() {
+                value.clone()
+            } else {
+                let nested: Vec<_> = fields.iter().map(|field| &field.selection_set).collect();
+                self.mask_value(&nested, value)
+            };
+            masked.insert(response_key.as_str(), value);
+        }
+        masked
+    }
+
+    /// Like [`CollectFields()`], but `object_type` may be unknown for abstract types.
+    /// Fragments then only apply if they do not narrow down `parent_type`.
+    ///
+    /// [`CollectFields()`]: https://spec.graphql.org/October2021/#CollectFields()
+    fn collect_fields(
+        &self,
+        parent_type: &Name,
+        object_type: Option<&ObjectType>,
+        selections: &'a [Selection],
+        visited_fragments: &mut HashSet<&'a Name>,
+        grouped_fields: &mut IndexMap<&'a Name, Vec<&'a Field>>,
+    ) {
+        let applies = |type_condition: &Name| match object_type {
+            Some(object_type) => does_fragment_type_apply(self.schema, object_type, type_condition),
+            None => type_condition == parent_type,
+        };
+        for selection in selections {
+            if eval_if_arg(selection, "skip", self.variable_values).unwrap_or(false)
+                || !eval_if_arg(selection, "include", self.variable_values).unwrap_or(true)
+            {
+                continue;
+            }
+            match selection {
+                Selection::Field(field) => grouped_fields
+                    .entry(field.response_key())
+                    .or_default()
+                    .push(field.as_ref()),
+                Selection::FragmentSpread(spread) => {
+                    if !visited_fragments.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    let Some(fragment) = self.document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if applies(fragment.type_condition()) {
+                        self.collect_fields(
+                            parent_type,
+                            object_type,
+                            &fragment.selection_set.selections,
+                            visited_fragments,
+                            grouped_fields,
+                        )
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    if inline.type_condition.as_ref().is_none_or(applies) {
+                        self.collect_fields(
+                            parent_type,
+                            object_type,
+                            &inline.selection_set.selections,
+                            visited_fragments,
+                            grouped_fields,
+                        )
+                    }
+                }
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+}
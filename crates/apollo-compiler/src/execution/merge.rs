@@ -0,0 +1,352 @@
+use crate::collections::HashSet;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use crate::execution::ResponseDataPathElement;
+use crate::execution::ResponsePath;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use std::fmt;
+
+/// Two responses being merged by [`merge_responses`] have different values at the same path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseMergeConflict {
+    pub path: ResponsePath,
+    /// The value in the response merged into
+    pub left: JsonValue,
+    /// The value in the other response, which was discarded
+    pub right: JsonValue,
+}
+
+/// Error type of [`merge_responses`]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub struct ResponseMergeError {
+    /// Every conflict found, in response order
+    pub conflicts: Vec<ResponseMergeConflict>,
+    /// The merged response, keeping the left value for each conflict
+    pub response: Box<Response>,
+}
+
+/// Deep-merge two partial responses for parts of the same operation,
+/// such as the two parts of [`SchemaIntrospectionSplit::Both`][crate::execution::SchemaIntrospectionSplit::Both]
+/// or the results of fetching different fields from different sources.
+///
+/// `other` is a response for the object found at `at_path` in `response.data`.
+/// With an empty path, both are responses for the whole operation.
+///
+/// * Objects are merged key by key, and lists item by item, guided by the selection sets
+///   of `operation`. Values of leaf types (scalars and enums) are compared as a whole.
+/// * A `null` on either side wins, since it results from a field error
+///   (or was `null` on both sides).
+/// * Errors of `other` are appended after those of `response`,
+///   with `at_path` prepended to their path.
+///   A request error in `other` (absent data) becomes a field error at `at_path`,
+///   where the value is set to `null`.
+/// * Extensions are merged, with those of `other` replacing those of `response` with the same key.
+///
+/// Unlike [`Response::merge`], with an empty path
+/// fields selected in both responses are merged instead of replaced.
+///
+/// Returns an error if the two responses have different non-null values for the same field
+/// or lists of different lengths. The error has the list of conflicts
+/// and the merged response with values from `response` for those fields.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::merge_responses;
+/// use apollo_compiler::execution::Response;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { me: User } type User { id: ID name: String reviews: [String] }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "{ me { id name reviews } }",
+///     "query.graphql",
+/// ).unwrap();
+/// let operation = document.operations.get(None).unwrap();
+///
+/// let users: Response = serde_json::from_str(
+///     r#"{ "data": { "me": { "id": "1", "name": "Alice" } } }"#
+/// ).unwrap();
+/// let reviews: Response = serde_json::from_str(
+///     r#"{ "data": { "reviews": null }, "errors": [{ "message": "timeout", "path": ["reviews"] }] }"#
+/// ).unwrap();
+///
+/// let merged = merge_responses(
+///     &schema, &document, operation, users, reviews, &"me".parse().unwrap(),
+/// ).unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&merged).unwrap(),
+///     r#"{"errors":[{"message":"timeout","path":["me","reviews"]}],"data":{"me":{"id":"1","name":"Alice","reviews":null}}}"#,
+/// );
+/// ```
+pub fn merge_responses(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Operation,
+    mut response: Response,
+    mut other: Response,
+    at_path: &ResponsePath,
+) -> Result<Response, ResponseMergeError> {
+    let mut merger = Merger {
+        schema,
+        document,
+        conflicts: Vec::new(),
+    };
+    for error in &mut other.errors {
+        if !at_path.is_empty() {
+            error.path.splice(0..0, at_path.iter().cloned());
+        }
+    }
+    response.errors.append(&mut other.errors);
+    response.extensions.extend(other.extensions);
+    if at_path.is_empty() {
+        match (&mut response.data, other.data) {
+            (ResponseData::Absent, _) | (_, ResponseData::Absent) => {
+                response.data = ResponseData::Absent
+            }
+            (ResponseData::Null, _) | (_, ResponseData::Null) => response.data = ResponseData::Null,
+            (ResponseData::Object(data), ResponseData::Object(other_data)) => merger.merge_objects(
+                &[&operation.selection_set],
+                data,
+                other_data,
+                &mut ResponsePath::new(),
+            ),
+        }
+    } else if let ResponseData::Object(data) = &mut response.data {
+        if let Some((target, selection_sets)) =
+            merger.find_at_path(&operation.selection_set, data, at_path)
+        {
+            match other.data {
+                ResponseData::Object(other_data) => {
+                    if let JsonValue::Object(target) = target {
+                        merger.merge_objects(
+                            &selection_sets,
+                            target,
+                            other_data,
+                            &mut at_path.clone(),
+                        )
+                    } else if !target.is_null() {
+                        merger.conflicts.push(ResponseMergeConflict {
+                            path: at_path.clone(),
+                            left: target.clone(),
+                            right: other_data.into(),
+                        })
+                    }
+                }
+                ResponseData::Null | ResponseData::Absent => *target = JsonValue::Null,
+            }
+        }
+    }
+    if merger.conflicts.is_empty() {
+        Ok(response)
+    } else {
+        Err(ResponseMergeError {
+            conflicts: merger.conflicts,
+            response: Box::new(response),
+        })
+    }
+}
+
+struct Merger<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    conflicts: Vec<ResponseMergeConflict>,
+}
+
+impl<'a> Merger<'a> {
+    /// Returns the value at `path` in `data` and the selection sets that apply to it,
+    /// or `None` if there is no such value.
+    fn find_at_path<'data>(
+        &self,
+        selection_set: &'a SelectionSet,
+        data: &'data mut JsonMap,
+        path: &ResponsePath,
+    ) -> Option<(&'data mut JsonValue, Vec<&'a SelectionSet>)> {
+        let mut selection_sets = vec![selection_set];
+        let (ResponseDataPathElement::Field(first_key), rest) = path.split_first()? else {
+            return None;
+        };
+        selection_sets = self.nested_selection_sets(&selection_sets, first_key);
+        let mut value = data.get_mut(first_key.as_str())?;
+        for element in rest {
+            value = match (element, value) {
+                (ResponseDataPathElement::Field(key), JsonValue::Object(object)) => {
+                    selection_sets = self.nested_selection_sets(&selection_sets, key);
+                    object.get_mut(key.as_str())?
+                }
+                (ResponseDataPathElement::ListIndex(index), JsonValue::Array(items)) => {
+                    items.get_mut(*index)?
+                }
+                _ => return None,
+            }
+        }
+        Some((value, selection_sets))
+    }
+
+    fn merge_objects(
+        &mut self,
+        selection_sets: &[&'a SelectionSet],
+        object: &mut JsonMap,
+        other: JsonMap,
+        path: &mut ResponsePath,
+    ) {
+        for (key, other_value) in other {
+            let Some(value) = object.get_mut(key.as_str()) else {
+                object.insert(key, other_value);
+                continue;
+            };
+            let Ok(name) = Name::new(key.as_str()) else {
+                // Not a response key
+                continue;
+            };
+            let fields = self.fields_for_key(selection_sets, &name);
+            let is_leaf = fields.first().is_none_or(|field| {
+                self.schema
+                    .types
+                    .get(field.definition.ty.inner_named_type())
+                    .is_none_or(|ty| ty.is_leaf())
+            });
+            let nested: Vec<_> = fields.iter().map(|field| &field.selection_set).collect();
+            path.push(ResponseDataPathElement::Field(name));
+            self.merge_values(is_leaf, &nested, value, other_value, path);
+            path.pop();
+        }
+    }
+
+    fn merge_values(
+        &mut self,
+        is_leaf: bool,
+        selection_sets: &[&'a SelectionSet],
+        value: &mut JsonValue,
+        other: JsonValue,
+        path: &mut ResponsePath,
+    ) {
+        match (value, other) {
+            (JsonValue::Null, _) => {}
+            (value, JsonValue::Null) => *value = JsonValue::Null,
+            (value, other) if is_leaf => {
+                if *value != other {
+                    self.conflict(path, value, other)
+                }
+            }
+            (JsonValue::Object(object), JsonValue::Object(other)) => {
+                self.merge_objects(selection_sets, object, other, path)
+            }
+            (JsonValue::Array(items), JsonValue::Array(other_items))
+                if items.len() == other_items.len() =>
+            {
+                for (index, (item, other_item)) in items.iter_mut().zip(other_items).enumerate() {
+                    path.push(ResponseDataPathElement::ListIndex(index));
+                    self.merge_values(is_leaf, selection_sets, item, other_item, path);
+                    path.pop();
+                }
+            }
+            (value, other) => self.conflict(path, value, other),
+        }
+    }
+
+    fn conflict(&mut self, path: &ResponsePath, left: &JsonValue, right: JsonValue) {
+        self.conflicts.push(ResponseMergeConflict {
+            path: path.clone(),
+            left: left.clone(),
+            right,
+        })
+    }
+
+    fn nested_selection_sets(
+        &self,
+        selection_sets: &[&'a SelectionSet],
+        response_key: &Name,
+    ) -> Vec<&'a SelectionSet> {
+        self.fields_for_key(selection_sets, response_key)
+            .into_iter()
+            .map(|field| &field.selection_set)
+            .collect()
+    }
+
+    /// Fields with this response key, including in fragments regardless of type condition:
+    /// in a valid document they all have the same response shape.
+    fn fields_for_key(
+        &self,
+        selection_sets: &[&'a SelectionSet],
+        response_key: &Name,
+    ) -> Vec<&'a Field> {
+        let mut fields = Vec::new();
+        let mut visited_fragments = HashSet::default();
+        for selection_set in selection_sets {
+            self.collect_fields(
+                &selection_set.selections,
+                response_key,
+                &mut visited_fragments,
+                &mut fields,
+            )
+        }
+        fields
+    }
+
+    fn collect_fields(
+        &self,
+        selections: &'a [Selection],
+        response_key: &Name,
+        visited_fragments: &mut HashSet<&'a Name>,
+        fields: &mut Vec<&'a Field>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    if field.response_key() == response_key {
+                        fields.push(field)
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    if !visited_fragments.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.document.fragments.get(&spread.fragment_name) {
+                        self.collect_fields(
+                            &fragment.selection_set.selections,
+                            response_key,
+                            visited_fragments,
+                            fields,
+                        )
+                    }
+                }
+                Selection::InlineFragment(inline) => self.collect_fields(
+                    &inline.selection_set.selections,
+                    response_key,
+                    visited_fragments,
+                    fields,
+                ),
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+}
+
+impl fmt::Display for ResponseMergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conflicting values when merging responses")?;
+        for (i, conflict) in self.conflicts.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(
+                f,
+                "{sep}at `{}`, {} and {}",
+                conflict.path, conflict.left, conflict.right
+            )?
+        }
+        Ok(())
+    }
+}
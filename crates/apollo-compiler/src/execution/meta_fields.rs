@@ -0,0 +1,226 @@
+use crate::collections::IndexMap;
+use crate::execution::json_data::resolve_owned_json;
+use crate::execution::resolver::ObjectValue;
+use crate::execution::resolver::ResolvedValue;
+use crate::execution::resolver::Resolver;
+use crate::execution::resolver::ResolverError;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::Schema;
+use std::sync::Arc;
+
+/// Resolves a meta-field registered in a [`MetaFieldRegistry`]
+///
+/// Implemented for closures that take coerced argument values
+/// and return the value of the field as JSON.
+pub trait MetaFieldResolver: Send + Sync {
+    /// Returns the value of the field, or a message for a field error.
+    ///
+    /// The value is completed like with
+    /// [`execute_with_json_data`][crate::execution::execute_with_json_data]:
+    /// JSON objects of interface or union type need a `__typename` entry.
+    fn resolve(&self, arguments: &JsonMap) -> Result<JsonValue, String>;
+}
+
+impl<F> MetaFieldResolver for F
+where
+    F: Fn(&JsonMap) -> Result<JsonValue, String> + Send + Sync,
+{
+    fn resolve(&self, arguments: &JsonMap) -> Result<JsonValue, String> {
+        self(arguments)
+    }
+}
+
+/// Additional meta-fields handled by execution, such as `_service` and `_entities`
+/// for a [federation] subgraph.
+///
+/// During execution, a field registered here is resolved by its [`MetaFieldResolver`]
+/// instead of the usual data source (such as the root value or a route handler)
+/// for objects of the given type, whether at the root of the operation or nested.
+///
+/// Meta-fields are regular fields as far as validation is concerned:
+/// the schema needs to define them.
+/// [`definitions`][Self::definitions] and [`extend_schema`][Self::extend_schema]
+/// can add those definitions to an existing schema.
+///
+/// Used with [`RequestOptions::meta_fields`][crate::execution::request::RequestOptions::meta_fields]
+/// or [`OperationRouter::meta_fields`][crate::execution::OperationRouter::meta_fields].
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::request;
+/// use apollo_compiler::execution::request::RequestOptions;
+/// use apollo_compiler::execution::JsonMap;
+/// use apollo_compiler::execution::MetaFieldRegistry;
+/// use apollo_compiler::Schema;
+///
+/// let sdl = "type Query { me: User } type User { id: ID! name: String }";
+/// let schema = Schema::parse(sdl, "schema.graphql").unwrap();
+/// let meta_fields = MetaFieldRegistry::new()
+///     .definitions("
+///         type _Service { sdl: String }
+///         extend type Query { _service: _Service! }
+///     ")
+///     .register("Query", "_service", move |_arguments: &JsonMap| {
+///         Ok(serde_json::json!({ "sdl": sdl }).into())
+///     });
+/// let schema = meta_fields.extend_schema(&schema).unwrap();
+///
+/// let options = RequestOptions { meta_fields, ..Default::default() };
+/// let root_value = Default::default();
+/// let response = request::execute(
+///     &schema, "{ _service { sdl } }", None, &Default::default(), &root_value, &options,
+/// );
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     format!(r#"{{"data":{{"_service":{{"sdl":"{sdl}"}}}}}}"#),
+/// );
+/// ```
+///
+/// [federation]: https://www.apollographql.com/docs/federation/subgraph-spec/
+#[derive(Clone, Default)]
+pub struct MetaFieldRegistry {
+    definitions: Vec<String>,
+    /// Keyed by object type name then field name
+    resolvers: IndexMap<String, IndexMap<String, Arc<dyn MetaFieldResolver>>>,
+}
+
+impl MetaFieldRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a resolver for the meta-field `field_name` of the object type `type_name`.
+    /// Replaces any previous resolver for the same field.
+    pub fn register(
+        mut self,
+        type_name: impl Into<String>,
+        field_name: impl Into<String>,
+        resolver: impl MetaFieldResolver + 'static,
+    ) -> Self {
+        self.resolvers
+            .entry(type_name.into())
+            .or_default()
+            .insert(field_name.into(), Arc::new(resolver));
+        self
+    }
+
+    /// Add type system definitions for meta-fields and the types they use,
+    /// typically including an extension of the type that has them such as `extend type Query`.
+    ///
+    /// These are not used by execution, only by [`extend_schema`][Self::extend_schema].
+    pub fn definitions(mut self, sdl: impl Into<String>) -> Self {
+        self.definitions.push(sdl.into());
+        self
+    }
+
+    /// Returns a validated copy of `schema` with [`definitions`][Self::definitions] added
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn extend_schema(&self, schema: &Schema) -> Result<Valid<Schema>, WithErrors<Schema>> {
+        let document = crate::ast::Document {
+            sources: schema.sources.clone(),
+            definitions: schema.to_ast().collect(),
+        };
+        let mut builder = Schema::builder().add_ast(&document);
+        for sdl in &self.definitions {
+            builder = builder.parse(sdl, "meta_fields.graphql");
+        }
+        builder.build()?.validate()
+    }
+
+    /// Returns the resolver registered for a meta-field, if any
+    pub fn get(&self, type_name: &str, field_name: &str) -> Option<&dyn MetaFieldResolver> {
+        Some(&**self.resolvers.get(type_name)?.get(field_name)?)
+    }
+
+    /// Returns whether no resolver is registered
+    pub fn is_empty(&self) -> bool {
+        self.resolvers.is_empty()
+    }
+
+    /// Wraps the resolver of an object so that registered meta-fields are consulted first
+    pub(crate) fn wrap<'a>(
+        &'a self,
+        schema: &'a Schema,
+        object_value: Box<ObjectValue<'a>>,
+    ) -> Box<ObjectValue<'a>> {
+        if self.is_empty() {
+            object_value
+        } else {
+            Box::new(MetaFieldsResolver {
+                registry: self,
+                schema,
+                inner: object_value,
+            })
+        }
+    }
+}
+
+impl std::fmt::Debug for MetaFieldRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coordinates = self
+            .resolvers
+            .iter()
+            .flat_map(|(ty, fields)| fields.keys().map(move |field| format!("{ty}.{field}")))
+            .collect::<Vec<_>>();
+        f.debug_struct("MetaFieldRegistry")
+            .field("definitions", &self.definitions)
+            .field("resolvers", &coordinates)
+            .finish()
+    }
+}
+
+struct MetaFieldsResolver<'a> {
+    registry: &'a MetaFieldRegistry,
+    schema: &'a Schema,
+    inner: Box<ObjectValue<'a>>,
+}
+
+impl Resolver for MetaFieldsResolver<'_> {
+    fn type_name(&self) -> &str {
+        self.inner.type_name()
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a JsonMap,
+    ) -> Result<ResolvedValue<'a>, ResolverError> {
+        let type_name = self.inner.type_name();
+        if let Some(resolver) = self.registry.get(type_name, field_name) {
+            let value = resolver
+                .resolve(arguments)
+                .map_err(|message| ResolverError { message })?;
+            let field_type = match self.schema.type_field(type_name, field_name) {
+                Ok(def) => def.ty.inner_named_type().as_str(),
+                // Undefined field: validation should have caught this
+                Err(_) => return Ok(ResolvedValue::leaf(value)),
+            };
+            let resolved = resolve_owned_json(self.schema, field_type, value)?;
+            return Ok(wrap_resolved(self.registry, self.schema, resolved));
+        }
+        let resolved = self.inner.resolve_field(field_name, arguments)?;
+        Ok(wrap_resolved(self.registry, self.schema, resolved))
+    }
+}
+
+/// Wrap nested objects so that their meta-fields are also consulted
+fn wrap_resolved<'a>(
+    registry: &'a MetaFieldRegistry,
+    schema: &'a Schema,
+    resolved: ResolvedValue<'a>,
+) -> ResolvedValue<'a> {
+    match resolved {
+        ResolvedValue::Leaf(_) => resolved,
+        ResolvedValue::Object(object_value) => {
+            ResolvedValue::Object(registry.wrap(schema, object_value))
+        }
+        ResolvedValue::List(iter) => ResolvedValue::List(Box::new(
+            iter.map(move |item| wrap_resolved(registry, schema, item)),
+        )),
+    }
+}
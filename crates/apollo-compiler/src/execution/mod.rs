@@ -6,25 +6,98 @@
 
 #[macro_use]
 mod resolver;
+mod archive;
+mod batch;
+pub(crate) mod cost;
 mod engine;
-mod input_coercion;
-mod introspection_execute;
+mod http;
+pub(crate) mod input_coercion;
+pub(crate) mod introspection_execute;
+mod introspection_failure;
 mod introspection_max_depth;
 mod introspection_split;
+pub(crate) mod introspection_to_ast;
+pub(crate) mod json_data;
+mod mask;
+mod merge;
+mod meta_fields;
+mod operation_router;
+mod payload;
+mod representations;
+pub mod request;
 mod response;
+mod response_keys;
 mod result_coercion;
+mod scalars;
+mod subscription;
+mod websocket;
 
+pub use self::archive::variables_json_schema;
+pub use self::archive::ArchivedOperation;
+pub use self::archive::OperationArchive;
+pub use self::archive::OperationArchiveError;
+pub use self::archive::OPERATION_ARCHIVE_VERSION;
+pub use self::batch::execute_batch_with_json_data;
+pub use self::batch::Request;
+pub use self::cost::CostPolicy;
+pub use self::cost::QueryCost;
+pub use self::http::HttpRequestRejection;
+pub use self::http::HttpResponseHead;
+pub use self::http::ResponseMediaType;
 pub use self::input_coercion::coerce_variable_values;
+pub use self::input_coercion::coerce_variable_values_with_scalars;
+pub use self::input_coercion::CoercedValue;
 pub use self::input_coercion::InputCoercionError;
+pub use self::introspection_execute::execute_introspection;
 pub use self::introspection_execute::execute_introspection_only_query;
 pub use self::introspection_execute::SchemaIntrospectionQuery;
+pub use self::introspection_failure::IntrospectionFailure;
 pub use self::introspection_max_depth::check_introspection_max_depth;
 pub use self::introspection_split::SchemaIntrospectionError;
 pub use self::introspection_split::SchemaIntrospectionSplit;
+pub use self::introspection_to_ast::introspection_response_to_ast;
+pub use self::introspection_to_ast::FromIntrospectionError;
+pub use self::introspection_to_ast::InvalidIntrospectionError;
+pub use self::json_data::execute_with_json_data;
+pub use self::mask::mask;
+pub use self::merge::merge_responses;
+pub use self::merge::ResponseMergeConflict;
+pub use self::merge::ResponseMergeError;
+pub use self::meta_fields::MetaFieldRegistry;
+pub use self::meta_fields::MetaFieldResolver;
+pub use self::operation_router::OperationRouter;
+pub use self::payload::PayloadOptions;
+pub use self::payload::PersistedQueryMode;
+pub use self::payload::RequestPayload;
+pub use self::representations::parse_representations;
+pub use self::representations::EntityRepresentation;
+pub use self::representations::RepresentationError;
 pub use self::response::GraphQLError;
+pub use self::response::GraphQLErrorBuilder;
+pub use self::response::GraphQLErrorShapeError;
 pub use self::response::Response;
 pub use self::response::ResponseData;
 pub use self::response::ResponseDataPathElement;
+pub use self::response::ResponsePath;
+pub use self::response::ResponsePathParseError;
+pub use self::response_keys::ResponseKeyLimitError;
+pub use self::response_keys::ResponseKeyMetrics;
+pub use self::response_keys::ResponseKeyPolicy;
+pub use self::result_coercion::NumberPrecision;
+pub use self::scalars::ScalarRegistry;
+pub use self::scalars::ScalarSpec;
+pub use self::scalars::WellKnownScalar;
+pub use self::subscription::check_subscription_no_introspection_root;
+pub use self::subscription::check_subscription_single_root_field;
+pub use self::subscription::subscription_root_field;
+pub use self::subscription::SubscriptionRootField;
+pub use self::subscription::SubscriptionRootFieldError;
+pub use self::websocket::WsClientMessage;
+pub use self::websocket::WsConnection;
+pub use self::websocket::WsEvent;
+pub use self::websocket::WsProtocolError;
+pub use self::websocket::WsServerMessage;
+pub use self::websocket::GRAPHQL_TRANSPORT_WS_PROTOCOL;
 /// Re-export of the version of the `serde_json_bytes` crate used for [`JsonValue`] and [`JsonMap`]
 pub use serde_json_bytes;
 
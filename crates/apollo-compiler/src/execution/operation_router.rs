@@ -0,0 +1,304 @@
+use crate::collections::HashMap;
+use crate::executable::Operation;
+use crate::executable::OperationType;
+use crate::execution::batch::execute_batch;
+use crate::execution::batch::BatchCache;
+use crate::execution::engine::execute_selection_set;
+use crate::execution::engine::ExecutionMode;
+use crate::execution::json_data::execute_with_json_data_and_meta_fields;
+use crate::execution::resolver::ResolvedValue;
+use crate::execution::resolver::Resolver;
+use crate::execution::resolver::ResolverError;
+use crate::execution::GraphQLError;
+use crate::execution::JsonMap;
+use crate::execution::MetaFieldRegistry;
+use crate::execution::NumberPrecision;
+use crate::execution::Request;
+use crate::execution::Response;
+use crate::schema::ExtendedType;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+
+type Handler = Box<dyn Fn(&Valid<JsonMap>) -> JsonMap + Send + Sync>;
+
+/// Building blocks for a mock GraphQL server: executes requests against a schema,
+/// with data from handlers bound to operation names or generated mock data otherwise.
+///
+/// This does not do any networking. An HTTP server only needs to pass the `query`,
+/// `operationName`, and `variables` of each request to [`execute`][Self::execute]
+/// and serialize the returned [`Response`].
+///
+/// A handler receives coerced variable values and returns the value of the root object,
+/// which is executed as with [`execute_with_json_data`][crate::execution::execute_with_json_data].
+/// Operations without a handler get mock data instead, based on the schema:
+/// every field is non-null, lists have one item, scalars have placeholder values
+/// (the schema coordinate of the field for strings and custom scalars),
+/// enums use their first value, and abstract types use their first possible object type.
+///
+/// Schema introspection is executed as with
+/// [`SchemaIntrospectionQuery::split_and_execute`][crate::execution::SchemaIntrospectionQuery::split_and_execute].
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::OperationRouter;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { user(id: ID!): User } type User { id: ID! name: String }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let router = OperationRouter::new(schema).route("GetUser", |variables| {
+///     serde_json::from_value(serde_json::json!({
+///         "user": { "id": variables["id"], "name": "Alice" },
+///     })).unwrap()
+/// });
+///
+/// let query = "query GetUser($id: ID!) { user(id: $id) { name } }";
+/// let variables = serde_json::from_str(r#"{ "id": "1" }"#).unwrap();
+/// let response = router.execute(query, None, &variables);
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"data":{"user":{"name":"Alice"}}}"#,
+/// );
+///
+/// let query = "query Other { user(id: 2) { id name } }";
+/// let response = router.execute(query, None, &Default::default());
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"data":{"user":{"id":"1","name":"User.name"}}}"#,
+/// );
+/// ```
+pub struct OperationRouter {
+    schema: Valid<Schema>,
+    handlers: HashMap<String, Handler>,
+    meta_fields: MetaFieldRegistry,
+}
+
+impl OperationRouter {
+    /// Create a router that generates mock data for every operation
+    pub fn new(schema: Valid<Schema>) -> Self {
+        Self {
+            schema,
+            handlers: HashMap::default(),
+            meta_fields: MetaFieldRegistry::default(),
+        }
+    }
+
+    /// Bind a named operation to a handler that returns the value of the root object.
+    /// Replaces any previous handler for the same name.
+    pub fn route(
+        mut self,
+        operation_name: impl Into<String>,
+        handler: impl Fn(&Valid<JsonMap>) -> JsonMap + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers
+            .insert(operation_name.into(), Box::new(handler));
+        self
+    }
+
+    /// Resolve these meta-fields with their registered resolver,
+    /// instead of with handlers or mock data
+    pub fn meta_fields(mut self, meta_fields: MetaFieldRegistry) -> Self {
+        self.meta_fields = meta_fields;
+        self
+    }
+
+    pub fn schema(&self) -> &Valid<Schema> {
+        &self.schema
+    }
+
+    /// Parse, validate, and execute a request.
+    ///
+    /// Returns a response with [request errors] if the query is invalid,
+    /// if `operation_name` does not select an operation, or if variables cannot be coerced.
+    ///
+    /// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+    pub fn execute(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variable_values: &JsonMap,
+    ) -> Response {
+        BatchCache::new(&self.schema, [query]).execute_request(
+            query,
+            operation_name,
+            variable_values,
+            |document, operation, variable_values| {
+                self.execute_operation(document, operation, variable_values)
+            },
+        )
+    }
+
+    /// Execute a batch of requests, with up to `max_concurrency` threads.
+    ///
+    /// Each request is executed as with [`execute`][Self::execute],
+    /// except that identical query strings are parsed and validated once,
+    /// and data about the schema needed by introspection is computed at most once.
+    /// See [`execute_batch_with_json_data`][crate::execution::execute_batch_with_json_data].
+    ///
+    /// Responses are returned in the same order as requests.
+    pub fn execute_batch(&self, requests: &[Request], max_concurrency: usize) -> Vec<Response> {
+        let cache = BatchCache::new(&self.schema, requests.iter().map(|r| r.query.as_str()));
+        execute_batch(requests, max_concurrency, |request| {
+            cache.execute_request(
+                &request.query,
+                request.operation_name.as_deref(),
+                &request.variables,
+                |document, operation, variable_values| {
+                    self.execute_operation(document, operation, variable_values)
+                },
+            )
+        })
+    }
+
+    fn execute_operation(
+        &self,
+        document: &Valid<ExecutableDocument>,
+        operation: &Node<Operation>,
+        variable_values: &Valid<JsonMap>,
+    ) -> Response {
+        let handler = operation
+            .name
+            .as_ref()
+            .and_then(|name| self.handlers.get(name.as_str()));
+        if let Some(handler) = handler {
+            let root_value = handler(variable_values);
+            return execute_with_json_data_and_meta_fields(
+                &self.schema,
+                document,
+                operation,
+                variable_values,
+                &root_value,
+                &self.meta_fields,
+                NumberPrecision::Preserve,
+            );
+        }
+        let mode = match operation.operation_type {
+            OperationType::Query => ExecutionMode::Normal,
+            OperationType::Mutation => ExecutionMode::Sequential,
+            OperationType::Subscription => {
+                return Response::from_request_error(GraphQLError::new(
+                    "subscriptions are not supported",
+                    operation.location(),
+                    &document.sources,
+                ))
+            }
+        };
+        let object_type_name = operation.object_type();
+        let Some(object_type_def) = self.schema.get_object(object_type_name) else {
+            return Response::from_request_error(GraphQLError::new(
+                "Undefined root operation type",
+                object_type_name.location(),
+                &document.sources,
+            ));
+        };
+        let initial_value = self.meta_fields.wrap(
+            &self.schema,
+            Box::new(MockResolver {
+                schema: &self.schema,
+                type_name: object_type_name,
+            }),
+        );
+        let mut errors = Vec::new();
+        let path = None;
+        let data = execute_selection_set(
+            &self.schema,
+            document,
+            variable_values,
+            &mut errors,
+            path,
+            mode,
+            NumberPrecision::Preserve,
+            object_type_def,
+            &*initial_value,
+            &operation.selection_set.selections,
+        );
+        Response {
+            data: data.into(),
+            errors,
+            extensions: Default::default(),
+        }
+    }
+}
+
+impl std::fmt::Debug for OperationRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OperationRouter")
+            .field("schema", &self.schema)
+            .field("handlers", &self.handlers.keys().collect::<Vec<_>>())
+            .field("meta_fields", &self.meta_fields)
+            .finish()
+    }
+}
+
+struct MockResolver<'a> {
+    schema: &'a Schema,
+    type_name: &'a str,
+}
+
+impl Resolver for MockResolver<'_> {
+    fn type_name(&self) -> &str {
+        self.type_name
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        _arguments: &'a JsonMap,
+    ) -> Result<ResolvedValue<'a>, ResolverError> {
+        let field_def = self
+            .schema
+            .type_field(self.type_name, field_name)
+            .map_err(|_| ResolverError {
+                message: format!("undefined field {}.{field_name}", self.type_name),
+            })?;
+        Ok(self.mock_value(field_name, &field_def.ty))
+    }
+}
+
+impl<'a> MockResolver<'a> {
+    fn mock_value(&self, field_name: &str, ty: &'a Type) -> ResolvedValue<'a> {
+        let type_name = match ty {
+            Type::List(item) | Type::NonNullList(item) => {
+                return ResolvedValue::list([self.mock_value(field_name, item)])
+            }
+            Type::Named(name) | Type::NonNullNamed(name) => name,
+        };
+        let object_type_name = match self.schema.types.get(type_name) {
+            Some(ExtendedType::Object(_)) => type_name,
+            Some(ExtendedType::Interface(_) | ExtendedType::Union(_)) => {
+                let first_possible_type = self.schema.types.iter().find_map(|(name, def)| {
+                    (def.is_object() && self.schema.is_subtype(type_name, name)).then_some(name)
+                });
+                match first_possible_type {
+                    Some(name) => name,
+                    None => return ResolvedValue::null(),
+                }
+            }
+            Some(ExtendedType::Enum(def)) => {
+                return match def.values.keys().next() {
+                    Some(value) => ResolvedValue::leaf(value.as_str()),
+                    None => ResolvedValue::null(),
+                }
+            }
+            Some(ExtendedType::Scalar(_)) => {
+                return match type_name.as_str() {
+                    "Int" => ResolvedValue::leaf(0),
+                    "Float" => ResolvedValue::leaf(0.0),
+                    "Boolean" => ResolvedValue::leaf(false),
+                    "ID" => ResolvedValue::leaf("1"),
+                    _ => ResolvedValue::leaf(format!("{}.{field_name}", self.type_name)),
+                }
+            }
+            Some(ExtendedType::InputObject(_)) | None => return ResolvedValue::null(),
+        };
+        ResolvedValue::object(MockResolver {
+            schema: self.schema,
+            type_name: object_type_name,
+        })
+    }
+}
@@ -0,0 +1,268 @@
+use crate::executable::validation::validate_executable_document;
+use crate::executable::OperationType;
+use crate::execution::coerce_variable_values_with_scalars;
+use crate::execution::GraphQLError;
+use crate::execution::HttpRequestRejection;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::execution::ScalarRegistry;
+use crate::parser::Parser;
+use crate::schema::canonical::sha256_hex;
+use crate::validation::Valid;
+use crate::Schema;
+use serde::Serialize;
+use serde_json_bytes::serde_json;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Configuration of [`RequestPayload::build`]
+#[derive(Debug, Clone, Default)]
+pub struct PayloadOptions {
+    /// Configuration for parsing the request document
+    pub parser: Parser,
+
+    /// Specs used to coerce variable values of custom scalars.
+    /// Empty by default: custom scalar values are accepted as-is.
+    pub scalars: ScalarRegistry,
+
+    /// If true, `query` is the document serialized on a single line
+    /// instead of the source text as given.
+    /// This makes for shorter URLs with the `GET` method.
+    pub compact: bool,
+
+    /// Whether to add an [automatic persisted query] entry to `extensions`
+    ///
+    /// [automatic persisted query]: https://www.apollographql.com/docs/apollo-server/performance/apq
+    pub persisted_query: PersistedQueryMode,
+
+    /// Other entries to add to `extensions`
+    pub extensions: JsonMap,
+}
+
+/// How [`RequestPayload::build`] uses automatic persisted queries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PersistedQueryMode {
+    /// Send the query text only
+    #[default]
+    Disabled,
+    /// Send the query text together with its SHA-256 hash in
+    /// `extensions.persistedQuery`, so that the server stores it
+    Register,
+    /// Send only the hash, for a query that the server is expected to have stored.
+    /// The server responds with a `PERSISTED_QUERY_NOT_FOUND` error otherwise.
+    HashOnly,
+}
+
+/// The body of a GraphQL request, ready to send over HTTP.
+///
+/// Built from source text and variables with [`RequestPayload::build`],
+/// which checks that the request would be accepted by a server using the same schema.
+/// This is intended for debugging tools such as CLIs:
+/// the payload can be sent as a JSON body with [`to_json`][Self::to_json],
+/// as a URL query string with [`to_query_string`][Self::to_query_string],
+/// or printed as a [cURL] command with [`to_curl`][Self::to_curl].
+///
+/// [cURL]: https://curl.se/
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::PayloadOptions;
+/// use apollo_compiler::execution::PersistedQueryMode;
+/// use apollo_compiler::execution::RequestPayload;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { greeting(name: String!, polite: Boolean = true): String }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let variables = serde_json::from_str(r#"{ "name": "Alice" }"#).unwrap();
+/// let options = PayloadOptions {
+///     compact: true,
+///     persisted_query: PersistedQueryMode::Register,
+///     ..Default::default()
+/// };
+/// let payload = RequestPayload::build(
+///     &schema,
+///     "query Greet($name: String!) {\n  greeting(name: $name)\n}",
+///     None,
+///     &variables,
+///     &options,
+/// ).unwrap();
+/// assert_eq!(payload.query.as_deref(), Some("query Greet($name: String!) { greeting(name: $name) }"));
+/// assert_eq!(payload.operation_name.as_deref(), Some("Greet"));
+/// assert!(payload.to_json().contains(r#""persistedQuery":{"version":1,"sha256Hash":"#));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPayload {
+    /// `None` with [`PersistedQueryMode::HashOnly`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+
+    /// The name of the selected operation, if it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+
+    /// Variable values after [coercion][crate::execution::coerce_variable_values]
+    #[serde(skip_serializing_if = "JsonMap::is_empty")]
+    pub variables: JsonMap,
+
+    #[serde(skip_serializing_if = "JsonMap::is_empty")]
+    pub extensions: JsonMap,
+
+    /// The type of the selected operation. Not serialized.
+    #[serde(skip)]
+    pub operation_type: OperationType,
+}
+
+impl RequestPayload {
+    /// Build the body of a request for the operation named `operation_name`
+    /// (or the only operation if `None`) in `document_text`.
+    ///
+    /// The document is parsed and validated against `schema`,
+    /// and `variables` are coerced for the selected operation.
+    /// If any of these steps fail, returns the errors a server would respond with.
+    pub fn build(
+        schema: &Valid<Schema>,
+        document_text: impl Into<String>,
+        operation_name: Option<&str>,
+        variables: &JsonMap,
+        options: &PayloadOptions,
+    ) -> Result<Self, Vec<GraphQLError>> {
+        let document_text = document_text.into();
+        let mut parser = options.parser.clone();
+        let (document, mut errors) =
+            parser.parse_executable_inner(schema, document_text.clone(), "request.graphql");
+        Arc::make_mut(&mut errors.sources)
+            .extend(schema.sources.iter().map(|(k, v)| (*k, v.clone())));
+        validate_executable_document(&mut errors, schema, &document);
+        let document = errors
+            .into_valid_result(document)
+            .map_err(|with_errors| with_errors.errors.to_graphql_errors())?;
+        let Ok(operation) = document.operations.get(operation_name) else {
+            let message = match operation_name {
+                Some(name) => format!("operation `{name}` not found in the request"),
+                None => "operation name is required in a request with many operations".into(),
+            };
+            return Err(vec![GraphQLError::new(message, None, &document.sources)]);
+        };
+        let variables =
+            coerce_variable_values_with_scalars(schema, operation, variables, &options.scalars)
+                .map_err(|err| vec![err.into_graphql_error(&document.sources)])?;
+        let query = if options.compact {
+            document.serialize().no_indent().to_string()
+        } else {
+            document_text
+        };
+        let mut extensions = options.extensions.clone();
+        let query = match options.persisted_query {
+            PersistedQueryMode::Disabled => Some(query),
+            PersistedQueryMode::Register | PersistedQueryMode::HashOnly => {
+                let mut persisted_query = JsonMap::new();
+                persisted_query.insert("version", 1.into());
+                persisted_query.insert("sha256Hash", sha256_hex(&query).into());
+                extensions.insert("persistedQuery", persisted_query.into());
+                (options.persisted_query == PersistedQueryMode::Register).then_some(query)
+            }
+        };
+        Ok(Self {
+            query,
+            operation_name: operation.name.as_ref().map(|name| name.to_string()),
+            variables: variables.into_inner(),
+            extensions,
+            operation_type: operation.operation_type,
+        })
+    }
+
+    /// Serialize as a JSON request body, to send with the `POST` method
+    /// and `Content-Type: application/json`
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("JSON serialization should not fail")
+    }
+
+    /// Encode as a URL query string (without the leading `?`),
+    /// to send with the `GET` method as described in [GraphQL over HTTP].
+    ///
+    /// Returns an error for a mutation, which servers reject with the `GET` method.
+    ///
+    /// [GraphQL over HTTP]: https://graphql.github.io/graphql-over-http/draft/#sec-GET
+    pub fn to_query_string(&self) -> Result<String, HttpRequestRejection> {
+        if self.operation_type == OperationType::Mutation {
+            return Err(HttpRequestRejection::MutationOverGet);
+        }
+        let mut parameters = Vec::new();
+        if let Some(query) = &self.query {
+            parameters.push(("query", query.clone()))
+        }
+        if let Some(name) = &self.operation_name {
+            parameters.push(("operationName", name.clone()))
+        }
+        for (key, map) in [
+            ("variables", &self.variables),
+            ("extensions", &self.extensions),
+        ] {
+            if !map.is_empty() {
+                parameters.push((key, JsonValue::Object(map.clone()).to_string()))
+            }
+        }
+        let mut out = String::new();
+        for (key, value) in parameters {
+            if !out.is_empty() {
+                out.push('&')
+            }
+            out.push_str(key);
+            out.push('=');
+            percent_encode(&mut out, &value)
+        }
+        Ok(out)
+    }
+
+    /// Returns a [cURL](https://curl.se/) command that sends this request to `url`,
+    /// quoted for a POSIX shell.
+    ///
+    /// With `use_get`, the payload is sent in the query string with the `GET` method,
+    /// which fails for mutations as for [`to_query_string`][Self::to_query_string].
+    /// Otherwise it is sent as a JSON body with the `POST` method.
+    pub fn to_curl(&self, url: &str, use_get: bool) -> Result<String, HttpRequestRejection> {
+        let mut out = String::from("curl");
+        if use_get {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            let url = format!("{url}{separator}{}", self.to_query_string()?);
+            write!(out, " {}", shell_quote(&url)).unwrap();
+        } else {
+            write!(
+                out,
+                " --request POST {} --header {} --data {}",
+                shell_quote(url),
+                shell_quote("Content-Type: application/json"),
+                shell_quote(&self.to_json())
+            )
+            .unwrap();
+        }
+        write!(
+            out,
+            " --header {}",
+            shell_quote("Accept: application/graphql-response+json, application/json")
+        )
+        .unwrap();
+        Ok(out)
+    }
+}
+
+/// Percent-encode everything except unreserved characters of RFC 3986
+fn percent_encode(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char)
+        } else {
+            write!(out, "%{byte:02X}").unwrap()
+        }
+    }
+}
+
+/// Single-quote for a POSIX shell, where nothing is special within single quotes
+/// except the single quote itself
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
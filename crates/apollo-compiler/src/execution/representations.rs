@@ -0,0 +1,449 @@
+use crate::collections::HashMap;
+use crate::executable::Field;
+use crate::executable::FieldSet;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::input_coercion::coerce_variable_value;
+use crate::execution::InputCoercionError;
+use crate::execution::JsonMap;
+use crate::execution::JsonValue;
+use crate::execution::ResponseDataPathElement;
+use crate::execution::ResponsePath;
+use crate::execution::ScalarRegistry;
+use crate::schema::ExtendedType;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// An entity representation from the `representations` argument of a federation `_entities`
+/// field, matched to an `@key` of its type by [`parse_representations`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityRepresentation {
+    /// The value of `__typename`
+    pub type_name: Name,
+    /// The index of the matched key among `@key` directives of the type, in schema order
+    pub key_index: usize,
+    /// The values of the fields of the matched key, coerced to their types.
+    /// Fields of nested objects only contain key fields.
+    pub key_values: JsonMap,
+    /// The whole representation, with the values of key fields coerced.
+    /// Other entries such as `__typename` or fields needed by `@requires` are kept as-is.
+    pub representation: JsonMap,
+}
+
+/// Error type of [`parse_representations`], for one representation
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RepresentationError {
+    #[error("representation {index} is not an object")]
+    NotAnObject { index: usize },
+
+    #[error("representation {index} does not have a string `__typename`")]
+    MissingTypename { index: usize },
+
+    #[error("representation {index} has undefined type `{type_name}`")]
+    UndefinedType { index: usize, type_name: String },
+
+    #[error("representation {index} has type `{type_name}` which has no `@key` directive")]
+    NotAnEntity { index: usize, type_name: Name },
+
+    #[error(
+        "representation {index} has type `{type_name}` \
+         with invalid `@key(fields: {fields:?})`: {message}"
+    )]
+    InvalidKey {
+        index: usize,
+        type_name: Name,
+        /// The `fields` argument of the invalid `@key`
+        fields: String,
+        message: String,
+    },
+
+    #[error(
+        "representation {index} of type `{type_name}` does not match any `@key`, missing {}",
+        missing.iter().map(|path| format!("`{path}`")).collect::<Vec<_>>().join(", ")
+    )]
+    NoMatchingKey {
+        index: usize,
+        type_name: Name,
+        /// Paths of the missing fields for the closest key
+        missing: Vec<ResponsePath>,
+    },
+
+    #[error("representation {index} has an invalid value at `{path}`: {message}")]
+    InvalidValue {
+        index: usize,
+        path: ResponsePath,
+        message: String,
+    },
+}
+
+impl RepresentationError {
+    /// The index of the representation in the list passed to [`parse_representations`]
+    pub fn index(&self) -> usize {
+        match self {
+            Self::NotAnObject { index }
+            | Self::MissingTypename { index }
+            | Self::UndefinedType { index, .. }
+            | Self::NotAnEntity { index, .. }
+            | Self::InvalidKey { index, .. }
+            | Self::NoMatchingKey { index, .. }
+            | Self::InvalidValue { index, .. } => *index,
+        }
+    }
+}
+
+/// Parse and validate the `representations` argument of a federation `_entities` field
+/// (a list of `_Any` scalar values), for example in a
+/// [`MetaFieldResolver`][crate::execution::MetaFieldResolver].
+///
+/// For each representation, in order:
+///
+/// * Check that it is an object with a `__typename` naming an object or interface type
+///   that has at least one `@key(fields: "…")` directive
+/// * Select the first key whose fields are all present, including nested fields
+///   of composite key fields
+/// * Coerce the values of key fields to their type in the schema,
+///   with the same rules as for variables.
+///   Values of custom scalars are coerced by their spec in `scalars`, if any.
+///
+/// Each representation can fail independently. Errors include the index of the representation
+/// and, where relevant, the path within it.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::parse_representations;
+/// use apollo_compiler::execution::ScalarRegistry;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+///     type Query { me: User }
+///     type User @key(fields: "id") @key(fields: "email") { id: Int! email: String! }
+/// "#, "schema.graphql").unwrap();
+/// let representations: Vec<_> = serde_json::from_str(r#"[
+///     { "__typename": "User", "email": "alice@example.com" },
+///     { "__typename": "User", "id": "2" }
+/// ]"#).unwrap();
+///
+/// let results = parse_representations(&schema, &ScalarRegistry::new(), &representations);
+/// let first = results[0].as_ref().unwrap();
+/// assert_eq!(first.type_name, "User");
+/// assert_eq!(first.key_index, 1);
+/// assert_eq!(first.key_values["email"], "alice@example.com");
+/// assert_eq!(
+///     results[1].as_ref().unwrap_err().to_string(),
+///     "representation 1 has an invalid value at `id`: \
+///      Could not coerce field User.id: \"2\" to type Int",
+/// );
+/// ```
+pub fn parse_representations(
+    schema: &Valid<Schema>,
+    scalars: &ScalarRegistry,
+    representations: &[JsonValue],
+) -> Vec<Result<EntityRepresentation, RepresentationError>> {
+    let mut keys = HashMap::default();
+    representations
+        .iter()
+        .enumerate()
+        .map(|(index, representation)| {
+            parse_representation(schema, scalars, &mut keys, index, representation)
+        })
+        .collect()
+}
+
+/// Field sets of `@key` directives of a type, or their `fields` argument and an error message
+type Keys = Vec<Result<Valid<FieldSet>, (String, String)>>;
+
+fn parse_representation(
+    schema: &Valid<Schema>,
+    scalars: &ScalarRegistry,
+    keys: &mut HashMap<Name, Keys>,
+    index: usize,
+    representation: &JsonValue,
+) -> Result<EntityRepresentation, RepresentationError> {
+    let Some(object) = representation.as_object() else {
+        return Err(RepresentationError::NotAnObject { index });
+    };
+    let Some(type_name) = object.get("__typename").and_then(JsonValue::as_str) else {
+        return Err(RepresentationError::MissingTypename { index });
+    };
+    let type_name = match schema.types.get_key_value(type_name) {
+        Some((name, ExtendedType::Object(_) | ExtendedType::Interface(_))) => name,
+        _ => {
+            return Err(RepresentationError::UndefinedType {
+                index,
+                type_name: type_name.to_owned(),
+            })
+        }
+    };
+    let type_keys = keys
+        .entry(type_name.clone())
+        .or_insert_with(|| parse_keys(schema, type_name));
+    if type_keys.is_empty() {
+        return Err(RepresentationError::NotAnEntity {
+            index,
+            type_name: type_name.clone(),
+        });
+    }
+
+    let mut closest_missing: Option<Vec<ResponsePath>> = None;
+    for (key_index, key) in type_keys.iter().enumerate() {
+        let field_set = match key {
+            Ok(field_set) => field_set,
+            Err((fields, message)) => {
+                return Err(RepresentationError::InvalidKey {
+                    index,
+                    type_name: type_name.clone(),
+                    fields: fields.clone(),
+                    message: message.clone(),
+                })
+            }
+        };
+        let mut missing = Vec::new();
+        find_missing(
+            &field_set.selection_set,
+            object,
+            &mut Vec::new(),
+            &mut missing,
+        );
+        if missing.is_empty() {
+            let mut path = Vec::new();
+            let mut representation = object.clone();
+            let key_values = coerce_selection_set(
+                schema,
+                scalars,
+                &field_set.selection_set,
+                &mut representation,
+                &mut path,
+            )
+            .map_err(|(path, message)| RepresentationError::InvalidValue {
+                index,
+                path,
+                message,
+            })?;
+            return Ok(EntityRepresentation {
+                type_name: type_name.clone(),
+                key_index,
+                key_values,
+                representation,
+            });
+        }
+        if closest_missing
+            .as_ref()
+            .is_none_or(|closest| missing.len() < closest.len())
+        {
+            closest_missing = Some(missing)
+        }
+    }
+    Err(RepresentationError::NoMatchingKey {
+        index,
+        type_name: type_name.clone(),
+        missing: closest_missing.unwrap_or_default(),
+    })
+}
+
+fn parse_keys(schema: &Valid<Schema>, type_name: &Name) -> Keys {
+    schema.types[type_name]
+        .directives()
+        .get_all("key")
+        .map(|key| {
+            let Some(fields) = key
+                .specified_argument_by_name("fields")
+                .and_then(|value| value.as_str())
+            else {
+                return Err((String::new(), "missing `fields` argument".to_owned()));
+            };
+            FieldSet::parse_and_validate(schema, type_name.clone(), fields, "@key").map_err(
+                |with_errors| {
+                    let message = with_errors
+                        .errors
+                        .iter()
+                        .map(|diagnostic| diagnostic.error.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    (fields.to_owned(), message)
+                },
+            )
+        })
+        .collect()
+}
+
+/// Fields in `selection_set` selected by a representation, for inline fragments
+fn fields_of<'a>(
+    selection_set: &'a SelectionSet,
+    object: &'a JsonMap,
+) -> impl Iterator<Item = &'a Node<Field>> + 'a {
+    selection_set
+        .selections
+        .iter()
+        .flat_map(move |selection| -> Box<dyn Iterator<Item = _> + 'a> {
+            match selection {
+                Selection::Field(field) => Box::new(std::iter::once(field)),
+                Selection::InlineFragment(inline) => {
+                    let applies = inline.type_condition.as_ref().is_none_or(|condition| {
+                        object.get("__typename").and_then(JsonValue::as_str)
+                            == Some(condition.as_str())
+                    });
+                    if applies {
+                        Box::new(fields_of(&inline.selection_set, object))
+                    } else {
+                        Box::new(std::iter::empty())
+                    }
+                }
+                // Not allowed in field sets
+                Selection::FragmentSpread(_) => Box::new(std::iter::empty()),
+                Selection::Invalid(_) => Box::new(std::iter::empty()),
+            }
+        })
+}
+
+fn find_missing(
+    selection_set: &SelectionSet,
+    object: &JsonMap,
+    path: &mut Vec<ResponseDataPathElement>,
+    missing: &mut Vec<ResponsePath>,
+) {
+    for field in fields_of(selection_set, object) {
+        let key = field.response_key();
+        path.push(ResponseDataPathElement::Field(key.clone()));
+        match object.get(key.as_str()) {
+            None => missing.push(ResponsePath(path.clone())),
+            Some(value) => find_missing_in_value(&field.selection_set, value, path, missing),
+        }
+        path.pop();
+    }
+}
+
+fn find_missing_in_value(
+    selection_set: &SelectionSet,
+    value: &JsonValue,
+    path: &mut Vec<ResponseDataPathElement>,
+    missing: &mut Vec<ResponsePath>,
+) {
+    match value {
+        JsonValue::Object(object) if !selection_set.selections.is_empty() => {
+            find_missing(selection_set, object, path, missing)
+        }
+        JsonValue::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                path.push(ResponseDataPathElement::ListIndex(i));
+                find_missing_in_value(selection_set, item, path, missing);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Coerce in place the key fields of `object`, and return their coerced values
+fn coerce_selection_set(
+    schema: &Valid<Schema>,
+    scalars: &ScalarRegistry,
+    selection_set: &SelectionSet,
+    object: &mut JsonMap,
+    path: &mut Vec<ResponseDataPathElement>,
+) -> Result<JsonMap, (ResponsePath, String)> {
+    let mut key_values = JsonMap::new();
+    let fields: Vec<_> = fields_of(selection_set, object).cloned().collect();
+    for field in fields {
+        let key = field.response_key();
+        path.push(ResponseDataPathElement::Field(key.clone()));
+        if let Some(value) = object.get_mut(key.as_str()) {
+            let key_value = coerce_value(
+                schema,
+                scalars,
+                &selection_set.ty,
+                &field,
+                field.ty(),
+                value,
+                path,
+            )?;
+            key_values.insert(key.as_str(), key_value);
+        }
+        path.pop();
+    }
+    Ok(key_values)
+}
+
+fn coerce_value(
+    schema: &Valid<Schema>,
+    scalars: &ScalarRegistry,
+    parent_type: &Name,
+    field: &Field,
+    ty: &Type,
+    value: &mut JsonValue,
+    path: &mut Vec<ResponseDataPathElement>,
+) -> Result<JsonValue, (ResponsePath, String)> {
+    let error = |path: &[ResponseDataPathElement], message| (ResponsePath(path.to_vec()), message);
+    if value.is_null() {
+        if ty.is_non_null() {
+            return Err(error(
+                path,
+                format!(
+                    "null value for field {parent_type}.{} of non-null type {ty}",
+                    field.name
+                ),
+            ));
+        }
+        return Ok(JsonValue::Null);
+    }
+    if let Type::List(inner) | Type::NonNullList(inner) = ty {
+        let Some(items) = value.as_array_mut() else {
+            return Err(error(
+                path,
+                format!(
+                    "expected a list for field {parent_type}.{} of type {ty}",
+                    field.name
+                ),
+            ));
+        };
+        let mut key_values = Vec::with_capacity(items.len());
+        for (i, item) in items.iter_mut().enumerate() {
+            path.push(ResponseDataPathElement::ListIndex(i));
+            key_values.push(coerce_value(
+                schema,
+                scalars,
+                parent_type,
+                field,
+                inner,
+                item,
+                path,
+            )?);
+            path.pop();
+        }
+        return Ok(key_values.into());
+    }
+    if field.selection_set.selections.is_empty() {
+        // Leaf type
+        let coerced = coerce_variable_value(
+            schema,
+            scalars,
+            "field",
+            parent_type,
+            ".",
+            &field.name,
+            &Type::Named(ty.inner_named_type().clone()),
+            value,
+        )
+        .map_err(|err| match err {
+            InputCoercionError::ValueError { message, .. } => error(path, message),
+            InputCoercionError::SuspectedValidationBug(bug) => error(path, bug.message),
+        })?;
+        *value = coerced.clone();
+        return Ok(coerced);
+    }
+    let Some(object) = value.as_object_mut() else {
+        return Err(error(
+            path,
+            format!(
+                "expected an object for field {parent_type}.{} of type {ty}",
+                field.name
+            ),
+        ));
+    };
+    let key_values = coerce_selection_set(schema, scalars, &field.selection_set, object, path)?;
+    Ok(key_values.into())
+}
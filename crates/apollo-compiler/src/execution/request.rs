@@ -0,0 +1,169 @@
+//! Executing a whole GraphQL request in one call,
+//! from source text to a response.
+
+use crate::executable::validation::validate_executable_document;
+use crate::execution::coerce_variable_values_with_scalars;
+use crate::execution::json_data::execute_with_json_data_and_meta_fields;
+use crate::execution::CostPolicy;
+use crate::execution::GraphQLError;
+use crate::execution::JsonMap;
+use crate::execution::MetaFieldRegistry;
+use crate::execution::NumberPrecision;
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use crate::execution::ResponseKeyPolicy;
+use crate::execution::ScalarRegistry;
+use crate::execution::SchemaIntrospectionQuery;
+use crate::parser::Parser;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Schema;
+use std::sync::Arc;
+
+/// Configuration of each step of [`execute`]
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Configuration for parsing the request document,
+    /// such as recursion and token limits
+    pub parser: Parser,
+
+    /// Specs used to coerce variable values of custom scalars.
+    /// Empty by default: custom scalar values are accepted as-is.
+    pub scalars: ScalarRegistry,
+
+    /// If set, operations exceeding these limits get a request error instead of executing
+    pub response_keys: Option<ResponseKeyPolicy>,
+
+    /// If set, the cost of the operation is added to [`Response::extensions`]
+    pub cost: Option<CostPolicy>,
+
+    /// Meta-fields resolved before looking up `root_value`, such as `_service` or `_entities`
+    pub meta_fields: MetaFieldRegistry,
+
+    /// Whether to return field errors for `Float` and custom scalar values
+    /// that would lose precision as 64-bit floats.
+    /// By default, numbers are passed through unchanged.
+    pub number_precision: NumberPrecision,
+}
+
+/// Execute a GraphQL request against static JSON data, from source text to response.
+///
+/// This runs every step that would otherwise be wired manually:
+///
+/// 1. Parse `document_text` with [`RequestOptions::parser`] and validate it against `schema`
+/// 2. Select the operation named `operation_name`, or the only operation if `None`
+/// 3. Check [`RequestOptions::response_keys`] limits, if any
+/// 4. Coerce `variables` with [`RequestOptions::scalars`]
+/// 5. Execute [schema introspection] as with [`SchemaIntrospectionQuery::split_and_execute`],
+///    and the rest of the operation as with [`execute_with_json_data`] with `root_value`,
+///    except that [`RequestOptions::meta_fields`] are resolved by their registered resolver
+///    and [`RequestOptions::number_precision`] applies to result coercion
+/// 6. Add the cost of the operation to extensions if [`RequestOptions::cost`] is set
+///
+/// If any of steps 1 to 4 fail, the response has [request errors] and no `data` entry.
+/// Otherwise, field errors are returned next to data as usual.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::request;
+/// use apollo_compiler::execution::request::RequestOptions;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { greeting(name: String!): String }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let document = "query Greet($name: String!) { greeting(name: $name) __typename }";
+/// let variables = serde_json::from_str(r#"{ "name": "Alice" }"#).unwrap();
+/// let root_value = serde_json::from_str(r#"{ "greeting": "Hello Alice" }"#).unwrap();
+/// let options = RequestOptions::default();
+///
+/// let response = request::execute(
+///     &schema, document, Some("Greet"), &variables, &root_value, &options,
+/// );
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"data":{"greeting":"Hello Alice","__typename":"Query"}}"#,
+/// );
+///
+/// let response = request::execute(
+///     &schema, document, Some("Greet"), &Default::default(), &root_value, &options,
+/// );
+/// assert_eq!(
+///     response.errors[0].message,
+///     "missing value for non-null variable 'name'",
+/// );
+/// ```
+///
+/// [schema introspection]: https://spec.graphql.org/October2021/#sec-Schema-Introspection
+/// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+/// [`execute_with_json_data`]: crate::execution::execute_with_json_data
+pub fn execute(
+    schema: &Valid<Schema>,
+    document_text: impl Into<String>,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+    root_value: &JsonMap,
+    options: &RequestOptions,
+) -> Response {
+    let mut parser = options.parser.clone();
+    let (document, mut errors) =
+        parser.parse_executable_inner(schema, document_text, "request.graphql");
+    Arc::make_mut(&mut errors.sources).extend(schema.sources.iter().map(|(k, v)| (*k, v.clone())));
+    validate_executable_document(&mut errors, schema, &document);
+    let document = match errors.into_valid_result(document) {
+        Ok(document) => document,
+        Err(with_errors) => {
+            return Response {
+                errors: with_errors.errors.to_graphql_errors(),
+                data: ResponseData::Absent,
+                extensions: Default::default(),
+            }
+        }
+    };
+    let Ok(operation) = document.operations.get(operation_name) else {
+        let message = match operation_name {
+            Some(name) => format!("operation `{name}` not found in the request"),
+            None => "operation name is required in a request with many operations".into(),
+        };
+        return Response::from_request_error(GraphQLError::new(message, None, &document.sources));
+    };
+    if let Some(policy) = &options.response_keys {
+        if let Err(err) = policy.check(&document, operation) {
+            return err.into_response(&document.sources);
+        }
+    }
+    let variable_values =
+        match coerce_variable_values_with_scalars(schema, operation, variables, &options.scalars) {
+            Ok(variable_values) => variable_values,
+            Err(err) => return err.into_response(&document.sources),
+        };
+    let mut response = SchemaIntrospectionQuery::split_and_execute(
+        schema,
+        &document,
+        operation,
+        &variable_values,
+        |document: &Valid<ExecutableDocument>| {
+            let operation = document
+                .operations
+                .get(operation.name.as_deref())
+                .expect("split document keeps the operation");
+            execute_with_json_data_and_meta_fields(
+                schema,
+                document,
+                operation,
+                &variable_values,
+                root_value,
+                &options.meta_fields,
+                options.number_precision,
+            )
+        },
+    );
+    if let Some(policy) = &options.cost {
+        if !response.data.is_absent() {
+            policy.add_to_response(schema, &document, operation, &mut response);
+        }
+    }
+    response
+}
@@ -1,10 +1,13 @@
 use crate::execution::engine::PropagateNull;
 use crate::execution::JsonMap;
+use crate::execution::JsonValue;
 use crate::parser::LineColumn;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use serde::Deserialize;
 use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 
 /// A [GraphQL response](https://spec.graphql.org/October2021/#sec-Response-Format)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -65,9 +68,9 @@ pub struct GraphQLError {
     /// for the particular field found at this path in [`Response::data`].
     ///
     /// [field error]: https://spec.graphql.org/October2021/#sec-Errors.Field-errors
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "ResponsePath::is_empty")]
     #[serde(default)]
-    pub path: Vec<ResponseDataPathElement>,
+    pub path: ResponsePath,
 
     /// Reserved for any additional information
     #[serde(skip_serializing_if = "JsonMap::is_empty")]
@@ -75,8 +78,57 @@ pub struct GraphQLError {
     pub extensions: JsonMap,
 }
 
-/// An element of [`GraphQLError::path`]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// A [path](https://spec.graphql.org/October2021/#sec-Errors.Error-result-format)
+/// to a location in [`Response::data`], such as [`GraphQLError::path`].
+///
+/// Serializes to JSON as an array of strings (response keys) and integers (list indices).
+/// [`Display`][fmt::Display] and [`FromStr`] use a compact textual form
+/// where response keys are separated by dots and list indices are in brackets:
+///
+/// ```
+/// use apollo_compiler::execution::ResponseDataPathElement;
+/// use apollo_compiler::execution::ResponsePath;
+/// use apollo_compiler::name;
+///
+/// let path: ResponsePath = "a.b[3].c".parse().unwrap();
+/// assert_eq!(
+///     *path,
+///     [
+///         ResponseDataPathElement::Field(name!("a")),
+///         ResponseDataPathElement::Field(name!("b")),
+///         ResponseDataPathElement::ListIndex(3),
+///         ResponseDataPathElement::Field(name!("c")),
+///     ]
+/// );
+/// assert_eq!(path.to_string(), "a.b[3].c");
+/// assert_eq!(serde_json::to_string(&path).unwrap(), r#"["a","b",3,"c"]"#);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResponsePath(pub Vec<ResponseDataPathElement>);
+
+/// Builder for a [`GraphQLError`], returned by [`GraphQLError::builder`]
+#[derive(Debug, Clone)]
+pub struct GraphQLErrorBuilder {
+    error: GraphQLError,
+}
+
+/// A [`GraphQLError`] that does not have the shape described in
+/// [the GraphQL spec](https://spec.graphql.org/October2021/#sec-Errors.Error-result-format),
+/// returned by [`GraphQLError::check_spec_shape`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum GraphQLErrorShapeError {
+    /// Line and column numbers start at 1
+    #[error("error location {line}:{column} is not a positive line and column number")]
+    InvalidLocation { line: usize, column: usize },
+    /// A path starts at the root of [`Response::data`], which is an object
+    #[error("error path `{0}` starts with a list index instead of a response key")]
+    PathStartsWithListIndex(ResponsePath),
+}
+
+/// An element of a [`ResponsePath`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ResponseDataPathElement {
     /// The relevant key in an object value
@@ -86,6 +138,18 @@ pub enum ResponseDataPathElement {
     ListIndex(usize),
 }
 
+/// Tried to parse a [`ResponsePath`] from a string not in `a.b[3].c` syntax.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum ResponsePathParseError {
+    /// Invalid format, eg. unexpected characters
+    #[error("invalid response path")]
+    InvalidFormat,
+    /// A response key contains invalid characters
+    #[error(transparent)]
+    InvalidName(#[from] crate::InvalidNameError),
+}
+
 impl Response {
     /// Create a response for a [request error]:
     /// handling of a request was aborted before execution started.
@@ -101,6 +165,9 @@ impl Response {
 
     /// Merge two responses into one, such as to handle
     /// [`SchemaIntrospectionSplit::Both`][crate::execution::SchemaIntrospectionSplit::Both].
+    ///
+    /// Root fields present in both responses are replaced by those of `other`.
+    /// To deep-merge them instead, use [`merge_responses`][crate::execution::merge_responses].
     pub fn merge(mut self, mut other: Self) -> Self {
         match (&mut self.data, other.data) {
             (ResponseData::Absent, _) | (_, ResponseData::Absent) => {
@@ -139,6 +206,87 @@ impl GraphQLError {
             extensions: Default::default(),
         }
     }
+
+    /// Returns a builder for an error with the given message
+    /// and no location, path, or extensions.
+    ///
+    /// ```
+    /// use apollo_compiler::execution::GraphQLError;
+    ///
+    /// let error = GraphQLError::builder("something went wrong")
+    ///     .path("a.b[3]".parse::<apollo_compiler::execution::ResponsePath>().unwrap())
+    ///     .extension("code", "INTERNAL")
+    ///     .build();
+    /// assert_eq!(
+    ///     serde_json::to_string(&error).unwrap(),
+    ///     r#"{"message":"something went wrong","path":["a","b",3],"extensions":{"code":"INTERNAL"}}"#
+    /// );
+    /// ```
+    pub fn builder(message: impl Into<String>) -> GraphQLErrorBuilder {
+        GraphQLErrorBuilder {
+            error: Self {
+                message: message.into(),
+                locations: Vec::new(),
+                path: ResponsePath::new(),
+                extensions: JsonMap::new(),
+            },
+        }
+    }
+
+    /// Check that `locations` and `path` have the shape required by
+    /// [the GraphQL spec](https://spec.graphql.org/October2021/#sec-Errors.Error-result-format).
+    ///
+    /// Errors created from source spans always have this shape,
+    /// but errors deserialized from a subgraph response or built by hand may not.
+    pub fn check_spec_shape(&self) -> Result<(), GraphQLErrorShapeError> {
+        for &LineColumn { line, column } in &self.locations {
+            if line == 0 || column == 0 {
+                return Err(GraphQLErrorShapeError::InvalidLocation { line, column });
+            }
+        }
+        if let Some(ResponseDataPathElement::ListIndex(_)) = self.path.first() {
+            return Err(GraphQLErrorShapeError::PathStartsWithListIndex(
+                self.path.clone(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl GraphQLErrorBuilder {
+    /// Add the line and column of the start of `location`.
+    ///
+    /// Like in [`GraphQLError::new`], this does nothing if
+    /// the source file of `location` is not in `sources`.
+    pub fn location(mut self, location: SourceSpan, sources: &SourceMap) -> Self {
+        self.error.locations.extend(location.line_column(sources));
+        self
+    }
+
+    /// Add a location from its line and column numbers, which start at 1.
+    pub fn line_column(mut self, line_column: LineColumn) -> Self {
+        self.error.locations.push(line_column);
+        self
+    }
+
+    /// Set the path in [`Response::data`] of the field this error is for,
+    /// making it a [field error](https://spec.graphql.org/October2021/#sec-Errors.Field-errors).
+    pub fn path(mut self, path: impl Into<ResponsePath>) -> Self {
+        self.error.path = path.into();
+        self
+    }
+
+    /// Insert an entry in [`GraphQLError::extensions`],
+    /// replacing any existing entry with the same key.
+    pub fn extension(mut self, key: &str, value: impl Into<JsonValue>) -> Self {
+        self.error.extensions.insert(key, value.into());
+        self
+    }
+
+    /// Returns the built error
+    pub fn build(self) -> GraphQLError {
+        self.error
+    }
 }
 
 impl ResponseData {
@@ -183,3 +331,136 @@ impl From<Result<JsonMap, PropagateNull>> for ResponseData {
         }
     }
 }
+
+impl ResponsePath {
+    /// Create an empty path, pointing to the root of [`Response::data`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether this path points to the root of [`Response::data`]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns whether `self` is equal to or an ancestor of `other`
+    pub fn is_prefix_of(&self, other: &Self) -> bool {
+        other.0.starts_with(&self.0)
+    }
+}
+
+impl std::ops::Deref for ResponsePath {
+    type Target = Vec<ResponseDataPathElement>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for ResponsePath {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<ResponseDataPathElement>> for ResponsePath {
+    fn from(elements: Vec<ResponseDataPathElement>) -> Self {
+        Self(elements)
+    }
+}
+
+impl From<ResponsePath> for Vec<ResponseDataPathElement> {
+    fn from(path: ResponsePath) -> Self {
+        path.0
+    }
+}
+
+impl FromIterator<ResponseDataPathElement> for ResponsePath {
+    fn from_iter<I: IntoIterator<Item = ResponseDataPathElement>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for ResponsePath {
+    type Item = ResponseDataPathElement;
+    type IntoIter = std::vec::IntoIter<ResponseDataPathElement>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ResponsePath {
+    type Item = &'a ResponseDataPathElement;
+    type IntoIter = std::slice::Iter<'a, ResponseDataPathElement>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for ResponsePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, element) in self.0.iter().enumerate() {
+            match element {
+                ResponseDataPathElement::Field(name) if i == 0 => write!(f, "{name}")?,
+                ResponseDataPathElement::Field(name) => write!(f, ".{name}")?,
+                ResponseDataPathElement::ListIndex(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Same as [`Vec`], so that debug output matches the JSON representation
+impl fmt::Debug for ResponsePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for ResponsePath {
+    type Err = ResponsePathParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut path = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            if let Some(after_bracket) = rest.strip_prefix('[') {
+                let (index, after_index) = after_bracket
+                    .split_once(']')
+                    .ok_or(ResponsePathParseError::InvalidFormat)?;
+                // Reject signs and other forms accepted by `usize::from_str`
+                if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ResponsePathParseError::InvalidFormat);
+                }
+                let index = index
+                    .parse()
+                    .map_err(|_| ResponsePathParseError::InvalidFormat)?;
+                path.push(ResponseDataPathElement::ListIndex(index));
+                rest = after_index;
+            } else {
+                let key = if path.is_empty() {
+                    rest
+                } else {
+                    rest.strip_prefix('.')
+                        .ok_or(ResponsePathParseError::InvalidFormat)?
+                };
+                let end = key.find(['.', '[']).unwrap_or(key.len());
+                let (key, after_key) = key.split_at(end);
+                path.push(ResponseDataPathElement::Field(crate::Name::new(key)?));
+                rest = after_key;
+            }
+        }
+        Ok(Self(path))
+    }
+}
+
+impl fmt::Display for ResponseDataPathElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseDataPathElement::Field(name) => name.fmt(f),
+            ResponseDataPathElement::ListIndex(index) => index.fmt(f),
+        }
+    }
+}
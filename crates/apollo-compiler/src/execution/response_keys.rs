@@ -0,0 +1,209 @@
+use crate::collections::HashMap;
+use crate::executable::Operation;
+use crate::executable::SelectionSet;
+use crate::execution::cost::group_fields;
+use crate::execution::GraphQLError;
+use crate::execution::Response;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+
+/// Limits on the number of response keys (field names or aliases) of an operation,
+/// to reject requests that are cheap to send but expensive to execute or to serialize.
+///
+/// A common attack selects the same field with thousands of aliases:
+/// `{ a1: expensive a2: expensive a3: expensive … }`.
+/// Field merging does not apply since every alias is a different response key,
+/// and cost analysis based on field definitions may not be configured for that field.
+///
+/// Response keys are counted in each selection set after merging fields and fragments
+/// like [`CollectFields()`], regardless of `@skip`, `@include`, or type conditions.
+/// Keys inside lists are counted once, not once per list item.
+///
+/// ```
+/// use apollo_compiler::execution::ResponseKeyPolicy;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let query = format!("{{ {} }}", (0..100).map(|i| format!(" a{i}: a")).collect::<String>());
+/// let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+/// let operation = document.operations.get(None).unwrap();
+///
+/// let policy = ResponseKeyPolicy::default();
+/// let metrics = policy.analyze(&document, operation);
+/// assert_eq!(metrics.response_keys, 100);
+/// assert_eq!(metrics.max_keys_per_field, 100);
+///
+/// let error = policy.check(&document, operation).unwrap_err();
+/// let response = error.into_response(&document.sources);
+/// assert_eq!(
+///     response.errors[0].message,
+///     "Field `a` is selected with more than 30 different aliases in the same selection set",
+/// );
+/// ```
+///
+/// [`CollectFields()`]: https://spec.graphql.org/October2021/#CollectFields()
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseKeyPolicy {
+    /// Maximum number of response keys in an operation, over all selection sets.
+    /// Defaults to 1000.
+    pub max_response_keys: usize,
+
+    /// Maximum number of different response keys for the same field name
+    /// in one selection set, such as `{ a1: user(id: 1) a2: user(id: 2) }`.
+    /// Defaults to 30.
+    pub max_keys_per_field: usize,
+}
+
+/// Metrics about the response keys of an operation, as computed by [`ResponseKeyPolicy`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseKeyMetrics {
+    /// Number of response keys over all selection sets.
+    ///
+    /// Since nested fragments can make this grow exponentially with the size of the document,
+    /// counting stops once it exceeds [`ResponseKeyPolicy::max_response_keys`].
+    pub response_keys: usize,
+
+    /// How many of those response keys are an alias different from the field name
+    pub aliases: usize,
+
+    /// Largest number of different response keys for the same field name
+    /// in one selection set
+    pub max_keys_per_field: usize,
+}
+
+/// Error type of [`ResponseKeyPolicy::check`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ResponseKeyLimitError {
+    #[error("Operation selects more than {max} response keys")]
+    TooManyResponseKeys { max: usize },
+
+    #[error(
+        "Field `{field_name}` is selected with more than {max} different aliases \
+         in the same selection set"
+    )]
+    TooManyAliases {
+        field_name: Name,
+        max: usize,
+        /// The first selection beyond the limit
+        location: Option<SourceSpan>,
+    },
+}
+
+impl Default for ResponseKeyPolicy {
+    fn default() -> Self {
+        Self {
+            max_response_keys: 1000,
+            max_keys_per_field: 30,
+        }
+    }
+}
+
+impl ResponseKeyPolicy {
+    /// Count response keys and aliases of an operation
+    pub fn analyze(
+        &self,
+        document: &Valid<ExecutableDocument>,
+        operation: &Operation,
+    ) -> ResponseKeyMetrics {
+        self.run(document, operation).metrics
+    }
+
+    /// Count response keys and aliases of an operation,
+    /// and return an error if they exceed the limits of this policy.
+    ///
+    /// This is meant to run before execution: the error can be converted to a
+    /// [request error][ResponseKeyLimitError::into_response] response.
+    pub fn check(
+        &self,
+        document: &Valid<ExecutableDocument>,
+        operation: &Operation,
+    ) -> Result<ResponseKeyMetrics, ResponseKeyLimitError> {
+        let analysis = self.run(document, operation);
+        if let Some(error) = analysis.too_many_aliases {
+            Err(error)
+        } else if analysis.metrics.response_keys > self.max_response_keys {
+            Err(ResponseKeyLimitError::TooManyResponseKeys {
+                max: self.max_response_keys,
+            })
+        } else {
+            Ok(analysis.metrics)
+        }
+    }
+
+    fn run<'a>(
+        &'a self,
+        document: &'a Valid<ExecutableDocument>,
+        operation: &'a Operation,
+    ) -> Analysis<'a> {
+        let mut analysis = Analysis {
+            policy: self,
+            document,
+            metrics: ResponseKeyMetrics::default(),
+            too_many_aliases: None,
+        };
+        analysis.selection_sets(&[&operation.selection_set]);
+        analysis
+    }
+}
+
+struct Analysis<'a> {
+    policy: &'a ResponseKeyPolicy,
+    document: &'a Valid<ExecutableDocument>,
+    metrics: ResponseKeyMetrics,
+    too_many_aliases: Option<ResponseKeyLimitError>,
+}
+
+impl<'a> Analysis<'a> {
+    fn selection_sets(&mut self, selection_sets: &[&'a SelectionSet]) {
+        let grouped = group_fields(self.document, None, None, selection_sets.iter().copied());
+        self.metrics.response_keys += grouped.len();
+        let mut keys_per_field = HashMap::<&Name, usize>::default();
+        for (&response_key, fields) in &grouped {
+            // Fields with the same response key have the same name in a valid document
+            let field_name = &fields[0].name;
+            if response_key != field_name {
+                self.metrics.aliases += 1
+            }
+            let count = keys_per_field.entry(field_name).or_default();
+            *count += 1;
+            self.metrics.max_keys_per_field = self.metrics.max_keys_per_field.max(*count);
+            if *count > self.policy.max_keys_per_field && self.too_many_aliases.is_none() {
+                self.too_many_aliases = Some(ResponseKeyLimitError::TooManyAliases {
+                    field_name: field_name.clone(),
+                    max: self.policy.max_keys_per_field,
+                    location: response_key.location(),
+                })
+            }
+        }
+        for fields in grouped.values() {
+            if self.metrics.response_keys > self.policy.max_response_keys {
+                return;
+            }
+            let nested: Vec<_> = fields.iter().map(|field| &field.selection_set).collect();
+            self.selection_sets(&nested)
+        }
+    }
+}
+
+impl ResponseKeyLimitError {
+    /// Convert into a JSON-serializable error as represented in a GraphQL response
+    pub fn into_graphql_error(self, sources: &SourceMap) -> GraphQLError {
+        let location = match &self {
+            Self::TooManyResponseKeys { .. } => None,
+            Self::TooManyAliases { location, .. } => *location,
+        };
+        GraphQLError::new(self.to_string(), location, sources)
+    }
+
+    /// Convert into a response with this error as a [request error]
+    /// that prevented execution from starting.
+    ///
+    /// [request error]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+    pub fn into_response(self, sources: &SourceMap) -> Response {
+        Response::from_request_error(self.into_graphql_error(sources))
+    }
+}
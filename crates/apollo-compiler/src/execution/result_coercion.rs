@@ -17,6 +17,23 @@ use crate::validation::Valid;
 use crate::ExecutableDocument;
 use crate::Schema;
 
+/// How result coercion treats numbers returned by resolvers for `Float` and custom scalars
+/// that clients reading JSON numbers as 64-bit floats, like JavaScript’s `JSON.parse`,
+/// cannot represent exactly.
+///
+/// Numbers are never converted through `f64` during execution:
+/// 64-bit integers are always serialized exactly,
+/// and so are high-precision decimals with the `arbitrary-precision` Cargo feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NumberPrecision {
+    /// Pass numbers through unchanged
+    #[default]
+    Preserve,
+    /// Return a field error instead of a number that does not round-trip through `f64`,
+    /// such as an integer larger than 2<sup>53</sup>
+    Error,
+}
+
 /// <https://spec.graphql.org/October2021/#CompleteValue()>
 ///
 /// Returns `Err` for a field error being propagated upwards to find a nullable place
@@ -28,6 +45,7 @@ pub(crate) fn complete_value<'a, 'b>(
     errors: &'b mut Vec<GraphQLError>,
     path: LinkedPath<'b>,
     mode: ExecutionMode,
+    precision: NumberPrecision,
     ty: &'a Type,
     resolved: ResolvedValue<'a>,
     fields: &'a [&'a Field],
@@ -72,6 +90,7 @@ pub(crate) fn complete_value<'a, 'b>(
                         errors,
                         Some(&inner_path),
                         mode,
+                        precision,
                         inner_ty,
                         inner_resolved,
                         fields,
@@ -148,33 +167,44 @@ pub(crate) fn complete_value<'a, 'b>(
                             field_error!("Resolver returned {json_value}, expected Int")
                         }
                     }
-                    "Float" => {
-                        // https://spec.graphql.org/October2021/#sec-Float.Result-Coercion
-                        if !json_value.is_f64() {
-                            field_error!("Resolver returned {json_value}, expected Float")
-                        }
+                    // https://spec.graphql.org/October2021/#sec-Float.Result-Coercion
+                    // > If the server encounters an Int, it may coerce it to Float
+                    "Float" if !json_value.is_number() => {
+                        field_error!("Resolver returned {json_value}, expected Float")
                     }
-                    "String" => {
-                        // https://spec.graphql.org/October2021/#sec-String.Result-Coercion
-                        if !json_value.is_string() {
-                            field_error!("Resolver returned {json_value}, expected String")
-                        }
+                    "Float"
+                        if precision == NumberPrecision::Error && loses_precision(&json_value) =>
+                    {
+                        field_error!(
+                            "Resolver returned {json_value} which loses precision as Float"
+                        )
                     }
-                    "Boolean" => {
-                        // https://spec.graphql.org/October2021/#sec-Boolean.Result-Coercion
-                        if !json_value.is_boolean() {
-                            field_error!("Resolver returned {json_value}, expected Boolean")
-                        }
+                    // https://spec.graphql.org/October2021/#sec-String.Result-Coercion
+                    "String" if !json_value.is_string() => {
+                        field_error!("Resolver returned {json_value}, expected String")
                     }
-                    "ID" => {
-                        // https://spec.graphql.org/October2021/#sec-ID.Result-Coercion
-                        if !(json_value.is_string() || json_value.is_i64()) {
-                            field_error!("Resolver returned {json_value}, expected ID")
-                        }
+                    // https://spec.graphql.org/October2021/#sec-Boolean.Result-Coercion
+                    "Boolean" if !json_value.is_boolean() => {
+                        field_error!("Resolver returned {json_value}, expected Boolean")
+                    }
+                    // https://spec.graphql.org/October2021/#sec-ID.Result-Coercion
+                    "ID" if !(json_value.is_string() || json_value.is_i64()) => {
+                        field_error!("Resolver returned {json_value}, expected ID")
+                    }
+                    "Float" | "String" | "Boolean" | "ID" => {
+                        // Valid value for a built-in scalar.
+                    }
+                    _ if precision == NumberPrecision::Error && loses_precision(&json_value) => {
+                        field_error!(
+                            "Resolver returned {json_value} which loses precision \
+                             as a 64-bit float for {ty_name}"
+                        )
                     }
                     _ => {
                         // Custom scalar: accept any JSON value (including an array or object,
                         // despite this being a "leaf" as far as GraphQL resolution is concerned)
+                        // and pass it through unchanged, including 64-bit integers
+                        // and (with the `arbitrary-precision` feature) decimals
                     }
                 },
             };
@@ -213,6 +243,7 @@ pub(crate) fn complete_value<'a, 'b>(
         errors,
         path,
         mode,
+        precision,
         object_type,
         &*resolved_obj,
         fields
@@ -221,3 +252,40 @@ pub(crate) fn complete_value<'a, 'b>(
     )
     .map(JsonValue::Object)
 }
+
+/// Returns whether `value` is or contains a number that changes when converted to `f64`
+fn loses_precision(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Number(number) => {
+            let Some(float) = number.as_f64().filter(|float| float.is_finite()) else {
+                return true;
+            };
+            normalize_decimal(&number.to_string()) != normalize_decimal(&float.to_string())
+        }
+        JsonValue::Array(items) => items.iter().any(loses_precision),
+        JsonValue::Object(object) => object.values().any(loses_precision),
+        JsonValue::Null | JsonValue::Bool(_) | JsonValue::String(_) => false,
+    }
+}
+
+/// Parses a JSON number into sign, significant digits, and exponent,
+/// so that two representations of the same number compare equal
+fn normalize_decimal(number: &str) -> Option<(bool, String, i64)> {
+    let (negative, number) = match number.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, number),
+    };
+    let (mantissa, exponent) = match number.find(['e', 'E']) {
+        Some(index) => (&number[..index], number[index + 1..].parse::<i64>().ok()?),
+        None => (number, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{integer}{fraction}");
+    let trimmed_end = digits.trim_end_matches('0');
+    let exponent = exponent - fraction.len() as i64 + (digits.len() - trimmed_end.len()) as i64;
+    let significant = trimmed_end.trim_start_matches('0');
+    if significant.is_empty() {
+        return Some((false, String::new(), 0));
+    }
+    Some((negative, significant.to_owned(), exponent))
+}
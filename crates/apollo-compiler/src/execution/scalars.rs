@@ -0,0 +1,297 @@
+use crate::collections::HashMap;
+use crate::execution::JsonValue;
+use crate::schema::ExtendedType;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// Validation and coercion of the values of a custom scalar type.
+///
+/// Register implementations in a [`ScalarRegistry`] and pass it to
+/// [`coerce_variable_values_with_scalars`][crate::execution::coerce_variable_values_with_scalars].
+/// Without one, values of custom scalars are accepted as-is.
+pub trait ScalarSpec: Send + Sync {
+    /// Validate a JSON value provided as input, such as a request variable,
+    /// and return the coerced value.
+    ///
+    /// The error message is included in the request error.
+    fn coerce_input(&self, value: &JsonValue) -> Result<JsonValue, String>;
+
+    /// The URL that a schema may give in a `@specifiedBy` directive for this scalar, if any
+    fn specified_by_url(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Specifications for commonly used custom scalars, built into apollo-compiler.
+///
+/// Values are accepted in their JSON representation and returned unchanged when valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WellKnownScalar {
+    /// A string with a date, time, and time zone offset as in
+    /// [RFC 3339](https://datatracker.ietf.org/doc/html/rfc3339#section-5.6),
+    /// such as `"2024-02-29T13:45:00.5+01:00"`
+    DateTime,
+    /// A string with the hyphenated hexadecimal form of a UUID,
+    /// such as `"123e4567-e89b-12d3-a456-426614174000"`
+    Uuid,
+    /// A string with an absolute URL, such as `"https://example.com/a?b"`
+    Url,
+    /// An integer that may not fit in 32 bits:
+    /// either a JSON number without fractional part, or a string of decimal digits
+    /// with an optional leading `-`
+    BigInt,
+    /// Any JSON value
+    Json,
+}
+
+impl WellKnownScalar {
+    /// Every well-known scalar
+    pub const ALL: [Self; 5] = [
+        Self::DateTime,
+        Self::Uuid,
+        Self::Url,
+        Self::BigInt,
+        Self::Json,
+    ];
+
+    /// The conventional name of a custom scalar with this specification
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DateTime => "DateTime",
+            Self::Uuid => "UUID",
+            Self::Url => "URL",
+            Self::BigInt => "BigInt",
+            Self::Json => "JSON",
+        }
+    }
+
+    /// Returns the well-known scalar with this conventional name, if any
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|spec| spec.name() == name)
+    }
+}
+
+impl ScalarSpec for WellKnownScalar {
+    fn coerce_input(&self, value: &JsonValue) -> Result<JsonValue, String> {
+        let valid = match self {
+            Self::DateTime => value.as_str().is_some_and(is_date_time),
+            Self::Uuid => value.as_str().is_some_and(is_uuid),
+            Self::Url => value.as_str().is_some_and(is_url),
+            Self::BigInt => {
+                value.is_i64() || value.is_u64() || value.as_str().is_some_and(is_big_int)
+            }
+            Self::Json => true,
+        };
+        if valid {
+            Ok(value.clone())
+        } else {
+            let expected = match self {
+                Self::DateTime => "an RFC 3339 date-time string",
+                Self::Uuid => "a UUID string",
+                Self::Url => "an absolute URL string",
+                Self::BigInt => "an integer or a string of decimal digits",
+                Self::Json => unreachable!(),
+            };
+            Err(format!("expected {expected}, got {value}"))
+        }
+    }
+
+    fn specified_by_url(&self) -> Option<&str> {
+        match self {
+            Self::DateTime => Some("https://datatracker.ietf.org/doc/html/rfc3339"),
+            Self::Uuid => Some("https://datatracker.ietf.org/doc/html/rfc4122"),
+            Self::Url => Some("https://url.spec.whatwg.org/"),
+            Self::BigInt | Self::Json => None,
+        }
+    }
+}
+
+/// A set of [`ScalarSpec`]s, keyed by the name of the custom scalar type they apply to.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::coerce_variable_values_with_scalars;
+/// use apollo_compiler::execution::JsonMap;
+/// use apollo_compiler::execution::ScalarRegistry;
+/// use apollo_compiler::execution::WellKnownScalar;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = "scalar Timestamp type Query { events(after: Timestamp): [String] }";
+/// let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+/// let query = "query($after: Timestamp) { events(after: $after) }";
+/// let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+/// let operation = document.operations.get(None).unwrap();
+///
+/// let mut scalars = ScalarRegistry::new();
+/// scalars.register("Timestamp", WellKnownScalar::DateTime);
+///
+/// let variables: JsonMap = serde_json::from_str(r#"{ "after": "yesterday" }"#).unwrap();
+/// let result = coerce_variable_values_with_scalars(&schema, operation, &variables, &scalars);
+/// assert!(result.is_err());
+///
+/// let variables: JsonMap = serde_json::from_str(r#"{ "after": "2024-02-29T12:00:00Z" }"#).unwrap();
+/// let result = coerce_variable_values_with_scalars(&schema, operation, &variables, &scalars);
+/// assert!(result.is_ok());
+/// ```
+#[derive(Clone, Default)]
+pub struct ScalarRegistry {
+    specs: HashMap<String, Arc<dyn ScalarSpec>>,
+}
+
+impl ScalarRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with a [`WellKnownScalar`] for each custom scalar of `schema`
+    /// that has its conventional name, or a `@specifiedBy` directive with its URL.
+    pub fn well_known_for(schema: &Schema) -> Self {
+        let mut registry = Self::new();
+        for (name, ty) in &schema.types {
+            let ExtendedType::Scalar(def) = ty else {
+                continue;
+            };
+            if def.is_built_in() {
+                continue;
+            }
+            let specified_by = def
+                .directives
+                .get("specifiedBy")
+                .and_then(|directive| directive.specified_argument_by_name("url"))
+                .and_then(|url| url.as_str());
+            let spec = WellKnownScalar::from_name(name).or_else(|| {
+                WellKnownScalar::ALL
+                    .into_iter()
+                    .find(|spec| specified_by.is_some() && spec.specified_by_url() == specified_by)
+            });
+            if let Some(spec) = spec {
+                registry.register(name.as_str(), spec);
+            }
+        }
+        registry
+    }
+
+    /// Use `spec` for values of the custom scalar named `scalar_name`,
+    /// replacing any previously registered for that name.
+    pub fn register(
+        &mut self,
+        scalar_name: impl Into<String>,
+        spec: impl ScalarSpec + 'static,
+    ) -> &mut Self {
+        self.specs.insert(scalar_name.into(), Arc::new(spec));
+        self
+    }
+
+    /// Returns the spec registered for the custom scalar named `scalar_name`, if any
+    pub fn get(&self, scalar_name: &str) -> Option<&dyn ScalarSpec> {
+        self.specs.get(scalar_name).map(|spec| &**spec)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.specs.is_empty()
+    }
+
+    /// Coerce an input value of the custom scalar named `scalar_name`.
+    ///
+    /// Returns the value unchanged if no spec is registered for that name.
+    pub fn coerce_input(&self, scalar_name: &str, value: &JsonValue) -> Result<JsonValue, String> {
+        match self.get(scalar_name) {
+            Some(spec) => spec.coerce_input(value),
+            None => Ok(value.clone()),
+        }
+    }
+}
+
+impl fmt::Debug for ScalarRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.specs.keys()).finish()
+    }
+}
+
+fn digits(s: &str) -> Option<u32> {
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// `date-time` from <https://datatracker.ietf.org/doc/html/rfc3339#section-5.6>
+fn is_date_time(s: &str) -> bool {
+    let b = s.as_bytes();
+    if b.len() < 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't')
+        || b[13] != b':'
+        || b[16] != b':'
+        || !s.is_ascii()
+    {
+        return false;
+    }
+    let (Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second)) = (
+        digits(&s[0..4]),
+        digits(&s[5..7]),
+        digits(&s[8..10]),
+        digits(&s[11..13]),
+        digits(&s[14..16]),
+        digits(&s[17..19]),
+    ) else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    if !(1..=days_in_month).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return false;
+    }
+    let mut rest = &s[19..];
+    if let Some(after_dot) = rest.strip_prefix('.') {
+        let fraction_len = after_dot.bytes().take_while(u8::is_ascii_digit).count();
+        if fraction_len == 0 {
+            return false;
+        }
+        rest = &after_dot[fraction_len..];
+    }
+    match rest.as_bytes() {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', _, _, b':', _, _] => {
+            matches!((digits(&rest[1..3]), digits(&rest[4..6])), (Some(h), Some(m)) if h <= 23 && m <= 59)
+        }
+        _ => false,
+    }
+}
+
+fn is_uuid(s: &str) -> bool {
+    s.len() == 36
+        && s.bytes().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// An absolute URL: a scheme followed by `:` and a non-empty remainder without whitespace
+fn is_url(s: &str) -> bool {
+    let Some((scheme, rest)) = s.split_once(':') else {
+        return false;
+    };
+    let mut scheme = scheme.bytes();
+    scheme.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && scheme.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+        && !rest.is_empty()
+        && !rest.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+fn is_big_int(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
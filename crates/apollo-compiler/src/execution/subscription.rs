@@ -0,0 +1,262 @@
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::execution::engine::collect_fields;
+use crate::execution::GraphQLError;
+use crate::execution::InputCoercionError;
+use crate::execution::JsonMap;
+use crate::execution::Response;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::validation::operation::is_introspection_field;
+use crate::validation::operation::subscription_introspection_root_field;
+use crate::validation::operation::subscription_multiple_root_fields;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// The root field of a subscription operation, as returned by [`subscription_root_field`]
+#[derive(Debug, Clone)]
+pub struct SubscriptionRootField<'doc> {
+    /// The response key (alias or field name) of the root field
+    pub response_key: &'doc Name,
+    /// The first selection of the root field.
+    /// Other selections with the same response key are merged into the same root field.
+    pub field: &'doc Node<Field>,
+    /// All selections of the root field after evaluating `@skip` and `@include`,
+    /// as passed to [`ExecuteField()`] for each event
+    ///
+    /// [`ExecuteField()`]: https://spec.graphql.org/October2021/#ExecuteField()
+    pub fields: Vec<&'doc Field>,
+    /// The coerced argument values of the root field,
+    /// as returned by [`Field::effective_arguments`]
+    pub arguments: JsonMap,
+}
+
+/// Error type of subscription root field checks
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SubscriptionRootFieldError {
+    #[error("Operation is not a subscription")]
+    NotASubscription { location: Option<SourceSpan> },
+
+    #[error("Subscription root type is not defined in the schema")]
+    UndefinedRootType { location: Option<SourceSpan> },
+
+    #[error(
+        "{} must select only one top level field",
+        operation_name_or_anonymous(operation_name)
+    )]
+    MultipleRootFields {
+        operation_name: Option<Name>,
+        /// The name of each root field selection
+        fields: Vec<Name>,
+        location: Option<SourceSpan>,
+    },
+
+    /// Every root field was excluded by `@skip` or `@include`
+    #[error(
+        "{} does not select any top level field",
+        operation_name_or_anonymous(operation_name)
+    )]
+    NoRootField {
+        operation_name: Option<Name>,
+        location: Option<SourceSpan>,
+    },
+
+    #[error(
+        "{} must not select an introspection top level field",
+        operation_name_or_anonymous(operation_name)
+    )]
+    IntrospectionRootField {
+        operation_name: Option<Name>,
+        field: Name,
+        location: Option<SourceSpan>,
+    },
+
+    #[error("Invalid root field arguments")]
+    InvalidArguments(InputCoercionError),
+}
+
+/// Checks the [single root field] validation rule for a subscription operation,
+/// independently of the rest of validation.
+///
+/// Like validation, this ignores `@skip` and `@include`.
+/// Use [`subscription_root_field`] at execution time to account for variable values.
+/// Returns `Ok(())` for queries and mutations.
+///
+/// [single root field]: https://spec.graphql.org/October2021/#sec-Single-root-field
+pub fn check_subscription_single_root_field(
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+) -> Result<(), SubscriptionRootFieldError> {
+    match subscription_multiple_root_fields(document, operation) {
+        Some(fields) => Err(SubscriptionRootFieldError::MultipleRootFields {
+            operation_name: operation.name.clone(),
+            fields,
+            location: operation.location(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that a subscription operation does not select an introspection root field
+/// (`__typename`, `__schema`, or `__type`), which is part of the [single root field]
+/// validation rule, independently of the rest of validation.
+///
+/// Returns `Ok(())` for queries and mutations.
+///
+/// [single root field]: https://spec.graphql.org/October2021/#sec-Single-root-field
+pub fn check_subscription_no_introspection_root(
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+) -> Result<(), SubscriptionRootFieldError> {
+    match subscription_introspection_root_field(document, operation) {
+        Some(field) => Err(SubscriptionRootFieldError::IntrospectionRootField {
+            operation_name: operation.name.clone(),
+            field: field.name.clone(),
+            location: field.location(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the root field of a subscription operation with its coerced arguments,
+/// as needed by a subscription server before [creating a source stream][cses].
+///
+/// This also serves as an execution-time guard:
+/// validation checks the [single root field] rule without knowing variable values,
+/// but here root fields are collected with `@skip` and `@include` evaluated,
+/// and an error is returned unless there is exactly one that is not an introspection field.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::coerce_variable_values;
+/// use apollo_compiler::execution::subscription_root_field;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { unused: Int }
+///     type Subscription { reviews(product: ID!, limit: Int = 10): String }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(&schema, r#"
+///     subscription($id: ID!) { new: reviews(product: $id) }
+/// "#, "subscription.graphql").unwrap();
+/// let operation = document.operations.get(None).unwrap();
+/// let variables = serde_json::from_str(r#"{ "id": "42" }"#).unwrap();
+/// let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+///
+/// let root = subscription_root_field(&schema, &document, operation, &variables).unwrap();
+/// assert_eq!(root.response_key, "new");
+/// assert_eq!(root.field.name, "reviews");
+/// assert_eq!(
+///     serde_json::to_string(&root.arguments).unwrap(),
+///     r#"{"product":"42","limit":10}"#,
+/// );
+/// ```
+///
+/// [cses]: https://spec.graphql.org/October2021/#CreateSourceEventStream()
+/// [single root field]: https://spec.graphql.org/October2021/#sec-Single-root-field
+pub fn subscription_root_field<'doc>(
+    schema: &Valid<Schema>,
+    document: &'doc Valid<ExecutableDocument>,
+    operation: &'doc Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+) -> Result<SubscriptionRootField<'doc>, SubscriptionRootFieldError> {
+    if !operation.is_subscription() {
+        return Err(SubscriptionRootFieldError::NotASubscription {
+            location: operation.location(),
+        });
+    }
+    let Some(object_type) = schema.get_object(operation.object_type()) else {
+        return Err(SubscriptionRootFieldError::UndefinedRootType {
+            location: operation.location(),
+        });
+    };
+    let mut grouped_fields = IndexMap::default();
+    collect_fields(
+        schema,
+        document,
+        variable_values,
+        object_type,
+        &operation.selection_set.selections,
+        &mut HashSet::default(),
+        &mut grouped_fields,
+    );
+    if grouped_fields.len() > 1 {
+        return Err(SubscriptionRootFieldError::MultipleRootFields {
+            operation_name: operation.name.clone(),
+            fields: grouped_fields
+                .values()
+                .flatten()
+                .map(|field| field.name.clone())
+                .collect(),
+            location: operation.location(),
+        });
+    }
+    let Some((response_key, fields)) = grouped_fields.into_iter().next() else {
+        return Err(SubscriptionRootFieldError::NoRootField {
+            operation_name: operation.name.clone(),
+            location: operation.location(),
+        });
+    };
+    // `collect_fields` returns `&Field`, find the corresponding `Node` for its location
+    let field = operation
+        .root_fields(document)
+        .find(|field| std::ptr::eq(field.as_ref(), fields[0]))
+        .expect("collected field not found in root fields");
+    if is_introspection_field(&field.name) {
+        return Err(SubscriptionRootFieldError::IntrospectionRootField {
+            operation_name: operation.name.clone(),
+            field: field.name.clone(),
+            location: field.location(),
+        });
+    }
+    let arguments = field
+        .effective_arguments(schema, variable_values)
+        .map_err(SubscriptionRootFieldError::InvalidArguments)?;
+    Ok(SubscriptionRootField {
+        response_key,
+        field,
+        fields,
+        arguments,
+    })
+}
+
+impl SubscriptionRootFieldError {
+    /// Convert into a JSON-serializable error as represented in a GraphQL response
+    pub fn into_graphql_error(self, sources: &SourceMap) -> GraphQLError {
+        let location = match &self {
+            Self::NotASubscription { location }
+            | Self::UndefinedRootType { location }
+            | Self::MultipleRootFields { location, .. }
+            | Self::NoRootField { location, .. }
+            | Self::IntrospectionRootField { location, .. } => *location,
+            Self::InvalidArguments(_) => None,
+        };
+        if let Self::InvalidArguments(error) = self {
+            return error.into_graphql_error(sources);
+        }
+        GraphQLError::new(self.to_string(), location, sources)
+    }
+
+    /// Convert into a response with this error as a [request error]
+    /// that prevented execution from starting.
+    ///
+    /// [request error]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+    pub fn into_response(self, sources: &SourceMap) -> Response {
+        Response::from_request_error(self.into_graphql_error(sources))
+    }
+}
+
+fn operation_name_or_anonymous(name: &Option<Name>) -> impl std::fmt::Display + '_ {
+    crate::validation::diagnostics::NameOrAnon {
+        name: name.as_ref(),
+        if_some_prefix: "Subscription",
+        if_none: "Anonymous subscription",
+    }
+}
@@ -0,0 +1,338 @@
+use crate::collections::HashSet;
+use crate::execution::GraphQLError;
+use crate::execution::JsonMap;
+use crate::execution::Request;
+use crate::execution::Response;
+use crate::execution::ResponseData;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The WebSocket subprotocol name of [`WsConnection`],
+/// to be negotiated in the `Sec-WebSocket-Protocol` HTTP header
+pub const GRAPHQL_TRANSPORT_WS_PROTOCOL: &str = "graphql-transport-ws";
+
+/// A message sent by the client in the
+/// [`graphql-transport-ws` protocol](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md),
+/// as JSON in a WebSocket text frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsClientMessage {
+    ConnectionInit {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<JsonMap>,
+    },
+    Ping {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<JsonMap>,
+    },
+    Pong {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<JsonMap>,
+    },
+    Subscribe {
+        id: String,
+        payload: Request,
+    },
+    Complete {
+        id: String,
+    },
+}
+
+/// A message sent by the server in the
+/// [`graphql-transport-ws` protocol](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md),
+/// as JSON in a WebSocket text frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsServerMessage {
+    ConnectionAck {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<JsonMap>,
+    },
+    Ping {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<JsonMap>,
+    },
+    Pong {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<JsonMap>,
+    },
+    /// A response for an operation: the only one for a query or mutation,
+    /// or one of the events of a subscription
+    Next {
+        id: String,
+        payload: Response,
+    },
+    /// Request errors that prevented executing an operation, which is then complete
+    Error {
+        id: String,
+        payload: Vec<GraphQLError>,
+    },
+    Complete {
+        id: String,
+    },
+}
+
+/// Something the server needs to act on after [`WsConnection::receive`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsEvent {
+    /// The client requests a connection. Check `payload` if needed
+    /// (for example for authentication), then send [`WsConnection::acknowledge`]
+    /// or close with [`WsConnection::reject`].
+    Init { payload: Option<JsonMap> },
+    /// Send this message to the client, such as a `pong` in reply to a `ping`
+    Reply(WsServerMessage),
+    /// The client replied to a `ping`
+    Pong { payload: Option<JsonMap> },
+    /// The client requests executing an operation.
+    ///
+    /// Send responses with [`WsConnection::respond`] for a query or mutation,
+    /// or with [`WsConnection::next`] and [`WsConnection::complete`] for a subscription.
+    Subscribe { id: String, request: Request },
+    /// The client is no longer interested in this operation:
+    /// stop executing it. Nothing should be sent for it anymore.
+    Complete { id: String },
+}
+
+/// A protocol violation, after which the WebSocket should be closed
+/// with [`close_code`][Self::close_code] and the `Display` string as the close reason.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum WsProtocolError {
+    /// The message is not valid JSON or not a known client message
+    #[error("Invalid message received: {0}")]
+    InvalidMessage(String),
+    /// The client sent `subscribe` before the connection was acknowledged
+    #[error("Unauthorized")]
+    Unauthorized,
+    /// The server rejected the connection with [`WsConnection::reject`]
+    #[error("Forbidden")]
+    Forbidden,
+    /// The client sent `subscribe` with the ID of an operation that is not complete yet
+    #[error("Subscriber for {0} already exists")]
+    SubscriberAlreadyExists(String),
+    /// The client sent `connection_init` more than once
+    #[error("Too many initialisation requests")]
+    TooManyInitialisationRequests,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    AwaitingInit,
+    AwaitingAcknowledgement,
+    Acknowledged,
+}
+
+/// State machine for the server side of a WebSocket connection with the
+/// [`graphql-transport-ws` protocol](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md).
+///
+/// This does not do any IO: pass each text message received from the client to
+/// [`receive`][Self::receive], act on the returned [`WsEvent`], and send the
+/// [`WsServerMessage`]s returned by other methods serialized as JSON.
+/// Timeouts (such as for `connection_init`) and keep-alive pings are also left to the caller.
+///
+/// Executing operations is left to the caller too, for example with
+/// [`OperationRouter::execute`][crate::execution::OperationRouter::execute].
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::execution::OperationRouter;
+/// use apollo_compiler::execution::WsConnection;
+/// use apollo_compiler::execution::WsEvent;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let router = OperationRouter::new(schema);
+/// let mut connection = WsConnection::new();
+/// let mut sent = Vec::new();
+///
+/// let received = [
+///     r#"{ "type": "connection_init" }"#,
+///     r#"{ "type": "subscribe", "id": "1", "payload": { "query": "{ a }" } }"#,
+/// ];
+/// for message in received {
+///     match connection.receive(message).unwrap() {
+///         Some(WsEvent::Init { .. }) => sent.push(connection.acknowledge(None)),
+///         Some(WsEvent::Subscribe { id, request }) => {
+///             let response = router.execute(
+///                 &request.query,
+///                 request.operation_name.as_deref(),
+///                 &request.variables,
+///             );
+///             sent.extend(connection.respond(&id, response))
+///         }
+///         Some(WsEvent::Reply(message)) => sent.push(message),
+///         _ => {}
+///     }
+/// }
+/// let sent: Vec<_> = sent.iter().map(|m| serde_json::to_string(m).unwrap()).collect();
+/// assert_eq!(sent, [
+///     r#"{"type":"connection_ack"}"#,
+///     r#"{"type":"next","id":"1","payload":{"data":{"a":0}}}"#,
+///     r#"{"type":"complete","id":"1"}"#,
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WsConnection {
+    state: State,
+    active_operations: HashSet<String>,
+}
+
+impl Default for WsConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WsConnection {
+    /// Create the state for a new connection, waiting for `connection_init`
+    pub fn new() -> Self {
+        Self {
+            state: State::AwaitingInit,
+            active_operations: HashSet::default(),
+        }
+    }
+
+    /// Parse and handle a text message from the client.
+    ///
+    /// Returns `Ok(None)` for messages that need no action,
+    /// such as `complete` for an operation that already completed.
+    /// On error, the caller should close the WebSocket as described in [`WsProtocolError`].
+    pub fn receive(&mut self, message: &str) -> Result<Option<WsEvent>, WsProtocolError> {
+        let message = serde_json_bytes::serde_json::from_str(message)
+            .map_err(|e| WsProtocolError::InvalidMessage(e.to_string()))?;
+        self.receive_message(message)
+    }
+
+    /// Handle an already-parsed message from the client, like [`receive`][Self::receive]
+    pub fn receive_message(
+        &mut self,
+        message: WsClientMessage,
+    ) -> Result<Option<WsEvent>, WsProtocolError> {
+        match message {
+            WsClientMessage::ConnectionInit { payload } => {
+                if self.state != State::AwaitingInit {
+                    return Err(WsProtocolError::TooManyInitialisationRequests);
+                }
+                self.state = State::AwaitingAcknowledgement;
+                Ok(Some(WsEvent::Init { payload }))
+            }
+            WsClientMessage::Ping { payload: _ } => {
+                Ok(Some(WsEvent::Reply(WsServerMessage::Pong {
+                    payload: None,
+                })))
+            }
+            WsClientMessage::Pong { payload } => Ok(Some(WsEvent::Pong { payload })),
+            WsClientMessage::Subscribe { id, payload } => {
+                if self.state != State::Acknowledged {
+                    return Err(WsProtocolError::Unauthorized);
+                }
+                if !self.active_operations.insert(id.clone()) {
+                    return Err(WsProtocolError::SubscriberAlreadyExists(id));
+                }
+                Ok(Some(WsEvent::Subscribe {
+                    id,
+                    request: payload,
+                }))
+            }
+            WsClientMessage::Complete { id } => {
+                if self.active_operations.remove(&id) {
+                    Ok(Some(WsEvent::Complete { id }))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    /// Accept the connection after [`WsEvent::Init`], returning the `connection_ack` message
+    pub fn acknowledge(&mut self, payload: Option<JsonMap>) -> WsServerMessage {
+        self.state = State::Acknowledged;
+        WsServerMessage::ConnectionAck { payload }
+    }
+
+    /// Refuse the connection after [`WsEvent::Init`],
+    /// returning the error to close the WebSocket with
+    pub fn reject(&mut self) -> WsProtocolError {
+        WsProtocolError::Forbidden
+    }
+
+    /// Whether `connection_ack` was sent
+    pub fn is_acknowledged(&self) -> bool {
+        self.state == State::Acknowledged
+    }
+
+    /// Whether an operation with this ID was subscribed and is not complete yet
+    pub fn is_active(&self, id: &str) -> bool {
+        self.active_operations.contains(id)
+    }
+
+    /// IDs of operations that were subscribed and are not complete yet, in no particular order
+    pub fn active_operations(&self) -> impl Iterator<Item = &str> {
+        self.active_operations.iter().map(|id| id.as_str())
+    }
+
+    /// Returns a `next` message with a response for an active operation,
+    /// or `None` if the operation is complete or was cancelled by the client.
+    pub fn next(&self, id: &str, response: Response) -> Option<WsServerMessage> {
+        self.is_active(id).then(|| WsServerMessage::Next {
+            id: id.to_owned(),
+            payload: response,
+        })
+    }
+
+    /// Mark an operation as complete, such as at the end of a subscription stream,
+    /// and return the `complete` message.
+    /// Returns `None` if the operation is already complete or was cancelled by the client.
+    pub fn complete(&mut self, id: &str) -> Option<WsServerMessage> {
+        self.active_operations
+            .remove(id)
+            .then(|| WsServerMessage::Complete { id: id.to_owned() })
+    }
+
+    /// Mark an operation as complete because of request errors
+    /// (such as a validation error) and return the `error` message.
+    /// Returns `None` if the operation is already complete or was cancelled by the client.
+    pub fn error(&mut self, id: &str, errors: Vec<GraphQLError>) -> Option<WsServerMessage> {
+        self.active_operations
+            .remove(id)
+            .then(|| WsServerMessage::Error {
+                id: id.to_owned(),
+                payload: errors,
+            })
+    }
+
+    /// Returns messages for the single response of a query or mutation:
+    /// `next` then `complete`, or `error` if the response has [request errors]
+    /// (its [`data`][Response::data] is absent).
+    /// Returns nothing if the operation is already complete or was cancelled by the client.
+    ///
+    /// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+    pub fn respond(&mut self, id: &str, response: Response) -> Vec<WsServerMessage> {
+        if let ResponseData::Absent = response.data {
+            return self.error(id, response.errors).into_iter().collect();
+        }
+        let Some(next) = self.next(id, response) else {
+            return Vec::new();
+        };
+        vec![next, self.complete(id).expect("checked by next()")]
+    }
+
+    /// Returns a `ping` message, for the server to check that the client is still connected
+    pub fn ping(&self, payload: Option<JsonMap>) -> WsServerMessage {
+        WsServerMessage::Ping { payload }
+    }
+}
+
+impl WsProtocolError {
+    /// The WebSocket close code for this error, as defined by the protocol
+    pub fn close_code(&self) -> u16 {
+        match self {
+            Self::InvalidMessage(_) => 4400,
+            Self::Unauthorized => 4401,
+            Self::Forbidden => 4403,
+            Self::SubscriberAlreadyExists(_) => 4409,
+            Self::TooManyInitialisationRequests => 4429,
+        }
+    }
+}
@@ -4,17 +4,22 @@
 mod macros;
 pub mod ast;
 pub mod collections;
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod coordinate;
 pub mod diagnostic;
 pub mod executable;
 pub mod execution;
+mod memory;
 mod name;
 mod node;
 pub mod parser;
 pub mod schema;
+pub mod typescript;
 pub mod validation;
 
 pub use self::executable::ExecutableDocument;
+pub use self::memory::MemoryFootprint;
 pub use self::name::InvalidNameError;
 pub use self::name::Name;
 pub use self::node::Node;
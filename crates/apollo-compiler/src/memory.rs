@@ -0,0 +1,477 @@
+use crate::ast;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::executable;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::parser::SourceText;
+use crate::schema;
+use crate::schema::ExtendedType;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::mem::size_of;
+use std::mem::size_of_val;
+use std::ops::AddAssign;
+
+/// An estimate of the heap memory used by a [`Schema`] or an [`ExecutableDocument`],
+/// broken down by component.
+///
+/// Returned by [`Schema::memory_footprint`] and [`ExecutableDocument::memory_footprint`].
+/// This helps decide whether a workload would benefit from interning or
+/// from dropping source files, and detect pathological inputs before keeping them around.
+///
+/// Sizes are in bytes, and approximate: they are computed from the sizes of types
+/// and the lengths or capacities of collections, without allocator overhead.
+/// Allocations shared through reference counting, such as a [`Name`] or [`Node`]
+/// cloned in several places, are counted once.
+/// In an [`ExecutableDocument`], the schema definitions referenced by fields are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryFootprint {
+    /// Text of [`Name`]s that were not created from a `&'static str`
+    pub names: usize,
+    /// Text of descriptions and comments
+    pub descriptions: usize,
+    /// Directive applications and their arguments, excluding names
+    pub directives: usize,
+    /// Source locations stored with each [`Node`]
+    pub spans: usize,
+    /// Source files kept for diagnostics
+    pub sources: usize,
+    /// Definitions, fields, selections, values, and everything else
+    pub other: usize,
+}
+
+impl MemoryFootprint {
+    /// Returns the sum of all components
+    pub fn total(&self) -> usize {
+        self.names + self.descriptions + self.directives + self.spans + self.sources + self.other
+    }
+}
+
+impl AddAssign for MemoryFootprint {
+    fn add_assign(&mut self, other: Self) {
+        self.names += other.names;
+        self.descriptions += other.descriptions;
+        self.directives += other.directives;
+        self.spans += other.spans;
+        self.sources += other.sources;
+        self.other += other.other;
+    }
+}
+
+impl Schema {
+    /// Returns an estimate of the heap memory used by this schema.
+    /// See [`MemoryFootprint`].
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let mut estimate = Estimate::default();
+        estimate.schema(self);
+        estimate.footprint
+    }
+}
+
+impl ExecutableDocument {
+    /// Returns an estimate of the heap memory used by this document.
+    /// See [`MemoryFootprint`].
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let mut estimate = Estimate::default();
+        estimate.executable(self);
+        estimate.footprint
+    }
+}
+
+/// Which component to attribute structures to
+#[derive(Clone, Copy)]
+enum Part {
+    Directives,
+    Other,
+}
+
+#[derive(Default)]
+struct Estimate {
+    footprint: MemoryFootprint,
+    /// Addresses of reference-counted allocations already counted
+    seen: HashSet<*const ()>,
+}
+
+impl Estimate {
+    fn part(&mut self, part: Part) -> &mut usize {
+        match part {
+            Part::Directives => &mut self.footprint.directives,
+            Part::Other => &mut self.footprint.other,
+        }
+    }
+
+    /// Counts the allocation of `node` but not its contents.
+    /// Returns false if it was already counted.
+    fn node<T: ?Sized>(&mut self, part: Part, node: &Node<T>) -> bool {
+        let ptr = (&**node as *const T).cast::<()>();
+        if !self.seen.insert(ptr) {
+            return false;
+        }
+        // The reference count and the location are stored next to the value
+        self.footprint.spans += size_of::<Option<SourceSpan>>();
+        *self.part(part) += size_of::<usize>() + size_of_val(&**node);
+        true
+    }
+
+    fn vec<T>(&mut self, part: Part, vec: &Vec<T>) {
+        *self.part(part) += vec.capacity() * size_of::<T>();
+    }
+
+    fn map<K, V>(&mut self, map: &IndexMap<K, V>) {
+        // Entries with their hash, and the hash table of indices
+        self.footprint.other +=
+            map.capacity() * (size_of::<K>() + size_of::<V>() + 2 * size_of::<usize>());
+    }
+
+    fn set<K>(&mut self, set: &IndexSet<K>) {
+        self.footprint.other += set.capacity() * (size_of::<K>() + 2 * size_of::<usize>());
+    }
+
+    fn name(&mut self, name: &Name) {
+        if name.as_static_str().is_none() && self.seen.insert(name.as_str().as_ptr().cast()) {
+            // Strong and weak reference counts of `Arc<str>`
+            self.footprint.names += 2 * size_of::<usize>() + name.len();
+        }
+    }
+
+    fn text(&mut self, text: &Node<str>) {
+        if self.seen.insert(text.as_ptr().cast()) {
+            self.footprint.spans += size_of::<Option<SourceSpan>>();
+            self.footprint.descriptions += size_of::<usize>() + text.len();
+        }
+    }
+
+    fn description(&mut self, description: &Option<Node<str>>, comments: &Vec<Node<str>>) {
+        if let Some(description) = description {
+            self.text(description)
+        }
+        self.footprint.descriptions += comments.capacity() * size_of::<Node<str>>();
+        for comment in comments {
+            self.text(comment)
+        }
+    }
+
+    fn sources(&mut self, sources: &SourceMap) {
+        if !self.seen.insert(std::sync::Arc::as_ptr(sources).cast()) {
+            return;
+        }
+        self.footprint.sources += sources.capacity() * 3 * size_of::<usize>();
+        for file in sources.values() {
+            if !self.seen.insert(std::sync::Arc::as_ptr(file).cast()) {
+                continue;
+            }
+            self.footprint.sources += size_of_val(&**file) + file.path.as_os_str().len();
+            match &file.text {
+                SourceText::Retained(text) => self.footprint.sources += text.capacity(),
+                SourceText::Detached { line_starts, .. } => {
+                    if self.seen.insert(line_starts.as_ptr().cast()) {
+                        self.footprint.sources += size_of_val(&**line_starts)
+                    }
+                }
+            }
+        }
+    }
+
+    fn schema(&mut self, schema: &Schema) {
+        self.sources(&schema.sources);
+        let def = &schema.schema_definition;
+        if self.node(Part::Other, def) {
+            self.description(&def.description, &Vec::new());
+            self.schema_directives(&def.directives);
+            for root in [&def.query, &def.mutation, &def.subscription]
+                .into_iter()
+                .flatten()
+            {
+                self.name(&root.name)
+            }
+        }
+        self.map(&schema.directive_definitions);
+        for (name, def) in &schema.directive_definitions {
+            self.name(name);
+            self.directive_definition(def);
+        }
+        self.map(&schema.types);
+        for (name, ty) in &schema.types {
+            self.name(name);
+            self.extended_type(ty);
+        }
+    }
+
+    fn directive_definition(&mut self, def: &Node<ast::DirectiveDefinition>) {
+        if self.node(Part::Other, def) {
+            self.description(&def.description, &def.leading_comments);
+            self.name(&def.name);
+            self.input_values(&def.arguments);
+            self.vec(Part::Other, &def.locations);
+        }
+    }
+
+    fn extended_type(&mut self, ty: &ExtendedType) {
+        match ty {
+            ExtendedType::Scalar(def) => {
+                if self.node(Part::Other, def) {
+                    self.description(&def.description, &Vec::new());
+                    self.name(&def.name);
+                    self.schema_directives(&def.directives);
+                }
+            }
+            ExtendedType::Object(def) => {
+                if self.node(Part::Other, def) {
+                    self.description(&def.description, &Vec::new());
+                    self.name(&def.name);
+                    self.component_names(&def.implements_interfaces);
+                    self.schema_directives(&def.directives);
+                    self.map(&def.fields);
+                    for (name, field) in &def.fields {
+                        self.name(name);
+                        self.field_definition(field);
+                    }
+                }
+            }
+            ExtendedType::Interface(def) => {
+                if self.node(Part::Other, def) {
+                    self.description(&def.description, &Vec::new());
+                    self.name(&def.name);
+                    self.component_names(&def.implements_interfaces);
+                    self.schema_directives(&def.directives);
+                    self.map(&def.fields);
+                    for (name, field) in &def.fields {
+                        self.name(name);
+                        self.field_definition(field);
+                    }
+                }
+            }
+            ExtendedType::Union(def) => {
+                if self.node(Part::Other, def) {
+                    self.description(&def.description, &Vec::new());
+                    self.name(&def.name);
+                    self.schema_directives(&def.directives);
+                    self.component_names(&def.members);
+                }
+            }
+            ExtendedType::Enum(def) => {
+                if self.node(Part::Other, def) {
+                    self.description(&def.description, &Vec::new());
+                    self.name(&def.name);
+                    self.schema_directives(&def.directives);
+                    self.map(&def.values);
+                    for (name, value) in &def.values {
+                        self.name(name);
+                        if self.node(Part::Other, value) {
+                            self.description(&value.description, &value.leading_comments);
+                            self.name(&value.value);
+                            self.ast_directives(&value.directives);
+                        }
+                    }
+                }
+            }
+            ExtendedType::InputObject(def) => {
+                if self.node(Part::Other, def) {
+                    self.description(&def.description, &Vec::new());
+                    self.name(&def.name);
+                    self.schema_directives(&def.directives);
+                    self.map(&def.fields);
+                    for (name, field) in &def.fields {
+                        self.name(name);
+                        self.input_value(field);
+                    }
+                }
+            }
+        }
+    }
+
+    fn component_names(&mut self, names: &IndexSet<schema::ComponentName>) {
+        self.set(names);
+        for name in names {
+            self.name(&name.name)
+        }
+    }
+
+    fn field_definition(&mut self, def: &Node<ast::FieldDefinition>) {
+        if self.node(Part::Other, def) {
+            self.description(&def.description, &def.leading_comments);
+            self.name(&def.name);
+            self.input_values(&def.arguments);
+            self.ty(&def.ty);
+            self.ast_directives(&def.directives);
+        }
+    }
+
+    fn input_values(&mut self, defs: &Vec<Node<ast::InputValueDefinition>>) {
+        self.vec(Part::Other, defs);
+        for def in defs {
+            self.input_value(def)
+        }
+    }
+
+    fn input_value(&mut self, def: &Node<ast::InputValueDefinition>) {
+        if self.node(Part::Other, def) {
+            self.description(&def.description, &def.leading_comments);
+            self.name(&def.name);
+            if self.node(Part::Other, &def.ty) {
+                self.ty(&def.ty)
+            }
+            if let Some(value) = &def.default_value {
+                self.value(Part::Other, value)
+            }
+            self.ast_directives(&def.directives);
+        }
+    }
+
+    fn ty(&mut self, ty: &ast::Type) {
+        match ty {
+            ast::Type::Named(name) | ast::Type::NonNullNamed(name) => self.name(name),
+            ast::Type::List(inner) | ast::Type::NonNullList(inner) => {
+                self.footprint.other += size_of::<ast::Type>();
+                self.ty(inner)
+            }
+        }
+    }
+
+    fn schema_directives(&mut self, directives: &schema::DirectiveList) {
+        self.vec(Part::Directives, directives);
+        for directive in directives.iter() {
+            self.directive(&directive.node)
+        }
+    }
+
+    fn ast_directives(&mut self, directives: &ast::DirectiveList) {
+        self.vec(Part::Directives, directives);
+        for directive in directives.iter() {
+            self.directive(directive)
+        }
+    }
+
+    fn directive(&mut self, directive: &Node<ast::Directive>) {
+        if self.node(Part::Directives, directive) {
+            self.name(&directive.name);
+            self.arguments(Part::Directives, &directive.arguments);
+        }
+    }
+
+    fn arguments(&mut self, part: Part, arguments: &Vec<Node<ast::Argument>>) {
+        self.vec(part, arguments);
+        for argument in arguments {
+            if self.node(part, argument) {
+                self.name(&argument.name);
+                self.value(part, &argument.value);
+            }
+        }
+    }
+
+    fn value(&mut self, part: Part, value: &Node<ast::Value>) {
+        if !self.node(part, value) {
+            return;
+        }
+        match &**value {
+            ast::Value::Null | ast::Value::Boolean(_) => {}
+            ast::Value::Enum(name) | ast::Value::Variable(name) => self.name(name),
+            ast::Value::String(string) => *self.part(part) += string.capacity(),
+            ast::Value::Float(float) => *self.part(part) += float.as_str().len(),
+            ast::Value::Int(int) => *self.part(part) += int.as_str().len(),
+            ast::Value::List(items) => {
+                self.vec(part, items);
+                for item in items {
+                    self.value(part, item)
+                }
+            }
+            ast::Value::Object(fields) => {
+                self.vec(part, fields);
+                for (name, value) in fields {
+                    self.name(name);
+                    self.value(part, value)
+                }
+            }
+        }
+    }
+
+    fn executable(&mut self, document: &ExecutableDocument) {
+        self.sources(&document.sources);
+        let operations = &document.operations;
+        if let Some(operation) = &operations.anonymous {
+            self.operation(operation)
+        }
+        self.map(&operations.named);
+        for (name, operation) in &operations.named {
+            self.name(name);
+            self.operation(operation)
+        }
+        self.map(&document.fragments);
+        for (name, fragment) in &document.fragments {
+            self.name(name);
+            if self.node(Part::Other, fragment) {
+                self.name(&fragment.name);
+                self.ast_directives(&fragment.directives);
+                self.selection_set(&fragment.selection_set);
+            }
+        }
+    }
+
+    fn operation(&mut self, operation: &Node<executable::Operation>) {
+        if !self.node(Part::Other, operation) {
+            return;
+        }
+        if let Some(name) = &operation.name {
+            self.name(name)
+        }
+        self.vec(Part::Other, &operation.variables);
+        for variable in &operation.variables {
+            if self.node(Part::Other, variable) {
+                self.name(&variable.name);
+                if self.node(Part::Other, &variable.ty) {
+                    self.ty(&variable.ty)
+                }
+                if let Some(value) = &variable.default_value {
+                    self.value(Part::Other, value)
+                }
+                self.ast_directives(&variable.directives);
+            }
+        }
+        self.ast_directives(&operation.directives);
+        self.selection_set(&operation.selection_set);
+    }
+
+    fn selection_set(&mut self, selection_set: &executable::SelectionSet) {
+        self.name(&selection_set.ty);
+        self.vec(Part::Other, &selection_set.selections);
+        for selection in &selection_set.selections {
+            match selection {
+                executable::Selection::Field(field) => {
+                    // `field.definition` belongs to the schema
+                    if self.node(Part::Other, field) {
+                        if let Some(alias) = &field.alias {
+                            self.name(alias)
+                        }
+                        self.name(&field.name);
+                        self.arguments(Part::Other, &field.arguments);
+                        self.ast_directives(&field.directives);
+                        self.selection_set(&field.selection_set);
+                    }
+                }
+                executable::Selection::FragmentSpread(spread) => {
+                    if self.node(Part::Other, spread) {
+                        self.name(&spread.fragment_name);
+                        self.ast_directives(&spread.directives);
+                    }
+                }
+                executable::Selection::InlineFragment(inline) => {
+                    if self.node(Part::Other, inline) {
+                        if let Some(type_condition) = &inline.type_condition {
+                            self.name(type_condition)
+                        }
+                        self.ast_directives(&inline.directives);
+                        self.selection_set(&inline.selection_set);
+                    }
+                }
+                executable::Selection::Invalid(invalid) => {
+                    self.node(Part::Other, invalid);
+                }
+            }
+        }
+    }
+}
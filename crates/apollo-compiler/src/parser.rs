@@ -17,6 +17,7 @@ use crate::ast::Document;
 use crate::collections::IndexMap;
 use crate::executable;
 use crate::schema::SchemaBuilder;
+use crate::schema::SpecEdition;
 use crate::validation::Details;
 use crate::validation::DiagnosticList;
 use crate::validation::Valid;
@@ -27,6 +28,7 @@ use apollo_parser::SyntaxNode;
 use rowan::TextRange;
 use serde::Deserialize;
 use serde::Serialize;
+use std::borrow::Cow;
 use std::num::NonZeroU64;
 use std::ops::Range;
 use std::path::Path;
@@ -36,23 +38,76 @@ use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
+mod extension;
+mod storage;
+#[cfg(feature = "futures")]
+mod stream;
+
+pub use self::extension::ExtensionContext;
+pub use self::extension::SyntaxExtension;
+pub use self::storage::detach_sources;
+pub use self::storage::SourceStorage;
+#[cfg(feature = "futures")]
+pub use self::stream::read_stream;
+#[cfg(feature = "futures")]
+pub use self::stream::StreamParseError;
+
 /// Configuration for parsing an input string as GraphQL syntax
 #[derive(Default, Debug, Clone)]
 pub struct Parser {
     recursion_limit: Option<usize>,
     token_limit: Option<usize>,
+    materialize_default_arguments: bool,
+    keep_invalid_selections: bool,
+    syntax_extension: Option<ExtensionArc>,
+    source_offset: Option<(LineColumn, usize)>,
+    spec_edition: Option<SpecEdition>,
     recursion_reached: usize,
     tokens_reached: usize,
 }
 
+#[derive(Clone)]
+struct ExtensionArc(Arc<dyn SyntaxExtension>);
+
 /// Records for validation information about a file that was parsed
 #[derive(Clone)]
 pub struct SourceFile {
     pub(crate) path: PathBuf,
-    pub(crate) source_text: String,
+    pub(crate) text: SourceText,
+    /// Where the text starts in a host file, see [`Parser::with_source_offset`]
+    pub(crate) start: LineColumn,
+    pub(crate) start_offset: usize,
     pub(crate) source: OnceLock<ariadne::Source>,
 }
 
+#[derive(Clone)]
+pub(crate) enum SourceText {
+    Retained(String),
+    Detached {
+        storage: Arc<dyn SourceStorage>,
+        /// Byte offsets where lines start, so that line/column lookups do not load the text
+        line_starts: Arc<[usize]>,
+        /// Length of the text in bytes
+        len: usize,
+    },
+}
+
+/// A valid schema and executable document parsed from the same source text,
+/// returned by [`Parser::parse_mixed_and_validate`]
+#[derive(Debug, Clone)]
+pub struct MixedDocument {
+    pub schema: Valid<Schema>,
+    pub executable: Valid<ExecutableDocument>,
+}
+
+/// The schema and executable document returned with errors
+/// by [`Parser::parse_mixed_and_validate`]
+#[derive(Debug, Clone)]
+pub struct PartialMixedDocument {
+    pub schema: Schema,
+    pub executable: ExecutableDocument,
+}
+
 /// A map of source files relevant to a given document
 pub type SourceMap = Arc<IndexMap<FileId, Arc<SourceFile>>>;
 
@@ -89,6 +144,12 @@ pub struct LineColumn {
     pub column: usize,
 }
 
+impl std::fmt::Debug for ExtensionArc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SyntaxExtension")
+    }
+}
+
 impl std::fmt::Debug for LineColumn {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}:{}", self.line, self.column)
@@ -136,6 +197,75 @@ impl Parser {
         self
     }
 
+    /// Configure whether executable documents built by this parser get arguments that are
+    /// not specified but have a default value in the schema added to field selections
+    /// and directive applications, with
+    /// [`ExecutableDocument::materialize_default_arguments`].
+    /// By default they are not: `arguments` are as written in the source text.
+    pub fn materialize_default_arguments(mut self, value: bool) -> Self {
+        self.materialize_default_arguments = value;
+        self
+    }
+
+    /// Configure whether executable documents built by this parser keep selections
+    /// that cannot be converted from AST, such as a field not defined on its parent type,
+    /// as [`Selection::Invalid`][crate::executable::Selection::Invalid] nodes.
+    /// This lets tools like formatters or linters see the whole document as written.
+    ///
+    /// By default such selections are dropped and building the document records an error.
+    /// When they are kept, the same error is recorded by validation instead,
+    /// so the document can still never be [`Valid`].
+    pub fn keep_invalid_selections(mut self, value: bool) -> Self {
+        self.keep_invalid_selections = value;
+        self
+    }
+
+    /// Configure an extension that parses experimental top-level definitions
+    /// starting with a custom keyword, and desugars them into standard AST definitions.
+    /// By default, any such definition is a syntax error.
+    pub fn syntax_extension(mut self, extension: Arc<dyn SyntaxExtension>) -> Self {
+        self.syntax_extension = Some(ExtensionArc(extension));
+        self
+    }
+
+    /// Configure where source text starts in a host file, for GraphQL embedded
+    /// in a string literal of another language.
+    /// `line` and `column` start at 1, like in [`LineColumn`].
+    ///
+    /// Line and column numbers of diagnostics, including in [`GraphQLError`][crate::response::GraphQLError]
+    /// locations, are then in the host file. Columns are only shifted on the first line,
+    /// as other lines start at the beginning of a line of the host file.
+    /// [`SourceSpan`] offsets are still relative to the parsed source text;
+    /// add [`SourceFile::start_offset`] to get a byte offset in the host file.
+    ///
+    /// By default, source text starts at line 1, column 1, byte offset 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::parser::LineColumn;
+    /// use apollo_compiler::parser::Parser;
+    ///
+    /// let host = "fn main() {\n    let schema = graphql!(\"type Query { a: Missing }\");\n}";
+    /// let start = host.find("type").unwrap();
+    /// let snippet = "type Query { a: Missing }";
+    /// let schema = Parser::new()
+    ///     .with_source_offset(2, 27, start)
+    ///     .parse_schema(snippet, "main.rs")
+    ///     .unwrap();
+    /// let errors = schema.validate().unwrap_err().errors;
+    /// let error = errors.iter().next().unwrap();
+    /// let span = error.locations()[0];
+    /// assert_eq!(span.line_column(error.sources), Some(LineColumn { line: 2, column: 43 }));
+    ///
+    /// let file = &error.sources[&span.file_id()];
+    /// assert_eq!(&host[file.start_offset() + span.offset()..][..7], "Missing");
+    /// ```
+    pub fn with_source_offset(mut self, line: usize, column: usize, byte: usize) -> Self {
+        self.source_offset = Some((LineColumn { line, column }, byte));
+        self
+    }
+
     /// Parse the given source text into an AST document.
     ///
     /// `path` is the filesystem path (or arbitrary string) used in diagnostics
@@ -164,8 +294,11 @@ impl Parser {
             errors,
             |parser| parser.parse(),
         );
-        let sources = errors.sources.clone();
-        Document::from_cst(tree.document(), file_id, sources)
+        let extension = self.syntax_extension.clone();
+        let extension = extension
+            .as_ref()
+            .map(|ExtensionArc(extension)| &**extension);
+        Document::from_cst(tree.document(), file_id, extension, errors)
     }
 
     pub(crate) fn parse_common<T: apollo_parser::cst::CstNode>(
@@ -176,7 +309,14 @@ impl Parser {
         errors: &mut DiagnosticList,
         parse: impl FnOnce(apollo_parser::Parser) -> apollo_parser::SyntaxTree<T>,
     ) -> apollo_parser::SyntaxTree<T> {
+        let extension = self.syntax_extension.clone();
+        let extension = extension
+            .as_ref()
+            .map(|ExtensionArc(extension)| extension::ParserExtension(&**extension));
         let mut parser = apollo_parser::Parser::new(&source_text);
+        if let Some(extension) = &extension {
+            parser = parser.syntax_extension(extension)
+        }
         if let Some(value) = self.recursion_limit {
             parser = parser.recursion_limit(value)
         }
@@ -186,9 +326,14 @@ impl Parser {
         let tree = parse(parser);
         self.recursion_reached = tree.recursion_limit().high;
         self.tokens_reached = tree.token_limit().high;
+        let (start, start_offset) = self
+            .source_offset
+            .unwrap_or((LineColumn { line: 1, column: 1 }, 0));
         let source_file = Arc::new(SourceFile {
             path,
-            source_text,
+            text: SourceText::Retained(source_text),
+            start,
+            start_offset,
             source: OnceLock::new(),
         });
         Arc::make_mut(&mut errors.sources).insert(file_id, source_file);
@@ -220,6 +365,26 @@ impl Parser {
         tree
     }
 
+    /// Configure which edition of the GraphQL specification the built-in definitions
+    /// of schemas parsed by [`parse_schema`][Self::parse_schema] and
+    /// [`parse_mixed_and_validate`][Self::parse_mixed_and_validate] come from.
+    /// See [`SpecEdition`] for the differences between editions and the default.
+    ///
+    /// For [`parse_into_schema_builder`][Self::parse_into_schema_builder],
+    /// use [`SchemaBuilder::spec_edition`] instead.
+    pub fn spec_edition(mut self, edition: SpecEdition) -> Self {
+        self.spec_edition = Some(edition);
+        self
+    }
+
+    fn schema_builder(&self) -> SchemaBuilder {
+        let builder = SchemaBuilder::new();
+        match self.spec_edition {
+            Some(edition) => builder.spec_edition(edition),
+            None => builder,
+        }
+    }
+
     /// Parse the given source text as the sole input file of a schema.
     ///
     /// `path` is the filesystem path (or arbitrary string) used in diagnostics
@@ -233,7 +398,7 @@ impl Parser {
         source_text: impl Into<String>,
         path: impl AsRef<Path>,
     ) -> Result<Schema, WithErrors<Schema>> {
-        let mut builder = Schema::builder();
+        let mut builder = self.schema_builder();
         self.parse_into_schema_builder(source_text, path, &mut builder);
         builder.build()
     }
@@ -280,7 +445,11 @@ impl Parser {
     ) -> (ExecutableDocument, DiagnosticList) {
         let mut errors = DiagnosticList::new(Default::default());
         let ast = self.parse_ast_inner(source_text, path, FileId::new(), &mut errors);
-        let document = ast.to_executable_inner(schema, &mut errors);
+        let mut document =
+            ast.to_executable_inner(schema, &mut errors, self.keep_invalid_selections);
+        if self.materialize_default_arguments {
+            document.materialize_default_arguments(schema)
+        }
         (document, errors)
     }
 
@@ -291,32 +460,87 @@ impl Parser {
     ///
     /// `path` is the filesystem path (or arbitrary string) used in diagnostics
     /// to identify this source file to users.
+    ///
+    /// See also [`parse_mixed_and_validate`][Self::parse_mixed_and_validate]
+    /// which returns partial results on error.
     pub fn parse_mixed_validate(
         &mut self,
         source_text: impl Into<String>,
         path: impl AsRef<Path>,
     ) -> Result<(Valid<Schema>, Valid<ExecutableDocument>), DiagnosticList> {
-        let mut builder = SchemaBuilder::new();
+        self.parse_mixed_and_validate(source_text, path)
+            .map(|mixed| (mixed.schema, mixed.executable))
+            .map_err(|with_errors| with_errors.errors)
+    }
+
+    /// Parse a schema and executable document from the given source text
+    /// containing a mixture of type system definitions and executable definitions,
+    /// such as a single-file playground.
+    ///
+    /// The schema is built and validated first,
+    /// then executable definitions are built and validated against it.
+    /// Diagnostics from all steps are combined and sorted by location.
+    /// If there are any, the partial schema and document are returned with them.
+    ///
+    /// `path` is the filesystem path (or arbitrary string) used in diagnostics
+    /// to identify this source file to users.
+    ///
+    /// ```
+    /// use apollo_compiler::parser::Parser;
+    ///
+    /// let input = r#"
+    ///     type Query { greeting: String }
+    ///     query Greet { greeting }
+    /// "#;
+    /// let mixed = Parser::new().parse_mixed_and_validate(input, "playground.graphql").unwrap();
+    /// assert!(mixed.schema.types.contains_key("Query"));
+    /// assert!(mixed.executable.operations.get(Some("Greet")).is_ok());
+    ///
+    /// let invalid = "type Query { greeting: String } { greeting farewell }";
+    /// let with_errors = Parser::new()
+    ///     .parse_mixed_and_validate(invalid, "playground.graphql")
+    ///     .unwrap_err();
+    /// assert_eq!(with_errors.errors.len(), 1);
+    /// assert!(with_errors.partial.schema.types.contains_key("Query"));
+    /// ```
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn parse_mixed_and_validate(
+        &mut self,
+        source_text: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<MixedDocument, WithErrors<PartialMixedDocument>> {
+        let mut builder = self.schema_builder();
         let ast = self.parse_ast_inner(source_text, path, FileId::new(), &mut builder.errors);
         let executable_definitions_are_errors = false;
         let type_system_definitions_are_errors = false;
         builder.add_ast_document_not_adding_sources(&ast, executable_definitions_are_errors);
         let (mut schema, mut errors) = builder.build_inner();
-        let executable = crate::executable::from_ast::document_from_ast(
+        crate::schema::validation::validate_schema(&mut errors, &mut schema);
+        let mut executable = crate::executable::from_ast::document_from_ast(
             Some(&schema),
             &ast,
             &mut errors,
             type_system_definitions_are_errors,
+            self.keep_invalid_selections,
         );
-        crate::schema::validation::validate_schema(&mut errors, &mut schema);
+        if self.materialize_default_arguments {
+            executable.materialize_default_arguments(&schema)
+        }
         crate::executable::validation::validate_executable_document(
             &mut errors,
             &schema,
             &executable,
         );
-        errors
-            .into_result()
-            .map(|()| (Valid(schema), Valid(executable)))
+        match errors.into_result() {
+            Ok(()) => Ok(MixedDocument {
+                schema: Valid(schema),
+                executable: Valid(executable),
+            }),
+            Err(errors) => Err(WithErrors {
+                partial: PartialMixedDocument { schema, executable },
+                errors,
+            }),
+        }
     }
 
     /// Parse the given source text (e.g. `field_1 field_2 { field_2_1 }`
@@ -364,6 +588,7 @@ impl Parser {
                     ast::OperationType::Query,
                 ),
             },
+            keep_invalid_selections: self.keep_invalid_selections,
         };
         selection_set.extend_from_ast(Some(schema), &mut build_errors, &ast);
         let field_set = executable::FieldSet {
@@ -422,23 +647,81 @@ impl SourceFile {
         &self.path
     }
 
-    pub fn source_text(&self) -> &str {
-        &self.source_text
+    /// The text that was parsed.
+    ///
+    /// For a [detached][SourceFile::detach] source file this is loaded on demand,
+    /// and empty if it cannot be loaded.
+    /// Use [`try_source_text`][Self::try_source_text] to tell these cases apart.
+    pub fn source_text(&self) -> Cow<'_, str> {
+        self.try_source_text().unwrap_or_default()
+    }
+
+    /// The text that was parsed, or `None` if this source file is [detached][SourceFile::detach]
+    /// and its [`SourceStorage`] cannot load it.
+    pub fn try_source_text(&self) -> Option<Cow<'_, str>> {
+        match &self.text {
+            SourceText::Retained(text) => Some(Cow::Borrowed(text)),
+            SourceText::Detached { storage, .. } => storage.load().map(Cow::Owned),
+        }
+    }
+
+    /// The line and column in the host file where the source text starts,
+    /// as configured with [`Parser::with_source_offset`].
+    /// This is line 1, column 1 by default.
+    pub fn start(&self) -> LineColumn {
+        self.start
+    }
+
+    /// The byte offset in the host file where the source text starts,
+    /// as configured with [`Parser::with_source_offset`].
+    /// This is 0 by default.
+    pub fn start_offset(&self) -> usize {
+        self.start_offset
     }
 
-    pub(crate) fn ariadne(&self) -> &ariadne::Source {
-        self.source.get_or_init(|| {
+    /// Returns `None` for a detached source file, to avoid caching loaded text
+    pub(crate) fn ariadne(&self) -> Option<&ariadne::Source> {
+        let SourceText::Retained(text) = &self.text else {
+            return None;
+        };
+        Some(self.source.get_or_init(|| {
             // FIXME This string copy is not ideal, but changing to a reference counted string affects
             // public API
-            ariadne::Source::from(self.source_text.clone())
-        })
+            self.new_ariadne(text.clone())
+        }))
+    }
+
+    /// Returns an uncached ariadne source, for a detached source file
+    pub(crate) fn new_ariadne(&self, text: String) -> ariadne::Source {
+        ariadne::Source::from(text).with_display_line_offset(self.start.line.saturating_sub(1))
     }
 
     pub(crate) fn get_line_column(&self, index: usize) -> Option<LineColumn> {
-        let (_, zero_indexed_line, zero_indexed_column) = self.ariadne().get_byte_line(index)?;
+        let (zero_indexed_line, zero_indexed_column) = match &self.text {
+            SourceText::Retained(_) => {
+                let (_, line, column) = self.ariadne()?.get_byte_line(index)?;
+                (line, column)
+            }
+            SourceText::Detached {
+                line_starts, len, ..
+            } => {
+                if index > *len {
+                    return None;
+                }
+                let line = line_starts
+                    .binary_search(&index)
+                    .unwrap_or_else(|line| line.saturating_sub(1));
+                (line, index - line_starts[line])
+            }
+        };
+        let column = if zero_indexed_line == 0 {
+            self.start.column
+        } else {
+            1
+        };
         Some(LineColumn {
-            line: zero_indexed_line + 1,
-            column: zero_indexed_column + 1,
+            line: zero_indexed_line + self.start.line,
+            column: zero_indexed_column + column,
         })
     }
 }
@@ -447,18 +730,30 @@ impl std::fmt::Debug for SourceFile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let Self {
             path,
-            source_text,
+            text,
+            start,
+            start_offset,
             source: _, // Skipped: it’s a cache and would make debugging other things noisy
         } = self;
         let mut debug_struct = f.debug_struct("SourceFile");
         debug_struct.field("path", path);
-        if path != std::path::Path::new("built_in.graphql") {
-            debug_struct.field("source_text", source_text);
-        } else {
-            debug_struct.field(
-                "source_text",
-                &format_args!("include_str!(\"built_in.graphql\")"),
-            );
+        match text {
+            SourceText::Retained(_) if path == std::path::Path::new("built_in.graphql") => {
+                debug_struct.field(
+                    "source_text",
+                    &format_args!("include_str!(\"built_in.graphql\")"),
+                );
+            }
+            SourceText::Retained(source_text) => {
+                debug_struct.field("source_text", source_text);
+            }
+            SourceText::Detached { .. } => {
+                debug_struct.field("source_text", &format_args!("(detached)"));
+            }
+        }
+        if *start_offset != 0 || (start.line, start.column) != (1, 1) {
+            debug_struct.field("start", start);
+            debug_struct.field("start_offset", start_offset);
         }
         debug_struct.finish()
     }
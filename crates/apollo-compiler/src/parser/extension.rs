@@ -0,0 +1,218 @@
+use crate::ast;
+use crate::ast::from_cst::collect;
+use crate::ast::from_cst::leading_comments;
+use crate::ast::from_cst::with_location;
+use crate::ast::from_cst::Convert;
+use crate::parser::FileId;
+use crate::parser::SourceSpan;
+use crate::validation::Details;
+use crate::validation::DiagnosticList;
+use crate::Name;
+use crate::Node;
+use apollo_parser::cst;
+use apollo_parser::cst::CstNode;
+use apollo_parser::ExtensionParser;
+
+/// Support for experimental top-level definitions that are not part of GraphQL,
+/// registered with [`Parser::syntax_extension`][crate::parser::Parser::syntax_extension].
+///
+/// Parsing is delegated to the [`apollo_parser::SyntaxExtension`] supertrait,
+/// which produces a [`cst::CustomDefinition`] node.
+/// [`convert_definition`][Self::convert_definition] then desugars that node
+/// into standard AST definitions, so that the rest of the compiler
+/// (schema building, validation, serialization) does not need to know about the extension.
+///
+/// ## Example
+///
+/// ```
+/// use apollo_compiler::ast;
+/// use apollo_compiler::parser::ExtensionContext;
+/// use apollo_compiler::parser::Parser;
+/// use apollo_compiler::parser::SyntaxExtension;
+/// use apollo_compiler::Node;
+/// use apollo_parser::cst;
+/// use apollo_parser::cst::CstNode;
+/// use apollo_parser::ExtensionParser;
+/// use std::sync::Arc;
+///
+/// /// `entity User { id: ID! }` is sugar for `type User @entity { id: ID! }`
+/// struct Entities;
+///
+/// impl apollo_parser::SyntaxExtension for Entities {
+///     fn handles_keyword(&self, keyword: &str) -> bool {
+///         keyword == "entity"
+///     }
+///
+///     fn parse_definition(&self, _keyword: &str, p: &mut ExtensionParser<'_, '_>) {
+///         p.name();
+///         p.fields_definition();
+///     }
+/// }
+///
+/// impl SyntaxExtension for Entities {
+///     fn convert_definition(
+///         &self,
+///         definition: &cst::CustomDefinition,
+///         cx: &mut ExtensionContext<'_>,
+///     ) -> Vec<ast::Definition> {
+///         let children = || definition.syntax().children();
+///         let Some(name) = children().filter_map(cst::Name::cast).nth(1) else {
+///             return Vec::new(); // Already a syntax error
+///         };
+///         let Some(name) = cx.name(&name) else {
+///             return Vec::new();
+///         };
+///         let fields = children()
+///             .find_map(cst::FieldsDefinition::cast)
+///             .map(|fields| cx.fields_definition(&fields))
+///             .unwrap_or_default();
+///         let mut directives = ast::DirectiveList::new();
+///         directives.push(Node::new(ast::Directive::new(apollo_compiler::name!("entity"))));
+///         let location = cx.location(definition);
+///         vec![ast::Definition::ObjectTypeDefinition(Node::new_parsed(
+///             ast::ObjectTypeDefinition {
+///                 description: definition
+///                     .description()
+///                     .and_then(|description| cx.description(&description)),
+///                 name,
+///                 implements_interfaces: Vec::new(),
+///                 directives,
+///                 fields,
+///                 leading_comments: cx.leading_comments(definition),
+///             },
+///             location,
+///         ))]
+///     }
+/// }
+///
+/// let input = r#"
+///     directive @entity on OBJECT
+///     type Query { user: User }
+///     entity User { id: ID! }
+/// "#;
+/// let schema = Parser::new()
+///     .syntax_extension(Arc::new(Entities))
+///     .parse_schema(input, "schema.graphql")
+///     .unwrap();
+/// assert!(schema.get_object("User").unwrap().directives.has("entity"));
+/// ```
+pub trait SyntaxExtension: apollo_parser::SyntaxExtension + Send + Sync {
+    /// Converts a custom definition parsed by this extension into zero or more AST definitions.
+    ///
+    /// Syntax errors found while parsing are already reported.
+    /// Semantic errors can be reported with [`ExtensionContext::error`].
+    fn convert_definition(
+        &self,
+        definition: &cst::CustomDefinition,
+        cx: &mut ExtensionContext<'_>,
+    ) -> Vec<ast::Definition>;
+}
+
+/// Conversions from standard CST nodes found within a [`cst::CustomDefinition`],
+/// given to [`SyntaxExtension::convert_definition`].
+///
+/// Conversions return `None` or skip list items for nodes with syntax errors,
+/// which are already reported.
+pub struct ExtensionContext<'a> {
+    pub(crate) file_id: FileId,
+    pub(crate) errors: &'a mut DiagnosticList,
+}
+
+impl ExtensionContext<'_> {
+    /// Returns the source location of a node
+    pub fn location(&self, node: &impl CstNode) -> SourceSpan {
+        SourceSpan::new(self.file_id, node.syntax())
+    }
+
+    /// Reports an error at the location of `node`
+    pub fn error(&mut self, node: &impl CstNode, message: impl Into<String>) {
+        let location = Some(self.location(node));
+        self.errors.push(
+            location,
+            Details::SyntaxError {
+                message: message.into(),
+            },
+        )
+    }
+
+    /// Returns the comments before a node, as in `leading_comments` fields of AST definitions
+    pub fn leading_comments(&self, node: &impl CstNode) -> Vec<Node<str>> {
+        leading_comments(self.file_id, node)
+    }
+
+    pub fn name(&self, name: &cst::Name) -> Option<Name> {
+        name.convert(self.file_id)
+    }
+
+    pub fn description(&self, description: &cst::Description) -> Option<Node<str>> {
+        description.convert(self.file_id)
+    }
+
+    pub fn ty(&self, ty: &cst::Type) -> Option<ast::Type> {
+        ty.convert(self.file_id)
+    }
+
+    pub fn value(&self, value: &cst::Value) -> Option<Node<ast::Value>> {
+        Some(with_location(
+            self.file_id,
+            value.syntax(),
+            value.convert(self.file_id)?,
+        ))
+    }
+
+    pub fn directives(&self, directives: &cst::Directives) -> ast::DirectiveList {
+        ast::DirectiveList::from(collect(self.file_id, directives.directives()))
+    }
+
+    pub fn arguments(&self, arguments: &cst::Arguments) -> Vec<Node<ast::Argument>> {
+        collect(self.file_id, arguments.arguments())
+    }
+
+    pub fn arguments_definition(
+        &self,
+        arguments: &cst::ArgumentsDefinition,
+    ) -> Vec<Node<ast::InputValueDefinition>> {
+        collect(self.file_id, arguments.input_value_definitions())
+    }
+
+    pub fn implements_interfaces(
+        &self,
+        implements_interfaces: &cst::ImplementsInterfaces,
+    ) -> Vec<Name> {
+        Some(implements_interfaces.clone())
+            .convert(self.file_id)
+            .unwrap_or_default()
+    }
+
+    pub fn fields_definition(
+        &self,
+        fields: &cst::FieldsDefinition,
+    ) -> Vec<Node<ast::FieldDefinition>> {
+        collect(self.file_id, fields.field_definitions())
+    }
+
+    pub fn input_fields_definition(
+        &self,
+        fields: &cst::InputFieldsDefinition,
+    ) -> Vec<Node<ast::InputValueDefinition>> {
+        collect(self.file_id, fields.input_value_definitions())
+    }
+
+    pub fn selection_set(&self, selection_set: &cst::SelectionSet) -> Vec<ast::Selection> {
+        crate::ast::from_cst::convert_selection_set(selection_set, self.file_id)
+    }
+}
+
+/// Passes a compiler [`SyntaxExtension`] to `apollo_parser`
+/// without relying on trait upcasting
+pub(crate) struct ParserExtension<'a>(pub(crate) &'a dyn SyntaxExtension);
+
+impl apollo_parser::SyntaxExtension for ParserExtension<'_> {
+    fn handles_keyword(&self, keyword: &str) -> bool {
+        self.0.handles_keyword(keyword)
+    }
+
+    fn parse_definition(&self, keyword: &str, p: &mut ExtensionParser<'_, '_>) {
+        self.0.parse_definition(keyword, p)
+    }
+}
@@ -0,0 +1,132 @@
+use crate::parser::FileId;
+use crate::parser::SourceFile;
+use crate::parser::SourceMap;
+use crate::parser::SourceText;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+/// Provides the text of a [`SourceFile`] on demand instead of keeping it in memory.
+///
+/// Long-lived processes that hold many schemas or documents can replace the text
+/// of their source files with [`detach_sources`] after parsing.
+/// Diagnostics and line/column lookups then call [`load`][Self::load] when they need it.
+///
+/// The loaded text must be the same as when the file was parsed,
+/// since source locations are byte offsets into it.
+///
+/// This is implemented for:
+///
+/// * [`PathBuf`], reading a file from the file system
+/// * Closures `Fn() -> Option<String>`
+pub trait SourceStorage: Send + Sync {
+    /// Returns the full source text, or `None` if it is no longer available.
+    ///
+    /// Without source text, diagnostics are still printed but without code snippets.
+    fn load(&self) -> Option<String>;
+}
+
+impl SourceStorage for PathBuf {
+    fn load(&self) -> Option<String> {
+        std::fs::read_to_string(self).ok()
+    }
+}
+
+impl<F> SourceStorage for F
+where
+    F: Fn() -> Option<String> + Send + Sync,
+{
+    fn load(&self) -> Option<String> {
+        self()
+    }
+}
+
+impl SourceFile {
+    /// Returns a copy of this source file with the same path
+    /// whose text is provided by `storage` instead of kept in memory
+    pub fn detach(&self, storage: Arc<dyn SourceStorage>) -> Self {
+        let (line_starts, len) = match &self.text {
+            SourceText::Retained(text) => (self.line_starts(text), text.len()),
+            SourceText::Detached {
+                line_starts, len, ..
+            } => (line_starts.clone(), *len),
+        };
+        Self {
+            path: self.path.clone(),
+            text: SourceText::Detached {
+                storage,
+                line_starts,
+                len,
+            },
+            start: self.start,
+            start_offset: self.start_offset,
+            source: OnceLock::new(),
+        }
+    }
+
+    /// Returns the byte offsets where lines start, split as in `ariadne::Source`
+    fn line_starts(&self, text: &str) -> Arc<[usize]> {
+        let source = self.ariadne().expect("retained source file");
+        let mut char_starts = text.char_indices().map(|(index, _)| index);
+        let mut consumed = 0;
+        source
+            .lines()
+            .map(|line| {
+                let start = char_starts
+                    .nth(line.offset() - consumed)
+                    .unwrap_or(text.len());
+                consumed = line.offset() + 1;
+                start
+            })
+            .collect()
+    }
+
+    /// Returns whether the source text is kept in memory,
+    /// as opposed to provided on demand by a [`SourceStorage`]
+    pub fn is_retained(&self) -> bool {
+        matches!(self.text, SourceText::Retained(_))
+    }
+}
+
+/// Replace source files in `sources` with [detached][SourceFile::detach] copies
+/// whose text is provided by the storage that `storage` returns for each of them.
+/// Files for which it returns `None` keep their text in memory.
+/// Built-in definitions are always kept.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::parser::detach_sources;
+/// use apollo_compiler::Schema;
+/// use std::sync::Arc;
+///
+/// let text = "type Query { a: Missing }";
+/// let mut schema = Schema::parse(text, "schema.graphql").unwrap();
+/// // A real program might return `Arc::new(file.path().to_owned())`
+/// // to reload from the file system
+/// detach_sources(&mut schema.sources, |_file| Some(Arc::new(|| Some(text.to_owned()))));
+/// let file = schema.sources.values().last().unwrap();
+/// assert_eq!(file.path().to_str(), Some("schema.graphql"));
+/// assert!(!file.is_retained());
+///
+/// // Diagnostics still show source snippets
+/// let errors = schema.validate().unwrap_err();
+/// assert!(errors.to_string().contains("type Query { a: Missing }"));
+/// ```
+pub fn detach_sources(
+    sources: &mut SourceMap,
+    mut storage: impl FnMut(&SourceFile) -> Option<Arc<dyn SourceStorage>>,
+) {
+    let detached: Vec<_> = sources
+        .iter()
+        .filter(|(id, file)| **id != FileId::BUILT_IN && file.is_retained())
+        .filter_map(|(id, file)| Some((*id, file.detach(storage(file)?))))
+        .collect();
+    if detached.is_empty() {
+        return;
+    }
+    let sources = Arc::make_mut(sources);
+    for (id, file) in detached {
+        sources.insert(id, Arc::new(file));
+    }
+}
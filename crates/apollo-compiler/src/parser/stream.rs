@@ -0,0 +1,110 @@
+use crate::ast;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::ExecutableDocument;
+use crate::Schema;
+use futures_core::Stream;
+use std::io;
+use std::path::Path;
+use std::pin::pin;
+
+/// Error type of `parse_stream` methods such as [`Schema::parse_stream`]
+#[derive(Debug, thiserror::Error)]
+pub enum StreamParseError<T> {
+    /// Reading the stream failed, or it is not UTF-8
+    #[error("failed to read GraphQL source: {0}")]
+    Read(#[from] io::Error),
+    /// The source was read but has parse errors or build errors
+    #[error("{0}")]
+    Invalid(WithErrors<T>),
+}
+
+/// Read a stream of byte chunks to the end into a string,
+/// such as GraphQL source text from a file or network connection in an async program.
+///
+/// With Tokio, wrap an `AsyncRead` in
+/// [`tokio_util::io::ReaderStream`](https://docs.rs/tokio-util/latest/tokio_util/io/struct.ReaderStream.html).
+/// With the `futures` crate, use `AsyncReadExt::read_to_end` instead.
+///
+/// Returns an error with [`io::ErrorKind::InvalidData`] if the bytes are not UTF-8.
+pub async fn read_stream<B>(stream: impl Stream<Item = io::Result<B>>) -> io::Result<String>
+where
+    B: AsRef<[u8]>,
+{
+    let mut stream = pin!(stream);
+    let mut bytes = Vec::new();
+    while let Some(chunk) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        bytes.extend_from_slice(chunk?.as_ref())
+    }
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+impl Schema {
+    /// Read source text from a stream of byte chunks with [`read_stream`], then parse it
+    /// like [`Schema::parse`].
+    ///
+    /// Only reading is asynchronous. Parsing a large schema takes CPU time,
+    /// so async servers may want to run it outside of their event loop,
+    /// such as with Tokio’s `spawn_blocking`:
+    ///
+    /// ```ignore
+    /// let source = apollo_compiler::parser::read_stream(ReaderStream::new(file)).await?;
+    /// let schema = tokio::task::spawn_blocking(move || {
+    ///     Schema::parse_and_validate(source, "schema.graphql")
+    /// }).await?;
+    /// ```
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub async fn parse_stream<B>(
+        stream: impl Stream<Item = io::Result<B>>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, StreamParseError<Self>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let source_text = read_stream(stream).await?;
+        Self::parse(source_text, path).map_err(StreamParseError::Invalid)
+    }
+}
+
+impl ExecutableDocument {
+    /// Read source text from a stream of byte chunks with [`read_stream`], then parse it
+    /// like [`ExecutableDocument::parse`].
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub async fn parse_stream<B>(
+        schema: &Valid<Schema>,
+        stream: impl Stream<Item = io::Result<B>>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, StreamParseError<Self>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let source_text = read_stream(stream).await?;
+        Self::parse(schema, source_text, path).map_err(StreamParseError::Invalid)
+    }
+}
+
+impl ast::Document {
+    /// Read source text from a stream of byte chunks with [`read_stream`], then parse it
+    /// like [`ast::Document::parse`].
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub async fn parse_stream<B>(
+        stream: impl Stream<Item = io::Result<B>>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, StreamParseError<Self>>
+    where
+        B: AsRef<[u8]>,
+    {
+        let source_text = read_stream(stream).await?;
+        Self::parse(source_text, path).map_err(StreamParseError::Invalid)
+    }
+}
+
+impl<T> StreamParseError<T> {
+    /// Returns the parse or build errors, if reading succeeded
+    pub fn into_invalid(self) -> Option<WithErrors<T>> {
+        match self {
+            Self::Read(_) => None,
+            Self::Invalid(with_errors) => Some(with_errors),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use crate::ast;
+use crate::ast::serialize::Serialize;
+use crate::schema::ExtendedType;
+use crate::schema::SchemaDefinition;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use sha2::Digest;
+use sha2::Sha256;
+
+impl Serialize<'_, Schema> {
+    /// Serialize to a canonical form, such that schemas that differ only in ways
+    /// that do not affect their meaning serialize to the same text.
+    /// This is the input of [`Schema::content_hash`].
+    ///
+    /// The canonical form is:
+    ///
+    /// * Each type is a single definition, with any extensions merged into it.
+    ///   Built-in types are omitted. Extensions of built-in scalars are merged into
+    ///   a single `extend scalar`.
+    /// * The `schema` definition comes first, and is omitted if it would be implicit:
+    ///   no description, no directive, and root operations with default type names.
+    ///   Then directive definitions (except built-in ones) sorted by name,
+    ///   then type definitions sorted by name.
+    /// * Fields, input fields, enum values, arguments definitions, interfaces of
+    ///   `implements`, union members, and directive locations are sorted by name.
+    /// * Arguments of directive applications are sorted by name.
+    ///   The order of the directive applications themselves is kept,
+    ///   as is the order of fields in input object values.
+    /// * Descriptions have line endings normalized to `\n`,
+    ///   trailing whitespace removed from each line, and leading and trailing
+    ///   blank lines removed. Empty descriptions are omitted.
+    /// * There are no comments. Indentation is two spaces, with an empty line
+    ///   between top-level definitions and a final newline.
+    ///
+    /// Other builder methods called after this one still apply,
+    /// but their output is then not canonical.
+    ///
+    /// This form is part of the stable API: it only changes in a semver-breaking release.
+    pub fn canonical(mut self) -> Self {
+        self.config.canonical = true;
+        self.config.origin_comments = false;
+        self.config.indent_prefix = Some("  ");
+        self.config.initial_indent_level = 0;
+        self
+    }
+}
+
+impl Schema {
+    /// Returns the lowercase hexadecimal SHA-256 hash of the
+    /// [canonical serialization][Serialize::canonical] of this schema, encoded as UTF-8.
+    ///
+    /// Schemas that differ only in ways that do not affect their meaning
+    /// (order of definitions and fields, use of extensions, comments, whitespace)
+    /// have the same content hash.
+    /// Like the canonical form, this is stable across non-breaking releases.
+    pub fn content_hash(&self) -> String {
+        sha256_hex(&self.serialize().canonical().to_string())
+    }
+
+    pub(crate) fn to_canonical_ast(&self) -> impl Iterator<Item = ast::Definition> + '_ {
+        let mut directive_definitions: Vec<_> = self
+            .directive_definitions
+            .values()
+            .filter(|def| !def.is_built_in())
+            .collect();
+        directive_definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut types: Vec<_> = self.types.values().collect();
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+        self.schema_definition
+            .to_canonical_ast(self)
+            .into_iter()
+            .chain(
+                directive_definitions
+                    .into_iter()
+                    .map(|def| ast::Definition::DirectiveDefinition(directive_definition(def))),
+            )
+            .chain(types.into_iter().filter_map(|ty| ty.to_canonical_ast()))
+    }
+}
+
+impl Node<SchemaDefinition> {
+    fn to_canonical_ast(&self, schema: &Schema) -> Option<ast::Definition> {
+        let description = description(&self.description);
+        if description.is_none()
+            && self.directives.is_empty()
+            && self.has_implicit_root_operations(&schema.types)
+        {
+            return None;
+        }
+        Some(ast::Definition::SchemaDefinition(
+            self.same_location(ast::SchemaDefinition {
+                description,
+                leading_comments: Vec::new(),
+                directives: directives(self.directives.iter().map(|dir| &dir.node)),
+                root_operations: self
+                    .iter_root_operations()
+                    .map(|(ty, op)| (ty, op.name.clone()).into())
+                    .collect(),
+            }),
+        ))
+    }
+}
+
+impl ExtendedType {
+    fn to_canonical_ast(&self) -> Option<ast::Definition> {
+        let location = self.location();
+        Some(match self {
+            ExtendedType::Scalar(ty) if ty.is_built_in() => {
+                if ty.directives.is_empty() {
+                    return None;
+                }
+                ast::Definition::ScalarTypeExtension(Node::new_opt_location(
+                    ast::ScalarTypeExtension {
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                    },
+                    location,
+                ))
+            }
+            _ if self.is_built_in() => return None,
+            ExtendedType::Scalar(ty) => {
+                ast::Definition::ScalarTypeDefinition(Node::new_opt_location(
+                    ast::ScalarTypeDefinition {
+                        description: description(&ty.description),
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                    },
+                    location,
+                ))
+            }
+            ExtendedType::Object(ty) => {
+                ast::Definition::ObjectTypeDefinition(Node::new_opt_location(
+                    ast::ObjectTypeDefinition {
+                        description: description(&ty.description),
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        implements_interfaces: sorted(
+                            ty.implements_interfaces.iter().map(|i| i.name.clone()),
+                        ),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                        fields: fields(ty.fields.values().map(|field| &field.node)),
+                    },
+                    location,
+                ))
+            }
+            ExtendedType::Interface(ty) => {
+                ast::Definition::InterfaceTypeDefinition(Node::new_opt_location(
+                    ast::InterfaceTypeDefinition {
+                        description: description(&ty.description),
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        implements_interfaces: sorted(
+                            ty.implements_interfaces.iter().map(|i| i.name.clone()),
+                        ),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                        fields: fields(ty.fields.values().map(|field| &field.node)),
+                    },
+                    location,
+                ))
+            }
+            ExtendedType::Union(ty) => {
+                ast::Definition::UnionTypeDefinition(Node::new_opt_location(
+                    ast::UnionTypeDefinition {
+                        description: description(&ty.description),
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                        members: sorted(ty.members.iter().map(|member| member.name.clone())),
+                    },
+                    location,
+                ))
+            }
+            ExtendedType::Enum(ty) => {
+                let mut values: Vec<_> = ty
+                    .values
+                    .values()
+                    .map(|value| {
+                        let mut value = value.node.clone();
+                        let value_mut = value.make_mut();
+                        value_mut.description = description(&value_mut.description);
+                        value_mut.leading_comments = Vec::new();
+                        value_mut.directives = directives(&value_mut.directives);
+                        value
+                    })
+                    .collect();
+                values.sort_by(|a, b| a.value.cmp(&b.value));
+                ast::Definition::EnumTypeDefinition(Node::new_opt_location(
+                    ast::EnumTypeDefinition {
+                        description: description(&ty.description),
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                        values,
+                    },
+                    location,
+                ))
+            }
+            ExtendedType::InputObject(ty) => {
+                ast::Definition::InputObjectTypeDefinition(Node::new_opt_location(
+                    ast::InputObjectTypeDefinition {
+                        description: description(&ty.description),
+                        leading_comments: Vec::new(),
+                        name: ty.name.clone(),
+                        directives: directives(ty.directives.iter().map(|dir| &dir.node)),
+                        fields: input_values(ty.fields.values().map(|field| &field.node)),
+                    },
+                    location,
+                ))
+            }
+        })
+    }
+}
+
+fn directive_definition(def: &Node<ast::DirectiveDefinition>) -> Node<ast::DirectiveDefinition> {
+    let mut def = def.clone();
+    let def_mut = def.make_mut();
+    def_mut.description = description(&def_mut.description);
+    def_mut.leading_comments = Vec::new();
+    def_mut.arguments = input_values(&def_mut.arguments);
+    def_mut.locations.sort_by_key(|location| location.name());
+    def_mut.locations.dedup();
+    def
+}
+
+fn fields<'a>(
+    fields: impl IntoIterator<Item = &'a Node<ast::FieldDefinition>>,
+) -> Vec<Node<ast::FieldDefinition>> {
+    let mut fields: Vec<_> = fields
+        .into_iter()
+        .map(|field| {
+            let mut field = field.clone();
+            let field_mut = field.make_mut();
+            field_mut.description = description(&field_mut.description);
+            field_mut.leading_comments = Vec::new();
+            field_mut.arguments = input_values(&field_mut.arguments);
+            field_mut.directives = directives(&field_mut.directives);
+            field
+        })
+        .collect();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    fields
+}
+
+fn input_values<'a>(
+    values: impl IntoIterator<Item = &'a Node<ast::InputValueDefinition>>,
+) -> Vec<Node<ast::InputValueDefinition>> {
+    let mut values: Vec<_> = values
+        .into_iter()
+        .map(|value| {
+            let mut value = value.clone();
+            let value_mut = value.make_mut();
+            value_mut.description = description(&value_mut.description);
+            value_mut.leading_comments = Vec::new();
+            value_mut.directives = directives(&value_mut.directives);
+            value
+        })
+        .collect();
+    values.sort_by(|a, b| a.name.cmp(&b.name));
+    values
+}
+
+fn directives<'a>(
+    directives: impl IntoIterator<Item = &'a Node<ast::Directive>>,
+) -> ast::DirectiveList {
+    directives
+        .into_iter()
+        .map(|directive| {
+            let mut directive = directive.clone();
+            directive
+                .make_mut()
+                .arguments
+                .sort_by(|a, b| a.name.cmp(&b.name));
+            directive
+        })
+        .collect()
+}
+
+fn sorted(names: impl IntoIterator<Item = Name>) -> Vec<Name> {
+    let mut names: Vec<_> = names.into_iter().collect();
+    names.sort();
+    names
+}
+
+fn description(description: &Option<Node<str>>) -> Option<Node<str>> {
+    let original = description.as_ref()?;
+    let text = original.replace("\r\n", "\n").replace('\r', "\n");
+    let lines: Vec<&str> = text.split('\n').map(str::trim_end).collect();
+    let normalized = lines.join("\n");
+    let normalized = normalized.trim_matches('\n');
+    if normalized.is_empty() {
+        None
+    } else if normalized == &**original {
+        Some(original.clone())
+    } else {
+        Some(Node::new_str(normalized))
+    }
+}
+
+/// Lowercase hexadecimal SHA-256 hash of `text` encoded as UTF-8
+pub(crate) fn sha256_hex(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
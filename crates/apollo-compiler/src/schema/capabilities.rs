@@ -0,0 +1,149 @@
+use crate::schema::ExtendedType;
+use crate::Name;
+use crate::Schema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Names of directives defined by [Apollo Federation] specifications
+///
+/// [Apollo Federation]: https://www.apollographql.com/docs/federation/federated-schemas/federated-directives
+const FEDERATION_DIRECTIVES: &[&str] = &[
+    "authenticated",
+    "composeDirective",
+    "context",
+    "cost",
+    "extends",
+    "external",
+    "fromContext",
+    "inaccessible",
+    "interfaceObject",
+    "key",
+    "link",
+    "listSize",
+    "override",
+    "policy",
+    "provides",
+    "requires",
+    "requiresScopes",
+    "shareable",
+    "tag",
+];
+
+/// Which GraphQL features a schema relies on, returned by [`Schema::capabilities`].
+///
+/// Lists of names are in schema order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaCapabilities {
+    /// Whether the schema has a mutation root operation type
+    pub mutations: bool,
+    /// Whether the schema has a subscription root operation type
+    pub subscriptions: bool,
+    /// Scalar types other than the five built-in ones
+    pub custom_scalars: Vec<Name>,
+    /// Custom scalar types with a `@specifiedBy` directive
+    pub specified_by_scalars: Vec<Name>,
+    /// Interface types that implement other interfaces
+    pub interfaces_implementing_interfaces: Vec<Name>,
+    /// Input object types with the `@oneOf` directive
+    pub one_of_input_objects: Vec<Name>,
+    /// Whether the `@defer` directive is defined
+    pub defer: bool,
+    /// Whether the `@stream` directive is defined
+    pub stream: bool,
+    /// Directives defined by Apollo Federation that this schema defines,
+    /// as named in the schema (possibly with a `federation__` prefix)
+    pub federation_directives: Vec<Name>,
+}
+
+impl Schema {
+    /// Returns which GraphQL features this schema relies on,
+    /// so that a server or gateway can reject or adapt to a schema
+    /// that uses features it does not support.
+    ///
+    /// ```
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse(r#"
+    ///     directive @oneOf on INPUT_OBJECT
+    ///     type Query { search(by: SearchBy): [Result] }
+    ///     type Subscription { updates: Int }
+    ///     scalar DateTime @specifiedBy(url: "https://scalars.graphql.org/andimarek/date-time")
+    ///     input SearchBy @oneOf { text: String, since: DateTime }
+    ///     interface Node { id: ID! }
+    ///     interface Result implements Node { id: ID! }
+    /// "#, "schema.graphql").unwrap();
+    ///
+    /// let capabilities = schema.capabilities();
+    /// assert!(capabilities.subscriptions);
+    /// assert_eq!(capabilities.custom_scalars, ["DateTime"]);
+    /// assert_eq!(capabilities.one_of_input_objects, ["SearchBy"]);
+    /// assert_eq!(
+    ///     capabilities.feature_names(),
+    ///     ["subscriptions", "customScalars", "specifiedBy", "interfacesImplementingInterfaces", "oneOf"],
+    /// );
+    /// ```
+    pub fn capabilities(&self) -> SchemaCapabilities {
+        let mut capabilities = SchemaCapabilities {
+            mutations: self.schema_definition.mutation.is_some(),
+            subscriptions: self.schema_definition.subscription.is_some(),
+            defer: self.directive_definitions.contains_key("defer"),
+            stream: self.directive_definitions.contains_key("stream"),
+            ..Default::default()
+        };
+        for (name, ty) in &self.types {
+            match ty {
+                ExtendedType::Scalar(def) if !def.is_built_in() => {
+                    capabilities.custom_scalars.push(name.clone());
+                    if def.directives.has("specifiedBy") {
+                        capabilities.specified_by_scalars.push(name.clone())
+                    }
+                }
+                ExtendedType::Interface(def) if !def.implements_interfaces.is_empty() => {
+                    capabilities
+                        .interfaces_implementing_interfaces
+                        .push(name.clone())
+                }
+                ExtendedType::InputObject(def) if def.directives.has("oneOf") => {
+                    capabilities.one_of_input_objects.push(name.clone())
+                }
+                _ => {}
+            }
+        }
+        for (name, def) in &self.directive_definitions {
+            let unprefixed = name.strip_prefix("federation__").unwrap_or(name);
+            if !def.is_built_in() && FEDERATION_DIRECTIVES.contains(&unprefixed) {
+                capabilities.federation_directives.push(name.clone())
+            }
+        }
+        capabilities
+    }
+}
+
+impl SchemaCapabilities {
+    /// Returns the names of features this schema relies on, in a stable order:
+    /// `"mutations"`, `"subscriptions"`, `"customScalars"`, `"specifiedBy"`,
+    /// `"interfacesImplementingInterfaces"`, `"oneOf"`, `"defer"`, `"stream"`,
+    /// and `"federation"`.
+    ///
+    /// This can be compared against the list of features a server supports.
+    pub fn feature_names(&self) -> Vec<&'static str> {
+        [
+            ("mutations", self.mutations),
+            ("subscriptions", self.subscriptions),
+            ("customScalars", !self.custom_scalars.is_empty()),
+            ("specifiedBy", !self.specified_by_scalars.is_empty()),
+            (
+                "interfacesImplementingInterfaces",
+                !self.interfaces_implementing_interfaces.is_empty(),
+            ),
+            ("oneOf", !self.one_of_input_objects.is_empty()),
+            ("defer", self.defer),
+            ("stream", self.stream),
+            ("federation", !self.federation_directives.is_empty()),
+        ]
+        .into_iter()
+        .filter_map(|(name, used)| used.then_some(name))
+        .collect()
+    }
+}
@@ -0,0 +1,250 @@
+use crate::ast;
+use crate::ast::Definition;
+use crate::Node;
+use crate::Schema;
+
+const BUILT_IN_SCALARS: [&str; 5] = ["Int", "Float", "String", "Boolean", "ID"];
+const BUILT_IN_DIRECTIVES: [&str; 4] = ["skip", "include", "deprecated", "specifiedBy"];
+/// Directives whose applications are visible through introspection,
+/// as `isDeprecated`/`deprecationReason` and `specifiedByURL`
+const INTROSPECTABLE_DIRECTIVES: [&str; 2] = ["deprecated", "specifiedBy"];
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// A comparison of schemas that ignores differences which do not survive introspection,
+/// such as when checking that a schema fetched with
+/// [`Schema::from_introspection_json`] matches the SDL it was served from.
+///
+/// Comparing with `==` is too strict for this: source locations, definition order,
+/// extensions, and other details all differ after a round-trip through introspection.
+/// This comparison starts from the [canonical form][crate::ast::serialize::Serialize::canonical],
+/// which already ignores ordering, extensions, and trailing whitespace in descriptions,
+/// then ignores more depending on the options below. All options are enabled by default.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::schema::SchemaComparison;
+/// use apollo_compiler::Schema;
+///
+/// let sdl = Schema::parse(r#"
+///     directive @cacheControl(maxAge: Int) on FIELD_DEFINITION
+///     type Query {
+///         """
+///         The current user,
+///         if logged in
+///         """
+///         me: String @cacheControl(maxAge: 0)
+///         old: String @deprecated(reason: "No longer supported")
+///     }
+/// "#, "schema.graphql").unwrap();
+/// let introspected = Schema::parse(r#"
+///     directive @cacheControl(maxAge: Int) on FIELD_DEFINITION
+///     directive @specifiedBy(url: String!) on SCALAR
+///     type Query {
+///         old: String @deprecated
+///         "The current user, if logged in"
+///         me: String
+///     }
+/// "#, "introspection.graphql").unwrap();
+///
+/// assert_ne!(sdl, introspected);
+/// assert!(sdl.tolerant_eq(&introspected));
+///
+/// let comparison = SchemaComparison {
+///     introspectable_directives_only: false,
+///     ..Default::default()
+/// };
+/// assert!(!comparison.eq(&sdl, &introspected));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaComparison {
+    /// Ignore explicit definitions of built-in scalars (`Int`, `Float`, `String`, `Boolean`, `ID`),
+    /// built-in directives (`@skip`, `@include`, `@deprecated`, `@specifiedBy`),
+    /// and introspection types (names starting with `__`),
+    /// which some tools include in their SDL or introspection output and others omit.
+    pub ignore_built_in_definitions: bool,
+    /// Ignore applications of directives other than `@deprecated` and `@specifiedBy`,
+    /// which introspection does not expose, and treat a `@deprecated` reason of
+    /// `"No longer supported"` as equal to the default reason.
+    pub introspectable_directives_only: bool,
+    /// Compare descriptions with each sequence of whitespace, including line breaks,
+    /// replaced by a single space
+    pub normalize_description_whitespace: bool,
+}
+
+impl Default for SchemaComparison {
+    fn default() -> Self {
+        Self {
+            ignore_built_in_definitions: true,
+            introspectable_directives_only: true,
+            normalize_description_whitespace: true,
+        }
+    }
+}
+
+impl SchemaComparison {
+    /// Returns whether `left` and `right` are equal with this comparison
+    pub fn eq(&self, left: &Schema, right: &Schema) -> bool {
+        self.normalize(left) == self.normalize(right)
+    }
+
+    /// Returns the text that this comparison compares for `schema`:
+    /// its canonical form with ignored parts removed.
+    ///
+    /// When two schemas are not equal, comparing these strings with a diff
+    /// such as in `assert_eq!` shows why.
+    pub fn normalize(&self, schema: &Schema) -> String {
+        let definitions = schema
+            .to_canonical_ast()
+            .filter_map(|def| self.definition(def))
+            .collect();
+        let document = ast::Document {
+            definitions,
+            ..ast::Document::new()
+        };
+        document.serialize().indent_prefix("  ").to_string()
+    }
+
+    fn definition(&self, mut def: Definition) -> Option<Definition> {
+        if self.ignore_built_in_definitions {
+            match &def {
+                Definition::ScalarTypeDefinition(def)
+                    if BUILT_IN_SCALARS.contains(&def.name.as_str()) =>
+                {
+                    return None
+                }
+                Definition::ScalarTypeExtension(def)
+                    if BUILT_IN_SCALARS.contains(&def.name.as_str()) =>
+                {
+                    return None
+                }
+                Definition::DirectiveDefinition(def)
+                    if BUILT_IN_DIRECTIVES.contains(&def.name.as_str()) =>
+                {
+                    return None
+                }
+                _ if def.name().is_some_and(|name| name.starts_with("__")) => return None,
+                _ => {}
+            }
+        }
+        match &mut def {
+            Definition::SchemaDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+                if def.description.is_none()
+                    && def.directives.is_empty()
+                    && def
+                        .root_operations
+                        .iter()
+                        .all(|op| op.1 == op.0.default_type_name())
+                {
+                    return None;
+                }
+            }
+            Definition::DirectiveDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.input_values(&mut def.arguments);
+            }
+            Definition::ScalarTypeDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+            }
+            Definition::ScalarTypeExtension(def) => {
+                let def = def.make_mut();
+                self.directives(&mut def.directives);
+                if def.directives.is_empty() {
+                    return None;
+                }
+            }
+            Definition::ObjectTypeDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+                self.fields(&mut def.fields);
+            }
+            Definition::InterfaceTypeDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+                self.fields(&mut def.fields);
+            }
+            Definition::UnionTypeDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+            }
+            Definition::EnumTypeDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+                for value in &mut def.values {
+                    let value = value.make_mut();
+                    self.description(&mut value.description);
+                    self.directives(&mut value.directives);
+                }
+            }
+            Definition::InputObjectTypeDefinition(def) => {
+                let def = def.make_mut();
+                self.description(&mut def.description);
+                self.directives(&mut def.directives);
+                self.input_values(&mut def.fields);
+            }
+            // Not produced by the canonical form
+            _ => {}
+        }
+        Some(def)
+    }
+
+    fn fields(&self, fields: &mut [Node<ast::FieldDefinition>]) {
+        for field in fields {
+            let field = field.make_mut();
+            self.description(&mut field.description);
+            self.directives(&mut field.directives);
+            self.input_values(&mut field.arguments);
+        }
+    }
+
+    fn input_values(&self, values: &mut [Node<ast::InputValueDefinition>]) {
+        for value in values {
+            let value = value.make_mut();
+            self.description(&mut value.description);
+            self.directives(&mut value.directives);
+        }
+    }
+
+    fn directives(&self, directives: &mut ast::DirectiveList) {
+        if !self.introspectable_directives_only {
+            return;
+        }
+        directives.retain(|directive| INTROSPECTABLE_DIRECTIVES.contains(&directive.name.as_str()));
+        for directive in directives.iter_mut() {
+            if directive.name == "deprecated" {
+                directive.make_mut().arguments.retain(|arg| {
+                    !(arg.name == "reason"
+                        && arg.value.as_str() == Some(DEFAULT_DEPRECATION_REASON))
+                })
+            }
+        }
+    }
+
+    fn description(&self, description: &mut Option<Node<str>>) {
+        if !self.normalize_description_whitespace {
+            return;
+        }
+        if let Some(text) = description {
+            let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            *description = (!normalized.is_empty()).then(|| Node::new_str(&normalized));
+        }
+    }
+}
+
+impl Schema {
+    /// Returns whether this schema is equal to `other` when ignoring differences
+    /// that do not survive introspection, with the default [`SchemaComparison`] options
+    pub fn tolerant_eq(&self, other: &Schema) -> bool {
+        SchemaComparison::default().eq(self, other)
+    }
+}
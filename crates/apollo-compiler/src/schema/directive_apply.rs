@@ -0,0 +1,427 @@
+use crate::ast::Directive;
+use crate::ast::DirectiveDefinition;
+use crate::ast::DirectiveLocation;
+use crate::ast::InputValueDefinition;
+use crate::ast::Value;
+use crate::collections::HashSet;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::ExtendedType;
+use crate::validation::DiagnosticList;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// Error type of [`Schema::apply_directive`]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum DirectiveApplicationError {
+    /// The coordinate does not refer to an element of the schema
+    #[error("`{0}` does not exist in the schema")]
+    MissingElement(SchemaCoordinate),
+    /// The coordinate refers to a directive definition, which cannot have directives
+    #[error("directives cannot be applied to the directive definition `{0}`")]
+    NoDirectiveLocation(SchemaCoordinate),
+    /// The schema does not define the directive
+    #[error("cannot find directive `@{0}` in the schema")]
+    UndefinedDirective(Name),
+    /// The directive definition does not list the location of the element
+    #[error(
+        "directive `@{name}` cannot be applied to {location}, valid locations are: {}",
+        CommaSeparated(valid_locations)
+    )]
+    UnsupportedLocation {
+        name: Name,
+        location: DirectiveLocation,
+        valid_locations: Vec<DirectiveLocation>,
+    },
+    /// The directive is not repeatable and the element already has it
+    #[error("non-repeatable directive `@{name}` is already applied to this element")]
+    NotRepeatable { name: Name },
+    /// The directive definition has no argument with this name
+    #[error("directive `@{name}` has no argument `{argument}`")]
+    UndefinedArgument { name: Name, argument: Name },
+    /// The same argument is given more than once
+    #[error("argument `{argument}` of directive `@{name}` is given more than once")]
+    DuplicateArgument { name: Name, argument: Name },
+    /// A required argument is missing
+    #[error("directive `@{name}` requires argument `{argument}`")]
+    RequiredArgument { name: Name, argument: Name },
+    /// The value of an argument does not have the type of the argument definition
+    #[error("invalid value for argument `{argument}` of directive `@{name}`: {message}")]
+    InvalidArgumentValue {
+        name: Name,
+        argument: Name,
+        message: String,
+    },
+}
+
+struct CommaSeparated<'a>(&'a [DirectiveLocation]);
+
+impl fmt::Display for CommaSeparated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, location) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            location.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Schema {
+    /// Returns the [directive location][DirectiveLocation] of the schema element at `coordinate`,
+    /// which depends on the kind of element: for example [`DirectiveLocation::Object`]
+    /// for an object type or [`DirectiveLocation::InputFieldDefinition`] for an input field.
+    ///
+    /// Returns `None` if the element does not exist or is a directive definition.
+    /// The schema definition, whose location is [`DirectiveLocation::Schema`],
+    /// does not have a coordinate.
+    pub fn directive_location(&self, coordinate: &SchemaCoordinate) -> Option<DirectiveLocation> {
+        Some(match coordinate.lookup(self).ok()? {
+            SchemaCoordinateLookup::Type(ty) => match ty {
+                ExtendedType::Scalar(_) => DirectiveLocation::Scalar,
+                ExtendedType::Object(_) => DirectiveLocation::Object,
+                ExtendedType::Interface(_) => DirectiveLocation::Interface,
+                ExtendedType::Union(_) => DirectiveLocation::Union,
+                ExtendedType::Enum(_) => DirectiveLocation::Enum,
+                ExtendedType::InputObject(_) => DirectiveLocation::InputObject,
+            },
+            SchemaCoordinateLookup::Field(_) => DirectiveLocation::FieldDefinition,
+            SchemaCoordinateLookup::InputField(_) => DirectiveLocation::InputFieldDefinition,
+            SchemaCoordinateLookup::EnumValue(_) => DirectiveLocation::EnumValue,
+            SchemaCoordinateLookup::Argument(_) => DirectiveLocation::ArgumentDefinition,
+            SchemaCoordinateLookup::Directive(_) => return None,
+        })
+    }
+
+    /// Returns the definitions of directives that [`apply_directive`][Self::apply_directive]
+    /// could apply to the schema element at `coordinate`: those whose locations include
+    /// the location of the element, except non-repeatable directives already applied to it.
+    ///
+    /// This can power suggestions in a schema editor.
+    pub fn applicable_directives(
+        &self,
+        coordinate: &SchemaCoordinate,
+    ) -> Vec<&Node<DirectiveDefinition>> {
+        let Some(location) = self.directive_location(coordinate) else {
+            return Vec::new();
+        };
+        self.directive_definitions
+            .values()
+            .filter(|def| {
+                def.locations.contains(&location)
+                    && (def.repeatable || !self.has_directive_at(coordinate, &def.name))
+            })
+            .collect()
+    }
+
+    /// Returns the coordinates of every schema element that the directive named `name`
+    /// may be applied to, based on the locations of its definition,
+    /// in the order of [`types`][Self::types] then [`directive_definitions`][Self::directive_definitions].
+    ///
+    /// Returns an empty list if the directive is not defined.
+    /// Elements where a non-repeatable directive is already applied are included.
+    pub fn directive_targets(&self, name: &str) -> Vec<SchemaCoordinate> {
+        let Some(def) = self.directive_definitions.get(name) else {
+            return Vec::new();
+        };
+        let allows = |location| def.locations.contains(&location);
+        let mut targets = Vec::new();
+        for (type_name, ty) in &self.types {
+            if ty.is_built_in() && !ty.is_scalar() {
+                // Introspection types
+                continue;
+            }
+            let type_coordinate = TypeCoordinate {
+                ty: type_name.clone(),
+            };
+            let attribute = |attribute: &Name| type_coordinate.with_attribute(attribute.clone());
+            if self
+                .directive_location(&type_coordinate.clone().into())
+                .is_some_and(allows)
+            {
+                targets.push(type_coordinate.clone().into())
+            }
+            let fields = match ty {
+                ExtendedType::Object(ty) => &ty.fields,
+                ExtendedType::Interface(ty) => &ty.fields,
+                ExtendedType::InputObject(ty) => {
+                    if allows(DirectiveLocation::InputFieldDefinition) {
+                        targets.extend(ty.fields.keys().map(|name| attribute(name).into()))
+                    }
+                    continue;
+                }
+                ExtendedType::Enum(ty) => {
+                    if allows(DirectiveLocation::EnumValue) {
+                        targets.extend(ty.values.keys().map(|name| attribute(name).into()))
+                    }
+                    continue;
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) => continue,
+            };
+            for (field_name, field) in fields {
+                if allows(DirectiveLocation::FieldDefinition) {
+                    targets.push(attribute(field_name).into())
+                }
+                if allows(DirectiveLocation::ArgumentDefinition) {
+                    for arg in &field.arguments {
+                        targets.push(attribute(field_name).with_argument(arg.name.clone()).into())
+                    }
+                }
+            }
+        }
+        if allows(DirectiveLocation::ArgumentDefinition) {
+            for def in self.directive_definitions.values() {
+                if def.is_built_in() {
+                    continue;
+                }
+                let coordinate = DirectiveCoordinate {
+                    directive: def.name.clone(),
+                };
+                for arg in &def.arguments {
+                    targets.push(coordinate.with_argument(arg.name.clone()).into())
+                }
+            }
+        }
+        targets
+    }
+
+    /// Apply `directive` to the schema element at `coordinate`,
+    /// after checking it against the directive definition:
+    /// the definition must exist and include the location of the element,
+    /// a non-repeatable directive must not already be applied to the element,
+    /// and arguments must be defined, given at most once, and have values
+    /// of the argument type. Required arguments must be given.
+    ///
+    /// On error, the schema is not modified.
+    ///
+    /// This catches mistakes when building a schema programmatically
+    /// at the point where they are made, instead of in later validation of the whole schema.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::ast::Directive;
+    /// use apollo_compiler::ast::DirectiveLocation;
+    /// use apollo_compiler::coordinate::SchemaCoordinate;
+    /// use apollo_compiler::name;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let mut schema = Schema::parse_and_validate(
+    ///     "directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+    ///      type Query { me: String }",
+    ///     "schema.graphql",
+    /// ).unwrap().into_inner();
+    ///
+    /// let query: SchemaCoordinate = "Query".parse().unwrap();
+    /// let tag = Directive {
+    ///     name: name!("tag"),
+    ///     arguments: vec![(name!("name"), "public").into()],
+    /// };
+    /// schema.apply_directive(&query, tag).unwrap();
+    ///
+    /// let argument: SchemaCoordinate = "Query.me".parse().unwrap();
+    /// let error = schema.apply_directive(&argument, Directive::new(name!("tag"))).unwrap_err();
+    /// assert_eq!(error.to_string(), "directive `@tag` requires argument `name`");
+    ///
+    /// assert_eq!(
+    ///     schema.directive_definitions["tag"].locations,
+    ///     [DirectiveLocation::Object, DirectiveLocation::FieldDefinition],
+    /// );
+    /// assert_eq!(schema.directive_targets("tag").len(), 2);
+    /// assert!(schema.validate().is_ok());
+    /// ```
+    pub fn apply_directive(
+        &mut self,
+        coordinate: &SchemaCoordinate,
+        directive: impl Into<Node<Directive>>,
+    ) -> Result<(), DirectiveApplicationError> {
+        let directive = directive.into();
+        self.check_directive_application(coordinate, &directive)?;
+        let directives = match coordinate {
+            SchemaCoordinate::Type(coordinate) => {
+                let directives = match self.types.get_mut(&coordinate.ty) {
+                    Some(ExtendedType::Scalar(ty)) => &mut ty.make_mut().directives,
+                    Some(ExtendedType::Object(ty)) => &mut ty.make_mut().directives,
+                    Some(ExtendedType::Interface(ty)) => &mut ty.make_mut().directives,
+                    Some(ExtendedType::Union(ty)) => &mut ty.make_mut().directives,
+                    Some(ExtendedType::Enum(ty)) => &mut ty.make_mut().directives,
+                    Some(ExtendedType::InputObject(ty)) => &mut ty.make_mut().directives,
+                    None => unreachable!("checked above"),
+                };
+                directives.push(directive);
+                return Ok(());
+            }
+            SchemaCoordinate::TypeAttribute(coordinate) => self.attribute_directives(coordinate),
+            SchemaCoordinate::FieldArgument(coordinate) => {
+                let attribute = coordinate.field_coordinate();
+                let field = match self.types.get_mut(&attribute.ty) {
+                    Some(ExtendedType::Object(ty)) => {
+                        ty.make_mut().fields.get_mut(&attribute.attribute)
+                    }
+                    Some(ExtendedType::Interface(ty)) => {
+                        ty.make_mut().fields.get_mut(&attribute.attribute)
+                    }
+                    _ => None,
+                };
+                field.and_then(|field| {
+                    argument_directives(&mut field.make_mut().arguments, &coordinate.argument)
+                })
+            }
+            SchemaCoordinate::DirectiveArgument(coordinate) => self
+                .directive_definitions
+                .get_mut(&coordinate.directive)
+                .and_then(|def| {
+                    argument_directives(&mut def.make_mut().arguments, &coordinate.argument)
+                }),
+            SchemaCoordinate::Directive(_) => None,
+        };
+        directives.expect("checked above").push(directive);
+        Ok(())
+    }
+
+    fn attribute_directives(
+        &mut self,
+        coordinate: &TypeAttributeCoordinate,
+    ) -> Option<&mut crate::ast::DirectiveList> {
+        let name = &coordinate.attribute;
+        Some(match self.types.get_mut(&coordinate.ty)? {
+            ExtendedType::Object(ty) => {
+                &mut ty.make_mut().fields.get_mut(name)?.make_mut().directives
+            }
+            ExtendedType::Interface(ty) => {
+                &mut ty.make_mut().fields.get_mut(name)?.make_mut().directives
+            }
+            ExtendedType::InputObject(ty) => {
+                &mut ty.make_mut().fields.get_mut(name)?.make_mut().directives
+            }
+            ExtendedType::Enum(ty) => {
+                &mut ty.make_mut().values.get_mut(name)?.make_mut().directives
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => return None,
+        })
+    }
+
+    fn has_directive_at(&self, coordinate: &SchemaCoordinate, name: &str) -> bool {
+        match coordinate.lookup(self) {
+            Ok(SchemaCoordinateLookup::Type(ty)) => ty.directives().has(name),
+            Ok(SchemaCoordinateLookup::Field(def)) => def.directives.has(name),
+            Ok(SchemaCoordinateLookup::InputField(def)) => def.directives.has(name),
+            Ok(SchemaCoordinateLookup::EnumValue(def)) => def.directives.has(name),
+            Ok(SchemaCoordinateLookup::Argument(def)) => def.directives.has(name),
+            Ok(SchemaCoordinateLookup::Directive(_)) | Err(_) => false,
+        }
+    }
+
+    fn check_directive_application(
+        &self,
+        coordinate: &SchemaCoordinate,
+        directive: &Directive,
+    ) -> Result<(), DirectiveApplicationError> {
+        let name = &directive.name;
+        if coordinate.lookup(self).is_err() {
+            return Err(DirectiveApplicationError::MissingElement(
+                coordinate.clone(),
+            ));
+        }
+        let location = self
+            .directive_location(coordinate)
+            .ok_or_else(|| DirectiveApplicationError::NoDirectiveLocation(coordinate.clone()))?;
+        let def = self
+            .directive_definitions
+            .get(name)
+            .ok_or_else(|| DirectiveApplicationError::UndefinedDirective(name.clone()))?;
+        if !def.locations.contains(&location) {
+            return Err(DirectiveApplicationError::UnsupportedLocation {
+                name: name.clone(),
+                location,
+                valid_locations: def.locations.clone(),
+            });
+        }
+        if !def.repeatable && self.has_directive_at(coordinate, name) {
+            return Err(DirectiveApplicationError::NotRepeatable { name: name.clone() });
+        }
+        let mut given = HashSet::default();
+        for arg in &directive.arguments {
+            if !given.insert(&arg.name) {
+                return Err(DirectiveApplicationError::DuplicateArgument {
+                    name: name.clone(),
+                    argument: arg.name.clone(),
+                });
+            }
+            let arg_def = def.argument_by_name(&arg.name).ok_or_else(|| {
+                DirectiveApplicationError::UndefinedArgument {
+                    name: name.clone(),
+                    argument: arg.name.clone(),
+                }
+            })?;
+            self.check_argument_value(name, arg_def, &arg.value)?;
+        }
+        for arg_def in &def.arguments {
+            if arg_def.is_required() && !given.contains(&arg_def.name) {
+                return Err(DirectiveApplicationError::RequiredArgument {
+                    name: name.clone(),
+                    argument: arg_def.name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_argument_value(
+        &self,
+        name: &Name,
+        arg_def: &Node<InputValueDefinition>,
+        value: &Node<Value>,
+    ) -> Result<(), DirectiveApplicationError> {
+        let error = |message: String| DirectiveApplicationError::InvalidArgumentValue {
+            name: name.clone(),
+            argument: arg_def.name.clone(),
+            message,
+        };
+        if let Some(variable) = find_variable(value) {
+            return Err(error(format!(
+                "variable `${variable}` is not allowed in a schema"
+            )));
+        }
+        let mut diagnostics = DiagnosticList::new(self.sources.clone());
+        crate::validation::value::value_of_correct_type(
+            &mut diagnostics,
+            self,
+            &arg_def.ty,
+            value,
+            &[],
+        );
+        let message = diagnostics
+            .iter()
+            .next()
+            .map(|diagnostic| diagnostic.error.to_string());
+        match message {
+            Some(message) => Err(error(message)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn argument_directives<'a>(
+    arguments: &'a mut [Node<InputValueDefinition>],
+    name: &Name,
+) -> Option<&'a mut crate::ast::DirectiveList> {
+    let arg = arguments.iter_mut().find(|arg| arg.name == *name)?;
+    Some(&mut arg.make_mut().directives)
+}
+
+fn find_variable(value: &Value) -> Option<&Name> {
+    match value {
+        Value::Variable(name) => Some(name),
+        Value::List(items) => items.iter().find_map(|item| find_variable(item)),
+        Value::Object(fields) => fields.iter().find_map(|(_, value)| find_variable(value)),
+        _ => None,
+    }
+}
@@ -0,0 +1,299 @@
+use crate::ast::ArgumentByNameError;
+use crate::ast::Value;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::schema::Directive;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// A Rust type that can be extracted from applications of a custom directive.
+///
+/// Implement this trait for a struct that mirrors the arguments of a directive definition,
+/// then use [`Schema::extract_directive`] or [`Schema::extract_directives`]
+/// to get typed values instead of matching on [`Value`] by hand.
+/// [`DirectiveArguments`] provides argument lookup that accounts for default values
+/// declared in the directive definition.
+///
+/// The `apollo-compiler-macros` crate provides `#[derive(FromDirective)]`,
+/// which generates the implementation below from the struct definition.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::Schema;
+/// use apollo_compiler::coord;
+/// use apollo_compiler::schema::DirectiveArguments;
+/// use apollo_compiler::schema::DirectiveExtractionError;
+/// use apollo_compiler::schema::FromDirective;
+///
+/// struct CacheControl {
+///     max_age: i32,
+///     scope: Option<String>,
+/// }
+///
+/// impl FromDirective for CacheControl {
+///     const NAME: &'static str = "cacheControl";
+///
+///     fn from_directive(args: &DirectiveArguments<'_>) -> Result<Self, DirectiveExtractionError> {
+///         Ok(Self {
+///             max_age: args.get("maxAge")?,
+///             scope: args.get("scope")?,
+///         })
+///     }
+/// }
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     directive @cacheControl(maxAge: Int = 0, scope: String) on FIELD_DEFINITION
+///     type Query {
+///         user: String @cacheControl(maxAge: 60)
+///     }
+/// "#, "schema.graphql").unwrap();
+///
+/// let cache = schema.extract_directive::<CacheControl>(&coord!(Query.user).into()).unwrap().unwrap();
+/// assert_eq!(cache.max_age, 60);
+/// assert_eq!(cache.scope, None);
+/// ```
+pub trait FromDirective: Sized {
+    /// The name of the directive, without the `@` sigil
+    const NAME: &'static str;
+
+    /// Convert one application of the directive
+    fn from_directive(args: &DirectiveArguments<'_>) -> Result<Self, DirectiveExtractionError>;
+}
+
+/// Arguments of a directive application, passed to [`FromDirective::from_directive`].
+#[derive(Debug, Clone, Copy)]
+pub struct DirectiveArguments<'schema> {
+    schema: &'schema Schema,
+    directive: &'schema Directive,
+}
+
+/// A Rust type that can be converted from a GraphQL argument value,
+/// for use with [`DirectiveArguments::get`].
+pub trait FromArgumentValue: Sized {
+    /// Describes the expected GraphQL value, for error messages
+    const EXPECTED: &'static str;
+
+    /// Returns `None` if `value` does not have the expected kind
+    fn from_argument_value(value: &Node<Value>) -> Option<Self>;
+}
+
+/// Error type of [`Schema::extract_directive`] and [`FromDirective`] implementations
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum DirectiveExtractionError {
+    /// The schema coordinate does not point to anything in the schema
+    #[error("schema coordinate `{0}` does not exist")]
+    NoSuchCoordinate(SchemaCoordinate),
+
+    /// The schema coordinate points to something that cannot have directives applied
+    #[error("schema coordinate `{0}` cannot have directives")]
+    NoDirectives(SchemaCoordinate),
+
+    /// Found a non-repeatable directive applied more than once
+    #[error("directive `@{directive}` is applied more than once")]
+    MultipleApplications { directive: Name },
+
+    /// The directive is not defined in the schema
+    #[error("directive `@{directive}` is not defined")]
+    UndefinedDirective { directive: Name },
+
+    /// The directive definition does not define the requested argument
+    #[error("directive `@{directive}` does not have an argument `{argument}`")]
+    NoSuchArgument { directive: Name, argument: String },
+
+    /// A required argument is not specified
+    #[error("required argument `{argument}` of directive `@{directive}` is not specified")]
+    RequiredArgumentNotSpecified { directive: Name, argument: String },
+
+    /// The argument value does not have the kind expected by the Rust type
+    #[error("argument `{argument}` of directive `@{directive}` must be {expected}")]
+    InvalidArgument {
+        directive: Name,
+        argument: String,
+        expected: &'static str,
+    },
+
+    /// Custom error from a [`FromDirective`] implementation
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl Schema {
+    /// Returns the application of directive `T` on the schema element at `coordinate`,
+    /// converted with [`FromDirective`].
+    ///
+    /// Returns `Ok(None)` if the directive is not applied there,
+    /// and an error if it is applied more than once.
+    /// Use [`extract_directives`][Self::extract_directives] for repeatable directives.
+    pub fn extract_directive<'schema, T: FromDirective + 'schema>(
+        &'schema self,
+        coordinate: &SchemaCoordinate,
+    ) -> Result<Option<T>, DirectiveExtractionError> {
+        let mut iter = self.extract_directives::<T>(coordinate)?;
+        let Some(first) = iter.next() else {
+            return Ok(None);
+        };
+        if iter.next().is_some() {
+            return Err(DirectiveExtractionError::MultipleApplications {
+                directive: Name::new_static_unchecked(T::NAME),
+            });
+        }
+        first.map(Some)
+    }
+
+    /// Returns an iterator of every application of directive `T`
+    /// on the schema element at `coordinate`, converted with [`FromDirective`].
+    ///
+    /// Directives on types include those from type extensions.
+    pub fn extract_directives<'schema, T: FromDirective + 'schema>(
+        &'schema self,
+        coordinate: &SchemaCoordinate,
+    ) -> Result<
+        impl Iterator<Item = Result<T, DirectiveExtractionError>> + 'schema,
+        DirectiveExtractionError,
+    > {
+        let lookup = coordinate
+            .lookup(self)
+            .map_err(|_| DirectiveExtractionError::NoSuchCoordinate(coordinate.clone()))?;
+        let directives: Vec<&'schema Node<Directive>> = match lookup {
+            SchemaCoordinateLookup::Type(ty) => ty.directives().iter_ast().collect(),
+            SchemaCoordinateLookup::Field(def) => def.directives.iter().collect(),
+            SchemaCoordinateLookup::InputField(def) => def.directives.iter().collect(),
+            SchemaCoordinateLookup::EnumValue(def) => def.directives.iter().collect(),
+            SchemaCoordinateLookup::Argument(def) => def.directives.iter().collect(),
+            SchemaCoordinateLookup::Directive(_) => {
+                return Err(DirectiveExtractionError::NoDirectives(coordinate.clone()))
+            }
+        };
+        Ok(directives
+            .into_iter()
+            .filter(|directive| directive.name == T::NAME)
+            .map(move |directive| {
+                T::from_directive(&DirectiveArguments {
+                    schema: self,
+                    directive,
+                })
+            }))
+    }
+}
+
+impl<'schema> DirectiveArguments<'schema> {
+    /// The directive application being converted
+    pub fn directive(&self) -> &'schema Directive {
+        self.directive
+    }
+
+    /// Returns the value of argument `name` converted to `T`,
+    /// accounting for nullability and for the default value in the directive definition.
+    ///
+    /// Use `Option<T>` for nullable arguments.
+    pub fn get<T: FromArgumentValue>(&self, name: &str) -> Result<T, DirectiveExtractionError> {
+        let directive = &self.directive.name;
+        let value =
+            self.directive
+                .argument_by_name(name, self.schema)
+                .map_err(|err| match err {
+                    ArgumentByNameError::UndefinedDirective => {
+                        DirectiveExtractionError::UndefinedDirective {
+                            directive: directive.clone(),
+                        }
+                    }
+                    ArgumentByNameError::NoSuchArgument => {
+                        DirectiveExtractionError::NoSuchArgument {
+                            directive: directive.clone(),
+                            argument: name.to_owned(),
+                        }
+                    }
+                    ArgumentByNameError::RequiredArgumentNotSpecified => {
+                        DirectiveExtractionError::RequiredArgumentNotSpecified {
+                            directive: directive.clone(),
+                            argument: name.to_owned(),
+                        }
+                    }
+                })?;
+        T::from_argument_value(value).ok_or_else(|| DirectiveExtractionError::InvalidArgument {
+            directive: directive.clone(),
+            argument: name.to_owned(),
+            expected: T::EXPECTED,
+        })
+    }
+}
+
+impl FromArgumentValue for bool {
+    const EXPECTED: &'static str = "a boolean";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        value.to_bool()
+    }
+}
+
+impl FromArgumentValue for i32 {
+    const EXPECTED: &'static str = "an integer";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        value.to_i32()
+    }
+}
+
+impl FromArgumentValue for f64 {
+    const EXPECTED: &'static str = "a number";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        value.to_f64()
+    }
+}
+
+impl FromArgumentValue for String {
+    const EXPECTED: &'static str = "a string";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        value.as_str().map(ToOwned::to_owned)
+    }
+}
+
+/// Converts from an enum value
+impl FromArgumentValue for Name {
+    const EXPECTED: &'static str = "an enum value";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        value.as_enum().cloned()
+    }
+}
+
+/// Returns the value unchanged, for arguments of custom scalar or input object types
+impl FromArgumentValue for Node<Value> {
+    const EXPECTED: &'static str = "a value";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+/// Converts `null` to `None`
+impl<T: FromArgumentValue> FromArgumentValue for Option<T> {
+    const EXPECTED: &'static str = T::EXPECTED;
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        if value.is_null() {
+            Some(None)
+        } else {
+            T::from_argument_value(value).map(Some)
+        }
+    }
+}
+
+/// Like [input coercion](https://spec.graphql.org/draft/#sec-List.Input-Coercion),
+/// a single non-list value is converted to a list of one item.
+impl<T: FromArgumentValue> FromArgumentValue for Vec<T> {
+    const EXPECTED: &'static str = "a list";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        if let Some(list) = value.as_list() {
+            list.iter().map(T::from_argument_value).collect()
+        } else {
+            T::from_argument_value(value).map(|item| vec![item])
+        }
+    }
+}
@@ -7,6 +7,10 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct SchemaBuilder {
     adopt_orphan_extensions: bool,
+    dedup_directives: bool,
+    tolerant: bool,
+    preserve_source_order: bool,
+    collisions: DefinitionCollisions,
     pub(crate) schema: Schema,
     schema_definition: SchemaDefinitionStatus,
     orphan_type_extensions: IndexMap<Name, Vec<ast::Definition>>,
@@ -30,36 +34,56 @@ impl Default for SchemaBuilder {
 impl SchemaBuilder {
     pub(crate) fn built_in() -> &'static Self {
         static BUILT_IN: std::sync::OnceLock<SchemaBuilder> = std::sync::OnceLock::new();
-        BUILT_IN.get_or_init(|| {
-            let mut builder = SchemaBuilder {
-                adopt_orphan_extensions: false,
-                schema: Schema {
-                    sources: Default::default(),
-                    schema_definition: Node::new(SchemaDefinition {
-                        description: None,
-                        directives: DirectiveList::default(),
-                        query: None,
-                        mutation: None,
-                        subscription: None,
-                    }),
-                    directive_definitions: IndexMap::with_hasher(Default::default()),
-                    types: IndexMap::with_hasher(Default::default()),
-                },
-                schema_definition: SchemaDefinitionStatus::NoneSoFar {
-                    orphan_extensions: Vec::new(),
-                },
-                orphan_type_extensions: IndexMap::with_hasher(Default::default()),
-                errors: DiagnosticList::new(Default::default()),
-            };
-            let input = include_str!("../built_in_types.graphql").to_owned();
-            let path = "built_in.graphql";
-            let id = FileId::BUILT_IN;
-            let ast = ast::Document::parser().parse_ast_inner(input, path, id, &mut builder.errors);
-            let executable_definitions_are_errors = true;
-            builder.add_ast_document(&ast, executable_definitions_are_errors);
-            assert!(builder.errors.is_empty());
-            builder
-        })
+        BUILT_IN.get_or_init(|| Self::parse_built_in(None))
+    }
+
+    fn built_in_edition(edition: SpecEdition) -> &'static Self {
+        static OCTOBER_2021: std::sync::OnceLock<SchemaBuilder> = std::sync::OnceLock::new();
+        static DRAFT: std::sync::OnceLock<SchemaBuilder> = std::sync::OnceLock::new();
+        match edition {
+            SpecEdition::October2021 => {
+                OCTOBER_2021.get_or_init(|| Self::parse_built_in(Some(SpecEdition::October2021)))
+            }
+            SpecEdition::Draft => {
+                DRAFT.get_or_init(|| Self::parse_built_in(Some(SpecEdition::Draft)))
+            }
+        }
+    }
+
+    fn parse_built_in(edition: Option<SpecEdition>) -> Self {
+        let mut builder = SchemaBuilder {
+            adopt_orphan_extensions: false,
+            dedup_directives: false,
+            tolerant: false,
+            preserve_source_order: true,
+            collisions: DefinitionCollisions::default(),
+            schema: Schema {
+                sources: Default::default(),
+                schema_definition: Node::new(SchemaDefinition {
+                    description: None,
+                    directives: DirectiveList::default(),
+                    query: None,
+                    mutation: None,
+                    subscription: None,
+                }),
+                directive_definitions: IndexMap::with_hasher(Default::default()),
+                types: IndexMap::with_hasher(Default::default()),
+            },
+            schema_definition: SchemaDefinitionStatus::NoneSoFar {
+                orphan_extensions: Vec::new(),
+            },
+            orphan_type_extensions: IndexMap::with_hasher(Default::default()),
+            errors: DiagnosticList::new(Default::default()),
+        };
+        let input = include_str!("../built_in_types.graphql").to_owned();
+        let path = "built_in.graphql";
+        let id = FileId::BUILT_IN;
+        let ast = ast::Document::parser().parse_ast_inner(input, path, id, &mut builder.errors);
+        let executable_definitions_are_errors = true;
+        builder.add_ast_document(&ast, executable_definitions_are_errors);
+        assert!(builder.errors.is_empty());
+        builder.restrict_built_ins(edition);
+        builder
     }
 
     /// Returns a new schema builder initialized with built-in directives, built-in scalars,
@@ -68,6 +92,41 @@ impl SchemaBuilder {
         Self::built_in().clone()
     }
 
+    /// Configure which edition of the GraphQL specification
+    /// the built-in directives and introspection types come from.
+    /// See [`SpecEdition`] for the differences between editions and the default.
+    ///
+    /// Built-in directives redefined by documents already added to this builder
+    /// keep their new definition.
+    pub fn spec_edition(mut self, edition: SpecEdition) -> Self {
+        let built_in = &Self::built_in_edition(edition).schema;
+        let schema = &mut self.schema;
+        let mut directives = IndexMap::with_hasher(Default::default());
+        for (name, def) in &built_in.directive_definitions {
+            let def = match schema.directive_definitions.get(name) {
+                Some(redefined) if !redefined.is_built_in() => redefined,
+                _ => def,
+            };
+            directives.insert(name.clone(), def.clone());
+        }
+        for (name, def) in &schema.directive_definitions {
+            if !def.is_built_in() {
+                directives
+                    .entry(name.clone())
+                    .or_insert_with(|| def.clone());
+            }
+        }
+        schema.directive_definitions = directives;
+        for (name, def) in &built_in.types {
+            if let Some(ty) = schema.types.get_mut(name) {
+                if ty.is_built_in() {
+                    *ty = def.clone()
+                }
+            }
+        }
+        self
+    }
+
     /// Configure the builder so that “orphan” schema extensions and type extensions
     /// (without a corresponding definition) are “adopted”:
     /// accepted as if extending an empty definition instead of being rejected as errors.
@@ -76,6 +135,41 @@ impl SchemaBuilder {
         self
     }
 
+    /// Configure the builder so that when a non-repeatable directive is applied more than once
+    /// with the same arguments to the same schema element (for example across type extensions,
+    /// or in a supergraph where each subgraph contributed a copy), only the first application
+    /// is kept instead of being rejected by validation.
+    ///
+    /// See [`DirectiveList::dedup_non_repeatable`].
+    pub fn dedup_non_repeatable_directives(mut self) -> Self {
+        self.dedup_directives = true;
+        self
+    }
+
+    /// Configure the builder so that when a schema, directive, or type definition collides
+    /// with an earlier definition of the same name, the later definition is kept
+    /// in [`DefinitionCollisions`] instead of being dropped.
+    ///
+    /// Collisions are still reported as errors,
+    /// and the resulting [`Schema`] still contains only the first definition of each name.
+    /// The other ones are available from [`WithErrors::collisions`]
+    /// so that tools like IDEs can provide hover or go-to-definition on every definition
+    /// of a schema that is still being edited.
+    pub fn tolerant(mut self) -> Self {
+        self.tolerant = true;
+        self
+    }
+
+    /// Configure whether the built schema keeps definitions in the order they were added
+    /// (see [ordering][Schema#ordering]), which is the default.
+    /// With `false`, types, directive definitions, fields, input fields, and enum values
+    /// are sorted by name with [`Schema::sort_by_name`] after building,
+    /// so that the order does not depend on how definitions are split across source files.
+    pub fn preserve_source_order(mut self, value: bool) -> Self {
+        self.preserve_source_order = value;
+        self
+    }
+
     /// Parse an input file with the default configuration as an additional input for this schema.
     ///
     /// Create a [`Parser`] to use different parser configuration.
@@ -109,6 +203,7 @@ impl SchemaBuilder {
         executable_definitions_are_errors: bool,
     ) {
         for definition in &document.definitions {
+            let definition = &without_leading_comments(definition);
             macro_rules! type_definition {
                 ($def: ident, $Type: ident, is_scalar = $is_scalar: literal) => {
                     match self.schema.types.entry($def.name.clone()) {
@@ -138,6 +233,18 @@ impl SchemaBuilder {
                                     },
                                 )
                             }
+                            if self.tolerant {
+                                // Errors within the duplicate definition are not reported,
+                                // so that the error list is the same as without tolerant mode
+                                let mut ignored_errors = DiagnosticList::new(Default::default());
+                                let duplicate =
+                                    $Type::from_ast(&mut ignored_errors, $def, Vec::new());
+                                self.collisions
+                                    .types
+                                    .entry($def.name.clone())
+                                    .or_default()
+                                    .push(duplicate.into());
+                            }
                         }
                     }
                 };
@@ -173,12 +280,20 @@ impl SchemaBuilder {
                             SchemaDefinition::from_ast(&mut self.errors, def, orphan_extensions);
                         self.schema_definition = SchemaDefinitionStatus::Found;
                     }
-                    SchemaDefinitionStatus::Found => self.errors.push(
-                        def.location(),
-                        BuildError::SchemaDefinitionCollision {
-                            previous_location: self.schema.schema_definition.location(),
-                        },
-                    ),
+                    SchemaDefinitionStatus::Found => {
+                        self.errors.push(
+                            def.location(),
+                            BuildError::SchemaDefinitionCollision {
+                                previous_location: self.schema.schema_definition.location(),
+                            },
+                        );
+                        if self.tolerant {
+                            let mut ignored_errors = DiagnosticList::new(Default::default());
+                            let duplicate =
+                                SchemaDefinition::from_ast(&mut ignored_errors, def, &[]);
+                            self.collisions.schema_definitions.push(duplicate)
+                        }
+                    }
                 },
                 ast::Definition::DirectiveDefinition(def) => {
                     match self.schema.directive_definitions.entry(def.name.clone()) {
@@ -200,7 +315,14 @@ impl SchemaBuilder {
                                         previous_location: previous.name.location(),
                                         name: def.name.clone(),
                                     },
-                                )
+                                );
+                                if self.tolerant {
+                                    self.collisions
+                                        .directive_definitions
+                                        .entry(def.name.clone())
+                                        .or_default()
+                                        .push(def.clone())
+                                }
                             }
                         }
                     }
@@ -264,6 +386,10 @@ impl SchemaBuilder {
     pub(crate) fn build_inner(self) -> (Schema, DiagnosticList) {
         let SchemaBuilder {
             adopt_orphan_extensions,
+            dedup_directives,
+            tolerant,
+            preserve_source_order,
+            collisions,
             mut schema,
             schema_definition,
             orphan_type_extensions,
@@ -323,10 +449,223 @@ impl SchemaBuilder {
                 }
             }
         }
+        if dedup_directives {
+            dedup_schema_directives(&mut schema)
+        }
+        if !preserve_source_order {
+            schema.sort_by_name()
+        }
+        if tolerant && !collisions.is_empty() {
+            errors.collisions = Some(Box::new(collisions))
+        }
         (schema, errors)
     }
 }
 
+/// Definitions that were not added to a schema
+/// because an earlier definition had the same name,
+/// collected when [`SchemaBuilder::tolerant`] is enabled.
+///
+/// Each of these definitions is converted on its own:
+/// type extensions are only applied to the definition that was kept in the schema.
+#[derive(Debug, Clone, Default)]
+pub struct DefinitionCollisions {
+    /// `schema` definitions after the first one
+    pub schema_definitions: Vec<Node<SchemaDefinition>>,
+    /// Directive definitions whose name was already defined, keyed by name
+    pub directive_definitions: IndexMap<Name, Vec<Node<DirectiveDefinition>>>,
+    /// Type definitions whose name was already defined, keyed by name
+    pub types: IndexMap<Name, Vec<ExtendedType>>,
+}
+
+impl DefinitionCollisions {
+    /// Returns whether no collision was recorded
+    pub fn is_empty(&self) -> bool {
+        self.schema_definitions.is_empty()
+            && self.directive_definitions.is_empty()
+            && self.types.is_empty()
+    }
+}
+
+impl WithErrors<Schema> {
+    /// Returns the definitions that collided with an earlier definition of the same name,
+    /// if the schema was built with [`SchemaBuilder::tolerant`] and there were any.
+    ///
+    /// ```
+    /// use apollo_compiler::Schema;
+    ///
+    /// let input = "type Query { a: Int } type Query { b: Int }";
+    /// let result = Schema::builder()
+    ///     .tolerant()
+    ///     .parse(input, "schema.graphql")
+    ///     .build();
+    /// let with_errors = result.unwrap_err();
+    /// let collisions = with_errors.collisions().unwrap();
+    /// assert!(with_errors.partial.types["Query"].as_object().unwrap().fields.contains_key("a"));
+    /// assert!(collisions.types["Query"][0].as_object().unwrap().fields.contains_key("b"));
+    /// ```
+    pub fn collisions(&self) -> Option<&DefinitionCollisions> {
+        self.errors.collisions.as_deref()
+    }
+
+    /// Returns every definition of the type with the given name:
+    /// the one kept in [`partial`][Self::partial] first,
+    /// then the ones that collided with it in [tolerant mode][SchemaBuilder::tolerant].
+    pub fn type_definitions<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = &'a ExtendedType> + 'a {
+        let duplicates = self
+            .collisions()
+            .and_then(|collisions| collisions.types.get(name))
+            .into_iter()
+            .flatten();
+        self.partial.types.get(name).into_iter().chain(duplicates)
+    }
+
+    /// Returns every definition of the directive with the given name:
+    /// the one kept in [`partial`][Self::partial] first,
+    /// then the ones that collided with it in [tolerant mode][SchemaBuilder::tolerant].
+    pub fn directive_definitions<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = &'a Node<DirectiveDefinition>> + 'a {
+        let duplicates = self
+            .collisions()
+            .and_then(|collisions| collisions.directive_definitions.get(name))
+            .into_iter()
+            .flatten();
+        self.partial
+            .directive_definitions
+            .get(name)
+            .into_iter()
+            .chain(duplicates)
+    }
+}
+
+fn dedup_schema_directives(schema: &mut Schema) {
+    use crate::ast::impls::dedup_non_repeatable;
+    let definitions = &schema.directive_definitions;
+    let component_list = |directives: &mut DirectiveList| {
+        if has_duplicate(directives, definitions) {
+            let mut deduped = std::mem::take(directives).into();
+            dedup_non_repeatable(&mut deduped, definitions);
+            *directives = DirectiveList::from(deduped)
+        }
+    };
+    let ast_list = |directives: &mut ast::DirectiveList| {
+        if has_duplicate(directives, definitions) {
+            dedup_non_repeatable(&mut **directives, definitions)
+        }
+    };
+    let input_values = |values: &mut [Node<InputValueDefinition>]| {
+        for value in values {
+            if has_duplicate(&value.directives, definitions) {
+                ast_list(&mut value.make_mut().directives)
+            }
+        }
+    };
+    let field_has_duplicate = |field: &FieldDefinition| {
+        has_duplicate(&field.directives, definitions)
+            || field
+                .arguments
+                .iter()
+                .any(|arg| has_duplicate(&arg.directives, definitions))
+    };
+    let fields = |fields: &mut IndexMap<Name, Component<FieldDefinition>>| {
+        for field in fields.values_mut() {
+            if field_has_duplicate(field) {
+                let field = field.make_mut();
+                ast_list(&mut field.directives);
+                input_values(&mut field.arguments)
+            }
+        }
+    };
+    if has_duplicate(&schema.schema_definition.directives, definitions) {
+        component_list(&mut schema.schema_definition.make_mut().directives)
+    }
+    for ty in schema.types.values_mut() {
+        if ty.is_built_in() {
+            continue;
+        }
+        match ty {
+            ExtendedType::Scalar(ty) => {
+                if has_duplicate(&ty.directives, definitions) {
+                    component_list(&mut ty.make_mut().directives)
+                }
+            }
+            ExtendedType::Object(ty) => {
+                if has_duplicate(&ty.directives, definitions)
+                    || ty.fields.values().any(|field| field_has_duplicate(field))
+                {
+                    let ty = ty.make_mut();
+                    component_list(&mut ty.directives);
+                    fields(&mut ty.fields)
+                }
+            }
+            ExtendedType::Interface(ty) => {
+                if has_duplicate(&ty.directives, definitions)
+                    || ty.fields.values().any(|field| field_has_duplicate(field))
+                {
+                    let ty = ty.make_mut();
+                    component_list(&mut ty.directives);
+                    fields(&mut ty.fields)
+                }
+            }
+            ExtendedType::Union(ty) => {
+                if has_duplicate(&ty.directives, definitions) {
+                    component_list(&mut ty.make_mut().directives)
+                }
+            }
+            ExtendedType::Enum(ty) => {
+                if has_duplicate(&ty.directives, definitions)
+                    || ty
+                        .values
+                        .values()
+                        .any(|value| has_duplicate(&value.directives, definitions))
+                {
+                    let ty = ty.make_mut();
+                    component_list(&mut ty.directives);
+                    for value in ty.values.values_mut() {
+                        if has_duplicate(&value.directives, definitions) {
+                            ast_list(&mut value.make_mut().directives)
+                        }
+                    }
+                }
+            }
+            ExtendedType::InputObject(ty) => {
+                if has_duplicate(&ty.directives, definitions)
+                    || ty
+                        .fields
+                        .values()
+                        .any(|field| has_duplicate(&field.directives, definitions))
+                {
+                    let ty = ty.make_mut();
+                    component_list(&mut ty.directives);
+                    for field in ty.fields.values_mut() {
+                        if has_duplicate(&field.directives, definitions) {
+                            ast_list(&mut field.make_mut().directives)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether `dedup_non_repeatable` would remove anything, to avoid unnecessary `make_mut` clones
+fn has_duplicate<T: PartialEq + AsRef<ast::Directive>>(
+    directives: &[T],
+    definitions: &IndexMap<Name, Node<DirectiveDefinition>>,
+) -> bool {
+    directives.iter().enumerate().any(|(i, directive)| {
+        definitions
+            .get(&directive.as_ref().name)
+            .is_some_and(|def| !def.repeatable)
+            && directives[..i].contains(directive)
+    })
+}
+
 fn add_implicit_root_types(
     schema_def: &mut SchemaDefinition,
     types: &IndexMap<Name, ExtendedType>,
@@ -935,6 +1274,89 @@ impl InputObjectType {
     }
 }
 
+/// Returns `definition` without the leading comments of its nested field, argument,
+/// input field, and enum value definitions, or of itself for a directive definition:
+/// a `Schema` does not keep comments, but does keep these AST nodes.
+///
+/// Nodes without comments are shared, not cloned.
+fn without_leading_comments(definition: &ast::Definition) -> ast::Definition {
+    fn has_comments(values: &[Node<ast::InputValueDefinition>]) -> bool {
+        values
+            .iter()
+            .any(|value| !value.leading_comments.is_empty())
+    }
+    fn strip_input_values(values: &mut [Node<ast::InputValueDefinition>]) {
+        for value in values {
+            if !value.leading_comments.is_empty() {
+                value.make_mut().leading_comments = Vec::new()
+            }
+        }
+    }
+    fn strip_fields(fields: &mut [Node<ast::FieldDefinition>]) {
+        for field in fields {
+            if !field.leading_comments.is_empty() || has_comments(&field.arguments) {
+                let field = field.make_mut();
+                field.leading_comments = Vec::new();
+                strip_input_values(&mut field.arguments)
+            }
+        }
+    }
+    fn strip_enum_values(values: &mut [Node<ast::EnumValueDefinition>]) {
+        for value in values {
+            if !value.leading_comments.is_empty() {
+                value.make_mut().leading_comments = Vec::new()
+            }
+        }
+    }
+    fn fields_have_comments(fields: &[Node<ast::FieldDefinition>]) -> bool {
+        fields
+            .iter()
+            .any(|field| !field.leading_comments.is_empty() || has_comments(&field.arguments))
+    }
+    fn enum_values_have_comments(values: &[Node<ast::EnumValueDefinition>]) -> bool {
+        values
+            .iter()
+            .any(|value| !value.leading_comments.is_empty())
+    }
+
+    let mut definition = definition.clone();
+    match &mut definition {
+        ast::Definition::DirectiveDefinition(def)
+            if !def.leading_comments.is_empty() || has_comments(&def.arguments) =>
+        {
+            let def = def.make_mut();
+            def.leading_comments = Vec::new();
+            strip_input_values(&mut def.arguments)
+        }
+        ast::Definition::ObjectTypeDefinition(def) if fields_have_comments(&def.fields) => {
+            strip_fields(&mut def.make_mut().fields)
+        }
+        ast::Definition::ObjectTypeExtension(def) if fields_have_comments(&def.fields) => {
+            strip_fields(&mut def.make_mut().fields)
+        }
+        ast::Definition::InterfaceTypeDefinition(def) if fields_have_comments(&def.fields) => {
+            strip_fields(&mut def.make_mut().fields)
+        }
+        ast::Definition::InterfaceTypeExtension(def) if fields_have_comments(&def.fields) => {
+            strip_fields(&mut def.make_mut().fields)
+        }
+        ast::Definition::InputObjectTypeDefinition(def) if has_comments(&def.fields) => {
+            strip_input_values(&mut def.make_mut().fields)
+        }
+        ast::Definition::InputObjectTypeExtension(def) if has_comments(&def.fields) => {
+            strip_input_values(&mut def.make_mut().fields)
+        }
+        ast::Definition::EnumTypeDefinition(def) if enum_values_have_comments(&def.values) => {
+            strip_enum_values(&mut def.make_mut().values)
+        }
+        ast::Definition::EnumTypeExtension(def) if enum_values_have_comments(&def.values) => {
+            strip_enum_values(&mut def.make_mut().values)
+        }
+        _ => {}
+    }
+    definition
+}
+
 /// Like `IndexMap::extend`, but does not replace a value if an equivalent key is already in the map.
 ///
 /// On collision, calls `duplicate` with the previous key and the value not inserted
@@ -0,0 +1,437 @@
+//! Configurable lint rules for type system definitions.
+//!
+//! Lints go beyond [validation][crate::Schema::validate]:
+//! they report schemas that are valid but do not follow some policy,
+//! such as naming conventions.
+//! A [`Linter`] runs built-in rules and user-provided [`LintRule`]s in a single walk of a schema.
+//!
+//! # Example
+//!
+//! ```rust
+//! use apollo_compiler::schema::lint::LintElement;
+//! use apollo_compiler::schema::lint::Linter;
+//! use apollo_compiler::Schema;
+//!
+//! let schema = Schema::parse_and_validate(r#"
+//!     type Query { user_by_id(id: ID!): User }
+//!     type User { id: ID! }
+//! "#, "schema.graphql").unwrap();
+//!
+//! let linter = Linter::new().rule_fn("types-need-description", |cx, element| {
+//!     if let LintElement::Type(ty) = element {
+//!         if ty.description().is_none() {
+//!             cx.report(format!("type {} has no description", ty.name()))
+//!         }
+//!     }
+//! });
+//! let diagnostics = linter.lint(&schema);
+//! let codes: Vec<_> = diagnostics.iter().map(|d| d.code.as_str()).collect();
+//! assert_eq!(
+//!     codes,
+//!     ["types-need-description", "field-names-camel-case", "types-need-description"],
+//! );
+//! assert_eq!(diagnostics[1].coordinate.to_string(), "Query.user_by_id");
+//! ```
+
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::diagnostic::CliReport;
+use crate::diagnostic::ToCliReport;
+use crate::parser::SourceSpan;
+use crate::schema::Component;
+use crate::schema::DirectiveDefinition;
+use crate::schema::EnumValueDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::validation::Valid;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// A lint rule, called for every element of a schema other than built-in definitions.
+///
+/// Implement this trait for rules that are configurable or reused,
+/// or use [`Linter::rule_fn`] for a closure.
+pub trait LintRule {
+    /// Identifies this rule in the [`LintDiagnostic`]s it reports,
+    /// and in [`Linter::disable`]. For example `field-names-camel-case`.
+    fn code(&self) -> &str;
+
+    /// Check one element of the schema, calling [`LintContext::report`] for each problem found
+    fn check(&self, cx: &mut LintContext<'_>, element: LintElement<'_>);
+}
+
+/// A schema element visited by a [`Linter`]
+#[derive(Debug, Clone, Copy)]
+pub enum LintElement<'schema> {
+    /// A type definition, including its extensions
+    Type(&'schema ExtendedType),
+    /// A field of an object or interface type
+    Field {
+        ty: &'schema ExtendedType,
+        def: &'schema Component<FieldDefinition>,
+    },
+    /// An argument of a field of an object or interface type
+    FieldArgument {
+        ty: &'schema ExtendedType,
+        field: &'schema Component<FieldDefinition>,
+        def: &'schema Node<InputValueDefinition>,
+    },
+    /// A field of an input object type
+    InputField {
+        ty: &'schema ExtendedType,
+        def: &'schema Component<InputValueDefinition>,
+    },
+    /// A value of an enum type
+    EnumValue {
+        ty: &'schema ExtendedType,
+        def: &'schema Component<EnumValueDefinition>,
+    },
+    /// A directive definition
+    Directive(&'schema Node<DirectiveDefinition>),
+    /// An argument of a directive definition
+    DirectiveArgument {
+        directive: &'schema Node<DirectiveDefinition>,
+        def: &'schema Node<InputValueDefinition>,
+    },
+}
+
+/// Passed to [`LintRule::check`] to access the schema being linted and report problems
+pub struct LintContext<'a> {
+    schema: &'a Valid<Schema>,
+    code: &'a str,
+    element: LintElement<'a>,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+/// A problem reported by a [`LintRule`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    /// The [code][LintRule::code] of the rule that reported this
+    pub code: String,
+    pub message: String,
+    /// The schema element this was reported for
+    pub coordinate: SchemaCoordinate,
+    /// The location of the definition of the element, or the one given to
+    /// [`LintContext::report_at`]
+    pub location: Option<SourceSpan>,
+}
+
+/// Runs built-in and custom lint rules on a schema.
+///
+/// See the [module documentation][self] for an example.
+pub struct Linter {
+    rules: Vec<Box<dyn LintRule + Send + Sync>>,
+}
+
+/// Built-in rule: names of object, interface, union, enum, input object,
+/// and custom scalar types are `PascalCase`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TypeNamesPascalCase;
+
+/// Built-in rule: names of fields, input fields, and arguments are `camelCase`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldNamesCamelCase;
+
+/// Built-in rule: enum values are `UPPER_CASE`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnumValuesUpperCase;
+
+struct FnRule<F> {
+    code: String,
+    check: F,
+}
+
+impl LintElement<'_> {
+    /// Returns the schema coordinate of this element, such as `Query.user(id:)`
+    pub fn coordinate(&self) -> SchemaCoordinate {
+        match *self {
+            LintElement::Type(ty) => TypeCoordinate {
+                ty: ty.name().clone(),
+            }
+            .into(),
+            LintElement::Field { ty, def } => TypeAttributeCoordinate {
+                ty: ty.name().clone(),
+                attribute: def.name.clone(),
+            }
+            .into(),
+            LintElement::FieldArgument { ty, field, def } => FieldArgumentCoordinate {
+                ty: ty.name().clone(),
+                field: field.name.clone(),
+                argument: def.name.clone(),
+            }
+            .into(),
+            LintElement::InputField { ty, def } => TypeAttributeCoordinate {
+                ty: ty.name().clone(),
+                attribute: def.name.clone(),
+            }
+            .into(),
+            LintElement::EnumValue { ty, def } => TypeAttributeCoordinate {
+                ty: ty.name().clone(),
+                attribute: def.value.clone(),
+            }
+            .into(),
+            LintElement::Directive(def) => DirectiveCoordinate {
+                directive: def.name.clone(),
+            }
+            .into(),
+            LintElement::DirectiveArgument { directive, def } => DirectiveArgumentCoordinate {
+                directive: directive.name.clone(),
+                argument: def.name.clone(),
+            }
+            .into(),
+        }
+    }
+
+    /// Returns the source location of the definition of this element
+    pub fn location(&self) -> Option<SourceSpan> {
+        match *self {
+            LintElement::Type(ty) => ty.location(),
+            LintElement::Field { def, .. } => def.location(),
+            LintElement::FieldArgument { def, .. } => def.location(),
+            LintElement::InputField { def, .. } => def.location(),
+            LintElement::EnumValue { def, .. } => def.location(),
+            LintElement::Directive(def) => def.location(),
+            LintElement::DirectiveArgument { def, .. } => def.location(),
+        }
+    }
+}
+
+impl<'a> LintContext<'a> {
+    /// Returns the schema being linted
+    pub fn schema(&self) -> &'a Valid<Schema> {
+        self.schema
+    }
+
+    /// Report a problem with the element being checked, at the location of its definition
+    pub fn report(&mut self, message: impl Into<String>) {
+        self.report_at(self.element.location(), message)
+    }
+
+    /// Report a problem with the element being checked, at a more specific location
+    /// such as that of a directive application
+    pub fn report_at(&mut self, location: Option<SourceSpan>, message: impl Into<String>) {
+        self.diagnostics.push(LintDiagnostic {
+            code: self.code.to_owned(),
+            message: message.into(),
+            coordinate: self.element.coordinate(),
+            location,
+        })
+    }
+}
+
+impl Linter {
+    /// Create a linter with the built-in rules:
+    /// [`TypeNamesPascalCase`], [`FieldNamesCamelCase`], and [`EnumValuesUpperCase`]
+    pub fn new() -> Self {
+        Self::empty()
+            .rule(TypeNamesPascalCase)
+            .rule(FieldNamesCamelCase)
+            .rule(EnumValuesUpperCase)
+    }
+
+    /// Create a linter without any rule
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule. Rules run in the order they are added.
+    pub fn rule(mut self, rule: impl LintRule + Send + Sync + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Add a rule defined by a closure
+    pub fn rule_fn(
+        self,
+        code: impl Into<String>,
+        check: impl Fn(&mut LintContext<'_>, LintElement<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.rule(FnRule {
+            code: code.into(),
+            check,
+        })
+    }
+
+    /// Remove rules with the given code, such as a built-in rule
+    pub fn disable(mut self, code: &str) -> Self {
+        self.rules.retain(|rule| rule.code() != code);
+        self
+    }
+
+    /// Returns the codes of rules in this linter
+    pub fn codes(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.code())
+    }
+
+    /// Run every rule on every element of the schema.
+    ///
+    /// Built-in types and directive definitions are skipped.
+    /// Elements are visited in schema order, a type before its fields
+    /// and a field before its arguments.
+    /// For each element, rules run in the order they were added.
+    pub fn lint(&self, schema: &Valid<Schema>) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut visit = |element: LintElement<'_>| {
+            for rule in &self.rules {
+                let mut cx = LintContext {
+                    schema,
+                    code: rule.code(),
+                    element,
+                    diagnostics: std::mem::take(&mut diagnostics),
+                };
+                rule.check(&mut cx, element);
+                diagnostics = cx.diagnostics;
+            }
+        };
+        for ty in schema.types.values() {
+            if ty.is_built_in() {
+                continue;
+            }
+            visit(LintElement::Type(ty));
+            match ty {
+                ExtendedType::Object(def) => {
+                    for field in def.fields.values() {
+                        visit_field(&mut visit, ty, field)
+                    }
+                }
+                ExtendedType::Interface(def) => {
+                    for field in def.fields.values() {
+                        visit_field(&mut visit, ty, field)
+                    }
+                }
+                ExtendedType::InputObject(def) => {
+                    for def in def.fields.values() {
+                        visit(LintElement::InputField { ty, def })
+                    }
+                }
+                ExtendedType::Enum(def) => {
+                    for def in def.values.values() {
+                        visit(LintElement::EnumValue { ty, def })
+                    }
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+            }
+        }
+        for directive in schema.directive_definitions.values() {
+            if directive.is_built_in() {
+                continue;
+            }
+            visit(LintElement::Directive(directive));
+            for def in &directive.arguments {
+                visit(LintElement::DirectiveArgument { directive, def })
+            }
+        }
+        diagnostics
+    }
+}
+
+fn visit_field<'schema>(
+    visit: &mut impl FnMut(LintElement<'schema>),
+    ty: &'schema ExtendedType,
+    field: &'schema Component<FieldDefinition>,
+) {
+    visit(LintElement::Field { ty, def: field });
+    for def in &field.arguments {
+        visit(LintElement::FieldArgument { ty, field, def })
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.codes()).finish()
+    }
+}
+
+impl<F> LintRule for FnRule<F>
+where
+    F: Fn(&mut LintContext<'_>, LintElement<'_>),
+{
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn check(&self, cx: &mut LintContext<'_>, element: LintElement<'_>) {
+        (self.check)(cx, element)
+    }
+}
+
+impl LintRule for TypeNamesPascalCase {
+    fn code(&self) -> &str {
+        "type-names-pascal-case"
+    }
+
+    fn check(&self, cx: &mut LintContext<'_>, element: LintElement<'_>) {
+        if let LintElement::Type(ty) = element {
+            let name = ty.name();
+            if !name.starts_with(|c: char| c.is_ascii_uppercase()) || name.contains('_') {
+                cx.report(format!("type name `{name}` should be PascalCase"))
+            }
+        }
+    }
+}
+
+impl LintRule for FieldNamesCamelCase {
+    fn code(&self) -> &str {
+        "field-names-camel-case"
+    }
+
+    fn check(&self, cx: &mut LintContext<'_>, element: LintElement<'_>) {
+        let (kind, name) = match element {
+            LintElement::Field { def, .. } => ("field", &def.name),
+            LintElement::InputField { def, .. } => ("input field", &def.name),
+            LintElement::FieldArgument { def, .. } | LintElement::DirectiveArgument { def, .. } => {
+                ("argument", &def.name)
+            }
+            _ => return,
+        };
+        if !name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('_') {
+            cx.report(format!("{kind} name `{name}` should be camelCase"))
+        }
+    }
+}
+
+impl LintRule for EnumValuesUpperCase {
+    fn code(&self) -> &str {
+        "enum-values-upper-case"
+    }
+
+    fn check(&self, cx: &mut LintContext<'_>, element: LintElement<'_>) {
+        if let LintElement::EnumValue { def, .. } = element {
+            let value = &def.value;
+            if value.chars().any(|c| c.is_ascii_lowercase()) {
+                cx.report(format!("enum value `{value}` should be UPPER_CASE"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.message, self.code)
+    }
+}
+
+impl ToCliReport for LintDiagnostic {
+    fn location(&self) -> Option<SourceSpan> {
+        self.location
+    }
+
+    fn report(&self, report: &mut CliReport<'_>) {
+        report.with_label_opt(
+            self.location,
+            format!("`{}` is defined here", self.coordinate),
+        )
+    }
+}
@@ -45,9 +45,17 @@
 //! and also implements `Display` and `ToString`.
 
 use crate::ast;
+use crate::ast::directive_index::DirectiveIndex;
 use crate::collections::HashMap;
 use crate::collections::IndexMap;
 use crate::collections::IndexSet;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
 use crate::name;
 use crate::parser::FileId;
 use crate::parser::Parser;
@@ -61,16 +69,60 @@ use crate::Node;
 use std::path::Path;
 use std::sync::OnceLock;
 
+pub(crate) mod canonical;
+mod capabilities;
+mod compare;
 mod component;
+mod directive_apply;
+mod directive_extract;
 mod from_ast;
+pub mod lint;
+mod name_matching;
+mod ordering;
+mod origins;
+mod propagate;
 mod serialize;
+mod service;
+mod spec_edition;
+mod split;
+pub mod sunset;
+mod tables;
 pub(crate) mod validation;
 
+pub use self::capabilities::SchemaCapabilities;
+pub use self::compare::SchemaComparison;
 pub use self::component::Component;
 pub use self::component::ComponentName;
 pub use self::component::ComponentOrigin;
 pub use self::component::ExtensionId;
+pub use self::directive_apply::DirectiveApplicationError;
+pub use self::directive_extract::DirectiveArguments;
+pub use self::directive_extract::DirectiveExtractionError;
+pub use self::directive_extract::FromArgumentValue;
+pub use self::directive_extract::FromDirective;
+pub use self::from_ast::DefinitionCollisions;
 pub use self::from_ast::SchemaBuilder;
+pub use self::name_matching::NameMatch;
+pub use self::name_matching::NameMatching;
+pub use self::name_matching::Rename;
+pub use self::name_matching::Resolved;
+pub use self::origins::DefinitionOrigin;
+pub use self::origins::SchemaOrigins;
+pub use self::propagate::propagate_interface_fields;
+pub use self::propagate::PropagatedField;
+pub use self::propagate::PropagatedInterfaceFields;
+pub use self::service::CoordinateMetadata;
+pub use self::service::ServiceDescription;
+pub use self::spec_edition::SpecEdition;
+pub use self::split::split_by;
+pub use self::split::split_by_type;
+pub use self::split::CrossReference;
+pub use self::split::SchemaSplit;
+pub use self::tables::ArgumentView;
+pub use self::tables::FieldView;
+pub use self::tables::SchemaTables;
+pub use self::tables::TypeKind;
+pub use self::tables::TypeView;
 pub use crate::ast::Directive;
 pub use crate::ast::DirectiveDefinition;
 pub use crate::ast::DirectiveLocation;
@@ -82,6 +134,21 @@ pub use crate::ast::Type;
 pub use crate::ast::Value;
 
 /// High-level representation of a GraphQL type system document a.k.a. schema.
+///
+/// # Ordering
+///
+/// The maps of a `Schema` are in a deterministic order
+/// that depends only on its inputs, not on hashing:
+///
+/// * Built-in directives and types come first, in the order of the GraphQL specification
+/// * Then other definitions in the order they were added to the [`SchemaBuilder`],
+///   which is source order within one file
+/// * Fields, input fields, and enum values are in the order of their type definition,
+///   followed by those of each extension in the order extensions were added
+///
+/// Serialization and introspection follow this order.
+/// Methods like [`sort_types`][Self::sort_types] and [`sort_by_name`][Self::sort_by_name]
+/// change it, for example to get stable output to diff after reorganizing source files.
 #[derive(Clone)]
 pub struct Schema {
     /// Source files, if any, that were parsed to contribute to this schema.
@@ -136,8 +203,13 @@ pub struct SchemaDefinition {
 /// Confusingly, [`ast::DirectiveList`] is also used in other parts of a [`Schema`],
 /// for example for the directives applied to a field definition.
 /// (The field definition as a whole is already a [`Component`] to keep track of its origin.)
+///
+/// [`get`][Self::get] and [`has`][Self::has] use a name index, built on first use.
 #[derive(Clone, Eq, PartialEq, Hash, Default)]
-pub struct DirectiveList(pub Vec<Component<Directive>>);
+pub struct DirectiveList {
+    directives: Vec<Component<Directive>>,
+    index: DirectiveIndex,
+}
 
 /// The definition of a named type, with all information from type extensions folded in.
 ///
@@ -411,6 +483,37 @@ impl Schema {
         errors.into_valid_result(schema)
     }
 
+    /// Build and validate a schema from the JSON response to a [schema introspection] query,
+    /// such as one fetched from a remote server by client tooling.
+    ///
+    /// `json` can be either a full GraphQL response or only its `data` object.
+    /// The query must select the fields of the [standard introspection query],
+    /// as described in [`introspection_response_to_ast`][crate::execution::introspection_response_to_ast].
+    /// Responses from servers using graphql-js before v16 (with `specifiedByUrl`) are also accepted.
+    ///
+    /// ```
+    /// use apollo_compiler::Schema;
+    ///
+    /// let json = r#"{"data": {"__schema": {
+    ///     "queryType": {"name": "Query"},
+    ///     "types": [{
+    ///         "kind": "OBJECT",
+    ///         "name": "Query",
+    ///         "fields": [{"name": "hello", "args": [], "type": {"kind": "SCALAR", "name": "String"}}]
+    ///     }]
+    /// }}}"#;
+    /// let schema = Schema::from_introspection_json(json).unwrap();
+    /// assert!(schema.get_object("Query").unwrap().fields.contains_key("hello"));
+    /// ```
+    ///
+    /// [schema introspection]: https://spec.graphql.org/October2021/#sec-Schema-Introspection
+    /// [standard introspection query]: https://github.com/graphql/graphql-js/blob/v16.9.0/src/utilities/getIntrospectionQuery.ts
+    pub fn from_introspection_json(
+        json: impl AsRef<[u8]>,
+    ) -> Result<Valid<Self>, crate::execution::FromIntrospectionError> {
+        crate::execution::introspection_to_ast::schema_from_introspection_json(json.as_ref())
+    }
+
     /// Returns a new builder for creating a Schema from AST documents,
     /// initialized with built-in directives, built-in scalars, and introspection types
     ///
@@ -625,6 +728,116 @@ impl Schema {
         }
     }
 
+    /// Returns every [schema coordinate][SchemaCoordinate] of this schema
+    /// together with the definition it points to:
+    /// types, then their fields and field arguments, input fields, or enum values,
+    /// followed by directive definitions, each followed by its arguments.
+    ///
+    /// Built-in types and directives are skipped.
+    /// The order is otherwise that of [`types`][Self::types]
+    /// and [`directive_definitions`][Self::directive_definitions] and their members,
+    /// so it is deterministic for a given schema.
+    ///
+    /// ```
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(r#"
+    ///     type Query { user(id: ID!): User }
+    ///     type User { name: String }
+    ///     directive @tag(name: String) on FIELD_DEFINITION
+    /// "#, "schema.graphql").unwrap();
+    /// let coordinates: Vec<String> = schema
+    ///     .coordinates()
+    ///     .map(|(coordinate, _definition)| coordinate.to_string())
+    ///     .collect();
+    /// assert_eq!(
+    ///     coordinates,
+    ///     ["Query", "Query.user", "Query.user(id:)", "User", "User.name", "@tag", "@tag(name:)"],
+    /// );
+    /// ```
+    pub fn coordinates(
+        &self,
+    ) -> impl Iterator<Item = (SchemaCoordinate, SchemaCoordinateLookup<'_>)> {
+        let types = self
+            .types
+            .values()
+            .filter(|ty| !ty.is_built_in())
+            .flat_map(|ty| {
+                let type_name = ty.name();
+                let type_coordinate = TypeCoordinate {
+                    ty: type_name.clone(),
+                };
+                let attribute = |attribute: &Name| TypeAttributeCoordinate {
+                    ty: type_name.clone(),
+                    attribute: attribute.clone(),
+                };
+                let mut coordinates = vec![(
+                    SchemaCoordinate::from(type_coordinate),
+                    SchemaCoordinateLookup::Type(ty),
+                )];
+                let fields = match ty {
+                    ExtendedType::Object(def) => Some(&def.fields),
+                    ExtendedType::Interface(def) => Some(&def.fields),
+                    _ => None,
+                };
+                for field in fields.into_iter().flat_map(|fields| fields.values()) {
+                    coordinates.push((
+                        attribute(&field.name).into(),
+                        SchemaCoordinateLookup::Field(field),
+                    ));
+                    for argument in &field.arguments {
+                        let coordinate = FieldArgumentCoordinate {
+                            ty: type_name.clone(),
+                            field: field.name.clone(),
+                            argument: argument.name.clone(),
+                        };
+                        coordinates.push((
+                            coordinate.into(),
+                            SchemaCoordinateLookup::Argument(argument),
+                        ))
+                    }
+                }
+                if let ExtendedType::InputObject(def) = ty {
+                    for field in def.fields.values() {
+                        coordinates.push((
+                            attribute(&field.name).into(),
+                            SchemaCoordinateLookup::InputField(field),
+                        ))
+                    }
+                }
+                if let ExtendedType::Enum(def) = ty {
+                    for value in def.values.values() {
+                        coordinates.push((
+                            attribute(&value.value).into(),
+                            SchemaCoordinateLookup::EnumValue(value),
+                        ))
+                    }
+                }
+                coordinates
+            });
+        let directives =
+            self.directive_definitions
+                .values()
+                .filter(|def| !def.is_built_in())
+                .flat_map(|def| {
+                    let directive = DirectiveCoordinate {
+                        directive: def.name.clone(),
+                    };
+                    std::iter::once((directive.into(), SchemaCoordinateLookup::Directive(def)))
+                        .chain(def.arguments.iter().map(|argument| {
+                            let coordinate = DirectiveArgumentCoordinate {
+                                directive: def.name.clone(),
+                                argument: argument.name.clone(),
+                            };
+                            (
+                                coordinate.into(),
+                                SchemaCoordinateLookup::Argument(argument),
+                            )
+                        }))
+                });
+        types.chain(directives)
+    }
+
     serialize_method!();
 }
 
@@ -970,7 +1183,10 @@ impl InputObjectType {
 
 impl DirectiveList {
     pub const fn new() -> Self {
-        Self(Vec::new())
+        Self {
+            directives: Vec::new(),
+            index: DirectiveIndex::new(),
+        }
     }
 
     /// Returns an iterator of directives with the given name.
@@ -981,7 +1197,10 @@ impl DirectiveList {
         &'def self,
         name: &'name str,
     ) -> impl Iterator<Item = &'def Component<Directive>> + 'name {
-        self.0.iter().filter(move |dir| dir.name == name)
+        let first = self.first_position(name).unwrap_or(self.directives.len());
+        self.directives[first..]
+            .iter()
+            .filter(move |dir| dir.name == name)
     }
 
     /// Returns the first directive with the given name, if any.
@@ -989,21 +1208,40 @@ impl DirectiveList {
     /// This method is best for non-repeatable directives.
     /// See also [`get_all`][Self::get_all] for repeatable directives.
     pub fn get(&self, name: &str) -> Option<&Component<Directive>> {
-        self.get_all(name).next()
+        Some(&self.directives[self.first_position(name)?])
     }
 
     /// Returns whether there is a directive with the given name
     pub fn has(&self, name: &str) -> bool {
-        self.get(name).is_some()
+        self.first_position(name).is_some()
+    }
+
+    fn first_position(&self, name: &str) -> Option<usize> {
+        self.index
+            .first_position(&self.directives, name, |dir| &dir.name)
     }
 
     pub(crate) fn iter_ast(&self) -> impl Iterator<Item = &Node<ast::Directive>> {
-        self.0.iter().map(|component| &component.node)
+        self.directives.iter().map(|component| &component.node)
     }
 
     /// Accepts either [`Component<Directive>`], [`Node<Directive>`], or [`Directive`].
     pub fn push(&mut self, directive: impl Into<Component<Directive>>) {
-        self.0.push(directive.into());
+        self.index.invalidate();
+        self.directives.push(directive.into());
+    }
+
+    /// Removes applications of a non-repeatable directive that are identical
+    /// (same name and same arguments) to an earlier application in this list.
+    /// The first application is kept, with its extension origin.
+    ///
+    /// See [`ast::DirectiveList::dedup_non_repeatable`].
+    pub fn dedup_non_repeatable(&mut self, schema: &Schema) {
+        crate::ast::impls::dedup_non_repeatable(
+            &mut self.directives,
+            &schema.directive_definitions,
+        );
+        self.index.invalidate();
     }
 
     serialize_method!();
@@ -1011,7 +1249,7 @@ impl DirectiveList {
 
 impl std::fmt::Debug for DirectiveList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+        self.directives.fmt(f)
     }
 }
 
@@ -1019,13 +1257,29 @@ impl std::ops::Deref for DirectiveList {
     type Target = Vec<Component<Directive>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.directives
     }
 }
 
 impl std::ops::DerefMut for DirectiveList {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        self.index.invalidate();
+        &mut self.directives
+    }
+}
+
+impl From<Vec<Component<Directive>>> for DirectiveList {
+    fn from(directives: Vec<Component<Directive>>) -> Self {
+        Self {
+            directives,
+            index: DirectiveIndex::new(),
+        }
+    }
+}
+
+impl From<DirectiveList> for Vec<Component<Directive>> {
+    fn from(list: DirectiveList) -> Self {
+        list.directives
     }
 }
 
@@ -1035,7 +1289,7 @@ impl IntoIterator for DirectiveList {
     type IntoIter = std::vec::IntoIter<Component<Directive>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.directives.into_iter()
     }
 }
 
@@ -1045,7 +1299,7 @@ impl<'a> IntoIterator for &'a DirectiveList {
     type IntoIter = std::slice::Iter<'a, Component<Directive>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter()
+        self.directives.iter()
     }
 }
 
@@ -1055,7 +1309,8 @@ impl<'a> IntoIterator for &'a mut DirectiveList {
     type IntoIter = std::slice::IterMut<'a, Component<Directive>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter_mut()
+        self.index.invalidate();
+        self.directives.iter_mut()
     }
 }
 
@@ -1064,7 +1319,7 @@ where
     D: Into<Component<Directive>>,
 {
     fn from_iter<T: IntoIterator<Item = D>>(iter: T) -> Self {
-        Self(iter.into_iter().map(Into::into).collect())
+        Self::from(iter.into_iter().map(Into::into).collect::<Vec<_>>())
     }
 }
 
@@ -1230,6 +1485,7 @@ impl MetaFieldDefinitions {
             // __typename: String!
             __typename: Component::new(FieldDefinition {
                 description: None,
+                leading_comments: Vec::new(),
                 name: name!("__typename"),
                 arguments: Vec::new(),
                 ty: ty!(String!),
@@ -1238,6 +1494,7 @@ impl MetaFieldDefinitions {
             // __schema: __Schema!
             __schema: Component::new(FieldDefinition {
                 description: None,
+                leading_comments: Vec::new(),
                 name: name!("__schema"),
                 arguments: Vec::new(),
                 ty: ty!(__Schema!),
@@ -1246,9 +1503,11 @@ impl MetaFieldDefinitions {
             // __type(name: String!): __Type
             __type: Component::new(FieldDefinition {
                 description: None,
+                leading_comments: Vec::new(),
                 name: name!("__type"),
                 arguments: vec![InputValueDefinition {
                     description: None,
+                    leading_comments: Vec::new(),
                     name: name!("name"),
                     ty: ty!(String!).into(),
                     default_value: None,
@@ -0,0 +1,289 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::Name;
+use crate::Schema;
+
+/// Opt-in rules to resolve type and field names that do not exactly match a schema,
+/// such as in legacy operations written with different casing conventions
+/// or before a type or field was renamed.
+///
+/// Validation and execution always require exact names. This is a separate lookup layer
+/// for migration tools: each resolved name reports how it was matched,
+/// and [`rename_document`][Self::rename_document] rewrites a document to use schema names.
+///
+/// An exact match always takes precedence, then aliases, then case-insensitive matching.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::ast;
+/// use apollo_compiler::coordinate::TypeAttributeCoordinate;
+/// use apollo_compiler::name;
+/// use apollo_compiler::schema::NameMatch;
+/// use apollo_compiler::schema::NameMatching;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { user(id: ID!): User }
+///     type User { id: ID! displayName: String }
+/// "#, "schema.graphql").unwrap();
+///
+/// let mut matching = NameMatching {
+///     case_insensitive: true,
+///     ..Default::default()
+/// };
+/// matching.field_aliases.insert(
+///     TypeAttributeCoordinate { ty: name!("User"), attribute: name!("userName") },
+///     name!("displayName"),
+/// );
+/// let resolved = matching.resolve_field(&schema, "USER", "userName").unwrap();
+/// assert_eq!(resolved.name, "displayName");
+/// assert_eq!(resolved.kind, NameMatch::Alias);
+///
+/// let legacy = "{ User(id: 1) { ID userName } }";
+/// let mut document = ast::Document::parse(legacy, "legacy.graphql").unwrap();
+/// let renames = matching.rename_document(&schema, &mut document);
+/// assert_eq!(renames.len(), 3);
+/// assert_eq!(
+///     document.to_string(),
+///     "{\n  User: user(id: 1) {\n    ID: id\n    userName: displayName\n  }\n}\n"
+/// );
+/// ExecutableDocument::parse_and_validate(&schema, document.to_string(), "fixed.graphql")
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameMatching {
+    /// Match names that differ from a schema name only in ASCII letter case,
+    /// unless several schema names match
+    pub case_insensitive: bool,
+    /// Legacy type names, mapped to the name of the type in the schema
+    pub type_aliases: HashMap<Name, Name>,
+    /// Legacy field names, as `SchemaTypeName.legacyFieldName` coordinates,
+    /// mapped to the name of the field in the schema
+    pub field_aliases: HashMap<TypeAttributeCoordinate, Name>,
+}
+
+/// How a name was resolved by [`NameMatching`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameMatch {
+    /// The name is exactly a schema name
+    Exact,
+    /// The name is an alias in [`NameMatching::type_aliases`] or [`NameMatching::field_aliases`]
+    Alias,
+    /// The name matches a schema name when ignoring letter case
+    CaseInsensitive,
+}
+
+/// A type or field resolved by [`NameMatching`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved<'schema, T> {
+    /// The name of the type or field in the schema
+    pub name: Name,
+    /// How the name was resolved
+    pub kind: NameMatch,
+    pub definition: &'schema T,
+}
+
+/// A name changed by [`NameMatching::rename_document`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    /// The name as it was in the document, with its source location if it was parsed
+    pub from: Name,
+    /// The name in the schema
+    pub to: Name,
+    /// How the name was resolved
+    pub kind: NameMatch,
+}
+
+impl NameMatching {
+    /// Resolve a type name
+    pub fn resolve_type<'schema>(
+        &self,
+        schema: &'schema Schema,
+        name: &str,
+    ) -> Option<Resolved<'schema, ExtendedType>> {
+        let (name, kind) = if schema.types.contains_key(name) {
+            (name, NameMatch::Exact)
+        } else if let Some(alias) = self.type_aliases.get(name) {
+            (alias.as_str(), NameMatch::Alias)
+        } else if self.case_insensitive {
+            (
+                case_insensitive(schema.types.keys(), name)?,
+                NameMatch::CaseInsensitive,
+            )
+        } else {
+            return None;
+        };
+        let (name, definition) = schema.types.get_key_value(name)?;
+        Some(Resolved {
+            name: name.clone(),
+            kind,
+            definition,
+        })
+    }
+
+    /// Resolve a field name, including meta-fields such as `__typename`.
+    ///
+    /// The type name is resolved first, and the field is looked up in that type.
+    /// A field that was resolved exactly in a type that was not is reported
+    /// with the kind of the type match.
+    pub fn resolve_field<'schema>(
+        &self,
+        schema: &'schema Schema,
+        type_name: &str,
+        field_name: &str,
+    ) -> Option<Resolved<'schema, Component<FieldDefinition>>> {
+        let ty = self.resolve_type(schema, type_name)?;
+        let fields = match ty.definition {
+            ExtendedType::Object(def) => &def.fields,
+            ExtendedType::Interface(def) => &def.fields,
+            _ => return None,
+        };
+        let (name, kind) = if let Ok(def) = schema.type_field(&ty.name, field_name) {
+            return Some(Resolved {
+                name: def.name.clone(),
+                kind: ty.kind,
+                definition: def,
+            });
+        } else if let Some(alias) = self.field_aliases.get(&TypeAttributeCoordinate {
+            ty: ty.name.clone(),
+            attribute: Name::new(field_name).ok()?,
+        }) {
+            (alias.as_str(), NameMatch::Alias)
+        } else if self.case_insensitive {
+            (
+                case_insensitive(fields.keys(), field_name)?,
+                NameMatch::CaseInsensitive,
+            )
+        } else {
+            return None;
+        };
+        let (name, definition) = fields.get_key_value(name)?;
+        Some(Resolved {
+            name: name.clone(),
+            kind,
+            definition,
+        })
+    }
+
+    /// Rewrite type and field names in the operations and fragments of `document`
+    /// to the names they resolve to in `schema`, and return what was renamed.
+    ///
+    /// Renamed fields without an alias get their old name as an alias,
+    /// so that responses keep the same shape.
+    /// Names that do not resolve are kept, for validation to report.
+    pub fn rename_document(&self, schema: &Schema, document: &mut ast::Document) -> Vec<Rename> {
+        let mut renames = Vec::new();
+        for definition in &mut document.definitions {
+            match definition {
+                ast::Definition::OperationDefinition(operation) => {
+                    let operation = operation.make_mut();
+                    for variable in &mut operation.variables {
+                        let variable = variable.make_mut();
+                        self.rename_type_reference(schema, variable.ty.make_mut(), &mut renames);
+                    }
+                    if let Some(root) = schema.root_operation(operation.operation_type) {
+                        self.rename_selections(
+                            schema,
+                            root,
+                            &mut operation.selection_set,
+                            &mut renames,
+                        )
+                    }
+                }
+                ast::Definition::FragmentDefinition(fragment) => {
+                    let fragment = fragment.make_mut();
+                    let ty = self.rename_type(schema, &mut fragment.type_condition, &mut renames);
+                    self.rename_selections(schema, &ty, &mut fragment.selection_set, &mut renames)
+                }
+                _ => {}
+            }
+        }
+        renames
+    }
+
+    /// Renames `name` if it resolves to a different type name, and returns the resolved name
+    fn rename_type(&self, schema: &Schema, name: &mut Name, renames: &mut Vec<Rename>) -> Name {
+        if let Some(resolved) = self.resolve_type(schema, name) {
+            if resolved.kind != NameMatch::Exact {
+                let from = std::mem::replace(name, resolved.name.clone());
+                renames.push(Rename {
+                    from,
+                    to: resolved.name.clone(),
+                    kind: resolved.kind,
+                });
+            }
+        }
+        name.clone()
+    }
+
+    fn rename_type_reference(
+        &self,
+        schema: &Schema,
+        ty: &mut ast::Type,
+        renames: &mut Vec<Rename>,
+    ) {
+        match ty {
+            ast::Type::Named(name) | ast::Type::NonNullNamed(name) => {
+                self.rename_type(schema, name, renames);
+            }
+            ast::Type::List(inner) | ast::Type::NonNullList(inner) => {
+                self.rename_type_reference(schema, inner, renames)
+            }
+        }
+    }
+
+    fn rename_selections(
+        &self,
+        schema: &Schema,
+        parent_type: &Name,
+        selections: &mut [ast::Selection],
+        renames: &mut Vec<Rename>,
+    ) {
+        for selection in selections {
+            match selection {
+                ast::Selection::Field(field) => {
+                    let Some(resolved) = self.resolve_field(schema, parent_type, &field.name)
+                    else {
+                        continue;
+                    };
+                    let field = field.make_mut();
+                    if resolved.name != field.name {
+                        let from = std::mem::replace(&mut field.name, resolved.name.clone());
+                        field.alias.get_or_insert_with(|| from.clone());
+                        renames.push(Rename {
+                            from,
+                            to: resolved.name.clone(),
+                            kind: resolved.kind,
+                        });
+                    }
+                    let ty = resolved.definition.ty.inner_named_type().clone();
+                    self.rename_selections(schema, &ty, &mut field.selection_set, renames)
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    let inline = inline.make_mut();
+                    let ty = match &mut inline.type_condition {
+                        Some(type_condition) => self.rename_type(schema, type_condition, renames),
+                        None => parent_type.clone(),
+                    };
+                    self.rename_selections(schema, &ty, &mut inline.selection_set, renames)
+                }
+                ast::Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+}
+
+/// Returns the only name in `names` equal to `name` when ignoring ASCII case
+fn case_insensitive<'a>(names: impl Iterator<Item = &'a Name>, name: &str) -> Option<&'a str> {
+    let mut matches = names.filter(|candidate| candidate.eq_ignore_ascii_case(name));
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => Some(only.as_str()),
+        _ => None,
+    }
+}
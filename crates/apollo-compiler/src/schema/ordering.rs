@@ -0,0 +1,83 @@
+use crate::schema::DirectiveDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::NamedType;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::cmp::Ordering;
+
+impl Schema {
+    /// Sort [`types`][Self::types] with a comparator function
+    /// given the name and definition of two types
+    pub fn sort_types(
+        &mut self,
+        compare: impl FnMut(&NamedType, &ExtendedType, &NamedType, &ExtendedType) -> Ordering,
+    ) {
+        self.types.sort_by(compare)
+    }
+
+    /// Sort [`directive_definitions`][Self::directive_definitions] with a comparator function
+    /// given the name and definition of two directives
+    pub fn sort_directive_definitions(
+        &mut self,
+        compare: impl FnMut(
+            &Name,
+            &Node<DirectiveDefinition>,
+            &Name,
+            &Node<DirectiveDefinition>,
+        ) -> Ordering,
+    ) {
+        self.directive_definitions.sort_by(compare)
+    }
+
+    /// Sort the fields of every object and interface type and the input fields
+    /// of every input object type, with a comparator function given the name of
+    /// the parent type and the names of two of its fields
+    pub fn sort_fields(&mut self, mut compare: impl FnMut(&NamedType, &Name, &Name) -> Ordering) {
+        for (type_name, ty) in &mut self.types {
+            match ty {
+                ExtendedType::Object(def) => def
+                    .make_mut()
+                    .fields
+                    .sort_by(|a, _, b, _| compare(type_name, a, b)),
+                ExtendedType::Interface(def) => def
+                    .make_mut()
+                    .fields
+                    .sort_by(|a, _, b, _| compare(type_name, a, b)),
+                ExtendedType::InputObject(def) => def
+                    .make_mut()
+                    .fields
+                    .sort_by(|a, _, b, _| compare(type_name, a, b)),
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) | ExtendedType::Enum(_) => {}
+            }
+        }
+    }
+
+    /// Sort the values of every enum type, with a comparator function given the name of
+    /// the enum type and two of its values
+    pub fn sort_enum_values(
+        &mut self,
+        mut compare: impl FnMut(&NamedType, &Name, &Name) -> Ordering,
+    ) {
+        for (type_name, ty) in &mut self.types {
+            if let ExtendedType::Enum(def) = ty {
+                def.make_mut()
+                    .values
+                    .sort_by(|a, _, b, _| compare(type_name, a, b))
+            }
+        }
+    }
+
+    /// Sort types, directive definitions, fields, input fields, and enum values
+    /// by name. Built-in definitions are not kept first.
+    ///
+    /// This is what [`SchemaBuilder::preserve_source_order(false)`] does after building.
+    ///
+    /// [`SchemaBuilder::preserve_source_order(false)`]: crate::schema::SchemaBuilder::preserve_source_order
+    pub fn sort_by_name(&mut self) {
+        self.sort_types(|a, _, b, _| a.cmp(b));
+        self.sort_directive_definitions(|a, _, b, _| a.cmp(b));
+        self.sort_fields(|_, a, b| a.cmp(b));
+        self.sort_enum_values(|_, a, b| a.cmp(b));
+    }
+}
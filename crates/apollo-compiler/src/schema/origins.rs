@@ -0,0 +1,165 @@
+use crate::ast;
+use crate::ast::serialize::Serialize;
+use crate::parser::LineColumn;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::Schema;
+use std::path::PathBuf;
+
+/// A machine-readable sidecar for a serialized [`Schema`], recording where each of its
+/// top-level definitions and extensions was originally parsed.
+///
+/// A schema built from multiple files serializes to a single document,
+/// where an element merged from several inputs (for example by composition)
+/// no longer says which file or which `extend` block it came from.
+/// This sidecar keeps that information next to the serialized text,
+/// for example as a JSON file with `serde_json`.
+/// After parsing the serialized text again, [`find`][Self::find] re-imports it.
+///
+/// Created by [`Serialize::to_string_with_origins`].
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::parser::LineColumn;
+/// use apollo_compiler::schema::SchemaOrigins;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::builder()
+///     .parse("type Query { users: [User] }\ntype User { id: ID! }", "users.graphql")
+///     .parse("extend type User {\n  reviews: [String]\n}", "reviews.graphql")
+///     .build()
+///     .unwrap();
+/// let (text, origins) = schema.serialize().to_string_with_origins();
+/// let json = serde_json::to_string(&origins).unwrap();
+///
+/// // Later, possibly in another process
+/// let merged = Schema::parse(text, "merged.graphql").unwrap();
+/// let origins: SchemaOrigins = serde_json::from_str(&json).unwrap();
+/// let reviews = &merged.types["User"].as_object().unwrap().fields["reviews"];
+/// let origin = origins.find(&merged, reviews.location().unwrap()).unwrap();
+/// assert_eq!(origin.definition, "extend type User");
+/// assert_eq!(origin.path.as_ref().unwrap().to_str(), Some("reviews.graphql"));
+/// assert_eq!(origin.location, Some(LineColumn { line: 1, column: 1 }));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SchemaOrigins {
+    /// One entry for each top-level definition or extension, in serialization order
+    pub definitions: Vec<DefinitionOrigin>,
+}
+
+/// Where a top-level definition or extension of a serialized schema was originally parsed.
+/// Part of [`SchemaOrigins`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DefinitionOrigin {
+    /// What is defined, such as `type User`, `extend type User`, `directive @key`,
+    /// or `extend schema`
+    pub definition: String,
+    /// The path of the file it was parsed from, as given to the parser.
+    /// `None` if it was created programmatically.
+    pub path: Option<PathBuf>,
+    /// The line and column where it starts in that file
+    pub location: Option<LineColumn>,
+}
+
+impl SchemaOrigins {
+    /// Returns the origin of the top-level definition or extension that contains `location`,
+    /// given a `schema` parsed from text serialized together with this sidecar.
+    ///
+    /// `location` can be that of any node of the schema, such as a field definition
+    /// or the [`ExtensionId`][crate::schema::ExtensionId] of a component.
+    ///
+    /// Returns `None` if `location` is not within a top-level definition, or if this sidecar
+    /// does not correspond to `schema` (for example because it was modified after parsing).
+    pub fn find(&self, schema: &Schema, location: SourceSpan) -> Option<&DefinitionOrigin> {
+        let (definition, origin) = schema.to_ast().zip(&self.definitions).find(|(def, _)| {
+            def.location().is_some_and(|span| {
+                span.file_id() == location.file_id()
+                    && span.offset() <= location.offset()
+                    && location.end_offset() <= span.end_offset()
+            })
+        })?;
+        (header(&definition) == origin.definition).then_some(origin)
+    }
+}
+
+impl DefinitionOrigin {
+    fn new(definition: &ast::Definition, sources: &SourceMap) -> Self {
+        let location = definition.location();
+        Self {
+            definition: header(definition),
+            path: location
+                .and_then(|span| sources.get(&span.file_id()))
+                .map(|file| file.path().to_owned()),
+            location: location.and_then(|span| span.line_column(sources)),
+        }
+    }
+}
+
+impl Serialize<'_, Schema> {
+    /// Write a comment before each top-level definition and extension
+    /// with the path, line, and column where it was originally parsed, such as
+    /// `# from: reviews.graphql:1:1`.
+    ///
+    /// Comments are not written when indentation is disabled.
+    /// For a machine-readable equivalent, see [`to_string_with_origins`][Self::to_string_with_origins].
+    pub fn origin_comments(mut self) -> Self {
+        self.config.origin_comments = true;
+        self
+    }
+
+    /// Serialize to a string, and return it together with the origin
+    /// of each top-level definition and extension.
+    pub fn to_string_with_origins(&self) -> (String, SchemaOrigins) {
+        let text = self.to_string();
+        let origins = SchemaOrigins {
+            definitions: self
+                .node
+                .to_ast()
+                .map(|def| DefinitionOrigin::new(&def, &self.node.sources))
+                .collect(),
+        };
+        (text, origins)
+    }
+}
+
+impl Schema {
+    /// The comment written by [`Serialize::origin_comments`], without the `#` prefix
+    pub(crate) fn origin_comment(&self, definition: &ast::Definition) -> Option<String> {
+        let DefinitionOrigin { path, location, .. } =
+            DefinitionOrigin::new(definition, &self.sources);
+        let LineColumn { line, column } = location?;
+        Some(format!(" from: {}:{line}:{column}", path?.display()))
+    }
+}
+
+fn header(definition: &ast::Definition) -> String {
+    let keyword = match definition {
+        ast::Definition::SchemaDefinition(_) | ast::Definition::SchemaExtension(_) => "schema",
+        ast::Definition::DirectiveDefinition(_) => "directive @",
+        ast::Definition::ScalarTypeDefinition(_) | ast::Definition::ScalarTypeExtension(_) => {
+            "scalar "
+        }
+        ast::Definition::ObjectTypeDefinition(_) | ast::Definition::ObjectTypeExtension(_) => {
+            "type "
+        }
+        ast::Definition::InterfaceTypeDefinition(_)
+        | ast::Definition::InterfaceTypeExtension(_) => "interface ",
+        ast::Definition::UnionTypeDefinition(_) | ast::Definition::UnionTypeExtension(_) => {
+            "union "
+        }
+        ast::Definition::EnumTypeDefinition(_) | ast::Definition::EnumTypeExtension(_) => "enum ",
+        ast::Definition::InputObjectTypeDefinition(_)
+        | ast::Definition::InputObjectTypeExtension(_) => "input ",
+        ast::Definition::OperationDefinition(_) | ast::Definition::FragmentDefinition(_) => {
+            unreachable!("executable definition in a schema")
+        }
+    };
+    let extend = if definition.is_extension_definition() {
+        "extend "
+    } else {
+        ""
+    };
+    let name = definition.name().map(|name| name.as_str()).unwrap_or("");
+    format!("{extend}{keyword}{name}")
+}
@@ -0,0 +1,142 @@
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::Name;
+use crate::Schema;
+
+/// Returned by [`propagate_interface_fields`]:
+/// which field definitions were copied from an interface into an implementing type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropagatedInterfaceFields {
+    /// In the order fields were added
+    pub added: Vec<PropagatedField>,
+}
+
+/// A field definition copied by [`propagate_interface_fields`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropagatedField {
+    /// The object or interface type that received the field
+    pub type_name: Name,
+    /// The name of the copied field
+    pub field_name: Name,
+    /// The interface the field definition was copied from
+    pub interface_name: Name,
+}
+
+/// Copies field definitions that implementing types are missing from the interfaces they implement.
+///
+/// The GraphQL spec requires object and interface types to repeat every field
+/// of every interface they implement.
+/// For schemas with many implementers this can be an opt-in normalization step
+/// that runs before [validation][Schema::validate]:
+/// a field definition that is declared on an interface but not on an implementing type
+/// is copied (including its description, arguments, and directives) into that type.
+///
+/// Interfaces are followed transitively,
+/// so an interface that implements another interface first inherits its fields.
+/// Fields that an implementing type already defines are never modified,
+/// even if their definition differs from that of the interface.
+///
+/// Copied fields keep the source location of the interface field they come from,
+/// and are marked as part of the main type definition (not a type extension).
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::Schema;
+/// use apollo_compiler::schema::propagate_interface_fields;
+///
+/// let mut schema = Schema::parse(r#"
+///     type Query { node: Node }
+///     interface Node { "Unique identifier" id: ID! }
+///     type User implements Node { name: String }
+/// "#, "schema.graphql").unwrap();
+///
+/// let report = propagate_interface_fields(&mut schema);
+/// assert_eq!(report.added.len(), 1);
+/// assert_eq!(report.added[0].type_name, "User");
+/// assert_eq!(report.added[0].field_name, "id");
+/// assert!(schema.get_object("User").unwrap().fields.contains_key("id"));
+/// schema.validate().unwrap();
+/// ```
+pub fn propagate_interface_fields(schema: &mut Schema) -> PropagatedInterfaceFields {
+    let interfaces: HashMap<Name, InterfaceFields> = schema
+        .types
+        .iter()
+        .filter_map(|(name, ty)| {
+            let ExtendedType::Interface(def) = ty else {
+                return None;
+            };
+            let fields = InterfaceFields {
+                implements_interfaces: def.implements_interfaces.clone(),
+                fields: def.fields.clone(),
+            };
+            Some((name.clone(), fields))
+        })
+        .collect();
+
+    let mut report = PropagatedInterfaceFields::default();
+    for (type_name, ty) in &mut schema.types {
+        let (implements_interfaces, existing_fields) = match ty {
+            ExtendedType::Object(def) => (&def.implements_interfaces, &def.fields),
+            ExtendedType::Interface(def) => (&def.implements_interfaces, &def.fields),
+            ExtendedType::Scalar(_)
+            | ExtendedType::Union(_)
+            | ExtendedType::Enum(_)
+            | ExtendedType::InputObject(_) => continue,
+        };
+        let mut missing = IndexMap::default();
+        let mut visited = IndexSet::default();
+        let mut stack: Vec<&Name> = implements_interfaces.iter().map(|c| &c.name).collect();
+        stack.reverse();
+        while let Some(interface_name) = stack.pop() {
+            if interface_name == type_name || !visited.insert(interface_name) {
+                continue;
+            }
+            let Some(interface) = interfaces.get(interface_name) else {
+                // Undefined interface, reported by validation
+                continue;
+            };
+            for (field_name, field) in &interface.fields {
+                if !existing_fields.contains_key(field_name) {
+                    missing
+                        .entry(field_name.clone())
+                        .or_insert_with(|| (interface_name.clone(), field.node.clone()));
+                }
+            }
+            stack.extend(
+                interface
+                    .implements_interfaces
+                    .iter()
+                    .rev()
+                    .map(|c| &c.name),
+            );
+        }
+        if missing.is_empty() {
+            continue;
+        }
+        let fields = match ty {
+            ExtendedType::Object(def) => &mut def.make_mut().fields,
+            ExtendedType::Interface(def) => &mut def.make_mut().fields,
+            _ => unreachable!(),
+        };
+        for (field_name, (interface_name, field)) in missing {
+            fields.insert(field_name.clone(), field.into());
+            report.added.push(PropagatedField {
+                type_name: type_name.clone(),
+                field_name,
+                interface_name,
+            })
+        }
+    }
+    report
+}
+
+struct InterfaceFields {
+    implements_interfaces: IndexSet<ComponentName>,
+    fields: IndexMap<Name, Component<FieldDefinition>>,
+}
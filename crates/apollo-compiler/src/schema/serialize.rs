@@ -1,4 +1,5 @@
 use super::*;
+use crate::ast::serialize::serialize_leading_comments;
 use crate::ast::serialize::top_level;
 use crate::ast::serialize::State;
 use crate::ast::OperationType;
@@ -6,9 +7,21 @@ use std::fmt;
 
 impl Schema {
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
+        if state.canonical_enabled() {
+            return top_level(state, self.to_canonical_ast(), |state, def| {
+                def.serialize_impl(state)
+            });
+        }
         // TODO: avoid allocating temporary AST nodes?
         // it would ~duplicate large parts of ast/serialize.rs
-        top_level(state, self.to_ast(), |state, def| def.serialize_impl(state))
+        top_level(state, self.to_ast(), |state, def| {
+            if state.origin_comments_enabled() {
+                if let Some(comment) = self.origin_comment(&def) {
+                    serialize_leading_comments(state, &[comment.into()])?
+                }
+            }
+            def.serialize_impl(state)
+        })
     }
 
     pub(crate) fn to_ast(&self) -> impl Iterator<Item = ast::Definition> + '_ {
@@ -47,24 +60,7 @@ impl Node<SchemaDefinition> {
         let implict = description.is_none()
             && directives.is_empty()
             && extensions.is_empty()
-            && [
-                (query, ast::OperationType::Query),
-                (mutation, ast::OperationType::Mutation),
-                (subscription, ast::OperationType::Subscription),
-            ]
-            .into_iter()
-            .all(|(root_operation, operation_type)| {
-                // If there were no explict `schema` definition,
-                // what implicit root operation would we get for this operation type?
-                let default_type_name = operation_type.default_type_name();
-                let has_object = types
-                    .get(&default_type_name).is_some_and(|def| def.is_object());
-                let implicit_root_operation = has_object.then_some(&default_type_name);
-                // What we have
-                let actual_root_operation = root_operation.as_ref().map(|r| &r.name);
-                // Only allow an implicit `schema` definition if they match
-                actual_root_operation == implicit_root_operation
-            })
+            && self.has_implicit_root_operations(types)
             // Hack: if there is *nothing*, still emit an empty SchemaDefinition AST node
             // that carries a location, so AST-based validation can emit an error
             // with `DiagnosticData::QueryRootOperationType`.
@@ -84,7 +80,8 @@ impl Node<SchemaDefinition> {
             Some(ast::Definition::SchemaDefinition(self.same_location(
                 ast::SchemaDefinition {
                     description: self.description.clone(),
-                    directives: ast::DirectiveList(components(&self.directives, None)),
+                    leading_comments: Vec::new(),
+                    directives: ast::DirectiveList::from(components(&self.directives, None)),
                     root_operations: root_ops(None),
                 },
             )))
@@ -92,13 +89,43 @@ impl Node<SchemaDefinition> {
         .into_iter()
         .chain(extensions.into_iter().map(move |ext| {
             ast::Definition::SchemaExtension(ext.same_location(ast::SchemaExtension {
-                directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                leading_comments: Vec::new(),
+                directives: ast::DirectiveList::from(components(&self.directives, Some(ext))),
                 root_operations: root_ops(Some(ext)),
             }))
         }))
     }
 }
 
+impl SchemaDefinition {
+    /// Whether root operations are those that would be implied
+    /// if there were no `schema` definition
+    pub(crate) fn has_implicit_root_operations(
+        &self,
+        types: &IndexMap<Name, ExtendedType>,
+    ) -> bool {
+        [
+            (&self.query, ast::OperationType::Query),
+            (&self.mutation, ast::OperationType::Mutation),
+            (&self.subscription, ast::OperationType::Subscription),
+        ]
+        .into_iter()
+        .all(|(root_operation, operation_type)| {
+            // If there were no explict `schema` definition,
+            // what implicit root operation would we get for this operation type?
+            let default_type_name = operation_type.default_type_name();
+            let has_object = types
+                .get(&default_type_name)
+                .is_some_and(|def| def.is_object());
+            let implicit_root_operation = has_object.then_some(&default_type_name);
+            // What we have
+            let actual_root_operation = root_operation.as_ref().map(|r| &r.name);
+            // Only allow an implicit `schema` definition if they match
+            actual_root_operation == implicit_root_operation
+        })
+    }
+}
+
 impl ExtendedType {
     fn to_ast(&self) -> impl Iterator<Item = ast::Definition> + '_ {
         match self {
@@ -129,14 +156,16 @@ impl ScalarType {
     fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
         let def = ast::ScalarTypeDefinition {
             description: self.description.clone(),
+            leading_comments: Vec::new(),
             name: self.name.clone(),
-            directives: ast::DirectiveList(components(&self.directives, None)),
+            directives: ast::DirectiveList::from(components(&self.directives, None)),
         };
         std::iter::once(Node::new_opt_location(def, location).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::ScalarTypeExtension(ext.same_location(ast::ScalarTypeExtension {
+                    leading_comments: Vec::new(),
                     name: self.name.clone(),
-                    directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                    directives: ast::DirectiveList::from(components(&self.directives, Some(ext))),
                 }))
             }),
         )
@@ -153,17 +182,19 @@ impl ObjectType {
     fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
         let def = ast::ObjectTypeDefinition {
             description: self.description.clone(),
+            leading_comments: Vec::new(),
             name: self.name.clone(),
             implements_interfaces: names(&self.implements_interfaces, None),
-            directives: ast::DirectiveList(components(&self.directives, None)),
+            directives: ast::DirectiveList::from(components(&self.directives, None)),
             fields: components(self.fields.values(), None),
         };
         std::iter::once(Node::new_opt_location(def, location).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::ObjectTypeExtension(ext.same_location(ast::ObjectTypeExtension {
+                    leading_comments: Vec::new(),
                     name: self.name.clone(),
                     implements_interfaces: names(&self.implements_interfaces, Some(ext)),
-                    directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                    directives: ast::DirectiveList::from(components(&self.directives, Some(ext))),
                     fields: components(self.fields.values(), Some(ext)),
                 }))
             }),
@@ -181,18 +212,23 @@ impl InterfaceType {
     fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
         let def = ast::InterfaceTypeDefinition {
             description: self.description.clone(),
+            leading_comments: Vec::new(),
             name: self.name.clone(),
             implements_interfaces: names(&self.implements_interfaces, None),
-            directives: ast::DirectiveList(components(&self.directives, None)),
+            directives: ast::DirectiveList::from(components(&self.directives, None)),
             fields: components(self.fields.values(), None),
         };
         std::iter::once(Node::new_opt_location(def, location).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::InterfaceTypeExtension(ext.same_location(
                     ast::InterfaceTypeExtension {
+                        leading_comments: Vec::new(),
                         name: self.name.clone(),
                         implements_interfaces: names(&self.implements_interfaces, Some(ext)),
-                        directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                        directives: ast::DirectiveList::from(components(
+                            &self.directives,
+                            Some(ext),
+                        )),
                         fields: components(self.fields.values(), Some(ext)),
                     },
                 ))
@@ -211,15 +247,17 @@ impl UnionType {
     fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
         let def = ast::UnionTypeDefinition {
             description: self.description.clone(),
+            leading_comments: Vec::new(),
             name: self.name.clone(),
-            directives: ast::DirectiveList(components(&self.directives, None)),
+            directives: ast::DirectiveList::from(components(&self.directives, None)),
             members: names(&self.members, None),
         };
         std::iter::once(Node::new_opt_location(def, location).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::UnionTypeExtension(ext.same_location(ast::UnionTypeExtension {
+                    leading_comments: Vec::new(),
                     name: self.name.clone(),
-                    directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                    directives: ast::DirectiveList::from(components(&self.directives, Some(ext))),
                     members: names(&self.members, Some(ext)),
                 }))
             }),
@@ -237,15 +275,17 @@ impl EnumType {
     fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
         let def = ast::EnumTypeDefinition {
             description: self.description.clone(),
+            leading_comments: Vec::new(),
             name: self.name.clone(),
-            directives: ast::DirectiveList(components(&self.directives, None)),
+            directives: ast::DirectiveList::from(components(&self.directives, None)),
             values: components(self.values.values(), None),
         };
         std::iter::once(Node::new_opt_location(def, location).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::EnumTypeExtension(ext.same_location(ast::EnumTypeExtension {
+                    leading_comments: Vec::new(),
                     name: self.name.clone(),
-                    directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                    directives: ast::DirectiveList::from(components(&self.directives, Some(ext))),
                     values: components(self.values.values(), Some(ext)),
                 }))
             }),
@@ -263,16 +303,21 @@ impl InputObjectType {
     fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
         let def = ast::InputObjectTypeDefinition {
             description: self.description.clone(),
+            leading_comments: Vec::new(),
             name: self.name.clone(),
-            directives: ast::DirectiveList(components(&self.directives, None)),
+            directives: ast::DirectiveList::from(components(&self.directives, None)),
             fields: components(self.fields.values(), None),
         };
         std::iter::once(Node::new_opt_location(def, location).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::InputObjectTypeExtension(ext.same_location(
                     ast::InputObjectTypeExtension {
+                        leading_comments: Vec::new(),
                         name: self.name.clone(),
-                        directives: ast::DirectiveList(components(&self.directives, Some(ext))),
+                        directives: ast::DirectiveList::from(components(
+                            &self.directives,
+                            Some(ext),
+                        )),
                         fields: components(self.fields.values(), Some(ext)),
                     },
                 ))
@@ -0,0 +1,260 @@
+use crate::collections::IndexMap;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::Directive;
+use crate::schema::ExtendedType;
+use crate::schema::InputValueDefinition;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// A valid schema together with metadata for each of its schema coordinates,
+/// extracted once for tools like documentation generators or gateways.
+///
+/// Every type, field, input field, enum value, field argument, directive,
+/// and directive argument of the schema has an entry, except for built-in definitions.
+/// Entries are in schema order.
+///
+/// Metadata comes from descriptions and from these directives, when applied:
+///
+/// * `@deprecated(reason: String)`
+/// * `@cost(weight: Int!)`
+/// * `@tag(name: String!)`, which is repeatable
+///
+/// ```
+/// use apollo_compiler::schema::ServiceDescription;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+///     directive @cost(weight: Int!) on FIELD_DEFINITION
+///     type Query {
+///       "Look up a user"
+///       user(id: ID!): User @cost(weight: 5) @tag(name: "public")
+///       me: User @deprecated
+///     }
+///     type User @tag(name: "public") { name: String }
+/// "#, "schema.graphql").unwrap();
+///
+/// let service = ServiceDescription::new(schema);
+/// let user = service.get(&"Query.user".parse().unwrap()).unwrap();
+/// assert_eq!(user.description.as_deref(), Some("Look up a user"));
+/// assert_eq!(user.cost, Some(5));
+///
+/// let me = service.get(&"Query.me".parse().unwrap()).unwrap();
+/// assert_eq!(me.deprecation_reason.as_deref(), Some("No longer supported"));
+///
+/// let public: Vec<_> = service.tagged("public").map(|c| c.to_string()).collect();
+/// assert_eq!(public, ["Query.user", "User"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ServiceDescription {
+    schema: Valid<Schema>,
+    metadata: IndexMap<SchemaCoordinate, CoordinateMetadata>,
+    tags: IndexMap<String, Vec<SchemaCoordinate>>,
+}
+
+/// Metadata of a schema coordinate in a [`ServiceDescription`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoordinateMetadata {
+    pub description: Option<Node<str>>,
+
+    /// `Some` if `@deprecated` is applied, with the default reason if not specified
+    pub deprecation_reason: Option<String>,
+
+    /// The `weight` argument of `@cost`
+    pub cost: Option<u64>,
+
+    /// The `name` argument of each `@tag`, in order of application
+    pub tags: Vec<String>,
+}
+
+impl ServiceDescription {
+    /// Extract metadata for every schema coordinate of `schema`
+    pub fn new(schema: Valid<Schema>) -> Self {
+        let mut metadata = IndexMap::with_hasher(Default::default());
+        let mut add = |coordinate: SchemaCoordinate, entry| {
+            metadata.insert(coordinate, entry);
+        };
+        let new =
+            |description, directives| CoordinateMetadata::new(&schema, description, directives);
+        for (name, ty) in &schema.types {
+            if ty.is_built_in() {
+                continue;
+            }
+            add(
+                TypeCoordinate { ty: name.clone() }.into(),
+                new(
+                    ty.description(),
+                    ty.directives().iter().map(|d| &*d.node).collect(),
+                ),
+            );
+            let attribute = |attribute: &Name| TypeAttributeCoordinate {
+                ty: name.clone(),
+                attribute: attribute.clone(),
+            };
+            let fields = match ty {
+                ExtendedType::Object(def) => Some(&def.fields),
+                ExtendedType::Interface(def) => Some(&def.fields),
+                _ => None,
+            };
+            for (field_name, field) in fields.into_iter().flatten() {
+                add(
+                    attribute(field_name).into(),
+                    new(
+                        field.description.as_ref(),
+                        field.directives.iter().map(|d| &**d).collect(),
+                    ),
+                );
+                for argument in &field.arguments {
+                    let coordinate = FieldArgumentCoordinate {
+                        ty: name.clone(),
+                        field: field_name.clone(),
+                        argument: argument.name.clone(),
+                    };
+                    add(coordinate.into(), new_argument(&new, argument))
+                }
+            }
+            if let ExtendedType::InputObject(def) = ty {
+                for (field_name, field) in &def.fields {
+                    add(attribute(field_name).into(), new_argument(&new, field))
+                }
+            }
+            if let ExtendedType::Enum(def) = ty {
+                for (value_name, value) in &def.values {
+                    add(
+                        attribute(value_name).into(),
+                        new(
+                            value.description.as_ref(),
+                            value.directives.iter().map(|d| &**d).collect(),
+                        ),
+                    )
+                }
+            }
+        }
+        for (name, def) in &schema.directive_definitions {
+            if def.is_built_in() {
+                continue;
+            }
+            let coordinate = DirectiveCoordinate {
+                directive: name.clone(),
+            };
+            add(coordinate.into(), new(def.description.as_ref(), Vec::new()));
+            for argument in &def.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: name.clone(),
+                    argument: argument.name.clone(),
+                };
+                add(coordinate.into(), new_argument(&new, argument))
+            }
+        }
+
+        let mut tags = IndexMap::<_, Vec<_>>::with_hasher(Default::default());
+        for (coordinate, entry) in &metadata {
+            for tag in &entry.tags {
+                let coordinates = tags.entry(tag.clone()).or_default();
+                if coordinates.last() != Some(coordinate) {
+                    coordinates.push(coordinate.clone())
+                }
+            }
+        }
+        Self {
+            schema,
+            metadata,
+            tags,
+        }
+    }
+
+    /// The schema this metadata was extracted from
+    pub fn schema(&self) -> &Valid<Schema> {
+        &self.schema
+    }
+
+    /// Returns the metadata of a schema coordinate,
+    /// or `None` if it does not exist in the schema or is built-in
+    pub fn get(&self, coordinate: &SchemaCoordinate) -> Option<&CoordinateMetadata> {
+        self.metadata.get(coordinate)
+    }
+
+    /// Iterate over all schema coordinates and their metadata, in schema order
+    pub fn iter(&self) -> impl Iterator<Item = (&SchemaCoordinate, &CoordinateMetadata)> {
+        self.metadata.iter()
+    }
+
+    /// Iterate over deprecated schema coordinates and their deprecation reason
+    pub fn deprecated(&self) -> impl Iterator<Item = (&SchemaCoordinate, &str)> {
+        self.metadata.iter().filter_map(|(coordinate, entry)| {
+            Some((coordinate, entry.deprecation_reason.as_deref()?))
+        })
+    }
+
+    /// Iterate over the names of all `@tag` applications in the schema, without duplicates
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.keys().map(|tag| tag.as_str())
+    }
+
+    /// Iterate over schema coordinates with a `@tag` of the given name
+    pub fn tagged(&self, tag: &str) -> impl Iterator<Item = &SchemaCoordinate> {
+        self.tags.get(tag).into_iter().flatten()
+    }
+}
+
+impl From<Valid<Schema>> for ServiceDescription {
+    fn from(schema: Valid<Schema>) -> Self {
+        Self::new(schema)
+    }
+}
+
+fn new_argument<'a>(
+    new: &impl Fn(Option<&'a Node<str>>, Vec<&'a Directive>) -> CoordinateMetadata,
+    argument: &'a InputValueDefinition,
+) -> CoordinateMetadata {
+    new(
+        argument.description.as_ref(),
+        argument.directives.iter().map(|d| &**d).collect(),
+    )
+}
+
+impl CoordinateMetadata {
+    fn new(schema: &Schema, description: Option<&Node<str>>, directives: Vec<&Directive>) -> Self {
+        let mut metadata = Self {
+            description: description.cloned(),
+            ..Default::default()
+        };
+        for directive in directives {
+            match directive.name.as_str() {
+                "deprecated" => {
+                    metadata.deprecation_reason = Some(
+                        directive
+                            .argument_by_name("reason", schema)
+                            .ok()
+                            .and_then(|value| value.as_str())
+                            .unwrap_or_default()
+                            .to_owned(),
+                    )
+                }
+                "cost" => {
+                    metadata.cost = directive
+                        .specified_argument_by_name("weight")
+                        .and_then(|value| value.to_i32())
+                        .and_then(|weight| u64::try_from(weight).ok())
+                }
+                "tag" => {
+                    if let Some(name) = directive
+                        .specified_argument_by_name("name")
+                        .and_then(|value| value.as_str())
+                    {
+                        metadata.tags.push(name.to_owned())
+                    }
+                }
+                _ => {}
+            }
+        }
+        metadata
+    }
+}
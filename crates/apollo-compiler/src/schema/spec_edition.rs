@@ -0,0 +1,124 @@
+use crate::ast::DirectiveLocation;
+use crate::parser::FileId;
+use crate::schema::ExtendedType;
+use crate::schema::InputObjectType;
+use crate::schema::SchemaBuilder;
+use crate::Schema;
+
+/// An edition of the GraphQL specification, to pin the built-in definitions of a schema
+/// to the semantics implemented by a GraphQL server.
+/// Configured with [`SchemaBuilder::spec_edition`] or [`Parser::spec_edition`][crate::parser::Parser::spec_edition].
+///
+/// Editions differ in their built-in directives and introspection types.
+/// Validation follows these definitions, so a schema or document that uses a feature
+/// that is not part of the selected edition is invalid:
+///
+/// | | [`October2021`][Self::October2021] | [`Draft`][Self::Draft] | Unpinned (default) |
+/// |---|---|---|---|
+/// | `@deprecated` on arguments and input fields | No | Yes | Yes |
+/// | `includeDeprecated` argument of `__Type.inputFields`, `__Field.args`, and `__Directive.args` | No | Yes | Yes |
+/// | `@oneOf` input objects and `__Type.isOneOf` | No | Yes | No |
+///
+/// Without a pinned edition, a schema has the built-in definitions of October 2021
+/// with the additions to deprecation from the draft,
+/// as in earlier versions of apollo-compiler.
+///
+/// With the draft edition, input objects with a `@oneOf` directive
+/// are validated and coerced as [oneOf input objects]:
+/// their fields must be nullable without default values,
+/// and values must have exactly one field which is not `null`.
+/// A schema can also define its own `@oneOf` directive, which then has no such meaning.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::schema::SpecEdition;
+/// use apollo_compiler::Schema;
+///
+/// let sdl = r#"
+///   type Query { search(by: SearchBy): [String] }
+///   input SearchBy @oneOf { name: String, id: ID }
+/// "#;
+/// let draft = Schema::builder()
+///     .spec_edition(SpecEdition::Draft)
+///     .parse(sdl, "schema.graphql")
+///     .build()
+///     .unwrap();
+/// draft.validate().unwrap();
+///
+/// let october_2021 = Schema::builder()
+///     .spec_edition(SpecEdition::October2021)
+///     .parse(sdl, "schema.graphql")
+///     .build()
+///     .unwrap();
+/// let errors = october_2021.validate().unwrap_err().errors;
+/// assert_eq!(
+///     errors.iter().next().unwrap().error.to_string(),
+///     "cannot find directive `@oneOf` in this document",
+/// );
+/// ```
+///
+/// [oneOf input objects]: https://spec.graphql.org/draft/#sec-OneOf-Input-Objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SpecEdition {
+    /// [The October 2021 edition](https://spec.graphql.org/October2021/)
+    October2021,
+    /// [The working draft](https://spec.graphql.org/draft/), as of this version of apollo-compiler
+    Draft,
+}
+
+impl Schema {
+    /// Returns whether `input_object` is a [oneOf input object][SpecEdition]:
+    /// it has the `@oneOf` directive, and that directive is the built-in definition
+    /// of the draft edition rather than one defined by the schema.
+    pub(crate) fn is_one_of(&self, input_object: &InputObjectType) -> bool {
+        input_object.directives.has("oneOf")
+            && self
+                .directive_definitions
+                .get("oneOf")
+                .and_then(|definition| definition.location())
+                .is_some_and(|location| location.file_id() == FileId::BUILT_IN)
+    }
+}
+
+impl SchemaBuilder {
+    /// Remove the built-in definitions of `built_in_types.graphql` that are not part of `edition`.
+    /// That file has the definitions of the draft.
+    pub(crate) fn restrict_built_ins(&mut self, edition: Option<SpecEdition>) {
+        if edition == Some(SpecEdition::Draft) {
+            return;
+        }
+        let schema = &mut self.schema;
+        schema.directive_definitions.shift_remove("oneOf");
+        let Some(ExtendedType::Object(ty)) = schema.types.get_mut("__Type") else {
+            unreachable!()
+        };
+        ty.make_mut().fields.shift_remove("isOneOf");
+        if edition.is_none() {
+            return;
+        }
+
+        let deprecated = schema.directive_definitions.get_mut("deprecated").unwrap();
+        deprecated.make_mut().locations.retain(|location| {
+            matches!(
+                location,
+                DirectiveLocation::FieldDefinition | DirectiveLocation::EnumValue
+            )
+        });
+        for (ty, field) in [
+            ("__Type", "inputFields"),
+            ("__Field", "args"),
+            ("__Directive", "args"),
+        ] {
+            let Some(ExtendedType::Object(ty)) = schema.types.get_mut(ty) else {
+                unreachable!()
+            };
+            let field = ty.make_mut().fields.get_mut(field).unwrap();
+            field
+                .make_mut()
+                .arguments
+                .retain(|arg| arg.name != "includeDeprecated");
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::SchemaBuilder;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::hash::Hash;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Returned by [`split_by`]: a schema split into several documents
+#[derive(Debug, Clone)]
+pub struct SchemaSplit<K> {
+    /// One document per part, in the order parts were first returned by the callback
+    pub documents: IndexMap<K, ast::Document>,
+    /// References from a definition in one part to a type or directive defined in another
+    pub cross_references: Vec<CrossReference<K>>,
+}
+
+/// A reference across parts of a [`SchemaSplit`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CrossReference<K> {
+    /// The part containing the reference
+    pub from: K,
+    /// The part containing the definition of the referenced type or directive
+    pub to: K,
+    /// The type or directive definition (or extension) containing the reference,
+    /// or `None` for the schema definition.
+    pub referenced_by: Option<Name>,
+    /// The referenced type or directive
+    pub target: SchemaCoordinate,
+}
+
+/// Splits a schema into several type system documents, such as to reorganize
+/// a monolithic SDL file into one file per domain.
+///
+/// `part_of` is called for each top-level definition of the schema,
+/// as they would be [serialized][Schema::serialize], and returns the key of the part
+/// (for example a file name) that definition goes into.
+/// Type extensions and schema extensions are kept as extensions,
+/// so they can go in a different part than the definition they extend.
+/// Built-in definitions are omitted, except extensions of built-in scalars.
+///
+/// Building a schema from all documents (in any order) produces the same schema,
+/// except for source locations and the order of definitions.
+///
+/// The returned [`cross_references`][SchemaSplit::cross_references] lists
+/// each type or directive that a part uses but that is defined in another part,
+/// once per referencing definition.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::schema::split_by;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { user: User product: Product }
+///     type User { name: String }
+///     type Product { name: String seller: User }
+///     extend type User { purchases: [Product] }
+/// "#, "schema.graphql").unwrap();
+///
+/// let split = split_by(&schema, |definition| match definition.name().map(|n| n.as_str()) {
+///     Some("Product") => "products.graphql",
+///     _ if definition.is_extension_definition() => "products.graphql",
+///     _ => "users.graphql",
+/// });
+/// assert_eq!(split.documents.len(), 2);
+/// assert_eq!(
+///     split.documents["products.graphql"].to_string(),
+///     "extend type User {\n  purchases: [Product]\n}\n\n\
+///      type Product {\n  name: String\n  seller: User\n}\n",
+/// );
+/// for reference in &split.cross_references {
+///     println!("{} uses {} from {}", reference.from, reference.target, reference.to);
+/// }
+///
+/// let mut builder = Schema::builder();
+/// for document in split.documents.values() {
+///     builder = builder.add_ast(document);
+/// }
+/// let rebuilt = builder.build().unwrap();
+/// assert_eq!(rebuilt.content_hash(), schema.content_hash());
+/// ```
+pub fn split_by<K: Clone + Eq + Hash>(
+    schema: &Schema,
+    mut part_of: impl FnMut(&ast::Definition) -> K,
+) -> SchemaSplit<K> {
+    let mut documents = IndexMap::<K, ast::Document>::default();
+    // Part of the (non-extension) definition of each type and directive
+    let mut defined_in = HashMap::<SchemaCoordinate, K>::default();
+    for definition in schema.to_ast() {
+        let part = part_of(&definition);
+        if !definition.is_extension_definition() {
+            if let Some(target) = defined(&definition) {
+                defined_in.insert(target, part.clone());
+            }
+        }
+        documents
+            .entry(part.clone())
+            .or_insert_with(ast::Document::new)
+            .definitions
+            .push(definition);
+    }
+
+    let mut cross_references = IndexSet::default();
+    let mut targets = Vec::new();
+    for (from, document) in &documents {
+        for definition in &document.definitions {
+            references(definition, &mut targets);
+            for target in targets.drain(..) {
+                let Some(to) = defined_in.get(&target) else {
+                    // Built-in
+                    continue;
+                };
+                if to != from {
+                    cross_references.insert(CrossReference {
+                        from: from.clone(),
+                        to: to.clone(),
+                        referenced_by: definition.name().cloned(),
+                        target,
+                    });
+                }
+            }
+        }
+    }
+    SchemaSplit {
+        documents,
+        cross_references: cross_references.into_iter().collect(),
+    }
+}
+
+/// Splits a schema into one document per type and directive,
+/// for use with [`SchemaSplit::write_to_dir`].
+///
+/// Each type goes in `types/<name>.graphql` together with its extensions,
+/// each directive definition in `directives/<name>.graphql`,
+/// and the schema definition and extensions in `schema.graphql`.
+/// This is otherwise like [`split_by`].
+///
+/// Type names that only differ by case map to different files,
+/// which a case-insensitive file system would write to the same file.
+pub fn split_by_type(schema: &Schema) -> SchemaSplit<PathBuf> {
+    split_by(schema, |definition| match definition {
+        ast::Definition::DirectiveDefinition(def) => {
+            Path::new("directives").join(format!("{}.graphql", def.name))
+        }
+        _ => match definition.name() {
+            Some(name) => Path::new("types").join(format!("{name}.graphql")),
+            None => PathBuf::from("schema.graphql"),
+        },
+    })
+}
+
+impl<K: AsRef<Path>> SchemaSplit<K> {
+    /// The name of the index file written by [`write_to_dir`][Self::write_to_dir]
+    pub const INDEX_FILE: &'static str = "index.txt";
+
+    /// Serializes each document to a file in `dir` whose relative path is the key of its part,
+    /// creating directories as needed.
+    ///
+    /// Also writes an [index file][Self::INDEX_FILE] listing the path of each document,
+    /// one per line, so that [`SchemaBuilder::parse_dir`] can read them back in the same order.
+    ///
+    /// Returns an error of kind [`InvalidInput`][io::ErrorKind::InvalidInput] without writing
+    /// anything if a key is not a relative path inside `dir`, or is the index file itself.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use apollo_compiler::schema::split_by_type;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// split_by_type(&schema).write_to_dir("schema").unwrap();
+    ///
+    /// let rebuilt = Schema::builder().parse_dir("schema").unwrap().build().unwrap();
+    /// assert_eq!(rebuilt.content_hash(), schema.content_hash());
+    /// ```
+    pub fn write_to_dir(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let mut index = String::new();
+        for key in self.documents.keys() {
+            let path = index_entry(key.as_ref())?;
+            index.push_str(&path);
+            index.push('\n');
+        }
+        for (key, document) in &self.documents {
+            let path = dir.join(key);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, document.to_string())?;
+        }
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(Self::INDEX_FILE), index)
+    }
+}
+
+/// Returns how `path` is written in an index file: with `/` separators
+fn index_entry(path: &Path) -> io::Result<String> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a relative path for a schema part: {}", path.display()),
+        )
+    };
+    let mut entry = String::new();
+    for component in path.components() {
+        let Component::Normal(component) = component else {
+            return Err(invalid());
+        };
+        let component = component.to_str().ok_or_else(invalid)?;
+        if component.contains('\n') {
+            return Err(invalid());
+        }
+        if !entry.is_empty() {
+            entry.push('/');
+        }
+        entry.push_str(component);
+    }
+    if entry.is_empty() || entry == SchemaSplit::<PathBuf>::INDEX_FILE {
+        return Err(invalid());
+    }
+    Ok(entry)
+}
+
+impl SchemaBuilder {
+    /// Parses the files of a schema written by [`SchemaSplit::write_to_dir`]
+    /// as additional inputs for this schema.
+    ///
+    /// Files are read in the order of the [index file][SchemaSplit::INDEX_FILE] in `dir`,
+    /// where empty lines and lines starting with `#` are ignored.
+    /// Source locations of the parsed definitions use the path of each file, joined to `dir`.
+    ///
+    /// Returns an error if the index or one of the files listed in it can not be read.
+    /// Syntax and schema errors are reported when building the schema.
+    pub fn parse_dir(mut self, dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let index = std::fs::read_to_string(dir.join(SchemaSplit::<PathBuf>::INDEX_FILE))?;
+        for line in index.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let path = dir.join(line);
+            let source_text = std::fs::read_to_string(&path)?;
+            self = self.parse(source_text, path);
+        }
+        Ok(self)
+    }
+}
+
+/// The type or directive defined by a definition or extension
+fn defined(definition: &ast::Definition) -> Option<SchemaCoordinate> {
+    match definition {
+        ast::Definition::DirectiveDefinition(def) => Some(directive(&def.name)),
+        ast::Definition::SchemaDefinition(_) | ast::Definition::SchemaExtension(_) => None,
+        _ => definition.name().map(ty),
+    }
+}
+
+fn ty(name: &Name) -> SchemaCoordinate {
+    SchemaCoordinate::Type(TypeCoordinate { ty: name.clone() })
+}
+
+fn directive(name: &Name) -> SchemaCoordinate {
+    SchemaCoordinate::Directive(DirectiveCoordinate {
+        directive: name.clone(),
+    })
+}
+
+/// Types and directives used by a definition or extension
+fn references(definition: &ast::Definition, targets: &mut Vec<SchemaCoordinate>) {
+    use ast::Definition as D;
+    let directives = |targets: &mut Vec<_>, directives: &ast::DirectiveList| {
+        targets.extend(directives.iter().map(|d| directive(&d.name)))
+    };
+    let input_values = |targets: &mut Vec<_>, values: &[Node<ast::InputValueDefinition>]| {
+        for value in values {
+            targets.push(ty(value.ty.inner_named_type()));
+            directives(targets, &value.directives);
+        }
+    };
+    let fields = |targets: &mut Vec<_>, fields: &[Node<ast::FieldDefinition>]| {
+        for field in fields {
+            targets.push(ty(field.ty.inner_named_type()));
+            input_values(targets, &field.arguments);
+            directives(targets, &field.directives);
+        }
+    };
+    let enum_values = |targets: &mut Vec<_>, values: &[Node<ast::EnumValueDefinition>]| {
+        for value in values {
+            directives(targets, &value.directives);
+        }
+    };
+    directives(targets, definition.directives());
+    match definition {
+        D::SchemaDefinition(def) => targets.extend(def.root_operations.iter().map(|op| ty(&op.1))),
+        D::SchemaExtension(def) => targets.extend(def.root_operations.iter().map(|op| ty(&op.1))),
+        D::DirectiveDefinition(def) => input_values(targets, &def.arguments),
+        D::ObjectTypeDefinition(def) => {
+            targets.extend(def.implements_interfaces.iter().map(ty));
+            fields(targets, &def.fields)
+        }
+        D::ObjectTypeExtension(def) => {
+            targets.extend(def.implements_interfaces.iter().map(ty));
+            fields(targets, &def.fields)
+        }
+        D::InterfaceTypeDefinition(def) => {
+            targets.extend(def.implements_interfaces.iter().map(ty));
+            fields(targets, &def.fields)
+        }
+        D::InterfaceTypeExtension(def) => {
+            targets.extend(def.implements_interfaces.iter().map(ty));
+            fields(targets, &def.fields)
+        }
+        D::UnionTypeDefinition(def) => targets.extend(def.members.iter().map(ty)),
+        D::UnionTypeExtension(def) => targets.extend(def.members.iter().map(ty)),
+        D::EnumTypeDefinition(def) => enum_values(targets, &def.values),
+        D::EnumTypeExtension(def) => enum_values(targets, &def.values),
+        D::InputObjectTypeDefinition(def) => input_values(targets, &def.fields),
+        D::InputObjectTypeExtension(def) => input_values(targets, &def.fields),
+        D::ScalarTypeDefinition(_)
+        | D::ScalarTypeExtension(_)
+        | D::OperationDefinition(_)
+        | D::FragmentDefinition(_) => {}
+    }
+    if let Some(own) = defined(definition) {
+        if definition.is_extension_definition() {
+            // An extension depends on the definition it extends
+            targets.push(own)
+        } else {
+            targets.retain(|target| *target != own)
+        }
+    }
+}
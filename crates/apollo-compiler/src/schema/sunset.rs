@@ -0,0 +1,435 @@
+//! Deprecation timelines: reporting schema elements past or approaching their sunset date.
+//!
+//! This module supports an opt-in convention for governance tooling:
+//! elements of a schema that are [deprecated](https://spec.graphql.org/draft/#sec--deprecated)
+//! may also have a `@sunset` directive with the date after which they can be removed.
+//! The schema must define this custom directive:
+//!
+//! ```graphql
+//! directive @sunset(date: String!) on FIELD_DEFINITION | ARGUMENT_DEFINITION
+//!   | INPUT_FIELD_DEFINITION | ENUM_VALUE | OBJECT | INTERFACE | UNION | ENUM
+//!   | INPUT_OBJECT | SCALAR
+//! ```
+//!
+//! Dates are in `YYYY-MM-DD` format. [`SunsetReport::new`] lists elements whose sunset date
+//! is past or within a given number of days of the current date, from SDL alone.
+//! [`Sunset`] and [`Deprecated`] implement [`FromDirective`] for tools that need
+//! the typed directive arguments of a single element.
+//!
+//! # Example
+//!
+//! ```rust
+//! use apollo_compiler::schema::sunset::Date;
+//! use apollo_compiler::schema::sunset::SunsetReport;
+//! use apollo_compiler::Schema;
+//!
+//! let schema = Schema::parse_and_validate(r#"
+//!     directive @sunset(date: String!) on FIELD_DEFINITION | ENUM_VALUE
+//!     type Query {
+//!         user(id: ID!): String
+//!         userById(id: ID!): String @deprecated(reason: "Use `user`") @sunset(date: "2024-03-01")
+//!         users: [String] @deprecated(reason: "Use `search`") @sunset(date: "2024-06-30")
+//!         search: [String]
+//!     }
+//! "#, "schema.graphql").unwrap();
+//!
+//! let today: Date = "2024-03-15".parse().unwrap();
+//! let report = SunsetReport::new(&schema, today, 30);
+//! assert_eq!(report.entries.len(), 1);
+//! assert_eq!(
+//!     report.to_string(),
+//!     "Query.userById: sunset 2024-03-01, 14 days ago (deprecated: Use `user`)\n",
+//! );
+//! ```
+
+use crate::ast::Value;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::parser::SourceSpan;
+use crate::schema::DirectiveArguments;
+use crate::schema::DirectiveExtractionError;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::FromArgumentValue;
+use crate::schema::FromDirective;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+use std::str::FromStr;
+
+/// A calendar date, in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+/// Error type of [`Date`]’s [`FromStr`] implementation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid date `{0}`, expected YYYY-MM-DD")]
+pub struct DateParseError(String);
+
+/// The `@sunset(date:)` directive: the date after which a deprecated element can be removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sunset {
+    pub date: Date,
+}
+
+/// The built-in `@deprecated(reason:)` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecated {
+    pub reason: Option<String>,
+}
+
+/// Schema elements past or approaching their sunset date, returned by [`SunsetReport::new`].
+///
+/// Its [`Display`][fmt::Display] implementation prints one line per entry,
+/// then one per problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SunsetReport {
+    /// Sorted by sunset date, then in schema order
+    pub entries: Vec<SunsetEntry>,
+    /// In schema order
+    pub problems: Vec<SunsetProblem>,
+}
+
+/// An element of [`SunsetReport::entries`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SunsetEntry {
+    /// The schema element, such as `Query.user(id:)`
+    pub coordinate: SchemaCoordinate,
+    /// The location of the `@sunset` directive application
+    pub location: Option<SourceSpan>,
+    pub sunset: Date,
+    /// The reason given to `@deprecated`, if any
+    pub deprecation_reason: Option<String>,
+    /// Number of days from the current date to the sunset date, negative if it is past
+    pub days_remaining: i32,
+}
+
+/// An element of [`SunsetReport::problems`]: a `@sunset` directive that does not follow
+/// the convention
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SunsetProblem {
+    pub coordinate: SchemaCoordinate,
+    /// The location of the `@sunset` directive application
+    pub location: Option<SourceSpan>,
+    pub kind: SunsetProblemKind,
+}
+
+/// What is wrong in a [`SunsetProblem`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SunsetProblemKind {
+    /// The `@sunset` directive could not be converted, for example because of an invalid date
+    #[error(transparent)]
+    Extraction(#[from] DirectiveExtractionError),
+    /// The element has a sunset date but is not deprecated
+    #[error("has a sunset date but is not deprecated")]
+    NotDeprecated,
+}
+
+impl Date {
+    /// Returns a date, or `None` if `month` or `day` is out of range
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        ((1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month))
+            .then_some(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// From 1 to 12
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// From 1 to 31
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the number of days from `self` to `other`, negative if `other` is earlier
+    pub fn days_until(&self, other: Date) -> i32 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    /// Days since 1970-01-01, from <https://howardhinnant.github.io/date_algorithms.html>
+    fn days_since_epoch(&self) -> i32 {
+        let month = i32::from(self.month);
+        let year = i32::from(self.year) - i32::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i32::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl FromStr for Date {
+    type Err = DateParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DateParseError(s.to_owned());
+        let bytes = s.as_bytes();
+        if !s.is_ascii() || bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+            return Err(error());
+        }
+        let number = |range: std::ops::Range<usize>| {
+            let digits = &s[range];
+            if digits.bytes().all(|b| b.is_ascii_digit()) {
+                digits.parse().ok()
+            } else {
+                None
+            }
+        };
+        let year = number(0..4).ok_or_else(error)?;
+        let month = number(5..7).ok_or_else(error)?;
+        let day = number(8..10).ok_or_else(error)?;
+        Date::new(year, month as u8, day as u8).ok_or_else(error)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Converts from a string in `YYYY-MM-DD` format
+impl FromArgumentValue for Date {
+    const EXPECTED: &'static str = "a date string in YYYY-MM-DD format";
+
+    fn from_argument_value(value: &Node<Value>) -> Option<Self> {
+        value.as_str()?.parse().ok()
+    }
+}
+
+impl FromDirective for Sunset {
+    const NAME: &'static str = "sunset";
+
+    fn from_directive(args: &DirectiveArguments<'_>) -> Result<Self, DirectiveExtractionError> {
+        Ok(Self {
+            date: args.get("date")?,
+        })
+    }
+}
+
+impl FromDirective for Deprecated {
+    const NAME: &'static str = "deprecated";
+
+    fn from_directive(args: &DirectiveArguments<'_>) -> Result<Self, DirectiveExtractionError> {
+        Ok(Self {
+            reason: args.get("reason")?,
+        })
+    }
+}
+
+impl SunsetReport {
+    /// Find elements of `schema` with a `@sunset` date that is before `today`,
+    /// or within `warning_days` days after it.
+    ///
+    /// Built-in definitions are skipped.
+    /// Elements that have a `@sunset` directive but no `@deprecated` directive,
+    /// or whose `@sunset` directive cannot be converted, are reported as [`problems`][Self::problems].
+    pub fn new(schema: &Valid<Schema>, today: Date, warning_days: u32) -> Self {
+        let mut report = Self {
+            entries: Vec::new(),
+            problems: Vec::new(),
+        };
+        for coordinate in coordinates(schema) {
+            report.check(schema, coordinate, today, warning_days)
+        }
+        report.entries.sort_by_key(|entry| entry.sunset);
+        report
+    }
+
+    fn check(
+        &mut self,
+        schema: &Schema,
+        coordinate: SchemaCoordinate,
+        today: Date,
+        warning_days: u32,
+    ) {
+        let Ok(mut applications) = schema.extract_directives::<Sunset>(&coordinate) else {
+            return;
+        };
+        let Some(sunset) = applications.next() else {
+            return;
+        };
+        let location = sunset_location(schema, &coordinate);
+        let mut problem = |kind| {
+            self.problems.push(SunsetProblem {
+                coordinate: coordinate.clone(),
+                location,
+                kind,
+            })
+        };
+        let sunset = match sunset {
+            Ok(sunset) => sunset,
+            Err(error) => return problem(error.into()),
+        };
+        let deprecated = match schema.extract_directive::<Deprecated>(&coordinate) {
+            Ok(Some(deprecated)) => deprecated,
+            Ok(None) => return problem(SunsetProblemKind::NotDeprecated),
+            Err(error) => return problem(error.into()),
+        };
+        let days_remaining = today.days_until(sunset.date);
+        if days_remaining <= warning_days.try_into().unwrap_or(i32::MAX) {
+            self.entries.push(SunsetEntry {
+                coordinate,
+                location,
+                sunset: sunset.date,
+                deprecation_reason: deprecated.reason,
+                days_remaining,
+            })
+        }
+    }
+
+    /// Returns entries whose sunset date is before the current date
+    pub fn past(&self) -> impl Iterator<Item = &SunsetEntry> {
+        self.entries.iter().filter(|entry| entry.is_past())
+    }
+
+    /// Returns entries whose sunset date is the current date or later
+    pub fn approaching(&self) -> impl Iterator<Item = &SunsetEntry> {
+        self.entries.iter().filter(|entry| !entry.is_past())
+    }
+}
+
+impl SunsetEntry {
+    /// Returns whether the sunset date is before the current date
+    pub fn is_past(&self) -> bool {
+        self.days_remaining < 0
+    }
+}
+
+/// Coordinates of every element that can have directives, other than built-in definitions
+fn coordinates(schema: &Schema) -> Vec<SchemaCoordinate> {
+    let mut coordinates = Vec::new();
+    for (name, ty) in &schema.types {
+        if ty.is_built_in() {
+            continue;
+        }
+        coordinates.push(TypeCoordinate { ty: name.clone() }.into());
+        let attribute = |attribute: &Name| {
+            SchemaCoordinate::from(TypeAttributeCoordinate {
+                ty: name.clone(),
+                attribute: attribute.clone(),
+            })
+        };
+        match ty {
+            ExtendedType::Object(def) => {
+                for field in def.fields.values() {
+                    coordinates.push(attribute(&field.name));
+                    field_arguments(&mut coordinates, name, field);
+                }
+            }
+            ExtendedType::Interface(def) => {
+                for field in def.fields.values() {
+                    coordinates.push(attribute(&field.name));
+                    field_arguments(&mut coordinates, name, field);
+                }
+            }
+            ExtendedType::InputObject(def) => {
+                coordinates.extend(def.fields.keys().map(attribute));
+            }
+            ExtendedType::Enum(def) => {
+                coordinates.extend(def.values.keys().map(attribute));
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+        }
+    }
+    for directive in schema.directive_definitions.values() {
+        if directive.is_built_in() {
+            continue;
+        }
+        for argument in &directive.arguments {
+            coordinates.push(
+                DirectiveArgumentCoordinate {
+                    directive: directive.name.clone(),
+                    argument: argument.name.clone(),
+                }
+                .into(),
+            )
+        }
+    }
+    coordinates
+}
+
+fn field_arguments(coordinates: &mut Vec<SchemaCoordinate>, ty: &Name, field: &FieldDefinition) {
+    coordinates.extend(field.arguments.iter().map(|argument| {
+        SchemaCoordinate::from(FieldArgumentCoordinate {
+            ty: ty.clone(),
+            field: field.name.clone(),
+            argument: argument.name.clone(),
+        })
+    }))
+}
+
+fn sunset_location(schema: &Schema, coordinate: &SchemaCoordinate) -> Option<SourceSpan> {
+    let directive = match coordinate.lookup(schema).ok()? {
+        SchemaCoordinateLookup::Type(ty) => ty.directives().get(Sunset::NAME).map(|d| &**d),
+        SchemaCoordinateLookup::Field(def) => def.directives.get(Sunset::NAME),
+        SchemaCoordinateLookup::InputField(def) => def.directives.get(Sunset::NAME),
+        SchemaCoordinateLookup::EnumValue(def) => def.directives.get(Sunset::NAME),
+        SchemaCoordinateLookup::Argument(def) => def.directives.get(Sunset::NAME),
+        SchemaCoordinateLookup::Directive(_) => None,
+    };
+    directive?.location()
+}
+
+impl fmt::Display for SunsetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?
+        }
+        for problem in &self.problems {
+            writeln!(f, "{problem}")?
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SunsetEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: sunset {}, ", self.coordinate, self.sunset)?;
+        match self.days_remaining {
+            0 => write!(f, "today")?,
+            1 => write!(f, "in 1 day")?,
+            -1 => write!(f, "1 day ago")?,
+            days if days < 0 => write!(f, "{} days ago", -days)?,
+            days => write!(f, "in {days} days")?,
+        }
+        if let Some(reason) = &self.deprecation_reason {
+            write!(f, " (deprecated: {reason})")?
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SunsetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.coordinate, self.kind)
+    }
+}
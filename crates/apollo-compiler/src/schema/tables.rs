@@ -0,0 +1,417 @@
+use crate::collections::HashMap;
+use crate::schema::ExtendedType;
+use crate::schema::InputValueDefinition;
+use crate::schema::Type;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// A flat copy of the types, fields, and field arguments of a [`Schema`],
+/// stored as parallel arrays indexed by row number.
+///
+/// Each table is a set of slices of the same length, one entry per row,
+/// and references between tables are `u32` row indices.
+/// This allows iterating over many rows without following pointers,
+/// and passing the data across FFI boundaries one slice at a time.
+/// [`TypeView`], [`FieldView`], and [`ArgumentView`] are borrowed handles to a row
+/// with accessors for its columns.
+///
+/// * The type table has one row per type definition, in schema order
+///   (including built-in scalars and introspection types).
+/// * The field table has one row per field of object and interface types
+///   and per input field of input object types, grouped by parent type.
+///   Enum values and union members are not included.
+/// * The argument table has one row per field argument, grouped by parent field.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::schema::SchemaTables;
+/// use apollo_compiler::schema::TypeKind;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     type Query { user(id: ID!): User }
+///     type User { name: String }
+/// "#, "schema.graphql").unwrap();
+/// let tables = SchemaTables::new(&schema);
+///
+/// let user = tables.type_by_name("User").unwrap();
+/// assert_eq!(user.kind(), TypeKind::Object);
+/// let fields: Vec<_> = user.fields().map(|field| field.name().as_str()).collect();
+/// assert_eq!(fields, ["name"]);
+///
+/// // Columns are also available as slices
+/// let query_user = tables.type_by_name("Query").unwrap().fields().next().unwrap();
+/// assert_eq!(tables.field_type_indices()[query_user.index()], user.index() as u32);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaTables {
+    type_names: Vec<Name>,
+    type_kinds: Vec<TypeKind>,
+    /// One more entry than types: fields of type `i` are rows
+    /// `type_field_offsets[i]..type_field_offsets[i + 1]` of the field table
+    type_field_offsets: Vec<u32>,
+    type_indices: HashMap<Name, u32>,
+
+    field_names: Vec<Name>,
+    field_parents: Vec<u32>,
+    field_types: Vec<Type>,
+    field_type_indices: Vec<u32>,
+    /// One more entry than fields, like `type_field_offsets`
+    field_argument_offsets: Vec<u32>,
+
+    argument_names: Vec<Name>,
+    argument_parents: Vec<u32>,
+    argument_types: Vec<Type>,
+    argument_type_indices: Vec<u32>,
+}
+
+/// The kind of a type definition in [`SchemaTables`],
+/// with the same values as the `__TypeKind` introspection enum for named types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+/// A row of the type table of [`SchemaTables`]
+#[derive(Debug, Clone, Copy)]
+pub struct TypeView<'a> {
+    tables: &'a SchemaTables,
+    index: usize,
+}
+
+/// A row of the field table of [`SchemaTables`]
+#[derive(Debug, Clone, Copy)]
+pub struct FieldView<'a> {
+    tables: &'a SchemaTables,
+    index: usize,
+}
+
+/// A row of the argument table of [`SchemaTables`]
+#[derive(Debug, Clone, Copy)]
+pub struct ArgumentView<'a> {
+    tables: &'a SchemaTables,
+    index: usize,
+}
+
+impl SchemaTables {
+    /// Row index used for a reference to a type that is not defined in the schema.
+    /// This does not happen with a valid schema.
+    pub const NO_INDEX: u32 = u32::MAX;
+
+    /// Copy the types, fields, and field arguments of `schema` into flat tables
+    pub fn new(schema: &Schema) -> Self {
+        let mut tables = Self::default();
+        for (index, name) in schema.types.keys().enumerate() {
+            tables.type_indices.insert(name.clone(), index as u32);
+        }
+        for (name, def) in &schema.types {
+            let type_index = tables.type_names.len() as u32;
+            tables.type_names.push(name.clone());
+            tables.type_kinds.push(TypeKind::of(def));
+            tables
+                .type_field_offsets
+                .push(tables.field_names.len() as u32);
+            match def {
+                ExtendedType::Object(def) => {
+                    for field in def.fields.values() {
+                        tables.push_field(type_index, &field.name, &field.ty, &field.arguments)
+                    }
+                }
+                ExtendedType::Interface(def) => {
+                    for field in def.fields.values() {
+                        tables.push_field(type_index, &field.name, &field.ty, &field.arguments)
+                    }
+                }
+                ExtendedType::InputObject(def) => {
+                    for field in def.fields.values() {
+                        tables.push_field(type_index, &field.name, &field.ty, &[])
+                    }
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) | ExtendedType::Enum(_) => {}
+            }
+        }
+        tables
+            .type_field_offsets
+            .push(tables.field_names.len() as u32);
+        tables
+            .field_argument_offsets
+            .push(tables.argument_names.len() as u32);
+        tables
+    }
+
+    fn push_field(
+        &mut self,
+        parent: u32,
+        name: &Name,
+        ty: &Type,
+        arguments: &[Node<InputValueDefinition>],
+    ) {
+        let field_index = self.field_names.len() as u32;
+        self.field_names.push(name.clone());
+        self.field_parents.push(parent);
+        self.field_types.push(ty.clone());
+        self.field_type_indices.push(self.index_of(ty));
+        self.field_argument_offsets
+            .push(self.argument_names.len() as u32);
+        for argument in arguments {
+            self.argument_names.push(argument.name.clone());
+            self.argument_parents.push(field_index);
+            self.argument_types.push((*argument.ty).clone());
+            self.argument_type_indices.push(self.index_of(&argument.ty));
+        }
+    }
+
+    fn index_of(&self, ty: &Type) -> u32 {
+        self.type_indices
+            .get(ty.inner_named_type())
+            .copied()
+            .unwrap_or(Self::NO_INDEX)
+    }
+
+    /// Returns the row of the type table for the type with the given name
+    pub fn type_by_name(&self, name: &str) -> Option<TypeView<'_>> {
+        let index = *self.type_indices.get(name)?;
+        Some(self.type_(index as usize))
+    }
+
+    /// Returns the row of the field table for a field or input field of the named type
+    pub fn field_by_name(&self, type_name: &str, field_name: &str) -> Option<FieldView<'_>> {
+        self.type_by_name(type_name)?
+            .fields()
+            .find(|field| field.name() == field_name)
+    }
+
+    /// Returns a row of the type table. Panics if `index` is out of bounds.
+    pub fn type_(&self, index: usize) -> TypeView<'_> {
+        assert!(index < self.type_names.len(), "type index out of bounds");
+        TypeView {
+            tables: self,
+            index,
+        }
+    }
+
+    /// Returns a row of the field table. Panics if `index` is out of bounds.
+    pub fn field(&self, index: usize) -> FieldView<'_> {
+        assert!(index < self.field_names.len(), "field index out of bounds");
+        FieldView {
+            tables: self,
+            index,
+        }
+    }
+
+    /// Returns a row of the argument table. Panics if `index` is out of bounds.
+    pub fn argument(&self, index: usize) -> ArgumentView<'_> {
+        assert!(
+            index < self.argument_names.len(),
+            "argument index out of bounds"
+        );
+        ArgumentView {
+            tables: self,
+            index,
+        }
+    }
+
+    /// Iterate over rows of the type table
+    pub fn types(&self) -> impl ExactSizeIterator<Item = TypeView<'_>> {
+        (0..self.type_names.len()).map(|index| TypeView {
+            tables: self,
+            index,
+        })
+    }
+
+    /// Iterate over rows of the field table
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = FieldView<'_>> {
+        (0..self.field_names.len()).map(|index| FieldView {
+            tables: self,
+            index,
+        })
+    }
+
+    /// Iterate over rows of the argument table
+    pub fn arguments(&self) -> impl ExactSizeIterator<Item = ArgumentView<'_>> {
+        (0..self.argument_names.len()).map(|index| ArgumentView {
+            tables: self,
+            index,
+        })
+    }
+
+    /// The name column of the type table
+    pub fn type_names(&self) -> &[Name] {
+        &self.type_names
+    }
+
+    /// The kind column of the type table
+    pub fn type_kinds(&self) -> &[TypeKind] {
+        &self.type_kinds
+    }
+
+    /// Offsets into the field table, with one more entry than there are types:
+    /// fields of type `i` are rows `type_field_offsets()[i]..type_field_offsets()[i + 1]`
+    pub fn type_field_offsets(&self) -> &[u32] {
+        &self.type_field_offsets
+    }
+
+    /// The name column of the field table
+    pub fn field_names(&self) -> &[Name] {
+        &self.field_names
+    }
+
+    /// The column of the field table with the type table row of each field’s parent type
+    pub fn field_parents(&self) -> &[u32] {
+        &self.field_parents
+    }
+
+    /// The type column of the field table
+    pub fn field_types(&self) -> &[Type] {
+        &self.field_types
+    }
+
+    /// The column of the field table with the type table row
+    /// of the inner named type of each field’s type, or [`NO_INDEX`][Self::NO_INDEX]
+    pub fn field_type_indices(&self) -> &[u32] {
+        &self.field_type_indices
+    }
+
+    /// Offsets into the argument table, with one more entry than there are fields:
+    /// arguments of field `i` are rows
+    /// `field_argument_offsets()[i]..field_argument_offsets()[i + 1]`
+    pub fn field_argument_offsets(&self) -> &[u32] {
+        &self.field_argument_offsets
+    }
+
+    /// The name column of the argument table
+    pub fn argument_names(&self) -> &[Name] {
+        &self.argument_names
+    }
+
+    /// The column of the argument table with the field table row of each argument’s field
+    pub fn argument_parents(&self) -> &[u32] {
+        &self.argument_parents
+    }
+
+    /// The type column of the argument table
+    pub fn argument_types(&self) -> &[Type] {
+        &self.argument_types
+    }
+
+    /// The column of the argument table with the type table row
+    /// of the inner named type of each argument’s type, or [`NO_INDEX`][Self::NO_INDEX]
+    pub fn argument_type_indices(&self) -> &[u32] {
+        &self.argument_type_indices
+    }
+
+    fn type_at(&self, index: u32) -> Option<TypeView<'_>> {
+        (index != Self::NO_INDEX).then(|| self.type_(index as usize))
+    }
+}
+
+impl From<&Schema> for SchemaTables {
+    fn from(schema: &Schema) -> Self {
+        Self::new(schema)
+    }
+}
+
+impl TypeKind {
+    fn of(def: &ExtendedType) -> Self {
+        match def {
+            ExtendedType::Scalar(_) => Self::Scalar,
+            ExtendedType::Object(_) => Self::Object,
+            ExtendedType::Interface(_) => Self::Interface,
+            ExtendedType::Union(_) => Self::Union,
+            ExtendedType::Enum(_) => Self::Enum,
+            ExtendedType::InputObject(_) => Self::InputObject,
+        }
+    }
+}
+
+impl<'a> TypeView<'a> {
+    /// The row index in the type table
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn name(&self) -> &'a Name {
+        &self.tables.type_names[self.index]
+    }
+
+    pub fn kind(&self) -> TypeKind {
+        self.tables.type_kinds[self.index]
+    }
+
+    /// Fields of an object, interface, or input object type
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = FieldView<'a>> {
+        let tables = self.tables;
+        let start = tables.type_field_offsets[self.index] as usize;
+        let end = tables.type_field_offsets[self.index + 1] as usize;
+        (start..end).map(move |index| FieldView { tables, index })
+    }
+}
+
+impl<'a> FieldView<'a> {
+    /// The row index in the field table
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn name(&self) -> &'a Name {
+        &self.tables.field_names[self.index]
+    }
+
+    /// The type this field is defined on
+    pub fn parent(&self) -> TypeView<'a> {
+        self.tables
+            .type_(self.tables.field_parents[self.index] as usize)
+    }
+
+    pub fn ty(&self) -> &'a Type {
+        &self.tables.field_types[self.index]
+    }
+
+    /// The inner named type of this field’s type, if it is defined in the schema
+    pub fn named_type(&self) -> Option<TypeView<'a>> {
+        self.tables
+            .type_at(self.tables.field_type_indices[self.index])
+    }
+
+    pub fn arguments(&self) -> impl ExactSizeIterator<Item = ArgumentView<'a>> {
+        let tables = self.tables;
+        let start = tables.field_argument_offsets[self.index] as usize;
+        let end = tables.field_argument_offsets[self.index + 1] as usize;
+        (start..end).map(move |index| ArgumentView { tables, index })
+    }
+}
+
+impl<'a> ArgumentView<'a> {
+    /// The row index in the argument table
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn name(&self) -> &'a Name {
+        &self.tables.argument_names[self.index]
+    }
+
+    /// The field this argument is defined on
+    pub fn parent(&self) -> FieldView<'a> {
+        self.tables
+            .field(self.tables.argument_parents[self.index] as usize)
+    }
+
+    pub fn ty(&self) -> &'a Type {
+        &self.tables.argument_types[self.index]
+    }
+
+    /// The inner named type of this argument’s type, if it is defined in the schema
+    pub fn named_type(&self) -> Option<TypeView<'a>> {
+        self.tables
+            .type_at(self.tables.argument_type_indices[self.index])
+    }
+}
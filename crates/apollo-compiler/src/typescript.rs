@@ -0,0 +1,448 @@
+//! Generating TypeScript type definitions (`.d.ts`)
+//! for the types of a schema and for the results of operations.
+//!
+//! See [`TypeScriptExporter`].
+
+use crate::ast::DirectiveList;
+use crate::ast::Type;
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::executable::Field;
+use crate::executable::SelectionSet;
+use crate::execution::cost::group_fields;
+use crate::schema::Component;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt::Write;
+
+/// Configuration for generating TypeScript type definitions
+/// from a schema and from executable documents.
+///
+/// [`schema_types`][Self::schema_types] emits one declaration for each type of the schema
+/// (excluding built-in types), and one `…Args` interface for each field with arguments.
+/// [`operation_types`][Self::operation_types] emits the shape of the result and variables
+/// of each named operation, and of each named fragment.
+/// Operation types refer to custom scalars, enums, and input object types by name,
+/// so they are meant to be in the same file as schema types, or to import them.
+///
+/// Built-in scalars map to `string`, `number`, or `boolean`.
+/// Custom scalars map to `unknown` unless configured with [`scalar`][Self::scalar].
+/// Nullable types are emitted as a union with `null`.
+/// Input object fields and variables that are nullable or have a default value
+/// are optional properties.
+///
+/// ```
+/// use apollo_compiler::typescript::EnumStyle;
+/// use apollo_compiler::typescript::TypeScriptExporter;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(r#"
+///     scalar DateTime
+///     enum Role { ADMIN USER }
+///     type Query { user(id: ID!): User }
+///     type User { name: String! role: Role createdAt: DateTime }
+/// "#, "schema.graphql").unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "query GetUser($id: ID!) { user(id: $id) { name role } }",
+///     "query.graphql",
+/// ).unwrap();
+///
+/// let exporter = TypeScriptExporter::new()
+///     .scalar("DateTime", "string")
+///     .enum_style(EnumStyle::Enum);
+/// let schema_types = exporter.schema_types(&schema);
+/// assert!(schema_types.contains("export type DateTime = string;\n"));
+/// assert!(schema_types.contains("export enum Role {\n  ADMIN = \"ADMIN\",\n"));
+///
+/// let operation_types = exporter.operation_types(&schema, &document);
+/// assert_eq!(operation_types, r#"export type GetUserQuery = {
+///   user: {
+///     name: string;
+///     role: Role | null;
+///   } | null;
+/// };
+///
+/// export type GetUserQueryVariables = {
+///   id: string;
+/// };
+/// "#);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TypeScriptExporter {
+    scalars: HashMap<String, String>,
+    enum_style: EnumStyle,
+}
+
+/// How [`TypeScriptExporter`] emits GraphQL enum types
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumStyle {
+    /// A union of string literal types: `export type Role = "ADMIN" | "USER";`
+    #[default]
+    Union,
+    /// A TypeScript enum with string values: `export enum Role { ADMIN = "ADMIN", … }`
+    Enum,
+}
+
+impl TypeScriptExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a scalar type to a TypeScript type, such as `"string"` for a `DateTime` scalar.
+    ///
+    /// This can also override the mapping of built-in scalars, such as `"string | number"` for `ID`.
+    pub fn scalar(mut self, scalar_name: impl Into<String>, typescript: impl Into<String>) -> Self {
+        self.scalars.insert(scalar_name.into(), typescript.into());
+        self
+    }
+
+    /// Configure how enum types are emitted. Defaults to [`EnumStyle::Union`].
+    pub fn enum_style(mut self, style: EnumStyle) -> Self {
+        self.enum_style = style;
+        self
+    }
+
+    /// Returns TypeScript declarations for the types of `schema`, excluding built-in types
+    pub fn schema_types(&self, schema: &Valid<Schema>) -> String {
+        let mut out = String::new();
+        for (name, def) in &schema.types {
+            if def.is_built_in() {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            description(&mut out, "", def.description().map(|d| &**d), None);
+            match def {
+                ExtendedType::Scalar(_) => {
+                    let ty = self.scalars.get(name.as_str()).map_or("unknown", |ty| ty);
+                    writeln!(out, "export type {name} = {ty};").unwrap();
+                }
+                ExtendedType::Enum(def) => match self.enum_style {
+                    EnumStyle::Union => {
+                        let values = enum_values_union(def.values.keys());
+                        writeln!(out, "export type {name} = {values};").unwrap();
+                    }
+                    EnumStyle::Enum => {
+                        writeln!(out, "export enum {name} {{").unwrap();
+                        for (value, value_def) in &def.values {
+                            let description_text = value_def.description.as_deref();
+                            let deprecated = deprecation(&value_def.directives);
+                            description(&mut out, "  ", description_text, deprecated);
+                            writeln!(out, "  {value} = \"{value}\",").unwrap();
+                        }
+                        out.push_str("}\n");
+                    }
+                },
+                ExtendedType::Union(def) => {
+                    let members = def
+                        .members
+                        .iter()
+                        .map(|member| member.as_str())
+                        .collect::<Vec<_>>();
+                    let members = if members.is_empty() {
+                        "never".to_owned()
+                    } else {
+                        members.join(" | ")
+                    };
+                    writeln!(out, "export type {name} = {members};").unwrap();
+                }
+                ExtendedType::Object(def) => {
+                    writeln!(out, "export interface {name} {{").unwrap();
+                    writeln!(out, "  __typename?: \"{name}\";").unwrap();
+                    self.output_fields(&mut out, schema, def.fields.values().map(|f| &f.node));
+                    out.push_str("}\n");
+                    self.arguments_types(&mut out, schema, name, def.fields.values());
+                }
+                ExtendedType::Interface(def) => {
+                    writeln!(out, "export interface {name} {{").unwrap();
+                    self.output_fields(&mut out, schema, def.fields.values().map(|f| &f.node));
+                    out.push_str("}\n");
+                    self.arguments_types(&mut out, schema, name, def.fields.values());
+                }
+                ExtendedType::InputObject(def) => {
+                    writeln!(out, "export interface {name} {{").unwrap();
+                    self.input_values(&mut out, schema, def.fields.values().map(|f| &f.node));
+                    out.push_str("}\n");
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns TypeScript declarations for the result and variables of each named operation
+    /// of `document`, and for the shape of each named fragment.
+    ///
+    /// For an operation named `GetUser`, this emits `GetUserQuery` and `GetUserQueryVariables`
+    /// (or `…Mutation` and `…Subscription`). For a fragment named `UserFields`,
+    /// this emits `UserFieldsFragment`. Anonymous operations are skipped.
+    ///
+    /// Selections on an interface or union type are emitted as a union of one shape
+    /// per possible object type when their fields differ, such as with type conditions
+    /// or with `__typename`. Fields with a `@skip` or `@include` directive
+    /// are optional properties.
+    pub fn operation_types(
+        &self,
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+    ) -> String {
+        let mut out = String::new();
+        for operation in document.operations.named.values() {
+            let name = format!(
+                "{}{}",
+                operation.name.as_ref().unwrap(),
+                operation.operation_type.default_type_name()
+            );
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let result = self.selection_set(schema, document, &[&operation.selection_set], "");
+            writeln!(out, "export type {name} = {result};").unwrap();
+            out.push('\n');
+            if operation.variables.is_empty() {
+                writeln!(out, "export type {name}Variables = {{}};").unwrap();
+                continue;
+            }
+            writeln!(out, "export type {name}Variables = {{").unwrap();
+            for variable in &operation.variables {
+                let optional = !variable.ty.is_non_null() || variable.default_value.is_some();
+                let optional = if optional { "?" } else { "" };
+                let ty = self.input_type(schema, &variable.ty);
+                writeln!(out, "  {}{optional}: {ty};", variable.name).unwrap();
+            }
+            out.push_str("};\n");
+        }
+        for (name, fragment) in &document.fragments {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let shape = self.selection_set(schema, document, &[&fragment.selection_set], "");
+            writeln!(out, "export type {name}Fragment = {shape};").unwrap();
+        }
+        out
+    }
+
+    fn output_fields<'a>(
+        &self,
+        out: &mut String,
+        schema: &Schema,
+        fields: impl Iterator<Item = &'a Node<FieldDefinition>>,
+    ) {
+        for field in fields {
+            let deprecated = deprecation(&field.directives);
+            description(out, "  ", field.description.as_deref(), deprecated);
+            let ty = type_reference(&field.ty, &|name| self.named_type(schema, name, false));
+            writeln!(out, "  {}: {ty};", field.name).unwrap();
+        }
+    }
+
+    fn arguments_types<'a>(
+        &self,
+        out: &mut String,
+        schema: &Schema,
+        type_name: &Name,
+        fields: impl Iterator<Item = &'a Component<FieldDefinition>>,
+    ) {
+        for field in fields {
+            if field.arguments.is_empty() {
+                continue;
+            }
+            let mut field_name = field.name.as_str().chars();
+            let first = field_name.next().unwrap().to_ascii_uppercase();
+            let field_name = field_name.as_str();
+            out.push('\n');
+            writeln!(
+                out,
+                "export interface {type_name}{first}{field_name}Args {{"
+            )
+            .unwrap();
+            self.input_values(out, schema, field.arguments.iter());
+            out.push_str("}\n");
+        }
+    }
+
+    fn input_values<'a>(
+        &self,
+        out: &mut String,
+        schema: &Schema,
+        values: impl Iterator<Item = &'a Node<InputValueDefinition>>,
+    ) {
+        for value in values {
+            let deprecated = deprecation(&value.directives);
+            description(out, "  ", value.description.as_deref(), deprecated);
+            let optional = !value.ty.is_non_null() || value.default_value.is_some();
+            let optional = if optional { "?" } else { "" };
+            let ty = self.input_type(schema, &value.ty);
+            writeln!(out, "  {}{optional}: {ty};", value.name).unwrap();
+        }
+    }
+
+    fn input_type(&self, schema: &Schema, ty: &Type) -> String {
+        type_reference(ty, &|name| self.named_type(schema, name, false))
+    }
+
+    /// The TypeScript type for a scalar or enum, or the name of another type.
+    ///
+    /// In operation types, built-in enums (used by introspection) are emitted inline
+    /// since schema types do not include them.
+    fn named_type(&self, schema: &Schema, name: &Name, in_operation: bool) -> String {
+        if let Some(ty) = self.scalars.get(name.as_str()) {
+            return ty.clone();
+        }
+        match schema.types.get(name) {
+            Some(ExtendedType::Scalar(def)) if def.is_built_in() => match name.as_str() {
+                "Int" | "Float" => "number".into(),
+                "Boolean" => "boolean".into(),
+                _ => "string".into(),
+            },
+            Some(ExtendedType::Enum(def)) if in_operation && def.is_built_in() => {
+                enum_values_union(def.values.keys())
+            }
+            _ => name.to_string(),
+        }
+    }
+
+    /// The shape of the given merged selection sets, as an object literal type
+    /// or as a union of them.
+    fn selection_set(
+        &self,
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+        selection_sets: &[&SelectionSet],
+        indent: &str,
+    ) -> String {
+        let parent_type = &selection_sets[0].ty;
+        let mut shapes: Vec<String> = Vec::new();
+        for (runtime_type, def) in &schema.types {
+            if !def.is_object()
+                || (runtime_type != parent_type && !schema.is_subtype(parent_type, runtime_type))
+            {
+                continue;
+            }
+            let fields = group_fields(
+                document,
+                Some(schema),
+                Some(runtime_type),
+                selection_sets.iter().copied(),
+            );
+            let shape = self.object_shape(schema, document, runtime_type, &fields, indent);
+            if !shapes.contains(&shape) {
+                shapes.push(shape)
+            }
+        }
+        if shapes.is_empty() {
+            "never".to_owned()
+        } else {
+            shapes.join(" | ")
+        }
+    }
+
+    fn object_shape(
+        &self,
+        schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+        runtime_type: &Name,
+        fields: &IndexMap<&Name, Vec<&Field>>,
+        indent: &str,
+    ) -> String {
+        if fields.is_empty() {
+            return "{}".to_owned();
+        }
+        let nested_indent = format!("{indent}  ");
+        let mut shape = "{\n".to_owned();
+        for (response_key, fields) in fields {
+            let field = fields[0];
+            let conditional = fields
+                .iter()
+                .all(|field| field.directives.has("skip") || field.directives.has("include"));
+            let optional = if conditional { "?" } else { "" };
+            let ty = if field.name == "__typename" {
+                format!("\"{runtime_type}\"")
+            } else {
+                type_reference(&field.definition.ty, &|name| {
+                    if field.selection_set.selections.is_empty() {
+                        self.named_type(schema, name, true)
+                    } else {
+                        let selection_sets = fields
+                            .iter()
+                            .map(|field| &field.selection_set)
+                            .collect::<Vec<_>>();
+                        self.selection_set(schema, document, &selection_sets, &nested_indent)
+                    }
+                })
+            };
+            writeln!(shape, "{nested_indent}{response_key}{optional}: {ty};").unwrap();
+        }
+        shape.push_str(indent);
+        shape.push('}');
+        shape
+    }
+}
+
+/// Wraps the TypeScript type of the inner named type in lists and `| null` as needed
+fn type_reference(ty: &Type, named: &dyn Fn(&Name) -> String) -> String {
+    let non_null = match ty {
+        Type::Named(name) | Type::NonNullNamed(name) => named(name),
+        Type::List(item) | Type::NonNullList(item) => {
+            format!("Array<{}>", type_reference(item, named))
+        }
+    };
+    if ty.is_non_null() {
+        non_null
+    } else {
+        format!("{non_null} | null")
+    }
+}
+
+fn enum_values_union<'a>(values: impl Iterator<Item = &'a Name>) -> String {
+    let values = values
+        .map(|value| format!("\"{value}\""))
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        "never".to_owned()
+    } else {
+        values.join(" | ")
+    }
+}
+
+fn deprecation(directives: &DirectiveList) -> Option<&str> {
+    let directive = directives.get("deprecated")?;
+    Some(
+        directive
+            .specified_argument_by_name("reason")
+            .and_then(|reason| reason.as_str())
+            .unwrap_or("No longer supported"),
+    )
+}
+
+/// Emits a JSDoc comment if there is a description or deprecation
+fn description(
+    out: &mut String,
+    indent: &str,
+    description: Option<&str>,
+    deprecated: Option<&str>,
+) {
+    if description.is_none() && deprecated.is_none() {
+        return;
+    }
+    // `*/` would end the comment early
+    let escape = |text: &str| text.replace("*/", "*\\/");
+    writeln!(out, "{indent}/**").unwrap();
+    if let Some(description) = description {
+        for line in description.lines() {
+            writeln!(out, "{indent} * {}", escape(line).trim_end()).unwrap();
+        }
+    }
+    if let Some(reason) = deprecated {
+        writeln!(out, "{indent} * @deprecated {}", escape(reason)).unwrap();
+    }
+    writeln!(out, "{indent} */").unwrap();
+}
@@ -0,0 +1,338 @@
+//! Typed accessors for the structured data of some kinds of diagnostics,
+//! so that programs do not need to parse rendered messages.
+
+use crate::ast::DirectiveLocation;
+use crate::ast::Type;
+use crate::ast::Value;
+use crate::coordinate::SchemaCoordinate;
+use crate::executable::BuildError as ExecutableBuildError;
+use crate::executable::SelectionPath;
+use crate::validation::diagnostics::DiagnosticData as Data;
+use crate::validation::Details;
+use crate::validation::DiagnosticData;
+use crate::validation::FragmentSpreadTypes;
+use crate::Name;
+
+/// A field selection of a field that is not defined in the schema,
+/// as returned by [`DiagnosticData::as_undefined_field`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndefinedField<'a> {
+    /// The type of the selection set where the field is selected
+    pub type_name: &'a Name,
+    pub field_name: &'a Name,
+    /// The path to the field selection, ending with `field_name`
+    pub path: &'a SelectionPath,
+}
+
+/// An argument that is not defined, as returned by [`DiagnosticData::as_undefined_argument`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndefinedArgument<'a> {
+    pub name: &'a Name,
+    /// The field or directive where the argument was provided
+    pub coordinate: &'a SchemaCoordinate,
+}
+
+/// An enum value or input object field that is not defined,
+/// as returned by [`DiagnosticData::as_undefined_enum_value`]
+/// and [`DiagnosticData::as_undefined_input_field`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndefinedValue<'a> {
+    /// The name of the enum value or input field
+    pub name: &'a Name,
+    /// The enum or input object type
+    pub type_name: &'a Name,
+}
+
+/// A required argument that is not provided, as returned by
+/// [`DiagnosticData::as_required_argument`] and [`DiagnosticData::as_required_input_field`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredArgument<'a> {
+    pub name: &'a Name,
+    /// The field, directive, or input object where the argument or input field is defined.
+    /// For a required input field, this is a [`SchemaCoordinate::TypeAttribute`].
+    pub coordinate: SchemaCoordinate,
+    pub expected_type: &'a Type,
+}
+
+/// A variable used where its type is not allowed,
+/// as returned by [`DiagnosticData::as_disallowed_variable_usage`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisallowedVariableUsage<'a> {
+    pub variable: &'a Name,
+    pub variable_type: &'a Type,
+    pub argument: &'a Name,
+    pub argument_type: &'a Type,
+}
+
+/// A directive used at a location it does not support,
+/// as returned by [`DiagnosticData::as_unsupported_directive_location`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedDirectiveLocation<'a> {
+    pub directive_name: &'a Name,
+    pub location: DirectiveLocation,
+    /// The locations where the directive definition allows it
+    pub valid_locations: &'a [DirectiveLocation],
+}
+
+/// A value that does not match the expected type,
+/// as returned by [`DiagnosticData::as_unsupported_value_type`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedValueType<'a> {
+    pub value: &'a Value,
+    pub expected_type: &'a Type,
+}
+
+/// An object or interface type missing a field of an interface it implements,
+/// as returned by [`DiagnosticData::as_missing_interface_field`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingInterfaceField<'a> {
+    /// The object or interface type that declares `implements`
+    pub type_name: &'a Name,
+    pub interface: &'a Name,
+    pub field: &'a Name,
+}
+
+impl DiagnosticData {
+    fn data(&self) -> Option<&Data> {
+        match &self.details {
+            Details::CompilerDiagnostic(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// If this is a syntax error, returns its message
+    pub fn as_syntax_error(&self) -> Option<&str> {
+        match &self.details {
+            Details::SyntaxError { message } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// If this is a selection of a field not defined in the schema, returns its details
+    pub fn as_undefined_field(&self) -> Option<UndefinedField<'_>> {
+        match &self.details {
+            Details::ExecutableBuildError(ExecutableBuildError::UndefinedField {
+                type_name,
+                field_name,
+                path,
+            }) => Some(UndefinedField {
+                type_name,
+                field_name,
+                path,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a reference to a type not defined in the schema, returns the type name
+    pub fn as_undefined_type(&self) -> Option<&Name> {
+        match self.data()? {
+            Data::UndefinedDefinition { name } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// If this is an application of a directive not defined in the schema,
+    /// returns the directive name
+    pub fn as_undefined_directive(&self) -> Option<&Name> {
+        match self.data()? {
+            Data::UndefinedDirective { name } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// If this is a use of a variable not defined by the operation, returns the variable name
+    pub fn as_undefined_variable(&self) -> Option<&Name> {
+        match self.data()? {
+            Data::UndefinedVariable { name } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// If this is a spread of a fragment not defined in the document, returns the fragment name
+    pub fn as_undefined_fragment(&self) -> Option<&Name> {
+        match self.data()? {
+            Data::UndefinedFragment { name } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// If this is an argument not defined by its field or directive, returns its details
+    pub fn as_undefined_argument(&self) -> Option<UndefinedArgument<'_>> {
+        match self.data()? {
+            Data::UndefinedArgument {
+                name, coordinate, ..
+            } => Some(UndefinedArgument { name, coordinate }),
+            _ => None,
+        }
+    }
+
+    /// If this is an enum value not defined by its enum type, returns its details
+    pub fn as_undefined_enum_value(&self) -> Option<UndefinedValue<'_>> {
+        match self.data()? {
+            Data::UndefinedEnumValue {
+                value, definition, ..
+            } => Some(UndefinedValue {
+                name: value,
+                type_name: definition,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is an input object field not defined by its input object type,
+    /// returns its details
+    pub fn as_undefined_input_field(&self) -> Option<UndefinedValue<'_>> {
+        match self.data()? {
+            Data::UndefinedInputValue {
+                value, definition, ..
+            } => Some(UndefinedValue {
+                name: value,
+                type_name: definition,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a required field or directive argument that is not provided,
+    /// returns its details
+    pub fn as_required_argument(&self) -> Option<RequiredArgument<'_>> {
+        match self.data()? {
+            Data::RequiredArgument {
+                name,
+                coordinate,
+                expected_type,
+                ..
+            } => Some(RequiredArgument {
+                name,
+                coordinate: coordinate.clone(),
+                expected_type,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a required input object field that is not provided, returns its details
+    pub fn as_required_input_field(&self) -> Option<RequiredArgument<'_>> {
+        match self.data()? {
+            Data::RequiredField {
+                name,
+                coordinate,
+                expected_type,
+                ..
+            } => Some(RequiredArgument {
+                name,
+                coordinate: SchemaCoordinate::TypeAttribute(coordinate.clone()),
+                expected_type,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a variable defined but not used by its operation, returns the variable name
+    pub fn as_unused_variable(&self) -> Option<&Name> {
+        match self.data()? {
+            Data::UnusedVariable { name } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// If this is a fragment defined but not used by any operation, returns the fragment name
+    pub fn as_unused_fragment(&self) -> Option<&Name> {
+        match self.data()? {
+            Data::UnusedFragment { name } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// If this is a variable used in a position that expects an incompatible type,
+    /// returns its details
+    pub fn as_disallowed_variable_usage(&self) -> Option<DisallowedVariableUsage<'_>> {
+        match self.data()? {
+            Data::DisallowedVariableUsage {
+                variable,
+                variable_type,
+                argument,
+                argument_type,
+                ..
+            } => Some(DisallowedVariableUsage {
+                variable,
+                variable_type,
+                argument,
+                argument_type,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a directive used at a location its definition does not allow,
+    /// returns its details
+    pub fn as_unsupported_directive_location(&self) -> Option<UnsupportedDirectiveLocation<'_>> {
+        match self.data()? {
+            Data::UnsupportedLocation {
+                name,
+                location,
+                valid_locations,
+                ..
+            } => Some(UnsupportedDirectiveLocation {
+                directive_name: name,
+                location: *location,
+                valid_locations,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a value that does not match its expected type, returns its details
+    pub fn as_unsupported_value_type(&self) -> Option<UnsupportedValueType<'_>> {
+        match self.data()? {
+            Data::UnsupportedValueType { value, ty, .. } => Some(UnsupportedValueType {
+                value,
+                expected_type: ty,
+            }),
+            _ => None,
+        }
+    }
+
+    /// If this is a type missing a field of an interface it implements, returns its details
+    pub fn as_missing_interface_field(&self) -> Option<MissingInterfaceField<'_>> {
+        match self.data()? {
+            Data::MissingInterfaceField {
+                name,
+                interface,
+                field,
+                ..
+            } => Some(MissingInterfaceField {
+                type_name: name,
+                interface,
+                field,
+            }),
+            _ => None,
+        }
+    }
+
+    /// For an invalid fragment spread or inline fragment, whose type condition
+    /// cannot apply to the type where it is used, returns the possible object types
+    /// of both sides. Tooling can use this to explain why they do not overlap.
+    ///
+    /// Returns `None` for other diagnostics.
+    pub fn fragment_spread_types(&self) -> Option<FragmentSpreadTypes<'_>> {
+        match self.data()? {
+            Data::InvalidFragmentSpread {
+                name,
+                type_name,
+                type_condition,
+                parent_possible_types,
+                condition_possible_types,
+                ..
+            } => Some(FragmentSpreadTypes {
+                fragment_name: name.as_ref(),
+                parent_type: type_name,
+                parent_possible_types,
+                type_condition,
+                condition_possible_types,
+            }),
+            _ => None,
+        }
+    }
+}
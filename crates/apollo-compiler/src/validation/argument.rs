@@ -26,5 +26,38 @@ pub(crate) fn validate_arguments(
         } else {
             seen.insert(name, argument.location());
         }
+        validate_input_object_fields(diagnostics, &argument.value);
+    }
+}
+
+/// Input object field uniqueness, in nested values too:
+/// <https://spec.graphql.org/October2021/#sec-Input-Object-Field-Uniqueness>
+fn validate_input_object_fields(diagnostics: &mut DiagnosticList, value: &ast::Value) {
+    match value {
+        ast::Value::List(items) => {
+            for item in items {
+                validate_input_object_fields(diagnostics, item)
+            }
+        }
+        ast::Value::Object(fields) => {
+            let mut seen = HashMap::<_, Option<SourceSpan>>::default();
+            for (name, field_value) in fields {
+                if let Some(&original_definition) = seen.get(name) {
+                    let redefined_definition = name.location();
+                    diagnostics.push(
+                        redefined_definition,
+                        DiagnosticData::UniqueInputObjectField {
+                            name: name.clone(),
+                            original_definition,
+                            redefined_definition,
+                        },
+                    );
+                } else {
+                    seen.insert(name, name.location());
+                }
+                validate_input_object_fields(diagnostics, field_value)
+            }
+        }
+        _ => {}
     }
 }
@@ -0,0 +1,279 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::diagnostic::ToCliReport;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::parser::SourceSpan;
+use crate::schema::Type;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// An operation that is valid against the old schema given to [`check_operations`]
+/// but would break with the new schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenOperation {
+    /// The index of the document containing this operation, in the slice given to
+    /// [`check_operations`]
+    pub document_index: usize,
+    /// The name of the operation, or `None` for an anonymous operation
+    pub operation_name: Option<Name>,
+    /// What breaks, in document order
+    pub reasons: Vec<BreakingReason>,
+}
+
+/// Why a [`BrokenOperation`] breaks. Locations are in the operation’s document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BreakingReason {
+    /// The operation or a fragment it uses is not valid against the new schema,
+    /// for example because it selects a field that was removed,
+    /// does not provide an argument that became required,
+    /// or passes a value that the argument’s new type does not accept.
+    ValidationError {
+        message: String,
+        location: Option<SourceSpan>,
+    },
+    /// A selected field has a new type, and its values in a response
+    /// would not always match what clients expect from the old type.
+    /// Making a nullable output type non-null is not a breaking change.
+    FieldTypeChanged {
+        coordinate: TypeAttributeCoordinate,
+        old_type: Type,
+        new_type: Type,
+        location: Option<SourceSpan>,
+    },
+}
+
+/// Find which operations from a set of stored documents would break
+/// if a schema is replaced with a new version.
+///
+/// Each document is validated against both schemas.
+/// Operations that already have validation errors with `old_schema` are not reported,
+/// since they were broken to begin with.
+/// Other operations are reported if validation errors with `new_schema`
+/// are located in the operation or in a fragment it uses,
+/// or if the type of a field they select changed in an incompatible way.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::ast;
+/// use apollo_compiler::validation::check_operations;
+/// use apollo_compiler::validation::BreakingReason;
+/// use apollo_compiler::Schema;
+///
+/// let old_schema = Schema::parse_and_validate(
+///     "type Query { user(id: ID): User } type User { name: String age: Int }",
+///     "old.graphql",
+/// ).unwrap();
+/// let new_schema = Schema::parse_and_validate(
+///     "type Query { user(id: ID!): User } type User { name: String! age: String }",
+///     "new.graphql",
+/// ).unwrap();
+/// let documents = [
+///     ast::Document::parse("query A { user(id: 1) { name } }", "a.graphql").unwrap(),
+///     ast::Document::parse("query B { user { name } } query C { user(id: 1) { age } }", "bc.graphql").unwrap(),
+/// ];
+///
+/// let broken = check_operations(&old_schema, &new_schema, &documents);
+/// assert_eq!(broken.len(), 2);
+/// assert_eq!(broken[0].document_index, 1);
+/// assert_eq!(broken[0].operation_name.as_ref().unwrap(), "B");
+/// assert!(matches!(broken[0].reasons[0], BreakingReason::ValidationError { .. }));
+/// assert_eq!(broken[1].operation_name.as_ref().unwrap(), "C");
+/// assert!(matches!(broken[1].reasons[0], BreakingReason::FieldTypeChanged { .. }));
+/// ```
+pub fn check_operations(
+    old_schema: &Valid<Schema>,
+    new_schema: &Valid<Schema>,
+    documents: &[ast::Document],
+) -> Vec<BrokenOperation> {
+    let mut broken = Vec::new();
+    for (document_index, document) in documents.iter().enumerate() {
+        let scopes = operation_scopes(document);
+        let (old_document, old_errors) = match document.to_executable_validate(old_schema) {
+            Ok(valid) => (valid.into_inner(), None),
+            Err(with_errors) => (with_errors.partial, Some(with_errors.errors)),
+        };
+        let new_errors = document.to_executable_validate(new_schema).err();
+        for scope in &scopes {
+            if old_errors
+                .iter()
+                .any(|errors| errors.iter().any(|e| scope.contains(e.error.location())))
+            {
+                continue;
+            }
+            let mut reasons = Vec::new();
+            if let Some(errors) = &new_errors {
+                validation_errors(&errors.errors, scope, &mut reasons)
+            }
+            if let Some(operation) = get_operation(&old_document, scope.name.as_ref()) {
+                field_type_changes(&old_document, new_schema, operation, &mut reasons)
+            }
+            if !reasons.is_empty() {
+                reasons.sort_by_key(|reason| {
+                    let location = match reason {
+                        BreakingReason::ValidationError { location, .. }
+                        | BreakingReason::FieldTypeChanged { location, .. } => location,
+                    };
+                    location.map(|loc| (loc.file_id(), loc.offset()))
+                });
+                broken.push(BrokenOperation {
+                    document_index,
+                    operation_name: scope.name.clone(),
+                    reasons,
+                })
+            }
+        }
+    }
+    broken
+}
+
+/// Source ranges of an operation definition and of the fragment definitions it uses
+struct OperationScope {
+    name: Option<Name>,
+    spans: Vec<SourceSpan>,
+}
+
+impl OperationScope {
+    /// Diagnostics without a location are considered to apply to every operation
+    fn contains(&self, location: Option<SourceSpan>) -> bool {
+        let Some(location) = location else {
+            return true;
+        };
+        self.spans.iter().any(|span| {
+            span.file_id() == location.file_id()
+                && (span.offset()..span.end_offset()).contains(&location.offset())
+        })
+    }
+}
+
+fn operation_scopes(document: &ast::Document) -> Vec<OperationScope> {
+    let fragments: HashMap<&Name, &Node<ast::FragmentDefinition>> = document
+        .definitions
+        .iter()
+        .filter_map(|def| def.as_fragment_definition())
+        .map(|def| (&def.name, def))
+        .collect();
+    document
+        .definitions
+        .iter()
+        .filter_map(|def| def.as_operation_definition())
+        .map(|operation| {
+            let mut spans: Vec<_> = operation.location().into_iter().collect();
+            let mut seen = HashSet::default();
+            let mut stack = vec![&operation.selection_set];
+            while let Some(selection_set) = stack.pop() {
+                for selection in selection_set {
+                    match selection {
+                        ast::Selection::Field(field) => stack.push(&field.selection_set),
+                        ast::Selection::InlineFragment(inline) => stack.push(&inline.selection_set),
+                        ast::Selection::FragmentSpread(spread) => {
+                            if !seen.insert(&spread.fragment_name) {
+                                continue;
+                            }
+                            if let Some(fragment) = fragments.get(&spread.fragment_name) {
+                                spans.extend(fragment.location());
+                                stack.push(&fragment.selection_set)
+                            }
+                        }
+                    }
+                }
+            }
+            OperationScope {
+                name: operation.name.clone(),
+                spans,
+            }
+        })
+        .collect()
+}
+
+fn validation_errors(
+    errors: &DiagnosticList,
+    scope: &OperationScope,
+    reasons: &mut Vec<BreakingReason>,
+) {
+    for diagnostic in errors.iter() {
+        let location = diagnostic.error.location();
+        if scope.contains(location) {
+            reasons.push(BreakingReason::ValidationError {
+                message: diagnostic.error.to_string(),
+                location,
+            })
+        }
+    }
+}
+
+fn get_operation<'doc>(
+    document: &'doc ExecutableDocument,
+    name: Option<&Name>,
+) -> Option<&'doc Operation> {
+    match name {
+        Some(name) => document.operations.named.get(name),
+        None => document.operations.anonymous.as_ref(),
+    }
+    .map(|operation| &**operation)
+}
+
+fn field_type_changes(
+    old_document: &ExecutableDocument,
+    new_schema: &Schema,
+    operation: &Operation,
+    reasons: &mut Vec<BreakingReason>,
+) {
+    let mut seen_fragments = HashSet::default();
+    let mut stack: Vec<&SelectionSet> = vec![&operation.selection_set];
+    while let Some(selection_set) = stack.pop() {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    stack.push(&field.selection_set);
+                    let Ok(new_definition) = new_schema.type_field(&selection_set.ty, &field.name)
+                    else {
+                        // Removed fields are reported as validation errors
+                        continue;
+                    };
+                    let old_type = field.ty();
+                    if !is_compatible_output_type(old_type, &new_definition.ty) {
+                        reasons.push(BreakingReason::FieldTypeChanged {
+                            coordinate: TypeAttributeCoordinate {
+                                ty: selection_set.ty.clone(),
+                                attribute: field.name.clone(),
+                            },
+                            old_type: old_type.clone(),
+                            new_type: new_definition.ty.clone(),
+                            location: field.location(),
+                        })
+                    }
+                }
+                Selection::InlineFragment(inline) => stack.push(&inline.selection_set),
+                Selection::FragmentSpread(spread) => {
+                    if seen_fragments.insert(&spread.fragment_name) {
+                        if let Some(fragment) = old_document.fragments.get(&spread.fragment_name) {
+                            stack.push(&fragment.selection_set)
+                        }
+                    }
+                }
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+}
+
+/// Whether every value of `new` in a response is also a valid value of `old`
+fn is_compatible_output_type(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new) | Type::NonNullNamed(new))
+        | (Type::NonNullNamed(old), Type::NonNullNamed(new)) => old == new,
+        (Type::List(old), Type::List(new) | Type::NonNullList(new))
+        | (Type::NonNullList(old), Type::NonNullList(new)) => is_compatible_output_type(old, new),
+        _ => false,
+    }
+}
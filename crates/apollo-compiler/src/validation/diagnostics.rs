@@ -27,6 +27,12 @@ pub(crate) enum DiagnosticData {
         original_definition: Option<SourceSpan>,
         redefined_definition: Option<SourceSpan>,
     },
+    #[error("the input object field `{name}` is provided multiple times")]
+    UniqueInputObjectField {
+        name: Name,
+        original_definition: Option<SourceSpan>,
+        redefined_definition: Option<SourceSpan>,
+    },
     #[error("the value `{name}` is defined multiple times")]
     UniqueInputValue {
         name: Name,
@@ -195,6 +201,13 @@ pub(crate) enum DiagnosticData {
         output_type: Name,
         describe_type: &'static str,
     },
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    #[error("list nullability `{nullability}` is nested deeper than the type of `{coordinate}`")]
+    InvalidListNullability {
+        coordinate: TypeAttributeCoordinate,
+        nullability: ast::Nullability,
+        ty: Type,
+    },
     #[error(
         "{} must have a composite type in its type condition",
         fragment_name_or_inline(name)
@@ -215,6 +228,11 @@ pub(crate) enum DiagnosticData {
         /// Type name the fragment is being applied to
         type_name: Name,
         type_condition: Name,
+        /// Object types that `type_name` can be, which do not intersect with
+        /// `condition_possible_types`
+        parent_possible_types: Vec<Name>,
+        /// Object types that `type_condition` can be
+        condition_possible_types: Vec<Name>,
         /// Source location where the fragment is defined
         fragment_location: Option<SourceSpan>,
         /// Source location of the type the fragment is being applied to.
@@ -292,6 +310,27 @@ pub(crate) enum DiagnosticData {
         "{describe} cannot be named `{name}` as names starting with two underscores are reserved"
     )]
     ReservedName { name: Name, describe: &'static str },
+    #[error(
+        "field `{coordinate}` of a oneOf input object must be nullable and have no default value"
+    )]
+    OneOfInputObjectField {
+        coordinate: TypeAttributeCoordinate,
+        /// Whether the field is invalid because it has a default value, rather than being non-null
+        has_default_value: bool,
+    },
+    #[error("exactly one field of oneOf input object `{name}` must be provided, and it must not be null")]
+    OneOfInputValue {
+        /// Name of the input object type
+        name: Name,
+        definition_location: Option<SourceSpan>,
+    },
+    #[error("variable `${variable}` must be non-null to be used as a field of oneOf input object `{name}`")]
+    OneOfNullableVariable {
+        variable: Name,
+        /// Name of the input object type
+        name: Name,
+        variable_location: Option<SourceSpan>,
+    },
 }
 
 impl DiagnosticData {
@@ -328,6 +367,23 @@ impl DiagnosticData {
                     "`{name}` argument must only be provided once."
                 ));
             }
+            DiagnosticData::UniqueInputObjectField {
+                name,
+                original_definition,
+                redefined_definition,
+            } => {
+                report.with_label_opt(
+                    *original_definition,
+                    format_args!("previously provided `{name}` here"),
+                );
+                report.with_label_opt(
+                    *redefined_definition,
+                    format_args!("`{name}` provided again here"),
+                );
+                report.with_help(format_args!(
+                    "`{name}` field must only be provided once in an input object value."
+                ));
+            }
             DiagnosticData::UniqueInputValue {
                 name,
                 original_definition,
@@ -613,6 +669,19 @@ impl DiagnosticData {
                     ),
                 );
             }
+            #[cfg(feature = "experimental-client-controlled-nullability")]
+            DiagnosticData::InvalidListNullability {
+                coordinate: _,
+                nullability,
+                ty,
+            } => {
+                report.with_label_opt(
+                    main_location,
+                    format_args!(
+                        "`{nullability}` has more nested lists than the field type `{ty}`"
+                    ),
+                );
+            }
             DiagnosticData::InvalidFragmentTarget { name: _, ty } => {
                 report.with_label_opt(
                     main_location,
@@ -624,6 +693,8 @@ impl DiagnosticData {
                 name,
                 type_name: _,
                 type_condition,
+                parent_possible_types: _,
+                condition_possible_types: _,
                 fragment_location,
                 type_location,
             } => {
@@ -721,6 +792,36 @@ impl DiagnosticData {
             DiagnosticData::ReservedName { name, .. } => {
                 report.with_label_opt(name.location(), "Pick a different name here");
             }
+            DiagnosticData::OneOfInputObjectField {
+                has_default_value, ..
+            } => {
+                if *has_default_value {
+                    report.with_label_opt(main_location, "remove the default value");
+                } else {
+                    report.with_label_opt(main_location, "make this field nullable");
+                }
+            }
+            DiagnosticData::OneOfInputValue {
+                name,
+                definition_location,
+            } => {
+                report.with_label_opt(main_location, "provide exactly one non-null field");
+                report.with_label_opt(
+                    *definition_location,
+                    format_args!("oneOf input object `{name}` defined here"),
+                );
+            }
+            DiagnosticData::OneOfNullableVariable {
+                variable,
+                variable_location,
+                ..
+            } => {
+                report.with_label_opt(main_location, format_args!("`${variable}` used here"));
+                report.with_label_opt(
+                    *variable_location,
+                    format_args!("`${variable}` declared with a nullable type here"),
+                );
+            }
         }
     }
 
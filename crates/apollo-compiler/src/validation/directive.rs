@@ -6,6 +6,7 @@ use crate::coordinate::DirectiveArgumentCoordinate;
 use crate::coordinate::DirectiveCoordinate;
 use crate::schema;
 use crate::schema::validation::BuiltInScalars;
+use crate::schema::SchemaBuilder;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DiagnosticList;
 use crate::validation::RecursionGuard;
@@ -206,8 +207,12 @@ pub(crate) fn validate_directives<'dir>(
 
         let name = &dir.name;
         let loc = dir.location();
-        let directive_definition =
-            schema.and_then(|schema| Some((schema, schema.directive_definitions.get(name)?)));
+        // Without a schema, only built-in directives are known
+        let schema_or_built_in = schema.unwrap_or(&SchemaBuilder::built_in().schema);
+        let directive_definition = schema_or_built_in
+            .directive_definitions
+            .get(name)
+            .map(|def| (schema_or_built_in, def));
 
         if let Some(&original_loc) = seen_directives.get(name) {
             let is_repeatable = directive_definition
@@ -313,11 +318,13 @@ pub(crate) fn validate_directives<'dir>(
                     );
                 }
             }
-        } else {
+        } else if schema.is_some() {
             diagnostics.push(
                 loc,
                 DiagnosticData::UndefinedDirective { name: name.clone() },
             )
+        } else {
+            super::variable::validate_defined_variables(diagnostics, &dir.arguments, var_defs)
         }
     }
 }
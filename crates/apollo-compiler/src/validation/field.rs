@@ -37,10 +37,34 @@ pub(crate) fn validate_field(
     // inside a selection set that has a wrong field, or if we are validating a standalone
     // operation without a schema.
     let Some((schema, against_type)) = against_type else {
+        if context.schema().is_none() {
+            super::variable::validate_defined_variables(
+                diagnostics,
+                &field.arguments,
+                context.variables,
+            );
+        }
         return;
     };
 
     if let Ok(field_definition) = schema.type_field(against_type, &field.name) {
+        #[cfg(feature = "experimental-client-controlled-nullability")]
+        if let Some(nullability) = &field.nullability {
+            if nullability.apply_to(&field_definition.ty).is_none() {
+                diagnostics.push(
+                    field.location(),
+                    DiagnosticData::InvalidListNullability {
+                        coordinate: TypeAttributeCoordinate {
+                            ty: against_type.clone(),
+                            attribute: field.name.clone(),
+                        },
+                        nullability: nullability.clone(),
+                        ty: field_definition.ty.clone(),
+                    },
+                );
+            }
+        }
+
         for argument in &field.arguments {
             let arg_definition = field_definition
                 .arguments
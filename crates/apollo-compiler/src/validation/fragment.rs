@@ -17,6 +17,27 @@ use crate::Name;
 use crate::Node;
 use std::borrow::Cow;
 
+/// The types involved in an invalid fragment spread or inline fragment,
+/// as returned by [`DiagnosticData::fragment_spread_types`][crate::validation::DiagnosticData::fragment_spread_types].
+///
+/// A fragment can be applied if its possible types intersect with those of the parent type.
+/// For an invalid fragment, `parent_possible_types` and `condition_possible_types` are disjoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentSpreadTypes<'a> {
+    /// The name of the fragment, or `None` for an inline fragment
+    pub fragment_name: Option<&'a Name>,
+    /// The type of the selection set where the fragment is applied
+    pub parent_type: &'a NamedType,
+    /// The object types that `parent_type` can be, in schema order:
+    /// itself for an object type, its implementers for an interface,
+    /// or its members for a union
+    pub parent_possible_types: &'a [NamedType],
+    /// The type condition of the fragment
+    pub type_condition: &'a NamedType,
+    /// The object types that `type_condition` can be, in schema order
+    pub condition_possible_types: &'a [NamedType],
+}
+
 /// Given a type definition, find all the type names that can be used for fragment spreading.
 ///
 /// Spec: https://spec.graphql.org/October2021/#GetPossibleTypes()
@@ -81,9 +102,11 @@ fn validate_fragment_spread_type(
 
     let mut applicable_types = concrete_parent_types.intersection(&concrete_condition_types);
     if applicable_types.next().is_none() {
+        let parent_possible_types: Vec<_> = concrete_parent_types.iter().cloned().collect();
+        let condition_possible_types: Vec<_> = concrete_condition_types.iter().cloned().collect();
         // Report specific errors for the different kinds of fragments.
         match selection {
-            executable::Selection::Field(_) => unreachable!(),
+            executable::Selection::Field(_) | executable::Selection::Invalid(_) => unreachable!(),
             executable::Selection::FragmentSpread(spread) => {
                 // TODO(@goto-bus-stop) Can we guarantee this unwrap()?
                 let fragment_definition = document.fragments.get(&spread.fragment_name).unwrap();
@@ -94,6 +117,8 @@ fn validate_fragment_spread_type(
                         name: Some(spread.fragment_name.clone()),
                         type_name: against_type.clone(),
                         type_condition: type_condition.clone(),
+                        parent_possible_types,
+                        condition_possible_types,
                         fragment_location: fragment_definition.location(),
                         type_location: against_type_definition.location(),
                     },
@@ -105,6 +130,8 @@ fn validate_fragment_spread_type(
                     name: None,
                     type_name: against_type.clone(),
                     type_condition: type_condition.clone(),
+                    parent_possible_types,
+                    condition_possible_types,
                     fragment_location: inline.location(),
                     type_location: against_type_definition.location(),
                 },
@@ -295,6 +322,7 @@ pub(crate) fn validate_fragment_cycles(
                 executable::Selection::Field(field) => {
                     detect_fragment_cycles(document, &field.selection_set, visited)?;
                 }
+                executable::Selection::Invalid(_) => {}
             }
         }
 
@@ -396,6 +424,7 @@ fn selection_uses_fragment(sel: &executable::Selection, name: &str) -> bool {
         executable::Selection::FragmentSpread(fragment) => return fragment.fragment_name == name,
         executable::Selection::Field(field) => &field.selection_set,
         executable::Selection::InlineFragment(inline) => &inline.selection_set,
+        executable::Selection::Invalid(_) => return false,
     };
 
     sub_selections
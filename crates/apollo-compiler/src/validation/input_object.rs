@@ -1,5 +1,6 @@
 use crate::ast;
 use crate::collections::HashMap;
+use crate::coordinate::TypeAttributeCoordinate;
 use crate::schema::validation::BuiltInScalars;
 use crate::schema::InputObjectType;
 use crate::validation::diagnostics::DiagnosticData;
@@ -117,6 +118,26 @@ pub(crate) fn validate_input_object_definition(
         "an input object field",
     );
 
+    // Fields of a oneOf input object must be nullable without a default value
+    // https://spec.graphql.org/draft/#sec-OneOf-Input-Objects
+    if schema.is_one_of(input_object) {
+        for field in input_object.fields.values() {
+            let has_default_value = field.default_value.is_some();
+            if field.ty.is_non_null() || has_default_value {
+                diagnostics.push(
+                    field.location(),
+                    DiagnosticData::OneOfInputObjectField {
+                        coordinate: TypeAttributeCoordinate {
+                            ty: input_object.name.clone(),
+                            attribute: field.name.clone(),
+                        },
+                        has_default_value,
+                    },
+                );
+            }
+        }
+    }
+
     // validate there is at least one input value on the input object type
     // https://spec.graphql.org/draft/#sel-HAHhBXDBABAB5BvgD
     if input_object.fields.is_empty() {
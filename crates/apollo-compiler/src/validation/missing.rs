@@ -0,0 +1,241 @@
+use crate::collections::IndexMap;
+use crate::executable::BuildError as ExecutableBuildError;
+use crate::executable::ExecutableDefinitionName;
+use crate::parser::SourceSpan;
+use crate::schema::BuildError as SchemaBuildError;
+use crate::validation::Details;
+use crate::validation::DiagnosticList;
+use crate::validation::WithErrors;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use std::fmt;
+
+/// Designates by name a top-level definition of a schema or executable document.
+///
+/// Unlike source locations or indices, this stays the same across
+/// edits of unrelated parts of the source text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DefinitionName {
+    /// The `schema` definition, or its extensions
+    SchemaDefinition,
+    /// A type definition, or its extensions
+    Type(Name),
+    /// A directive definition
+    Directive(Name),
+    /// An operation, or the anonymous operation if `None`
+    Operation(Option<Name>),
+    /// A fragment definition
+    Fragment(Name),
+}
+
+/// A definition that a [`WithErrors`] partial result is missing, in whole or in part.
+///
+/// Returned by [`WithErrors::<Schema>::missing_components`]
+/// and [`WithErrors::<ExecutableDocument>::missing_components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingComponent {
+    pub definition: DefinitionName,
+    /// `true` if the definition is absent from [`WithErrors::partial`].
+    /// `false` if it is present but some of its source was left out,
+    /// such as a duplicate field definition or a selection of an undefined field.
+    pub dropped: bool,
+    /// Locations of the source that was left out, one for each related error
+    pub locations: Vec<SourceSpan>,
+}
+
+impl WithErrors<Schema> {
+    /// Returns definitions that are missing from [`partial`][Self::partial] in whole or in part
+    /// because of a build error, in the order of their first error.
+    ///
+    /// This lets tools decide whether the partial schema is usable for their purpose,
+    /// for example if it has every type they need.
+    ///
+    /// Not listed are syntax errors (the parser recovers from them, but what was lost
+    /// cannot be attributed to a definition) and executable definitions,
+    /// which do not belong in a schema.
+    /// Check [`errors`][Self::errors] for those.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::name;
+    /// use apollo_compiler::validation::DefinitionName;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let input = "type Query { a: Int a: Int } extend type Missing { b: Int }";
+    /// let with_errors = Schema::parse(input, "schema.graphql").unwrap_err();
+    /// let missing = with_errors.missing_components();
+    /// assert_eq!(missing[0].definition, DefinitionName::Type(name!("Query")));
+    /// assert!(!missing[0].dropped);
+    /// assert_eq!(missing[1].definition, DefinitionName::Type(name!("Missing")));
+    /// assert!(missing[1].dropped);
+    /// ```
+    pub fn missing_components(&self) -> Vec<MissingComponent> {
+        collect(&self.errors, |details| {
+            let Details::SchemaBuildError(error) = details else {
+                return None;
+            };
+            let definition = match error {
+                SchemaBuildError::ExecutableDefinition { .. }
+                | SchemaBuildError::BuiltInScalarTypeRedefinition => return None,
+                SchemaBuildError::SchemaDefinitionCollision { .. }
+                | SchemaBuildError::OrphanSchemaExtension
+                | SchemaBuildError::DuplicateRootOperation { .. } => {
+                    DefinitionName::SchemaDefinition
+                }
+                SchemaBuildError::DirectiveDefinitionCollision { name, .. } => {
+                    DefinitionName::Directive(name.clone())
+                }
+                SchemaBuildError::TypeDefinitionCollision { name, .. }
+                | SchemaBuildError::OrphanTypeExtension { name }
+                | SchemaBuildError::TypeExtensionKindMismatch { name, .. } => {
+                    DefinitionName::Type(name.clone())
+                }
+                SchemaBuildError::DuplicateImplementsInterfaceInObject { type_name, .. }
+                | SchemaBuildError::DuplicateImplementsInterfaceInInterface { type_name, .. }
+                | SchemaBuildError::ObjectFieldNameCollision { type_name, .. }
+                | SchemaBuildError::InterfaceFieldNameCollision { type_name, .. }
+                | SchemaBuildError::EnumValueNameCollision { type_name, .. }
+                | SchemaBuildError::UnionMemberNameCollision { type_name, .. }
+                | SchemaBuildError::InputFieldNameCollision { type_name, .. } => {
+                    DefinitionName::Type(type_name.clone())
+                }
+            };
+            let dropped = match &definition {
+                DefinitionName::SchemaDefinition => false,
+                DefinitionName::Type(name) => !self.partial.types.contains_key(name),
+                DefinitionName::Directive(name) => {
+                    !self.partial.directive_definitions.contains_key(name)
+                }
+                DefinitionName::Operation(_) | DefinitionName::Fragment(_) => unreachable!(),
+            };
+            Some((definition, dropped))
+        })
+    }
+}
+
+impl WithErrors<ExecutableDocument> {
+    /// Returns operations and fragments that are missing from [`partial`][Self::partial]
+    /// in whole or in part because of a build error, in the order of their first error.
+    ///
+    /// This lets tools decide whether the partial document is usable for their purpose,
+    /// for example if the operation they want to run is complete.
+    ///
+    /// Not listed are syntax errors (the parser recovers from them, but what was lost
+    /// cannot be attributed to a definition) and type system definitions,
+    /// which do not belong in an executable document.
+    /// Check [`errors`][Self::errors] for those.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use apollo_compiler::name;
+    /// use apollo_compiler::validation::DefinitionName;
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// let input = "query A { a } query B { a b } mutation C { a }";
+    /// let with_errors = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap_err();
+    /// let missing = with_errors.missing_components();
+    /// assert_eq!(missing[0].definition, DefinitionName::Operation(Some(name!("B"))));
+    /// assert!(!missing[0].dropped);
+    /// assert_eq!(missing[1].definition, DefinitionName::Operation(Some(name!("C"))));
+    /// assert!(missing[1].dropped);
+    /// assert!(with_errors.partial.operations.get(Some("A")).is_ok());
+    /// ```
+    pub fn missing_components(&self) -> Vec<MissingComponent> {
+        collect(&self.errors, |details| {
+            let Details::ExecutableBuildError(error) = details else {
+                return None;
+            };
+            let definition = match error {
+                ExecutableBuildError::TypeSystemDefinition { .. } => return None,
+                ExecutableBuildError::AmbiguousAnonymousOperation => {
+                    DefinitionName::Operation(None)
+                }
+                ExecutableBuildError::OperationNameCollision {
+                    name_at_previous_location,
+                } => DefinitionName::Operation(Some(name_at_previous_location.clone())),
+                ExecutableBuildError::UndefinedRootOperation { name, .. } => {
+                    DefinitionName::Operation(name.clone())
+                }
+                ExecutableBuildError::FragmentNameCollision {
+                    name_at_previous_location,
+                } => DefinitionName::Fragment(name_at_previous_location.clone()),
+                ExecutableBuildError::UndefinedTypeInNamedFragmentTypeCondition {
+                    fragment_name,
+                    ..
+                } => DefinitionName::Fragment(fragment_name.clone()),
+                ExecutableBuildError::UndefinedTypeInInlineFragmentTypeCondition {
+                    path, ..
+                }
+                | ExecutableBuildError::SubselectionOnScalarType { path, .. }
+                | ExecutableBuildError::SubselectionOnEnumType { path, .. }
+                | ExecutableBuildError::UndefinedField { path, .. } => path.root.clone().into(),
+                // Validation errors: nothing was left out
+                ExecutableBuildError::SubscriptionUsesMultipleFields { .. }
+                | ExecutableBuildError::SubscriptionUsesIntrospection { .. }
+                | ExecutableBuildError::ConflictingFieldType(_)
+                | ExecutableBuildError::ConflictingFieldArgument(_)
+                | ExecutableBuildError::ConflictingFieldName(_) => return None,
+            };
+            let dropped = match &definition {
+                DefinitionName::Operation(None) => self.partial.operations.anonymous.is_none(),
+                DefinitionName::Operation(Some(name)) => {
+                    !self.partial.operations.named.contains_key(name)
+                }
+                DefinitionName::Fragment(name) => !self.partial.fragments.contains_key(name),
+                DefinitionName::SchemaDefinition
+                | DefinitionName::Type(_)
+                | DefinitionName::Directive(_) => unreachable!(),
+            };
+            Some((definition, dropped))
+        })
+    }
+}
+
+fn collect(
+    errors: &DiagnosticList,
+    mut definition: impl FnMut(&Details) -> Option<(DefinitionName, bool)>,
+) -> Vec<MissingComponent> {
+    let mut components = IndexMap::<DefinitionName, MissingComponent>::default();
+    for diagnostic in &errors.diagnostics_data {
+        let Some((name, dropped)) = definition(&diagnostic.details) else {
+            continue;
+        };
+        let component = components
+            .entry(name.clone())
+            .or_insert_with(|| MissingComponent {
+                definition: name,
+                dropped,
+                locations: Vec::new(),
+            });
+        component.locations.extend(diagnostic.location);
+    }
+    components.into_values().collect()
+}
+
+impl From<ExecutableDefinitionName> for DefinitionName {
+    fn from(name: ExecutableDefinitionName) -> Self {
+        match name {
+            ExecutableDefinitionName::AnonymousOperation(_) => Self::Operation(None),
+            ExecutableDefinitionName::NamedOperation(_, name) => Self::Operation(Some(name)),
+            ExecutableDefinitionName::Fragment(name) => Self::Fragment(name),
+        }
+    }
+}
+
+impl fmt::Display for DefinitionName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SchemaDefinition => f.write_str("schema definition"),
+            Self::Type(name) => write!(f, "type `{name}`"),
+            Self::Directive(name) => write!(f, "directive `@{name}`"),
+            Self::Operation(None) => f.write_str("anonymous operation"),
+            Self::Operation(Some(name)) => write!(f, "operation `{name}`"),
+            Self::Fragment(name) => write!(f, "fragment `{name}`"),
+        }
+    }
+}
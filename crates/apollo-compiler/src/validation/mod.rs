@@ -6,7 +6,9 @@ use crate::coordinate::SchemaCoordinate;
 use crate::ExecutableDocument;
 use crate::Schema;
 
+mod accessors;
 pub(crate) mod argument;
+mod compatibility;
 pub(crate) mod diagnostics;
 pub(crate) mod directive;
 pub(crate) mod enum_;
@@ -14,16 +16,45 @@ pub(crate) mod field;
 pub(crate) mod fragment;
 pub(crate) mod input_object;
 pub(crate) mod interface;
+mod missing;
+mod multi_schema;
 pub(crate) mod object;
 pub(crate) mod operation;
+pub(crate) mod options;
+pub(crate) mod rules;
 pub(crate) mod scalar;
 pub(crate) mod schema;
 pub(crate) mod selection;
 pub(crate) mod union_;
+mod validator;
 pub(crate) mod value;
 pub(crate) mod variable;
 
+pub use self::accessors::DisallowedVariableUsage;
+pub use self::accessors::MissingInterfaceField;
+pub use self::accessors::RequiredArgument;
+pub use self::accessors::UndefinedArgument;
+pub use self::accessors::UndefinedField;
+pub use self::accessors::UndefinedValue;
+pub use self::accessors::UnsupportedDirectiveLocation;
+pub use self::accessors::UnsupportedValueType;
+pub use self::compatibility::check_operations;
+pub use self::compatibility::BreakingReason;
+pub use self::compatibility::BrokenOperation;
+pub use self::fragment::FragmentSpreadTypes;
+pub use self::missing::DefinitionName;
+pub use self::missing::MissingComponent;
+pub use self::multi_schema::validate_against_schemas;
+pub use self::multi_schema::MultiSchemaReport;
+pub use self::multi_schema::SchemaDiagnostics;
+pub use self::options::ExecutableValidationOptions;
+pub use self::options::UnknownDirectives;
+pub use self::rules::ExecutableElement;
+pub use self::rules::ValidationRule;
+pub use self::rules::ValidationRuleContext;
+pub use self::validator::Validator;
 use crate::collections::HashMap;
+use crate::collections::IndexMap;
 use crate::collections::IndexSet;
 use crate::diagnostic::CliReport;
 use crate::diagnostic::Diagnostic;
@@ -38,6 +69,7 @@ use crate::execution::Response;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::schema::BuildError as SchemaBuildError;
+use crate::schema::DefinitionCollisions;
 use crate::schema::Implementers;
 use crate::Name;
 use crate::Node;
@@ -254,6 +286,8 @@ impl SuspectedValidationBug {
 pub struct DiagnosticList {
     pub(crate) sources: SourceMap,
     diagnostics_data: Vec<DiagnosticData>,
+    /// Set by [`SchemaBuilder::tolerant`][crate::schema::SchemaBuilder::tolerant]
+    pub(crate) collisions: Option<Box<DefinitionCollisions>>,
 }
 
 // TODO(@goto-bus-stop) Can/should this be non-pub?
@@ -279,6 +313,8 @@ pub(crate) enum Details {
     CompilerDiagnostic(diagnostics::DiagnosticData),
     #[error("too much recursion")]
     RecursionLimitError,
+    #[error("{message}")]
+    ValidationRule { code: String, message: String },
 }
 
 impl DiagnosticData {
@@ -293,6 +329,7 @@ impl DiagnosticData {
                     RecursionError { .. } => "RecursionError",
                     UniqueVariable { .. } => "UniqueVariable",
                     UniqueArgument { .. } => "UniqueArgument",
+                    UniqueInputObjectField { .. } => "UniqueInputObjectField",
                     UniqueInputValue { .. } => "UniqueInputValue",
                     UndefinedArgument { .. } => "UndefinedArgument",
                     UndefinedDefinition { .. } => "UndefinedDefinition",
@@ -308,7 +345,7 @@ impl DiagnosticData {
                     OutputType { .. } => "OutputType",
                     InputType { .. } => "InputType",
                     VariableInputType { .. } => "VariableInputType",
-                    QueryRootOperationType { .. } => "QueryRootOperationType",
+                    QueryRootOperationType => "QueryRootOperationType",
                     UnusedVariable { .. } => "UnusedVariable",
                     RootOperationObjectType { .. } => "RootOperationObjectType",
                     UnionMemberObjectType { .. } => "UnionMemberObjectType",
@@ -318,6 +355,8 @@ impl DiagnosticData {
                     FloatCoercionError { .. } => "FloatCoercionError",
                     UniqueDirective { .. } => "UniqueDirective",
                     MissingSubselection { .. } => "MissingSubselection",
+                    #[cfg(feature = "experimental-client-controlled-nullability")]
+                    InvalidListNullability { .. } => "InvalidListNullability",
                     InvalidFragmentTarget { .. } => "InvalidFragmentTarget",
                     InvalidFragmentSpread { .. } => "InvalidFragmentSpread",
                     UnusedFragment { .. } => "UnusedFragment",
@@ -332,14 +371,15 @@ impl DiagnosticData {
                     EmptyMemberSet { .. } => "EmptyMemberSet",
                     EmptyInputValueSet { .. } => "EmptyInputValueSet",
                     ReservedName { .. } => "ReservedName",
+                    OneOfInputObjectField { .. } => "OneOfInputObjectField",
+                    OneOfInputValue { .. } => "OneOfInputValue",
+                    OneOfNullableVariable { .. } => "OneOfNullableVariable",
                 })
             }
             Details::ExecutableBuildError(error) => Some(match error {
                 ExecutableBuildError::UndefinedField { .. } => "UndefinedField",
                 ExecutableBuildError::TypeSystemDefinition { .. } => "TypeSystemDefinition",
-                ExecutableBuildError::AmbiguousAnonymousOperation { .. } => {
-                    "AmbiguousAnonymousOperation"
-                }
+                ExecutableBuildError::AmbiguousAnonymousOperation => "AmbiguousAnonymousOperation",
                 ExecutableBuildError::OperationNameCollision { .. } => "OperationNameCollision",
                 ExecutableBuildError::FragmentNameCollision { .. } => "FragmentNameCollision",
                 ExecutableBuildError::UndefinedRootOperation { .. } => "UndefinedRootOperation",
@@ -383,6 +423,9 @@ impl DiagnosticData {
                     UniqueArgument { name, .. } => {
                         Some(format!(r#"There can be only one argument named "{name}"."#))
                     }
+                    UniqueInputObjectField { name, .. } => Some(format!(
+                        r#"There can be only one input field named "{name}"."#
+                    )),
                     UniqueInputValue { .. } => None,
                     UndefinedArgument {
                         name, coordinate, ..
@@ -436,7 +479,7 @@ impl DiagnosticData {
                     VariableInputType { name, ty, .. } => Some(format!(
                         r#"Variable "${name}" cannot be non-input type "{ty}"."#
                     )),
-                    QueryRootOperationType { .. } => None,
+                    QueryRootOperationType => None,
                     UnusedVariable { name } => {
                         Some(format!(r#"Variable "${name}" is never used."#))
                     }
@@ -475,6 +518,8 @@ impl DiagnosticData {
                         ty = coordinate.ty,
                         field = coordinate.attribute,
                     )),
+                    #[cfg(feature = "experimental-client-controlled-nullability")]
+                    InvalidListNullability { .. } => None,
                     InvalidFragmentTarget { name, ty } => {
                         if let Some(name) = name {
                             Some(format!(
@@ -529,6 +574,20 @@ impl DiagnosticData {
                     EmptyMemberSet { .. } => None,
                     EmptyInputValueSet { .. } => None,
                     ReservedName { .. } => None,
+                    OneOfInputObjectField {
+                        coordinate,
+                        has_default_value,
+                    } => Some(if *has_default_value {
+                        format!(r#"OneOf input field {coordinate} cannot have a default value."#)
+                    } else {
+                        format!(r#"OneOf input field {coordinate} must be nullable."#)
+                    }),
+                    OneOfInputValue { name, .. } => Some(format!(
+                        r#"OneOf Input Object "{name}" must specify exactly one key."#
+                    )),
+                    OneOfNullableVariable { variable, name, .. } => Some(format!(
+                        r#"Variable "${variable}" must be non-nullable to be used for OneOf Input Object "{name}"."#
+                    )),
                 }
             }
             Details::ExecutableBuildError(error) => match error {
@@ -560,10 +619,12 @@ impl DiagnosticData {
                 } => Some(format!(
                     r#"There can be only one fragment named "{name_at_previous_location}"."#
                 )),
-                ExecutableBuildError::UndefinedRootOperation { operation_type } => Some(format!(
-                    // no period unlike other messages :zany_face:
-                    r#"The schema has no "{operation_type}" root type defined"#
-                )),
+                ExecutableBuildError::UndefinedRootOperation { operation_type, .. } => {
+                    Some(format!(
+                        // no period unlike other messages :zany_face:
+                        r#"The schema has no "{operation_type}" root type defined"#
+                    ))
+                }
                 ExecutableBuildError::UndefinedTypeInNamedFragmentTypeCondition {
                     type_name,
                     ..
@@ -698,10 +759,10 @@ impl ToCliReport for DiagnosticData {
                     report.with_label_opt(self.location, format_args!("`{name}` redefined here"));
                     report.with_help("remove or rename one of the definitions, or use `extend`");
                 }
-                SchemaBuildError::BuiltInScalarTypeRedefinition { .. } => {
+                SchemaBuildError::BuiltInScalarTypeRedefinition => {
                     report.with_label_opt(self.location, "remove this scalar definition");
                 }
-                SchemaBuildError::OrphanSchemaExtension { .. } => {
+                SchemaBuildError::OrphanSchemaExtension => {
                     report.with_label_opt(self.location, "extension here")
                 }
                 SchemaBuildError::OrphanTypeExtension { .. } => {
@@ -778,7 +839,7 @@ impl ToCliReport for DiagnosticData {
                     self.location,
                     "remove this definition, or use `parse_mixed()`",
                 ),
-                ExecutableBuildError::AmbiguousAnonymousOperation { .. } => {
+                ExecutableBuildError::AmbiguousAnonymousOperation => {
                     report.with_label_opt(self.location, "provide a name for this definition");
                     report.with_help(
                         "GraphQL requires operations to be named if the document has more than one",
@@ -948,6 +1009,9 @@ impl ToCliReport for DiagnosticData {
                 }
             },
             Details::RecursionLimitError => {}
+            Details::ValidationRule { code, .. } => {
+                report.with_label_opt(self.location, format_args!("reported by rule `{code}`"))
+            }
         }
     }
 }
@@ -975,6 +1039,7 @@ impl DiagnosticList {
         Self {
             sources,
             diagnostics_data: Vec::new(),
+            collisions: None,
         }
     }
 
@@ -994,6 +1059,71 @@ impl DiagnosticList {
             .map(|data| data.to_diagnostic(&self.sources))
     }
 
+    /// Convert every diagnostic into a JSON-serializable error as represented
+    /// in the `errors` entry of a GraphQL response.
+    ///
+    /// See [`Diagnostic::to_json`].
+    pub fn to_graphql_errors(&self) -> Vec<GraphQLError> {
+        self.iter().map(|diagnostic| diagnostic.to_json()).collect()
+    }
+
+    /// Group diagnostics that are about the same issue, such as the same name
+    /// defined three times or the same directive applied three times to the same element,
+    /// so that each group can be reported as one error with multiple locations.
+    ///
+    /// Two diagnostics are in the same group if they are of the same kind with the same message,
+    /// and the earliest location they refer to (see [`Diagnostic::locations`]) is the same,
+    /// for example the location of the first of several colliding definitions.
+    /// A diagnostic not related to any other forms a group by itself.
+    /// Groups are in the order of their first diagnostic.
+    ///
+    /// ```
+    /// use apollo_compiler::Schema;
+    ///
+    /// let input = "type Query { a: Int } type Query { b: Int } type Query { c: Int }";
+    /// let errors = Schema::parse(input, "schema.graphql").unwrap_err().errors;
+    /// assert_eq!(errors.len(), 2);
+    /// let groups = errors.grouped();
+    /// assert_eq!(groups.len(), 1);
+    /// let json = groups[0].to_json();
+    /// assert_eq!(json.message, "the type `Query` is defined multiple times in the schema");
+    /// assert_eq!(json.locations.len(), 3);
+    /// ```
+    pub fn grouped(&self) -> Vec<DiagnosticGroup<'_>> {
+        let mut groups: IndexMap<_, DiagnosticGroup<'_>> = IndexMap::default();
+        for diagnostic in self.iter() {
+            let locations = diagnostic.locations();
+            let anchor = locations
+                .iter()
+                .min_by_key(|location| (location.file_id(), location.offset()))
+                .copied();
+            let key = (
+                diagnostic.error.unstable_error_name(),
+                diagnostic.error.to_string(),
+                anchor,
+            );
+            let group = groups.entry(key).or_insert_with(|| DiagnosticGroup {
+                diagnostics: Vec::new(),
+                locations: Vec::new(),
+            });
+            for location in locations {
+                if !group.locations.contains(&location) {
+                    group.locations.push(location)
+                }
+            }
+            group.diagnostics.push(diagnostic);
+        }
+        groups.into_values().collect()
+    }
+
+    /// Convert every [group][Self::grouped] of diagnostics into a JSON-serializable error
+    /// with one location per source location the group refers to.
+    ///
+    /// See [`DiagnosticGroup::to_json`].
+    pub fn to_grouped_graphql_errors(&self) -> Vec<GraphQLError> {
+        self.grouped().iter().map(|group| group.to_json()).collect()
+    }
+
     pub(crate) fn push(&mut self, location: Option<SourceSpan>, details: impl Into<Details>) {
         self.diagnostics_data.push(DiagnosticData {
             location,
@@ -1010,6 +1140,9 @@ impl DiagnosticList {
             }
         }
         self.diagnostics_data.extend(other.diagnostics_data);
+        if self.collisions.is_none() {
+            self.collisions = other.collisions
+        }
         self.sort()
     }
 
@@ -1058,6 +1191,71 @@ impl fmt::Display for DiagnosticList {
     }
 }
 
+/// Related diagnostics about the same issue, returned by [`DiagnosticList::grouped`]
+pub struct DiagnosticGroup<'a> {
+    diagnostics: Vec<Diagnostic<'a, DiagnosticData>>,
+    locations: Vec<SourceSpan>,
+}
+
+impl<'a> DiagnosticGroup<'a> {
+    /// The first diagnostic of the group, whose message represents the group
+    pub fn primary(&self) -> &Diagnostic<'a, DiagnosticData> {
+        &self.diagnostics[0]
+    }
+
+    /// Every diagnostic of the group, starting with [the primary one][Self::primary]
+    pub fn diagnostics(&self) -> &[Diagnostic<'a, DiagnosticData>] {
+        &self.diagnostics
+    }
+
+    /// Every location that diagnostics in this group refer to, without duplicates:
+    /// locations of the primary diagnostic first,
+    /// then any other location of the other diagnostics.
+    pub fn locations(&self) -> &[SourceSpan] {
+        &self.locations
+    }
+
+    /// Returns the internal error name shared by diagnostics of this group.
+    /// This is meant for debugging apollo-rs, not for public consumption.
+    #[doc(hidden)]
+    pub fn unstable_error_name(&self) -> Option<&'static str> {
+        self.primary().error.unstable_error_name()
+    }
+
+    /// Convert into a JSON-serializable error with the message of the primary diagnostic
+    /// and the line and column of every location of the group.
+    pub fn to_json(&self) -> GraphQLError {
+        let primary = self.primary();
+        let mut error = primary.to_json();
+        error.locations = self
+            .locations
+            .iter()
+            .filter_map(|location| location.line_column(primary.sources))
+            .collect();
+        error
+    }
+}
+
+/// Use Debug formatting to output with colors: `format!("{group:?}")`
+impl fmt::Display for DiagnosticGroup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            fmt::Display::fmt(diagnostic, f)?
+        }
+        Ok(())
+    }
+}
+
+/// Use Display formatting to output without colors: `format!("{group}")`
+impl fmt::Debug for DiagnosticGroup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            fmt::Debug::fmt(diagnostic, f)?
+        }
+        Ok(())
+    }
+}
+
 /// Use Display formatting to output without colors: `format!("{diagnostics}")`
 impl fmt::Debug for DiagnosticList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
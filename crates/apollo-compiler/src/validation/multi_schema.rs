@@ -0,0 +1,121 @@
+use crate::ast;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::Schema;
+use std::fmt;
+
+/// Diagnostics from validating the same documents against several schemas,
+/// returned by [`validate_against_schemas`].
+#[derive(Debug, Clone)]
+pub struct MultiSchemaReport {
+    /// The schema names given to [`validate_against_schemas`], in the same order
+    pub schema_names: Vec<String>,
+    /// One entry per pair of schema and document that has validation errors,
+    /// grouped by document then in schema order
+    pub results: Vec<SchemaDiagnostics>,
+}
+
+/// Validation errors of one document against one schema.
+/// Part of a [`MultiSchemaReport`].
+#[derive(Debug, Clone)]
+pub struct SchemaDiagnostics {
+    /// The name of the schema, as given to [`validate_against_schemas`]
+    pub schema_name: String,
+    /// The index of the document in the slice given to [`validate_against_schemas`]
+    pub document_index: usize,
+    pub errors: DiagnosticList,
+}
+
+/// Validate executable documents against each of several schemas,
+/// such as the versions of a schema deployed to staging and production during a migration.
+///
+/// Documents are parsed once by the caller and each AST is converted and validated
+/// against every schema. Schemas are given with a name that identifies them in the report.
+///
+/// # Example
+///
+/// ```rust
+/// use apollo_compiler::ast;
+/// use apollo_compiler::validation::validate_against_schemas;
+/// use apollo_compiler::Schema;
+///
+/// let prod = Schema::parse_and_validate(
+///     "type Query { user: User } type User { name: String }",
+///     "prod.graphql",
+/// ).unwrap();
+/// let staging = Schema::parse_and_validate(
+///     "type Query { user: User } type User { name: String email: String }",
+///     "staging.graphql",
+/// ).unwrap();
+/// let documents = [
+///     ast::Document::parse("{ user { name } }", "a.graphql").unwrap(),
+///     ast::Document::parse("{ user { email } }", "b.graphql").unwrap(),
+/// ];
+///
+/// let report = validate_against_schemas([("prod", &prod), ("staging", &staging)], &documents);
+/// assert!(report.is_valid_against("staging"));
+/// assert!(!report.is_valid_against("prod"));
+/// assert_eq!(report.results.len(), 1);
+/// assert_eq!(report.results[0].schema_name, "prod");
+/// assert_eq!(report.results[0].document_index, 1);
+/// ```
+pub fn validate_against_schemas<'a>(
+    schemas: impl IntoIterator<Item = (&'a str, &'a Valid<Schema>)>,
+    documents: &[ast::Document],
+) -> MultiSchemaReport {
+    let schemas: Vec<_> = schemas.into_iter().collect();
+    let mut results = Vec::new();
+    for (document_index, document) in documents.iter().enumerate() {
+        for &(schema_name, schema) in &schemas {
+            if let Err(with_errors) = document.to_executable_validate(schema) {
+                results.push(SchemaDiagnostics {
+                    schema_name: schema_name.to_owned(),
+                    document_index,
+                    errors: with_errors.errors,
+                })
+            }
+        }
+    }
+    MultiSchemaReport {
+        schema_names: schemas.iter().map(|(name, _)| (*name).to_owned()).collect(),
+        results,
+    }
+}
+
+impl MultiSchemaReport {
+    /// Returns whether every document is valid against every schema
+    pub fn is_valid(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns whether every document is valid against the schema with this name
+    pub fn is_valid_against(&self, schema_name: &str) -> bool {
+        !self
+            .results
+            .iter()
+            .any(|result| result.schema_name == schema_name)
+    }
+
+    /// Returns the names of schemas that the document at this index has errors with
+    pub fn invalid_schemas_for(&self, document_index: usize) -> impl Iterator<Item = &str> {
+        self.results
+            .iter()
+            .filter(move |result| result.document_index == document_index)
+            .map(|result| result.schema_name.as_str())
+    }
+}
+
+/// Each schema’s diagnostics are preceded by a line with the schema name.
+impl fmt::Display for MultiSchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            writeln!(
+                f,
+                "[{}] document {}:",
+                result.schema_name, result.document_index
+            )?;
+            fmt::Display::fmt(&result.errors, f)?
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::executable;
 use crate::validation::DiagnosticList;
 use crate::validation::ExecutableValidationContext;
 use crate::ExecutableDocument;
+use crate::Name;
 use crate::Node;
 
 pub(crate) fn validate_subscription(
@@ -9,44 +10,76 @@ pub(crate) fn validate_subscription(
     operation: &Node<executable::Operation>,
     diagnostics: &mut DiagnosticList,
 ) {
-    if operation.is_subscription() {
-        let fields = super::selection::expand_selections(
-            &document.fragments,
-            std::iter::once(&operation.selection_set),
+    if let Some(fields) = subscription_multiple_root_fields(document, operation) {
+        diagnostics.push(
+            operation.location(),
+            executable::BuildError::SubscriptionUsesMultipleFields {
+                name: operation.name.clone(),
+                fields,
+            },
         );
+    }
+    if let Some(field) = subscription_introspection_root_field(document, operation) {
+        diagnostics.push(
+            field.location(),
+            executable::BuildError::SubscriptionUsesIntrospection {
+                name: operation.name.clone(),
+                field: field.name.clone(),
+            },
+        );
+    }
+}
 
-        if fields.len() > 1 {
-            diagnostics.push(
-                operation.location(),
-                executable::BuildError::SubscriptionUsesMultipleFields {
-                    name: operation.name.clone(),
-                    fields: fields
-                        .iter()
-                        .map(|field| field.field.name.clone())
-                        .collect(),
-                },
-            );
-        }
-
-        let has_introspection_fields = fields
+/// <https://spec.graphql.org/October2021/#sec-Single-root-field>
+///
+/// If `operation` is a subscription with more than one root field,
+/// returns the names of all root field selections.
+/// `@skip` and `@include` are ignored since variable values are not known.
+pub(crate) fn subscription_multiple_root_fields(
+    document: &executable::ExecutableDocument,
+    operation: &executable::Operation,
+) -> Option<Vec<Name>> {
+    if !operation.is_subscription() {
+        return None;
+    }
+    let fields = super::selection::expand_selections(
+        &document.fragments,
+        std::iter::once(&operation.selection_set),
+    );
+    // Fields with the same response key are merged into one root field
+    let has_multiple_root_fields = fields.first().is_some_and(|first| {
+        fields
             .iter()
-            .find(|field| {
-                matches!(
-                    field.field.name.as_str(),
-                    "__type" | "__schema" | "__typename"
-                )
-            })
-            .map(|field| &field.field);
-        if let Some(field) = has_introspection_fields {
-            diagnostics.push(
-                field.location(),
-                executable::BuildError::SubscriptionUsesIntrospection {
-                    name: operation.name.clone(),
-                    field: field.name.clone(),
-                },
-            );
-        }
+            .any(|field| field.field.response_key() != first.field.response_key())
+    });
+    has_multiple_root_fields.then(|| {
+        fields
+            .iter()
+            .map(|field| field.field.name.clone())
+            .collect()
+    })
+}
+
+/// If `operation` is a subscription, returns its first introspection root field if any.
+/// Part of <https://spec.graphql.org/October2021/#sec-Single-root-field>
+pub(crate) fn subscription_introspection_root_field<'doc>(
+    document: &'doc executable::ExecutableDocument,
+    operation: &'doc executable::Operation,
+) -> Option<&'doc Node<executable::Field>> {
+    if !operation.is_subscription() {
+        return None;
     }
+    super::selection::expand_selections(
+        &document.fragments,
+        std::iter::once(&operation.selection_set),
+    )
+    .into_iter()
+    .find(|field| is_introspection_field(&field.field.name))
+    .map(|field| field.field)
+}
+
+pub(crate) fn is_introspection_field(name: &str) -> bool {
+    matches!(name, "__type" | "__schema" | "__typename")
 }
 
 pub(crate) fn validate_operation(
@@ -85,13 +118,3 @@ pub(crate) fn validate_operation(
         context.operation_context(&operation.variables),
     );
 }
-
-pub(crate) fn validate_operation_definitions(
-    diagnostics: &mut DiagnosticList,
-    document: &ExecutableDocument,
-    context: &ExecutableValidationContext<'_>,
-) {
-    for operation in document.operations.iter() {
-        validate_operation(diagnostics, document, operation, context);
-    }
-}
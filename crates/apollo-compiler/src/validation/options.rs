@@ -0,0 +1,215 @@
+use super::diagnostics::DiagnosticData as Data;
+use super::rules::ExecutableElement;
+use super::rules::FnRule;
+use super::rules::ValidationRule;
+use super::rules::ValidationRuleContext;
+use super::rules::ValidationRules;
+use super::Details;
+use super::DiagnosticList;
+use super::Valid;
+use super::WithErrors;
+use crate::ast;
+use crate::executable::validation::validate_executable_document_with;
+use crate::executable::validation::RunOperationStep;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Configuration for [`ExecutableDocument::validate_with_options`],
+/// for documents written against a schema that does not (yet) define every directive they use.
+/// Client-only directives like `@client` or `@connection` are a typical example.
+///
+/// ```
+/// use apollo_compiler::ast;
+/// use apollo_compiler::validation::ExecutableValidationOptions;
+/// use apollo_compiler::validation::UnknownDirectives;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let source = "{ a @client @connection(key: \"a\") }";
+/// let doc = ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+///
+/// let sdl = ast::Document::parse("directive @client on FIELD", "client.graphql").unwrap();
+/// let ast::Definition::DirectiveDefinition(client) = &sdl.definitions[0] else {
+///     unreachable!()
+/// };
+/// let options = ExecutableValidationOptions::new()
+///     .assume_directive(client.clone())
+///     .unknown_directives(UnknownDirectives::Warn);
+/// let (_doc, warnings) = doc.validate_with_options(&schema, &options).unwrap();
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(
+///     warnings.iter().next().unwrap().error.to_string(),
+///     "cannot find directive `@connection` in this document",
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExecutableValidationOptions {
+    unknown_directives: UnknownDirectives,
+    assumed_directives: Vec<Node<ast::DirectiveDefinition>>,
+    rules: ValidationRules,
+}
+
+/// How [`ExecutableDocument::validate_with_options`] treats directives
+/// that are neither defined in the schema nor assumed to be declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UnknownDirectives {
+    /// Unknown directives make the document invalid, like with [`ExecutableDocument::validate`]
+    #[default]
+    Error,
+    /// Unknown directives are reported as warnings but do not make the document invalid
+    Warn,
+}
+
+impl ExecutableValidationOptions {
+    /// Returns options for the same validation as [`ExecutableDocument::validate`]
+    pub fn new() -> Self {
+        Self {
+            unknown_directives: UnknownDirectives::default(),
+            assumed_directives: Vec::new(),
+            rules: ValidationRules::new(),
+        }
+    }
+
+    /// Configure how to treat unknown directives. The default is [`UnknownDirectives::Error`].
+    pub fn unknown_directives(mut self, unknown_directives: UnknownDirectives) -> Self {
+        self.unknown_directives = unknown_directives;
+        self
+    }
+
+    /// Validate as if the schema also defined `definition`.
+    ///
+    /// Applications of the directive are checked against its locations and arguments
+    /// like those of directives defined in the schema.
+    /// A definition of the same name in the schema takes precedence.
+    pub fn assume_directive(
+        mut self,
+        definition: impl Into<Node<ast::DirectiveDefinition>>,
+    ) -> Self {
+        self.assumed_directives.push(definition.into());
+        self
+    }
+
+    /// Add a user-defined rule, which runs after built-in validation.
+    /// Rules run in the order they are added.
+    pub fn rule(mut self, rule: impl ValidationRule + Send + Sync + 'static) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Add a user-defined rule defined by a closure.
+    ///
+    /// ```
+    /// use apollo_compiler::validation::ExecutableElement;
+    /// use apollo_compiler::validation::ExecutableValidationOptions;
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     "type Query { users(first: Int): [User] } type User { name: String }",
+    ///     "schema.graphql",
+    /// ).unwrap();
+    /// let options = ExecutableValidationOptions::new().rule_fn(
+    ///     "list-fields-need-first",
+    ///     |cx, element| {
+    ///         if let ExecutableElement::Field(field) = element {
+    ///             if field.ty().is_list() && field.specified_argument_by_name("first").is_none() {
+    ///                 cx.report(format!(
+    ///                     "list field `{}` in `{}` must specify `first`",
+    ///                     field.name,
+    ///                     cx.path(),
+    ///                 ))
+    ///             }
+    ///         }
+    ///     },
+    /// );
+    ///
+    /// let source = "{ users { name } }";
+    /// let doc = ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+    /// let errors = doc.validate_with_options(&schema, &options).unwrap_err().errors;
+    /// assert_eq!(
+    ///     errors.iter().next().unwrap().error.to_string(),
+    ///     "list field `users` in `query` must specify `first`",
+    /// );
+    ///
+    /// let source = "{ users(first: 10) { name } }";
+    /// let doc = ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+    /// assert!(doc.validate_with_options(&schema, &options).is_ok());
+    /// ```
+    pub fn rule_fn(
+        self,
+        code: impl Into<String>,
+        check: impl Fn(&mut ValidationRuleContext<'_>, ExecutableElement<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.rule(FnRule {
+            code: code.into(),
+            check,
+        })
+    }
+}
+
+impl Default for ExecutableValidationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::result_large_err)] // Same as `ExecutableDocument::validate`
+pub(crate) fn validate_with_options(
+    document: ExecutableDocument,
+    schema: &Valid<Schema>,
+    options: &ExecutableValidationOptions,
+) -> Result<(Valid<ExecutableDocument>, DiagnosticList), WithErrors<ExecutableDocument>> {
+    validate_with_options_with(document, schema, options, &mut |_, _, _, errors, run| {
+        run(errors)
+    })
+}
+
+#[allow(clippy::result_large_err)] // Same as `ExecutableDocument::validate`
+pub(crate) fn validate_with_options_with(
+    document: ExecutableDocument,
+    schema: &Valid<Schema>,
+    options: &ExecutableValidationOptions,
+    run_step: &mut RunOperationStep<'_>,
+) -> Result<(Valid<ExecutableDocument>, DiagnosticList), WithErrors<ExecutableDocument>> {
+    let mut schema = Cow::Borrowed(&**schema);
+    if !options.assumed_directives.is_empty() {
+        let definitions = &mut schema.to_mut().directive_definitions;
+        for definition in &options.assumed_directives {
+            definitions
+                .entry(definition.name.clone())
+                .or_insert_with(|| definition.clone());
+        }
+    }
+
+    let mut sources = crate::collections::IndexMap::clone(&schema.sources);
+    sources.extend(document.sources.iter().map(|(k, v)| (*k, v.clone())));
+    let mut errors = DiagnosticList::new(Arc::new(sources));
+    validate_executable_document_with(&mut errors, &schema, &document, run_step);
+    if !options.rules.is_empty() {
+        let schema = Valid::assume_valid_ref(&*schema);
+        options.rules.check(&mut errors, schema, &document);
+    }
+
+    let mut warnings = DiagnosticList::new(errors.sources.clone());
+    if options.unknown_directives == UnknownDirectives::Warn {
+        let (undefined, others): (Vec<_>, Vec<_>) =
+            errors.diagnostics_data.iter().cloned().partition(|data| {
+                matches!(
+                    data.details,
+                    Details::CompilerDiagnostic(Data::UndefinedDirective { .. })
+                )
+            });
+        if others.is_empty() {
+            // Only return warnings for an otherwise valid document,
+            // and keep all diagnostics as errors otherwise.
+            errors.diagnostics_data = others;
+            warnings.diagnostics_data = undefined;
+        }
+    }
+    let valid = errors.into_valid_result(document)?;
+    Ok((valid, warnings))
+}
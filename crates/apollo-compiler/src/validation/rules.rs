@@ -0,0 +1,264 @@
+//! User-defined validation rules for executable documents.
+
+use super::DiagnosticList;
+use super::Valid;
+use crate::ast;
+use crate::executable::ExecutableDefinitionName;
+use crate::executable::Field;
+use crate::executable::Fragment;
+use crate::executable::FragmentSpread;
+use crate::executable::InlineFragment;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionPath;
+use crate::executable::SelectionSet;
+use crate::executable::VariableDefinition;
+use crate::parser::SourceSpan;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// A validation rule for executable documents, such as an organization-specific policy.
+/// Add rules with [`ExecutableValidationOptions::rule`][super::ExecutableValidationOptions::rule]
+/// so that they run as part of
+/// [`ExecutableDocument::validate_with_options`][crate::ExecutableDocument::validate_with_options].
+///
+/// The rule is called for every element of the document.
+/// Problems it reports are validation errors, which make the document invalid.
+///
+/// Implement this trait for rules that are configurable or reused,
+/// or use [`ExecutableValidationOptions::rule_fn`][super::ExecutableValidationOptions::rule_fn]
+/// for a closure.
+pub trait ValidationRule {
+    /// Identifies this rule in the diagnostics it reports, for example `list-fields-need-first`
+    fn code(&self) -> &str;
+
+    /// Check one element of the document, calling [`ValidationRuleContext::report`]
+    /// for each problem found
+    fn check(&self, cx: &mut ValidationRuleContext<'_>, element: ExecutableElement<'_>);
+}
+
+/// An element of an executable document visited by a [`ValidationRule`]
+#[derive(Debug, Clone, Copy)]
+pub enum ExecutableElement<'doc> {
+    /// An operation definition
+    Operation(&'doc Node<Operation>),
+    /// A variable definition of an operation
+    VariableDefinition {
+        operation: &'doc Node<Operation>,
+        def: &'doc Node<VariableDefinition>,
+    },
+    /// A fragment definition
+    Fragment(&'doc Node<Fragment>),
+    /// A field selection, whose [`definition`][Field::definition] comes from the schema
+    Field(&'doc Node<Field>),
+    /// A fragment spread selection
+    FragmentSpread(&'doc Node<FragmentSpread>),
+    /// An inline fragment selection
+    InlineFragment(&'doc Node<InlineFragment>),
+    /// A directive application on any of the other elements
+    Directive(&'doc Node<ast::Directive>),
+}
+
+/// Passed to [`ValidationRule::check`] to access the document being validated
+/// and report problems
+pub struct ValidationRuleContext<'a> {
+    schema: &'a Valid<Schema>,
+    document: &'a ExecutableDocument,
+    code: &'a str,
+    path: &'a SelectionPath,
+    location: Option<SourceSpan>,
+    diagnostics: &'a mut DiagnosticList,
+}
+
+#[derive(Clone)]
+pub(crate) struct ValidationRules(Vec<Arc<dyn ValidationRule + Send + Sync>>);
+
+pub(crate) struct FnRule<F> {
+    pub(crate) code: String,
+    pub(crate) check: F,
+}
+
+impl ExecutableElement<'_> {
+    /// Returns the source location of this element
+    pub fn location(&self) -> Option<SourceSpan> {
+        match *self {
+            ExecutableElement::Operation(def) => def.location(),
+            ExecutableElement::VariableDefinition { def, .. } => def.location(),
+            ExecutableElement::Fragment(def) => def.location(),
+            ExecutableElement::Field(field) => field.location(),
+            ExecutableElement::FragmentSpread(spread) => spread.location(),
+            ExecutableElement::InlineFragment(inline) => inline.location(),
+            ExecutableElement::Directive(directive) => directive.location(),
+        }
+    }
+}
+
+impl<'a> ValidationRuleContext<'a> {
+    /// Returns the schema the document is validated against
+    pub fn schema(&self) -> &'a Valid<Schema> {
+        self.schema
+    }
+
+    /// Returns the document being validated
+    pub fn document(&self) -> &'a ExecutableDocument {
+        self.document
+    }
+
+    /// Returns the path to the selection set that contains the element being checked,
+    /// or to the definition of an operation, variable, or fragment.
+    /// For a directive on a field, this includes the name of that field.
+    pub fn path(&self) -> &'a SelectionPath {
+        self.path
+    }
+
+    /// Report a problem with the element being checked, at its location
+    pub fn report(&mut self, message: impl Into<String>) {
+        self.report_at(self.location, message)
+    }
+
+    /// Report a problem with the element being checked, at a more specific location
+    /// such as that of an argument
+    pub fn report_at(&mut self, location: Option<SourceSpan>, message: impl Into<String>) {
+        self.diagnostics.push(
+            location,
+            super::Details::ValidationRule {
+                code: self.code.to_owned(),
+                message: message.into(),
+            },
+        )
+    }
+}
+
+impl<F> ValidationRule for FnRule<F>
+where
+    F: Fn(&mut ValidationRuleContext<'_>, ExecutableElement<'_>),
+{
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn check(&self, cx: &mut ValidationRuleContext<'_>, element: ExecutableElement<'_>) {
+        (self.check)(cx, element)
+    }
+}
+
+impl ValidationRules {
+    pub(crate) fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub(crate) fn push(&mut self, rule: impl ValidationRule + Send + Sync + 'static) {
+        self.0.push(Arc::new(rule))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run every rule on every element of `document`, in document order:
+    /// a definition before its variables, directives, and selections,
+    /// and a selection before its directives and nested selections.
+    /// Fragment definitions are visited once, not at each spread.
+    pub(crate) fn check(
+        &self,
+        diagnostics: &mut DiagnosticList,
+        schema: &Valid<Schema>,
+        document: &ExecutableDocument,
+    ) {
+        let mut walker = Walker {
+            rules: self,
+            schema,
+            document,
+            diagnostics,
+        };
+        for operation in document.operations.iter() {
+            let path = &mut SelectionPath {
+                root: ExecutableDefinitionName::of_operation(operation),
+                nested_fields: Vec::new(),
+            };
+            walker.visit(path, ExecutableElement::Operation(operation));
+            for def in &operation.variables {
+                walker.visit(
+                    path,
+                    ExecutableElement::VariableDefinition { operation, def },
+                );
+                walker.directives(path, &def.directives);
+            }
+            walker.directives(path, &operation.directives);
+            walker.selection_set(path, &operation.selection_set);
+        }
+        for fragment in document.fragments.values() {
+            let path = &mut SelectionPath {
+                root: ExecutableDefinitionName::Fragment(fragment.name.clone()),
+                nested_fields: Vec::new(),
+            };
+            walker.visit(path, ExecutableElement::Fragment(fragment));
+            walker.directives(path, &fragment.directives);
+            walker.selection_set(path, &fragment.selection_set);
+        }
+    }
+}
+
+impl fmt::Debug for ValidationRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|rule| rule.code()))
+            .finish()
+    }
+}
+
+struct Walker<'a> {
+    rules: &'a ValidationRules,
+    schema: &'a Valid<Schema>,
+    document: &'a ExecutableDocument,
+    diagnostics: &'a mut DiagnosticList,
+}
+
+impl Walker<'_> {
+    fn visit(&mut self, path: &SelectionPath, element: ExecutableElement<'_>) {
+        for rule in &self.rules.0 {
+            let mut cx = ValidationRuleContext {
+                schema: self.schema,
+                document: self.document,
+                code: rule.code(),
+                path,
+                location: element.location(),
+                diagnostics: self.diagnostics,
+            };
+            rule.check(&mut cx, element)
+        }
+    }
+
+    fn directives(&mut self, path: &SelectionPath, directives: &ast::DirectiveList) {
+        for directive in directives.iter() {
+            self.visit(path, ExecutableElement::Directive(directive))
+        }
+    }
+
+    fn selection_set(&mut self, path: &mut SelectionPath, selection_set: &SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    self.visit(path, ExecutableElement::Field(field));
+                    path.nested_fields.push(field.name.clone());
+                    self.directives(path, &field.directives);
+                    self.selection_set(path, &field.selection_set);
+                    path.nested_fields.pop();
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.visit(path, ExecutableElement::FragmentSpread(spread));
+                    self.directives(path, &spread.directives);
+                }
+                Selection::InlineFragment(inline) => {
+                    self.visit(path, ExecutableElement::InlineFragment(inline));
+                    self.directives(path, &inline.directives);
+                    self.selection_set(path, &inline.selection_set);
+                }
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+}
@@ -91,6 +91,7 @@ pub(crate) fn expand_selections<'doc>(
                 executable::Selection::FragmentSpread(_) => {
                     // Already seen
                 }
+                executable::Selection::Invalid(_) => {}
             }
         }
     }
@@ -225,21 +226,22 @@ fn same_output_type_shape(
     selection_a: FieldSelection<'_>,
     selection_b: FieldSelection<'_>,
 ) -> Result<(), BuildError> {
-    let field_a = &selection_a.field.definition;
-    let field_b = &selection_b.field.definition;
+    // Client-controlled nullability changes the type of values in the response
+    let field_type_a = selection_a.field.response_ty();
+    let field_type_b = selection_b.field.response_ty();
 
-    let mut type_a = &field_a.ty;
-    let mut type_b = &field_b.ty;
+    let mut type_a = field_type_a.as_ref();
+    let mut type_b = field_type_b.as_ref();
 
     let mismatching_type_diagnostic = || {
         BuildError::ConflictingFieldType(Box::new(ConflictingFieldType {
             alias: selection_a.field.response_key().clone(),
             original_location: selection_a.field.location(),
             original_coordinate: selection_a.coordinate(),
-            original_type: field_a.ty.clone(),
+            original_type: field_type_a.clone().into_owned(),
             conflicting_location: selection_b.field.location(),
             conflicting_coordinate: selection_b.coordinate(),
-            conflicting_type: field_b.ty.clone(),
+            conflicting_type: field_type_b.clone().into_owned(),
         }))
     };
 
@@ -344,6 +346,10 @@ impl<'alloc, 'doc> MergedFieldSet<'alloc, 'doc> {
         if self.same_response_shape_guard.already_done() {
             return;
         }
+        // Field types are only known with a schema
+        let Some(schema) = validator.schema else {
+            return;
+        };
 
         for fields_for_name in self.group_by_output_name(validator.alloc).values() {
             let Some((field_a, rest)) = fields_for_name.split_first() else {
@@ -351,7 +357,7 @@ impl<'alloc, 'doc> MergedFieldSet<'alloc, 'doc> {
             };
             for field_b in rest {
                 // Covers steps 3-5 of the spec algorithm.
-                if let Err(err) = same_output_type_shape(validator.schema, *field_a, *field_b) {
+                if let Err(err) = same_output_type_shape(schema, *field_a, *field_b) {
                     diagnostics.push(field_b.field.location(), err);
                     continue;
                 }
@@ -436,12 +442,27 @@ impl<'alloc, 'doc> MergedFieldSet<'alloc, 'doc> {
     /// Returns potentially overlapping groups of fields. Fields overlap if they are selected from
     /// the same concrete type or if they are selected from an abstract type (future schema changes
     /// can make any abstract type overlap with any other type).
+    ///
+    /// Without a schema, only fields selected from the same type name are known to overlap.
     fn group_by_common_parents(
         &self,
         alloc: &'alloc Arena<'doc>,
-        schema: &schema::Schema,
+        schema: Option<&schema::Schema>,
     ) -> &Vec<&'alloc [FieldSelection<'doc>]> {
         self.grouped_by_common_parents.get_or_init(|| {
+            let Some(schema) = schema else {
+                let mut by_parent = IndexMap::<_, Vec<_>>::with_hasher(Default::default());
+                for selection in self.selections {
+                    by_parent
+                        .entry(selection.parent_type)
+                        .or_default()
+                        .push(*selection);
+                }
+                return by_parent
+                    .into_values()
+                    .map(|group| alloc.alloc(group).as_slice())
+                    .collect();
+            };
             let mut abstract_parents = vec![];
             let mut concrete_parents = IndexMap::<_, Vec<_>>::with_hasher(Default::default());
             for selection in self.selections {
@@ -489,7 +510,8 @@ const FIELD_DEPTH_LIMIT: usize = 128;
 /// [1]: https://web.archive.org/web/20240208084612/https://tech.new-work.se/graphql-overlapping-fields-can-be-merged-fast-ea6e92e0a01
 pub(crate) struct FieldsInSetCanMerge<'alloc, 's, 'doc> {
     alloc: &'alloc Arena<'doc>,
-    schema: &'s schema::Schema,
+    /// When None, only fields selected from the same type name are checked
+    schema: Option<&'s schema::Schema>,
     document: &'doc executable::ExecutableDocument,
     /// Stores merged field sets.
     ///
@@ -504,7 +526,7 @@ pub(crate) struct FieldsInSetCanMerge<'alloc, 's, 'doc> {
 impl<'alloc, 's, 'doc> FieldsInSetCanMerge<'alloc, 's, 'doc> {
     pub(crate) fn new(
         alloc: &'alloc Arena<'doc>,
-        schema: &'s schema::Schema,
+        schema: Option<&'s schema::Schema>,
         document: &'doc executable::ExecutableDocument,
     ) -> Self {
         Self {
@@ -606,6 +628,9 @@ pub(crate) fn validate_selection_set(
                     context,
                 )
             }
+            executable::Selection::Invalid(invalid) => {
+                diagnostics.push(invalid.error_location, invalid.error.clone())
+            }
         }
     }
 }
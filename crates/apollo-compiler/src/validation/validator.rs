@@ -0,0 +1,247 @@
+use super::options::validate_with_options_with;
+use super::DiagnosticList;
+use super::ExecutableValidationOptions;
+use super::Valid;
+use super::WithErrors;
+use crate::collections::HashMap;
+use crate::executable::validation::OperationStep;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::parser::FileId;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::parser::SourceText;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::collections::BTreeSet;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+/// Validates executable documents against a schema,
+/// reusing results of previous calls for operations that did not change.
+///
+/// This is meant for tools like IDEs that repeatedly validate successive versions
+/// of the same document, where an edit typically only affects one operation.
+/// Results are the same as those of [`ExecutableDocument::validate_with_options`],
+/// including the order of diagnostics.
+///
+/// Diagnostics for an operation are cached, keyed by a hash of the operation
+/// and of the fragments it uses directly or indirectly.
+/// For definitions that were parsed, this includes their source text and position in the file,
+/// so that cached diagnostics still point to the correct locations
+/// when a new version of the document is parsed from an edited file.
+/// Only the results of the most recent call to [`validate`][Self::validate] are kept.
+/// User-defined [rules][ExecutableValidationOptions::rule] and checks that depend on
+/// the whole document, such as unused fragments, run again on every call.
+///
+/// ```
+/// use apollo_compiler::validation::Validator;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int, b: Int }", "schema.graphql").unwrap();
+/// let mut validator = Validator::new(schema);
+///
+/// let source = "query A { a } query B { b }";
+/// let doc = ExecutableDocument::parse(validator.schema(), source, "query.graphql").unwrap();
+/// assert!(validator.validate(doc).is_ok());
+///
+/// // Operation `A` is not validated again
+/// let source = "query A { a } query B($unused: Int) { b }";
+/// let doc = ExecutableDocument::parse(validator.schema(), source, "query.graphql").unwrap();
+/// assert!(validator.validate(doc).is_err());
+/// ```
+pub struct Validator {
+    schema: Valid<Schema>,
+    options: ExecutableValidationOptions,
+    cache: HashMap<(u64, OperationStep), CachedOperation>,
+}
+
+struct CachedOperation {
+    /// The file of each hashed definition that has a location
+    file_ids: Vec<FileId>,
+    diagnostics: DiagnosticList,
+}
+
+impl Validator {
+    /// Creates a validator for executable documents against the given schema
+    pub fn new(schema: Valid<Schema>) -> Self {
+        Self::with_options(schema, ExecutableValidationOptions::new())
+    }
+
+    /// Creates a validator like [`ExecutableDocument::validate_with_options`]
+    pub fn with_options(schema: Valid<Schema>, options: ExecutableValidationOptions) -> Self {
+        Self {
+            schema,
+            options,
+            cache: HashMap::default(),
+        }
+    }
+
+    /// Returns the schema that documents are validated against
+    pub fn schema(&self) -> &Valid<Schema> {
+        &self.schema
+    }
+
+    /// Validates `document` against the schema, like [`ExecutableDocument::validate_with_options`].
+    ///
+    /// Operations (and the fragments they use) that did not change
+    /// since the previous call are not validated again.
+    #[allow(clippy::result_large_err)] // Same as `ExecutableDocument::validate`
+    pub fn validate(
+        &mut self,
+        document: ExecutableDocument,
+    ) -> Result<(Valid<ExecutableDocument>, DiagnosticList), WithErrors<ExecutableDocument>> {
+        let mut previous_cache = std::mem::take(&mut self.cache);
+        let cache = &mut self.cache;
+        let mut keys = HashMap::default();
+        validate_with_options_with(
+            document,
+            &self.schema,
+            &self.options,
+            &mut |document, operation, step, errors, run| {
+                let (key, file_ids) = keys
+                    .entry(operation.name.clone())
+                    .or_insert_with(|| operation_key(document, operation));
+                let key = (*key, step);
+                let cached = previous_cache.remove(&key).or_else(|| cache.remove(&key));
+                let cached = if let Some(cached) = cached {
+                    cached.restore_into(errors, file_ids);
+                    cached
+                } else {
+                    let mut diagnostics = DiagnosticList::new(errors.sources.clone());
+                    run(&mut diagnostics);
+                    errors
+                        .diagnostics_data
+                        .extend(diagnostics.diagnostics_data.iter().cloned());
+                    CachedOperation {
+                        file_ids: file_ids.clone(),
+                        diagnostics,
+                    }
+                };
+                cache.insert(key, cached);
+            },
+        )
+    }
+}
+
+impl CachedOperation {
+    /// Adds cached diagnostics to `errors`, for a matching operation
+    /// whose hashed definitions are in `file_ids`
+    fn restore_into(&self, errors: &mut DiagnosticList, file_ids: &[FileId]) {
+        // Definitions have the same source text at the same position as when cached,
+        // but may be from a different parse of the same file.
+        let renamed: HashMap<FileId, FileId> = self
+            .file_ids
+            .iter()
+            .zip(file_ids)
+            .filter(|(old, new)| old != new)
+            .map(|(old, new)| (*old, *new))
+            .collect();
+        for data in &self.diagnostics.diagnostics_data {
+            let mut data = data.clone();
+            if let Some(location) = &mut data.location {
+                if let Some(&file_id) = renamed.get(&location.file_id) {
+                    location.file_id = file_id
+                }
+            }
+            errors.diagnostics_data.push(data)
+        }
+        // Secondary locations in diagnostic details are not renamed,
+        // keep the files they point to available.
+        if !renamed.is_empty() {
+            let sources = Arc::make_mut(&mut errors.sources);
+            for (file_id, source) in self.diagnostics.sources.iter() {
+                sources.entry(*file_id).or_insert_with(|| source.clone());
+            }
+        }
+    }
+}
+
+/// Returns a hash of everything that validation of `operation` depends on other than the schema,
+/// and the files of the definitions that were hashed.
+fn operation_key(document: &ExecutableDocument, operation: &Node<Operation>) -> (u64, Vec<FileId>) {
+    let mut hasher = DefaultHasher::new();
+    let mut file_ids = Vec::new();
+    hash_definition(
+        &mut hasher,
+        &mut file_ids,
+        &document.sources,
+        operation.location(),
+        &**operation,
+    );
+    for name in used_fragments(document, operation) {
+        name.hash(&mut hasher);
+        let fragment = document.fragments.get(name);
+        fragment.is_some().hash(&mut hasher);
+        if let Some(fragment) = fragment {
+            hash_definition(
+                &mut hasher,
+                &mut file_ids,
+                &document.sources,
+                fragment.location(),
+                &**fragment,
+            );
+        }
+    }
+    (hasher.finish(), file_ids)
+}
+
+fn hash_definition(
+    hasher: &mut DefaultHasher,
+    file_ids: &mut Vec<FileId>,
+    sources: &SourceMap,
+    location: Option<SourceSpan>,
+    definition: &impl Hash,
+) {
+    // Structural hash, in case the definition was modified after parsing
+    definition.hash(hasher);
+    let Some(location) = location else {
+        false.hash(hasher);
+        return;
+    };
+    true.hash(hasher);
+    file_ids.push(location.file_id());
+    // Diagnostics contain locations, so they can only be reused
+    // if the source text and its position are unchanged
+    if let Some(source) = sources.get(&location.file_id()) {
+        source.path().hash(hasher);
+        location.offset().hash(hasher);
+        // The text of a detached file is unchanged since parsing, don’t load it
+        if let SourceText::Retained(text) = &source.text {
+            text.get(location.offset()..location.end_offset())
+                .hash(hasher);
+        }
+        source.get_line_column(location.offset()).hash(hasher);
+    }
+}
+
+/// Returns the names of fragments used by `operation`, directly or through other fragments
+fn used_fragments<'doc>(
+    document: &'doc ExecutableDocument,
+    operation: &'doc Operation,
+) -> BTreeSet<&'doc Name> {
+    let mut used = BTreeSet::new();
+    let mut stack = vec![&operation.selection_set];
+    while let Some(selection_set) = stack.pop() {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => stack.push(&field.selection_set),
+                Selection::InlineFragment(inline) => stack.push(&inline.selection_set),
+                Selection::FragmentSpread(spread) => {
+                    if used.insert(&spread.fragment_name) {
+                        if let Some(fragment) = document.fragments.get(&spread.fragment_name) {
+                            stack.push(&fragment.selection_set)
+                        }
+                    }
+                }
+                Selection::Invalid(_) => {}
+            }
+        }
+    }
+    used
+}
@@ -219,6 +219,35 @@ pub(crate) fn value_of_correct_type(
                     );
                 }
 
+                // A oneOf input object value must have exactly one field, which is not null
+                // https://spec.graphql.org/draft/#sec-OneOf-Input-Objects
+                if schema.is_one_of(input_obj) {
+                    match obj.as_slice() {
+                        [(_, value)] if !value.is_null() => {
+                            if let ast::Value::Variable(var_name) = &**value {
+                                let var_def = var_defs.iter().find(|v| v.name == *var_name);
+                                if let Some(var_def) = var_def.filter(|v| !v.ty.is_non_null()) {
+                                    diagnostics.push(
+                                        value.location(),
+                                        DiagnosticData::OneOfNullableVariable {
+                                            variable: var_name.clone(),
+                                            name: input_obj.name.clone(),
+                                            variable_location: var_def.location(),
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                        _ => diagnostics.push(
+                            arg_value.location(),
+                            DiagnosticData::OneOfInputValue {
+                                name: input_obj.name.clone(),
+                                definition_location: input_obj.location(),
+                            },
+                        ),
+                    }
+                }
+
                 input_obj.fields.iter().for_each(|(input_name, f)| {
                     let ty = &f.ty;
                     let is_missing = !obj.iter().any(|(value_name, ..)| input_name == value_name);
@@ -121,6 +121,7 @@ fn walk_selections<'doc>(
                 executable::Selection::InlineFragment(fragment) => {
                     walk_selections_inner(document, &fragment.selection_set, guard, f)?;
                 }
+                executable::Selection::Invalid(_) => {}
             }
         }
         Ok(())
@@ -160,6 +161,43 @@ fn variables_in_directives(
         .flat_map(|directive| variables_in_arguments(&directive.arguments))
 }
 
+fn variables_in_ast_selection(selection: &ast::Selection) -> Vec<&Name> {
+    let (directives, selections) = match selection {
+        ast::Selection::Field(field) => (&field.directives, &field.selection_set),
+        ast::Selection::FragmentSpread(spread) => {
+            return variables_in_directives(&spread.directives).collect()
+        }
+        ast::Selection::InlineFragment(inline) => (&inline.directives, &inline.selection_set),
+    };
+    let mut variables: Vec<_> = variables_in_directives(directives).collect();
+    if let ast::Selection::Field(field) = selection {
+        variables.extend(variables_in_arguments(&field.arguments))
+    }
+    variables.extend(selections.iter().flat_map(variables_in_ast_selection));
+    variables
+}
+
+/// Report variables used in `args` that are not defined in `var_defs`.
+///
+/// Only needed where arguments are not validated against their definition,
+/// which reports undefined variables together with other value errors.
+pub(crate) fn validate_defined_variables(
+    diagnostics: &mut DiagnosticList,
+    args: &[Node<ast::Argument>],
+    var_defs: &[Node<ast::VariableDefinition>],
+) {
+    for var_name in variables_in_arguments(args) {
+        if !var_defs.iter().any(|v| v.name == *var_name) {
+            diagnostics.push(
+                var_name.location(),
+                DiagnosticData::UndefinedVariable {
+                    name: var_name.clone(),
+                },
+            );
+        }
+    }
+}
+
 // TODO add test:
 // should NOT report a unused variable warning
 // query ($var1: Boolean!, $var2: Boolean!) {
@@ -215,6 +253,12 @@ pub(crate) fn validate_unused_variables(
                     unused_vars.remove(used);
                 }
             }
+            // Only its own error is reported, not that its variables are unused
+            executable::Selection::Invalid(invalid) => {
+                for used in variables_in_ast_selection(&invalid.ast) {
+                    unused_vars.remove(used);
+                }
+            }
         },
     );
     if walked.is_err() {
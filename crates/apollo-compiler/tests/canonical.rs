@@ -0,0 +1,153 @@
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+# Comments are not part of the canonical form
+"""
+  The root query type   
+"""
+type Query implements Node & Entity {
+  users(limit: Int = 10, after: String): [User] @deprecated(reason: "use search") @cost(weight: 2, max: 10)
+  id: ID!
+  node(id: ID!): Node
+}
+
+directive @cost(weight: Int, max: Int) repeatable on FIELD_DEFINITION | OBJECT
+
+interface Node { id: ID! }
+
+interface Entity { id: ID! }
+
+type User implements Node @cost(weight: 1) { id: ID! name: String }
+
+extend type User { email: String }
+
+union SearchResult = User | Query
+
+enum Role { USER ADMIN }
+
+extend enum Role { GUEST }
+
+scalar DateTime @specifiedBy(url: "https://example.com")
+
+input Filter { name: String = "x", role: Role }
+
+extend scalar Int @tag(name: "number")
+directive @tag(name: String!) on SCALAR
+"#;
+
+/// The same schema with definitions, fields, and arguments in a different order,
+/// extensions instead of single definitions, and different comments and whitespace
+const SHUFFLED: &str = r#"
+directive @tag(name: String!) on SCALAR
+extend scalar Int @tag(name: "number")
+input Filter { role: Role, name: String = "x" }
+scalar DateTime @specifiedBy(url: "https://example.com")
+enum Role { GUEST ADMIN USER }
+union SearchResult = Query | User
+type User implements Node @cost(weight: 1) { name: String email: String id: ID! }
+interface Entity { id: ID! }
+interface Node {
+  # The ID
+  id: ID!
+}
+directive @cost(max: Int, weight: Int) repeatable on OBJECT | FIELD_DEFINITION
+"The root query type"
+type Query implements Entity {
+  node(id: ID!): Node
+  id: ID!
+  users(after: String, limit: Int = 10): [User] @deprecated(reason: "use search") @cost(max: 10, weight: 2)
+}
+extend type Query implements Node
+"#;
+
+#[test]
+fn canonical_serialization() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let expected = expect![[r#"
+        directive @cost(max: Int, weight: Int) repeatable on FIELD_DEFINITION | OBJECT
+
+        directive @tag(name: String!) on SCALAR
+
+        scalar DateTime @specifiedBy(url: "https://example.com")
+
+        interface Entity {
+          id: ID!
+        }
+
+        input Filter {
+          name: String = "x"
+          role: Role
+        }
+
+        extend scalar Int @tag(name: "number")
+
+        interface Node {
+          id: ID!
+        }
+
+        """The root query type"""
+        type Query implements Entity & Node {
+          id: ID!
+          node(id: ID!): Node
+          users(after: String, limit: Int = 10): [User] @deprecated(reason: "use search") @cost(max: 10, weight: 2)
+        }
+
+        enum Role {
+          ADMIN
+          GUEST
+          USER
+        }
+
+        union SearchResult = Query | User
+
+        type User implements Node @cost(weight: 1) {
+          email: String
+          id: ID!
+          name: String
+        }
+    "#]];
+    let canonical = schema.serialize().canonical().to_string();
+    expected.assert_eq(&canonical);
+
+    let shuffled = Schema::parse_and_validate(SHUFFLED, "shuffled.graphql").unwrap();
+    assert_eq!(shuffled.serialize().canonical().to_string(), canonical);
+
+    // Canonical output is a fixed point
+    let reparsed = Schema::parse_and_validate(&canonical, "canonical.graphql").unwrap();
+    assert_eq!(reparsed.serialize().canonical().to_string(), canonical);
+
+    // Other builder methods called before `canonical` do not apply
+    let configured = schema
+        .serialize()
+        .no_indent()
+        .origin_comments()
+        .canonical()
+        .to_string();
+    assert_eq!(configured, canonical);
+}
+
+#[test]
+fn content_hash() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let shuffled = Schema::parse_and_validate(SHUFFLED, "shuffled.graphql").unwrap();
+    // This value must not change in non-breaking releases,
+    // as registries may store it to detect schema changes.
+    expect!["4551c22304aa486137a615cd6372bc1318a06550a12f00b84f95535313b03e20"]
+        .assert_eq(&schema.content_hash());
+    assert_eq!(schema.content_hash(), shuffled.content_hash());
+
+    let changed = Schema::parse_and_validate(
+        SCHEMA.replace("name: String }", "name: String! }"),
+        "changed.graphql",
+    )
+    .unwrap();
+    assert_ne!(schema.content_hash(), changed.content_hash());
+
+    let with_schema_definition = Schema::parse_and_validate(
+        format!("schema {{ query: Query }}\n{SCHEMA}"),
+        "schema.graphql",
+    )
+    .unwrap();
+    assert_eq!(schema.content_hash(), with_schema_definition.content_hash());
+}
@@ -0,0 +1,168 @@
+use apollo_compiler::ast;
+use apollo_compiler::ast::Nullability;
+use apollo_compiler::ast::NullabilityDesignator;
+use apollo_compiler::executable::Selection;
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::execute_introspection_only_query;
+use apollo_compiler::ty;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    type Query {
+        user: User
+        users: [User!]!
+        matrix: [[Int]]
+    }
+    type User {
+        name: String!
+        nickname: String
+    }
+"#;
+
+#[test]
+fn parse_and_serialize() {
+    let input = "{ user! { name? nickname! } users[?]? alias: matrix[[!]]! @skip(if: false) }";
+    let document = ast::Document::parse(input, "query.graphql").unwrap();
+    expect![[r#"
+        {
+          user! {
+            name?
+            nickname!
+          }
+          users[?]?
+          alias: matrix[[!]]! @skip(if: false)
+        }
+    "#]]
+    .assert_eq(&document.to_string());
+
+    let operation = document.definitions[0].as_operation_definition().unwrap();
+    let ast::Selection::Field(users) = &operation.selection_set[1] else {
+        panic!("expected a field")
+    };
+    assert_eq!(
+        users.nullability,
+        Some(Nullability {
+            list: Some(Box::new(Nullability {
+                list: None,
+                designator: Some(NullabilityDesignator::Optional),
+            })),
+            designator: Some(NullabilityDesignator::Optional),
+        })
+    );
+}
+
+#[test]
+fn executable_field_type() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let input = "{ user! { name? } users[?] { name } matrix[[!]] }";
+    let document = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let types: Vec<_> = operation
+        .selection_set
+        .fields()
+        .map(|field| (field.ty().clone(), field.ty_with_nullability().unwrap()))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            (ty!(User), ty!(User!)),
+            (ty!([User!]!), ty!([User]!)),
+            (ty!([[Int]]), ty!([[Int!]])),
+        ]
+    );
+    let Selection::Field(user) = &operation.selection_set.selections[0] else {
+        panic!("expected a field")
+    };
+    let name = user.selection_set.fields().next().unwrap();
+    assert_eq!(name.ty_with_nullability(), Some(ty!(String)));
+    assert_eq!(
+        document.to_string(),
+        "{\n  user! {\n    name?\n  }\n  users[?] {\n    name\n  }\n  matrix[[!]]\n}\n"
+    );
+}
+
+#[test]
+fn list_nullability_too_deep() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let input = "{ user[!] { name } users[[!]] { name } matrix[[]] }";
+    let errors = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql")
+        .unwrap_err()
+        .errors;
+    expect![[r#"
+        Error: list nullability `[!]` is nested deeper than the type of `Query.user`
+           ╭─[query.graphql:1:3]
+           │
+         1 │ { user[!] { name } users[[!]] { name } matrix[[]] }
+           │   ────────┬───────  
+           │           ╰───────── `[!]` has more nested lists than the field type `User`
+        ───╯
+        Error: list nullability `[[!]]` is nested deeper than the type of `Query.users`
+           ╭─[query.graphql:1:20]
+           │
+         1 │ { user[!] { name } users[[!]] { name } matrix[[]] }
+           │                    ─────────┬─────────  
+           │                             ╰─────────── `[[!]]` has more nested lists than the field type `[User!]!`
+        ───╯
+    "#]]
+    .assert_eq(&errors.to_string());
+}
+
+#[test]
+fn conflicting_nullability() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let input = "{ user { nickname! } user { nickname } }";
+    let errors = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql")
+        .unwrap_err()
+        .errors;
+    expect![[r#"
+        Error: operation must not select different types using the same name `nickname`
+           ╭─[query.graphql:1:29]
+           │
+         1 │ { user { nickname! } user { nickname } }
+           │          ────┬────          ────┬───  
+           │              ╰──────────────────────── `nickname` is selected from `User.nickname: String!` here
+           │                                 │     
+           │                                 ╰───── `nickname` is selected from `User.nickname: String` here
+        ───╯
+    "#]].assert_eq(&errors.to_string());
+
+    let input = "{ user { nickname! } user { nickname! } }";
+    ExecutableDocument::parse_and_validate(&schema, input, "query.graphql").unwrap();
+}
+
+#[test]
+fn null_propagation() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let input = r#"{ __schema { description } type: __type(name: "Query") { description! } }"#;
+    let document = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let response = execute_introspection_only_query(&schema, &document, operation, &variables);
+    expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "Non-null type String! resolved to null",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 58
+                }
+              ],
+              "path": [
+                "type",
+                "description"
+              ]
+            }
+          ],
+          "data": {
+            "__schema": {
+              "description": null
+            },
+            "type": null
+          }
+        }"#]]
+    .assert_eq(&serde_json::to_string_pretty(&response).unwrap());
+}
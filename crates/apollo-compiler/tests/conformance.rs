@@ -0,0 +1,52 @@
+use apollo_compiler::ast;
+use apollo_compiler::conformance;
+use apollo_compiler::conformance::Expected;
+use apollo_compiler::conformance::Mode;
+use apollo_compiler::Schema;
+
+#[test]
+fn apollo_compiler_conforms() {
+    let mismatches = conformance::run(|case| {
+        let valid = match case.mode {
+            Mode::TypeSystem => Schema::parse_and_validate(case.input, case.name).is_ok(),
+            Mode::Executable => ast::Document::parse(case.input, case.name)
+                .is_ok_and(|document| document.validate_standalone_executable().is_ok()),
+            Mode::Mixed => apollo_compiler::parse_mixed_validate(case.input, case.name).is_ok(),
+        };
+        if valid {
+            Expected::Accepted
+        } else {
+            Expected::Rejected
+        }
+    });
+    assert_eq!(mismatches, []);
+
+    let mismatches = conformance::round_trip(|document| document.to_string());
+    assert_eq!(mismatches, []);
+    for case in conformance::validation_cases() {
+        let document =
+            ast::Document::parse(case.input, case.name).unwrap_or_else(|invalid| invalid.partial);
+        assert_eq!(document.to_string(), case.serialized, "{}", case.name);
+    }
+}
+
+#[test]
+fn reports_round_trip_mismatches() {
+    // Drops all definitions after the first
+    let mismatches = conformance::round_trip(|document| {
+        document
+            .definitions
+            .first()
+            .map(|definition| definition.to_string())
+            .unwrap_or_default()
+    });
+    assert!(!mismatches.is_empty());
+    let message = mismatches[0].to_string();
+    assert!(message.contains(": serialization does not round-trip:\n"));
+}
+
+#[test]
+fn parser_corpus() {
+    let cases = apollo_parser::conformance::parser_cases();
+    assert!(cases.iter().any(|case| case.expected == Expected::Rejected));
+}
@@ -0,0 +1,117 @@
+use apollo_compiler::execution::CostPolicy;
+use apollo_compiler::execution::QueryCost;
+use apollo_compiler::execution::Response;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+    directive @cost(weight: Int!) on FIELD_DEFINITION
+    directive @listSize(assumedSize: Int!) on FIELD_DEFINITION
+
+    type Query {
+        me: User
+        users: [User!]! @listSize(assumedSize: 3)
+        grid: [[Int]]
+        search: [Result] @listSize(assumedSize: 2)
+    }
+
+    type User {
+        name: String
+        friends: [User]
+        avatar: String @cost(weight: 5)
+    }
+
+    type Post {
+        title: String
+        author: User
+    }
+
+    union Result = User | Post
+"#;
+
+fn cost(policy: &CostPolicy, query: &str, data: &str) -> QueryCost {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let mut response: Response = serde_json::from_str(data).unwrap();
+    let cost = policy.add_to_response(&schema, &document, operation, &mut response);
+    assert_eq!(
+        response.extensions["cost"],
+        serde_json_bytes::json!({"estimated": cost.estimated, "actual": cost.actual})
+    );
+    cost
+}
+
+fn estimate(policy: &CostPolicy, query: &str) -> u64 {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document: Valid<ExecutableDocument> =
+        ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    policy.estimate(&document, document.operations.get(None).unwrap())
+}
+
+#[test]
+fn weights_and_list_sizes() {
+    let policy = CostPolicy::default();
+    // me: 1 + avatar 5
+    assert_eq!(estimate(&policy, "{ me { name avatar } }"), 6);
+    // users: 3 * (1 + friends: 10 * 1)
+    assert_eq!(estimate(&policy, "{ users { friends { name } } }"), 33);
+    // grid: 10 * 10 * 0
+    assert_eq!(estimate(&policy, "{ grid }"), 0);
+    let policy = CostPolicy {
+        leaf_cost: 1,
+        ..Default::default()
+    };
+    assert_eq!(estimate(&policy, "{ grid }"), 100);
+    assert_eq!(estimate(&policy, "{ me { name avatar } }"), 7);
+}
+
+#[test]
+fn merged_fields_and_fragments() {
+    let policy = CostPolicy::default();
+    let query = r#"
+        {
+            me { name }
+            me { avatar }
+            ...F
+            search {
+                ... on User { name avatar }
+                ... on Post { author { name } }
+            }
+        }
+        fragment F on Query { me { avatar } }
+    "#;
+    // me: 1 + 5, search: 2 * (1 + avatar 5 + author 1)
+    assert_eq!(estimate(&policy, query), 20);
+
+    let data = r#"{"data": {
+        "me": null,
+        "search": [
+            {"__typename": "User", "name": "Alice", "avatar": "a.png"},
+            {"__typename": "Post", "author": {"name": "Bob"}},
+            {"__typename": "Post", "author": null},
+            null
+        ]
+    }}"#;
+    // search: (1 + 5) + (1 + 1) + 1
+    assert_eq!(
+        cost(&policy, query, data),
+        QueryCost {
+            estimated: 20,
+            actual: 9
+        }
+    );
+}
+
+#[test]
+fn no_data() {
+    let policy = CostPolicy::default();
+    let query = "{ users { name } }";
+    assert_eq!(cost(&policy, query, r#"{"data": null}"#).actual, 0);
+    assert_eq!(
+        cost(&policy, query, r#"{"errors": [{"message": "nope"}]}"#).actual,
+        0
+    );
+    assert_eq!(cost(&policy, query, r#"{"data": {"users": []}}"#).actual, 0);
+}
@@ -0,0 +1,131 @@
+use apollo_compiler::executable::SchemaCoverage;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"type Query {
+  node(id: ID!): Node
+  users(first: Int, after: String): [User]
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name(format: String): String
+  email: String
+}
+
+input UserFilter {
+  name: String
+}
+
+enum Role { ADMIN USER }
+"#;
+
+fn coverage() -> SchemaCoverage {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let documents = [
+        "query A { users(first: 10) { ...UserFields } } fragment UserFields on User { id name }",
+        "query B { node(id: 1) { id ... on User { name(format: \"short\") } } __typename }",
+        "query C { users { ...UserFields } } fragment UserFields on User { id }",
+    ];
+    let mut coverage = SchemaCoverage::new(&schema);
+    for (i, source) in documents.into_iter().enumerate() {
+        let document =
+            ExecutableDocument::parse_and_validate(&schema, source, format!("{i}.graphql"))
+                .unwrap();
+        coverage.add_document(&document);
+    }
+    coverage
+}
+
+#[test]
+fn counts() {
+    let coverage = coverage();
+    let counts = coverage
+        .counts()
+        .map(|(coordinate, count)| format!("{coordinate}: {count}\n"))
+        .collect::<String>();
+    expect![[r#"
+        Query.node: 1
+        Query.node(id:): 1
+        Query.users: 2
+        Query.users(first:): 1
+        Query.users(after:): 0
+        Node.id: 1
+        User.id: 2
+        User.name: 2
+        User.name(format:): 1
+        User.email: 0
+    "#]]
+    .assert_eq(&counts);
+    assert_eq!(coverage.covered(), 8);
+    assert_eq!(coverage.total(), 10);
+}
+
+#[test]
+fn json_report() {
+    let coverage = coverage();
+    expect![[r#"
+        {
+          "covered": 8,
+          "total": 10,
+          "coordinates": {
+            "Query.node": 1,
+            "Query.node(id:)": 1,
+            "Query.users": 2,
+            "Query.users(first:)": 1,
+            "Query.users(after:)": 0,
+            "Node.id": 1,
+            "User.id": 2,
+            "User.name": 2,
+            "User.name(format:)": 1,
+            "User.email": 0
+          }
+        }"#]]
+    .assert_eq(&serde_json::to_string_pretty(&coverage.to_json()).unwrap());
+}
+
+#[test]
+fn lcov_report() {
+    let coverage = coverage();
+    expect![[r#"
+        TN:
+        SF:schema.graphql
+        FN:2,Query.node
+        FN:2,Query.node(id:)
+        FN:3,Query.users
+        FN:3,Query.users(first:)
+        FN:3,Query.users(after:)
+        FN:7,Node.id
+        FN:11,User.id
+        FN:12,User.name
+        FN:12,User.name(format:)
+        FN:13,User.email
+        FNDA:1,Query.node
+        FNDA:1,Query.node(id:)
+        FNDA:2,Query.users
+        FNDA:1,Query.users(first:)
+        FNDA:0,Query.users(after:)
+        FNDA:1,Node.id
+        FNDA:2,User.id
+        FNDA:2,User.name
+        FNDA:1,User.name(format:)
+        FNDA:0,User.email
+        FNF:10
+        FNH:8
+        DA:2,1
+        DA:3,0
+        DA:7,1
+        DA:11,2
+        DA:12,1
+        DA:13,0
+        LF:6
+        LH:4
+        end_of_record
+    "#]]
+    .assert_eq(&coverage.to_lcov());
+}
@@ -1,6 +1,10 @@
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::CoercedValue;
 use apollo_compiler::parse_mixed_validate;
+use apollo_compiler::validation::Valid;
 use apollo_compiler::ExecutableDocument;
 use apollo_compiler::Schema;
+use expect_test::expect;
 
 #[test]
 fn get_operations() {
@@ -290,3 +294,383 @@ fn iter_all_fields() {
         ["f1", "inner", "f2", "f3", "f3"]
     );
 }
+
+#[test]
+fn exhaustiveness() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            type Query {
+                node: Node
+                search: [Result]
+                media: Media
+            }
+            interface Node { id: ID! }
+            interface Media implements Node { id: ID! url: String }
+            type User implements Node { id: ID! name: String friend: Node }
+            type Image implements Node & Media { id: ID! url: String }
+            type Video implements Node & Media { id: ID! url: String }
+            union Result = User | Image | Video
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let check = |query| {
+        let document =
+            ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+        apollo_compiler::executable::check_exhaustiveness(&schema, &document)
+            .into_iter()
+            .map(|item| {
+                format!(
+                    "{} ({}): {}",
+                    item.coordinate,
+                    item.abstract_type,
+                    item.missing_types
+                        .iter()
+                        .map(|ty| ty.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // No type condition: all types are handled the same way
+    assert!(check("{ node { id } media { url } }").is_empty());
+    // Type condition on the field type itself does not narrow
+    assert!(check("{ node { ... on Node { id } } }").is_empty());
+
+    assert_eq!(
+        check("{ node { id ... on User { name } } }"),
+        ["Query.node (Node): Image, Video"]
+    );
+    // A type condition on another interface covers its implementers
+    assert!(check("{ node { ... on User { name } ... on Media { url } } }").is_empty());
+    assert_eq!(
+        check("{ search { ... on Media { url } } }"),
+        ["Query.search (Result): User"]
+    );
+
+    // Fragments, nested in a non-narrowing inline fragment
+    let query = r#"
+        {
+            search { ... { ...U } ...I }
+            other: search { ...U }
+        }
+        fragment U on User { name friend { ...I } }
+        fragment I on Image { url }
+    "#;
+    assert_eq!(
+        check(query),
+        [
+            "Query.search (Result): Video",
+            "Query.search (Result): Image, Video",
+            "User.friend (Node): User, Video",
+        ]
+    );
+}
+
+#[test]
+fn effective_arguments() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        type Query {
+            update(id: ID!, patch: Patch!, dryRun: Boolean = false): Boolean
+        }
+        input Patch {
+            name: String
+            email: String
+            tags: [String!] = []
+            address: Address
+        }
+        input Address {
+            city: String!
+            zip: String
+        }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        query($id: ID!, $name: String, $email: String, $city: String!, $dryRun: Boolean) {
+            update(
+                id: $id,
+                patch: { name: $name, email: $email, address: { city: $city, zip: null } },
+                dryRun: $dryRun,
+            )
+        }
+        "#,
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let field = operation.selection_set.fields().next().unwrap();
+    let effective_arguments = |variables: &str| {
+        let variables = serde_json::from_str(variables).unwrap();
+        let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+        match field.effective_arguments(&schema, &variables) {
+            Ok(arguments) => serde_json::to_string(&arguments).unwrap(),
+            Err(err) => format!("{err:?}"),
+        }
+    };
+
+    // `$name` is explicitly null, `$email` and `$dryRun` are absent
+    expect![[r#"{"id":"1","patch":{"name":null,"tags":[],"address":{"city":"Paris","zip":null}},"dryRun":false}"#]]
+        .assert_eq(&effective_arguments(r#"{"id": "1", "name": null, "city": "Paris"}"#));
+    expect![[r#"{"id":"1","patch":{"name":"N","email":"E","tags":[],"address":{"city":"Paris","zip":null}},"dryRun":true}"#]]
+        .assert_eq(&effective_arguments(
+            r#"{"id": "1", "name": "N", "email": "E", "city": "Paris", "dryRun": true}"#,
+        ));
+}
+
+#[test]
+fn coerced_value_null_vs_absent() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        type Query { update(patch: Patch, limit: Int): Boolean }
+        input Patch { name: String, email: String, age: Int = 0 }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        query($patch: Patch, $name: String, $limit: Int) {
+            fromVariable: update(patch: $patch, limit: $limit)
+            fromLiteral: update(patch: { name: $name, email: null })
+        }
+        "#,
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = serde_json::from_str(r#"{"patch": {"email": null}, "limit": null}"#).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+    assert_eq!(CoercedValue::get(&variables, "limit"), CoercedValue::Null);
+    assert_eq!(CoercedValue::get(&variables, "name"), CoercedValue::Absent);
+    let patch = CoercedValue::get(&variables, "patch");
+    assert_eq!(patch.field("name"), CoercedValue::Absent);
+    assert_eq!(patch.field("email"), CoercedValue::Null);
+    assert_eq!(patch.field("age").value().unwrap(), 0);
+
+    let mut fields = operation.selection_set.fields();
+    let from_variable = fields.next().unwrap();
+    let arguments = from_variable
+        .effective_arguments(&schema, &variables)
+        .unwrap();
+    assert!(CoercedValue::get(&arguments, "limit").is_null());
+    let patch = CoercedValue::get(&arguments, "patch");
+    assert!(patch.field("name").is_absent());
+    assert!(patch.field("email").is_null());
+
+    let from_literal = fields.next().unwrap();
+    let arguments = from_literal
+        .effective_arguments(&schema, &variables)
+        .unwrap();
+    assert!(CoercedValue::get(&arguments, "limit").is_absent());
+    let patch = CoercedValue::get(&arguments, "patch");
+    assert!(patch.field("name").is_absent());
+    assert!(patch.field("email").is_null());
+    assert_eq!(patch.field("age").value().unwrap(), 0);
+    assert!(patch.field("email").field("nested").is_absent());
+}
+
+#[test]
+fn effective_arguments_errors() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        type Query { field(arg: In): Int }
+        input In { required: Int! }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        "query($v: Int!) { field(arg: { required: $v }) }",
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let field = operation.selection_set.fields().next().unwrap();
+    // Skip `coerce_variable_values`, which would reject these variables
+    let effective_arguments = |variables: &str| {
+        let variables = Valid::assume_valid(serde_json::from_str(variables).unwrap());
+        let err = field.effective_arguments(&schema, &variables).unwrap_err();
+        serde_json::to_string(&err.into_graphql_error(&document.sources)).unwrap()
+    };
+
+    expect![[r#"{"message":"null variable value for non-null input field In.required","locations":[{"line":1,"column":42}]}"#]].assert_eq(&effective_arguments(r#"{"v": null}"#));
+    expect![[r#"{"message":"Missing value for non-null input object field In.required","locations":[{"line":1,"column":30}]}"#]].assert_eq(&effective_arguments("{}"));
+}
+
+#[test]
+fn materialize_default_arguments() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @cached(ttl: Int = 60, scope: String) on FIELD | FRAGMENT_SPREAD
+        type Query { users(first: Int = 10, after: String, order: Order = ASC): [User] }
+        type User { name(format: String = "full"): String }
+        enum Order { ASC DESC }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let query = r#"
+        query($after: String) {
+          users(after: $after, first: 5) @cached { ...F @cached(ttl: 1) }
+        }
+        fragment F on User { name @skip(if: false) }
+    "#;
+    let raw = apollo_compiler::parser::Parser::new()
+        .parse_executable(&schema, query, "query.graphql")
+        .unwrap();
+    let users = raw
+        .operations
+        .get(None)
+        .unwrap()
+        .root_fields(&raw)
+        .next()
+        .unwrap();
+    assert_eq!(users.arguments.len(), 2);
+    let with_defaults = users.arguments_with_defaults();
+    let names: Vec<_> = with_defaults.iter().map(|arg| arg.name.as_str()).collect();
+    assert_eq!(names, ["after", "first", "order"]);
+    let cached = users.directives.get("cached").unwrap();
+    assert_eq!(cached.arguments_with_defaults(&schema).len(), 1);
+
+    let effective = apollo_compiler::parser::Parser::new()
+        .materialize_default_arguments(true)
+        .parse_executable(&schema, query, "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap();
+    expect![[r#"
+        query($after: String) {
+          users(after: $after, first: 5, order: ASC) @cached(ttl: 60) {
+            ...F @cached(ttl: 1)
+          }
+        }
+
+        fragment F on User {
+          name(format: "full") @skip(if: false)
+        }
+    "#]]
+    .assert_eq(&effective.to_string());
+}
+
+#[test]
+fn keep_invalid_selections() {
+    let schema = Schema::parse_and_validate(
+        "type Query { user: User } type User { name: String, age: Int }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let query = r#"
+        query($verbose: Boolean!) {
+          user {
+            name
+            nickname @include(if: $verbose)
+            age { value }
+            ... on Admin { level }
+          }
+        }
+    "#;
+
+    let dropped = apollo_compiler::parser::Parser::new()
+        .parse_executable(&schema, query, "query.graphql")
+        .unwrap_err();
+    assert_eq!(dropped.errors.len(), 3);
+    expect![[r#"
+        query($verbose: Boolean!) {
+          user {
+            name
+          }
+        }
+    "#]]
+    .assert_eq(&dropped.partial.to_string());
+
+    let kept = apollo_compiler::parser::Parser::new()
+        .keep_invalid_selections(true)
+        .parse_executable(&schema, query, "query.graphql")
+        .unwrap();
+    let user = kept
+        .operations
+        .get(None)
+        .unwrap()
+        .root_fields(&kept)
+        .next()
+        .unwrap();
+    let messages: Vec<_> = user
+        .selection_set
+        .selections
+        .iter()
+        .filter_map(|selection| selection.as_invalid())
+        .map(|invalid| invalid.message())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "type `User` does not have a field `nickname`",
+            "field selection of scalar type `Int` must not have subselections",
+            "type condition `Admin` of inline fragment is not a type defined in the schema",
+        ]
+    );
+    expect![[r#"
+        query($verbose: Boolean!) {
+          user {
+            name
+            nickname @include(if: $verbose)
+            age {
+              value
+            }
+            ... on Admin {
+              level
+            }
+          }
+        }
+    "#]]
+    .assert_eq(&kept.to_string());
+
+    // The same errors are reported by validation,
+    // without `$verbose` being reported as unused
+    let errors = kept.validate(&schema).unwrap_err().errors;
+    expect![[r#"
+        Error: type `User` does not have a field `nickname`
+           ╭─[query.graphql:5:13]
+           │
+         5 │             nickname @include(if: $verbose)
+           │             ────┬───  
+           │                 ╰───── field `nickname` selected here
+           │
+           ├─[schema.graphql:1:32]
+           │
+         1 │ type Query { user: User } type User { name: String, age: Int }
+           │                                ──┬─  
+           │                                  ╰─── type `User` defined here
+           │ 
+           │ Note: path to the field: `query → user → nickname`
+        ───╯
+        Error: field selection of scalar type `Int` must not have subselections
+           ╭─[query.graphql:6:13]
+           │
+         6 │             age { value }
+           │             ──────┬──────  
+           │                   ╰──────── remove subselections here
+           │ 
+           │ Note: path to the field: `query → user → age`
+        ───╯
+        Error: type condition `Admin` of inline fragment is not a type defined in the schema
+           ╭─[query.graphql:7:20]
+           │
+         7 │             ... on Admin { level }
+           │                    ──┬──  
+           │                      ╰──── type condition here
+           │ 
+           │ Note: path to the inline fragment: `query → user → ...`
+        ───╯
+    "#]]
+    .assert_eq(&errors.to_string());
+}
@@ -0,0 +1,178 @@
+use apollo_compiler::executable::extract_fragments;
+use apollo_compiler::executable::FragmentExtraction;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  me: User
+  user(id: ID!): User
+  node(id: ID!): Node
+}
+interface Node { id: ID! }
+type User implements Node {
+  id: ID!
+  name: String
+  email: String
+  friends: [User]
+  posts: [Post]
+}
+type Post implements Node {
+  id: ID!
+  title: String
+  body: String
+  author: User
+}
+"#;
+
+fn parse(source: &str) -> (Valid<Schema>, ExecutableDocument) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, source, "query.graphql")
+        .unwrap()
+        .into_inner();
+    (schema, document)
+}
+
+#[test]
+fn extracts_nested_repeats_first_by_savings() {
+    let (schema, document) = parse(
+        r#"
+        {
+          me { id name friends { id name email } posts { id title author { id name email } } }
+          user(id: "1") { id name friends { id name email } posts { id title author { id name email } } }
+        }
+        "#,
+    );
+    let extracted = extract_fragments(&document, &FragmentExtraction::default());
+    expect![[r#"
+        {
+          me {
+            ...UserFields
+          }
+          user(id: "1") {
+            ...UserFields
+          }
+        }
+
+        fragment UserFields on User {
+          id
+          name
+          friends {
+            ...UserFields2
+          }
+          posts {
+            id
+            title
+            author {
+              ...UserFields2
+            }
+          }
+        }
+
+        fragment UserFields2 on User {
+          id
+          name
+          email
+        }
+    "#]]
+    .assert_eq(&extracted.to_string());
+    extracted.validate(&schema).unwrap();
+}
+
+#[test]
+fn replaces_inline_fragments() {
+    let (schema, document) = parse(
+        r#"
+        query A { node(id: "1") { ... on Post { id title body } } }
+        query B { node(id: "2") { id ... on Post { id title body } } }
+        query C { node(id: "3") { ... on Post @skip(if: false) { id title body } } }
+        "#,
+    );
+    let extracted = extract_fragments(&document, &FragmentExtraction::default());
+    expect![[r#"
+        query A {
+          node(id: "1") {
+            ...PostFields
+          }
+        }
+
+        query B {
+          node(id: "2") {
+            id
+            ...PostFields
+          }
+        }
+
+        query C {
+          node(id: "3") {
+            ... on Post @skip(if: false) {
+              ...PostFields
+            }
+          }
+        }
+
+        fragment PostFields on Post {
+          id
+          title
+          body
+        }
+    "#]]
+    .assert_eq(&extracted.to_string());
+    extracted.validate(&schema).unwrap();
+}
+
+#[test]
+fn respects_thresholds_and_existing_names() {
+    let (schema, document) = parse(
+        r#"
+        {
+          me { ...UserFields friends { id name } }
+          user(id: "1") { friends { id name } }
+        }
+        fragment UserFields on User { email }
+        "#,
+    );
+
+    let unchanged = extract_fragments(&document, &FragmentExtraction::default());
+    assert_eq!(unchanged, document);
+
+    let options = FragmentExtraction {
+        min_selections: 2,
+        ..Default::default()
+    };
+    let extracted = extract_fragments(&document, &options);
+    expect![[r#"
+        {
+          me {
+            ...UserFields
+            friends {
+              ...UserFields2
+            }
+          }
+          user(id: "1") {
+            friends {
+              ...UserFields2
+            }
+          }
+        }
+
+        fragment UserFields on User {
+          email
+        }
+
+        fragment UserFields2 on User {
+          id
+          name
+        }
+    "#]]
+    .assert_eq(&extracted.to_string());
+    extracted.validate(&schema).unwrap();
+
+    let options = FragmentExtraction {
+        min_selections: 2,
+        min_occurrences: 3,
+    };
+    assert_eq!(extract_fragments(&document, &options), document);
+}
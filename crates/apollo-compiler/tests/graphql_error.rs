@@ -0,0 +1,101 @@
+use apollo_compiler::execution::GraphQLError;
+use apollo_compiler::execution::GraphQLErrorShapeError;
+use apollo_compiler::execution::ResponseDataPathElement;
+use apollo_compiler::execution::ResponsePath;
+use apollo_compiler::name;
+use apollo_compiler::parser::LineColumn;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+#[test]
+fn builder() {
+    let doc = ExecutableDocument::parse(
+        &Schema::parse_and_validate("type Query { a: [Int] }", "schema.graphql").unwrap(),
+        "query {\n  a\n}",
+        "query.graphql",
+    )
+    .unwrap();
+    let field = doc
+        .operations
+        .anonymous
+        .as_ref()
+        .unwrap()
+        .selection_set
+        .selections[0]
+        .as_field()
+        .unwrap();
+    let error = GraphQLError::builder("could not resolve `a`")
+        .location(field.location().unwrap(), &doc.sources)
+        .line_column(LineColumn { line: 1, column: 1 })
+        .path(vec![
+            ResponseDataPathElement::Field(name!("a")),
+            ResponseDataPathElement::ListIndex(2),
+        ])
+        .extension("code", "RESOLVER_ERROR")
+        .extension("retry", true)
+        .build();
+    error.check_spec_shape().unwrap();
+    let json = serde_json::to_string_pretty(&error).unwrap();
+    expect![[r#"
+        {
+          "message": "could not resolve `a`",
+          "locations": [
+            {
+              "line": 2,
+              "column": 3
+            },
+            {
+              "line": 1,
+              "column": 1
+            }
+          ],
+          "path": [
+            "a",
+            2
+          ],
+          "extensions": {
+            "code": "RESOLVER_ERROR",
+            "retry": true
+          }
+        }"#]]
+    .assert_eq(&json);
+
+    let minimal = GraphQLError::builder("oops").build();
+    assert_eq!(minimal, GraphQLError::new("oops", None, &doc.sources));
+}
+
+#[test]
+fn spec_shape() {
+    let error = GraphQLError::builder("oops")
+        .line_column(LineColumn { line: 0, column: 4 })
+        .build();
+    assert_eq!(
+        error.check_spec_shape(),
+        Err(GraphQLErrorShapeError::InvalidLocation { line: 0, column: 4 })
+    );
+
+    let error = GraphQLError::builder("oops")
+        .path("[0].a".parse::<ResponsePath>().unwrap())
+        .build();
+    let err = error.check_spec_shape().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error path `[0].a` starts with a list index instead of a response key"
+    );
+}
+
+#[test]
+fn from_diagnostics() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let errors = ExecutableDocument::parse_and_validate(&schema, "{ a b }", "query.graphql")
+        .unwrap_err()
+        .errors;
+    let json = errors.to_graphql_errors();
+    assert_eq!(json.len(), 1);
+    json[0].check_spec_shape().unwrap();
+    let from_diagnostic = GraphQLError::from(errors.iter().next().unwrap());
+    assert_eq!(json[0], from_diagnostic);
+    expect![[r#"{"message":"type `Query` does not have a field `b`","locations":[{"line":1,"column":5}]}"#]]
+        .assert_eq(&serde_json::to_string(&json[0]).unwrap());
+}
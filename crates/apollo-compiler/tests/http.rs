@@ -0,0 +1,80 @@
+use apollo_compiler::execution::HttpRequestRejection;
+use apollo_compiler::execution::Response;
+use apollo_compiler::execution::ResponseMediaType;
+
+#[test]
+fn media_type_from_accept() {
+    use ResponseMediaType::GraphQLResponseJson;
+    use ResponseMediaType::Json;
+
+    let cases = [
+        (None, Some(Json)),
+        (Some("application/json"), Some(Json)),
+        (
+            Some("application/graphql-response+json"),
+            Some(GraphQLResponseJson),
+        ),
+        (
+            Some("application/json, application/graphql-response+json"),
+            Some(GraphQLResponseJson),
+        ),
+        (
+            Some("application/graphql-response+json;q=0.5, application/json"),
+            Some(Json),
+        ),
+        (Some("application/json;q=0.9, */*;q=0.1"), Some(Json)),
+        (Some("*/*"), Some(GraphQLResponseJson)),
+        (Some("Application/JSON; charset=utf-8"), Some(Json)),
+        (Some("text/html"), None),
+        (Some("application/json;q=0"), None),
+    ];
+    for (accept, expected) in cases {
+        assert_eq!(
+            ResponseMediaType::from_accept(accept),
+            expected,
+            "{accept:?}"
+        );
+    }
+}
+
+#[test]
+fn status_codes() {
+    let data: Response = serde_json::from_str(r#"{ "data": { "a": 1 } }"#).unwrap();
+    let null_data: Response =
+        serde_json::from_str(r#"{ "data": null, "errors": [{ "message": "oops" }] }"#).unwrap();
+    let request_error: Response =
+        serde_json::from_str(r#"{ "errors": [{ "message": "invalid" }] }"#).unwrap();
+    let status = |response: &Response, media_type| response.http_head(media_type).status;
+
+    assert_eq!(status(&data, ResponseMediaType::GraphQLResponseJson), 200);
+    assert_eq!(
+        status(&null_data, ResponseMediaType::GraphQLResponseJson),
+        200
+    );
+    assert_eq!(
+        status(&request_error, ResponseMediaType::GraphQLResponseJson),
+        400
+    );
+    assert_eq!(status(&data, ResponseMediaType::Json), 200);
+    assert_eq!(status(&null_data, ResponseMediaType::Json), 200);
+    assert_eq!(status(&request_error, ResponseMediaType::Json), 200);
+    assert_eq!(
+        request_error.http_head(ResponseMediaType::Json).headers,
+        [("Content-Type", "application/json; charset=utf-8".to_owned())]
+    );
+
+    let head = HttpRequestRejection::MutationOverGet.http_head();
+    assert_eq!(head.status, 405);
+    assert_eq!(head.headers, [("Allow", "POST".to_owned())]);
+    assert_eq!(
+        HttpRequestRejection::MalformedRequest.http_head().status,
+        400
+    );
+    assert_eq!(HttpRequestRejection::NotAcceptable.http_head().status, 406);
+    assert_eq!(
+        HttpRequestRejection::UnsupportedMediaType
+            .http_head()
+            .status,
+        415
+    );
+}
@@ -1,11 +1,16 @@
 use apollo_compiler::ast::FieldDefinition;
 use apollo_compiler::ast::InputValueDefinition;
 use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::execute_introspection;
+use apollo_compiler::execution::execute_introspection_only_query;
+use apollo_compiler::execution::introspection_response_to_ast;
 use apollo_compiler::execution::JsonMap;
 use apollo_compiler::execution::Response;
+use apollo_compiler::execution::ResponseData;
 use apollo_compiler::execution::SchemaIntrospectionQuery;
 use apollo_compiler::name;
 use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::schema::SchemaComparison;
 use apollo_compiler::ty;
 use apollo_compiler::ExecutableDocument;
 use apollo_compiler::Schema;
@@ -216,9 +221,11 @@ fn built_in_scalars() {
         name!(sensor),
         FieldDefinition {
             description: None,
+            leading_comments: Vec::new(),
             name: name!(sensor),
             arguments: vec![InputValueDefinition {
                 description: None,
+                leading_comments: Vec::new(),
                 name: name!(sensorId),
                 ty: ty!(ID).into(),
                 default_value: None,
@@ -239,3 +246,390 @@ fn built_in_scalars() {
     assert!(valid_after_mutation.types.contains_key("String"));
     assert!(valid_after_mutation.types.contains_key("Boolean"));
 }
+
+#[test]
+fn round_trip_to_ast() {
+    let sdl = r#"
+        schema {
+            query: TheQuery
+        }
+
+        "A directive"
+        directive @dir(arg: [In!] = [{ a: 1 }]) on FIELD_DEFINITION | ENUM_VALUE
+
+        """
+        Root query type
+        """
+        type TheQuery implements I {
+            id: ID!
+            ints: [[Int!]]! @deprecated(reason: "…")
+            url(arg: In = { b: 4, a: 2 }): Url
+            union: U @deprecated
+        }
+
+        interface I {
+            id: ID!
+        }
+
+        input In {
+            a: Int! = 0
+            b: Int @deprecated
+        }
+
+        scalar Url
+
+        union U = TheQuery | T
+
+        type T {
+            enum(e: E = OLD): E
+        }
+
+        enum E {
+            "New"
+            NEW
+            OLD @deprecated
+        }
+    "#;
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+    let query = include_str!("../test_data/introspection/introspect_full_schema.graphql");
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let response = execute_introspection_only_query(&schema, &document, operation, &variables);
+    let ResponseData::Object(data) = response.data else {
+        panic!("expected data")
+    };
+    let ast = introspection_response_to_ast(&data).unwrap();
+    expect![[r#"
+        schema {
+          query: TheQuery
+        }
+
+        """A directive"""
+        directive @dir(arg: [In!] = [{a: 1}]) on FIELD_DEFINITION | ENUM_VALUE
+
+        """Root query type"""
+        type TheQuery implements I {
+          id: ID!
+          ints: [[Int!]]! @deprecated(reason: "…")
+          url(arg: In = {b: 4, a: 2}): Url
+          union: U @deprecated
+        }
+
+        interface I {
+          id: ID!
+        }
+
+        input In {
+          a: Int! = 0
+          b: Int @deprecated
+        }
+
+        scalar Url
+
+        union U = TheQuery | T
+
+        type T {
+          enum(e: E = OLD): E
+        }
+
+        enum E {
+          """New"""
+          NEW
+          OLD @deprecated
+        }
+    "#]]
+    .assert_eq(&ast.to_string());
+    let round_tripped = ast.to_schema_validate().unwrap();
+    assert_eq!(round_tripped.to_string(), schema.to_string());
+}
+
+#[test]
+fn invalid_introspection_response() {
+    let convert = |json: &str| {
+        let data: JsonMap = serde_json::from_str(json).unwrap();
+        introspection_response_to_ast(&data)
+            .unwrap_err()
+            .to_string()
+    };
+    expect!["invalid introspection response: missing `__schema`"].assert_eq(&convert("{}"));
+    expect!["invalid introspection response: unexpected kind `WAT` for named type `X`"].assert_eq(
+        &convert(r#"{"__schema": {"types": [{"kind": "WAT", "name": "X"}]}}"#),
+    );
+    expect!["invalid introspection response: missing `ofType` for NON_NULL"].assert_eq(&convert(
+        r#"{"__schema": {"types": [{"kind": "OBJECT", "name": "X", "fields": [
+            {"name": "f", "type": {"kind": "NON_NULL"}}
+        ]}]}}"#,
+    ));
+    expect!["invalid introspection response: unknown directive location `NOWHERE`"].assert_eq(
+        &convert(
+            r#"{"__schema": {"types": [], "directives": [
+            {"name": "d", "locations": ["NOWHERE"]}
+        ]}}"#,
+        ),
+    );
+    expect![
+        "invalid introspection response: invalid type: floating point `4.0`, expected a sequence"
+    ]
+    .assert_eq(&convert(r#"{"__schema": {"types": 4}}"#));
+}
+
+#[test]
+fn schema_from_introspection_json() {
+    let sdl = r#"
+        type Query {
+          id: ID!
+          when: Date
+        }
+
+        scalar Date
+    "#;
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+    let query = include_str!("../test_data/introspection/introspect_full_schema.graphql");
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let response = execute_introspection_only_query(&schema, &document, operation, &variables);
+    let json = serde_json::to_string(&response).unwrap();
+    let from_response = Schema::from_introspection_json(&json).unwrap();
+    assert_eq!(from_response.to_string(), schema.to_string());
+
+    let ResponseData::Object(data) = response.data else {
+        panic!("expected data")
+    };
+    let json = serde_json::to_vec(&data).unwrap();
+    let from_data = Schema::from_introspection_json(json).unwrap();
+    assert_eq!(from_data.to_string(), schema.to_string());
+
+    // graphql-js before v16
+    let json = r#"{"__schema": {"queryType": {"name": "Query"}, "types": [
+        {"kind": "OBJECT", "name": "Query", "fields": [
+            {"name": "when", "args": [], "type": {"kind": "SCALAR", "name": "Date"}}
+        ]},
+        {"kind": "SCALAR", "name": "Date", "specifiedByUrl": "https://example.com/date"}
+    ]}}"#;
+    let from_legacy = Schema::from_introspection_json(json).unwrap();
+    expect![[r#"
+        type Query {
+          when: Date
+        }
+
+        scalar Date @specifiedBy(url: "https://example.com/date")
+    "#]]
+    .assert_eq(&from_legacy.to_string());
+}
+
+#[test]
+fn schema_from_invalid_introspection_json() {
+    let convert = |json: &str| {
+        Schema::from_introspection_json(json)
+            .unwrap_err()
+            .to_string()
+    };
+    expect!["malformed introspection JSON: EOF while parsing a value at line 1 column 12"]
+        .assert_eq(&convert(r#"{"__schema":"#));
+    expect!["invalid introspection response: expected a JSON object"].assert_eq(&convert("[]"));
+    expect!["introspection response has errors: introspection is disabled; second"].assert_eq(
+        &convert(
+            r#"{"data": null, "errors": [{"message": "introspection is disabled"}, {"message": "second"}]}"#,
+        ),
+    );
+    expect!["invalid introspection response: missing `__schema`"]
+        .assert_eq(&convert(r#"{"data": {"__type": null}}"#));
+    expect![[r#"
+        introspected schema is invalid:
+        Error: cannot find type `Missing` in this document
+    "#]]
+    .assert_eq(&convert(
+        r#"{"__schema": {"queryType": {"name": "Query"}, "types": [
+            {"kind": "OBJECT", "name": "Query", "fields": [
+                {"name": "f", "args": [], "type": {"kind": "OBJECT", "name": "Missing"}}
+            ]}
+        ]}}"#,
+    ));
+}
+
+#[test]
+fn classify_introspection_failures() {
+    let classify = |json: &str| {
+        let response: Response = serde_json::from_str(json).unwrap();
+        let failure = response.introspection_failure().unwrap();
+        format!("{failure}\n{}", failure.hint())
+    };
+    let response: Response = serde_json::from_str(r#"{"data": {"__schema": null}}"#).unwrap();
+    assert!(response.introspection_failure().is_none());
+
+    expect![[r#"
+        introspection is disabled: GraphQL introspection is not allowed by Apollo Server, but the query contained __schema or __type.
+        Introspection is disabled on this server. Enable it, or get the schema from another source such as a schema file."#]].assert_eq(&classify(
+        r#"{"errors": [{"message": "GraphQL introspection is not allowed by Apollo Server, but the query contained __schema or __type."}]}"#,
+    ));
+    expect![[r#"
+        introspection query uses unsupported fields: __Type.isOneOf, __Type.inputFields(includeDeprecated:)
+        The server does not support `__Type.isOneOf`, `__Type.inputFields(includeDeprecated:)`, which may have been added in a later version of the GraphQL specification. Remove them from the introspection query."#]].assert_eq(&classify(
+        r#"{"errors": [
+            {"message": "Cannot query field \"isOneOf\" on type \"__Type\"."},
+            {"message": "Unknown argument \"includeDeprecated\" on field \"__Type.inputFields\"."},
+            {"message": "Cannot query field \"nope\" on type \"Query\"."}
+        ]}"#,
+    ));
+    expect![[r#"
+        introspection query uses unsupported fields: __Directive.isRepeatable
+        The server does not support `__Directive.isRepeatable`, which may have been added in a later version of the GraphQL specification. Remove it from the introspection query."#]].assert_eq(&classify(
+        r#"{"errors": [{"message": "type `__Directive` does not have a field `isRepeatable`"}]}"#,
+    ));
+    expect![[r#"
+        introspection response is partial: resolver failed
+        The server returned errors together with data, so the schema may be incomplete. Check the errors and the server logs."#]].assert_eq(&classify(
+        r#"{"data": {"__schema": {}}, "errors": [{"message": "resolver failed"}]}"#,
+    ));
+    expect![[r#"
+        introspection response has errors: Unauthorized
+        Check the errors returned by the server."#]]
+    .assert_eq(&classify(r#"{"errors": [{"message": "Unauthorized"}]}"#));
+
+    let error = Schema::from_introspection_json(
+        r#"{"data": null, "errors": [{"message": "Introspection is disabled"}]}"#,
+    )
+    .unwrap_err();
+    expect!["introspection is disabled: Introspection is disabled"]
+        .assert_eq(&error.introspection_failure().unwrap().to_string());
+    let error = Schema::from_introspection_json("[]").unwrap_err();
+    assert!(error.introspection_failure().is_none());
+}
+
+#[test]
+fn execute_introspection_from_text() {
+    let schema = Schema::parse_and_validate(
+        "type Query { a: Int } enum E { X Y @deprecated }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let execute = |query: &str, operation_name: Option<&str>, variables: &str| {
+        let variables = serde_json::from_str(variables).unwrap();
+        let response = execute_introspection(&schema, query, operation_name, &variables);
+        serde_json::to_string(&response).unwrap()
+    };
+
+    let query = r#"
+        query A($deprecated: Boolean) {
+            __typename
+            __type(name: "E") { enumValues(includeDeprecated: $deprecated) { name } }
+        }
+        query B { __schema { queryType { name } } }
+    "#;
+    expect![[
+        r#"{"data":{"__typename":"Query","__type":{"enumValues":[{"name":"X"},{"name":"Y"}]}}}"#
+    ]]
+    .assert_eq(&execute(query, Some("A"), r#"{"deprecated": true}"#));
+    expect![[r#"{"data":{"__schema":{"queryType":{"name":"Query"}}}}"#]].assert_eq(&execute(
+        query,
+        Some("B"),
+        "{}",
+    ));
+
+    // Request errors
+    expect![[
+        r#"{"errors":[{"message":"operation name is required in a request with many operations"}]}"#
+    ]]
+    .assert_eq(&execute(query, None, "{}"));
+    expect![[r#"{"errors":[{"message":"operation selects fields other than introspection meta-fields","locations":[{"line":1,"column":1}]}]}"#]]
+        .assert_eq(&execute("{ a __typename }", None, "{}"));
+    expect![[r#"{"errors":[{"message":"type `Query` does not have a field `b`","locations":[{"line":1,"column":3}]}]}"#]]
+        .assert_eq(&execute("{ b }", None, "{}"));
+    expect![[
+        r#"{"errors":[{"message":"Could not coerce variable deprecated: 1 to type Boolean"}]}"#
+    ]]
+    .assert_eq(&execute(query, Some("A"), r#"{"deprecated": 1}"#));
+}
+
+#[test]
+fn tolerant_comparison_with_introspection() {
+    let sdl = r#"
+        directive @cacheControl(maxAge: Int) on FIELD_DEFINITION | OBJECT | ARGUMENT_DEFINITION | SCALAR
+        directive @specifiedBy(url: String!) on SCALAR
+
+        extend scalar String @cacheControl
+
+        type Query @cacheControl(maxAge: 60) {
+          """
+          A user,
+            by ID
+          """
+          user(id: ID! @cacheControl): User
+          me: User @deprecated(reason: "No longer supported")
+        }
+
+        type User {
+          name: String
+        }
+
+        scalar Date
+    "#;
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+    let query = include_str!("../test_data/introspection/introspect_full_schema.graphql");
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let response = execute_introspection_only_query(&schema, &document, operation, &variables);
+    let json = serde_json::to_string(&response).unwrap();
+    let introspected = Schema::from_introspection_json(&json).unwrap();
+
+    assert_ne!(
+        introspected.serialize().canonical().to_string(),
+        schema.serialize().canonical().to_string()
+    );
+    let comparison = SchemaComparison::default();
+    assert_eq!(
+        comparison.normalize(&introspected),
+        comparison.normalize(&schema)
+    );
+    assert!(introspected.tolerant_eq(&schema));
+    expect![[r#"
+        directive @cacheControl(maxAge: Int) on ARGUMENT_DEFINITION | FIELD_DEFINITION | OBJECT | SCALAR
+
+        scalar Date
+
+        type Query {
+          me: User @deprecated
+          """A user, by ID"""
+          user(id: ID!): User
+        }
+
+        type User {
+          name: String
+        }
+    "#]]
+    .assert_eq(&comparison.normalize(&schema));
+
+    // Each option is needed for this schema
+    for comparison in [
+        SchemaComparison {
+            ignore_built_in_definitions: false,
+            ..Default::default()
+        },
+        SchemaComparison {
+            introspectable_directives_only: false,
+            ..Default::default()
+        },
+    ] {
+        assert!(!comparison.eq(&introspected, &schema), "{comparison:?}");
+    }
+    let reformatted =
+        Schema::parse_and_validate(sdl.replace("A user,", "A user,\n\n"), "schema.graphql")
+            .unwrap();
+    assert!(introspected.tolerant_eq(&reformatted));
+    let comparison = SchemaComparison {
+        normalize_description_whitespace: false,
+        ..Default::default()
+    };
+    assert!(!comparison.eq(&introspected, &reformatted));
+
+    // Meaningful differences are not ignored
+    let other = Schema::parse_and_validate(
+        sdl.replace("name: String", "name: String!"),
+        "schema.graphql",
+    )
+    .unwrap();
+    assert!(!introspected.tolerant_eq(&other));
+}
@@ -0,0 +1,184 @@
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::execute_with_json_data;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    scalar JSON
+
+    type Query {
+        me: User!
+        node(id: ID!): Node
+        search: [Result!]
+        settings: JSON
+        count: Int
+    }
+
+    type Mutation {
+        rename(name: String!): User
+    }
+
+    interface Node {
+        id: ID!
+    }
+
+    type User implements Node {
+        id: ID!
+        name: String!
+        friends: [User]
+    }
+
+    type Post implements Node {
+        id: ID!
+        title: String
+    }
+
+    union Result = User | Post
+"#;
+
+fn execute(query: &str, root_value: &str) -> String {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let root_value = serde_json::from_str(root_value).unwrap();
+    let response = execute_with_json_data(&schema, &document, operation, &variables, &root_value);
+    serde_json::to_string_pretty(&response).unwrap()
+}
+
+#[test]
+fn objects_lists_and_scalars() {
+    let query = r#"
+        {
+            me { __typename id name friends { name } }
+            node(id: "2") { id ... on Post { title } }
+            settings
+            count
+        }
+    "#;
+    let root_value = r#"{
+        "me": {"id": 1, "name": "Alice", "friends": [{"name": "Bob"}, null]},
+        "node": {"__typename": "Post", "id": "2", "title": "Hello"},
+        "settings": {"theme": "dark"}
+    }"#;
+    expect![[r#"
+        {
+          "data": {
+            "me": {
+              "__typename": "User",
+              "id": 1,
+              "name": "Alice",
+              "friends": [
+                {
+                  "name": "Bob"
+                },
+                null
+              ]
+            },
+            "node": {
+              "id": "2",
+              "title": "Hello"
+            },
+            "settings": {
+              "theme": "dark"
+            },
+            "count": null
+          }
+        }"#]]
+    .assert_eq(&execute(query, root_value));
+}
+
+#[test]
+fn field_errors() {
+    let query = "{ search { ... on User { name } } count }";
+    let root_value = r#"{
+        "search": [{"__typename": "User"}, {"name": "Bob"}],
+        "count": "many"
+    }"#;
+    expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "resolver error: missing __typename in JSON object of abstract type Result",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 3
+                }
+              ],
+              "path": [
+                "search"
+              ]
+            },
+            {
+              "message": "Resolver returned \"many\", expected Int",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 35
+                }
+              ],
+              "path": [
+                "count"
+              ]
+            }
+          ],
+          "data": {
+            "search": null,
+            "count": null
+          }
+        }"#]]
+    .assert_eq(&execute(query, root_value));
+}
+
+#[test]
+fn null_propagation() {
+    let query = "{ search { ... on User { name } } me { id name } }";
+    let root_value = r#"{
+        "search": [{"__typename": "Post"}, {"__typename": "User"}],
+        "me": {"id": "1", "name": "Alice"}
+    }"#;
+    expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "Non-null type String! resolved to null",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 26
+                }
+              ],
+              "path": [
+                "search",
+                1,
+                "name"
+              ]
+            }
+          ],
+          "data": {
+            "search": null,
+            "me": {
+              "id": "1",
+              "name": "Alice"
+            }
+          }
+        }"#]]
+    .assert_eq(&execute(query, root_value));
+}
+
+#[test]
+fn mutation() {
+    let query = r#"mutation { rename(name: "Carol") { name } }"#;
+    let root_value = r#"{"rename": {"id": "1", "name": "Carol"}}"#;
+    expect![[r#"
+        {
+          "data": {
+            "rename": {
+              "name": "Carol"
+            }
+          }
+        }"#]]
+    .assert_eq(&execute(query, root_value));
+}
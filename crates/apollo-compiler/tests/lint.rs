@@ -0,0 +1,137 @@
+use apollo_compiler::ast::OperationType;
+use apollo_compiler::diagnostic::ToCliReport;
+use apollo_compiler::schema::lint::LintContext;
+use apollo_compiler::schema::lint::LintElement;
+use apollo_compiler::schema::lint::LintRule;
+use apollo_compiler::schema::lint::Linter;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    directive @owner(team: String!, on_call: String) on OBJECT | FIELD_DEFINITION
+
+    type Query {
+        user(user_id: ID!): User @owner(team: "accounts")
+        all_users: [User]
+    }
+
+    type User @owner(team: "accounts") {
+        id: ID!
+        status: account_status
+    }
+
+    type internal_Audit {
+        Action: String
+    }
+
+    enum account_status {
+        ACTIVE
+        suspended
+    }
+
+    input UserFilter {
+        Status: account_status
+    }
+"#;
+
+/// Requires every object type except root operation types to have an `@owner` directive
+struct RequireOwner;
+
+impl LintRule for RequireOwner {
+    fn code(&self) -> &str {
+        "acme/require-owner"
+    }
+
+    fn check(&self, cx: &mut LintContext<'_>, element: LintElement<'_>) {
+        let LintElement::Type(ty) = element else {
+            return;
+        };
+        let is_root = [OperationType::Query, OperationType::Mutation]
+            .into_iter()
+            .any(|kind| cx.schema().root_operation(kind) == Some(ty.name()));
+        if ty.is_object() && !is_root && ty.directives().get("owner").is_none() {
+            cx.report(format!("type {} has no @owner", ty.name()))
+        }
+    }
+}
+
+fn format(linter: &Linter, schema: &str) -> String {
+    let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+    linter
+        .lint(&schema)
+        .iter()
+        .map(|diagnostic| format!("{}: {diagnostic}\n", diagnostic.coordinate))
+        .collect()
+}
+
+#[test]
+fn built_in_rules() {
+    expect![[r#"
+        Query.user(user_id:): argument name `user_id` should be camelCase [field-names-camel-case]
+        Query.all_users: field name `all_users` should be camelCase [field-names-camel-case]
+        internal_Audit: type name `internal_Audit` should be PascalCase [type-names-pascal-case]
+        internal_Audit.Action: field name `Action` should be camelCase [field-names-camel-case]
+        account_status: type name `account_status` should be PascalCase [type-names-pascal-case]
+        account_status.suspended: enum value `suspended` should be UPPER_CASE [enum-values-upper-case]
+        UserFilter.Status: input field name `Status` should be camelCase [field-names-camel-case]
+        @owner(on_call:): argument name `on_call` should be camelCase [field-names-camel-case]
+    "#]]
+    .assert_eq(&format(&Linter::new(), SCHEMA));
+}
+
+#[test]
+fn custom_rules() {
+    let linter = Linter::new()
+        .disable("enum-values-upper-case")
+        .rule(RequireOwner)
+        .rule_fn("acme/no-internal-types", |cx, element| {
+            if let LintElement::Type(ty) = element {
+                if ty.name().starts_with("internal") {
+                    cx.report_at(ty.name().location(), "internal types must not be exposed")
+                }
+            }
+        });
+    assert_eq!(
+        linter.codes().collect::<Vec<_>>(),
+        [
+            "type-names-pascal-case",
+            "field-names-camel-case",
+            "acme/require-owner",
+            "acme/no-internal-types",
+        ]
+    );
+    expect![[r#"
+        Query.user(user_id:): argument name `user_id` should be camelCase [field-names-camel-case]
+        Query.all_users: field name `all_users` should be camelCase [field-names-camel-case]
+        internal_Audit: type name `internal_Audit` should be PascalCase [type-names-pascal-case]
+        internal_Audit: type internal_Audit has no @owner [acme/require-owner]
+        internal_Audit: internal types must not be exposed [acme/no-internal-types]
+        internal_Audit.Action: field name `Action` should be camelCase [field-names-camel-case]
+        account_status: type name `account_status` should be PascalCase [type-names-pascal-case]
+        UserFilter.Status: input field name `Status` should be camelCase [field-names-camel-case]
+        @owner(on_call:): argument name `on_call` should be camelCase [field-names-camel-case]
+    "#]]
+    .assert_eq(&format(&linter, SCHEMA));
+}
+
+#[test]
+fn cli_report() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let diagnostics = Linter::empty().rule(RequireOwner).lint(&schema);
+    let reports: String = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.to_diagnostic(&schema.sources).to_string())
+        .collect();
+    expect![[r#"
+        Error: type internal_Audit has no @owner [acme/require-owner]
+            ╭─[schema.graphql:14:5]
+            │
+         14 │ ╭─▶     type internal_Audit {
+            ┆ ┆   
+         16 │ ├─▶     }
+            │ │           
+            │ ╰─────────── `internal_Audit` is defined here
+        ────╯
+    "#]]
+    .assert_eq(&reports);
+}
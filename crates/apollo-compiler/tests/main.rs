@@ -1,19 +1,58 @@
+mod canonical;
+#[cfg(feature = "experimental-client-controlled-nullability")]
+mod client_controlled_nullability;
+#[cfg(feature = "conformance")]
+mod conformance;
+mod cost;
+mod coverage;
 mod executable;
 mod extensions;
+mod extract_fragments;
 mod field_set;
 mod field_type;
+mod graphql_error;
+mod http;
 mod introspection;
 mod introspection_max_depth;
 mod introspection_split;
+mod json_data;
+mod lint;
 mod locations;
+mod mask;
+mod memory_footprint;
+mod merge_responses;
 mod merge_schemas;
+mod meta_fields;
+mod metadata;
 /// Formerly in src/lib.rs
 mod misc;
 mod name;
+mod number_precision;
+mod operation_archive;
+mod operation_router;
 mod parser;
+mod payload;
+mod persisted;
+mod provenance;
+mod representations;
+mod request;
+mod response_keys;
+mod response_path;
+mod scalars;
 mod schema;
 mod serde;
+mod spec_edition;
+mod split_schema;
+#[cfg(feature = "futures")]
+mod stream;
+mod strip_directives;
+mod subscription;
+mod sunset;
+mod tables;
+mod typescript;
 mod validation;
+mod value_builder;
+mod websocket;
 
 #[path = "../examples/rename.rs"]
 mod rename;
@@ -0,0 +1,150 @@
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::mask;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    type Query {
+        me: User
+        search: [Result]
+        node(id: ID!): Node
+    }
+
+    interface Node {
+        id: ID!
+    }
+
+    type User implements Node {
+        id: ID!
+        name: String
+        email: String
+        friends: [User]
+    }
+
+    type Post implements Node {
+        id: ID!
+        title: String
+        body: String
+        author: User
+    }
+
+    union Result = User | Post
+"#;
+
+const DATA: &str = r#"{
+    "me": {
+        "id": "1",
+        "name": "Alice",
+        "email": "alice@example.com",
+        "friends": [
+            {"id": "2", "name": "Bob", "email": "bob@example.com"},
+            null
+        ]
+    },
+    "search": [
+        {"__typename": "User", "id": "2", "name": "Bob", "email": "bob@example.com"},
+        {"__typename": "Post", "id": "3", "title": "Hello", "body": "…", "author": {"id": "1"}},
+        {"id": "4", "title": "Unknown type"}
+    ],
+    "node": {"__typename": "Post", "id": "3", "title": "Hello", "body": "…"}
+}"#;
+
+fn mask_data(query: &str, variables: &str) -> String {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = serde_json::from_str(variables).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+    let data = serde_json::from_str(DATA).unwrap();
+    let masked = mask(&schema, &document, operation, &variables, &data);
+    serde_json::to_string_pretty(&masked).unwrap()
+}
+
+#[test]
+fn fields_and_aliases() {
+    let query = "{ me { name mail: email friends { name } } }";
+    expect![[r#"
+        {
+          "me": {
+            "name": "Alice",
+            "mail": "alice@example.com",
+            "friends": [
+              {
+                "name": "Bob"
+              },
+              null
+            ]
+          }
+        }"#]]
+    .assert_eq(&mask_data(query, "{}"));
+}
+
+#[test]
+fn abstract_types() {
+    let query = r#"
+        {
+            search {
+                __typename
+                ... on User { name }
+                ... PostFields
+            }
+            node(id: "3") {
+                id
+                ... on Node { __typename }
+                ... on User { email }
+            }
+        }
+        fragment PostFields on Post { title author { id name } }
+    "#;
+    expect![[r#"
+        {
+          "search": [
+            {
+              "__typename": "User",
+              "name": "Bob"
+            },
+            {
+              "__typename": "Post",
+              "title": "Hello",
+              "author": {
+                "id": "1"
+              }
+            },
+            {}
+          ],
+          "node": {
+            "id": "3",
+            "__typename": "Post"
+          }
+        }"#]]
+    .assert_eq(&mask_data(query, "{}"));
+}
+
+#[test]
+fn skip_and_include() {
+    let query = r#"
+        query($withEmail: Boolean!) {
+            me {
+                id @skip(if: true)
+                name
+                email @include(if: $withEmail)
+            }
+        }
+    "#;
+    expect![[r#"
+        {
+          "me": {
+            "name": "Alice"
+          }
+        }"#]]
+    .assert_eq(&mask_data(query, r#"{"withEmail": false}"#));
+    expect![[r#"
+        {
+          "me": {
+            "name": "Alice",
+            "email": "alice@example.com"
+          }
+        }"#]]
+    .assert_eq(&mask_data(query, r#"{"withEmail": true}"#));
+}
@@ -0,0 +1,77 @@
+use apollo_compiler::ast::Directive;
+use apollo_compiler::name;
+use apollo_compiler::parser::detach_sources;
+use apollo_compiler::schema::Component;
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use std::sync::Arc;
+
+const SCHEMA: &str = r#"
+    "The root query type, with a description long enough to stand out in the footprint"
+    type Query {
+        users(first: Int = 10): [User!]! @deprecated(reason: "Use `search` instead")
+        search(text: String!): [User!]!
+    }
+
+    type User @key(fields: "id") {
+        id: ID!
+        name: String
+    }
+
+    directive @key(fields: String!) on OBJECT
+"#;
+
+#[test]
+fn schema_footprint_by_component() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let footprint = schema.memory_footprint();
+    assert!(footprint.names > 0);
+    assert!(footprint.descriptions >= "The root query type".len());
+    assert!(footprint.directives > 0);
+    assert!(footprint.spans > 0);
+    assert!(footprint.sources >= SCHEMA.len());
+    assert!(footprint.other > 0);
+    assert_eq!(
+        footprint.total(),
+        footprint.names
+            + footprint.descriptions
+            + footprint.directives
+            + footprint.spans
+            + footprint.sources
+            + footprint.other
+    );
+
+    // Cloning shares allocations, so the estimate does not change
+    assert_eq!(schema.clone().memory_footprint(), footprint);
+
+    let mut larger = schema.clone().into_inner();
+    let ExtendedType::Object(user) = &mut larger.types["User"] else {
+        panic!("expected an object type")
+    };
+    user.make_mut()
+        .directives
+        .push(Component::new(Directive::new(name!("key"))));
+    assert!(larger.memory_footprint().directives > footprint.directives);
+}
+
+#[test]
+fn executable_footprint_excludes_schema() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = "query Users { users { id name } search(text: \"a\") { id } }";
+    let doc = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let footprint = doc.memory_footprint();
+    assert!(footprint.names > 0);
+    assert_eq!(footprint.descriptions, 0);
+    assert_eq!(footprint.directives, 0);
+    assert!(footprint.sources >= query.len());
+    assert!(footprint.total() < schema.memory_footprint().total());
+
+    // Dropping source text reduces the `sources` component
+    let mut doc = doc.into_inner();
+    detach_sources(&mut doc.sources, |_file| {
+        Some(Arc::new(|| Some(query.to_owned())))
+    });
+    assert!(doc.memory_footprint().sources < footprint.sources);
+    assert_eq!(doc.memory_footprint().other, footprint.other);
+}
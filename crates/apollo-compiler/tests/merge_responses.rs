@@ -0,0 +1,144 @@
+use apollo_compiler::execution::merge_responses;
+use apollo_compiler::execution::Response;
+use apollo_compiler::execution::ResponseMergeError;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    type Query {
+        me: User
+        products: [Product]
+    }
+
+    type User {
+        id: ID!
+        name: String
+        tags: [String]
+        best: Product
+    }
+
+    type Product {
+        upc: ID!
+        price: Int
+        reviews: [Review]
+        dimensions: JSON
+    }
+
+    type Review {
+        body: String
+    }
+
+    scalar JSON
+"#;
+
+const QUERY: &str = r#"
+    query {
+        me { id ...UserFields }
+        products { upc price reviews { body } dimensions }
+    }
+
+    fragment UserFields on User {
+        name
+        tags
+        best { upc price }
+    }
+"#;
+
+fn merge(left: &str, right: &str, at_path: &str) -> Result<String, ResponseMergeError> {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let left: Response = serde_json::from_str(left).unwrap();
+    let right: Response = serde_json::from_str(right).unwrap();
+    let at_path = if at_path.is_empty() {
+        Default::default()
+    } else {
+        at_path.parse().unwrap()
+    };
+    let merged = merge_responses(&schema, &document, operation, left, right, &at_path)?;
+    Ok(serde_json::to_string(&merged).unwrap())
+}
+
+#[test]
+fn deep_merge_at_root() {
+    let left = r#"{
+        "data": {
+            "me": { "id": "1", "name": "Alice" },
+            "products": [{ "upc": "a", "reviews": [{ "body": "good" }] }, { "upc": "b" }]
+        }
+    }"#;
+    let right = r#"{
+        "data": {
+            "me": { "id": "1", "tags": ["x"], "best": { "upc": "a", "price": 12 } },
+            "products": [{ "upc": "a", "price": 12 }, { "upc": "b", "price": null }]
+        },
+        "errors": [{ "message": "no price", "path": ["products", 1, "price"] }],
+        "extensions": { "cost": 3 }
+    }"#;
+    expect![[r#"{"errors":[{"message":"no price","path":["products",1,"price"]}],"data":{"me":{"id":"1","name":"Alice","tags":["x"],"best":{"upc":"a","price":12}},"products":[{"upc":"a","reviews":[{"body":"good"}],"price":12},{"upc":"b","price":null}]},"extensions":{"cost":3}}"#]]
+        .assert_eq(&merge(left, right, "").unwrap());
+
+    // Null from a field error wins over a value
+    let right = r#"{ "data": { "me": null }, "errors": [{ "message": "boom", "path": ["me"] }] }"#;
+    expect![[r#"{"errors":[{"message":"boom","path":["me"]}],"data":{"me":null,"products":[{"upc":"a","reviews":[{"body":"good"}]},{"upc":"b"}]}}"#]]
+        .assert_eq(&merge(left, right, "").unwrap());
+
+    // Root-level null and request errors
+    let right = r#"{ "data": null, "errors": [{ "message": "boom" }] }"#;
+    expect![[r#"{"errors":[{"message":"boom"}],"data":null}"#]]
+        .assert_eq(&merge(left, right, "").unwrap());
+    let right = r#"{ "errors": [{ "message": "invalid" }] }"#;
+    expect![[r#"{"errors":[{"message":"invalid"}]}"#]].assert_eq(&merge(left, right, "").unwrap());
+}
+
+#[test]
+fn merge_at_path() {
+    let left = r#"{
+        "data": { "products": [{ "upc": "a" }, { "upc": "b" }] },
+        "errors": [{ "message": "first" }]
+    }"#;
+    let right = r#"{
+        "data": { "price": 5, "reviews": [{ "body": null }] },
+        "errors": [{ "message": "no body", "path": ["reviews", 0, "body"] }]
+    }"#;
+    expect![[r#"{"errors":[{"message":"first"},{"message":"no body","path":["products",1,"reviews",0,"body"]}],"data":{"products":[{"upc":"a"},{"upc":"b","price":5,"reviews":[{"body":null}]}]}}"#]]
+        .assert_eq(&merge(left, right, "products[1]").unwrap());
+
+    // A request error from a sub-fetch becomes a field error
+    let right = r#"{ "errors": [{ "message": "unreachable" }] }"#;
+    expect![[r#"{"errors":[{"message":"first"},{"message":"unreachable","path":["products",1]}],"data":{"products":[{"upc":"a"},null]}}"#]]
+        .assert_eq(&merge(left, right, "products[1]").unwrap());
+
+    // Nothing at the path: data is discarded but errors are kept
+    let right = r#"{ "data": { "price": 5 }, "errors": [{ "message": "e" }] }"#;
+    expect![[r#"{"errors":[{"message":"first"},{"message":"e","path":["products",7]}],"data":{"products":[{"upc":"a"},{"upc":"b"}]}}"#]]
+        .assert_eq(&merge(left, right, "products[7]").unwrap());
+}
+
+#[test]
+fn conflicts() {
+    let left = r#"{
+        "data": {
+            "me": { "id": "1", "tags": ["x", "y"], "best": { "upc": "a" } },
+            "products": [{ "upc": "a", "dimensions": { "w": 1 } }]
+        }
+    }"#;
+    let right = r#"{
+        "data": {
+            "me": { "id": "2", "tags": ["x"], "best": { "upc": "a", "price": 1 } },
+            "products": [{ "upc": "a", "dimensions": { "h": 2 } }, { "upc": "b" }]
+        }
+    }"#;
+    let err = merge(left, right, "").unwrap_err();
+    expect![[r#"conflicting values when merging responses: at `me.id`, "1" and "2"; at `me.tags`, ["x","y"] and ["x"]; at `products`, [{"upc":"a","dimensions":{"w":1}}] and [{"upc":"a","dimensions":{"h":2}},{"upc":"b"}]"#]]
+        .assert_eq(&err.to_string());
+    // Non-conflicting parts are still merged
+    expect![[r#"{"data":{"me":{"id":"1","tags":["x","y"],"best":{"upc":"a","price":1}},"products":[{"upc":"a","dimensions":{"w":1}}]}}"#]]
+        .assert_eq(&serde_json::to_string(&err.response).unwrap());
+
+    // Custom scalars are compared as a whole, not merged
+    let right = r#"{ "data": { "products": [{ "upc": "a", "dimensions": { "h": 2 } }] } }"#;
+    expect![[r#"conflicting values when merging responses: at `products[0].dimensions`, {"w":1} and {"h":2}"#]]
+        .assert_eq(&merge(left, right, "").unwrap_err().to_string());
+}
@@ -0,0 +1,188 @@
+use apollo_compiler::execution::request;
+use apollo_compiler::execution::request::RequestOptions;
+use apollo_compiler::execution::JsonMap;
+use apollo_compiler::execution::JsonValue;
+use apollo_compiler::execution::MetaFieldRegistry;
+use apollo_compiler::execution::OperationRouter;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    type Query {
+        me: User
+    }
+
+    type User {
+        id: ID!
+        name: String
+        reviews: [Review]
+    }
+
+    type Review {
+        body: String
+    }
+"#;
+
+fn federation() -> MetaFieldRegistry {
+    MetaFieldRegistry::new()
+        .definitions(
+            r#"
+            scalar _Any
+            union _Entity = User | Review
+            type _Service { sdl: String }
+            extend type Query {
+                _service: _Service!
+                _entities(representations: [_Any!]!): [_Entity]!
+            }
+            extend type User {
+                _version: Int
+            }
+            "#,
+        )
+        .register("Query", "_service", |_: &JsonMap| {
+            Ok(serde_json::json!({ "sdl": SCHEMA.trim() }).into())
+        })
+        .register("Query", "_entities", |arguments: &JsonMap| {
+            let representations = arguments["representations"].as_array().unwrap();
+            Ok(representations
+                .iter()
+                .map(|representation| {
+                    let mut entity = representation.as_object().unwrap().clone();
+                    entity.insert("name", format!("User {}", entity["id"]).into());
+                    JsonValue::Object(entity)
+                })
+                .collect::<Vec<_>>()
+                .into())
+        })
+        .register("User", "_version", |_: &JsonMap| Ok(2.into()))
+        .register("Review", "body", |_: &JsonMap| {
+            Err("reviews are not available".into())
+        })
+}
+
+#[test]
+fn federation_meta_fields() {
+    let meta_fields = federation();
+    let schema = Schema::parse(SCHEMA, "schema.graphql").unwrap();
+    let schema = meta_fields.extend_schema(&schema).unwrap();
+    let options = RequestOptions {
+        meta_fields,
+        ..Default::default()
+    };
+    let root_value = serde_json::from_str(
+        r#"{ "me": { "id": "1", "name": "Alice", "reviews": [{ "body": "Great" }] } }"#,
+    )
+    .unwrap();
+    let execute = |document: &str, variables: &str| {
+        let variables = serde_json::from_str(variables).unwrap();
+        let response = request::execute(&schema, document, None, &variables, &root_value, &options);
+        serde_json::to_string_pretty(&response).unwrap()
+    };
+
+    let expected = expect![[r#"
+        {
+          "data": {
+            "_service": {
+              "sdl": "type Query {\n        me: User\n    }\n\n    type User {\n        id: ID!\n        name: String\n        reviews: [Review]\n    }\n\n    type Review {\n        body: String\n    }"
+            }
+          }
+        }"#]];
+    expected.assert_eq(&execute("{ _service { sdl } }", "{}"));
+
+    let document = r#"
+        query($representations: [_Any!]!) {
+            _entities(representations: $representations) {
+                __typename
+                ... on User { id name _version }
+            }
+        }
+    "#;
+    let variables = r#"{ "representations": [
+        { "__typename": "User", "id": "1" },
+        { "__typename": "User", "id": "2" }
+    ] }"#;
+    let expected = expect![[r#"
+        {
+          "data": {
+            "_entities": [
+              {
+                "__typename": "User",
+                "id": "1",
+                "name": "User \"1\"",
+                "_version": 2
+              },
+              {
+                "__typename": "User",
+                "id": "2",
+                "name": "User \"2\"",
+                "_version": 2
+              }
+            ]
+          }
+        }"#]];
+    expected.assert_eq(&execute(document, variables));
+
+    // Nested objects from the root value also have meta-fields,
+    // which take precedence over data
+    let expected = expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "resolver error: reviews are not available",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 32
+                }
+              ],
+              "path": [
+                "me",
+                "reviews",
+                0,
+                "body"
+              ]
+            }
+          ],
+          "data": {
+            "me": {
+              "name": "Alice",
+              "_version": 2,
+              "reviews": [
+                {
+                  "body": null
+                }
+              ]
+            }
+          }
+        }"#]];
+    expected.assert_eq(&execute("{ me { name _version reviews { body } } }", "{}"));
+}
+
+#[test]
+fn operation_router_meta_fields() {
+    let meta_fields = federation();
+    let schema = Schema::parse(SCHEMA, "schema.graphql").unwrap();
+    let schema = meta_fields.extend_schema(&schema).unwrap();
+    let router = OperationRouter::new(schema).meta_fields(meta_fields);
+    let response = router.execute("{ me { id _version } }", None, &Default::default());
+    let expected = expect![[r#"{"data":{"me":{"id":"1","_version":2}}}"#]];
+    expected.assert_eq(&serde_json::to_string(&response).unwrap());
+}
+
+#[test]
+fn extend_schema_errors() {
+    let meta_fields =
+        MetaFieldRegistry::new().definitions("extend type Query { _service: _Service }");
+    let schema = Schema::parse(SCHEMA, "schema.graphql").unwrap();
+    let errors = meta_fields.extend_schema(&schema).unwrap_err().errors;
+    let expected = expect![[r#"
+        Error: cannot find type `_Service` in this document
+           ╭─[meta_fields.graphql:1:31]
+           │
+         1 │ extend type Query { _service: _Service }
+           │                               ────┬───  
+           │                                   ╰───── not found in this scope
+        ───╯
+    "#]];
+    expected.assert_eq(&errors.to_string());
+}
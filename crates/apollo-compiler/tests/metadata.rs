@@ -0,0 +1,129 @@
+use apollo_compiler::executable;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+directive @cached on QUERY
+directive @trace(label: String) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT | VARIABLE_DEFINITION
+
+type Query {
+  node(id: ID!): Node
+  search(filter: SearchFilter): [Result]
+  version: String
+}
+
+type Mutation {
+  rename(id: ID!, name: String!): User
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  friends(first: Int): [User]
+}
+
+type Post implements Node {
+  id: ID!
+  author: User
+}
+
+union Result = User | Post
+
+input SearchFilter {
+  text: String
+}
+"#;
+
+#[test]
+fn query_with_fragments() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        query Search($filter: SearchFilter, $first: Int = 3 @trace) @cached {
+          search(filter: $filter) {
+            ... on Post @trace(label: "post") { author { ...UserFields } }
+            ...UserFields
+          }
+          ... { version @include(if: false) }
+          ...RootFields
+        }
+
+        fragment UserFields on User {
+          name
+          friends(first: $first) { ...Friend @trace }
+        }
+
+        fragment Friend on User { id }
+
+        fragment RootFields on Query {
+          searchAgain: search { __typename }
+          node(id: 1) { id }
+        }
+        "#,
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(Some("Search")).unwrap();
+    let metadata = executable::metadata(operation, &document, &schema);
+    expect![[r#"
+        {
+          "operationType": "query",
+          "name": "Search",
+          "rootFields": [
+            "search",
+            "version",
+            "node"
+          ],
+          "referencedTypes": [
+            "Int",
+            "String",
+            "ID",
+            "Query",
+            "Node",
+            "User",
+            "Post",
+            "Result",
+            "SearchFilter"
+          ],
+          "directives": [
+            "cached",
+            "trace",
+            "include"
+          ],
+          "depth": 4,
+          "variables": [
+            {
+              "name": "filter",
+              "type": "SearchFilter"
+            },
+            {
+              "name": "first",
+              "type": "Int"
+            }
+          ]
+        }"#]]
+    .assert_eq(&serde_json::to_string_pretty(&metadata).unwrap());
+    let roundtrip: executable::OperationMetadata =
+        serde_json::from_str(&serde_json::to_string(&metadata).unwrap()).unwrap();
+    assert_eq!(roundtrip, metadata);
+}
+
+#[test]
+fn anonymous_mutation() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"mutation { rename(id: 1, name: "a") { name } }"#,
+        "mutation.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let metadata = executable::metadata(operation, &document, &schema);
+    expect![[r#"{"operationType":"mutation","name":null,"rootFields":["rename"],"referencedTypes":["String","Mutation","User"],"directives":[],"depth":2,"variables":[]}"#]].assert_eq(&serde_json::to_string(&metadata).unwrap());
+}
@@ -0,0 +1,120 @@
+use apollo_compiler::execution::request;
+use apollo_compiler::execution::request::RequestOptions;
+use apollo_compiler::execution::NumberPrecision;
+use apollo_compiler::Schema;
+use expect_test::expect;
+use expect_test::Expect;
+
+const SCHEMA: &str = r#"
+    scalar BigInt
+    scalar Decimal
+    scalar JSON
+
+    type Query {
+        bigInt: BigInt
+        bigInts: [BigInt]
+        decimal: Decimal
+        float: Float
+        json: JSON
+    }
+"#;
+
+fn check(document: &str, root_value: &str, precision: NumberPrecision, expected: Expect) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let root_value = serde_json::from_str(root_value).unwrap();
+    let options = RequestOptions {
+        number_precision: precision,
+        ..Default::default()
+    };
+    let response = request::execute(
+        &schema,
+        document,
+        None,
+        &Default::default(),
+        &root_value,
+        &options,
+    );
+    expected.assert_eq(&serde_json::to_string(&response).unwrap());
+}
+
+#[test]
+fn preserves_64_bit_integers() {
+    check(
+        "{ bigInt bigInts json }",
+        r#"{
+            "bigInt": 18446744073709551615,
+            "bigInts": [-9223372036854775808, 9007199254740993],
+            "json": { "id": 9223372036854775807 }
+        }"#,
+        NumberPrecision::Preserve,
+        expect![[
+            r#"{"data":{"bigInt":18446744073709551615,"bigInts":[-9223372036854775808,9007199254740993],"json":{"id":9223372036854775807}}}"#
+        ]],
+    );
+}
+
+#[test]
+fn float_accepts_integers() {
+    check(
+        "{ float }",
+        r#"{ "float": 42 }"#,
+        NumberPrecision::Preserve,
+        expect![[r#"{"data":{"float":42}}"#]],
+    );
+    check(
+        "{ float }",
+        r#"{ "float": "42" }"#,
+        NumberPrecision::Preserve,
+        expect![[
+            r#"{"errors":[{"message":"Resolver returned \"42\", expected Float","locations":[{"line":1,"column":3}],"path":["float"]}],"data":{"float":null}}"#
+        ]],
+    );
+}
+
+#[test]
+fn errors_on_precision_loss() {
+    check(
+        "{ bigInt float json decimal }",
+        r#"{
+            "bigInt": 18446744073709551615,
+            "float": 9007199254740993,
+            "json": { "list": [1, 2.5, 9007199254740993] },
+            "decimal": 0.1
+        }"#,
+        NumberPrecision::Error,
+        expect![[
+            r#"{"errors":[{"message":"Resolver returned 18446744073709551615 which loses precision as a 64-bit float for BigInt","locations":[{"line":1,"column":3}],"path":["bigInt"]},{"message":"Resolver returned 9007199254740993 which loses precision as Float","locations":[{"line":1,"column":10}],"path":["float"]},{"message":"Resolver returned {\"list\":[1,2.5,9007199254740993]} which loses precision as a 64-bit float for JSON","locations":[{"line":1,"column":16}],"path":["json"]}],"data":{"bigInt":null,"float":null,"json":null,"decimal":0.1}}"#
+        ]],
+    );
+    check(
+        "{ bigInt float json }",
+        r#"{
+            "bigInt": -9007199254740992,
+            "float": 1e300,
+            "json": { "list": [1, 2.5, 0.30000000000000004] }
+        }"#,
+        NumberPrecision::Error,
+        expect![[
+            r#"{"data":{"bigInt":-9007199254740992,"float":1e+300,"json":{"list":[1,2.5,0.30000000000000004]}}}"#
+        ]],
+    );
+}
+
+#[cfg(feature = "arbitrary-precision")]
+#[test]
+fn preserves_decimals() {
+    check(
+        "{ decimal }",
+        r#"{ "decimal": 3.14159265358979323846264338327950288 }"#,
+        NumberPrecision::Preserve,
+        expect![[r#"{"data":{"decimal":3.14159265358979323846264338327950288}}"#]],
+    );
+    check(
+        "{ decimal }",
+        r#"{ "decimal": 3.14159265358979323846264338327950288 }"#,
+        NumberPrecision::Error,
+        expect![[
+            r#"{"errors":[{"message":"Resolver returned 3.14159265358979323846264338327950288 which loses precision as a 64-bit float for Decimal","locations":[{"line":1,"column":3}],"path":["decimal"]}],"data":{"decimal":null}}"#
+        ]],
+    );
+}
@@ -0,0 +1,199 @@
+use apollo_compiler::execution::CostPolicy;
+use apollo_compiler::execution::OperationArchive;
+use apollo_compiler::execution::OperationArchiveError;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    type Query {
+        users(filter: UserFilter, first: Int = 10): [User]
+        node(id: ID!): User
+    }
+    type User {
+        id: ID!
+        name: String
+        friends: [User]
+    }
+    input UserFilter {
+        role: Role
+        names: [String!]
+        and: [UserFilter!]
+    }
+    enum Role { ADMIN, MEMBER }
+"#;
+
+fn build() -> (apollo_compiler::validation::Valid<Schema>, OperationArchive) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let a = r#"
+        query Users($filter: UserFilter, $first: Int) {
+            users(filter: $filter, first: $first) { ...UserFields friends { ...UserFields } }
+        }
+        query Node($id: ID!) { node(id: $id) { ...UserFields } }
+        fragment UserFields on User { id name }
+    "#;
+    // Same operation, with different formatting and definition order
+    let b = r#"
+        query Node($id: ID!) {
+            node(id: $id) { ...UserFields }
+        }
+        fragment UserFields on User { id name }
+    "#;
+    let documents = [
+        ExecutableDocument::parse_and_validate(&schema, a, "a.graphql").unwrap(),
+        ExecutableDocument::parse_and_validate(&schema, b, "b.graphql").unwrap(),
+    ];
+    let archive = OperationArchive::build(&schema, &documents, &CostPolicy::default());
+    (schema, archive)
+}
+
+#[test]
+fn builds_archive() {
+    let (schema, archive) = build();
+    assert_eq!(archive.version, 1);
+    assert_eq!(archive.schema_hash, schema.content_hash());
+    assert_eq!(archive.operations.len(), 2);
+
+    let users = &archive.operations[0];
+    assert_eq!(users.name.as_deref(), Some("Users"));
+    expect![[r#"query Users($filter: UserFilter, $first: Int) { users(filter: $filter, first: $first) { ...UserFields friends { ...UserFields } } } fragment UserFields on User { id name }"#]]
+    .assert_eq(&users.document);
+    assert_eq!(users.estimated_cost, 110);
+    assert_eq!(users.signature.len(), 64);
+    expect![[r##"
+        {
+          "$schema": "https://json-schema.org/draft/2020-12/schema",
+          "type": "object",
+          "properties": {
+            "filter": {
+              "anyOf": [
+                {
+                  "$ref": "#/$defs/UserFilter"
+                },
+                {
+                  "type": "null"
+                }
+              ]
+            },
+            "first": {
+              "anyOf": [
+                {
+                  "type": "integer",
+                  "minimum": -2147483648,
+                  "maximum": 2147483647
+                },
+                {
+                  "type": "null"
+                }
+              ]
+            }
+          },
+          "required": [],
+          "additionalProperties": false,
+          "$defs": {
+            "UserFilter": {
+              "type": "object",
+              "properties": {
+                "role": {
+                  "anyOf": [
+                    {
+                      "enum": [
+                        "ADMIN",
+                        "MEMBER"
+                      ]
+                    },
+                    {
+                      "type": "null"
+                    }
+                  ]
+                },
+                "names": {
+                  "anyOf": [
+                    {
+                      "anyOf": [
+                        {
+                          "type": "array",
+                          "items": {
+                            "type": "string"
+                          }
+                        },
+                        {
+                          "type": "string"
+                        }
+                      ]
+                    },
+                    {
+                      "type": "null"
+                    }
+                  ]
+                },
+                "and": {
+                  "anyOf": [
+                    {
+                      "anyOf": [
+                        {
+                          "type": "array",
+                          "items": {
+                            "$ref": "#/$defs/UserFilter"
+                          }
+                        },
+                        {
+                          "$ref": "#/$defs/UserFilter"
+                        }
+                      ]
+                    },
+                    {
+                      "type": "null"
+                    }
+                  ]
+                }
+              },
+              "required": [],
+              "additionalProperties": false
+            }
+          }
+        }"##]]
+    .assert_eq(&serde_json::to_string_pretty(&users.variables_schema).unwrap());
+
+    let node = &archive.operations[1];
+    assert_eq!(node.name.as_deref(), Some("Node"));
+    assert_eq!(
+        node.document,
+        "query Node($id: ID!) { node(id: $id) { ...UserFields } } fragment UserFields on User { id name }"
+    );
+    assert_eq!(
+        node.variables_schema["required"],
+        serde_json_bytes::json!(["id"])
+    );
+}
+
+#[test]
+fn round_trips_through_json() {
+    let (schema, archive) = build();
+    let json = archive.to_json();
+    let read = OperationArchive::from_json(&json).unwrap();
+    assert_eq!(read, archive);
+    assert!(read.matches_schema(&schema));
+
+    let node = read.get(&archive.operations[1].signature).unwrap();
+    let document = node.to_document(&schema).unwrap();
+    assert_eq!(document.serialize().no_indent().to_string(), node.document);
+    assert!(read.get("unknown").is_none());
+
+    let other = Schema::parse_and_validate("type Query { a: Int }", "other.graphql").unwrap();
+    assert!(!read.matches_schema(&other));
+}
+
+#[test]
+fn rejects_other_versions() {
+    let (_, mut archive) = build();
+    archive.version = 2;
+    assert_eq!(
+        OperationArchive::from_json(&archive.to_json()),
+        Err(OperationArchiveError::UnsupportedVersion(2))
+    );
+    assert!(matches!(
+        OperationArchive::from_json("{}"),
+        Err(OperationArchiveError::Json(_))
+    ));
+}
@@ -0,0 +1,201 @@
+use apollo_compiler::execution::OperationRouter;
+use apollo_compiler::execution::Request;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    scalar DateTime
+
+    type Query {
+        me: User
+        search(text: String!): [Result!]!
+    }
+
+    type Mutation {
+        rename(name: String!): User!
+    }
+
+    type User {
+        id: ID!
+        name: String
+        age: Int
+        role: Role
+        createdAt: DateTime
+    }
+
+    type Post {
+        title: String
+    }
+
+    union Result = Post | User
+
+    enum Role {
+        ADMIN
+        USER
+    }
+"#;
+
+fn router() -> OperationRouter {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    OperationRouter::new(schema).route("Rename", |variables| {
+        let mut user = serde_json_bytes::Map::new();
+        user.insert("id", "42".into());
+        user.insert("name", variables["name"].clone());
+        let mut root = serde_json_bytes::Map::new();
+        root.insert("rename", user.into());
+        root
+    })
+}
+
+fn execute(query: &str, operation_name: Option<&str>, variables: &str) -> String {
+    let variables = serde_json::from_str(variables).unwrap();
+    let response = router().execute(query, operation_name, &variables);
+    serde_json::to_string(&response).unwrap()
+}
+
+#[test]
+fn mock_data() {
+    let query = r#"
+        {
+            me { __typename id name age role createdAt }
+            search(text: "a") { ... on Post { title } ... on User { name } }
+        }
+    "#;
+    expect![[r#"{"data":{"me":{"__typename":"User","id":"1","name":"User.name","age":0,"role":"ADMIN","createdAt":"User.createdAt"},"search":[{"name":"User.name"}]}}"#]]
+        .assert_eq(&execute(query, None, "{}"));
+}
+
+#[test]
+fn handlers() {
+    let query = r#"
+        mutation Rename($name: String!) { rename(name: $name) { id name } }
+        mutation Other { rename(name: "B") { id name } }
+    "#;
+    expect![[r#"{"data":{"rename":{"id":"42","name":"A"}}}"#]].assert_eq(&execute(
+        query,
+        Some("Rename"),
+        r#"{"name": "A"}"#,
+    ));
+    expect![[r#"{"data":{"rename":{"id":"1","name":"User.name"}}}"#]].assert_eq(&execute(
+        query,
+        Some("Other"),
+        "{}",
+    ));
+}
+
+#[test]
+fn introspection() {
+    let query = r#"{ __type(name: "Role") { enumValues { name } } me { name } }"#;
+    expect![[r#"{"data":{"me":{"name":"User.name"},"__type":{"enumValues":[{"name":"ADMIN"},{"name":"USER"}]}}}"#]]
+        .assert_eq(&execute(query, None, "{}"));
+}
+
+#[test]
+fn request_errors() {
+    expect![[r#"{"errors":[{"message":"interface, union and object types must have a subselection set","locations":[{"line":1,"column":3}]},{"message":"type `User` does not have a field `email`","locations":[{"line":1,"column":8}]}]}"#]]
+        .assert_eq(&execute("{ me { email } }", None, "{}"));
+    expect![[r#"{"errors":[{"message":"operation `Nope` not found in the request"}]}"#]]
+        .assert_eq(&execute("query A { me { id } }", Some("Nope"), "{}"));
+    expect![[
+        r#"{"errors":[{"message":"operation name is required in a request with many operations"}]}"#
+    ]]
+    .assert_eq(&execute(
+        "query A { me { id } } query B { me { id } }",
+        None,
+        "{}",
+    ));
+    expect![[r#"{"errors":[{"message":"missing value for non-null variable 'name'","locations":[{"line":1,"column":17}]}]}"#]]
+        .assert_eq(&execute(
+            "mutation Rename($name: String!) { rename(name: $name) { id } }",
+            None,
+            "{}",
+        ));
+}
+
+#[test]
+fn batch() {
+    let requests: Vec<Request> = serde_json::from_str(
+        r#"[
+            { "query": "mutation Rename($name: String!) { rename(name: $name) { name } }", "variables": { "name": "A" } },
+            { "query": "{ me { email } }" },
+            { "query": "mutation Rename($name: String!) { rename(name: $name) { name } }", "variables": { "name": "B" } },
+            { "query": "{ me { id } __schema { queryType { name } } }", "variables": null },
+            { "query": "mutation Rename($name: String!) { rename(name: $name) { name } }" }
+        ]"#,
+    )
+    .unwrap();
+    let router = router();
+    let execute = |max_concurrency| {
+        let responses = router.execute_batch(&requests, max_concurrency);
+        serde_json::to_string_pretty(&responses).unwrap()
+    };
+    let responses = execute(0);
+    for max_concurrency in [1, 2, 16] {
+        assert_eq!(execute(max_concurrency), responses);
+    }
+    expect![[r#"
+        [
+          {
+            "data": {
+              "rename": {
+                "name": "A"
+              }
+            }
+          },
+          {
+            "errors": [
+              {
+                "message": "interface, union and object types must have a subselection set",
+                "locations": [
+                  {
+                    "line": 1,
+                    "column": 3
+                  }
+                ]
+              },
+              {
+                "message": "type `User` does not have a field `email`",
+                "locations": [
+                  {
+                    "line": 1,
+                    "column": 8
+                  }
+                ]
+              }
+            ]
+          },
+          {
+            "data": {
+              "rename": {
+                "name": "B"
+              }
+            }
+          },
+          {
+            "data": {
+              "me": {
+                "id": "1"
+              },
+              "__schema": {
+                "queryType": {
+                  "name": "Query"
+                }
+              }
+            }
+          },
+          {
+            "errors": [
+              {
+                "message": "missing value for non-null variable 'name'",
+                "locations": [
+                  {
+                    "line": 1,
+                    "column": 17
+                  }
+                ]
+              }
+            ]
+          }
+        ]"#]]
+    .assert_eq(&responses);
+}
@@ -1,4 +1,19 @@
+use apollo_compiler::ast;
+use apollo_compiler::parser::detach_sources;
+use apollo_compiler::parser::ExtensionContext;
+use apollo_compiler::parser::LineColumn;
 use apollo_compiler::parser::Parser;
+use apollo_compiler::parser::SyntaxExtension;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+use apollo_parser::cst;
+use apollo_parser::cst::CstNode;
+use apollo_parser::ExtensionParser;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 #[test]
 fn it_errors_when_selection_set_recursion_limit_exceeded() {
@@ -98,3 +113,344 @@ fn it_errors_with_multiple_limits() {
     );
     assert!(errors.contains("doc.graphql:6:25"), "{errors}");
 }
+
+#[test]
+fn it_attaches_leading_comments() {
+    let input = r#"# Unrelated file header
+
+# The root query type
+# with two lines
+type Query { # not a leading comment
+  # Fetch a user
+  user(
+    # Identifier
+    id: ID!
+  ): String
+}
+
+enum Color {
+  # The default
+  RED
+  GREEN
+}
+"#;
+    let document = ast::Document::parse(input, "schema.graphql").unwrap();
+    let query = document.definitions[0].as_object_type_definition().unwrap();
+    let comments = |list: &[Node<str>]| -> Vec<String> {
+        list.iter().map(|comment| comment.to_string()).collect()
+    };
+    assert_eq!(
+        comments(&query.leading_comments),
+        [" The root query type", " with two lines"]
+    );
+    let user = &query.fields[0];
+    assert_eq!(comments(&user.leading_comments), [" Fetch a user"]);
+    assert_eq!(
+        comments(&user.arguments[0].leading_comments),
+        [" Identifier"]
+    );
+    let color = document.definitions[1].as_enum_type_definition().unwrap();
+    assert_eq!(
+        comments(&color.values[0].leading_comments),
+        [" The default"]
+    );
+    assert!(color.values[1].leading_comments.is_empty());
+
+    let expected = r#"# The root query type
+# with two lines
+type Query {
+  # Fetch a user
+  user(
+    # Identifier
+    id: ID!,
+  ): String
+}
+
+enum Color {
+  # The default
+  RED
+  GREEN
+}
+"#;
+    assert_eq!(document.to_string(), expected);
+
+    let without_comments: String = expected
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let without_comments = ast::Document::parse(without_comments, "other.graphql").unwrap();
+    assert_eq!(document, without_comments);
+    let hash = |document: &ast::Document| {
+        let mut hasher = std::hash::DefaultHasher::new();
+        document.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&document), hash(&without_comments));
+
+    let schema = Schema::parse(input, "schema.graphql").unwrap();
+    let expected = r#"type Query {
+  user(id: ID!): String
+}
+
+enum Color {
+  RED
+  GREEN
+}
+"#;
+    assert_eq!(schema.to_string(), expected);
+}
+
+/// `queries { a: { x } b: { y } }` is sugar for `query a { x } query b { y }`
+struct NamedQueries;
+
+impl apollo_parser::SyntaxExtension for NamedQueries {
+    fn handles_keyword(&self, keyword: &str) -> bool {
+        keyword == "queries"
+    }
+
+    fn parse_definition(&self, _keyword: &str, p: &mut ExtensionParser<'_, '_>) {
+        p.block(|p| {
+            p.name();
+            p.bump(apollo_parser::SyntaxKind::COLON);
+            p.selection_set();
+        })
+    }
+}
+
+impl SyntaxExtension for NamedQueries {
+    fn convert_definition(
+        &self,
+        definition: &cst::CustomDefinition,
+        cx: &mut ExtensionContext<'_>,
+    ) -> Vec<ast::Definition> {
+        let names = definition.syntax().children().filter_map(cst::Name::cast);
+        let selection_sets = definition
+            .syntax()
+            .children()
+            .filter_map(cst::SelectionSet::cast);
+        let mut definitions = Vec::new();
+        // Skip the keyword
+        for (cst_name, selection_set) in names.skip(1).zip(selection_sets) {
+            let Some(name) = cx.name(&cst_name) else {
+                continue;
+            };
+            if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                cx.error(&cst_name, "query names must start with a lowercase letter");
+                continue;
+            }
+            let operation = ast::OperationDefinition {
+                operation_type: ast::OperationType::Query,
+                name: Some(name),
+                variables: Vec::new(),
+                directives: ast::DirectiveList::new(),
+                selection_set: cx.selection_set(&selection_set),
+                leading_comments: Vec::new(),
+            };
+            definitions.push(ast::Definition::OperationDefinition(Node::new_parsed(
+                operation,
+                cx.location(&selection_set),
+            )));
+        }
+        definitions
+    }
+}
+
+#[test]
+fn it_desugars_custom_definitions_with_a_syntax_extension() {
+    let input = r#"
+type Query { x: Int, y: Int }
+
+queries {
+  a: { x }
+  b: { y }
+}
+"#;
+    let err = Parser::new()
+        .parse_ast(input, "doc.graphql")
+        .unwrap_err()
+        .errors
+        .to_string();
+    assert!(err.contains("expected definition"), "{err}");
+
+    let mut parser = Parser::new().syntax_extension(Arc::new(NamedQueries));
+    let document = parser.parse_ast(input, "doc.graphql").unwrap();
+    let expected = r#"type Query {
+  x: Int
+  y: Int
+}
+
+query a {
+  x
+}
+
+query b {
+  y
+}
+"#;
+    assert_eq!(document.to_string(), expected);
+
+    let (schema, executable) = parser.parse_mixed_validate(input, "doc.graphql").unwrap();
+    assert_eq!(executable.operations.named.len(), 2);
+    assert!(schema.get_object("Query").is_some());
+
+    let err = parser
+        .parse_ast("queries { A: { x } b: { y } }", "doc.graphql")
+        .unwrap_err();
+    let errors = err.errors.to_string();
+    assert!(
+        errors.contains("query names must start with a lowercase letter"),
+        "{errors}"
+    );
+    assert!(errors.contains("doc.graphql:1:11"), "{errors}");
+    assert_eq!(err.partial.definitions.len(), 1);
+}
+
+#[test]
+fn it_loads_detached_source_text_on_demand() {
+    let text = "type Query {\n  a: Int\n  b: Missing\n}\n";
+    let mut schema = apollo_compiler::Schema::parse(text, "schema.graphql").unwrap();
+    let loads = Arc::new(AtomicUsize::new(0));
+    let counter = loads.clone();
+    detach_sources(&mut schema.sources, move |_| {
+        let counter = counter.clone();
+        Some(Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Some(text.to_owned())
+        }))
+    });
+    assert_eq!(loads.load(Ordering::SeqCst), 0);
+    let file = schema.sources.values().last().unwrap().clone();
+    assert!(!file.is_retained());
+    assert!(format!("{file:?}").contains("(detached)"));
+
+    let b = &schema.types["Query"].as_object().unwrap().fields["b"];
+    assert_eq!(
+        b.location().unwrap().line_column(&schema.sources),
+        Some(LineColumn { line: 3, column: 3 })
+    );
+    let errors = schema.validate().unwrap_err();
+    let json = errors.errors.to_graphql_errors();
+    assert_eq!(json[0].locations, [LineColumn { line: 3, column: 6 }]);
+    assert_eq!(loads.load(Ordering::SeqCst), 0);
+    let report = errors.to_string();
+    assert!(report.contains("b: Missing"), "{report}");
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+    assert_eq!(file.try_source_text().as_deref(), Some(text));
+
+    // Without source text, diagnostics are still reported
+    let mut schema = apollo_compiler::Schema::parse(text, "schema.graphql").unwrap();
+    detach_sources(&mut schema.sources, |_| Some(Arc::new(|| None)));
+    let file = schema.sources.values().last().unwrap();
+    assert_eq!(file.try_source_text(), None);
+    assert_eq!(file.source_text(), "");
+    let errors = schema.validate().unwrap_err();
+    assert_eq!(
+        errors.errors.to_graphql_errors()[0].locations,
+        [LineColumn { line: 3, column: 6 }]
+    );
+    assert!(errors
+        .to_string()
+        .contains("cannot find type `Missing` in this document"));
+}
+
+#[test]
+fn detached_line_columns_match_retained_ones() {
+    let text = "\"é\u{2028}\" type Query {\r\n  a: Int # ü\u{85}\r  b: [String]\n\n}\r\n\"\"\"\u{2029}\"\"\" scalar X";
+    let mut doc = ast::Document::parse(text, "schema.graphql").unwrap();
+    let retained = doc.sources.clone();
+    detach_sources(&mut doc.sources, |_| Some(Arc::new(|| None)));
+    let mut locations = Vec::new();
+    for definition in &doc.definitions {
+        locations.extend(definition.location());
+        locations.extend(definition.name().and_then(|name| name.location()));
+        if let ast::Definition::ObjectTypeDefinition(object) = definition {
+            for field in &object.fields {
+                locations.extend(field.location());
+                locations.extend(field.name.location());
+            }
+        }
+    }
+    assert_eq!(locations.len(), 8);
+    for location in locations {
+        assert_eq!(
+            location.line_column_range(&doc.sources),
+            location.line_column_range(&retained),
+        );
+    }
+}
+
+#[test]
+fn it_parses_mixed_documents_with_combined_diagnostics() {
+    let input = r#"
+        type Query { user: User }
+        type User { name: String, friend: Missing }
+        query { user { name age } }
+    "#;
+    let with_errors = Parser::new()
+        .parse_mixed_and_validate(input, "mixed.graphql")
+        .unwrap_err();
+    let messages: Vec<_> = with_errors
+        .errors
+        .iter()
+        .map(|diagnostic| diagnostic.error.to_string())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "cannot find type `Missing` in this document",
+            "type `User` does not have a field `age`",
+        ]
+    );
+    let partial = with_errors.partial;
+    assert!(partial.schema.types.contains_key("User"));
+    assert_eq!(partial.executable.operations.len(), 1);
+
+    let mixed = Parser::new()
+        .parse_mixed_and_validate(
+            "type Query { a: Int } query A { a } query B { a }",
+            "mixed.graphql",
+        )
+        .unwrap();
+    assert_eq!(mixed.executable.operations.named.len(), 2);
+}
+
+#[test]
+fn it_offsets_locations_of_embedded_source_text() {
+    let host = "const QUERY = gql`{ a: Missing }\n  type Query { b(x: ) }\n`;\n";
+    let start = host.find('`').unwrap() + 1;
+    let snippet = &host[start..host.rfind('`').unwrap()];
+    let mut parser = Parser::new().with_source_offset(1, start + 1, start);
+
+    let errors = parser.parse_ast(snippet, "query.js").unwrap_err().errors;
+    let json = errors.iter().next().unwrap().to_json();
+    let location = json.locations[0];
+    assert_eq!((location.line, location.column), (2, 21));
+    let report = errors.to_string();
+    assert!(report.contains("query.js:2:21"), "{report}");
+    assert!(report.contains(" 2 │   type Query { b(x: ) }"), "{report}");
+
+    let ast = parser
+        .parse_ast("type Query { a: Missing }", "query.js")
+        .unwrap();
+    let file = ast.sources.values().last().unwrap();
+    assert_eq!(
+        file.start(),
+        LineColumn {
+            line: 1,
+            column: start + 1
+        }
+    );
+    assert_eq!(file.start_offset(), start);
+    let errors = ast.to_schema_validate().unwrap_err().errors;
+    let error = errors.iter().next().unwrap();
+    let span = error.locations()[0];
+    assert_eq!(
+        span.line_column(&ast.sources),
+        Some(LineColumn {
+            line: 1,
+            column: start + 17
+        })
+    );
+    assert!(format!("{file:?}").contains("start_offset: 18"));
+}
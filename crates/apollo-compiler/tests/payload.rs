@@ -0,0 +1,119 @@
+use apollo_compiler::execution::HttpRequestRejection;
+use apollo_compiler::execution::PayloadOptions;
+use apollo_compiler::execution::PersistedQueryMode;
+use apollo_compiler::execution::RequestPayload;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+fn schema() -> Valid<Schema> {
+    Schema::parse_and_validate(
+        r#"
+        type Query { search(text: String!, limit: Int = 10): [String] }
+        type Mutation { post(text: String!): String }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap()
+}
+
+#[test]
+fn post_body_and_curl() {
+    let variables = serde_json::from_str(r#"{ "text": "it's here" }"#).unwrap();
+    let payload = RequestPayload::build(
+        &schema(),
+        "query Search($text: String!) { search(text: $text) }",
+        None,
+        &variables,
+        &PayloadOptions::default(),
+    )
+    .unwrap();
+    expect![[r#"{"query":"query Search($text: String!) { search(text: $text) }","operationName":"Search","variables":{"text":"it's here"}}"#]].assert_eq(&payload.to_json());
+    expect![[r#"curl --request POST 'http://localhost:4000/graphql' --header 'Content-Type: application/json' --data '{"query":"query Search($text: String!) { search(text: $text) }","operationName":"Search","variables":{"text":"it'\''s here"}}' --header 'Accept: application/graphql-response+json, application/json'"#]].assert_eq(
+        &payload
+            .to_curl("http://localhost:4000/graphql", false)
+            .unwrap(),
+    );
+}
+
+#[test]
+fn get_with_persisted_query_hash() {
+    let variables = serde_json::from_str(r#"{ "text": "a&b" }"#).unwrap();
+    let options = PayloadOptions {
+        persisted_query: PersistedQueryMode::HashOnly,
+        ..Default::default()
+    };
+    let payload = RequestPayload::build(
+        &schema(),
+        "query Search($text: String!) { search(text: $text) }",
+        Some("Search"),
+        &variables,
+        &options,
+    )
+    .unwrap();
+    assert!(payload.query.is_none());
+    // echo -n 'query Search($text: String!) { search(text: $text) }' | sha256sum
+    expect![[r#"{"operationName":"Search","variables":{"text":"a&b"},"extensions":{"persistedQuery":{"version":1,"sha256Hash":"d698c70cb937173a7e319ca9c917797e59c0ffc5b0075269b94f6709ff17f1a0"}}}"#]].assert_eq(&payload.to_json());
+    expect!["operationName=Search&variables=%7B%22text%22%3A%22a%26b%22%7D&extensions=%7B%22persistedQuery%22%3A%7B%22version%22%3A1%2C%22sha256Hash%22%3A%22d698c70cb937173a7e319ca9c917797e59c0ffc5b0075269b94f6709ff17f1a0%22%7D%7D"].assert_eq(&payload.to_query_string().unwrap());
+    expect!["curl 'http://localhost/graphql?app=cli&operationName=Search&variables=%7B%22text%22%3A%22a%26b%22%7D&extensions=%7B%22persistedQuery%22%3A%7B%22version%22%3A1%2C%22sha256Hash%22%3A%22d698c70cb937173a7e319ca9c917797e59c0ffc5b0075269b94f6709ff17f1a0%22%7D%7D' --header 'Accept: application/graphql-response+json, application/json'"].assert_eq(
+        &payload
+            .to_curl("http://localhost/graphql?app=cli", true)
+            .unwrap(),
+    );
+}
+
+#[test]
+fn mutation_over_get_is_rejected() {
+    let variables = serde_json::from_str(r#"{ "text": "hi" }"#).unwrap();
+    let payload = RequestPayload::build(
+        &schema(),
+        "mutation Post($text: String!) { post(text: $text) } query Q { search(text: \"\") }",
+        Some("Post"),
+        &variables,
+        &PayloadOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        payload.to_query_string(),
+        Err(HttpRequestRejection::MutationOverGet)
+    );
+    assert!(payload.to_curl("http://localhost/", false).is_ok());
+}
+
+#[test]
+fn invalid_requests() {
+    let schema = schema();
+    let errors = |document: &str, operation_name: Option<&str>, variables: &str| {
+        let variables = serde_json::from_str(variables).unwrap();
+        let errors = RequestPayload::build(
+            &schema,
+            document,
+            operation_name,
+            &variables,
+            &PayloadOptions::default(),
+        )
+        .unwrap_err();
+        errors
+            .iter()
+            .map(|err| err.message.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        errors("{ nope }", None, "{}"),
+        ["type `Query` does not have a field `nope`"]
+    );
+    assert_eq!(
+        errors("query A { search(text: \"\") }", Some("B"), "{}"),
+        ["operation `B` not found in the request"]
+    );
+    expect![[r#"
+        [
+            "Could not coerce variable text: 1 to type String",
+        ]
+    "#]]
+    .assert_debug_eq(&errors(
+        "query A($text: String!) { search(text: $text) }",
+        None,
+        r#"{ "text": 1 }"#,
+    ));
+}
@@ -0,0 +1,149 @@
+use apollo_compiler::executable::deduplicate_operations;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!): User
+  users(first: Int, filter: Filter): [User]
+}
+type User {
+  name: String
+  avatar(size: Int): String
+  friends(first: Int): [User]
+}
+input Filter { role: Role, tags: [String] }
+enum Role { ADMIN USER }
+"#;
+
+fn deduplicate(sources: &[&str]) -> (String, Vec<Vec<(usize, String)>>) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let documents: Vec<Valid<ExecutableDocument>> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, source)| {
+            ExecutableDocument::parse_and_validate(&schema, *source, format!("{i}.graphql"))
+                .unwrap()
+        })
+        .collect();
+    let persisted = deduplicate_operations(&schema, &documents);
+    let manifest = persisted
+        .iter()
+        .map(|entry| entry.document.to_string())
+        .collect::<Vec<_>>()
+        .join("---\n");
+    let occurrences = persisted
+        .iter()
+        .map(|entry| {
+            entry
+                .occurrences
+                .iter()
+                .map(|occurrence| {
+                    (
+                        occurrence.document_index,
+                        serde_json::to_string(&occurrence.variables).unwrap(),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    (manifest, occurrences)
+}
+
+#[test]
+fn deduplicates_operations_differing_in_literals() {
+    let (manifest, occurrences) = deduplicate(&[
+        r#"query Users { users(first: 10, filter: { role: ADMIN, tags: ["a"] }) { name } }"#,
+        r#"query Users { users(first: 5, filter: { role: USER, tags: [] }) { name } }"#,
+        r#"query Users { users(first: 5) { name } }"#,
+    ]);
+    expect![[r#"
+        query Users($first: Int, $filter: Filter) {
+          users(first: $first, filter: $filter) {
+            name
+          }
+        }
+        ---
+        query Users($first: Int) {
+          users(first: $first) {
+            name
+          }
+        }
+    "#]]
+    .assert_eq(&manifest);
+    assert_eq!(
+        occurrences,
+        [
+            vec![
+                (
+                    0,
+                    r#"{"first":10,"filter":{"role":"ADMIN","tags":["a"]}}"#.to_owned()
+                ),
+                (
+                    1,
+                    r#"{"first":5,"filter":{"role":"USER","tags":[]}}"#.to_owned()
+                ),
+            ],
+            vec![(2, r#"{"first":5}"#.to_owned())],
+        ]
+    );
+}
+
+#[test]
+fn variable_names_and_fragments() {
+    let (manifest, occurrences) = deduplicate(&[
+        r#"
+        query Friends($first: Int) {
+          users(first: $first) { ...F }
+          user(id: "1") { ...F avatar(size: 10) }
+        }
+        fragment F on User { avatar(size: 10) friends(first: 3) { name } }
+        "#,
+        r#"
+        query Friends($first: Int) {
+          users(first: $first) { ...F }
+          user(id: "2") { ...F avatar(size: 20) }
+        }
+        fragment F on User { avatar(size: 20) friends(first: 4) { name } }
+        "#,
+        // Directive arguments are kept
+        r#"{ user(id: "3") @include(if: true) { name } }"#,
+    ]);
+    expect![[r#"
+        query Friends($first: Int, $id: ID!, $size: Int, $first_1: Int) {
+          users(first: $first) {
+            ...F
+          }
+          user(id: $id) {
+            ...F
+            avatar(size: $size)
+          }
+        }
+
+        fragment F on User {
+          avatar(size: $size)
+          friends(first: $first_1) {
+            name
+          }
+        }
+        ---
+        query($id: ID!) {
+          user(id: $id) @include(if: true) {
+            name
+          }
+        }
+    "#]]
+    .assert_eq(&manifest);
+    assert_eq!(
+        occurrences,
+        [
+            vec![
+                (0, r#"{"id":"1","size":10,"first_1":3}"#.to_owned()),
+                (1, r#"{"id":"2","size":20,"first_1":4}"#.to_owned()),
+            ],
+            vec![(2, r#"{"id":"3"}"#.to_owned())],
+        ]
+    );
+}
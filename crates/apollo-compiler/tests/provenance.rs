@@ -0,0 +1,76 @@
+use apollo_compiler::execution::SchemaIntrospectionSplit;
+use apollo_compiler::parser::LineColumn;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn remap_errors_on_split_document() {
+    let schema = Schema::parse_and_validate(
+        "type Query { user(id: ID): User } type User { name: String age: Int }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let query = r#"
+        query Q {
+          __schema { description }
+          user(id: 1) {
+            name
+            age
+          }
+        }
+    "#;
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let SchemaIntrospectionSplit::Both {
+        filtered_document, ..
+    } = SchemaIntrospectionSplit::split(&schema, &document, operation).unwrap()
+    else {
+        panic!("expected both parts")
+    };
+    let (derived, provenance) = filtered_document
+        .serialize()
+        .no_indent()
+        .to_string_with_provenance("derived.graphql");
+    assert_eq!(derived, "query Q { user(id: 1) { name age } }");
+
+    // Another server has a schema where `User.age` was removed
+    let other_schema = Schema::parse_and_validate(
+        "type Query { user(id: ID): User } type User { name: String }",
+        "other.graphql",
+    )
+    .unwrap();
+    let errors =
+        ExecutableDocument::parse_and_validate(&other_schema, &derived, "received.graphql")
+            .unwrap_err()
+            .errors;
+    let mut errors = errors.to_graphql_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].locations,
+        [LineColumn {
+            line: 1,
+            column: 30
+        }]
+    );
+    provenance.remap_graphql_error(&mut errors[0], &document.sources);
+    assert_eq!(
+        errors[0].locations,
+        [LineColumn {
+            line: 6,
+            column: 13
+        }]
+    );
+
+    // Byte offsets work too
+    let user = provenance
+        .original_span_at(derived.find("user").unwrap())
+        .unwrap();
+    assert_eq!(
+        user.line_column(&document.sources),
+        Some(LineColumn {
+            line: 4,
+            column: 11
+        })
+    );
+    assert_eq!(provenance.original_span_at(derived.len()), None);
+}
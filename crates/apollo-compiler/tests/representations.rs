@@ -0,0 +1,102 @@
+use apollo_compiler::execution::parse_representations;
+use apollo_compiler::execution::RepresentationError;
+use apollo_compiler::execution::ScalarRegistry;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+    scalar UUID
+
+    type Query { products: [Product] }
+
+    type Product @key(fields: "upc") @key(fields: "sku variation { id tags }") {
+        upc: UUID!
+        sku: String!
+        variation: Variation
+        price: Int
+    }
+
+    type Variation {
+        id: ID!
+        tags: [String!]
+    }
+
+    type Review @key(fields: "unknown") {
+        body: String
+    }
+
+    type Category {
+        name: String
+    }
+"#;
+
+#[test]
+fn representations() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let scalars = ScalarRegistry::well_known_for(&schema);
+    let representations: Vec<_> = serde_json::from_str(
+        r#"[
+            { "__typename": "Product", "upc": "67e55044-10b1-426f-9247-bb680e5fe0c8", "price": 5 },
+            {
+                "__typename": "Product",
+                "sku": "chair",
+                "variation": { "id": 7, "tags": ["red"], "name": "Red chair" }
+            },
+            "Product",
+            { "upc": "67e55044-10b1-426f-9247-bb680e5fe0c8" },
+            { "__typename": "Unicorn" },
+            { "__typename": "Category", "name": "furniture" },
+            { "__typename": "Review", "unknown": 1 },
+            { "__typename": "Product", "sku": "chair", "variation": { "tags": null } },
+            { "__typename": "Product", "upc": "not a uuid" },
+            { "__typename": "Product", "sku": "chair", "variation": { "id": "1", "tags": ["a", null] } }
+        ]"#,
+    )
+    .unwrap();
+    let results = parse_representations(&schema, &scalars, &representations);
+    let results = results
+        .into_iter()
+        .map(|result| match result {
+            Ok(entity) => format!(
+                "{} key {}: {} in {}",
+                entity.type_name,
+                entity.key_index,
+                serde_json::to_string(&entity.key_values).unwrap(),
+                serde_json::to_string(&entity.representation).unwrap(),
+            ),
+            Err(err) => format!("{} (index {})", err, err.index()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    expect![[r#"
+        Product key 0: {"upc":"67e55044-10b1-426f-9247-bb680e5fe0c8"} in {"__typename":"Product","upc":"67e55044-10b1-426f-9247-bb680e5fe0c8","price":5}
+        Product key 1: {"sku":"chair","variation":{"id":7,"tags":["red"]}} in {"__typename":"Product","sku":"chair","variation":{"id":7,"tags":["red"],"name":"Red chair"}}
+        representation 2 is not an object (index 2)
+        representation 3 does not have a string `__typename` (index 3)
+        representation 4 has undefined type `Unicorn` (index 4)
+        representation 5 has type `Category` which has no `@key` directive (index 5)
+        representation 6 has type `Review` with invalid `@key(fields: "unknown")`: type `Review` does not have a field `unknown` (index 6)
+        representation 7 of type `Product` does not match any `@key`, missing `upc` (index 7)
+        representation 8 has an invalid value at `upc`: Could not coerce field Product.upc to type UUID: expected a UUID string, got "not a uuid" (index 8)
+        representation 9 has an invalid value at `variation.tags[1]`: null value for field Variation.tags of non-null type String! (index 9)"#]].assert_eq(&results);
+}
+
+#[test]
+fn error_variants() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let representations = [serde_json::json!({ "__typename": "Product" }).into()];
+    let results = parse_representations(&schema, &ScalarRegistry::new(), &representations);
+    let Err(RepresentationError::NoMatchingKey {
+        index,
+        type_name,
+        missing,
+    }) = &results[0]
+    else {
+        panic!("expected NoMatchingKey: {results:?}")
+    };
+    assert_eq!(*index, 0);
+    assert_eq!(type_name, "Product");
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].to_string(), "upc");
+}
@@ -0,0 +1,302 @@
+use apollo_compiler::execution::request;
+use apollo_compiler::execution::request::RequestOptions;
+use apollo_compiler::execution::CostPolicy;
+use apollo_compiler::execution::ResponseKeyPolicy;
+use apollo_compiler::execution::ScalarRegistry;
+use apollo_compiler::parser::Parser;
+use apollo_compiler::Schema;
+use expect_test::expect;
+use expect_test::Expect;
+
+const SCHEMA: &str = r#"
+    scalar UUID
+
+    type Query {
+        user(id: UUID!): User
+        users: [User]
+    }
+
+    type User {
+        id: UUID!
+        name: String
+    }
+"#;
+
+const ROOT_VALUE: &str = r#"{
+    "user": { "id": "67e55044-10b1-426f-9247-bb680e5fe0c8", "name": "Alice" },
+    "users": [{ "name": "Alice" }, { "name": "Bob" }]
+}"#;
+
+fn check(
+    document: &str,
+    operation_name: Option<&str>,
+    variables: &str,
+    options: &RequestOptions,
+    expected: Expect,
+) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let variables = serde_json::from_str(variables).unwrap();
+    let root_value = serde_json::from_str(ROOT_VALUE).unwrap();
+    let response = request::execute(
+        &schema,
+        document,
+        operation_name,
+        &variables,
+        &root_value,
+        options,
+    );
+    expected.assert_eq(&serde_json::to_string_pretty(&response).unwrap());
+}
+
+#[test]
+fn executes_with_introspection() {
+    let document = r#"
+        query A { users { name } }
+        query B($id: UUID!) { user(id: $id) { name } __schema { queryType { name } } }
+    "#;
+    check(
+        document,
+        Some("B"),
+        r#"{ "id": "67e55044-10b1-426f-9247-bb680e5fe0c8" }"#,
+        &RequestOptions::default(),
+        expect![[r#"
+            {
+              "data": {
+                "user": {
+                  "name": "Alice"
+                },
+                "__schema": {
+                  "queryType": {
+                    "name": "Query"
+                  }
+                }
+              }
+            }"#]],
+    );
+}
+
+#[test]
+fn request_errors() {
+    let options = RequestOptions::default();
+    check(
+        "{ users { name ",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "syntax error: expected R_CURLY, got EOF",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 16
+                    }
+                  ]
+                },
+                {
+                  "message": "syntax error: expected R_CURLY, got EOF",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 16
+                    }
+                  ]
+                }
+              ]
+            }"#]],
+    );
+    check(
+        "{ users { email } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "interface, union and object types must have a subselection set",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 3
+                    }
+                  ]
+                },
+                {
+                  "message": "type `User` does not have a field `email`",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 11
+                    }
+                  ]
+                }
+              ]
+            }"#]],
+    );
+    check(
+        "query A { users { name } } query B { users { id } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "operation name is required in a request with many operations"
+                }
+              ]
+            }"#]],
+    );
+    check(
+        "query A { users { name } }",
+        Some("C"),
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "operation `C` not found in the request"
+                }
+              ]
+            }"#]],
+    );
+    check(
+        "query($id: UUID!) { user(id: $id) { name } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "missing value for non-null variable 'id'",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 7
+                    }
+                  ]
+                }
+              ]
+            }"#]],
+    );
+}
+
+#[test]
+fn applies_options() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let options = RequestOptions {
+        parser: Parser::new().token_limit(15),
+        ..Default::default()
+    };
+    check(
+        "{ users { name } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "data": {
+                "users": [
+                  {
+                    "name": "Alice"
+                  },
+                  {
+                    "name": "Bob"
+                  }
+                ]
+              }
+            }"#]],
+    );
+    check(
+        "{ users { name id } users2: users { name } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "token limit reached, aborting lexing",
+                  "locations": [
+                    {
+                      "line": 1,
+                      "column": 29
+                    }
+                  ]
+                }
+              ]
+            }"#]],
+    );
+
+    let options = RequestOptions {
+        scalars: ScalarRegistry::well_known_for(&schema),
+        ..Default::default()
+    };
+    check(
+        "query($id: UUID!) { user(id: $id) { name } }",
+        None,
+        r#"{ "id": "not a UUID" }"#,
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "Could not coerce variable id to type UUID: expected a UUID string, got \"not a UUID\""
+                }
+              ]
+            }"#]],
+    );
+
+    let options = RequestOptions {
+        response_keys: Some(ResponseKeyPolicy {
+            max_response_keys: 2,
+            ..Default::default()
+        }),
+        cost: Some(CostPolicy::default()),
+        ..Default::default()
+    };
+    check(
+        "{ users { name } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "data": {
+                "users": [
+                  {
+                    "name": "Alice"
+                  },
+                  {
+                    "name": "Bob"
+                  }
+                ]
+              },
+              "extensions": {
+                "cost": {
+                  "estimated": 10,
+                  "actual": 2
+                }
+              }
+            }"#]],
+    );
+    check(
+        "{ users { id name } }",
+        None,
+        "{}",
+        &options,
+        expect![[r#"
+            {
+              "errors": [
+                {
+                  "message": "Operation selects more than 2 response keys"
+                }
+              ]
+            }"#]],
+    );
+}
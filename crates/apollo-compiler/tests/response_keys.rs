@@ -0,0 +1,90 @@
+use apollo_compiler::execution::ResponseKeyLimitError;
+use apollo_compiler::execution::ResponseKeyMetrics;
+use apollo_compiler::execution::ResponseKeyPolicy;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+type Query { user(id: ID): User, node: Node }
+interface Node { id: ID }
+type User implements Node { id: ID, name: String, friends: [User] }
+"#;
+
+fn document(query: &str) -> Valid<ExecutableDocument> {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap()
+}
+
+#[test]
+fn metrics_merge_fields_and_fragments() {
+    let document = document(
+        r#"
+        {
+          user(id: 1) { id ...F friends { id name } }
+          other: user(id: 2) { ...F }
+          node { id ... on User { name } }
+        }
+        fragment F on User { id name friends { id } }
+        "#,
+    );
+    let operation = document.operations.get(None).unwrap();
+    let metrics = ResponseKeyPolicy::default()
+        .check(&document, operation)
+        .unwrap();
+    assert_eq!(
+        metrics,
+        ResponseKeyMetrics {
+            // user, other, node
+            // + user { id name friends { id name } }
+            // + other { id name friends { id } }
+            // + node { id name }
+            response_keys: 3 + 5 + 4 + 2,
+            aliases: 1,
+            max_keys_per_field: 2,
+        }
+    );
+}
+
+#[test]
+fn too_many_aliases() {
+    let document = document("{ user { a: id b: id c: id name n: name } }");
+    let operation = document.operations.get(None).unwrap();
+    let policy = ResponseKeyPolicy {
+        max_keys_per_field: 2,
+        ..Default::default()
+    };
+    let error = policy.check(&document, operation).unwrap_err();
+    assert!(matches!(
+        &error,
+        ResponseKeyLimitError::TooManyAliases { field_name, max: 2, .. } if field_name == "id"
+    ));
+    assert_eq!(
+        serde_json::to_string(&error.into_response(&document.sources)).unwrap(),
+        r#"{"errors":[{"message":"Field `id` is selected with more than 2 different aliases in the same selection set","locations":[{"line":1,"column":22}]}]}"#
+    );
+}
+
+#[test]
+fn too_many_response_keys_stops_counting() {
+    // Each level doubles the number of response keys
+    let mut query = String::from("{ user { ...F0 } }\n");
+    for i in 0..12 {
+        query.push_str(&format!(
+            "fragment F{i} on User {{ a: friends {{ ...F{next} }} b: friends {{ ...F{next} }} }}\n",
+            next = i + 1
+        ));
+    }
+    query.push_str("fragment F12 on User { id }");
+    let document = document(&query);
+    let operation = document.operations.get(None).unwrap();
+    let policy = ResponseKeyPolicy::default();
+    let metrics = policy.analyze(&document, operation);
+    assert!(metrics.response_keys > 1000);
+    assert!(metrics.response_keys < 2000);
+    let error = policy.check(&document, operation).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Operation selects more than 1000 response keys"
+    );
+}
@@ -0,0 +1,67 @@
+use apollo_compiler::execution::GraphQLError;
+use apollo_compiler::execution::ResponseDataPathElement;
+use apollo_compiler::execution::ResponsePath;
+use apollo_compiler::name;
+
+#[test]
+fn parse_and_print() {
+    for input in [
+        "", "a", "a.b[3].c", "[0]", "[0][1].a", "a[10][2]", "_x.y2[0]",
+    ] {
+        let path: ResponsePath = input.parse().unwrap();
+        assert_eq!(path.to_string(), input);
+    }
+    let path: ResponsePath = "[1].a".parse().unwrap();
+    assert_eq!(
+        *path,
+        [
+            ResponseDataPathElement::ListIndex(1),
+            ResponseDataPathElement::Field(name!("a")),
+        ]
+    );
+
+    for input in [
+        ".a", "a.", "a..b", "a[", "a[]", "a[-1]", "a[+1]", "a[x]", "a]", "a b", "a[1]b", "1a",
+    ] {
+        assert!(
+            input.parse::<ResponsePath>().is_err(),
+            "`{input}` should be rejected"
+        );
+    }
+}
+
+#[test]
+fn comparison() {
+    let parse = |input: &str| input.parse::<ResponsePath>().unwrap();
+    assert_eq!(parse("a[0]"), parse("a[0]"));
+    assert_ne!(parse("a[0]"), parse("a[1]"));
+    assert!(parse("a[0]") < parse("a[1]"));
+    assert!(parse("a") < parse("a.b"));
+    assert!(parse("a.b") < parse("a.c"));
+    assert!(parse("a").is_prefix_of(&parse("a[2].b")));
+    assert!(parse("").is_prefix_of(&parse("a")));
+    assert!(!parse("a.b").is_prefix_of(&parse("a")));
+    assert!(!parse("a.b").is_prefix_of(&parse("a.bc")));
+}
+
+#[test]
+fn json() {
+    let path: ResponsePath = "a.b[3].c".parse().unwrap();
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(json, r#"["a","b",3,"c"]"#);
+    assert_eq!(serde_json::from_str::<ResponsePath>(&json).unwrap(), path);
+
+    let mut error: GraphQLError =
+        serde_json::from_str(r#"{"message": "oops", "path": ["a", 0]}"#).unwrap();
+    assert_eq!(error.path.to_string(), "a[0]");
+    error.path.push(ResponseDataPathElement::Field(name!("b")));
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        r#"{"message":"oops","path":["a",0,"b"]}"#
+    );
+    error.path.clear();
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        r#"{"message":"oops"}"#
+    );
+}
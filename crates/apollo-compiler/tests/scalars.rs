@@ -0,0 +1,175 @@
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::coerce_variable_values_with_scalars;
+use apollo_compiler::execution::InputCoercionError;
+use apollo_compiler::execution::JsonMap;
+use apollo_compiler::execution::JsonValue;
+use apollo_compiler::execution::ScalarRegistry;
+use apollo_compiler::execution::ScalarSpec;
+use apollo_compiler::execution::WellKnownScalar;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+  scalar DateTime
+  scalar UUID
+  scalar URL
+  scalar BigInt
+  scalar JSON
+  scalar Instant @specifiedBy(url: "https://datatracker.ietf.org/doc/html/rfc3339")
+  scalar Other
+
+  input Filter { ids: [UUID!] }
+
+  type Query {
+    f(
+      dateTime: DateTime
+      uuid: UUID
+      url: URL
+      bigInt: BigInt
+      json: JSON
+      instant: Instant
+      other: Other
+      filter: Filter
+    ): Int
+  }
+"#;
+
+const QUERY: &str = r#"
+  query(
+    $dateTime: DateTime
+    $uuid: UUID
+    $url: URL
+    $bigInt: BigInt
+    $json: JSON
+    $instant: Instant
+    $other: Other
+    $filter: Filter
+  ) {
+    f(
+      dateTime: $dateTime
+      uuid: $uuid
+      url: $url
+      bigInt: $bigInt
+      json: $json
+      instant: $instant
+      other: $other
+      filter: $filter
+    )
+  }
+"#;
+
+fn coerce(scalars: &ScalarRegistry, variables: JsonValue) -> Result<JsonMap, String> {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let JsonValue::Object(variables) = variables else {
+        panic!("expected an object")
+    };
+    match coerce_variable_values_with_scalars(&schema, operation, &variables, scalars) {
+        Ok(coerced) => Ok(coerced.into_inner()),
+        Err(InputCoercionError::ValueError { message, .. }) => Err(message),
+        Err(InputCoercionError::SuspectedValidationBug(bug)) => panic!("{}", bug.message),
+    }
+}
+
+fn well_known() -> ScalarRegistry {
+    let schema = Schema::parse(SCHEMA, "schema.graphql").unwrap();
+    ScalarRegistry::well_known_for(&schema)
+}
+
+#[test]
+fn well_known_for_schema() {
+    let registry = well_known();
+    for name in ["DateTime", "UUID", "URL", "BigInt", "JSON", "Instant"] {
+        assert!(registry.get(name).is_some(), "{name}");
+    }
+    assert!(registry.get("Other").is_none());
+    assert!(registry.get("String").is_none());
+}
+
+#[test]
+fn valid_values() {
+    let variables = serde_json_bytes::json!({
+        "dateTime": "2024-02-29T13:45:00.5+01:00",
+        "uuid": "123e4567-e89b-12d3-a456-426614174000",
+        "url": "https://example.com/a?b",
+        "bigInt": "-123456789012345678901234567890",
+        "json": { "any": ["thing", 1, null] },
+        "instant": "1985-04-12T23:20:50Z",
+        "other": [true],
+        "filter": { "ids": ["00000000-0000-0000-0000-000000000000"] },
+    });
+    let coerced = coerce(&well_known(), variables.clone()).unwrap();
+    assert_eq!(JsonValue::Object(coerced), variables);
+    let coerced = coerce(&well_known(), serde_json_bytes::json!({ "bigInt": 12 })).unwrap();
+    assert_eq!(coerced["bigInt"], 12);
+}
+
+#[test]
+fn invalid_values() {
+    let registry = well_known();
+    let cases = [
+        ("dateTime", serde_json_bytes::json!("2023-02-29T00:00:00Z")),
+        ("dateTime", serde_json_bytes::json!("2024-01-01 00:00:00Z")),
+        ("dateTime", serde_json_bytes::json!("2024-01-01T00:00:00")),
+        ("dateTime", serde_json_bytes::json!("2024-01-01T24:00:00Z")),
+        ("dateTime", serde_json_bytes::json!(1700000000)),
+        (
+            "uuid",
+            serde_json_bytes::json!("123e4567e89b12d3a456426614174000"),
+        ),
+        ("url", serde_json_bytes::json!("/relative/path")),
+        ("url", serde_json_bytes::json!("https://exa mple.com")),
+        ("bigInt", serde_json_bytes::json!(1.5)),
+        ("bigInt", serde_json_bytes::json!("12e3")),
+        ("instant", serde_json_bytes::json!("now")),
+    ];
+    for (name, value) in cases {
+        let variables = serde_json_bytes::json!({ name: value.clone() });
+        assert!(
+            coerce(&registry, variables).is_err(),
+            "{name}: {value} should be rejected"
+        );
+    }
+
+    let variables = serde_json_bytes::json!({ "filter": { "ids": ["nope"] } });
+    expect_test::expect![[r#"
+        Could not coerce input field Filter.ids to type UUID: expected a UUID string, got "nope"
+    "#]]
+    .assert_eq(&format!("{}\n", coerce(&registry, variables).unwrap_err()));
+}
+
+#[test]
+fn without_registry_custom_scalars_are_accepted() {
+    let variables = serde_json_bytes::json!({ "uuid": "nope", "dateTime": 42 });
+    assert!(coerce(&ScalarRegistry::new(), variables.clone()).is_ok());
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let JsonValue::Object(variables) = variables else {
+        unreachable!()
+    };
+    assert!(coerce_variable_values(&schema, operation, &variables).is_ok());
+}
+
+#[test]
+fn custom_spec() {
+    struct Lowercase;
+
+    impl ScalarSpec for Lowercase {
+        fn coerce_input(&self, value: &JsonValue) -> Result<JsonValue, String> {
+            let s = value.as_str().ok_or("expected a string")?;
+            Ok(s.to_lowercase().into())
+        }
+    }
+
+    let mut registry = ScalarRegistry::new();
+    registry
+        .register("Other", Lowercase)
+        .register("UUID", WellKnownScalar::Uuid);
+    let variables = serde_json_bytes::json!({ "other": "HeLLo" });
+    assert_eq!(coerce(&registry, variables).unwrap()["other"], "hello");
+    let variables = serde_json_bytes::json!({ "other": 1 });
+    assert!(coerce(&registry, variables).is_err());
+}
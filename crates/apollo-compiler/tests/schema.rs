@@ -226,3 +226,849 @@ fn test_default_root_op_name_ignored_with_explicit_schema_def() {
     let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
     assert!(schema.schema_definition.mutation.is_none())
 }
+
+#[test]
+fn test_propagate_interface_fields() {
+    let input = r#"
+    type Query { node: Node }
+    interface Node {
+        "Unique identifier"
+        id: ID!
+    }
+    interface Named implements Node {
+        id: ID!
+        name(locale: String = "en"): String
+    }
+    type User implements Named & Node {
+        name(locale: String = "en"): String
+        email: String
+    }
+    type Robot implements Node & Named
+    "#;
+    let mut schema = Schema::parse(input, "schema.graphql").unwrap();
+    let report = apollo_compiler::schema::propagate_interface_fields(&mut schema);
+    let added: Vec<_> = report
+        .added
+        .iter()
+        .map(|added| {
+            format!(
+                "{}.{} from {}",
+                added.type_name, added.field_name, added.interface_name
+            )
+        })
+        .collect();
+    assert_eq!(
+        added,
+        [
+            "User.id from Named",
+            "Robot.id from Node",
+            "Robot.name from Named",
+        ]
+    );
+    let user = schema.get_object("User").unwrap();
+    assert_eq!(
+        user.fields
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>(),
+        ["name", "email", "id"]
+    );
+    let robot = schema.get_object("Robot").unwrap();
+    assert_eq!(
+        robot.fields["id"].description.as_deref(),
+        Some("Unique identifier")
+    );
+    assert_eq!(robot.fields["name"].arguments.len(), 1);
+    schema.validate().unwrap();
+}
+
+#[test]
+fn test_extract_directive() {
+    use apollo_compiler::coord;
+    use apollo_compiler::schema::DirectiveArguments;
+    use apollo_compiler::schema::DirectiveExtractionError;
+    use apollo_compiler::schema::FromDirective;
+    use apollo_compiler::Name;
+
+    #[derive(Debug, PartialEq)]
+    struct Auth {
+        requires: Name,
+        scopes: Vec<String>,
+    }
+
+    impl FromDirective for Auth {
+        const NAME: &'static str = "auth";
+
+        fn from_directive(args: &DirectiveArguments<'_>) -> Result<Self, DirectiveExtractionError> {
+            Ok(Self {
+                requires: args.get("requires")?,
+                scopes: args.get::<Option<_>>("scopes")?.unwrap_or_default(),
+            })
+        }
+    }
+
+    let input = r#"
+    directive @auth(requires: Role = USER, scopes: [String!]) repeatable on OBJECT | FIELD_DEFINITION
+    enum Role { USER ADMIN }
+    type Query @auth(scopes: "read") {
+        me: String
+        users: [String] @auth(requires: ADMIN) @auth(scopes: ["a", "b"])
+    }
+    "#;
+    let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+
+    let query = schema
+        .extract_directive::<Auth>(&coord!(Query).into())
+        .unwrap();
+    assert_eq!(
+        query,
+        Some(Auth {
+            requires: Name::new("USER").unwrap(),
+            scopes: vec!["read".to_owned()],
+        })
+    );
+    let me = schema
+        .extract_directive::<Auth>(&coord!(Query.me).into())
+        .unwrap();
+    assert_eq!(me, None);
+
+    let users = schema.extract_directive::<Auth>(&coord!(Query.users).into());
+    assert!(matches!(
+        users,
+        Err(DirectiveExtractionError::MultipleApplications { .. })
+    ));
+    let users: Vec<_> = schema
+        .extract_directives::<Auth>(&coord!(Query.users).into())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].requires, "ADMIN");
+    assert_eq!(users[1].scopes, ["a", "b"]);
+
+    let missing = schema.extract_directive::<Auth>(&coord!(Query.nope).into());
+    assert_eq!(
+        missing.unwrap_err().to_string(),
+        "schema coordinate `Query.nope` does not exist"
+    );
+}
+
+#[test]
+fn test_coordinates() {
+    let schema = r#"
+        type Query { search(text: String!, first: Int = 10): [Result] node: Node }
+        interface Node { id: ID! }
+        type Post implements Node { id: ID! title: String @deprecated }
+        union Result = Post
+        input Filter { tag: String }
+        enum Order { ASC DESC }
+        scalar Date
+        directive @auth(role: String!, scope: [String]) on FIELD_DEFINITION
+        extend type Post { date(format: String): Date }
+    "#;
+    let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+    let coordinates: Vec<_> = schema
+        .coordinates()
+        .map(|(coordinate, _)| coordinate.to_string())
+        .collect();
+    expect_test::expect![[r#"
+        Query
+        Query.search
+        Query.search(text:)
+        Query.search(first:)
+        Query.node
+        Node
+        Node.id
+        Post
+        Post.id
+        Post.title
+        Post.date
+        Post.date(format:)
+        Result
+        Filter
+        Filter.tag
+        Order
+        Order.ASC
+        Order.DESC
+        Date
+        @auth
+        @auth(role:)
+        @auth(scope:)
+    "#]]
+    .assert_eq(&(coordinates.join("\n") + "\n"));
+
+    for (coordinate, definition) in schema.coordinates() {
+        assert_eq!(coordinate.lookup(&schema).unwrap(), definition);
+        assert_eq!(coordinate.to_string().parse().ok(), Some(coordinate));
+    }
+}
+
+#[test]
+fn test_origins_round_trip() {
+    let schema = Schema::builder()
+        .parse(
+            r#"
+directive @key(fields: String!) on OBJECT
+type Query { users: [User] }
+type User @key(fields: "id") { id: ID! }
+"#,
+            "users.graphql",
+        )
+        .parse(
+            "extend type User {\n  reviews: [String]\n}\nextend schema @key(fields: \"\")",
+            "reviews.graphql",
+        )
+        .build()
+        .unwrap();
+    expect_test::expect![[r#"
+        schema {
+          query: Query
+        }
+
+        # from: reviews.graphql:4:1
+        extend schema @key(fields: "")
+
+        # from: users.graphql:2:1
+        directive @key(fields: String!) on OBJECT
+
+        # from: users.graphql:3:1
+        type Query {
+          users: [User]
+        }
+
+        # from: users.graphql:4:1
+        type User @key(fields: "id") {
+          id: ID!
+        }
+
+        # from: reviews.graphql:1:1
+        extend type User {
+          reviews: [String]
+        }
+    "#]]
+    .assert_eq(&schema.serialize().origin_comments().to_string());
+
+    let (text, origins) = schema.serialize().to_string_with_origins();
+    let json = serde_json::to_string(&origins).unwrap();
+    let origins: apollo_compiler::schema::SchemaOrigins = serde_json::from_str(&json).unwrap();
+    let merged = Schema::parse(&text, "merged.graphql").unwrap();
+
+    let user = merged.types["User"].as_object().unwrap();
+    let id = origins.find(&merged, user.fields["id"].location().unwrap());
+    assert_eq!(id.unwrap().definition, "type User");
+    let reviews = &user.fields["reviews"];
+    let extension = reviews.origin.extension_id().unwrap();
+    let origin = origins
+        .find(&merged, extension.location().unwrap())
+        .unwrap();
+    assert_eq!(origin.definition, "extend type User");
+    assert_eq!(
+        origin.path.as_ref().unwrap().to_str(),
+        Some("reviews.graphql")
+    );
+    assert_eq!(
+        origin,
+        origins.find(&merged, reviews.location().unwrap()).unwrap()
+    );
+    let key = &merged.schema_definition.directives[0];
+    let origin = origins.find(&merged, key.location().unwrap()).unwrap();
+    assert_eq!(origin.definition, "extend schema");
+
+    // A sidecar for another schema does not match
+    let other = Schema::parse("type Query { a: Int }", "other.graphql").unwrap();
+    let (_, other_origins) = other.serialize().to_string_with_origins();
+    assert_eq!(
+        other_origins.find(&merged, user.fields["id"].location().unwrap()),
+        None
+    );
+}
+
+#[test]
+fn test_directive_list_lookups_and_dedup() {
+    let sdl = r#"
+directive @shareable on OBJECT | FIELD_DEFINITION
+directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+type Query { t: T }
+type T @tag(name: "a") @tag(name: "b") @tag(name: "c") @tag(name: "d")
+       @tag(name: "e") @tag(name: "f") @tag(name: "g") @tag(name: "a") @shareable {
+  f: Int @shareable @shareable @tag(name: "a") @tag(name: "a")
+}
+extend type T @shareable
+"#;
+    let errors = Schema::parse_and_validate(sdl, "schema.graphql").unwrap_err();
+    assert_eq!(errors.errors.len(), 2);
+
+    let schema = Schema::builder()
+        .dedup_non_repeatable_directives()
+        .parse(sdl, "schema.graphql")
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap();
+    let t = schema.types["T"].as_object().unwrap();
+    // Long enough to use the name index
+    assert_eq!(t.directives.len(), 9);
+    assert!(t.directives.has("shareable"));
+    assert!(!t.directives.has("inaccessible"));
+    assert_eq!(t.directives.get_all("tag").count(), 8);
+    assert_eq!(
+        t.directives
+            .get("tag")
+            .unwrap()
+            .specified_argument_by_name("name"),
+        Some(&apollo_compiler::ast::Value::String("a".into()).into())
+    );
+    // Repeatable directives are kept
+    let f = &t.fields["f"];
+    assert_eq!(f.directives.len(), 3);
+    assert_eq!(f.directives.get_all("tag").count(), 2);
+
+    // Mutation through `DerefMut` keeps lookups up to date
+    let mut directives = t.directives.clone();
+    assert!(directives.has("shareable"));
+    directives.retain(|dir| dir.name != "shareable");
+    assert!(!directives.has("shareable"));
+    directives[0].make_mut().name = apollo_compiler::name!("renamed");
+    assert!(directives.has("renamed"));
+    assert_eq!(directives.get_all("tag").count(), 7);
+}
+
+#[test]
+fn tolerant_mode_keeps_colliding_definitions() {
+    let input = r#"
+        schema { query: Query }
+        schema { query: Other }
+        type Query { a: Int }
+        type Query { b: String }
+        enum Query { C }
+        type Other { c: Int }
+        directive @tag(name: String) on OBJECT
+        directive @tag(label: String) on FIELD_DEFINITION
+        extend type Query { d: Int }
+    "#;
+    let err = Schema::builder()
+        .tolerant()
+        .parse(input, "schema.graphql")
+        .build()
+        .unwrap_err();
+    let collisions = err.collisions().unwrap();
+    assert_eq!(collisions.schema_definitions.len(), 1);
+    assert_eq!(
+        collisions.schema_definitions[0]
+            .query
+            .as_ref()
+            .unwrap()
+            .name,
+        "Other"
+    );
+
+    let query_definitions = err
+        .type_definitions("Query")
+        .map(|ty| (ty.is_object(), ty.is_enum()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        query_definitions,
+        [(true, false), (true, false), (false, true)]
+    );
+    let query = err.partial.types["Query"].as_object().unwrap();
+    assert!(query.fields.contains_key("a"));
+    assert!(query.fields.contains_key("d"));
+    let duplicate = collisions.types["Query"][0].as_object().unwrap();
+    assert!(duplicate.fields.contains_key("b"));
+    assert!(!duplicate.fields.contains_key("d"));
+    // Go-to-definition for each duplicate
+    let lines = err
+        .type_definitions("Query")
+        .map(|ty| {
+            ty.name()
+                .line_column_range(&err.partial.sources)
+                .unwrap()
+                .start
+                .line
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [4, 5, 6]);
+
+    let tag = err
+        .directive_definitions("tag")
+        .map(|def| def.arguments[0].name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(tag, ["name", "label"]);
+    assert_eq!(err.type_definitions("Other").count(), 1);
+    assert_eq!(err.type_definitions("Missing").count(), 0);
+
+    // Same errors as without tolerant mode, where duplicates are dropped
+    let default_err = Schema::parse(input, "schema.graphql").unwrap_err();
+    assert!(default_err.collisions().is_none());
+    assert_eq!(err.errors.to_string(), default_err.errors.to_string());
+    assert_eq!(default_err.type_definitions("Query").count(), 1);
+}
+
+#[test]
+fn capabilities() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @federation__key(fields: String!) repeatable on OBJECT | INTERFACE
+        directive @shareable on OBJECT | FIELD_DEFINITION
+        directive @defer(label: String, if: Boolean! = true) on FRAGMENT_SPREAD | INLINE_FRAGMENT
+        directive @custom on OBJECT
+        type Query { product: Product }
+        type Mutation { buy: Boolean }
+        type Product @federation__key(fields: "id") @shareable @custom { id: ID! }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let capabilities = schema.capabilities();
+    assert_eq!(
+        capabilities.federation_directives,
+        ["federation__key", "shareable"]
+    );
+    assert!(capabilities.custom_scalars.is_empty());
+    assert_eq!(
+        capabilities.feature_names(),
+        ["mutations", "defer", "federation"]
+    );
+    assert_eq!(
+        serde_json::to_string(&capabilities).unwrap(),
+        r#"{"mutations":true,"subscriptions":false,"customScalars":[],"specifiedByScalars":[],"interfacesImplementingInterfaces":[],"oneOfInputObjects":[],"defer":true,"stream":false,"federationDirectives":["federation__key","shareable"]}"#
+    );
+
+    let minimal = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    assert_eq!(
+        minimal.capabilities(),
+        apollo_compiler::schema::SchemaCapabilities::default()
+    );
+    assert!(minimal.capabilities().feature_names().is_empty());
+}
+
+#[test]
+fn ordering() {
+    let build = |files: &[&str], preserve_source_order| {
+        let mut builder = Schema::builder().preserve_source_order(preserve_source_order);
+        for (i, source) in files.iter().enumerate() {
+            builder = builder.parse(*source, format!("{i}.graphql"))
+        }
+        builder.build().unwrap()
+    };
+    let a = "type Query { b: Int, a: Thing } directive @z on FIELD";
+    let b = "type Thing { y: Int, x: Int } extend type Thing { w: Int } enum E { B A }";
+
+    let source_order = build(&[b, a], true);
+    let names: Vec<_> = source_order.types.keys().rev().take(3).collect();
+    assert_eq!(names, ["Query", "E", "Thing"]);
+    let fields: Vec<_> = source_order.types["Thing"]
+        .as_object()
+        .unwrap()
+        .fields
+        .keys()
+        .collect();
+    assert_eq!(fields, ["y", "x", "w"]);
+
+    let sorted = build(&[b, a], false);
+    assert_eq!(sorted.to_string(), build(&[a, b], false).to_string());
+    let names: Vec<_> = sorted.types.keys().rev().take(3).collect();
+    assert_eq!(names, ["__TypeKind", "__Type", "__Schema"]);
+    let fields: Vec<_> = sorted.types["Thing"]
+        .as_object()
+        .unwrap()
+        .fields
+        .keys()
+        .collect();
+    assert_eq!(fields, ["w", "x", "y"]);
+    let values: Vec<_> = sorted.types["E"].as_enum().unwrap().values.keys().collect();
+    assert_eq!(values, ["A", "B"]);
+
+    // Custom order: built-in types last, others by name
+    let mut schema = source_order;
+    schema.sort_types(|name_a, a, name_b, b| {
+        (a.is_built_in(), name_a).cmp(&(b.is_built_in(), name_b))
+    });
+    let names: Vec<_> = schema.types.keys().take(4).collect();
+    assert_eq!(names, ["E", "Query", "Thing", "Boolean"]);
+}
+
+#[test]
+fn service_description() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        "Marks internal elements"
+        directive @tag(name: String!) repeatable on FIELD_DEFINITION | ENUM_VALUE | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION
+        type Query {
+          search(
+            "What to look for"
+            text: String @tag(name: "internal") @deprecated(reason: "Use `by`")
+            by: By
+          ): [Color] @tag(name: "a") @tag(name: "b") @tag(name: "a")
+        }
+        input By { color: Color @tag(name: "b") }
+        enum Color { RED GREEN @deprecated }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let service = apollo_compiler::schema::ServiceDescription::from(schema);
+    let coordinates: Vec<_> = service.iter().map(|(c, _)| c.to_string()).collect();
+    assert_eq!(
+        coordinates,
+        [
+            "Query",
+            "Query.search",
+            "Query.search(text:)",
+            "Query.search(by:)",
+            "By",
+            "By.color",
+            "Color",
+            "Color.RED",
+            "Color.GREEN",
+            "@tag",
+            "@tag(name:)",
+        ]
+    );
+    let deprecated: Vec<_> = service
+        .deprecated()
+        .map(|(c, reason)| format!("{c}: {reason}"))
+        .collect();
+    assert_eq!(
+        deprecated,
+        [
+            "Query.search(text:): Use `by`",
+            "Color.GREEN: No longer supported"
+        ]
+    );
+    assert_eq!(service.tags().collect::<Vec<_>>(), ["a", "b", "internal"]);
+    let tagged_b: Vec<_> = service.tagged("b").map(|c| c.to_string()).collect();
+    assert_eq!(tagged_b, ["Query.search", "By.color"]);
+    assert_eq!(service.tagged("missing").count(), 0);
+
+    let search = service.get(&"Query.search".parse().unwrap()).unwrap();
+    assert_eq!(search.tags, ["a", "b", "a"]);
+    let text = service
+        .get(&"Query.search(text:)".parse().unwrap())
+        .unwrap();
+    assert_eq!(text.description.as_deref(), Some("What to look for"));
+    let tag = service.get(&"@tag".parse().unwrap()).unwrap();
+    assert_eq!(tag.description.as_deref(), Some("Marks internal elements"));
+    assert!(service.get(&"String".parse().unwrap()).is_none());
+    assert!(service.get(&"@deprecated".parse().unwrap()).is_none());
+}
+
+#[test]
+fn apply_directives_programmatically() {
+    use apollo_compiler::ast::Directive;
+    use apollo_compiler::ast::DirectiveLocation;
+    use apollo_compiler::coordinate::SchemaCoordinate;
+    use apollo_compiler::name;
+
+    let mut schema = Schema::parse_and_validate(
+        r#"
+        directive @key(fields: String!) on OBJECT | INTERFACE
+        directive @tag(name: String!) repeatable on FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE
+        directive @limit(max: Int = 10) on INPUT_FIELD_DEFINITION | ARGUMENT_DEFINITION
+
+        type Query {
+          users(filter: Filter, first: Int): [User]
+        }
+
+        type User {
+          id: ID!
+        }
+
+        input Filter {
+          name: String
+        }
+
+        enum Role { ADMIN }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap()
+    .into_inner();
+    let coordinate = |text: &str| text.parse::<SchemaCoordinate>().unwrap();
+    let key = |fields: &str| Directive {
+        name: name!("key"),
+        arguments: vec![(name!("fields"), fields).into()],
+    };
+
+    assert_eq!(
+        schema.directive_location(&coordinate("User")),
+        Some(DirectiveLocation::Object)
+    );
+    assert_eq!(
+        schema.directive_location(&coordinate("Filter.name")),
+        Some(DirectiveLocation::InputFieldDefinition)
+    );
+    assert_eq!(
+        schema.directive_location(&coordinate("Role.ADMIN")),
+        Some(DirectiveLocation::EnumValue)
+    );
+    assert_eq!(
+        schema.directive_location(&coordinate("Query.users(first:)")),
+        Some(DirectiveLocation::ArgumentDefinition)
+    );
+    assert_eq!(
+        schema.directive_location(&coordinate("@limit(max:)")),
+        Some(DirectiveLocation::ArgumentDefinition)
+    );
+    assert_eq!(schema.directive_location(&coordinate("@limit")), None);
+    assert_eq!(schema.directive_location(&coordinate("Missing")), None);
+
+    let names = |defs: Vec<&apollo_compiler::Node<apollo_compiler::ast::DirectiveDefinition>>| {
+        defs.iter()
+            .map(|def| def.name.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(schema.applicable_directives(&coordinate("Query.users(first:)"))),
+        ["deprecated", "tag", "limit"]
+    );
+    assert_eq!(
+        names(schema.applicable_directives(&coordinate("User"))),
+        ["key"]
+    );
+    let targets: Vec<_> = schema
+        .directive_targets("limit")
+        .iter()
+        .map(|coordinate| coordinate.to_string())
+        .collect();
+    assert_eq!(
+        targets,
+        [
+            "Query.users(filter:)",
+            "Query.users(first:)",
+            "Filter.name",
+            "@key(fields:)",
+            "@tag(name:)",
+            "@limit(max:)",
+        ]
+    );
+
+    schema
+        .apply_directive(&coordinate("User"), key("id"))
+        .unwrap();
+    assert!(schema.applicable_directives(&coordinate("User")).is_empty());
+    let tag = Directive {
+        name: name!("tag"),
+        arguments: vec![(name!("name"), "internal").into()],
+    };
+    for target in ["Query.users", "Query.users(first:)", "Role.ADMIN"] {
+        schema
+            .apply_directive(&coordinate(target), tag.clone())
+            .unwrap();
+    }
+    // Repeatable
+    schema
+        .apply_directive(&coordinate("Role.ADMIN"), tag.clone())
+        .unwrap();
+    let limit = Directive {
+        name: name!("limit"),
+        arguments: Vec::new(),
+    };
+    schema
+        .apply_directive(&coordinate("Filter.name"), limit.clone())
+        .unwrap();
+    schema
+        .apply_directive(&coordinate("@key(fields:)"), limit.clone())
+        .unwrap();
+    expect_test::expect![[r#"
+        directive @key(
+          fields: String! @limit,
+        ) on OBJECT | INTERFACE
+
+        directive @tag(name: String!) repeatable on FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE
+
+        directive @limit(max: Int = 10) on INPUT_FIELD_DEFINITION | ARGUMENT_DEFINITION
+
+        type Query {
+          users(
+            filter: Filter,
+            first: Int @tag(name: "internal"),
+          ): [User] @tag(name: "internal")
+        }
+
+        type User @key(fields: "id") {
+          id: ID!
+        }
+
+        input Filter {
+          name: String @limit
+        }
+
+        enum Role {
+          ADMIN @tag(name: "internal") @tag(name: "internal")
+        }
+    "#]].assert_eq(&schema.to_string());
+    schema.clone().validate().unwrap();
+
+    let before = schema.clone();
+    let error = |target: &str, directive: Directive| {
+        let mut schema = before.clone();
+        let error = schema
+            .apply_directive(&coordinate(target), directive)
+            .unwrap_err();
+        assert_eq!(schema, before, "schema should not change on error");
+        error.to_string()
+    };
+    assert_eq!(
+        error("Missing", key("id")),
+        "`Missing` does not exist in the schema"
+    );
+    assert_eq!(
+        error("@key", key("id")),
+        "directives cannot be applied to the directive definition `@key`"
+    );
+    assert_eq!(
+        error(
+            "User",
+            Directive {
+                name: name!("unknown"),
+                arguments: Vec::new()
+            }
+        ),
+        "cannot find directive `@unknown` in the schema"
+    );
+    assert_eq!(
+        error("Query.users", key("id")),
+        "directive `@key` cannot be applied to FIELD_DEFINITION, valid locations are: OBJECT, INTERFACE"
+    );
+    assert_eq!(
+        error("User", key("name")),
+        "non-repeatable directive `@key` is already applied to this element"
+    );
+    let mut with_unknown = key("id");
+    with_unknown
+        .arguments
+        .push((name!("resolvable"), true).into());
+    assert_eq!(
+        error("Query", with_unknown),
+        "directive `@key` has no argument `resolvable`"
+    );
+    let mut twice = key("id");
+    twice.arguments.push((name!("fields"), "name").into());
+    assert_eq!(
+        error("Query", twice),
+        "argument `fields` of directive `@key` is given more than once"
+    );
+    assert_eq!(
+        error(
+            "Query",
+            Directive {
+                name: name!("key"),
+                arguments: Vec::new()
+            }
+        ),
+        "directive `@key` requires argument `fields`"
+    );
+    let mut wrong_type = limit.clone();
+    wrong_type.arguments.push((name!("max"), "ten").into());
+    assert_eq!(
+        error("Query.users(filter:)", wrong_type),
+        "invalid value for argument `max` of directive `@limit`: \
+         expected value of type Int, found a string"
+    );
+    let mut variable = limit.clone();
+    variable.arguments.push(
+        (
+            name!("max"),
+            apollo_compiler::ast::Value::Variable(name!("max")),
+        )
+            .into(),
+    );
+    assert_eq!(
+        error("Query.users(filter:)", variable),
+        "invalid value for argument `max` of directive `@limit`: \
+         variable `$max` is not allowed in a schema"
+    );
+}
+
+#[test]
+fn resolve_legacy_names() {
+    use apollo_compiler::ast;
+    use apollo_compiler::name;
+    use apollo_compiler::schema::NameMatch;
+    use apollo_compiler::schema::NameMatching;
+    use apollo_compiler::ExecutableDocument;
+
+    let schema = Schema::parse_and_validate(
+        r#"
+        type Query { search(filter: SearchFilter): [Result] node: Node }
+        input SearchFilter { text: String }
+        union Result = Product | Review
+        interface Node { id: ID! }
+        type Product implements Node { id: ID! title: String }
+        type Review implements Node { id: ID! Title: String title: String }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+
+    let strict = NameMatching::default();
+    assert!(strict.resolve_type(&schema, "product").is_none());
+    assert_eq!(
+        strict.resolve_type(&schema, "Product").unwrap().kind,
+        NameMatch::Exact
+    );
+
+    let mut matching = NameMatching {
+        case_insensitive: true,
+        ..Default::default()
+    };
+    matching
+        .type_aliases
+        .insert(name!("LegacyFilter"), name!("SearchFilter"));
+    assert_eq!(
+        matching.resolve_type(&schema, "product").unwrap().name,
+        "Product"
+    );
+    let field = matching.resolve_field(&schema, "product", "TITLE").unwrap();
+    assert_eq!(
+        (field.name.as_str(), field.kind),
+        ("title", NameMatch::CaseInsensitive)
+    );
+    // Exact matches take precedence, and ambiguous case-insensitive matches do not resolve
+    let field = matching.resolve_field(&schema, "Review", "Title").unwrap();
+    assert_eq!(
+        (field.name.as_str(), field.kind),
+        ("Title", NameMatch::Exact)
+    );
+    assert!(matching.resolve_field(&schema, "Review", "TITLE").is_none());
+    let field = matching
+        .resolve_field(&schema, "Node", "__typename")
+        .unwrap();
+    assert_eq!(field.name, "__typename");
+
+    let legacy = r#"
+        query Search($filter: LegacyFilter) {
+          search(filter: $filter) { ... on product { Title } ...Fields }
+          Node { ID }
+        }
+        fragment Fields on REVIEW { title unknown }
+    "#;
+    let mut document = ast::Document::parse(legacy, "legacy.graphql").unwrap();
+    let renames: Vec<_> = matching
+        .rename_document(&schema, &mut document)
+        .into_iter()
+        .map(|rename| (rename.from.to_string(), rename.to.to_string(), rename.kind))
+        .collect();
+    let rename = |from: &str, to: &str, kind| (from.to_owned(), to.to_owned(), kind);
+    assert_eq!(
+        renames,
+        [
+            rename("LegacyFilter", "SearchFilter", NameMatch::Alias),
+            rename("product", "Product", NameMatch::CaseInsensitive),
+            rename("Title", "title", NameMatch::CaseInsensitive),
+            rename("Node", "node", NameMatch::CaseInsensitive),
+            rename("ID", "id", NameMatch::CaseInsensitive),
+            rename("REVIEW", "Review", NameMatch::CaseInsensitive),
+        ]
+    );
+    let errors =
+        ExecutableDocument::parse_and_validate(&schema, document.to_string(), "fixed.graphql")
+            .unwrap_err()
+            .errors
+            .to_string();
+    assert!(
+        errors.contains("type `Review` does not have a field `unknown`"),
+        "{errors}"
+    );
+}
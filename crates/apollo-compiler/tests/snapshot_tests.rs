@@ -114,6 +114,15 @@ fn serialize_and_reparse_ast() {
             let serialized = original.to_string();
             expect_file![output_path].assert_eq(&serialized);
 
+            // Checks in debug mode that provenance traversals line up
+            let (_, provenance) = original
+                .serialize()
+                .to_string_with_provenance("serialized.graphql");
+            assert_eq!(
+                provenance.entries().is_empty(),
+                original.definitions.is_empty()
+            );
+
             let round_tripped = ast::Document::parse(&serialized, "serialized.graphql")
                 .unwrap_or_else(|invalid| invalid.partial);
             if original != round_tripped {
@@ -127,6 +136,61 @@ fn serialize_and_reparse_ast() {
     }
 }
 
+/// Lists the corpus embedded by the `conformance` feature,
+/// so that the suite includes files added to `test_data`.
+#[test]
+#[serial]
+fn conformance_corpus() {
+    let mut generated = String::from(
+        "// Generated by `conformance_corpus` in tests/snapshot_tests.rs, do not edit.\n\
+         // To update: env UPDATE_EXPECT=1 cargo test --package apollo-compiler\n\
+         \n\
+         use super::Case;\n\
+         use super::Expected::Accepted;\n\
+         use super::Expected::Rejected;\n\
+         use super::Mode::Executable;\n\
+         use super::Mode::Mixed;\n\
+         use super::Mode::TypeSystem;\n\
+         \n\
+         macro_rules! case {\n    \
+             ($name: literal, $mode: expr, $expected: expr) => {\n        \
+                 Case {\n            \
+                     name: $name,\n            \
+                     input: include_str!(concat!(\"../../test_data/\", $name, \".graphql\")),\n            \
+                     mode: $mode,\n            \
+                     expected: $expected,\n            \
+                     serialized: include_str!(concat!(\"../../test_data/serializer/\", $name, \".graphql\")),\n        \
+                 }\n    \
+             };\n\
+         }\n\
+         \n\
+         pub(super) const VALIDATION: &[Case] = &[\n",
+    );
+    for (dir, expected) in [("ok", "Accepted"), ("diagnostics", "Rejected")] {
+        for path in graphql_files_in_dir(&test_data_dir().join(dir)) {
+            let stem = path.file_stem().unwrap().to_str().unwrap();
+            // Same as the `validation` test
+            let mode = if expected == "Accepted" {
+                "Mixed"
+            } else if stem.contains("type_system_document") {
+                "TypeSystem"
+            } else if stem.contains("executable_document") {
+                "Executable"
+            } else {
+                "Mixed"
+            };
+            writeln!(
+                generated,
+                "    case!(\"{dir}/{stem}\", {mode}, {expected}),"
+            )
+            .unwrap();
+        }
+    }
+    generated.push_str("];\n");
+    let path = project_root().join("apollo-compiler/src/conformance/corpus.rs");
+    expect_file![path].assert_eq(&generated);
+}
+
 /// Compares input code taken from a `.graphql` file in test_fixtures and its
 /// expected output in the corresponding `.txt` file.
 ///
@@ -242,6 +306,7 @@ fn test_invalid_synthetic_node() {
                 name!("field"),
                 schema::FieldDefinition {
                     description: Default::default(),
+                    leading_comments: Vec::new(),
                     name: name!("field"),
                     arguments: Default::default(),
                     ty: ty!(UndefinedType),
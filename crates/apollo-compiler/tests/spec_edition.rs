@@ -0,0 +1,336 @@
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::execute_introspection;
+use apollo_compiler::parser::Parser;
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::schema::SpecEdition;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+fn built_ins(schema: &Schema) -> String {
+    let directives: Vec<_> = schema
+        .directive_definitions
+        .values()
+        .filter(|def| def.is_built_in())
+        .map(|def| def.name.as_str())
+        .collect();
+    let deprecated = &schema.directive_definitions["deprecated"];
+    let ExtendedType::Object(ty) = &schema.types["__Type"] else {
+        panic!()
+    };
+    let type_fields: Vec<_> = ty.fields.values().map(|field| field.to_string()).collect();
+    format!(
+        "{}\n{deprecated}\n{}\n",
+        directives.join(", "),
+        type_fields.join("\n")
+    )
+}
+
+#[test]
+fn built_ins_per_edition() {
+    let unpinned = Schema::builder().build().unwrap();
+    expect![[r#"
+        skip, include, deprecated, specifiedBy
+        """Marks an element of a GraphQL schema as no longer supported."""
+        directive @deprecated(
+          """
+          Explains why this element was deprecated, usually also including a suggestion for how to access supported similar data. Formatted using the Markdown syntax, as specified by [CommonMark](https://commonmark.org/).
+          """
+          reason: String = "No longer supported",
+        ) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+        kind: __TypeKind!
+        name: String
+        description: String
+        fields(includeDeprecated: Boolean = false): [__Field!]
+        interfaces: [__Type!]
+        possibleTypes: [__Type!]
+        enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+        inputFields(includeDeprecated: Boolean = false): [__InputValue!]
+        ofType: __Type
+        specifiedByURL: String
+    "#]]
+    .assert_eq(&built_ins(&unpinned));
+
+    let october_2021 = Schema::builder()
+        .spec_edition(SpecEdition::October2021)
+        .build()
+        .unwrap();
+    expect![[r#"
+        skip, include, deprecated, specifiedBy
+        """Marks an element of a GraphQL schema as no longer supported."""
+        directive @deprecated(
+          """
+          Explains why this element was deprecated, usually also including a suggestion for how to access supported similar data. Formatted using the Markdown syntax, as specified by [CommonMark](https://commonmark.org/).
+          """
+          reason: String = "No longer supported",
+        ) on FIELD_DEFINITION | ENUM_VALUE
+        kind: __TypeKind!
+        name: String
+        description: String
+        fields(includeDeprecated: Boolean = false): [__Field!]
+        interfaces: [__Type!]
+        possibleTypes: [__Type!]
+        enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+        inputFields: [__InputValue!]
+        ofType: __Type
+        specifiedByURL: String
+    "#]]
+    .assert_eq(&built_ins(&october_2021));
+
+    let draft = Schema::builder()
+        .spec_edition(SpecEdition::Draft)
+        .build()
+        .unwrap();
+    expect![[r#"
+        skip, include, deprecated, specifiedBy, oneOf
+        """Marks an element of a GraphQL schema as no longer supported."""
+        directive @deprecated(
+          """
+          Explains why this element was deprecated, usually also including a suggestion for how to access supported similar data. Formatted using the Markdown syntax, as specified by [CommonMark](https://commonmark.org/).
+          """
+          reason: String = "No longer supported",
+        ) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+        kind: __TypeKind!
+        name: String
+        description: String
+        fields(includeDeprecated: Boolean = false): [__Field!]
+        interfaces: [__Type!]
+        possibleTypes: [__Type!]
+        enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+        inputFields(includeDeprecated: Boolean = false): [__InputValue!]
+        ofType: __Type
+        specifiedByURL: String
+        isOneOf: Boolean
+    "#]]
+    .assert_eq(&built_ins(&draft));
+}
+
+#[test]
+fn deprecated_arguments_require_draft() {
+    let sdl = r#"
+        type Query { field(old: Int @deprecated, new: Int): Int }
+    "#;
+    Parser::new()
+        .spec_edition(SpecEdition::Draft)
+        .parse_schema(sdl, "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap();
+    let errors = Parser::new()
+        .spec_edition(SpecEdition::October2021)
+        .parse_schema(sdl, "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap_err()
+        .errors;
+    expect![[r#"
+        Error: deprecated directive is not supported for ARGUMENT_DEFINITION location
+             ╭─[schema.graphql:2:37]
+             │
+           2 │         type Query { field(old: Int @deprecated, new: Int): Int }
+             │                                     ─────┬─────  
+             │                                          ╰─────── directive cannot be used on ARGUMENT_DEFINITION
+             │
+             ├─[built_in.graphql:149:1]
+             │
+         149 │ ╭─▶ "Marks an element of a GraphQL schema as no longer supported."
+             ┆ ┆   
+         155 │ ├─▶ ) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+             │ │                                                                                       
+             │ ╰─────────────────────────────────────────────────────────────────────────────────────── directive defined here
+             │     
+             │     Help: the directive must be used in a location that the service has declared support for: FIELD_DEFINITION, ENUM_VALUE
+        ─────╯
+    "#]]
+    .assert_eq(&errors.to_string());
+}
+
+#[test]
+fn redefinitions_are_kept() {
+    let mut builder = Schema::builder().adopt_orphan_extensions();
+    Parser::new().parse_into_schema_builder(
+        "directive @oneOf on INPUT_OBJECT | ARGUMENT_DEFINITION type Query { a: Int }",
+        "schema.graphql",
+        &mut builder,
+    );
+    let schema = builder.spec_edition(SpecEdition::Draft).build().unwrap();
+    let one_of = &schema.directive_definitions["oneOf"];
+    assert!(!one_of.is_built_in());
+    assert_eq!(one_of.locations.len(), 2);
+    let names: Vec<_> = schema.directive_definitions.keys().collect();
+    assert_eq!(
+        names,
+        ["skip", "include", "deprecated", "specifiedBy", "oneOf"]
+    );
+}
+
+fn one_of_schema() -> Valid<Schema> {
+    Parser::new()
+        .spec_edition(SpecEdition::Draft)
+        .parse_schema(
+            r#"
+            type Query { search(by: SearchBy): [String] }
+            input SearchBy @oneOf { name: String, id: ID }
+            "#,
+            "schema.graphql",
+        )
+        .unwrap()
+        .validate()
+        .unwrap()
+}
+
+#[test]
+fn one_of_input_object_definitions() {
+    let errors = Parser::new()
+        .spec_edition(SpecEdition::Draft)
+        .parse_schema(
+            r#"
+            type Query { search(by: SearchBy): [String] }
+            input SearchBy @oneOf { name: String!, id: ID = 1, other: Int }
+            "#,
+            "schema.graphql",
+        )
+        .unwrap()
+        .validate()
+        .unwrap_err()
+        .errors;
+    let messages: Vec<_> = errors
+        .iter()
+        .map(|error| error.error.unstable_compat_message().unwrap())
+        .collect();
+    expect![[r#"
+        [
+            "OneOf input field SearchBy.name must be nullable.",
+            "OneOf input field SearchBy.id cannot have a default value.",
+        ]
+    "#]]
+    .assert_debug_eq(&messages);
+}
+
+#[test]
+fn one_of_input_values() {
+    let schema = one_of_schema();
+    let messages = |query: &str| -> Vec<_> {
+        match ExecutableDocument::parse_and_validate(&schema, query, "query.graphql") {
+            Ok(_) => Vec::new(),
+            Err(with_errors) => with_errors
+                .errors
+                .iter()
+                .map(|error| error.error.to_string())
+                .collect(),
+        }
+    };
+    assert!(messages(r#"{ search(by: { name: "x" }) }"#).is_empty());
+    assert!(messages(r#"query($id: ID!) { search(by: { id: $id }) }"#).is_empty());
+    expect![[r#"
+        [
+            "exactly one field of oneOf input object `SearchBy` must be provided, and it must not be null",
+        ]
+    "#]]
+    .assert_debug_eq(&messages(r#"{ search(by: { name: "x", id: 1 }) }"#));
+    expect![[r#"
+        [
+            "exactly one field of oneOf input object `SearchBy` must be provided, and it must not be null",
+        ]
+    "#]]
+    .assert_debug_eq(&messages(r#"{ search(by: { name: null }) }"#));
+    expect![[r#"
+        [
+            "exactly one field of oneOf input object `SearchBy` must be provided, and it must not be null",
+        ]
+    "#]]
+    .assert_debug_eq(&messages(r#"{ search(by: {}) }"#));
+    expect![[r#"
+        [
+            "variable `$id` must be non-null to be used as a field of oneOf input object `SearchBy`",
+        ]
+    "#]]
+    .assert_debug_eq(&messages(r#"query($id: ID) { search(by: { id: $id }) }"#));
+}
+
+#[test]
+fn one_of_variable_coercion() {
+    let schema = one_of_schema();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        "query($by: SearchBy) { search(by: $by) }",
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let coerce = |variables: &str| {
+        let variables = serde_json::from_str(variables).unwrap();
+        match coerce_variable_values(&schema, operation, &variables) {
+            Ok(_) => "ok".to_owned(),
+            Err(error) => error.into_graphql_error(&document.sources).message,
+        }
+    };
+    expect!["ok"].assert_eq(&coerce(r#"{"by": {"id": "1"}}"#));
+    expect!["OneOf input object SearchBy must specify exactly one non-null field"]
+        .assert_eq(&coerce(r#"{"by": {"id": "1", "name": "x"}}"#));
+    expect!["OneOf input object SearchBy must specify exactly one non-null field"]
+        .assert_eq(&coerce(r#"{"by": {"id": null}}"#));
+}
+
+#[test]
+fn user_defined_one_of_has_no_semantics() {
+    // Without a pinned edition, `@oneOf` is not built-in
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @oneOf on INPUT_OBJECT
+        type Query { search(by: SearchBy): [String] }
+        input SearchBy @oneOf { name: String!, id: ID = 1 }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"query($by: SearchBy) { search(by: { name: "x", id: 2 }) other: search(by: $by) }"#,
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = serde_json::from_str(r#"{"by": {"name": "x", "id": "2"}}"#).unwrap();
+    assert!(coerce_variable_values(&schema, operation, &variables).is_ok());
+}
+
+#[test]
+fn introspection_per_edition() {
+    let sdl = r#"
+        type Query { field(old: Int, new: Int): Int }
+        input In { a: Int }
+    "#;
+    let query = r#"
+        {
+            __type(name: "In") { isOneOf }
+            __schema { directives { name } }
+        }
+    "#;
+    let draft = Parser::new()
+        .spec_edition(SpecEdition::Draft)
+        .parse_schema(sdl, "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap();
+    let response = execute_introspection(&draft, query, None, &Default::default());
+    expect![[
+        r#"{"data":{"__type":{"isOneOf":false},"__schema":{"directives":[{"name":"skip"},{"name":"include"},{"name":"deprecated"},{"name":"specifiedBy"},{"name":"oneOf"}]}}}"#
+    ]]
+    .assert_eq(&serde_json::to_string(&response).unwrap());
+
+    let october_2021 = Parser::new()
+        .spec_edition(SpecEdition::October2021)
+        .parse_schema(sdl, "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap();
+    let response = execute_introspection(&october_2021, query, None, &Default::default());
+    expect![[r#"{"errors":[{"message":"interface, union and object types must have a subselection set","locations":[{"line":3,"column":13}]},{"message":"type `__Type` does not have a field `isOneOf`","locations":[{"line":3,"column":34}]}]}"#]]
+    .assert_eq(&serde_json::to_string(&response).unwrap());
+    let query = r#"{ __type(name: "Query") { fields { args { name } } } }"#;
+    let response = execute_introspection(&october_2021, query, None, &Default::default());
+    expect![[r#"{"data":{"__type":{"fields":[{"args":[{"name":"old"},{"name":"new"}]}]}}}"#]]
+        .assert_eq(&serde_json::to_string(&response).unwrap());
+}
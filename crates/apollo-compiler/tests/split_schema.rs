@@ -0,0 +1,244 @@
+use apollo_compiler::schema::split_by;
+use apollo_compiler::schema::split_by_type;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    schema { query: Query mutation: Mutation }
+    extend schema @link(url: "https://example.com/shop")
+
+    directive @link(url: String!) repeatable on SCHEMA
+    directive @domain(name: String!) repeatable on OBJECT | INTERFACE | ENUM | INPUT_OBJECT | SCALAR
+
+    type Query {
+        me: User
+        products(filter: ProductFilter): [Product!]!
+    }
+
+    type Mutation @domain(name: "shop") {
+        buy(id: ID!): Order
+    }
+
+    interface Node { id: ID! }
+
+    type User implements Node @domain(name: "users") {
+        id: ID!
+        name: String @deprecated
+    }
+
+    extend type User @domain(name: "shop") {
+        orders: [Order!]!
+    }
+
+    type Product implements Node @domain(name: "shop") {
+        id: ID!
+        price: Money
+        category: Category
+    }
+
+    type Order @domain(name: "shop") {
+        buyer: User!
+        items: [Product!]!
+    }
+
+    enum Category @domain(name: "shop") { BOOKS GAMES }
+    input ProductFilter @domain(name: "shop") { category: Category }
+    scalar Money @domain(name: "shop")
+    directive @internal on SCALAR
+    extend scalar Boolean @internal
+"#;
+
+#[test]
+fn split_by_domain_directive() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let split = split_by(&schema, |definition| {
+        definition
+            .directives()
+            .get("domain")
+            .and_then(|directive| directive.specified_argument_by_name("name"))
+            .and_then(|name| name.as_str())
+            .unwrap_or("common")
+            .to_owned()
+    });
+    let mut documents = String::new();
+    for (part, document) in &split.documents {
+        documents.push_str(&format!("# {part}\n{document}\n"));
+    }
+    expect![[r##"
+        # common
+        schema {
+          query: Query
+          mutation: Mutation
+        }
+
+        extend schema @link(url: "https://example.com/shop")
+
+        directive @link(url: String!) repeatable on SCHEMA
+
+        directive @domain(name: String!) repeatable on OBJECT | INTERFACE | ENUM | INPUT_OBJECT | SCALAR
+
+        directive @internal on SCALAR
+
+        extend scalar Boolean @internal
+
+        type Query {
+          me: User
+          products(filter: ProductFilter): [Product!]!
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        # shop
+        type Mutation @domain(name: "shop") {
+          buy(id: ID!): Order
+        }
+
+        extend type User @domain(name: "shop") {
+          orders: [Order!]!
+        }
+
+        type Product implements Node @domain(name: "shop") {
+          id: ID!
+          price: Money
+          category: Category
+        }
+
+        type Order @domain(name: "shop") {
+          buyer: User!
+          items: [Product!]!
+        }
+
+        enum Category @domain(name: "shop") {
+          BOOKS
+          GAMES
+        }
+
+        input ProductFilter @domain(name: "shop") {
+          category: Category
+        }
+
+        scalar Money @domain(name: "shop")
+
+        # users
+        type User implements Node @domain(name: "users") {
+          id: ID!
+          name: String @deprecated
+        }
+
+    "##]].assert_eq(&documents);
+
+    let cross_references = split
+        .cross_references
+        .iter()
+        .map(|reference| {
+            let by = reference.referenced_by.as_deref().unwrap_or("schema");
+            format!(
+                "{} -> {}: {by} uses {}\n",
+                reference.from, reference.to, reference.target
+            )
+        })
+        .collect::<String>();
+    expect![[r#"
+        common -> shop: schema uses Mutation
+        common -> users: Query uses User
+        common -> shop: Query uses Product
+        common -> shop: Query uses ProductFilter
+        shop -> common: Mutation uses @domain
+        shop -> common: User uses @domain
+        shop -> users: User uses User
+        shop -> common: Product uses @domain
+        shop -> common: Product uses Node
+        shop -> common: Order uses @domain
+        shop -> users: Order uses User
+        shop -> common: Category uses @domain
+        shop -> common: ProductFilter uses @domain
+        shop -> common: Money uses @domain
+        users -> common: User uses @domain
+        users -> common: User uses Node
+    "#]]
+    .assert_eq(&cross_references);
+
+    let mut builder = Schema::builder();
+    for document in split.documents.values().rev() {
+        builder = builder.add_ast(document);
+    }
+    let rebuilt = builder.build().unwrap().validate().unwrap();
+    assert_eq!(rebuilt.content_hash(), schema.content_hash());
+}
+
+#[test]
+fn write_and_read_dir() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("split_schema");
+    let _ = std::fs::remove_dir_all(&dir);
+    split_by_type(&schema).write_to_dir(&dir).unwrap();
+
+    let index = std::fs::read_to_string(dir.join("index.txt")).unwrap();
+    expect![[r#"
+        schema.graphql
+        directives/link.graphql
+        directives/domain.graphql
+        directives/internal.graphql
+        types/Boolean.graphql
+        types/Query.graphql
+        types/Mutation.graphql
+        types/Node.graphql
+        types/User.graphql
+        types/Product.graphql
+        types/Order.graphql
+        types/Category.graphql
+        types/ProductFilter.graphql
+        types/Money.graphql
+    "#]]
+    .assert_eq(&index);
+    let user = std::fs::read_to_string(dir.join("types/User.graphql")).unwrap();
+    expect![[r#"
+        type User implements Node @domain(name: "users") {
+          id: ID!
+          name: String @deprecated
+        }
+
+        extend type User @domain(name: "shop") {
+          orders: [Order!]!
+        }
+    "#]]
+    .assert_eq(&user);
+
+    let rebuilt = Schema::builder()
+        .parse_dir(&dir)
+        .unwrap()
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap();
+    assert_eq!(rebuilt.content_hash(), schema.content_hash());
+    assert!(rebuilt
+        .sources
+        .values()
+        .any(|file| file.path() == dir.join("types/User.graphql")));
+}
+
+#[test]
+fn write_to_dir_rejects_paths_outside_dir() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("split_schema_invalid");
+    for key in ["../escape.graphql", "/absolute.graphql", "index.txt"] {
+        let err = split_by(&schema, |_| key).write_to_dir(&dir).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+    assert!(!dir.exists());
+}
+
+#[test]
+fn parse_dir_missing_file() {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("split_schema_missing");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.txt"), "# parts\n\nmissing.graphql\n").unwrap();
+    let Err(err) = Schema::builder().parse_dir(&dir) else {
+        panic!("expected an error")
+    };
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,93 @@
+use apollo_compiler::ast;
+use apollo_compiler::parser::read_stream;
+use apollo_compiler::parser::StreamParseError;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use futures_executor::block_on;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+/// Yields chunks one at a time, returning `Pending` once before each
+struct Chunks {
+    chunks: VecDeque<io::Result<Vec<u8>>>,
+    pending: bool,
+}
+
+fn chunks(text: &str, chunk_size: usize) -> Chunks {
+    Chunks {
+        chunks: text
+            .as_bytes()
+            .chunks(chunk_size)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect(),
+        pending: false,
+    }
+}
+
+impl futures_core::Stream for Chunks {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(self.chunks.pop_front())
+    }
+}
+
+#[test]
+fn parse_from_stream() {
+    // Split inside a multi-byte character
+    let sdl = "type Query { \"Café\" f: Int }";
+    let schema = block_on(Schema::parse_stream(chunks(sdl, 3), "schema.graphql")).unwrap();
+    let schema = schema.validate().unwrap();
+    assert_eq!(
+        schema.to_string(),
+        Schema::parse(sdl, "").unwrap().to_string()
+    );
+
+    let query = "{ f }";
+    let document = block_on(ExecutableDocument::parse_stream(
+        &schema,
+        chunks(query, 2),
+        "q.graphql",
+    ))
+    .unwrap();
+    assert_eq!(document.to_string(), "{\n  f\n}\n");
+
+    let document = block_on(ast::Document::parse_stream(chunks(sdl, 1), "s.graphql")).unwrap();
+    assert_eq!(document.definitions.len(), 1);
+}
+
+#[test]
+fn stream_errors() {
+    let result = block_on(Schema::parse_stream(
+        chunks("type Query {", 4),
+        "schema.graphql",
+    ));
+    let Err(StreamParseError::Invalid(with_errors)) = result else {
+        panic!("expected a parse error")
+    };
+    assert!(!with_errors.errors.is_empty());
+
+    let mut stream = chunks("type Query", 4);
+    stream
+        .chunks
+        .push_back(Err(io::Error::other("connection reset")));
+    let err = block_on(Schema::parse_stream(stream, "schema.graphql")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "failed to read GraphQL source: connection reset"
+    );
+    assert!(err.into_invalid().is_none());
+
+    let mut stream = chunks("", 1);
+    stream.chunks.push_back(Ok(vec![0xFF]));
+    let err = block_on(read_stream(stream)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
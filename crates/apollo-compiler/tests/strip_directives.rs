@@ -0,0 +1,136 @@
+use apollo_compiler::executable::strip_directives;
+use apollo_compiler::executable::strip_selections_with_directives;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!): User
+  feed(offset: Int, limit: Int): [Post]
+}
+type User {
+  name: String
+  isLoggedIn: Boolean
+  friends: [User]
+}
+type Post { title: String }
+directive @client(always: Boolean) on QUERY | FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT | FRAGMENT_DEFINITION
+directive @connection(key: String!, filter: [String]) on FIELD
+"#;
+
+fn parse(source: &str) -> ExecutableDocument {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    ExecutableDocument::parse(&schema, source, "query.graphql").unwrap()
+}
+
+#[test]
+fn strips_directives_everywhere() {
+    let document = parse(
+        r#"
+        query Feed($offset: Int, $always: Boolean) @client {
+          feed(offset: $offset) @connection(key: "feed") { title }
+          user(id: "1") { ...UserFields @client(always: $always) }
+        }
+        fragment UserFields on User @client { name }
+        "#,
+    );
+    let stripped = strip_directives(&document, &["client", "connection"]);
+    expect![[r#"
+        query Feed($offset: Int) {
+          feed(offset: $offset) {
+            title
+          }
+          user(id: "1") {
+            ...UserFields
+          }
+        }
+
+        fragment UserFields on User {
+          name
+        }
+    "#]]
+    .assert_eq(&stripped.document.to_string());
+    let directives: Vec<_> = stripped
+        .directives
+        .iter()
+        .map(|stripped| format!("{}: {}", stripped.path, stripped.node))
+        .collect();
+    assert_eq!(
+        directives,
+        [
+            "query Feed: @client",
+            r#"query Feed → feed: @connection(key: "feed")"#,
+            "query Feed → user: @client(always: $always)",
+            "fragment UserFields: @client",
+        ]
+    );
+    assert!(stripped.selections.is_empty());
+    assert!(stripped.fragments.is_empty());
+    assert_eq!(stripped.variables.len(), 1);
+    assert_eq!(stripped.variables[0].node.name, "always");
+}
+
+#[test]
+fn strips_client_selections() {
+    let document = parse(
+        r#"
+        query User($id: ID!, $unused: Int) {
+          user(id: $id) {
+            name
+            isLoggedIn @client
+            friends @client { ...Friend }
+            ... @client { friends { name } }
+          }
+          feed @connection(key: "feed") { title }
+        }
+        fragment Friend on User { name }
+        "#,
+    );
+    let stripped = strip_selections_with_directives(&document, &["client"]);
+    expect![[r#"
+        query User($id: ID!, $unused: Int) {
+          user(id: $id) {
+            name
+          }
+          feed @connection(key: "feed") {
+            title
+          }
+        }
+    "#]]
+    .assert_eq(&stripped.document.to_string());
+    let selections: Vec<_> = stripped
+        .selections
+        .iter()
+        .map(|stripped| stripped.path.to_string())
+        .collect();
+    assert_eq!(selections, ["query User → user"; 3]);
+    assert!(stripped.directives.is_empty());
+    assert_eq!(stripped.fragments.len(), 1);
+    assert_eq!(stripped.fragments[0].name, "Friend");
+    // Variables that were already unused are kept
+    assert!(stripped.variables.is_empty());
+}
+
+#[test]
+fn removes_emptied_fields() {
+    let document = parse(
+        r#"
+        query($always: Boolean) {
+          user(id: "1") { isLoggedIn @client(always: $always) }
+          feed { title }
+        }
+        "#,
+    );
+    let stripped = strip_selections_with_directives(&document, &["client"]);
+    expect![[r#"
+        {
+          feed {
+            title
+          }
+        }
+    "#]]
+    .assert_eq(&stripped.document.to_string());
+    assert_eq!(stripped.variables.len(), 1);
+    assert_eq!(stripped.variables[0].path.to_string(), "query");
+}
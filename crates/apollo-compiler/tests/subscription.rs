@@ -0,0 +1,125 @@
+use apollo_compiler::execution::check_subscription_no_introspection_root;
+use apollo_compiler::execution::check_subscription_single_root_field;
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::subscription_root_field;
+use apollo_compiler::execution::SubscriptionRootFieldError;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+type Query { unused: Int }
+type Subscription { reviews(product: ID!, limit: Int = 10): String, ratings: Int }
+"#;
+
+fn schema() -> Valid<Schema> {
+    Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap()
+}
+
+#[test]
+fn standalone_checks() {
+    let schema = schema();
+    let document = ExecutableDocument::parse(
+        &schema,
+        r#"
+        subscription Two { reviews(product: 1) ...F }
+        subscription Introspection { __typename }
+        subscription Merged { reviews(product: 1) ... { reviews(product: 1) } }
+        query Query { __typename unused }
+        fragment F on Subscription { ratings }
+        "#,
+        "subscription.graphql",
+    )
+    .unwrap();
+    let op = |name| document.operations.get(Some(name)).unwrap();
+
+    let error = check_subscription_single_root_field(&document, op("Two")).unwrap_err();
+    let SubscriptionRootFieldError::MultipleRootFields { fields, .. } = &error else {
+        panic!("unexpected error {error:?}")
+    };
+    assert_eq!(fields, &["reviews", "ratings"]);
+    assert_eq!(
+        error.to_string(),
+        "Subscription `Two` must select only one top level field"
+    );
+    check_subscription_no_introspection_root(&document, op("Two")).unwrap();
+
+    check_subscription_single_root_field(&document, op("Introspection")).unwrap();
+    let error = check_subscription_no_introspection_root(&document, op("Introspection"));
+    assert!(matches!(
+        error,
+        Err(SubscriptionRootFieldError::IntrospectionRootField { field, .. }) if field == "__typename"
+    ));
+
+    check_subscription_single_root_field(&document, op("Merged")).unwrap();
+    check_subscription_single_root_field(&document, op("Query")).unwrap();
+    check_subscription_no_introspection_root(&document, op("Query")).unwrap();
+}
+
+#[test]
+fn root_field_with_skip_and_include() {
+    let schema = schema();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        subscription($id: ID!, $skip: Boolean!) {
+          reviews(product: $id, limit: 5) @skip(if: $skip)
+          ... @include(if: $skip) { reviews(product: $id, limit: 5) }
+        }
+        "#,
+        "subscription.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = |json| {
+        coerce_variable_values(&schema, operation, &serde_json::from_str(json).unwrap()).unwrap()
+    };
+
+    let vars = variables(r#"{ "id": "1", "skip": false }"#);
+    let root = subscription_root_field(&schema, &document, operation, &vars).unwrap();
+    assert_eq!(root.response_key, "reviews");
+    assert_eq!(root.fields.len(), 1);
+    assert_eq!(
+        serde_json::to_string(&root.arguments).unwrap(),
+        r#"{"product":"1","limit":5}"#
+    );
+
+    // The inline fragment's selection is the root field this time
+    let vars = variables(r#"{ "id": "2", "skip": true }"#);
+    let root = subscription_root_field(&schema, &document, operation, &vars).unwrap();
+    assert_eq!(root.fields.len(), 1);
+    let first = operation.selection_set.fields().next().unwrap();
+    assert_ne!(root.field.location(), first.location());
+}
+
+#[test]
+fn execution_time_guard() {
+    let schema = schema();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        subscription Skipped($skip: Boolean!) { reviews(product: 1) @skip(if: $skip) }
+        query Query { unused }
+        "#,
+        "subscription.graphql",
+    )
+    .unwrap();
+
+    let operation = document.operations.get(Some("Skipped")).unwrap();
+    let vars = serde_json::from_str(r#"{ "skip": true }"#).unwrap();
+    let vars = coerce_variable_values(&schema, operation, &vars).unwrap();
+    let error = subscription_root_field(&schema, &document, operation, &vars).unwrap_err();
+    let response = error.into_response(&document.sources);
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"errors":[{"message":"Subscription `Skipped` does not select any top level field","locations":[{"line":2,"column":9}]}]}"#
+    );
+
+    let operation = document.operations.get(Some("Query")).unwrap();
+    let vars = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let error = subscription_root_field(&schema, &document, operation, &vars).unwrap_err();
+    assert!(matches!(
+        error,
+        SubscriptionRootFieldError::NotASubscription { .. }
+    ));
+}
@@ -0,0 +1,112 @@
+use apollo_compiler::schema::sunset::Date;
+use apollo_compiler::schema::sunset::Sunset;
+use apollo_compiler::schema::sunset::SunsetProblemKind;
+use apollo_compiler::schema::sunset::SunsetReport;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    directive @sunset(date: String!) on FIELD_DEFINITION | ARGUMENT_DEFINITION
+        | INPUT_FIELD_DEFINITION | ENUM_VALUE | OBJECT
+
+    type Query {
+        user(id: ID!, legacyId: Int @deprecated @sunset(date: "2024-04-01")): User
+        users: [User] @deprecated(reason: "Use `search`") @sunset(date: "2024-06-30")
+        search(filter: Filter): [User]
+        me: User @deprecated(reason: "Use `viewer`") @sunset(date: "2024-01-31")
+        viewer: User
+    }
+
+    type User {
+        id: ID!
+        name: String @sunset(date: "2024-03-20")
+        status: Status
+    }
+
+    type Legacy @sunset(date: "2024-13-01") {
+        id: ID!
+    }
+
+    enum Status {
+        ACTIVE
+        DISABLED @deprecated(reason: "Use `SUSPENDED`") @sunset(date: "2024-03-15")
+        SUSPENDED
+    }
+
+    input Filter {
+        name: String
+        nickname: String @deprecated @sunset(date: "2025-01-01")
+    }
+"#;
+
+#[test]
+fn report() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let today = Date::new(2024, 3, 15).unwrap();
+    let report = SunsetReport::new(&schema, today, 30);
+    expect![[r#"
+        Query.me: sunset 2024-01-31, 44 days ago (deprecated: Use `viewer`)
+        Status.DISABLED: sunset 2024-03-15, today (deprecated: Use `SUSPENDED`)
+        Query.user(legacyId:): sunset 2024-04-01, in 17 days (deprecated: No longer supported)
+        User.name: has a sunset date but is not deprecated
+        Legacy: argument `date` of directive `@sunset` must be a date string in YYYY-MM-DD format
+    "#]]
+    .assert_eq(&report.to_string());
+
+    let past: Vec<_> = report.past().map(|e| e.coordinate.to_string()).collect();
+    assert_eq!(past, ["Query.me"]);
+    assert_eq!(report.approaching().count(), 2);
+    assert_eq!(report.problems[0].kind, SunsetProblemKind::NotDeprecated);
+    assert!(report.problems[0].location.is_some());
+
+    // A longer window reaches more entries
+    let report = SunsetReport::new(&schema, today, 365);
+    let coordinates: Vec<_> = report
+        .entries
+        .iter()
+        .map(|e| e.coordinate.to_string())
+        .collect();
+    assert_eq!(
+        coordinates,
+        [
+            "Query.me",
+            "Status.DISABLED",
+            "Query.user(legacyId:)",
+            "Query.users",
+            "Filter.nickname"
+        ]
+    );
+}
+
+#[test]
+fn typed_extraction() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let sunset = schema
+        .extract_directive::<Sunset>(&"Query.users".parse().unwrap())
+        .unwrap()
+        .unwrap();
+    assert_eq!(sunset.date.to_string(), "2024-06-30");
+    let none = schema
+        .extract_directive::<Sunset>(&"Query.viewer".parse().unwrap())
+        .unwrap();
+    assert_eq!(none, None);
+}
+
+#[test]
+fn dates() {
+    let date = |s: &str| s.parse::<Date>().map(|d| d.to_string());
+    assert_eq!(date("2024-02-29").unwrap(), "2024-02-29");
+    expect!["invalid date `2023-02-29`, expected YYYY-MM-DD"]
+        .assert_eq(&date("2023-02-29").unwrap_err().to_string());
+    assert!(date("2024-1-01").is_err());
+    assert!(date("2024-00-01").is_err());
+    assert!(date("+024-01-01").is_err());
+    assert!(date("2024-01-0é").is_err());
+
+    let days = |a: &str, b: &str| a.parse::<Date>().unwrap().days_until(b.parse().unwrap());
+    assert_eq!(days("1970-01-01", "1970-01-02"), 1);
+    assert_eq!(days("2024-03-01", "2024-02-28"), -2);
+    assert_eq!(days("2023-12-31", "2025-01-01"), 367);
+    assert_eq!(days("1999-12-31", "2000-03-01"), 61);
+    assert_eq!(days("1900-02-28", "1900-03-01"), 1);
+}
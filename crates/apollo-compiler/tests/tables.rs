@@ -0,0 +1,160 @@
+use apollo_compiler::executable::ExecutableTables;
+use apollo_compiler::executable::OperationType;
+use apollo_compiler::schema::SchemaTables;
+use apollo_compiler::schema::TypeKind;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+use std::fmt::Write;
+
+const SCHEMA: &str = r#"
+type Query {
+  node(id: ID!): Node
+  users(first: Int, filter: UserFilter): [User!]!
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name(format: String): String
+}
+
+union Result = User
+
+input UserFilter {
+  name: String
+}
+
+enum Role { ADMIN }
+"#;
+
+#[test]
+fn schema_tables() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let tables = SchemaTables::new(&schema);
+    assert_eq!(tables.type_names().len(), schema.types.len());
+    assert_eq!(tables.type_field_offsets().len(), schema.types.len() + 1);
+    assert_eq!(
+        tables.field_argument_offsets().len(),
+        tables.fields().len() + 1
+    );
+
+    let mut out = String::new();
+    for ty in tables.types() {
+        if ty.name().starts_with("__") || ty.kind() == TypeKind::Scalar {
+            continue;
+        }
+        writeln!(out, "{:?} {}", ty.kind(), ty.name()).unwrap();
+        for field in ty.fields() {
+            let named_type = field.named_type().unwrap();
+            assert_eq!(field.parent().name(), ty.name());
+            writeln!(
+                out,
+                "  {}: {} ({:?})",
+                field.name(),
+                field.ty(),
+                named_type.kind()
+            )
+            .unwrap();
+            for argument in field.arguments() {
+                assert_eq!(argument.parent().index(), field.index());
+                writeln!(
+                    out,
+                    "    ({}: {} -> {})",
+                    argument.name(),
+                    argument.ty(),
+                    argument.named_type().unwrap().name()
+                )
+                .unwrap();
+            }
+        }
+    }
+    expect![[r#"
+        Object Query
+          node: Node (Interface)
+            (id: ID! -> ID)
+          users: [User!]! (Object)
+            (first: Int -> Int)
+            (filter: UserFilter -> UserFilter)
+        Interface Node
+          id: ID! (Scalar)
+        Object User
+          id: ID! (Scalar)
+          name: String (Scalar)
+            (format: String -> String)
+        Union Result
+        InputObject UserFilter
+          name: String (Scalar)
+        Enum Role
+    "#]]
+    .assert_eq(&out);
+
+    let filter_name = tables.field_by_name("UserFilter", "name").unwrap();
+    assert_eq!(filter_name.arguments().len(), 0);
+    assert!(tables.field_by_name("Role", "ADMIN").is_none());
+    assert!(tables.type_by_name("Missing").is_none());
+
+    // Columns agree with views
+    for field in tables.fields() {
+        let parent = tables.field_parents()[field.index()] as usize;
+        let offsets = tables.type_field_offsets();
+        assert!((offsets[parent] as usize..offsets[parent + 1] as usize).contains(&field.index()));
+        assert_eq!(&tables.field_names()[field.index()], field.name());
+    }
+}
+
+#[test]
+fn executable_tables() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        query Users {
+          users(first: 2) { ...UserFields ... on User { name } }
+          __typename
+        }
+        query Node {
+          node(id: 1) { id ... on User { alias: name } }
+        }
+        fragment UserFields on User { id }
+        "#,
+        "query.graphql",
+    )
+    .unwrap();
+    let schema_tables = SchemaTables::new(&schema);
+    let tables = ExecutableTables::new(&document, &schema_tables);
+    assert_eq!(
+        tables.definition_operation_types(),
+        [Some(OperationType::Query), Some(OperationType::Query), None]
+    );
+
+    let mut out = String::new();
+    for selection in tables.selections() {
+        let definition = selection.definition_name().unwrap();
+        let parent = selection
+            .parent()
+            .map_or("-".to_owned(), |parent| parent.response_key().to_string());
+        let field = selection.schema_field().map_or("-".to_owned(), |field| {
+            format!("{}.{}", field.parent().name(), field.name())
+        });
+        writeln!(
+            out,
+            "{} {definition} {parent} {field}",
+            selection.response_key()
+        )
+        .unwrap();
+    }
+    expect![[r#"
+        users Users - Query.users
+        name Users users User.name
+        __typename Users - -
+        node Node - Query.node
+        id Node node Node.id
+        alias Node node User.name
+        id UserFields - User.id
+    "#]]
+    .assert_eq(&out);
+}
@@ -0,0 +1,219 @@
+use apollo_compiler::typescript::EnumStyle;
+use apollo_compiler::typescript::TypeScriptExporter;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    "An ISO 8601 date and time"
+    scalar DateTime
+
+    enum Role {
+        ADMIN
+        "A regular user"
+        USER
+        GUEST @deprecated(reason: "Use `USER`")
+    }
+
+    type Query {
+        node(id: ID!): Node
+        search(text: String!, limit: Int = 10): [Result!]!
+    }
+
+    interface Node {
+        id: ID!
+    }
+
+    """
+    Someone who can log in.
+    Might be a bot.
+    """
+    type User implements Node {
+        id: ID!
+        name: String!
+        role: Role
+        createdAt: DateTime
+        friends(first: Int): [User]
+        legacyName: String @deprecated
+    }
+
+    type Post implements Node {
+        id: ID!
+        title: String
+        author: User!
+    }
+
+    union Result = User | Post
+
+    input UserFilter {
+        role: Role
+        ids: [ID!]!
+        limit: Int! = 10
+    }
+"#;
+
+#[test]
+fn schema_types() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let exporter = TypeScriptExporter::new().scalar("DateTime", "string");
+    expect![[r#"
+        /**
+         * An ISO 8601 date and time
+         */
+        export type DateTime = string;
+
+        export type Role = "ADMIN" | "USER" | "GUEST";
+
+        export interface Query {
+          __typename?: "Query";
+          node: Node | null;
+          search: Array<Result>;
+        }
+
+        export interface QueryNodeArgs {
+          id: string;
+        }
+
+        export interface QuerySearchArgs {
+          text: string;
+          limit?: number | null;
+        }
+
+        export interface Node {
+          id: string;
+        }
+
+        /**
+         * Someone who can log in.
+         * Might be a bot.
+         */
+        export interface User {
+          __typename?: "User";
+          id: string;
+          name: string;
+          role: Role | null;
+          createdAt: string | null;
+          friends: Array<User | null> | null;
+          /**
+           * @deprecated No longer supported
+           */
+          legacyName: string | null;
+        }
+
+        export interface UserFriendsArgs {
+          first?: number | null;
+        }
+
+        export interface Post {
+          __typename?: "Post";
+          id: string;
+          title: string | null;
+          author: User;
+        }
+
+        export type Result = User | Post;
+
+        export interface UserFilter {
+          role?: Role | null;
+          ids: Array<string>;
+          limit?: number;
+        }
+    "#]]
+    .assert_eq(&exporter.schema_types(&schema));
+
+    let exporter = exporter.enum_style(EnumStyle::Enum);
+    let schema_types = exporter.schema_types(&schema);
+    let start = schema_types.find("export enum Role").unwrap();
+    let end = start + schema_types[start..].find("}\n").unwrap() + 2;
+    expect![[r#"
+        export enum Role {
+          ADMIN = "ADMIN",
+          /**
+           * A regular user
+           */
+          USER = "USER",
+          /**
+           * @deprecated Use `USER`
+           */
+          GUEST = "GUEST",
+        }
+    "#]]
+    .assert_eq(&schema_types[start..end]);
+}
+
+#[test]
+fn operation_types() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+            query Search($text: String!, $limit: Int, $withFriends: Boolean = false) {
+                search(text: $text, limit: $limit) {
+                    __typename
+                    ... on Node { id }
+                    ... on User {
+                        name
+                        friends @include(if: $withFriends) { ...UserFields }
+                    }
+                    ... on Post { title author { ...UserFields } }
+                }
+            }
+
+            query Node {
+                node(id: "1") { id }
+                __type(name: "User") { kind }
+            }
+
+            fragment UserFields on User {
+                name
+                role
+            }
+        "#,
+        "query.graphql",
+    )
+    .unwrap();
+    let exporter = TypeScriptExporter::new();
+    expect![[r#"
+        export type SearchQuery = {
+          search: Array<{
+            __typename: "User";
+            id: string;
+            name: string;
+            friends?: Array<{
+              name: string;
+              role: Role | null;
+            } | null> | null;
+          } | {
+            __typename: "Post";
+            id: string;
+            title: string | null;
+            author: {
+              name: string;
+              role: Role | null;
+            };
+          }>;
+        };
+
+        export type SearchQueryVariables = {
+          text: string;
+          limit?: number | null;
+          withFriends?: boolean | null;
+        };
+
+        export type NodeQuery = {
+          node: {
+            id: string;
+          } | null;
+          __type: {
+            kind: "SCALAR" | "OBJECT" | "INTERFACE" | "UNION" | "ENUM" | "INPUT_OBJECT" | "LIST" | "NON_NULL";
+          } | null;
+        };
+
+        export type NodeQueryVariables = {};
+
+        export type UserFieldsFragment = {
+          name: string;
+          role: Role | null;
+        };
+    "#]].assert_eq(&exporter.operation_types(&schema, &document));
+}
@@ -0,0 +1,116 @@
+use apollo_compiler::ast;
+use apollo_compiler::validation::check_operations;
+use apollo_compiler::validation::BreakingReason;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const OLD_SCHEMA: &str = r#"
+    type Query {
+        user(id: ID): User
+        users(role: Role): [User]
+        ids: [ID]
+    }
+    type User {
+        name: String
+        email: String
+        role: Role
+    }
+    enum Role { ADMIN GUEST }
+"#;
+
+const NEW_SCHEMA: &str = r#"
+    type Query {
+        user(id: ID!): User
+        users(role: Role, first: Int!): [User!]!
+        ids: [Int]
+    }
+    type User {
+        name: String!
+        role: Role
+    }
+    enum Role { ADMIN }
+"#;
+
+fn check(documents: &[&str]) -> String {
+    let old_schema = Schema::parse_and_validate(OLD_SCHEMA, "old.graphql").unwrap();
+    let new_schema = Schema::parse_and_validate(NEW_SCHEMA, "new.graphql").unwrap();
+    let documents: Vec<_> = documents
+        .iter()
+        .enumerate()
+        .map(|(i, source)| ast::Document::parse(*source, format!("{i}.graphql")).unwrap())
+        .collect();
+    let mut out = String::new();
+    for broken in check_operations(&old_schema, &new_schema, &documents) {
+        let name = broken
+            .operation_name
+            .as_ref()
+            .map_or("(anonymous)", |n| n.as_str());
+        out.push_str(&format!("{}: {name}\n", broken.document_index));
+        let sources = &documents[broken.document_index].sources;
+        for reason in &broken.reasons {
+            match reason {
+                BreakingReason::ValidationError { message, location } => out.push_str(&format!(
+                    "  {:?} {message}\n",
+                    location.unwrap().line_column(sources).unwrap()
+                )),
+                BreakingReason::FieldTypeChanged {
+                    coordinate,
+                    old_type,
+                    new_type,
+                    location,
+                } => out.push_str(&format!(
+                    "  {:?} `{coordinate}` changed from `{old_type}` to `{new_type}`\n",
+                    location.unwrap().line_column(sources).unwrap()
+                )),
+                _ => unreachable!(),
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn compatible_changes() {
+    // Making an output type non-null is fine
+    let report = check(&["{ user(id: 1) { name role } }"]);
+    expect![""].assert_eq(&report);
+}
+
+#[test]
+fn breaking_changes() {
+    let report = check(&[
+        "query Fine { user(id: 1) { name } }",
+        "{ user { name } }",
+        "query Args { users(role: GUEST) { name } }",
+        "query Ids { ids }",
+        "query A { user(id: 1) { ...F } } query B { user(id: 2) { name } } query C { user(id: 3) { ...F } }
+         fragment F on User { name email }",
+    ]);
+    expect![[r#"
+        1: (anonymous)
+          1:3 the required argument `Query.user(id:)` is not provided
+        2: Args
+          1:14 the required argument `Query.users(first:)` is not provided
+          1:26 value `GUEST` does not exist on `Role`
+        3: Ids
+          1:13 `Query.ids` changed from `[ID]` to `[Int]`
+        4: A
+          2:36 type `User` does not have a field `email`
+        4: C
+          2:36 type `User` does not have a field `email`
+    "#]]
+    .assert_eq(&report);
+}
+
+#[test]
+fn already_invalid_operations_are_skipped() {
+    let report = check(&[
+        "query Invalid { user(id: 1) { nope } } query Broken { user { name } }",
+        "query Invalid($x: Int) { ids }",
+    ]);
+    expect![[r#"
+        0: Broken
+          1:55 the required argument `Query.user(id:)` is not provided
+    "#]]
+    .assert_eq(&report);
+}
@@ -0,0 +1,126 @@
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+#[test]
+fn collisions_across_files_are_one_group() {
+    let errors = Schema::builder()
+        .parse("type Query { a: Int }\nscalar Date", "a.graphql")
+        .parse("\n\ntype Query { b: Int }", "b.graphql")
+        .parse("type Query { c: Int }\nscalar Date", "c.graphql")
+        .build()
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 3);
+    let groups = errors.grouped();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].diagnostics().len(), 2);
+    assert_eq!(groups[1].diagnostics().len(), 1);
+    assert_eq!(
+        groups[0].unstable_error_name(),
+        errors.iter().next().unwrap().error.unstable_error_name()
+    );
+    let json = serde_json::to_string_pretty(&errors.to_grouped_graphql_errors()).unwrap();
+    expect![[r#"
+        [
+          {
+            "message": "the type `Query` is defined multiple times in the schema",
+            "locations": [
+              {
+                "line": 3,
+                "column": 6
+              },
+              {
+                "line": 1,
+                "column": 6
+              },
+              {
+                "line": 1,
+                "column": 6
+              }
+            ]
+          },
+          {
+            "message": "the type `Date` is defined multiple times in the schema",
+            "locations": [
+              {
+                "line": 2,
+                "column": 8
+              },
+              {
+                "line": 2,
+                "column": 8
+              }
+            ]
+          }
+        ]"#]]
+    .assert_eq(&json);
+}
+
+#[test]
+fn repeated_directive_is_one_group() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @once on FIELD
+        type Query { a: Int b: Int }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let errors = ExecutableDocument::parse_and_validate(
+        &schema,
+        "{ a @once @once @once b @once @once }",
+        "query.graphql",
+    )
+    .unwrap_err()
+    .errors;
+    assert_eq!(errors.len(), 3);
+    let groups = errors.grouped();
+    let locations = groups
+        .iter()
+        .map(|group| {
+            group
+                .locations()
+                .iter()
+                .map(|location| location.offset())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(locations, [vec![10, 4, 16], vec![30, 24]]);
+    expect![[r#"
+        Error: non-repeatable directive once can only be used once per location
+           ╭─[query.graphql:1:11]
+           │
+         1 │ { a @once @once @once b @once @once }
+           │     ──┬── ──┬──  
+           │       ╰────────── directive `@once` first called here
+           │             │    
+           │             ╰──── directive `@once` called again here
+        ───╯
+        Error: non-repeatable directive once can only be used once per location
+           ╭─[query.graphql:1:17]
+           │
+         1 │ { a @once @once @once b @once @once }
+           │     ──┬──       ──┬──  
+           │       ╰──────────────── directive `@once` first called here
+           │                   │    
+           │                   ╰──── directive `@once` called again here
+        ───╯
+    "#]]
+    .assert_eq(&groups[0].to_string());
+}
+
+#[test]
+fn unrelated_diagnostics_are_not_grouped() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let errors = ExecutableDocument::parse_and_validate(&schema, "{ a x y }", "query.graphql")
+        .unwrap_err()
+        .errors;
+    let groups = errors.grouped();
+    assert_eq!(groups.len(), errors.len());
+    for (group, diagnostic) in groups.iter().zip(errors.iter()) {
+        assert_eq!(group.diagnostics().len(), 1);
+        assert_eq!(group.locations(), diagnostic.locations());
+        assert_eq!(group.to_json().message, diagnostic.to_json().message);
+    }
+}
@@ -0,0 +1,93 @@
+use apollo_compiler::name;
+use apollo_compiler::validation::DefinitionName;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+fn summary(missing: &[apollo_compiler::validation::MissingComponent]) -> Vec<String> {
+    missing
+        .iter()
+        .map(|component| {
+            let status = if component.dropped {
+                "dropped"
+            } else {
+                "incomplete"
+            };
+            format!(
+                "{} {status} ({} errors)",
+                component.definition,
+                component.locations.len()
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn schema_missing_components() {
+    let input = r#"
+        schema { query: Query }
+        schema { query: Query }
+        type Query { a: Int a: Int b: Int b: Int }
+        type Query { c: Int }
+        extend interface Query { d: Int }
+        extend type Orphan { e: Int }
+        directive @dir on FIELD
+        directive @dir on FIELD
+        enum Ok { A B }
+    "#;
+    let with_errors = Schema::parse(input, "schema.graphql").unwrap_err();
+    assert_eq!(
+        summary(&with_errors.missing_components()),
+        [
+            "schema definition incomplete (1 errors)",
+            "type `Query` incomplete (4 errors)",
+            "type `Orphan` dropped (1 errors)",
+            "directive `@dir` incomplete (1 errors)",
+        ]
+    );
+    assert!(with_errors.partial.types.contains_key("Ok"));
+}
+
+#[test]
+fn executable_missing_components() {
+    let schema = Schema::parse_and_validate(
+        "type Query { a: Int b: B } type B { c: Int }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query Complete { a }
+        query Undefined { a b { d } }
+        subscription NoRoot { a }
+        fragment F on Nope { a }
+        fragment G on Query { a ... on Nope { a } }
+        fragment G on Query { b { c } }
+    "#;
+    let with_errors = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap_err();
+    let missing = with_errors.missing_components();
+    assert_eq!(
+        summary(&missing),
+        [
+            "operation `Undefined` incomplete (1 errors)",
+            "operation `NoRoot` dropped (1 errors)",
+            "fragment `F` dropped (1 errors)",
+            "fragment `G` incomplete (2 errors)",
+        ]
+    );
+    assert!(!missing
+        .iter()
+        .any(|c| c.definition == DefinitionName::Operation(Some(name!("Complete")))));
+}
+
+#[test]
+fn syntax_and_validation_errors_are_not_missing_components() {
+    let with_errors = Schema::parse("type Query { a: Int", "schema.graphql").unwrap_err();
+    assert!(!with_errors.errors.is_empty());
+    assert!(with_errors.missing_components().is_empty());
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let with_errors =
+        ExecutableDocument::parse_and_validate(&schema, "query($v: Int) { a }", "query.graphql")
+            .unwrap_err();
+    assert!(!with_errors.errors.is_empty());
+    assert!(with_errors.missing_components().is_empty());
+}
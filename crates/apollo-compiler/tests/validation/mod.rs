@@ -1,9 +1,16 @@
+mod compatibility;
 mod field_merging;
+mod grouped;
 mod interface;
+mod missing;
+mod multi_schema;
 mod object;
 mod operation;
 mod recursion;
+mod rules;
 mod types;
+mod unknown_directives;
+mod validator;
 mod variable;
 
 use apollo_compiler::ast;
@@ -301,6 +308,57 @@ fn validate_variable_usage_without_type_system() {
     doc.validate_standalone_executable().unwrap()
 }
 
+#[test]
+fn standalone_schema_independent_rules() {
+    let input = r#"
+        subscription Sub { a b }
+        query Q($unused: Int) {
+          x: a
+          x: b
+          c(arg: {y: 1, y: 2}, v: $undefined)
+          d @skip(if: true) @skip(if: false)
+          e @include
+          f @custom(arg: $alsoUndefined) @custom
+        }
+    "#;
+    let doc = ast::Document::parse(input, "query.graphql").unwrap();
+    let errors = doc.validate_standalone_executable().unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    expect_test::expect![[r#"
+        [
+            "subscription `Sub` can only have one root field",
+            "unused variable: `$unused`",
+            "cannot select different fields into the same alias `x`",
+            "the input object field `y` is provided multiple times",
+            "variable `$undefined` is not defined",
+            "non-repeatable directive skip can only be used once per location",
+            "the required argument `@include(if:)` is not provided",
+            "variable `$alsoUndefined` is not defined",
+        ]
+    "#]]
+    .assert_debug_eq(&messages);
+
+    // Valid documents with unknown types and directives
+    let input = r#"
+        subscription Sub($if: Boolean!) { a ... on Subscription { a @include(if: $if) } }
+        query Q($v: CustomInput) {
+          x: a { ... on A { y: b } ... on B { y: c } }
+          ... on T { x: b }
+          d @custom(arg: $v) @custom
+        }
+    "#;
+    let doc = ast::Document::parse(input, "query.graphql").unwrap();
+    doc.validate_standalone_executable().unwrap();
+
+    // Also available for documents built from a schema or programmatically
+    let schema =
+        Schema::parse_and_validate("type Query { a(b: Int): Int }", "schema.graphql").unwrap();
+    let doc =
+        ExecutableDocument::parse(&schema, "{ x: a(b: 1) x: a(b: 2) }", "query.graphql").unwrap();
+    let errors = doc.validate_standalone().unwrap_err();
+    assert_eq!(errors.len(), 1, "{errors}");
+}
+
 #[test]
 fn json_location_with_multibyte() {
     let input_type_system = r#"
@@ -351,3 +409,156 @@ type TestObject {
         }"#]];
     expected.assert_eq(&actual);
 }
+
+#[test]
+fn invalid_fragment_spread_possible_types() {
+    let schema = r#"
+        type Query { pet: Pet, dog: Dog }
+        interface Pet { name: String }
+        interface Animal { name: String }
+        type Dog implements Pet & Animal { name: String }
+        type Cat implements Pet & Animal { name: String }
+        type Lion implements Animal { name: String }
+        type Tiger implements Animal { name: String }
+        union BigCat = Lion | Tiger
+    "#;
+    let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+    let query = r#"
+        query { pet { ...bigCat } dog { ... on BigCat { __typename } ... on Animal { name } } }
+        fragment bigCat on BigCat { __typename }
+    "#;
+    let errors = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql")
+        .unwrap_err()
+        .errors;
+    let types: Vec<_> = errors
+        .iter()
+        .filter_map(|diagnostic| diagnostic.error.fragment_spread_types())
+        .map(|types| {
+            format!(
+                "{:?} {} {:?} / {} {:?}",
+                types.fragment_name.map(|name| name.as_str()),
+                types.parent_type,
+                types
+                    .parent_possible_types
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>(),
+                types.type_condition,
+                types
+                    .condition_possible_types
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    expect_test::expect![[r#"
+        [
+            "Some(\"bigCat\") Pet [\"Dog\", \"Cat\"] / BigCat [\"Lion\", \"Tiger\"]",
+            "None Dog [\"Dog\"] / BigCat [\"Lion\", \"Tiger\"]",
+        ]
+    "#]]
+    .assert_debug_eq(&types);
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn typed_diagnostic_accessors() {
+    let schema = r#"
+        type Query { user(id: ID!): User, search(role: Role, filter: Filter): [User] }
+        type User { name: String }
+        enum Role { ADMIN USER }
+        input Filter { name: String! }
+    "#;
+    let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+    let query = r#"
+        query GetUser($id: ID, $unused: Int) {
+          user(id: $id, extra: 1) { name avatar }
+          search(role: GUEST, filter: { size: 1 }) { name @unknown }
+          ...missing
+        }
+    "#;
+    let errors = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql")
+        .unwrap_err()
+        .errors;
+    let undefined_field = errors
+        .iter()
+        .find_map(|d| d.error.as_undefined_field())
+        .unwrap();
+    assert_eq!(undefined_field.type_name, "User");
+    assert_eq!(undefined_field.field_name, "avatar");
+    assert_eq!(
+        undefined_field.path.to_string(),
+        "query GetUser → user → avatar"
+    );
+
+    let argument = errors
+        .iter()
+        .find_map(|d| d.error.as_undefined_argument())
+        .unwrap();
+    assert_eq!(argument.name, "extra");
+    assert_eq!(argument.coordinate.to_string(), "Query.user");
+
+    let usage = errors
+        .iter()
+        .find_map(|d| d.error.as_disallowed_variable_usage())
+        .unwrap();
+    assert_eq!(usage.variable, "id");
+    assert_eq!(usage.variable_type.to_string(), "ID");
+    assert_eq!(usage.argument_type.to_string(), "ID!");
+
+    let enum_value = errors
+        .iter()
+        .find_map(|d| d.error.as_undefined_enum_value())
+        .unwrap();
+    assert_eq!(
+        (enum_value.name.as_str(), enum_value.type_name.as_str()),
+        ("GUEST", "Role")
+    );
+
+    let input_field = errors
+        .iter()
+        .find_map(|d| d.error.as_undefined_input_field())
+        .unwrap();
+    assert_eq!(
+        (input_field.name.as_str(), input_field.type_name.as_str()),
+        ("size", "Filter")
+    );
+
+    let required = errors
+        .iter()
+        .find_map(|d| d.error.as_required_input_field())
+        .unwrap();
+    assert_eq!(required.name, "name");
+    assert_eq!(required.coordinate.to_string(), "Filter.name");
+    assert_eq!(required.expected_type.to_string(), "String!");
+
+    let unused: Vec<_> = errors
+        .iter()
+        .filter_map(|d| d.error.as_unused_variable())
+        .collect();
+    assert_eq!(unused, ["unused"]);
+    assert_eq!(
+        errors
+            .iter()
+            .find_map(|d| d.error.as_undefined_directive())
+            .unwrap(),
+        "unknown"
+    );
+    assert_eq!(
+        errors
+            .iter()
+            .find_map(|d| d.error.as_undefined_fragment())
+            .unwrap(),
+        "missing"
+    );
+    assert!(errors.iter().all(|d| d.error.as_syntax_error().is_none()));
+
+    let errors = ExecutableDocument::parse_and_validate(&schema, "{ user(", "query.graphql")
+        .unwrap_err()
+        .errors;
+    assert!(errors.iter().any(|d| d
+        .error
+        .as_syntax_error()
+        .is_some_and(|m| m.contains("expected"))));
+}
@@ -0,0 +1,65 @@
+use apollo_compiler::ast;
+use apollo_compiler::validation::validate_against_schemas;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+#[test]
+fn report_distinguishes_schemas() {
+    let prod = Schema::parse_and_validate(
+        "type Query { user(id: ID): User } type User { name: String }",
+        "prod.graphql",
+    )
+    .unwrap();
+    let staging = Schema::parse_and_validate(
+        "type Query { user(id: ID!): User } type User { name: String email: String }",
+        "staging.graphql",
+    )
+    .unwrap();
+    let documents = [
+        ast::Document::parse("query A { user(id: 1) { name } }", "a.graphql").unwrap(),
+        ast::Document::parse("query B { user { email } }", "b.graphql").unwrap(),
+    ];
+    let report = validate_against_schemas([("prod", &prod), ("staging", &staging)], &documents);
+    assert_eq!(report.schema_names, ["prod", "staging"]);
+    assert!(!report.is_valid());
+    assert!(!report.is_valid_against("prod"));
+    assert!(!report.is_valid_against("staging"));
+    assert_eq!(report.invalid_schemas_for(0).count(), 0);
+    assert_eq!(
+        report.invalid_schemas_for(1).collect::<Vec<_>>(),
+        ["prod", "staging"]
+    );
+    expect![[r#"
+        [prod] document 1:
+        Error: interface, union and object types must have a subselection set
+           ╭─[b.graphql:1:11]
+           │
+         1 │ query B { user { email } }
+           │           ───────┬──────  
+           │                  ╰──────── `Query.user` is an object type `User` and must select fields
+        ───╯
+        Error: type `User` does not have a field `email`
+           ╭─[b.graphql:1:18]
+           │
+         1 │ query B { user { email } }
+           │                  ──┬──  
+           │                    ╰──── field `email` selected here
+           │ 
+           │ Note: path to the field: `query B → user → email`
+        ───╯
+        [staging] document 1:
+        Error: the required argument `Query.user(id:)` is not provided
+           ╭─[b.graphql:1:11]
+           │
+         1 │ query B { user { email } }
+           │           ───────┬──────  
+           │                  ╰──────── missing value for argument `id`
+        ───╯
+    "#]]
+    .assert_eq(&report.to_string());
+
+    let report =
+        validate_against_schemas([("prod", &prod), ("staging", &staging)], &documents[..1]);
+    assert!(report.is_valid());
+    assert_eq!(report.to_string(), "");
+}
@@ -0,0 +1,134 @@
+use apollo_compiler::validation::ExecutableElement;
+use apollo_compiler::validation::ExecutableValidationOptions;
+use apollo_compiler::validation::ValidationRule;
+use apollo_compiler::validation::ValidationRuleContext;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+const SCHEMA: &str = r#"
+    type Query {
+        users(first: Int): [User]
+        viewer: User
+    }
+    type User {
+        name: String
+        friends(first: Int): [User]
+    }
+    directive @deprecatedClient on QUERY | FIELD
+"#;
+
+/// Every list field must specify a `first` argument
+struct ListFieldsNeedFirst;
+
+impl ValidationRule for ListFieldsNeedFirst {
+    fn code(&self) -> &str {
+        "list-fields-need-first"
+    }
+
+    fn check(&self, cx: &mut ValidationRuleContext<'_>, element: ExecutableElement<'_>) {
+        if let ExecutableElement::Field(field) = element {
+            if field.ty().is_list() && field.specified_argument_by_name("first").is_none() {
+                cx.report(format!(
+                    "list field `{}` in `{}` must specify `first`",
+                    field.name,
+                    cx.path()
+                ))
+            }
+        }
+    }
+}
+
+fn errors(source: &str, options: &ExecutableValidationOptions) -> String {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let doc = ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+    match doc.validate_with_options(&schema, options) {
+        Ok(_) => String::new(),
+        Err(with_errors) => with_errors.errors.to_string(),
+    }
+}
+
+#[test]
+fn reports_with_spans() {
+    let options = ExecutableValidationOptions::new().rule(ListFieldsNeedFirst);
+    let source = r#"
+query Users {
+  users(first: 10) { ...UserFriends }
+  viewer { friends { name } }
+}
+fragment UserFriends on User { friends { name } }
+"#;
+    expect![[r#"
+        Error: list field `friends` in `query Users → viewer` must specify `first`
+           ╭─[query.graphql:4:12]
+           │
+         4 │   viewer { friends { name } }
+           │            ────────┬───────  
+           │                    ╰───────── reported by rule `list-fields-need-first`
+        ───╯
+        Error: list field `friends` in `fragment UserFriends` must specify `first`
+           ╭─[query.graphql:6:32]
+           │
+         6 │ fragment UserFriends on User { friends { name } }
+           │                                ────────┬───────  
+           │                                        ╰───────── reported by rule `list-fields-need-first`
+        ───╯
+    "#]]
+    .assert_eq(&errors(source, &options));
+
+    let source = "{ users(first: 10) { friends(first: 5) { name } } }";
+    assert_eq!(errors(source, &options), "");
+}
+
+#[test]
+fn visits_every_element_in_order() {
+    let visited = Arc::new(Mutex::new(Vec::new()));
+    let options = ExecutableValidationOptions::new().rule_fn("record", {
+        let visited = visited.clone();
+        move |cx, element| {
+            let name = match element {
+                ExecutableElement::Operation(op) => format!("operation {:?}", op.name),
+                ExecutableElement::VariableDefinition { def, .. } => {
+                    format!("variable {}", def.name)
+                }
+                ExecutableElement::Fragment(def) => format!("fragment {}", def.name),
+                ExecutableElement::Field(field) => format!("field {}", field.name),
+                ExecutableElement::FragmentSpread(spread) => {
+                    format!("spread {}", spread.fragment_name)
+                }
+                ExecutableElement::InlineFragment(_) => "inline fragment".to_owned(),
+                ExecutableElement::Directive(directive) => format!("@{}", directive.name),
+            };
+            visited
+                .lock()
+                .unwrap()
+                .push(format!("{name} at {}", cx.path()));
+        }
+    });
+    let source = r#"
+query Q($n: Int, $skip: Boolean!) @deprecatedClient {
+  users(first: $n) @skip(if: $skip) { ... on User { name } ...F }
+}
+fragment F on User { name @deprecatedClient }
+"#;
+    assert_eq!(errors(source, &options), "");
+    assert_eq!(
+        *visited.lock().unwrap(),
+        [
+            "operation Some(\"Q\") at query Q",
+            "variable n at query Q",
+            "variable skip at query Q",
+            "@deprecatedClient at query Q",
+            "field users at query Q",
+            "@skip at query Q → users",
+            "inline fragment at query Q → users",
+            "field name at query Q → users",
+            "spread F at query Q → users",
+            "fragment F at fragment F",
+            "field name at fragment F",
+            "@deprecatedClient at fragment F → name",
+        ]
+    );
+}
@@ -0,0 +1,98 @@
+use apollo_compiler::ast;
+use apollo_compiler::validation::ExecutableValidationOptions;
+use apollo_compiler::validation::UnknownDirectives;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = "type Query { cat: Cat } type Cat { name: String }";
+
+fn assumed(sdl: &str) -> Vec<Node<ast::DirectiveDefinition>> {
+    ast::Document::parse(sdl, "client.graphql")
+        .unwrap()
+        .definitions
+        .into_iter()
+        .map(|def| match def {
+            ast::Definition::DirectiveDefinition(def) => def,
+            _ => panic!("expected a directive definition"),
+        })
+        .collect()
+}
+
+fn validate(source: &str, options: &ExecutableValidationOptions) -> (bool, Vec<String>) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let doc = ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+    match doc.validate_with_options(&schema, options) {
+        Ok((_, warnings)) => (
+            true,
+            warnings.iter().map(|diag| diag.error.to_string()).collect(),
+        ),
+        Err(with_errors) => (
+            false,
+            with_errors
+                .errors
+                .iter()
+                .map(|diag| diag.error.to_string())
+                .collect(),
+        ),
+    }
+}
+
+#[test]
+fn default_options_match_validate() {
+    let (valid, errors) = validate(
+        "{ cat @client { name } }",
+        &ExecutableValidationOptions::new(),
+    );
+    assert!(!valid);
+    assert_eq!(errors, ["cannot find directive `@client` in this document"]);
+}
+
+#[test]
+fn unknown_directives_as_warnings() {
+    let options = ExecutableValidationOptions::new().unknown_directives(UnknownDirectives::Warn);
+    let (valid, warnings) = validate("{ cat @client { name @export(as: \"n\") } }", &options);
+    assert!(valid);
+    assert_eq!(warnings.len(), 2);
+
+    // Other errors make the document invalid, and unknown directives are reported with them
+    let (valid, errors) = validate("query($unused: String) { cat @client { name } }", &options);
+    assert!(!valid);
+    assert_eq!(
+        errors,
+        [
+            "unused variable: `$unused`",
+            "cannot find directive `@client` in this document",
+        ]
+    );
+}
+
+#[test]
+fn assumed_directives_are_checked() {
+    let mut options = ExecutableValidationOptions::new();
+    for def in assumed(
+        "directive @client(always: Boolean) on FIELD
+         directive @connection(key: String!) on FIELD",
+    ) {
+        options = options.assume_directive(def);
+    }
+    let (valid, warnings) = validate(
+        "{ cat @client(always: true) @connection(key: \"cat\") { name } }",
+        &options,
+    );
+    assert!(valid);
+    assert!(warnings.is_empty());
+
+    let (valid, errors) = validate(
+        "query { cat @connection { ...F } } fragment F on Cat @client { name }",
+        &options,
+    );
+    assert!(!valid);
+    assert_eq!(
+        errors,
+        [
+            "the required argument `@connection(key:)` is not provided",
+            "client directive is not supported for FRAGMENT_DEFINITION location",
+        ]
+    );
+}
@@ -0,0 +1,232 @@
+use apollo_compiler::validation::DiagnosticList;
+use apollo_compiler::validation::ExecutableElement;
+use apollo_compiler::validation::ExecutableValidationOptions;
+use apollo_compiler::validation::UnknownDirectives;
+use apollo_compiler::validation::Validator;
+use apollo_compiler::validation::WithErrors;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+type Query {
+  cat: Cat
+  dog: Dog
+}
+
+type Cat {
+  name: String
+  meowVolume(unit: String!): Int
+}
+
+type Dog {
+  name: String
+  barkVolume: Int
+}
+"#;
+
+/// Validates with both `Validator` and `ExecutableDocument::validate_with_options`,
+/// expecting the same diagnostics in the same order
+fn validate_with(
+    validator: &mut Validator,
+    options: &ExecutableValidationOptions,
+    source: &str,
+) -> String {
+    let doc = ExecutableDocument::parse(validator.schema(), source, "query.graphql").unwrap();
+    let to_strings = |result: Result<(_, DiagnosticList), WithErrors<_>>| match result {
+        Ok((_, warnings)) => warnings.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+        Err(e) => e.errors.iter().map(|d| d.to_string()).collect(),
+    };
+    let expected = to_strings(
+        doc.clone()
+            .validate_with_options(validator.schema(), options),
+    );
+    let actual = to_strings(validator.validate(doc));
+    assert_eq!(actual, expected);
+    actual.concat()
+}
+
+fn validate(validator: &mut Validator, source: &str) -> String {
+    validate_with(validator, &ExecutableValidationOptions::new(), source)
+}
+
+#[test]
+fn matches_non_incremental_validation() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let mut validator = Validator::new(schema);
+
+    let source = r#"
+query A($unused: Int) {
+  cat { ...catFields }
+}
+
+query B {
+  dog { name barkVolume(unit: "dB") }
+}
+
+fragment catFields on Cat {
+  name
+  meowVolume
+}
+
+fragment unusedFields on Dog {
+  name
+}
+"#;
+    let errors = validate(&mut validator, source);
+    assert!(errors.contains("unused variable: `$unused`"), "{errors}");
+    assert!(
+        errors.contains("missing value for argument `unit`"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("argument `unit` is not supported by `Dog.barkVolume`"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("fragment `unusedFields` must be used"),
+        "{errors}"
+    );
+
+    // Same document again, all operations come from the cache
+    assert_eq!(validate(&mut validator, source), errors);
+}
+
+#[test]
+fn revalidates_after_edits() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let mut validator = Validator::new(schema);
+
+    let errors = validate(
+        &mut validator,
+        r#"
+query A {
+  cat { ...catFields }
+}
+
+fragment catFields on Cat {
+  meowVolume
+}
+
+query B {
+  dog { name(loud: true) }
+}
+"#,
+    );
+    assert!(
+        errors.contains("missing value for argument `unit`"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("argument `loud` is not supported by `Dog.name`"),
+        "{errors}"
+    );
+
+    // Edit operation `B` only: errors for `A` are reused
+    let errors = validate(
+        &mut validator,
+        r#"
+query A {
+  cat { ...catFields }
+}
+
+fragment catFields on Cat {
+  meowVolume
+}
+
+query B {
+  dog { name }
+}
+"#,
+    );
+    assert!(
+        errors.contains("missing value for argument `unit`"),
+        "{errors}"
+    );
+    assert!(!errors.contains("loud"), "{errors}");
+
+    // Edit a fragment used by `A`
+    let errors = validate(
+        &mut validator,
+        r#"
+query A {
+  cat { ...catFields }
+}
+
+fragment catFields on Cat {
+  meowVolume(unit: "dB")
+}
+
+query B {
+  dog { name }
+}
+"#,
+    );
+    assert_eq!(errors, "");
+
+    // Move everything further down the file
+    let errors = validate(
+        &mut validator,
+        r#"
+
+
+query A {
+  cat { ...catFields }
+}
+
+fragment catFields on Cat {
+  meowVolume
+}
+
+query B {
+  dog { name }
+}
+"#,
+    );
+    assert!(
+        errors.contains("missing value for argument `unit`"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn uses_validation_options() {
+    let options = || {
+        ExecutableValidationOptions::new()
+            .unknown_directives(UnknownDirectives::Warn)
+            .rule_fn("no-cat-names", |cx, element| {
+                if let ExecutableElement::Field(field) = element {
+                    if field.name == "name" && cx.path().to_string().contains("cat") {
+                        cx.report("cat names are private")
+                    }
+                }
+            })
+    };
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let mut validator = Validator::with_options(schema, options());
+
+    let source = r#"
+query A {
+  cat { name @client }
+}
+
+query B {
+  dog { name @client }
+}
+"#;
+    let errors = validate_with(&mut validator, &options(), source);
+    assert!(errors.contains("cat names are private"), "{errors}");
+    assert!(
+        errors.contains("cannot find directive `@client` in this document"),
+        "{errors}"
+    );
+
+    // Rules run again even when operations are cached
+    let errors = validate_with(&mut validator, &options(), source);
+    assert!(errors.contains("cat names are private"), "{errors}");
+
+    // Only warnings
+    let source = "query B { dog { name @client } }";
+    let doc = ExecutableDocument::parse(validator.schema(), source, "query.graphql").unwrap();
+    let (_, warnings) = validator.validate(doc).unwrap();
+    assert_eq!(warnings.len(), 1);
+}
@@ -0,0 +1,191 @@
+use apollo_compiler::ast::Value;
+use apollo_compiler::ast::ValueBuildError;
+use apollo_compiler::name;
+use apollo_compiler::ty;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+fn schema() -> Valid<Schema> {
+    Schema::parse_and_validate(
+        r#"
+        type Query {
+          search(filter: Filter!, page: Int = 1): [String]
+          lookup(by: LookupBy!): String
+        }
+        input Filter {
+          terms: [String!]!
+          kind: Kind
+          limit: Int
+          score: Float
+          range: Range
+          extra: JSON
+          ranges: [Range]
+        }
+        input Range { min: Int!, max: Int = 10 }
+        input LookupBy @oneOf { id: ID, email: String }
+        enum Kind { BOOK, FILM }
+        scalar JSON
+        directive @oneOf on INPUT_OBJECT
+        "#,
+        "schema.graphql",
+    )
+    .unwrap()
+}
+
+#[test]
+fn builds_nested_values() {
+    let schema = schema();
+    let ty = ty!(Filter!);
+    let filter = Value::build(&schema, &ty)
+        .object(|o| {
+            o.field("terms", ["graphql", "rust"])
+                .field("kind", Value::Enum(name!("BOOK")))
+                .field("limit", Value::Variable(name!("limit")))
+                .field("score", 2)
+                .object("range", |r| r.field("min", 1))
+                .object("extra", |e| e.field("anything", vec![true, false]))
+                .list("ranges", |l| {
+                    l.object(|r| r.field("min", 0).field("max", 5)).item(())
+                })
+        })
+        .unwrap();
+    let expected = r#"{terms: ["graphql", "rust"], kind: BOOK, limit: $limit, score: 2, range: {min: 1}, extra: {anything: [true, false]}, ranges: [{min: 0, max: 5}, null]}"#;
+    assert_eq!(filter.serialize().no_indent().to_string(), expected);
+
+    // The built value is valid in a document
+    let query = format!("query($limit: Int) {{ search(filter: {filter}) }}");
+    ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+
+    // Input coercion: a single item for a list type
+    let terms = Value::build(&schema, &ty!([String!]!))
+        .value("one")
+        .unwrap();
+    assert_eq!(terms.to_string(), r#""one""#);
+    let ranges = Value::build(&schema, &ty!([Range]))
+        .object(|r| r.field("min", 3))
+        .unwrap();
+    assert_eq!(ranges.serialize().no_indent().to_string(), "{min: 3}");
+
+    // Raw values are checked recursively
+    let raw = Value::Object(vec![
+        (name!("terms"), Value::from(["a"]).into()),
+        (
+            name!("range"),
+            Value::Object(vec![(name!("min"), 1.into())]).into(),
+        ),
+    ]);
+    Value::build(&schema, &ty).value(raw).unwrap();
+
+    let lookup = Value::build(&schema, &ty!(LookupBy!))
+        .object(|o| o.field("email", "a@example.com"))
+        .unwrap();
+    assert_eq!(
+        lookup.serialize().no_indent().to_string(),
+        r#"{email: "a@example.com"}"#
+    );
+}
+
+#[test]
+fn reports_first_mistake_with_path() {
+    let schema = schema();
+    let ty = ty!(Filter!);
+    let error = |result: Result<_, ValueBuildError>| result.unwrap_err().to_string();
+
+    let result = Value::build(&schema, &ty).object(|o| o.field("terms", [1]));
+    assert_eq!(
+        error(result),
+        "value.terms[0]: expected a value of type `String!`, found an integer"
+    );
+
+    let result = Value::build(&schema, &ty).object(|o| o.field("terms", vec![()]));
+    assert_eq!(
+        error(result),
+        "value.terms[0]: null value for non-null type `String!`"
+    );
+
+    let result = Value::build(&schema, &ty).object(|o| o.field("limit", 1));
+    assert_eq!(
+        error(result),
+        "value: missing value for required field `terms` of input object `Filter`"
+    );
+
+    let result = Value::build(&schema, &ty).object(|o| {
+        o.field("terms", ["a"])
+            .field("sort", "asc")
+            .field("limit", "not checked after the first error")
+    });
+    assert_eq!(
+        error(result),
+        "value: input object `Filter` has no field `sort`"
+    );
+
+    let result =
+        Value::build(&schema, &ty).object(|o| o.field("terms", ["a"]).field("terms", ["b"]));
+    assert_eq!(
+        error(result),
+        "value: field `terms` of input object `Filter` is set more than once"
+    );
+
+    let result = Value::build(&schema, &ty).object(|o| {
+        o.field("terms", ["a"])
+            .field("kind", Value::Enum(name!("GAME")))
+    });
+    assert_eq!(error(result), "value.kind: enum `Kind` has no value `GAME`");
+
+    let result = Value::build(&schema, &ty).object(|o| {
+        o.field("terms", ["a"]).list("ranges", |l| {
+            l.object(|r| r.field("min", 1))
+                .object(|r| r.field("max", 1))
+        })
+    });
+    assert_eq!(
+        error(result),
+        "value.ranges[1]: missing value for required field `min` of input object `Range`"
+    );
+
+    let big = Value::Int(apollo_compiler::ast::IntValue::new_parsed("3000000000"));
+    let result = Value::build(&schema, &ty!(Int)).value(big);
+    assert_eq!(
+        error(result),
+        "value: 3000000000 is out of range for type `Int`"
+    );
+
+    let result = Value::build(&schema, &ty!(Int)).list(|l| l.item(1));
+    assert_eq!(
+        error(result),
+        "value: expected a value of type `Int`, found a list"
+    );
+
+    let result = Value::build(&schema, &ty!(Kind)).object(|o| o);
+    assert_eq!(
+        error(result),
+        "value: expected a value of type `Kind`, found an input object"
+    );
+
+    let result = Value::build(&schema, &ty!(Query)).value(1);
+    assert_eq!(error(result), "value: `Query` is not an input type");
+
+    let result = Value::build(&schema, &ty!(Missing)).value(1);
+    assert_eq!(
+        error(result),
+        "value: type `Missing` is not defined in the schema"
+    );
+
+    let result = Value::build(&schema, &ty!(JSON)).object(|o| o.field("not a name", 1));
+    assert_eq!(
+        error(result),
+        "value: `not a name` is not a valid GraphQL name"
+    );
+
+    for result in [
+        Value::build(&schema, &ty!(LookupBy)).object(|o| o),
+        Value::build(&schema, &ty!(LookupBy)).object(|o| o.field("id", 1).field("email", "e")),
+        Value::build(&schema, &ty!(LookupBy)).object(|o| o.field("id", ())),
+    ] {
+        assert_eq!(
+            error(result),
+            "value: oneOf input object `LookupBy` must have exactly one field, which is not null"
+        );
+    }
+}
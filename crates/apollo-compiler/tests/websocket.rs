@@ -0,0 +1,165 @@
+use apollo_compiler::execution::Response;
+use apollo_compiler::execution::WsConnection;
+use apollo_compiler::execution::WsEvent;
+use apollo_compiler::execution::WsProtocolError;
+use apollo_compiler::execution::WsServerMessage;
+
+fn to_json(message: &WsServerMessage) -> String {
+    serde_json::to_string(message).unwrap()
+}
+
+fn acknowledged() -> WsConnection {
+    let mut connection = WsConnection::new();
+    let event = connection
+        .receive(r#"{"type":"connection_init","payload":{"token":"abc"}}"#)
+        .unwrap();
+    let Some(WsEvent::Init { payload }) = event else {
+        panic!("{event:?}")
+    };
+    assert_eq!(payload.unwrap()["token"], "abc");
+    assert!(!connection.is_acknowledged());
+    let ack = connection.acknowledge(None);
+    assert_eq!(to_json(&ack), r#"{"type":"connection_ack"}"#);
+    assert!(connection.is_acknowledged());
+    connection
+}
+
+fn subscribe(connection: &mut WsConnection, id: &str) {
+    let message = format!(r#"{{"type":"subscribe","id":"{id}","payload":{{"query":"{{ a }}"}}}}"#);
+    let event = connection.receive(&message).unwrap();
+    let Some(WsEvent::Subscribe {
+        id: event_id,
+        request,
+    }) = event
+    else {
+        panic!("{event:?}")
+    };
+    assert_eq!(event_id, id);
+    assert_eq!(request.query, "{ a }");
+    assert!(connection.is_active(id));
+}
+
+#[test]
+fn subscription_stream() {
+    let mut connection = acknowledged();
+    subscribe(&mut connection, "s");
+    let event: Response = serde_json::from_str(r#"{"data":{"a":1}}"#).unwrap();
+    let next = connection.next("s", event.clone()).unwrap();
+    assert_eq!(
+        to_json(&next),
+        r#"{"type":"next","id":"s","payload":{"data":{"a":1}}}"#
+    );
+    assert!(connection.next("s", event.clone()).is_some());
+    let complete = connection.complete("s").unwrap();
+    assert_eq!(to_json(&complete), r#"{"type":"complete","id":"s"}"#);
+    assert!(connection.next("s", event).is_none());
+    assert!(connection.complete("s").is_none());
+
+    // The ID can be reused once complete
+    subscribe(&mut connection, "s");
+}
+
+#[test]
+fn client_cancels_operation() {
+    let mut connection = acknowledged();
+    subscribe(&mut connection, "1");
+    subscribe(&mut connection, "2");
+    assert_eq!(
+        connection.receive(r#"{"type":"complete","id":"1"}"#),
+        Ok(Some(WsEvent::Complete { id: "1".into() }))
+    );
+    assert!(connection
+        .respond("1", Response::from_request_error(error("late")))
+        .is_empty());
+    assert_eq!(connection.active_operations().collect::<Vec<_>>(), ["2"]);
+    // Already complete: nothing to do
+    assert_eq!(
+        connection.receive(r#"{"type":"complete","id":"1"}"#),
+        Ok(None)
+    );
+}
+
+#[test]
+fn request_errors() {
+    let mut connection = acknowledged();
+    subscribe(&mut connection, "1");
+    let messages = connection.respond("1", Response::from_request_error(error("invalid")));
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        to_json(&messages[0]),
+        r#"{"type":"error","id":"1","payload":[{"message":"invalid"}]}"#
+    );
+    assert!(!connection.is_active("1"));
+}
+
+#[test]
+fn ping_pong() {
+    let mut connection = WsConnection::new();
+    // Allowed before `connection_init`
+    let event = connection.receive(r#"{"type":"ping"}"#).unwrap();
+    let Some(WsEvent::Reply(pong)) = event else {
+        panic!("{event:?}")
+    };
+    assert_eq!(to_json(&pong), r#"{"type":"pong"}"#);
+    assert_eq!(
+        connection.receive(r#"{"type":"pong","payload":{"n":1}}"#),
+        Ok(Some(WsEvent::Pong {
+            payload: Some(serde_json::from_str(r#"{"n":1}"#).unwrap())
+        }))
+    );
+    assert_eq!(to_json(&connection.ping(None)), r#"{"type":"ping"}"#);
+}
+
+#[test]
+fn protocol_errors() {
+    let close_code = |result: Result<Option<WsEvent>, WsProtocolError>| {
+        let error = result.unwrap_err();
+        (error.close_code(), error.to_string())
+    };
+
+    let mut connection = WsConnection::new();
+    assert_eq!(
+        close_code(connection.receive(r#"{"type":"unknown"}"#)).0,
+        4400
+    );
+    assert_eq!(close_code(connection.receive("not JSON")).0, 4400);
+    assert_eq!(
+        close_code(
+            connection.receive(r#"{"type":"subscribe","id":"1","payload":{"query":"{ a }"}}"#)
+        ),
+        (4401, "Unauthorized".into())
+    );
+
+    let mut connection = WsConnection::new();
+    connection.receive(r#"{"type":"connection_init"}"#).unwrap();
+    // Not acknowledged yet
+    assert_eq!(
+        close_code(
+            connection.receive(r#"{"type":"subscribe","id":"1","payload":{"query":"{ a }"}}"#)
+        )
+        .0,
+        4401
+    );
+    assert_eq!(
+        close_code(connection.receive(r#"{"type":"connection_init"}"#)),
+        (4429, "Too many initialisation requests".into())
+    );
+    let rejected = connection.reject();
+    assert_eq!(
+        (rejected.close_code(), rejected.to_string()),
+        (4403, "Forbidden".into())
+    );
+
+    let mut connection = acknowledged();
+    subscribe(&mut connection, "1");
+    assert_eq!(
+        close_code(
+            connection.receive(r#"{"type":"subscribe","id":"1","payload":{"query":"{ b }"}}"#)
+        ),
+        (4409, "Subscriber for 1 already exists".into())
+    );
+}
+
+fn error(message: &str) -> apollo_compiler::execution::GraphQLError {
+    serde_json::from_value(serde_json::json!({ "message": message })).unwrap()
+}
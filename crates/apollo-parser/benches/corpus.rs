@@ -0,0 +1,31 @@
+use apollo_parser::Lexer;
+use apollo_parser::Parser;
+use criterion::*;
+
+#[path = "corpus/mod.rs"]
+mod corpus;
+
+fn bench_corpus(c: &mut Criterion) {
+    for document in corpus::all() {
+        let mut group = c.benchmark_group(&document.name);
+        group.throughput(Throughput::Bytes(document.source.len() as u64));
+        group.bench_function("lexer", |b| {
+            b.iter(|| {
+                for token_res in Lexer::new(&document.source) {
+                    black_box(token_res.unwrap());
+                }
+            })
+        });
+        group.bench_function("parser", |b| {
+            b.iter(|| {
+                let tree = Parser::new(&document.source).parse();
+                assert_eq!(tree.errors().len(), 0, "{}", document.name);
+                black_box(tree)
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_corpus);
+criterion_main!(benches);
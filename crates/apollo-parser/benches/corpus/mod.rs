@@ -0,0 +1,112 @@
+//! Representative GraphQL documents for benchmarks.
+//!
+//! In addition to built-in documents, setting the `APOLLO_BENCH_CORPUS` environment variable
+//! to a directory path also benchmarks every `.graphql` file in that directory,
+//! so that users can measure parser performance on their own documents:
+//!
+//! ```bash
+//! env APOLLO_BENCH_CORPUS=path/to/graphql/files cargo bench --package apollo-parser --bench corpus
+//! ```
+//!
+//! The `corpus` benchmark of `apollo-compiler` includes this module to load the same directory.
+
+// Not every benchmark uses every built-in document
+#![allow(dead_code)]
+
+use std::path::Path;
+
+pub struct Document {
+    pub name: String,
+    pub source: String,
+}
+
+/// All built-in documents, followed by those found in `APOLLO_BENCH_CORPUS` if set
+pub fn all() -> Vec<Document> {
+    let mut documents = vec![
+        supergraph(),
+        many_aliases(),
+        deep_query(100),
+        many_fragments(500),
+    ];
+    documents.extend(from_env());
+    documents
+}
+
+/// A large supergraph schema
+pub fn supergraph() -> Document {
+    Document {
+        name: "supergraph".into(),
+        source: include_str!("../../test_data/parser/ok/0032_supergraph.graphql").into(),
+    }
+}
+
+/// A query with many aliased fields
+pub fn many_aliases() -> Document {
+    Document {
+        name: "many_aliases".into(),
+        source: include_str!("../testdata/alias.graphql").into(),
+    }
+}
+
+/// A query with `depth` levels of nested selection sets and argument values
+pub fn deep_query(depth: usize) -> Document {
+    let mut source = String::from("query Deep");
+    for _ in 0..depth {
+        source.push_str(" { field(arg: { list: [1, 2.5, \"three\"] })");
+    }
+    source.push_str(" { leaf }");
+    for _ in 0..depth {
+        source.push_str(" }");
+    }
+    Document {
+        name: format!("deep_query_{depth}"),
+        source,
+    }
+}
+
+/// A query spreading `count` fragments, each with a few fields and directives
+pub fn many_fragments(count: usize) -> Document {
+    let mut source = String::from("query ManyFragments {\n  node {\n");
+    for i in 0..count {
+        source.push_str(&format!("    ...Fragment{i}\n"));
+    }
+    source.push_str("  }\n}\n");
+    for i in 0..count {
+        source.push_str(&format!(
+            "\nfragment Fragment{i} on Node {{\n  \
+             id\n  \
+             field{i}(first: {i}) @include(if: $condition) {{ name }}\n  \
+             ... on Other {{ other{i} }}\n\
+             }}\n"
+        ));
+    }
+    Document {
+        name: format!("many_fragments_{count}"),
+        source,
+    }
+}
+
+/// Documents found in the directory named by the `APOLLO_BENCH_CORPUS` environment variable
+pub fn from_env() -> Vec<Document> {
+    let Some(dir) = std::env::var_os("APOLLO_BENCH_CORPUS") else {
+        return Vec::new();
+    };
+    read_dir(Path::new(&dir))
+}
+
+fn read_dir(dir: &Path) -> Vec<Document> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "graphql"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| Document {
+            name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+            source: std::fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display())),
+        })
+        .collect()
+}
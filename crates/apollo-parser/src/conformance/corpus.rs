@@ -0,0 +1,165 @@
+// Generated by `conformance_corpus` in src/tests.rs, do not edit.
+// To update: env UPDATE_EXPECT=1 cargo test --package apollo-parser
+
+use super::Case;
+use super::Expected::Accepted;
+use super::Expected::Rejected;
+
+macro_rules! case {
+    ($name: literal, $expected: expr) => {
+        Case {
+            name: $name,
+            input: include_str!(concat!("../../test_data/", $name, ".graphql")),
+            expected: $expected,
+        }
+    };
+}
+
+pub(super) const LEXER: &[Case] = &[
+    case!("lexer/ok/0001_hello", Accepted),
+    case!("lexer/ok/0002_whitespace", Accepted),
+    case!("lexer/ok/0003_name", Accepted),
+    case!("lexer/ok/0004_string_value", Accepted),
+    case!("lexer/ok/0005_int", Accepted),
+    case!("lexer/ok/0006_float", Accepted),
+    case!("lexer/ok/0007_symbols", Accepted),
+    case!("lexer/ok/0008_block_string", Accepted),
+    case!("lexer/ok/0009_block_string_character_with_loose_quotations", Accepted),
+    case!("lexer/ok/0010_empty_string_value_followed_by_eof", Accepted),
+    case!("lexer/ok/0011_escaped_char", Accepted),
+    case!("lexer/ok/0012_unicode_char", Accepted),
+    case!("lexer/ok/0013_emoji_char_in_string_value", Accepted),
+    case!("lexer/err/0001_unterminated_spread_operator_with_one", Rejected),
+    case!("lexer/err/0002_unterminated_spread_operator_with_2", Rejected),
+    case!("lexer/err/0003_float_with_incorrect_decimal_point", Rejected),
+    case!("lexer/err/0004_unterminated_string_value", Rejected),
+    case!("lexer/err/0005_escaped_char", Rejected),
+    case!("lexer/err/0006_unterminated_string_value_in_list_value_argument", Rejected),
+    case!("lexer/err/0007_unterminated_string_value_in_object_value_argument", Rejected),
+    case!("lexer/err/0008_unterminated_string_value", Rejected),
+    case!("lexer/err/0009_unterminated_string_value_as_default", Rejected),
+    case!("lexer/err/0010_unterminated_string_value_with_unicode", Rejected),
+    case!("lexer/err/0011_unterminated_string_value_with_unicode_and_escaped_characters", Rejected),
+    case!("lexer/err/0012_string_value_with_line_terminators", Rejected),
+    case!("lexer/err/0013_string_with_invalid_escapes", Rejected),
+    case!("lexer/err/0014_plus_sign", Rejected),
+    case!("lexer/err/0015_minus_sign", Rejected),
+    case!("lexer/err/0016_leading_zero", Rejected),
+    case!("lexer/err/0017_number_lookahead", Rejected),
+    case!("lexer/err/0018_eof_float_1", Rejected),
+    case!("lexer/err/0019_eof_float_2", Rejected),
+    case!("lexer/err/0020_eof_float_3", Rejected),
+    case!("lexer/err/0021_eof_float_4", Rejected),
+    case!("lexer/err/0022_eof_string_1", Rejected),
+    case!("lexer/err/0023_eof_string_1", Rejected),
+    case!("lexer/err/0024_eof_string_2", Rejected),
+    case!("lexer/err/0025_eof_string_3", Rejected),
+    case!("lexer/err/0026_eof_block_string_1", Rejected),
+    case!("lexer/err/0027_eof_block_string_2", Rejected),
+    case!("lexer/err/0029_not_whitespace", Rejected),
+    case!("lexer/err/0030_escaped_surrogate", Rejected),
+];
+
+pub(super) const PARSER: &[Case] = &[
+    case!("parser/ok/0001_input_type_definition_without_input_values", Accepted),
+    case!("parser/ok/0002_selection_simple", Accepted),
+    case!("parser/ok/0003_selection_with_fields", Accepted),
+    case!("parser/ok/0004_selection_with_fields_aliases_arguments", Accepted),
+    case!("parser/ok/0005_selection_with_inline_fragments", Accepted),
+    case!("parser/ok/0006_selection_with_fragment_spread", Accepted),
+    case!("parser/ok/0007_directive_definition", Accepted),
+    case!("parser/ok/0008_directive_definition_with_arguments", Accepted),
+    case!("parser/ok/0009_directive_definition_repeatable", Accepted),
+    case!("parser/ok/0010_enum_type_definition", Accepted),
+    case!("parser/ok/0011_enum_type_extension", Accepted),
+    case!("parser/ok/0012_fragment_definition", Accepted),
+    case!("parser/ok/0013_fragment_definition_with_fragment_spread", Accepted),
+    case!("parser/ok/0014_input_definition", Accepted),
+    case!("parser/ok/0015_input_extension", Accepted),
+    case!("parser/ok/0016_interface_definition", Accepted),
+    case!("parser/ok/0017_interface_extension", Accepted),
+    case!("parser/ok/0018_object_type_definition", Accepted),
+    case!("parser/ok/0019_object_type_extension", Accepted),
+    case!("parser/ok/0020_operation_type_definition", Accepted),
+    case!("parser/ok/0021_operation_type_definition_with_arguments", Accepted),
+    case!("parser/ok/0022_operation_type_definition_with_arguments_and_directives", Accepted),
+    case!("parser/ok/0023_scalar_definition", Accepted),
+    case!("parser/ok/0024_scalar_extension", Accepted),
+    case!("parser/ok/0025_schema_definition", Accepted),
+    case!("parser/ok/0026_schema_extension", Accepted),
+    case!("parser/ok/0027_union_type_definition", Accepted),
+    case!("parser/ok/0028_union_type_definition_followed_by_object_definition", Accepted),
+    case!("parser/ok/0029_union_type_extension", Accepted),
+    case!("parser/ok/0030_values", Accepted),
+    case!("parser/ok/0031_variables_with_default", Accepted),
+    case!("parser/ok/0032_supergraph", Accepted),
+    case!("parser/ok/0033_directive_on_argument_definition", Accepted),
+    case!("parser/ok/0034_query_shorthand_followed_by_fragment_definition", Accepted),
+    case!("parser/ok/0035_query_with_variables", Accepted),
+    case!("parser/ok/0036_parses_variable_definition_with_list_type", Accepted),
+    case!("parser/ok/0037_operation_type_definition_with_inline_fragment", Accepted),
+    case!("parser/ok/0038_wrapped_named_types", Accepted),
+    case!("parser/ok/0039_variable_with_directives", Accepted),
+    case!("parser/ok/0040_type_token_order", Accepted),
+    case!("parser/ok/0041_implements_list", Accepted),
+    case!("parser/ok/0042_object_type_definition_without_fields", Accepted),
+    case!("parser/ok/0043_interface_type_definition_without_fields", Accepted),
+    case!("parser/err/0001_directive_definition_missing_location", Rejected),
+    case!("parser/err/0002_enum_definition_with_missing_name", Rejected),
+    case!("parser/err/0003_enum_definition_with_missing_values", Rejected),
+    case!("parser/err/0004_enum_definition_with_missing_curly", Rejected),
+    case!("parser/err/0005_enum_extension_with_missing_name", Rejected),
+    case!("parser/err/0006_enum_extension_with_missing_requirements", Rejected),
+    case!("parser/err/0007_fragment_definition_with_invalid_fragment_name", Rejected),
+    case!("parser/err/0008_fragment_definition_with_invalid_type_condition", Rejected),
+    case!("parser/err/0009_fragment_definition_with_invalid_selection_set", Rejected),
+    case!("parser/err/0010_input_definition_with_missing_name", Rejected),
+    case!("parser/err/0011_input_definition_with_missing_input_values", Rejected),
+    case!("parser/err/0012_input_extension_with_missing_name", Rejected),
+    case!("parser/err/0013_input_extension_with_missing_requirements", Rejected),
+    case!("parser/err/0014_interface_extension_with_missing_name", Rejected),
+    case!("parser/err/0015_interface_extension_with_missing_requirements", Rejected),
+    case!("parser/err/0016_object_type_extension_with_missing_name", Rejected),
+    case!("parser/err/0017_object_type_extension_with_missing_requirements", Rejected),
+    case!("parser/err/0018_scalar_definition_with_missing_name", Rejected),
+    case!("parser/err/0019_scalar_extension_with_missing_name", Rejected),
+    case!("parser/err/0020_union_definition_with_missing_name", Rejected),
+    case!("parser/err/0021_union_definition_with_missing_union_members", Rejected),
+    case!("parser/err/0022_union_extension_with_missing_name", Rejected),
+    case!("parser/err/0023_union_extension_with_missing_requirements", Rejected),
+    case!("parser/err/0024_document_with_incorrect_definition", Rejected),
+    case!("parser/err/0025_document_with_incorrect_definition_and_selection_set", Rejected),
+    case!("parser/err/0026_invalid_definition_squished_between_two_valid_definitions", Rejected),
+    case!("parser/err/0027_invalid_type_system_extension", Rejected),
+    case!("parser/err/0028_invalid_type_system_extension_followed_by_valid", Rejected),
+    case!("parser/err/0029_operation_definition_with_empty_selection_set", Rejected),
+    case!("parser/err/0030_operation_definition_with_description", Rejected),
+    case!("parser/err/0031_argument_with_erronous_string_value", Rejected),
+    case!("parser/err/0032_input_value_with_erronous_string_value", Rejected),
+    case!("parser/err/0033_directive_with_erronous_string_value", Rejected),
+    case!("parser/err/0034_unterminated_string_value_in_list", Rejected),
+    case!("parser/err/0035_unterminated_string_value_in_object_value", Rejected),
+    case!("parser/err/0036_unterminated_string_in_default_value", Rejected),
+    case!("parser/err/0040_operation_definition_missing_selection_set", Rejected),
+    case!("parser/err/0041_operation_definition_with_missing_selection_set", Rejected),
+    case!("parser/err/0042_document_with_incorrect_token", Rejected),
+    case!("parser/err/0043_type_with_trailing_garbage", Rejected),
+    case!("parser/err/0044_list_type_with_no_type", Rejected),
+    case!("parser/err/0045_ignored_token_spans", Rejected),
+    case!("parser/err/0046_incomplete_spreads", Rejected),
+    case!("parser/err/0047_empty_variable_definition", Rejected),
+    case!("parser/err/0048_unbalanced_list_type_1", Rejected),
+    case!("parser/err/0049_unbalanced_list_type_2", Rejected),
+    case!("parser/err/0050_invalid_implements_list", Rejected),
+    case!("parser/err/0051_union_with_invalid_members_list", Rejected),
+    case!("parser/err/0052_const_value", Rejected),
+    case!("parser/err/0053_on_without_type_condition", Rejected),
+    case!("parser/err/0054_root_operation_type_with_extra_brackets", Rejected),
+    case!("parser/err/0055_object_definition_with_missing_name", Rejected),
+    case!("parser/err/0056_object_definition_with_missing_curly", Rejected),
+    case!("parser/err/0057_object_definition_with_missing_fields", Rejected),
+    case!("parser/err/0058_interface_definition_with_missing_name", Rejected),
+    case!("parser/err/0059_interface_definition_with_missing_fields", Rejected),
+    case!("parser/err/0060_interface_definition_with_missing_curly", Rejected),
+    case!("parser/err/0061_empty", Rejected),
+];
@@ -0,0 +1,131 @@
+//! The lexer and parser test corpus of apollo-parser, with the expected outcome of each case.
+//!
+//! This lets other GraphQL implementations (bindings, alternate lexers or parsers, …)
+//! run the same conformance checks as apollo-parser without copying its test files.
+//! Enabled by the `conformance` Cargo feature.
+//!
+//! ## Example
+//!
+//! ```
+//! use apollo_parser::conformance;
+//! use apollo_parser::conformance::Expected;
+//! use apollo_parser::Parser;
+//!
+//! let mismatches = conformance::run(conformance::parser_cases(), |input| {
+//!     if Parser::new(input).parse().errors().len() == 0 {
+//!         Expected::Accepted
+//!     } else {
+//!         Expected::Rejected
+//!     }
+//! });
+//! assert!(mismatches.is_empty(), "{mismatches:?}");
+//! ```
+
+use std::fmt;
+
+// Generated, kept on one line per case
+#[rustfmt::skip]
+mod corpus;
+
+/// A test case of the conformance suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Case {
+    /// Path of the test file in `apollo-parser/test_data` without extension,
+    /// such as `parser/ok/0001_directive_definition`
+    pub name: &'static str,
+    /// GraphQL source text
+    pub input: &'static str,
+    /// Whether a conforming implementation accepts `input`
+    pub expected: Expected,
+}
+
+/// The outcome of lexing or parsing a conformance test case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expected {
+    /// The input has no errors
+    Accepted,
+    /// The input has at least one error
+    Rejected,
+}
+
+/// A conformance test case whose outcome differs from the expected one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mismatch {
+    /// The test case
+    pub case: &'static Case,
+    /// The outcome returned by the implementation under test
+    pub actual: Expected,
+}
+
+/// Test cases for the lexer: `Accepted` when lexing produces no errors
+pub fn lexer_cases() -> &'static [Case] {
+    corpus::LEXER
+}
+
+/// Test cases for the parser: `Accepted` when parsing a full document produces no errors
+pub fn parser_cases() -> &'static [Case] {
+    corpus::PARSER
+}
+
+/// Runs `check` on the input of each case,
+/// and returns the cases for which it does not return the expected outcome.
+pub fn run(cases: &'static [Case], mut check: impl FnMut(&str) -> Expected) -> Vec<Mismatch> {
+    cases
+        .iter()
+        .filter_map(|case| {
+            let actual = check(case.input);
+            (actual != case.expected).then_some(Mismatch { case, actual })
+        })
+        .collect()
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {:?}, got {:?}",
+            self.case.name, self.case.expected, self.actual
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lexer;
+    use crate::Parser;
+
+    #[test]
+    fn apollo_parser_conforms() {
+        let mismatches = run(lexer_cases(), |input| {
+            if Lexer::new(input).all(|result| result.is_ok()) {
+                Expected::Accepted
+            } else {
+                Expected::Rejected
+            }
+        });
+        assert_eq!(mismatches, []);
+
+        let mismatches = run(parser_cases(), |input| {
+            if Parser::new(input).parse().errors().len() == 0 {
+                Expected::Accepted
+            } else {
+                Expected::Rejected
+            }
+        });
+        assert_eq!(mismatches, []);
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let mismatches = run(parser_cases(), |_| Expected::Accepted);
+        assert!(!mismatches.is_empty());
+        assert!(mismatches
+            .iter()
+            .all(|mismatch| mismatch.case.name.starts_with("parser/err/")));
+        let first = mismatches[0].to_string();
+        assert!(first.starts_with("parser/err/0001_"));
+        assert!(first.ends_with(": expected Rejected, got Accepted"));
+    }
+}
@@ -1 +1,2 @@
 pub mod nodes;
+pub mod visitor;
@@ -406,6 +406,27 @@ impl InputObjectTypeExtension {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomDefinition {
+    pub(crate) syntax: SyntaxNode,
+}
+impl CustomDefinition {
+    pub fn description(&self) -> Option<Description> {
+        support::child(&self.syntax)
+    }
+    pub fn keyword(&self) -> Option<Name> {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Description {
+    pub(crate) syntax: SyntaxNode,
+}
+impl Description {
+    pub fn string_value(&self) -> Option<StringValue> {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct OperationType {
     pub(crate) syntax: SyntaxNode,
 }
@@ -473,6 +494,9 @@ impl Field {
     pub fn arguments(&self) -> Option<Arguments> {
         support::child(&self.syntax)
     }
+    pub fn nullability(&self) -> Option<Nullability> {
+        support::child(&self.syntax)
+    }
     pub fn directives(&self) -> Option<Directives> {
         support::child(&self.syntax)
     }
@@ -541,6 +565,18 @@ impl Arguments {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Nullability {
+    pub(crate) syntax: SyntaxNode,
+}
+impl Nullability {
+    pub fn list_nullability(&self) -> Option<ListNullability> {
+        support::child(&self.syntax)
+    }
+    pub fn nullability_designator(&self) -> Option<NullabilityDesignator> {
+        support::child(&self.syntax)
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Argument {
     pub(crate) syntax: SyntaxNode,
 }
@@ -556,6 +592,33 @@ impl Argument {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListNullability {
+    pub(crate) syntax: SyntaxNode,
+}
+impl ListNullability {
+    pub fn l_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, S!['['])
+    }
+    pub fn nullability(&self) -> Option<Nullability> {
+        support::child(&self.syntax)
+    }
+    pub fn r_brack_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, S![']'])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NullabilityDesignator {
+    pub(crate) syntax: SyntaxNode,
+}
+impl NullabilityDesignator {
+    pub fn excl_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, S![!])
+    }
+    pub fn question_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, S![?])
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FragmentName {
     pub(crate) syntax: SyntaxNode,
 }
@@ -774,15 +837,6 @@ impl Directive {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Description {
-    pub(crate) syntax: SyntaxNode,
-}
-impl Description {
-    pub fn string_value(&self) -> Option<StringValue> {
-        support::child(&self.syntax)
-    }
-}
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RootOperationTypeDefinition {
     pub(crate) syntax: SyntaxNode,
 }
@@ -1041,6 +1095,7 @@ pub enum Definition {
     UnionTypeExtension(UnionTypeExtension),
     EnumTypeExtension(EnumTypeExtension),
     InputObjectTypeExtension(InputObjectTypeExtension),
+    CustomDefinition(CustomDefinition),
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Selection {
@@ -1351,6 +1406,36 @@ impl CstNode for InputObjectTypeExtension {
         &self.syntax
     }
 }
+impl CstNode for CustomDefinition {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CUSTOM_DEFINITION
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl CstNode for Description {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == DESCRIPTION
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
 impl CstNode for OperationType {
     fn can_cast(kind: SyntaxKind) -> bool {
         kind == OPERATION_TYPE
@@ -1486,6 +1571,21 @@ impl CstNode for Arguments {
         &self.syntax
     }
 }
+impl CstNode for Nullability {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == NULLABILITY
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
 impl CstNode for Argument {
     fn can_cast(kind: SyntaxKind) -> bool {
         kind == ARGUMENT
@@ -1501,6 +1601,36 @@ impl CstNode for Argument {
         &self.syntax
     }
 }
+impl CstNode for ListNullability {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == LIST_NULLABILITY
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl CstNode for NullabilityDesignator {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == NULLABILITY_DESIGNATOR
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
 impl CstNode for FragmentName {
     fn can_cast(kind: SyntaxKind) -> bool {
         kind == FRAGMENT_NAME
@@ -1771,21 +1901,6 @@ impl CstNode for Directive {
         &self.syntax
     }
 }
-impl CstNode for Description {
-    fn can_cast(kind: SyntaxKind) -> bool {
-        kind == DESCRIPTION
-    }
-    fn cast(syntax: SyntaxNode) -> Option<Self> {
-        if Self::can_cast(syntax.kind()) {
-            Some(Self { syntax })
-        } else {
-            None
-        }
-    }
-    fn syntax(&self) -> &SyntaxNode {
-        &self.syntax
-    }
-}
 impl CstNode for RootOperationTypeDefinition {
     fn can_cast(kind: SyntaxKind) -> bool {
         kind == ROOT_OPERATION_TYPE_DEFINITION
@@ -2051,6 +2166,11 @@ impl From<InputObjectTypeExtension> for Definition {
         Definition::InputObjectTypeExtension(node)
     }
 }
+impl From<CustomDefinition> for Definition {
+    fn from(node: CustomDefinition) -> Definition {
+        Definition::CustomDefinition(node)
+    }
+}
 impl CstNode for Definition {
     fn can_cast(kind: SyntaxKind) -> bool {
         matches!(
@@ -2072,6 +2192,7 @@ impl CstNode for Definition {
                 | UNION_TYPE_EXTENSION
                 | ENUM_TYPE_EXTENSION
                 | INPUT_OBJECT_TYPE_EXTENSION
+                | CUSTOM_DEFINITION
         )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
@@ -2111,6 +2232,7 @@ impl CstNode for Definition {
             INPUT_OBJECT_TYPE_EXTENSION => {
                 Definition::InputObjectTypeExtension(InputObjectTypeExtension { syntax })
             }
+            CUSTOM_DEFINITION => Definition::CustomDefinition(CustomDefinition { syntax }),
             _ => return None,
         };
         Some(res)
@@ -2134,6 +2256,7 @@ impl CstNode for Definition {
             Definition::UnionTypeExtension(it) => it.syntax(),
             Definition::EnumTypeExtension(it) => it.syntax(),
             Definition::InputObjectTypeExtension(it) => it.syntax(),
+            Definition::CustomDefinition(it) => it.syntax(),
         }
     }
 }
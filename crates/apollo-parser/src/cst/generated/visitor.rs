@@ -0,0 +1,378 @@
+//! This is a generated file, please do not edit manually. Changes can be
+//! made in codegeneration that lives in `xtask` top-level dir.
+
+use crate::cst::*;
+use crate::SyntaxKind::*;
+/// Callbacks called by [`walk`] when entering and exiting each kind of node.
+///
+/// All methods do nothing by default: implement those for the nodes of interest.
+pub trait Visitor {
+    fn enter_name(&mut self, _node: &Name) {}
+    fn exit_name(&mut self, _node: &Name) {}
+    fn enter_document(&mut self, _node: &Document) {}
+    fn exit_document(&mut self, _node: &Document) {}
+    fn enter_operation_definition(&mut self, _node: &OperationDefinition) {}
+    fn exit_operation_definition(&mut self, _node: &OperationDefinition) {}
+    fn enter_fragment_definition(&mut self, _node: &FragmentDefinition) {}
+    fn exit_fragment_definition(&mut self, _node: &FragmentDefinition) {}
+    fn enter_directive_definition(&mut self, _node: &DirectiveDefinition) {}
+    fn exit_directive_definition(&mut self, _node: &DirectiveDefinition) {}
+    fn enter_schema_definition(&mut self, _node: &SchemaDefinition) {}
+    fn exit_schema_definition(&mut self, _node: &SchemaDefinition) {}
+    fn enter_scalar_type_definition(&mut self, _node: &ScalarTypeDefinition) {}
+    fn exit_scalar_type_definition(&mut self, _node: &ScalarTypeDefinition) {}
+    fn enter_object_type_definition(&mut self, _node: &ObjectTypeDefinition) {}
+    fn exit_object_type_definition(&mut self, _node: &ObjectTypeDefinition) {}
+    fn enter_interface_type_definition(&mut self, _node: &InterfaceTypeDefinition) {}
+    fn exit_interface_type_definition(&mut self, _node: &InterfaceTypeDefinition) {}
+    fn enter_union_type_definition(&mut self, _node: &UnionTypeDefinition) {}
+    fn exit_union_type_definition(&mut self, _node: &UnionTypeDefinition) {}
+    fn enter_enum_type_definition(&mut self, _node: &EnumTypeDefinition) {}
+    fn exit_enum_type_definition(&mut self, _node: &EnumTypeDefinition) {}
+    fn enter_input_object_type_definition(&mut self, _node: &InputObjectTypeDefinition) {}
+    fn exit_input_object_type_definition(&mut self, _node: &InputObjectTypeDefinition) {}
+    fn enter_schema_extension(&mut self, _node: &SchemaExtension) {}
+    fn exit_schema_extension(&mut self, _node: &SchemaExtension) {}
+    fn enter_scalar_type_extension(&mut self, _node: &ScalarTypeExtension) {}
+    fn exit_scalar_type_extension(&mut self, _node: &ScalarTypeExtension) {}
+    fn enter_object_type_extension(&mut self, _node: &ObjectTypeExtension) {}
+    fn exit_object_type_extension(&mut self, _node: &ObjectTypeExtension) {}
+    fn enter_interface_type_extension(&mut self, _node: &InterfaceTypeExtension) {}
+    fn exit_interface_type_extension(&mut self, _node: &InterfaceTypeExtension) {}
+    fn enter_union_type_extension(&mut self, _node: &UnionTypeExtension) {}
+    fn exit_union_type_extension(&mut self, _node: &UnionTypeExtension) {}
+    fn enter_enum_type_extension(&mut self, _node: &EnumTypeExtension) {}
+    fn exit_enum_type_extension(&mut self, _node: &EnumTypeExtension) {}
+    fn enter_input_object_type_extension(&mut self, _node: &InputObjectTypeExtension) {}
+    fn exit_input_object_type_extension(&mut self, _node: &InputObjectTypeExtension) {}
+    fn enter_custom_definition(&mut self, _node: &CustomDefinition) {}
+    fn exit_custom_definition(&mut self, _node: &CustomDefinition) {}
+    fn enter_operation_type(&mut self, _node: &OperationType) {}
+    fn exit_operation_type(&mut self, _node: &OperationType) {}
+    fn enter_variable_definitions(&mut self, _node: &VariableDefinitions) {}
+    fn exit_variable_definitions(&mut self, _node: &VariableDefinitions) {}
+    fn enter_directives(&mut self, _node: &Directives) {}
+    fn exit_directives(&mut self, _node: &Directives) {}
+    fn enter_selection_set(&mut self, _node: &SelectionSet) {}
+    fn exit_selection_set(&mut self, _node: &SelectionSet) {}
+    fn enter_field(&mut self, _node: &Field) {}
+    fn exit_field(&mut self, _node: &Field) {}
+    fn enter_fragment_spread(&mut self, _node: &FragmentSpread) {}
+    fn exit_fragment_spread(&mut self, _node: &FragmentSpread) {}
+    fn enter_inline_fragment(&mut self, _node: &InlineFragment) {}
+    fn exit_inline_fragment(&mut self, _node: &InlineFragment) {}
+    fn enter_alias(&mut self, _node: &Alias) {}
+    fn exit_alias(&mut self, _node: &Alias) {}
+    fn enter_arguments(&mut self, _node: &Arguments) {}
+    fn exit_arguments(&mut self, _node: &Arguments) {}
+    fn enter_nullability(&mut self, _node: &Nullability) {}
+    fn exit_nullability(&mut self, _node: &Nullability) {}
+    fn enter_argument(&mut self, _node: &Argument) {}
+    fn exit_argument(&mut self, _node: &Argument) {}
+    fn enter_list_nullability(&mut self, _node: &ListNullability) {}
+    fn exit_list_nullability(&mut self, _node: &ListNullability) {}
+    fn enter_nullability_designator(&mut self, _node: &NullabilityDesignator) {}
+    fn exit_nullability_designator(&mut self, _node: &NullabilityDesignator) {}
+    fn enter_fragment_name(&mut self, _node: &FragmentName) {}
+    fn exit_fragment_name(&mut self, _node: &FragmentName) {}
+    fn enter_type_condition(&mut self, _node: &TypeCondition) {}
+    fn exit_type_condition(&mut self, _node: &TypeCondition) {}
+    fn enter_named_type(&mut self, _node: &NamedType) {}
+    fn exit_named_type(&mut self, _node: &NamedType) {}
+    fn enter_variable(&mut self, _node: &Variable) {}
+    fn exit_variable(&mut self, _node: &Variable) {}
+    fn enter_string_value(&mut self, _node: &StringValue) {}
+    fn exit_string_value(&mut self, _node: &StringValue) {}
+    fn enter_float_value(&mut self, _node: &FloatValue) {}
+    fn exit_float_value(&mut self, _node: &FloatValue) {}
+    fn enter_int_value(&mut self, _node: &IntValue) {}
+    fn exit_int_value(&mut self, _node: &IntValue) {}
+    fn enter_boolean_value(&mut self, _node: &BooleanValue) {}
+    fn exit_boolean_value(&mut self, _node: &BooleanValue) {}
+    fn enter_null_value(&mut self, _node: &NullValue) {}
+    fn exit_null_value(&mut self, _node: &NullValue) {}
+    fn enter_enum_value(&mut self, _node: &EnumValue) {}
+    fn exit_enum_value(&mut self, _node: &EnumValue) {}
+    fn enter_list_value(&mut self, _node: &ListValue) {}
+    fn exit_list_value(&mut self, _node: &ListValue) {}
+    fn enter_object_value(&mut self, _node: &ObjectValue) {}
+    fn exit_object_value(&mut self, _node: &ObjectValue) {}
+    fn enter_object_field(&mut self, _node: &ObjectField) {}
+    fn exit_object_field(&mut self, _node: &ObjectField) {}
+    fn enter_variable_definition(&mut self, _node: &VariableDefinition) {}
+    fn exit_variable_definition(&mut self, _node: &VariableDefinition) {}
+    fn enter_default_value(&mut self, _node: &DefaultValue) {}
+    fn exit_default_value(&mut self, _node: &DefaultValue) {}
+    fn enter_list_type(&mut self, _node: &ListType) {}
+    fn exit_list_type(&mut self, _node: &ListType) {}
+    fn enter_non_null_type(&mut self, _node: &NonNullType) {}
+    fn exit_non_null_type(&mut self, _node: &NonNullType) {}
+    fn enter_directive(&mut self, _node: &Directive) {}
+    fn exit_directive(&mut self, _node: &Directive) {}
+    fn enter_description(&mut self, _node: &Description) {}
+    fn exit_description(&mut self, _node: &Description) {}
+    fn enter_root_operation_type_definition(&mut self, _node: &RootOperationTypeDefinition) {}
+    fn exit_root_operation_type_definition(&mut self, _node: &RootOperationTypeDefinition) {}
+    fn enter_implements_interfaces(&mut self, _node: &ImplementsInterfaces) {}
+    fn exit_implements_interfaces(&mut self, _node: &ImplementsInterfaces) {}
+    fn enter_fields_definition(&mut self, _node: &FieldsDefinition) {}
+    fn exit_fields_definition(&mut self, _node: &FieldsDefinition) {}
+    fn enter_field_definition(&mut self, _node: &FieldDefinition) {}
+    fn exit_field_definition(&mut self, _node: &FieldDefinition) {}
+    fn enter_arguments_definition(&mut self, _node: &ArgumentsDefinition) {}
+    fn exit_arguments_definition(&mut self, _node: &ArgumentsDefinition) {}
+    fn enter_input_value_definition(&mut self, _node: &InputValueDefinition) {}
+    fn exit_input_value_definition(&mut self, _node: &InputValueDefinition) {}
+    fn enter_union_member_types(&mut self, _node: &UnionMemberTypes) {}
+    fn exit_union_member_types(&mut self, _node: &UnionMemberTypes) {}
+    fn enter_enum_values_definition(&mut self, _node: &EnumValuesDefinition) {}
+    fn exit_enum_values_definition(&mut self, _node: &EnumValuesDefinition) {}
+    fn enter_enum_value_definition(&mut self, _node: &EnumValueDefinition) {}
+    fn exit_enum_value_definition(&mut self, _node: &EnumValueDefinition) {}
+    fn enter_input_fields_definition(&mut self, _node: &InputFieldsDefinition) {}
+    fn exit_input_fields_definition(&mut self, _node: &InputFieldsDefinition) {}
+    fn enter_directive_locations(&mut self, _node: &DirectiveLocations) {}
+    fn exit_directive_locations(&mut self, _node: &DirectiveLocations) {}
+    fn enter_directive_location(&mut self, _node: &DirectiveLocation) {}
+    fn exit_directive_location(&mut self, _node: &DirectiveLocation) {}
+}
+pub(crate) fn enter(visitor: &mut (impl Visitor + ?Sized), node: SyntaxNode) {
+    match node.kind() {
+        NAME => visitor.enter_name(&Name { syntax: node }),
+        DOCUMENT => visitor.enter_document(&Document { syntax: node }),
+        OPERATION_DEFINITION => {
+            visitor.enter_operation_definition(&OperationDefinition { syntax: node })
+        }
+        FRAGMENT_DEFINITION => {
+            visitor.enter_fragment_definition(&FragmentDefinition { syntax: node })
+        }
+        DIRECTIVE_DEFINITION => {
+            visitor.enter_directive_definition(&DirectiveDefinition { syntax: node })
+        }
+        SCHEMA_DEFINITION => visitor.enter_schema_definition(&SchemaDefinition { syntax: node }),
+        SCALAR_TYPE_DEFINITION => {
+            visitor.enter_scalar_type_definition(&ScalarTypeDefinition { syntax: node })
+        }
+        OBJECT_TYPE_DEFINITION => {
+            visitor.enter_object_type_definition(&ObjectTypeDefinition { syntax: node })
+        }
+        INTERFACE_TYPE_DEFINITION => {
+            visitor.enter_interface_type_definition(&InterfaceTypeDefinition { syntax: node })
+        }
+        UNION_TYPE_DEFINITION => {
+            visitor.enter_union_type_definition(&UnionTypeDefinition { syntax: node })
+        }
+        ENUM_TYPE_DEFINITION => {
+            visitor.enter_enum_type_definition(&EnumTypeDefinition { syntax: node })
+        }
+        INPUT_OBJECT_TYPE_DEFINITION => {
+            visitor.enter_input_object_type_definition(&InputObjectTypeDefinition { syntax: node })
+        }
+        SCHEMA_EXTENSION => visitor.enter_schema_extension(&SchemaExtension { syntax: node }),
+        SCALAR_TYPE_EXTENSION => {
+            visitor.enter_scalar_type_extension(&ScalarTypeExtension { syntax: node })
+        }
+        OBJECT_TYPE_EXTENSION => {
+            visitor.enter_object_type_extension(&ObjectTypeExtension { syntax: node })
+        }
+        INTERFACE_TYPE_EXTENSION => {
+            visitor.enter_interface_type_extension(&InterfaceTypeExtension { syntax: node })
+        }
+        UNION_TYPE_EXTENSION => {
+            visitor.enter_union_type_extension(&UnionTypeExtension { syntax: node })
+        }
+        ENUM_TYPE_EXTENSION => {
+            visitor.enter_enum_type_extension(&EnumTypeExtension { syntax: node })
+        }
+        INPUT_OBJECT_TYPE_EXTENSION => {
+            visitor.enter_input_object_type_extension(&InputObjectTypeExtension { syntax: node })
+        }
+        CUSTOM_DEFINITION => visitor.enter_custom_definition(&CustomDefinition { syntax: node }),
+        OPERATION_TYPE => visitor.enter_operation_type(&OperationType { syntax: node }),
+        VARIABLE_DEFINITIONS => {
+            visitor.enter_variable_definitions(&VariableDefinitions { syntax: node })
+        }
+        DIRECTIVES => visitor.enter_directives(&Directives { syntax: node }),
+        SELECTION_SET => visitor.enter_selection_set(&SelectionSet { syntax: node }),
+        FIELD => visitor.enter_field(&Field { syntax: node }),
+        FRAGMENT_SPREAD => visitor.enter_fragment_spread(&FragmentSpread { syntax: node }),
+        INLINE_FRAGMENT => visitor.enter_inline_fragment(&InlineFragment { syntax: node }),
+        ALIAS => visitor.enter_alias(&Alias { syntax: node }),
+        ARGUMENTS => visitor.enter_arguments(&Arguments { syntax: node }),
+        NULLABILITY => visitor.enter_nullability(&Nullability { syntax: node }),
+        ARGUMENT => visitor.enter_argument(&Argument { syntax: node }),
+        LIST_NULLABILITY => visitor.enter_list_nullability(&ListNullability { syntax: node }),
+        NULLABILITY_DESIGNATOR => {
+            visitor.enter_nullability_designator(&NullabilityDesignator { syntax: node })
+        }
+        FRAGMENT_NAME => visitor.enter_fragment_name(&FragmentName { syntax: node }),
+        TYPE_CONDITION => visitor.enter_type_condition(&TypeCondition { syntax: node }),
+        NAMED_TYPE => visitor.enter_named_type(&NamedType { syntax: node }),
+        VARIABLE => visitor.enter_variable(&Variable { syntax: node }),
+        STRING_VALUE => visitor.enter_string_value(&StringValue { syntax: node }),
+        FLOAT_VALUE => visitor.enter_float_value(&FloatValue { syntax: node }),
+        INT_VALUE => visitor.enter_int_value(&IntValue { syntax: node }),
+        BOOLEAN_VALUE => visitor.enter_boolean_value(&BooleanValue { syntax: node }),
+        NULL_VALUE => visitor.enter_null_value(&NullValue { syntax: node }),
+        ENUM_VALUE => visitor.enter_enum_value(&EnumValue { syntax: node }),
+        LIST_VALUE => visitor.enter_list_value(&ListValue { syntax: node }),
+        OBJECT_VALUE => visitor.enter_object_value(&ObjectValue { syntax: node }),
+        OBJECT_FIELD => visitor.enter_object_field(&ObjectField { syntax: node }),
+        VARIABLE_DEFINITION => {
+            visitor.enter_variable_definition(&VariableDefinition { syntax: node })
+        }
+        DEFAULT_VALUE => visitor.enter_default_value(&DefaultValue { syntax: node }),
+        LIST_TYPE => visitor.enter_list_type(&ListType { syntax: node }),
+        NON_NULL_TYPE => visitor.enter_non_null_type(&NonNullType { syntax: node }),
+        DIRECTIVE => visitor.enter_directive(&Directive { syntax: node }),
+        DESCRIPTION => visitor.enter_description(&Description { syntax: node }),
+        ROOT_OPERATION_TYPE_DEFINITION => visitor
+            .enter_root_operation_type_definition(&RootOperationTypeDefinition { syntax: node }),
+        IMPLEMENTS_INTERFACES => {
+            visitor.enter_implements_interfaces(&ImplementsInterfaces { syntax: node })
+        }
+        FIELDS_DEFINITION => visitor.enter_fields_definition(&FieldsDefinition { syntax: node }),
+        FIELD_DEFINITION => visitor.enter_field_definition(&FieldDefinition { syntax: node }),
+        ARGUMENTS_DEFINITION => {
+            visitor.enter_arguments_definition(&ArgumentsDefinition { syntax: node })
+        }
+        INPUT_VALUE_DEFINITION => {
+            visitor.enter_input_value_definition(&InputValueDefinition { syntax: node })
+        }
+        UNION_MEMBER_TYPES => visitor.enter_union_member_types(&UnionMemberTypes { syntax: node }),
+        ENUM_VALUES_DEFINITION => {
+            visitor.enter_enum_values_definition(&EnumValuesDefinition { syntax: node })
+        }
+        ENUM_VALUE_DEFINITION => {
+            visitor.enter_enum_value_definition(&EnumValueDefinition { syntax: node })
+        }
+        INPUT_FIELDS_DEFINITION => {
+            visitor.enter_input_fields_definition(&InputFieldsDefinition { syntax: node })
+        }
+        DIRECTIVE_LOCATIONS => {
+            visitor.enter_directive_locations(&DirectiveLocations { syntax: node })
+        }
+        DIRECTIVE_LOCATION => visitor.enter_directive_location(&DirectiveLocation { syntax: node }),
+        _ => {}
+    }
+}
+pub(crate) fn exit(visitor: &mut (impl Visitor + ?Sized), node: SyntaxNode) {
+    match node.kind() {
+        NAME => visitor.exit_name(&Name { syntax: node }),
+        DOCUMENT => visitor.exit_document(&Document { syntax: node }),
+        OPERATION_DEFINITION => {
+            visitor.exit_operation_definition(&OperationDefinition { syntax: node })
+        }
+        FRAGMENT_DEFINITION => {
+            visitor.exit_fragment_definition(&FragmentDefinition { syntax: node })
+        }
+        DIRECTIVE_DEFINITION => {
+            visitor.exit_directive_definition(&DirectiveDefinition { syntax: node })
+        }
+        SCHEMA_DEFINITION => visitor.exit_schema_definition(&SchemaDefinition { syntax: node }),
+        SCALAR_TYPE_DEFINITION => {
+            visitor.exit_scalar_type_definition(&ScalarTypeDefinition { syntax: node })
+        }
+        OBJECT_TYPE_DEFINITION => {
+            visitor.exit_object_type_definition(&ObjectTypeDefinition { syntax: node })
+        }
+        INTERFACE_TYPE_DEFINITION => {
+            visitor.exit_interface_type_definition(&InterfaceTypeDefinition { syntax: node })
+        }
+        UNION_TYPE_DEFINITION => {
+            visitor.exit_union_type_definition(&UnionTypeDefinition { syntax: node })
+        }
+        ENUM_TYPE_DEFINITION => {
+            visitor.exit_enum_type_definition(&EnumTypeDefinition { syntax: node })
+        }
+        INPUT_OBJECT_TYPE_DEFINITION => {
+            visitor.exit_input_object_type_definition(&InputObjectTypeDefinition { syntax: node })
+        }
+        SCHEMA_EXTENSION => visitor.exit_schema_extension(&SchemaExtension { syntax: node }),
+        SCALAR_TYPE_EXTENSION => {
+            visitor.exit_scalar_type_extension(&ScalarTypeExtension { syntax: node })
+        }
+        OBJECT_TYPE_EXTENSION => {
+            visitor.exit_object_type_extension(&ObjectTypeExtension { syntax: node })
+        }
+        INTERFACE_TYPE_EXTENSION => {
+            visitor.exit_interface_type_extension(&InterfaceTypeExtension { syntax: node })
+        }
+        UNION_TYPE_EXTENSION => {
+            visitor.exit_union_type_extension(&UnionTypeExtension { syntax: node })
+        }
+        ENUM_TYPE_EXTENSION => {
+            visitor.exit_enum_type_extension(&EnumTypeExtension { syntax: node })
+        }
+        INPUT_OBJECT_TYPE_EXTENSION => {
+            visitor.exit_input_object_type_extension(&InputObjectTypeExtension { syntax: node })
+        }
+        CUSTOM_DEFINITION => visitor.exit_custom_definition(&CustomDefinition { syntax: node }),
+        OPERATION_TYPE => visitor.exit_operation_type(&OperationType { syntax: node }),
+        VARIABLE_DEFINITIONS => {
+            visitor.exit_variable_definitions(&VariableDefinitions { syntax: node })
+        }
+        DIRECTIVES => visitor.exit_directives(&Directives { syntax: node }),
+        SELECTION_SET => visitor.exit_selection_set(&SelectionSet { syntax: node }),
+        FIELD => visitor.exit_field(&Field { syntax: node }),
+        FRAGMENT_SPREAD => visitor.exit_fragment_spread(&FragmentSpread { syntax: node }),
+        INLINE_FRAGMENT => visitor.exit_inline_fragment(&InlineFragment { syntax: node }),
+        ALIAS => visitor.exit_alias(&Alias { syntax: node }),
+        ARGUMENTS => visitor.exit_arguments(&Arguments { syntax: node }),
+        NULLABILITY => visitor.exit_nullability(&Nullability { syntax: node }),
+        ARGUMENT => visitor.exit_argument(&Argument { syntax: node }),
+        LIST_NULLABILITY => visitor.exit_list_nullability(&ListNullability { syntax: node }),
+        NULLABILITY_DESIGNATOR => {
+            visitor.exit_nullability_designator(&NullabilityDesignator { syntax: node })
+        }
+        FRAGMENT_NAME => visitor.exit_fragment_name(&FragmentName { syntax: node }),
+        TYPE_CONDITION => visitor.exit_type_condition(&TypeCondition { syntax: node }),
+        NAMED_TYPE => visitor.exit_named_type(&NamedType { syntax: node }),
+        VARIABLE => visitor.exit_variable(&Variable { syntax: node }),
+        STRING_VALUE => visitor.exit_string_value(&StringValue { syntax: node }),
+        FLOAT_VALUE => visitor.exit_float_value(&FloatValue { syntax: node }),
+        INT_VALUE => visitor.exit_int_value(&IntValue { syntax: node }),
+        BOOLEAN_VALUE => visitor.exit_boolean_value(&BooleanValue { syntax: node }),
+        NULL_VALUE => visitor.exit_null_value(&NullValue { syntax: node }),
+        ENUM_VALUE => visitor.exit_enum_value(&EnumValue { syntax: node }),
+        LIST_VALUE => visitor.exit_list_value(&ListValue { syntax: node }),
+        OBJECT_VALUE => visitor.exit_object_value(&ObjectValue { syntax: node }),
+        OBJECT_FIELD => visitor.exit_object_field(&ObjectField { syntax: node }),
+        VARIABLE_DEFINITION => {
+            visitor.exit_variable_definition(&VariableDefinition { syntax: node })
+        }
+        DEFAULT_VALUE => visitor.exit_default_value(&DefaultValue { syntax: node }),
+        LIST_TYPE => visitor.exit_list_type(&ListType { syntax: node }),
+        NON_NULL_TYPE => visitor.exit_non_null_type(&NonNullType { syntax: node }),
+        DIRECTIVE => visitor.exit_directive(&Directive { syntax: node }),
+        DESCRIPTION => visitor.exit_description(&Description { syntax: node }),
+        ROOT_OPERATION_TYPE_DEFINITION => visitor
+            .exit_root_operation_type_definition(&RootOperationTypeDefinition { syntax: node }),
+        IMPLEMENTS_INTERFACES => {
+            visitor.exit_implements_interfaces(&ImplementsInterfaces { syntax: node })
+        }
+        FIELDS_DEFINITION => visitor.exit_fields_definition(&FieldsDefinition { syntax: node }),
+        FIELD_DEFINITION => visitor.exit_field_definition(&FieldDefinition { syntax: node }),
+        ARGUMENTS_DEFINITION => {
+            visitor.exit_arguments_definition(&ArgumentsDefinition { syntax: node })
+        }
+        INPUT_VALUE_DEFINITION => {
+            visitor.exit_input_value_definition(&InputValueDefinition { syntax: node })
+        }
+        UNION_MEMBER_TYPES => visitor.exit_union_member_types(&UnionMemberTypes { syntax: node }),
+        ENUM_VALUES_DEFINITION => {
+            visitor.exit_enum_values_definition(&EnumValuesDefinition { syntax: node })
+        }
+        ENUM_VALUE_DEFINITION => {
+            visitor.exit_enum_value_definition(&EnumValueDefinition { syntax: node })
+        }
+        INPUT_FIELDS_DEFINITION => {
+            visitor.exit_input_fields_definition(&InputFieldsDefinition { syntax: node })
+        }
+        DIRECTIVE_LOCATIONS => {
+            visitor.exit_directive_locations(&DirectiveLocations { syntax: node })
+        }
+        DIRECTIVE_LOCATION => visitor.exit_directive_location(&DirectiveLocation { syntax: node }),
+        _ => {}
+    }
+}
@@ -104,6 +104,7 @@
 //! [GraphQL grammar]: https://spec.graphql.org/October2021/#sec-Document-Syntax
 mod generated;
 mod node_ext;
+mod visitor;
 
 pub use crate::parser::SyntaxNodePtr;
 use crate::SyntaxKind;
@@ -111,7 +112,9 @@ pub use crate::SyntaxNode;
 use crate::SyntaxNodeChildren;
 use crate::SyntaxToken;
 pub use generated::nodes::*;
+pub use generated::visitor::Visitor;
 use std::marker::PhantomData;
+pub use visitor::walk;
 
 /// The main trait to go from untyped `SyntaxNode`  to a typed CST. The
 /// conversion itself has zero runtime cost: CST and syntax nodes have exactly
@@ -132,6 +135,26 @@ pub trait CstNode {
         self.syntax().to_string()
     }
 
+    /// Returns the `#` comments just before this node, in source order.
+    ///
+    /// Like doc comments in Rust, this is the block of comment lines
+    /// directly preceding the node. Comments are not included if:
+    ///
+    /// * A blank line separates them from the node
+    /// * They are at the end of a line after other tokens, such as a previous field
+    ///   or an opening brace
+    fn leading_comments(&self) -> Vec<SyntaxToken> {
+        node_ext::leading_comments(self.syntax())
+    }
+
+    /// Returns the `#` comment at the end of the line where this node ends, if any,
+    /// such as `# in cents` in `price: Int # in cents`.
+    ///
+    /// Only whitespace and commas may separate the comment from the node.
+    fn trailing_comment(&self) -> Option<SyntaxToken> {
+        node_ext::trailing_comment(self.syntax())
+    }
+
     fn clone_for_update(&self) -> Self
     where
         Self: Sized,
@@ -0,0 +1,104 @@
+use crate::cst::generated::visitor;
+use crate::cst::Visitor;
+use crate::SyntaxNode;
+use rowan::WalkEvent;
+
+/// Traverse `node` and its descendants in source order, calling the [`Visitor`] methods
+/// for each node: `enter_*` before its children and `exit_*` after them.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::cst;
+/// use apollo_parser::cst::CstNode;
+/// use apollo_parser::Parser;
+///
+/// /// Finds fields selected without an alias, with a nesting depth
+/// #[derive(Default)]
+/// struct Fields {
+///     depth: usize,
+///     found: Vec<(usize, String)>,
+/// }
+///
+/// impl cst::Visitor for Fields {
+///     fn enter_selection_set(&mut self, _node: &cst::SelectionSet) {
+///         self.depth += 1;
+///     }
+///
+///     fn exit_selection_set(&mut self, _node: &cst::SelectionSet) {
+///         self.depth -= 1;
+///     }
+///
+///     fn enter_field(&mut self, node: &cst::Field) {
+///         if node.alias().is_none() {
+///             let name = node.name().unwrap().text().to_string();
+///             self.found.push((self.depth, name));
+///         }
+///     }
+/// }
+///
+/// let tree = Parser::new("{ me { name friends: contacts { name } } }").parse();
+/// let mut fields = Fields::default();
+/// cst::walk(&mut fields, tree.document().syntax());
+/// assert_eq!(
+///     fields.found,
+///     [(1, "me".to_string()), (2, "name".to_string()), (3, "name".to_string())]
+/// );
+/// ```
+pub fn walk(visitor: &mut (impl Visitor + ?Sized), node: &SyntaxNode) {
+    for event in node.preorder() {
+        match event {
+            WalkEvent::Enter(node) => visitor::enter(visitor, node),
+            WalkEvent::Leave(node) => visitor::exit(visitor, node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cst;
+    use crate::cst::CstNode;
+    use crate::Parser;
+
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl cst::Visitor for Events {
+        fn enter_object_type_definition(&mut self, node: &cst::ObjectTypeDefinition) {
+            self.0
+                .push(format!("enter {}", node.name().unwrap().text()));
+        }
+
+        fn exit_object_type_definition(&mut self, node: &cst::ObjectTypeDefinition) {
+            self.0.push(format!("exit {}", node.name().unwrap().text()));
+        }
+
+        fn enter_field_definition(&mut self, node: &cst::FieldDefinition) {
+            self.0
+                .push(format!("field {}", node.name().unwrap().text()));
+        }
+
+        fn enter_non_null_type(&mut self, node: &cst::NonNullType) {
+            self.0.push(format!("non-null {}", node.source_string()));
+        }
+    }
+
+    #[test]
+    fn enter_and_exit_in_source_order() {
+        let tree = Parser::new("type A { a: Int! } type B { b: [A!] }").parse();
+        let mut events = Events::default();
+        cst::walk(&mut events, tree.document().syntax());
+        assert_eq!(
+            events.0,
+            [
+                "enter A",
+                "field a",
+                "non-null Int!",
+                "exit A",
+                "enter B",
+                "field b",
+                "non-null A!",
+                "exit B",
+            ]
+        );
+    }
+}
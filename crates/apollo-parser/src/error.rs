@@ -1,4 +1,50 @@
-use std::fmt;
+#[cfg(feature = "parser")]
+use crate::SyntaxKind;
+use alloc::string::String;
+use core::fmt;
+
+/// The class of an [`Error`], to branch on or to look up a localized message
+/// instead of matching on [`Error::message`].
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::ErrorKind;
+/// use apollo_parser::Parser;
+///
+/// let cst = Parser::new("query { name(arg: \"unterminated) }").parse();
+/// let kinds: Vec<_> = cst.errors().map(|err| err.kind()).collect();
+/// assert_eq!(kinds[0], ErrorKind::UnterminatedString);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A character that cannot start a token, such as `%`
+    UnexpectedCharacter,
+    /// A string value without closing quotes,
+    /// or with a line terminator in a single-line string
+    UnterminatedString,
+    /// An invalid escape sequence in a string value, such as `\x` or `\u{D800}`
+    InvalidEscapeSequence,
+    /// A malformed number, such as `01`, `1.`, or `1e`
+    InvalidNumber,
+    /// `..` not followed by a third `.`
+    UnterminatedSpread,
+    /// The parser expected a token of the given kind, such as `R_CURLY` for `}`
+    #[cfg(feature = "parser")]
+    ExpectedToken(SyntaxKind),
+    /// The parser found a token that does not fit the grammar at this position
+    UnexpectedToken,
+    /// The input ended before the parser could complete a construct
+    UnexpectedEof,
+    /// The [token limit](crate::Parser::token_limit) was reached and lexing was aborted
+    TokenLimit,
+    /// The [recursion limit](crate::Parser::recursion_limit) was reached
+    /// and parsing was aborted
+    RecursionLimit,
+    /// The [node limit](crate::Parser::node_limit) was reached and parsing was aborted
+    NodeLimit,
+}
 
 /// An `Error` type for operations performed in the lexer and the parser.
 ///
@@ -35,6 +81,7 @@ use std::fmt;
 /// [miette crate]: https://docs.rs/miette/3.2.0/miette/index.html
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum ErrorData {
     Eof,
     LimitExceeded,
@@ -60,40 +107,58 @@ impl fmt::Display for ErrorData {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Hash, thiserror::Error)]
-#[error("ERROR@{index}:{} {message:?} {data}", .index + .data.len())]
+#[derive(PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Error {
     pub(crate) message: String,
+    pub(crate) kind: ErrorKind,
     pub(crate) data: ErrorData,
     pub(crate) index: usize,
 }
 
 impl Error {
     /// Create a new instance of `Error` with a `Location`.
+    /// Its kind is [`ErrorKind::UnexpectedToken`] unless set with [`with_kind`][Self::with_kind].
     pub fn with_loc<S: Into<String>>(message: S, data: String, index: usize) -> Self {
         Self {
             message: message.into(),
+            kind: ErrorKind::UnexpectedToken,
             data: ErrorData::Text(data),
             index,
         }
     }
 
+    /// Its kind is [`ErrorKind::TokenLimit`] unless set with [`with_kind`][Self::with_kind].
     pub fn limit<S: Into<String>>(message: S, index: usize) -> Self {
         Self {
             message: message.into(),
+            kind: ErrorKind::TokenLimit,
             data: ErrorData::LimitExceeded,
             index,
         }
     }
 
+    /// Its kind is [`ErrorKind::UnexpectedEof`] unless set with [`with_kind`][Self::with_kind].
     pub fn eof<S: Into<String>>(message: S, index: usize) -> Self {
         Self {
             message: message.into(),
+            kind: ErrorKind::UnexpectedEof,
             data: ErrorData::Eof,
             index,
         }
     }
 
+    /// Set the class of this error.
+    pub fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Get the class of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
     /// Get a reference to the error's data. This is usually the token that
     /// `apollo-parser` has found to be lexically or syntactically incorrect.
     pub fn data(&self) -> &str {
@@ -139,3 +204,12 @@ impl fmt::Debug for Error {
         )
     }
 }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,488 @@
+use crate::cst::CstNode;
+use crate::SyntaxKind;
+use crate::SyntaxNode;
+use crate::SyntaxToken;
+use crate::SyntaxTree;
+use rowan::NodeOrToken;
+
+/// Pretty-prints a parsed GraphQL document, keeping its comments.
+///
+/// The output has one definition per paragraph, one field, selection, enum value,
+/// or input field per line, and normalized spacing around punctuation.
+/// Argument lists, variable definitions, list values, and object values stay on one line
+/// if they fit within the maximum line length, and have one item per line otherwise.
+/// Commas are removed, except between items on the same line.
+///
+/// Comments are kept in place: a comment at the end of a line stays at the end of that line,
+/// and other comments are on their own line. A blank line between two items of a block
+/// is kept.
+/// String values, including block strings, are printed exactly as in the source.
+///
+/// Formatting a document with syntax errors keeps every token,
+/// but the layout around errors may be unexpected.
+///
+/// ## Example
+///
+/// ```rust
+/// use apollo_parser::Formatter;
+/// use apollo_parser::Parser;
+///
+/// let tree = Parser::new("query($id:ID!){user(id:$id){name # full name\n}}").parse();
+/// let formatted = Formatter::new().indent_width(4).format(&tree);
+/// assert_eq!(
+///     formatted,
+///     "query($id: ID!) {\n    user(id: $id) {\n        name # full name\n    }\n}\n",
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Formatter {
+    indent_width: usize,
+    max_line_length: usize,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter {
+    /// Create a formatter with an indentation of 2 spaces and a maximum line length of 80.
+    pub fn new() -> Self {
+        Self {
+            indent_width: 2,
+            max_line_length: 80,
+        }
+    }
+
+    /// Configure the number of spaces per indentation level.
+    pub fn indent_width(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self
+    }
+
+    /// Configure the line length above which lists of arguments and values
+    /// are split into one item per line.
+    ///
+    /// Lines can still be longer, for example with a long string value.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    /// Format a syntax tree.
+    pub fn format<T: CstNode>(&self, tree: &SyntaxTree<T>) -> String {
+        self.format_node(&tree.syntax_node())
+    }
+
+    /// Format a node of a syntax tree, such as a single definition.
+    pub fn format_node(&self, node: &SyntaxNode) -> String {
+        let mut printer = Printer {
+            formatter: self,
+            out: String::new(),
+            indent: 0,
+            pending: Break::None,
+            previous: None,
+            flat: false,
+        };
+        printer.node(node);
+        if !printer.out.is_empty() {
+            printer.out.push('\n');
+        }
+        printer.out
+    }
+}
+
+/// What to print before the next token, ordered from weakest to strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Break {
+    None,
+    Space,
+    Newline,
+    BlankLine,
+}
+
+struct Printer<'a> {
+    formatter: &'a Formatter,
+    out: String,
+    indent: usize,
+    pending: Break,
+    /// The last token printed
+    previous: Option<SyntaxToken>,
+    /// Whether every group is printed on one line
+    flat: bool,
+}
+
+impl Printer<'_> {
+    fn node(&mut self, node: &SyntaxNode) {
+        match node.kind() {
+            SyntaxKind::DOCUMENT => self.document(node),
+            SyntaxKind::ARGUMENTS
+            | SyntaxKind::ARGUMENTS_DEFINITION
+            | SyntaxKind::VARIABLE_DEFINITIONS
+            | SyntaxKind::LIST_VALUE
+            | SyntaxKind::OBJECT_VALUE => self.group(node),
+            _ => self.children(node, false),
+        }
+    }
+
+    fn document(&mut self, node: &SyntaxNode) {
+        for child in node.children_with_tokens() {
+            match child {
+                NodeOrToken::Node(definition) => {
+                    self.item_break(&definition);
+                    self.node(&definition);
+                    self.pending = Break::BlankLine;
+                }
+                NodeOrToken::Token(token) => self.token_or_trivia(&token),
+            }
+        }
+    }
+
+    /// Print a list of items between delimiters on one line if it fits, or one item per line.
+    fn group(&mut self, node: &SyntaxNode) {
+        if self.flat {
+            return self.children(node, true);
+        }
+        let has_comments = node
+            .descendants_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::COMMENT);
+        if !has_comments {
+            let checkpoint = (self.out.len(), self.pending, self.previous.clone());
+            self.flat = true;
+            self.children(node, true);
+            self.flat = false;
+            let line_start = self.out.rfind('\n').map_or(0, |index| index + 1);
+            let line_length = self.out[line_start..].chars().count();
+            let fits = !self.out[checkpoint.0..].contains('\n')
+                && line_length <= self.formatter.max_line_length;
+            if fits {
+                return;
+            }
+            self.out.truncate(checkpoint.0);
+            self.pending = checkpoint.1;
+            self.previous = checkpoint.2;
+        }
+        self.children(node, false)
+    }
+
+    /// Print the children of `node`. Nodes between braces or group delimiters are items,
+    /// printed one per line or separated by commas if `flat_items`.
+    fn children(&mut self, node: &SyntaxNode, flat_items: bool) {
+        let is_group = matches!(
+            node.kind(),
+            SyntaxKind::ARGUMENTS
+                | SyntaxKind::ARGUMENTS_DEFINITION
+                | SyntaxKind::VARIABLE_DEFINITIONS
+                | SyntaxKind::LIST_VALUE
+                | SyntaxKind::OBJECT_VALUE
+        );
+        let mut in_items = false;
+        let mut first_item = true;
+        for child in node.children_with_tokens() {
+            match child {
+                NodeOrToken::Node(child) => {
+                    if in_items {
+                        if flat_items {
+                            if !first_item {
+                                self.out.push(',');
+                            }
+                        } else {
+                            self.item_break(&child);
+                        }
+                        first_item = false;
+                    }
+                    self.node(&child);
+                    if child.kind() == SyntaxKind::DESCRIPTION && !self.flat {
+                        self.pending = self.pending.max(Break::Newline);
+                    }
+                }
+                NodeOrToken::Token(token) => {
+                    let opens = match token.kind() {
+                        SyntaxKind::L_CURLY => true,
+                        SyntaxKind::L_PAREN | SyntaxKind::L_BRACK => is_group,
+                        _ => false,
+                    };
+                    let closes = match token.kind() {
+                        SyntaxKind::R_CURLY => true,
+                        SyntaxKind::R_PAREN | SyntaxKind::R_BRACK => is_group,
+                        _ => false,
+                    };
+                    if closes && in_items {
+                        in_items = false;
+                        self.indent -= 1;
+                        if !flat_items && (!first_item || self.pending >= Break::Newline) {
+                            self.pending = Break::Newline;
+                        }
+                    }
+                    self.token_or_trivia(&token);
+                    if opens && !in_items {
+                        in_items = true;
+                        first_item = true;
+                        self.indent += 1;
+                    }
+                }
+            }
+        }
+        if in_items {
+            // Missing closing delimiter
+            self.indent -= 1;
+        }
+    }
+
+    /// Start a new line for an item of a block, keeping a blank line from the source
+    fn item_break(&mut self, item: &SyntaxNode) {
+        let blank_line = item
+            .first_token()
+            .and_then(|token| token.prev_token())
+            .is_some_and(|token| {
+                token.kind() == SyntaxKind::WHITESPACE && token.text().matches('\n').count() >= 2
+            });
+        let is_first = self.previous.as_ref().is_some_and(|token| {
+            matches!(
+                token.kind(),
+                SyntaxKind::L_CURLY | SyntaxKind::L_PAREN | SyntaxKind::L_BRACK
+            )
+        });
+        let line_break = if blank_line && !is_first {
+            Break::BlankLine
+        } else {
+            Break::Newline
+        };
+        self.pending = self.pending.max(line_break);
+    }
+
+    fn token_or_trivia(&mut self, token: &SyntaxToken) {
+        match token.kind() {
+            SyntaxKind::WHITESPACE | SyntaxKind::COMMA => {}
+            SyntaxKind::COMMENT => self.comment(token),
+            _ => {
+                if self.pending == Break::None && self.needs_space(token) {
+                    self.pending = Break::Space;
+                }
+                self.write(token.text());
+                self.previous = Some(token.clone());
+            }
+        }
+    }
+
+    fn comment(&mut self, token: &SyntaxToken) {
+        let own_line = !matches!(
+            token.prev_token(),
+            Some(previous) if previous.kind() == SyntaxKind::WHITESPACE
+                && !previous.text().contains('\n')
+        );
+        if own_line || self.previous.is_none() {
+            let blank_line = token.prev_token().is_some_and(|previous| {
+                previous.kind() == SyntaxKind::WHITESPACE
+                    && previous.text().matches('\n').count() >= 2
+            });
+            let line_break = if blank_line {
+                Break::BlankLine
+            } else {
+                Break::Newline
+            };
+            self.pending = self.pending.max(line_break);
+        } else {
+            self.pending = Break::Space;
+        }
+        self.write(token.text().trim_end());
+        self.pending = Break::Newline;
+    }
+
+    fn write(&mut self, text: &str) {
+        if !self.out.is_empty() {
+            match self.pending {
+                Break::None => {}
+                Break::Space => self.out.push(' '),
+                Break::Newline | Break::BlankLine => {
+                    self.out.push('\n');
+                    if self.pending == Break::BlankLine {
+                        self.out.push('\n');
+                    }
+                    let width = self.indent * self.formatter.indent_width;
+                    self.out.push_str(&" ".repeat(width));
+                }
+            }
+        }
+        self.pending = Break::None;
+        self.out.push_str(text);
+    }
+
+    /// Whether there is a space between the previous token and `next` on the same line
+    fn needs_space(&self, next: &SyntaxToken) -> bool {
+        let Some(previous) = &self.previous else {
+            return false;
+        };
+        if self.out.ends_with(',') {
+            return true;
+        }
+        match previous.kind() {
+            SyntaxKind::L_PAREN | SyntaxKind::L_BRACK | SyntaxKind::AT | SyntaxKind::DOLLAR => {
+                return false
+            }
+            SyntaxKind::SPREAD if next.kind() != SyntaxKind::on_KW => {
+                return next.parent().is_some_and(|parent| {
+                    parent.kind() != SyntaxKind::NAME
+                        || parent
+                            .parent()
+                            .is_some_and(|grand| grand.kind() != SyntaxKind::FRAGMENT_NAME)
+                })
+            }
+            SyntaxKind::L_CURLY if next.kind() == SyntaxKind::R_CURLY => return false,
+            _ => {}
+        }
+        match next.kind() {
+            SyntaxKind::R_PAREN
+            | SyntaxKind::R_BRACK
+            | SyntaxKind::COLON
+            | SyntaxKind::BANG
+            | SyntaxKind::QUESTION
+            | SyntaxKind::L_PAREN => false,
+            SyntaxKind::L_BRACK => next
+                .parent()
+                .is_some_and(|parent| parent.kind() != SyntaxKind::LIST_NULLABILITY),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Formatter;
+    use crate::Parser;
+    use crate::SyntaxKind;
+    use expect_test::expect;
+
+    /// Format `source` and check that the result has the same tokens and comments,
+    /// and formats to itself
+    fn format(source: &str) -> String {
+        let formatter = Formatter::new().max_line_length(60);
+        let tree = Parser::new(source).parse();
+        let formatted = formatter.format(&tree);
+        let reparsed = Parser::new(&formatted).parse();
+        let tokens = |tree: &crate::SyntaxTree| {
+            tree.syntax_node()
+                .descendants_with_tokens()
+                .filter_map(|element| element.into_token())
+                .filter(|token| !matches!(token.kind(), SyntaxKind::WHITESPACE | SyntaxKind::COMMA))
+                .map(|token| (token.kind(), token.text().trim_end().to_owned()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tokens(&tree), tokens(&reparsed));
+        assert_eq!(formatter.format(&reparsed), formatted);
+        formatted
+    }
+
+    #[test]
+    fn schema() {
+        let source = r#"
+# The schema
+schema{query:Query}
+"""
+Block description
+"""
+type Query implements Node&Entity @key(fields:"id"){
+  "Field description" id:ID! # trailing
+  users(first:Int=10,after:String,filter:UserFilter={active:true,roles:[ADMIN,USER]}):[User!]!
+
+  # Group two
+  node(id:ID!):Node @deprecated(reason:"use users")
+}
+union U=|A|B
+enum Role{ADMIN USER}
+directive @key(fields:String!)repeatable on OBJECT|INTERFACE
+extend input UserFilter{active:Boolean roles:[Role!]}
+"#;
+        expect![[r##"
+            # The schema
+            schema {
+              query: Query
+            }
+
+            """
+            Block description
+            """
+            type Query implements Node & Entity @key(fields: "id") {
+              "Field description"
+              id: ID! # trailing
+              users(
+                first: Int = 10
+                after: String
+                filter: UserFilter = {
+                  active: true
+                  roles: [ADMIN, USER]
+                }
+              ): [User!]!
+
+              # Group two
+              node(id: ID!): Node @deprecated(reason: "use users")
+            }
+
+            union U = | A | B
+
+            enum Role {
+              ADMIN
+              USER
+            }
+
+            directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+
+            extend input UserFilter {
+              active: Boolean
+              roles: [Role!]
+            }
+        "##]]
+        .assert_eq(&format(source));
+    }
+
+    #[test]
+    fn operations() {
+        let source = r#"query Q($a:Int=1,$b:[String!]!@dir){
+  alias:field(a:$a,b:$b){...Frag ...on T{x} ... @include(if:true){y}}
+  other(arg: [
+    1 # one
+    2
+  ])
+}
+fragment Frag on T{id}
+{__typename}"#;
+        expect![[r#"
+            query Q($a: Int = 1, $b: [String!]! @dir) {
+              alias: field(a: $a, b: $b) {
+                ...Frag
+                ... on T {
+                  x
+                }
+                ... @include(if: true) {
+                  y
+                }
+              }
+              other(
+                arg: [
+                  1 # one
+                  2
+                ]
+              )
+            }
+
+            fragment Frag on T {
+              id
+            }
+
+            {
+              __typename
+            }
+        "#]]
+        .assert_eq(&format(source));
+    }
+
+    #[test]
+    fn indent_width() {
+        let tree = Parser::new("{ a { b } }").parse();
+        assert_eq!(
+            Formatter::new().indent_width(4).format(&tree),
+            "{\n    a {\n        b\n    }\n}\n"
+        );
+    }
+}
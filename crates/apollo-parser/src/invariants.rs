@@ -0,0 +1,84 @@
+use crate::cst::CstNode;
+use crate::Lexer;
+use crate::Parser;
+use crate::SyntaxKind;
+use std::collections::BTreeSet;
+
+/// The result of [`check_invariants`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvariantCheck {
+    /// Kinds of syntax nodes and tokens found in the syntax tree.
+    /// Inputs with different kinds exercise different parts of the grammar.
+    pub syntax_kinds: BTreeSet<SyntaxKind>,
+    /// Descriptions of invariants of the lexer and parser that do not hold for this input.
+    /// Empty if all hold.
+    pub violations: Vec<String>,
+}
+
+/// Lex and parse `input`, and check invariants that should hold for any input,
+/// valid GraphQL or not. Meant for fuzzing and for replaying fuzzer findings as tests.
+///
+/// Checked invariants:
+///
+/// * The concatenated text of lexer tokens and errors is `input`
+/// * If there is no lexer or parser error, the text of the syntax tree is `input`
+/// * Lexer and parser errors point to a character boundary within `input`,
+///   or just after the end of `input` (like the end of file token)
+///
+/// Panics in the lexer or parser are not caught.
+///
+/// ```
+/// let check = apollo_parser::check_invariants("{ a(b: [1, 2]) }");
+/// assert!(check.violations.is_empty());
+/// assert!(check.syntax_kinds.contains(&apollo_parser::SyntaxKind::LIST_VALUE));
+/// ```
+pub fn check_invariants(input: &str) -> InvariantCheck {
+    let mut violations = Vec::new();
+    let check_index = |violations: &mut Vec<_>, kind: &str, index: usize, message: &str| {
+        if !input.is_char_boundary(index) && index != input.len() + 1 {
+            violations.push(format!(
+                "{kind} error index {index} is not a character boundary \
+                 (input length {}): {message}",
+                input.len()
+            ))
+        }
+    };
+
+    let mut lexed = String::with_capacity(input.len());
+    // The syntax tree may not contain the text of errors
+    let mut has_errors = false;
+    for item in Lexer::new(input) {
+        match item {
+            Ok(token) => lexed.push_str(token.data()),
+            Err(error) => {
+                has_errors = true;
+                check_index(&mut violations, "lexer", error.index(), error.message());
+                lexed.push_str(error.data())
+            }
+        }
+    }
+    if lexed != input {
+        violations.push(format!(
+            "lexer is not lossless: tokens concatenate to {lexed:?}"
+        ));
+    }
+
+    let tree = Parser::new(input).parse();
+    for error in tree.errors() {
+        has_errors = true;
+        check_index(&mut violations, "parser", error.index(), error.message());
+    }
+    let root = tree.document();
+    let root = root.syntax();
+    let text = root.to_string();
+    if !has_errors && text != input {
+        violations.push(format!("syntax tree is not lossless: its text is {text:?}"));
+    }
+    InvariantCheck {
+        syntax_kinds: root
+            .descendants_with_tokens()
+            .map(|element| element.kind())
+            .collect(),
+        violations,
+    }
+}
@@ -1,5 +1,5 @@
 use crate::Error;
-use std::str::CharIndices;
+use core::str::Chars;
 
 /// Peekable iterator over a char sequence.
 #[derive(Debug, Clone)]
@@ -7,7 +7,7 @@ pub(crate) struct Cursor<'a> {
     index: usize,
     pub(super) offset: usize,
     pub(super) source: &'a str,
-    chars: CharIndices<'a>,
+    chars: Chars<'a>,
     pending: Option<char>,
     pub(crate) err: Option<Error>,
 }
@@ -19,7 +19,7 @@ impl<'a> Cursor<'a> {
             offset: 0,
             pending: None,
             source: input,
-            chars: input.char_indices(),
+            chars: input.chars(),
             err: None,
         }
     }
@@ -53,7 +53,7 @@ impl<'a> Cursor<'a> {
     pub(crate) fn current_str(&mut self) -> &'a str {
         self.pending = None;
 
-        if let Some((pos, next)) = self.chars.next() {
+        if let Some((pos, next)) = self.next_char() {
             let current = self.index;
 
             self.index = pos;
@@ -80,7 +80,7 @@ impl<'a> Cursor<'a> {
             return None;
         }
 
-        let (pos, c) = self.chars.next()?;
+        let (pos, c) = self.next_char()?;
         self.offset = pos;
 
         Some(c)
@@ -92,7 +92,7 @@ impl<'a> Cursor<'a> {
             panic!("dont call eatc when a character is pending");
         }
 
-        if let Some((pos, c_in)) = self.chars.next() {
+        if let Some((pos, c_in)) = self.next_char() {
             self.offset = pos;
 
             if c_in == c {
@@ -105,6 +105,88 @@ impl<'a> Cursor<'a> {
         false
     }
 
+    /// Returns the next character and its byte offset in the source text.
+    fn next_char(&mut self) -> Option<(usize, char)> {
+        let pos = self.source.len() - self.chars.as_str().len();
+        self.chars.next().map(|c| (pos, c))
+    }
+
+    /// Skips the longest run of ASCII bytes matching `predicate`
+    /// after the character `c` last returned by `bump()`,
+    /// as if each of them had been returned by `bump()` in turn.
+    pub(crate) fn skip_ascii_while(&mut self, c: char, predicate: impl Fn(u8) -> bool) {
+        self.skip_until(c, |rest| rest.iter().position(|&byte| !predicate(byte)))
+    }
+
+    /// Like `skip_ascii_while()`, then also returns the current token
+    /// as `bump()` followed by `prev_str()` would,
+    /// if the character after the skipped run is ASCII.
+    ///
+    /// Returns `None` without ending the token if that character is not ASCII
+    /// or at the end of input, leaving those cases to the state machine.
+    pub(crate) fn take_ascii_while(
+        &mut self,
+        c: char,
+        predicate: impl Fn(u8) -> bool,
+    ) -> Option<&'a str> {
+        if !cfg!(feature = "fast-lexer") || self.pending.is_some() {
+            return None;
+        }
+        let start = self.offset + c.len_utf8();
+        let bytes = self.source.as_bytes();
+        let end = match bytes[start..].iter().position(|&byte| !predicate(byte)) {
+            Some(len) => start + len,
+            None => {
+                self.skip_to(start, bytes.len());
+                return None;
+            }
+        };
+        let next = bytes[end];
+        if !next.is_ascii() {
+            self.skip_to(start, end);
+            return None;
+        }
+        let slice = &self.source[self.index..end];
+        self.index = end;
+        self.offset = end;
+        self.pending = Some(next.into());
+        self.chars = self.source[end + 1..].chars();
+        Some(slice)
+    }
+
+    /// Skips source text after the character `c` last returned by `bump()`,
+    /// up to the byte offset (relative to after `c`) returned by `find`
+    /// or to the end of input if `find` returns `None`,
+    /// as if each character had been returned by `bump()` in turn.
+    ///
+    /// `find` must return an offset at a character boundary,
+    /// for example by searching for an ASCII byte with `memchr`.
+    pub(crate) fn skip_until(&mut self, c: char, find: impl FnOnce(&[u8]) -> Option<usize>) {
+        // Without the feature, or if `eatc()` left a character pending,
+        // fall back to stepping through characters one at a time.
+        if !cfg!(feature = "fast-lexer") || self.pending.is_some() {
+            return;
+        }
+        let start = self.offset + c.len_utf8();
+        let rest = &self.source.as_bytes()[start..];
+        let len = find(rest).unwrap_or(rest.len());
+        self.skip_to(start, start + len)
+    }
+
+    /// Moves to the character before byte offset `end`,
+    /// where `start` is the offset just after the current character.
+    fn skip_to(&mut self, start: usize, end: usize) {
+        if end == start {
+            return;
+        }
+        let mut last = end - 1;
+        while !self.source.is_char_boundary(last) {
+            last -= 1;
+        }
+        self.offset = last;
+        self.chars = self.source[end..].chars();
+    }
+
     /// Get current error object in the cursor.
     pub(crate) fn err(&mut self) -> Option<Error> {
         self.err.clone()
@@ -1,100 +1,121 @@
 use crate::TokenKind;
 
-static PUNCTUATION_CHARS: [Option<TokenKind>; 256] = punctuation_lut();
-static NAMESTART_CHARS: [bool; 256] = namestart_lut();
+/// How the lexer starts a token on a given ASCII byte.
+///
+/// A single table lookup replaces a chain of comparisons in the `Start` state,
+/// which runs once per token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ByteClass {
+    /// A single-character token
+    Punctuation(TokenKind),
+    /// <https://spec.graphql.org/October2021/#NameStart>
+    NameStart,
+    /// A non-zero digit, starting an integer part
+    Digit,
+    /// A leading zero, which must not be followed by other digits
+    Zero,
+    Minus,
+    Dot,
+    Quote,
+    Hash,
+    /// Ignored ASCII characters other than comments and commas
+    Whitespace,
+    /// Not valid at the start of a token
+    Other,
+}
+
+/// <https://spec.graphql.org/October2021/#NameContinue>
+const NAME_CONTINUE: u8 = 1 << 0;
+/// ASCII characters of <https://spec.graphql.org/October2021/#Ignored>
+/// other than comments and commas
+const WHITESPACE: u8 = 1 << 1;
 
+static BYTE_CLASSES: [ByteClass; 128] = byte_class_lut();
+static BYTE_FLAGS: [u8; 256] = byte_flags_lut();
+
+/// Returns the class of an ASCII character, or `None` for other characters.
 #[inline]
-pub(crate) fn punctuation_kind(c: char) -> Option<TokenKind> {
-    if c.is_ascii() {
-        PUNCTUATION_CHARS[c as usize]
-    } else {
-        None
-    }
+pub(crate) fn byte_class(c: char) -> Option<ByteClass> {
+    BYTE_CLASSES.get(c as usize).copied()
 }
 
 #[inline]
 pub(crate) fn is_namestart(c: char) -> bool {
-    c.is_ascii() && NAMESTART_CHARS[c as usize]
+    byte_class(c) == Some(ByteClass::NameStart)
 }
 
-const fn punctuation_lut() -> [Option<TokenKind>; 256] {
-    let mut lut = [None; 256];
-    lut[b'{' as usize] = Some(TokenKind::LCurly);
-    lut[b'}' as usize] = Some(TokenKind::RCurly);
-    lut[b'!' as usize] = Some(TokenKind::Bang);
-    lut[b'$' as usize] = Some(TokenKind::Dollar);
-    lut[b'&' as usize] = Some(TokenKind::Amp);
-    lut[b'(' as usize] = Some(TokenKind::LParen);
-    lut[b')' as usize] = Some(TokenKind::RParen);
-    lut[b':' as usize] = Some(TokenKind::Colon);
-    lut[b',' as usize] = Some(TokenKind::Comma);
-    lut[b'[' as usize] = Some(TokenKind::LBracket);
-    lut[b']' as usize] = Some(TokenKind::RBracket);
-    lut[b'=' as usize] = Some(TokenKind::Eq);
-    lut[b'@' as usize] = Some(TokenKind::At);
-    lut[b'|' as usize] = Some(TokenKind::Pipe);
+#[inline]
+pub(crate) fn is_name_continue_byte(byte: u8) -> bool {
+    BYTE_FLAGS[byte as usize] & NAME_CONTINUE != 0
+}
 
-    lut
+/// The Unicode BOM is also assimilated to whitespace, but is not ASCII.
+#[inline]
+pub(crate) fn is_whitespace_byte(byte: u8) -> bool {
+    BYTE_FLAGS[byte as usize] & WHITESPACE != 0
 }
 
-/// <https://spec.graphql.org/October2021/#NameStart>
-const fn namestart_lut() -> [bool; 256] {
-    let mut lut = [false; 256];
-    lut[b'a' as usize] = true;
-    lut[b'b' as usize] = true;
-    lut[b'c' as usize] = true;
-    lut[b'd' as usize] = true;
-    lut[b'e' as usize] = true;
-    lut[b'f' as usize] = true;
-    lut[b'g' as usize] = true;
-    lut[b'h' as usize] = true;
-    lut[b'i' as usize] = true;
-    lut[b'j' as usize] = true;
-    lut[b'k' as usize] = true;
-    lut[b'l' as usize] = true;
-    lut[b'm' as usize] = true;
-    lut[b'n' as usize] = true;
-    lut[b'o' as usize] = true;
-    lut[b'p' as usize] = true;
-    lut[b'q' as usize] = true;
-    lut[b'r' as usize] = true;
-    lut[b's' as usize] = true;
-    lut[b't' as usize] = true;
-    lut[b'u' as usize] = true;
-    lut[b'v' as usize] = true;
-    lut[b'w' as usize] = true;
-    lut[b'x' as usize] = true;
-    lut[b'y' as usize] = true;
-    lut[b'z' as usize] = true;
+const fn byte_class_lut() -> [ByteClass; 128] {
+    let mut lut = [ByteClass::Other; 128];
+    lut[b'{' as usize] = ByteClass::Punctuation(TokenKind::LCurly);
+    lut[b'}' as usize] = ByteClass::Punctuation(TokenKind::RCurly);
+    lut[b'!' as usize] = ByteClass::Punctuation(TokenKind::Bang);
+    lut[b'$' as usize] = ByteClass::Punctuation(TokenKind::Dollar);
+    lut[b'&' as usize] = ByteClass::Punctuation(TokenKind::Amp);
+    lut[b'(' as usize] = ByteClass::Punctuation(TokenKind::LParen);
+    lut[b')' as usize] = ByteClass::Punctuation(TokenKind::RParen);
+    lut[b':' as usize] = ByteClass::Punctuation(TokenKind::Colon);
+    lut[b',' as usize] = ByteClass::Punctuation(TokenKind::Comma);
+    lut[b'[' as usize] = ByteClass::Punctuation(TokenKind::LBracket);
+    lut[b']' as usize] = ByteClass::Punctuation(TokenKind::RBracket);
+    lut[b'=' as usize] = ByteClass::Punctuation(TokenKind::Eq);
+    lut[b'@' as usize] = ByteClass::Punctuation(TokenKind::At);
+    lut[b'|' as usize] = ByteClass::Punctuation(TokenKind::Pipe);
+    // Nullability designator from the client-controlled nullability RFC
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    {
+        lut[b'?' as usize] = ByteClass::Punctuation(TokenKind::Question);
+    }
 
-    lut[b'A' as usize] = true;
-    lut[b'B' as usize] = true;
-    lut[b'C' as usize] = true;
-    lut[b'D' as usize] = true;
-    lut[b'E' as usize] = true;
-    lut[b'F' as usize] = true;
-    lut[b'G' as usize] = true;
-    lut[b'H' as usize] = true;
-    lut[b'I' as usize] = true;
-    lut[b'J' as usize] = true;
-    lut[b'K' as usize] = true;
-    lut[b'L' as usize] = true;
-    lut[b'M' as usize] = true;
-    lut[b'N' as usize] = true;
-    lut[b'O' as usize] = true;
-    lut[b'P' as usize] = true;
-    lut[b'Q' as usize] = true;
-    lut[b'R' as usize] = true;
-    lut[b'S' as usize] = true;
-    lut[b'T' as usize] = true;
-    lut[b'U' as usize] = true;
-    lut[b'V' as usize] = true;
-    lut[b'W' as usize] = true;
-    lut[b'X' as usize] = true;
-    lut[b'Y' as usize] = true;
-    lut[b'Z' as usize] = true;
+    let mut byte = 0;
+    while byte < 128 {
+        if matches!(byte, b'a'..=b'z' | b'A'..=b'Z' | b'_') {
+            lut[byte as usize] = ByteClass::NameStart;
+        } else if matches!(byte, b'1'..=b'9') {
+            lut[byte as usize] = ByteClass::Digit;
+        }
+        byte += 1;
+    }
+    lut[b'0' as usize] = ByteClass::Zero;
+    lut[b'-' as usize] = ByteClass::Minus;
+    lut[b'.' as usize] = ByteClass::Dot;
+    lut[b'"' as usize] = ByteClass::Quote;
+    lut[b'#' as usize] = ByteClass::Hash;
 
-    lut[b'_' as usize] = true;
+    // https://spec.graphql.org/October2021/#WhiteSpace
+    lut[b'\t' as usize] = ByteClass::Whitespace;
+    lut[b' ' as usize] = ByteClass::Whitespace;
+    // https://spec.graphql.org/October2021/#LineTerminator
+    lut[b'\n' as usize] = ByteClass::Whitespace;
+    lut[b'\r' as usize] = ByteClass::Whitespace;
 
     lut
 }
+
+/// Non-ASCII bytes have no flags,
+/// so scanning a run of flagged bytes always stops at a character boundary.
+const fn byte_flags_lut() -> [u8; 256] {
+    let classes = byte_class_lut();
+    let mut lut = [0; 256];
+    let mut byte = 0;
+    while byte < 128 {
+        if matches!(byte, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_') {
+            lut[byte as usize] |= NAME_CONTINUE;
+        }
+        if matches!(classes[byte as usize], ByteClass::Whitespace) {
+            lut[byte as usize] |= WHITESPACE;
+        }
+        byte += 1;
+    }
+    lut
+}
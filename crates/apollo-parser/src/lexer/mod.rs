@@ -1,11 +1,23 @@
 mod cursor;
 mod lookup;
+mod semantic_tokens;
+mod string_value;
 mod token;
 mod token_kind;
 
 use crate::lexer::cursor::Cursor;
+use crate::lexer::lookup::ByteClass;
 use crate::Error;
+use crate::ErrorKind;
 use crate::LimitTracker;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+pub use semantic_tokens::SemanticToken;
+pub use semantic_tokens::SemanticTokens;
+pub(crate) use string_value::cook_string_value;
 pub use token::Token;
 pub use token_kind::TokenKind;
 
@@ -88,6 +100,12 @@ impl<'a> Lexer<'a> {
         self
     }
 
+    /// Stop producing tokens, as if the end of the input was reached.
+    #[cfg(feature = "parser")]
+    pub(crate) fn abort(&mut self) {
+        self.finished = true;
+    }
+
     /// Lex the full source text, consuming the lexer.
     pub fn lex(self) -> (Vec<Token<'a>>, Vec<Error>) {
         let mut tokens = vec![];
@@ -148,59 +166,67 @@ impl<'a> Cursor<'a> {
             };
             match state {
                 State::Start => {
-                    if let Some(t) = lookup::punctuation_kind(c) {
-                        token.kind = t;
-                        token.data = self.current_str();
-                        return Ok(token);
-                    }
-
-                    if lookup::is_namestart(c) {
-                        token.kind = TokenKind::Name;
-                        state = State::Ident;
-
-                        continue;
-                    }
-
-                    if c != '0' && c.is_ascii_digit() {
-                        token.kind = TokenKind::Int;
-                        state = State::IntegerPart;
-
-                        continue;
-                    }
-
-                    match c {
-                        '"' => {
-                            token.kind = TokenKind::StringValue;
-                            state = State::StringLiteralStart;
+                    let Some(class) = lookup::byte_class(c) else {
+                        // The BOM is the only non-ASCII character that can start a token
+                        if is_whitespace_assimilated(c) {
+                            token.kind = TokenKind::Whitespace;
+                            state = State::Whitespace;
+                            continue;
                         }
-                        '#' => {
-                            token.kind = TokenKind::Comment;
-                            state = State::Comment;
+                        return Err(self.unexpected_character(c, &token));
+                    };
+                    match class {
+                        ByteClass::Punctuation(kind) => {
+                            token.kind = kind;
+                            token.data = self.current_str();
+                            return Ok(token);
                         }
-                        '.' => {
-                            token.kind = TokenKind::Spread;
-                            state = State::SpreadOperator;
+                        ByteClass::NameStart => {
+                            token.kind = TokenKind::Name;
+                            if let Some(data) =
+                                self.take_ascii_while(c, lookup::is_name_continue_byte)
+                            {
+                                token.data = data;
+                                return self.done(token);
+                            }
+                            state = State::Ident;
                         }
-                        '-' => {
+                        ByteClass::Digit => {
                             token.kind = TokenKind::Int;
-                            state = State::MinusSign;
+                            state = State::IntegerPart;
                         }
-                        '0' => {
+                        ByteClass::Zero => {
                             token.kind = TokenKind::Int;
                             state = State::LeadingZero;
                         }
-                        c if is_whitespace_assimilated(c) => {
+                        ByteClass::Minus => {
+                            token.kind = TokenKind::Int;
+                            state = State::MinusSign;
+                        }
+                        ByteClass::Dot => {
+                            token.kind = TokenKind::Spread;
+                            state = State::SpreadOperator;
+                        }
+                        ByteClass::Quote => {
+                            token.kind = TokenKind::StringValue;
+                            state = State::StringLiteralStart;
+                        }
+                        ByteClass::Hash => {
+                            token.kind = TokenKind::Comment;
+                            state = State::Comment;
+                            self.skip_until(c, |rest| memchr::memchr2(b'\n', b'\r', rest));
+                        }
+                        ByteClass::Whitespace => {
                             token.kind = TokenKind::Whitespace;
+                            if let Some(data) = self.take_ascii_while(c, lookup::is_whitespace_byte)
+                            {
+                                token.data = data;
+                                return self.done(token);
+                            }
                             state = State::Whitespace;
                         }
-                        c => {
-                            return Err(Error::with_loc(
-                                format!("Unexpected character \"{}\"", c),
-                                self.current_str().to_string(),
-                                token.index,
-                            ));
-                        }
-                    };
+                        ByteClass::Other => return Err(self.unexpected_character(c, &token)),
+                    }
                 }
                 State::Ident => match c {
                     curr if is_name_continue(curr) => {}
@@ -210,7 +236,9 @@ impl<'a> Cursor<'a> {
                     }
                 },
                 State::Whitespace => match c {
-                    curr if is_whitespace_assimilated(curr) => {}
+                    curr if is_whitespace_assimilated(curr) => {
+                        self.skip_ascii_while(curr, lookup::is_whitespace_byte);
+                    }
                     _ => {
                         token.data = self.prev_str();
                         return self.done(token);
@@ -220,14 +248,14 @@ impl<'a> Cursor<'a> {
                     '\\' => {
                         state = State::BlockStringLiteralBackslash;
                     }
-                    '"' => {
-                        // Require two additional quotes to complete the triple quote.
-                        if self.eatc('"') && self.eatc('"') {
-                            token.data = self.current_str();
-                            return self.done(token);
-                        }
+                    // Require two additional quotes to complete the triple quote.
+                    '"' if self.eatc('"') && self.eatc('"') => {
+                        token.data = self.current_str();
+                        return self.done(token);
+                    }
+                    curr => {
+                        self.skip_until(curr, |rest| memchr::memchr2(b'"', b'\\', rest));
                     }
-                    _ => {}
                 },
                 State::StringLiteralStart => match c {
                     '"' => {
@@ -255,20 +283,22 @@ impl<'a> Cursor<'a> {
                 },
                 State::StringLiteralEscapedUnicode(remaining) => match c {
                     '"' => {
-                        self.add_err(Error::with_loc(
-                            "incomplete unicode escape sequence",
-                            c.to_string(),
-                            token.index,
-                        ));
+                        self.add_err(
+                            Error::with_loc(
+                                "incomplete unicode escape sequence",
+                                c.to_string(),
+                                token.index,
+                            )
+                            .with_kind(ErrorKind::InvalidEscapeSequence),
+                        );
                         token.data = self.current_str();
                         return self.done(token);
                     }
                     c if !c.is_ascii_hexdigit() => {
-                        self.add_err(Error::with_loc(
-                            "invalid unicode escape sequence",
-                            c.to_string(),
-                            0,
-                        ));
+                        self.add_err(
+                            Error::with_loc("invalid unicode escape sequence", c.to_string(), 0)
+                                .with_kind(ErrorKind::InvalidEscapeSequence),
+                        );
                         state = State::StringLiteral;
 
                         continue;
@@ -293,7 +323,8 @@ impl<'a> Cursor<'a> {
                                      https://github.com/apollographql/apollo-rs/issues/657)",
                                     escape_sequence.to_owned(),
                                     0,
-                                ));
+                                )
+                                .with_kind(ErrorKind::InvalidEscapeSequence));
                             }
                             continue;
                         }
@@ -307,16 +338,21 @@ impl<'a> Cursor<'a> {
                         return self.done(token);
                     }
                     curr if is_line_terminator(curr) => {
-                        self.add_err(Error::with_loc(
-                            "unexpected line terminator",
-                            "".to_string(),
-                            0,
-                        ));
+                        self.add_err(
+                            Error::with_loc("unexpected line terminator", "".to_string(), 0)
+                                .with_kind(ErrorKind::UnterminatedString),
+                        );
                     }
                     '\\' => {
                         state = State::StringLiteralBackslash;
                     }
-                    _ => {}
+                    curr => {
+                        self.skip_until(curr, |rest| {
+                            let end =
+                                memchr::memchr3(b'"', b'\\', b'\n', rest).unwrap_or(rest.len());
+                            memchr::memchr(b'\r', &rest[..end]).or(Some(end))
+                        });
+                    }
                 },
                 State::BlockStringLiteralBackslash => match c {
                     '"' => {
@@ -347,11 +383,10 @@ impl<'a> Cursor<'a> {
                         state = State::StringLiteralEscapedUnicode(4);
                     }
                     _ => {
-                        self.add_err(Error::with_loc(
-                            "unexpected escaped character",
-                            c.to_string(),
-                            0,
-                        ));
+                        self.add_err(
+                            Error::with_loc("unexpected escaped character", c.to_string(), 0)
+                                .with_kind(ErrorKind::InvalidEscapeSequence),
+                        );
 
                         state = State::StringLiteral;
                     }
@@ -370,14 +405,16 @@ impl<'a> Cursor<'a> {
                             "Numbers must not have non-significant leading zeroes",
                             self.current_str().to_string(),
                             token.index,
-                        ));
+                        )
+                        .with_kind(ErrorKind::InvalidNumber));
                     }
                     _ if lookup::is_namestart(c) => {
                         return Err(Error::with_loc(
                             format!("Unexpected character `{c}` as integer suffix"),
                             self.current_str().to_string(),
                             token.index,
-                        ));
+                        )
+                        .with_kind(ErrorKind::InvalidNumber));
                     }
                     _ => {
                         token.data = self.prev_str();
@@ -385,7 +422,9 @@ impl<'a> Cursor<'a> {
                     }
                 },
                 State::IntegerPart => match c {
-                    curr if curr.is_ascii_digit() => {}
+                    curr if curr.is_ascii_digit() => {
+                        self.skip_ascii_while(curr, |byte| byte.is_ascii_digit());
+                    }
                     '.' => {
                         token.kind = TokenKind::Float;
                         state = State::DecimalPoint;
@@ -399,7 +438,8 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}` as integer suffix"),
                             self.current_str().to_string(),
                             token.index,
-                        ));
+                        )
+                        .with_kind(ErrorKind::InvalidNumber));
                     }
                     _ => {
                         token.data = self.prev_str();
@@ -415,11 +455,14 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}`, expected fractional digit"),
                             self.current_str().to_string(),
                             token.index,
-                        ));
+                        )
+                        .with_kind(ErrorKind::InvalidNumber));
                     }
                 },
                 State::FractionalPart => match c {
-                    curr if curr.is_ascii_digit() => {}
+                    curr if curr.is_ascii_digit() => {
+                        self.skip_ascii_while(curr, |byte| byte.is_ascii_digit());
+                    }
                     'e' | 'E' => {
                         state = State::ExponentIndicator;
                     }
@@ -428,7 +471,8 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}` as float suffix"),
                             self.current_str().to_string(),
                             token.index,
-                        ));
+                        )
+                        .with_kind(ErrorKind::InvalidNumber));
                     }
                     _ => {
                         token.data = self.prev_str();
@@ -447,7 +491,8 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}`, expected exponent digit or sign"),
                             self.current_str().to_string(),
                             token.index,
-                        ))
+                        )
+                        .with_kind(ErrorKind::InvalidNumber))
                     }
                 },
                 State::ExponentSign => match c {
@@ -459,7 +504,8 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}`, expected exponent digit"),
                             self.current_str().to_string(),
                             token.index,
-                        ))
+                        )
+                        .with_kind(ErrorKind::InvalidNumber))
                     }
                 },
                 State::ExponentDigit => match c {
@@ -471,7 +517,8 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}` as float suffix"),
                             self.current_str().to_string(),
                             token.index,
-                        ));
+                        )
+                        .with_kind(ErrorKind::InvalidNumber));
                     }
                     _ => {
                         token.data = self.prev_str();
@@ -497,7 +544,8 @@ impl<'a> Cursor<'a> {
                             format!("Unexpected character `{c}`"),
                             self.current_str().to_string(),
                             token.index,
-                        ))
+                        )
+                        .with_kind(ErrorKind::InvalidNumber))
                     }
                 },
                 State::Comment => match c {
@@ -505,7 +553,9 @@ impl<'a> Cursor<'a> {
                         token.data = self.prev_str();
                         return self.done(token);
                     }
-                    _ => {}
+                    curr => {
+                        self.skip_until(curr, |rest| memchr::memchr2(b'\n', b'\r', rest));
+                    }
                 },
             }
         }
@@ -524,7 +574,8 @@ impl<'a> Cursor<'a> {
                     "unexpected end of data while lexing string value",
                     curr.to_string(),
                     token.index,
-                ))
+                )
+                .with_kind(ErrorKind::UnterminatedString))
             }
             State::StringLiteral
             | State::BlockStringLiteral
@@ -533,24 +584,25 @@ impl<'a> Cursor<'a> {
             | State::StringLiteralBackslash => {
                 let curr = self.drain();
 
-                Err(Error::with_loc(
-                    "unterminated string value",
-                    curr.to_string(),
-                    token.index,
-                ))
+                Err(
+                    Error::with_loc("unterminated string value", curr.to_string(), token.index)
+                        .with_kind(ErrorKind::UnterminatedString),
+                )
             }
             State::SpreadOperator => self.unterminated_spread_operator(&token),
             State::MinusSign => Err(Error::with_loc(
                 "Unexpected character \"-\"",
                 self.current_str().to_string(),
                 token.index,
-            )),
+            )
+            .with_kind(ErrorKind::InvalidNumber)),
             State::DecimalPoint | State::ExponentIndicator | State::ExponentSign => {
                 Err(Error::with_loc(
                     "Unexpected EOF in float value",
                     self.current_str().to_string(),
                     token.index,
-                ))
+                )
+                .with_kind(ErrorKind::InvalidNumber))
             }
             State::Ident
             | State::LeadingZero
@@ -571,6 +623,15 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    fn unexpected_character(&mut self, c: char, token: &Token<'a>) -> Error {
+        Error::with_loc(
+            format!("Unexpected character \"{}\"", c),
+            self.current_str().to_string(),
+            token.index,
+        )
+        .with_kind(ErrorKind::UnexpectedCharacter)
+    }
+
     fn unterminated_spread_operator(&mut self, token: &Token<'a>) -> Result<Token<'a>, Error> {
         let data = if self.is_pending() {
             self.prev_str()
@@ -582,7 +643,8 @@ impl<'a> Cursor<'a> {
             "Unterminated spread operator",
             data.to_string(),
             token.index,
-        ))
+        )
+        .with_kind(ErrorKind::UnterminatedSpread))
     }
 
     fn done(&mut self, token: Token<'a>) -> Result<Token<'a>, Error> {
@@ -680,7 +742,8 @@ type Query {
         assert_eq!(
             errors,
             &[
-                Error::with_loc("Unterminated spread operator", "..".to_string(), 13),
+                Error::with_loc("Unterminated spread operator", "..".to_string(), 13)
+                    .with_kind(ErrorKind::UnterminatedSpread),
                 Error::limit("token limit reached, aborting lexing", 18),
             ],
         );
@@ -756,7 +819,8 @@ Escape character followed by a quote:
         "#
                 .to_string(),
                 59,
-            )]
+            )
+            .with_kind(ErrorKind::UnterminatedString)]
         );
     }
 
@@ -772,11 +836,121 @@ type Query {
         dbg!(tokens);
         assert_eq!(
             errors,
-            &[Error::with_loc(
-                "Unexpected character \"/\"",
-                "/".to_string(),
-                33,
-            )]
+            &[
+                Error::with_loc("Unexpected character \"/\"", "/".to_string(), 33,)
+                    .with_kind(ErrorKind::UnexpectedCharacter)
+            ]
         );
     }
+
+    #[test]
+    fn semantic_tokens() {
+        let input = r#"
+query ($id: ID!) {
+    # a comment
+    node(id: $id, note: """
+        indented
+          block
+    """) { ...frag }
+} /
+"#;
+        let tokens: Vec<_> = Lexer::new(input).semantic_tokens().collect();
+        let dumped: Vec<_> = tokens
+            .iter()
+            .map(|token| match token {
+                Ok(token) => format!("{:?} {:?}", token.kind(), token.cooked_value()),
+                Err(err) => format!("{err}"),
+            })
+            .collect();
+        assert_eq!(
+            dumped,
+            [
+                r#"Name "query""#,
+                r#"LParen "(""#,
+                r#"Dollar "$""#,
+                r#"Name "id""#,
+                r#"Colon ":""#,
+                r#"Name "ID""#,
+                r#"Bang "!""#,
+                r#"RParen ")""#,
+                r#"LCurly "{""#,
+                r#"Name "node""#,
+                r#"LParen "(""#,
+                r#"Name "id""#,
+                r#"Colon ":""#,
+                r#"Dollar "$""#,
+                r#"Name "id""#,
+                r#"Name "note""#,
+                r#"Colon ":""#,
+                r#"StringValue "indented\n  block""#,
+                r#"RParen ")""#,
+                r#"LCurly "{""#,
+                r#"Spread "...""#,
+                r#"Name "frag""#,
+                r#"RCurly "}""#,
+                r#"RCurly "}""#,
+                r#"ERROR@120:121 "Unexpected character \"/\"" /"#,
+            ]
+        );
+        let node = tokens[9].as_ref().unwrap();
+        assert_eq!(&input[node.range()], "node");
+    }
+
+    #[test]
+    fn multi_byte_characters_in_skipped_text() {
+        let input = "# café ☕\r\n\"naïve\" \"\"\"ünï\\\"\"\"code\"\"\" 1234.5678 \u{FEFF}ok \"bad\rline\" # 末尾";
+        let tokens: Vec<_> = Lexer::new(input)
+            .map(|token| match token {
+                Ok(token) => format!("{:?}@{} {:?}", token.kind(), token.index(), token.data()),
+                Err(err) => format!("{err:?}"),
+            })
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                r##"Comment@0 "# café ☕""##,
+                r#"Whitespace@11 "\r\n""#,
+                r#"StringValue@13 "\"naïve\"""#,
+                r#"Whitespace@21 " ""#,
+                r#"StringValue@22 "\"\"\"ünï\\\"\"\"code\"\"\"""#,
+                r#"Whitespace@41 " ""#,
+                r#"Float@42 "1234.5678""#,
+                r#"Whitespace@51 " \u{feff}""#,
+                r#"Name@55 "ok""#,
+                r#"Whitespace@57 " ""#,
+                "ERROR@58:68 \"unexpected line terminator\" \"bad\rline\"",
+                r#"Whitespace@68 " ""#,
+                r##"Comment@69 "# 末尾""##,
+                r#"Eof@77 """#,
+            ]
+        );
+    }
+
+    #[test]
+    fn byte_tables_match_spec_definitions() {
+        for byte in 0..=u8::MAX {
+            let c = char::from(byte);
+            assert_eq!(lookup::is_name_continue_byte(byte), is_name_continue(c));
+            assert_eq!(
+                lookup::is_whitespace_byte(byte),
+                is_whitespace_assimilated(c)
+            );
+            let Some(class) = lookup::byte_class(c) else {
+                assert!(!byte.is_ascii());
+                continue;
+            };
+            assert_eq!(class == ByteClass::NameStart, lookup::is_namestart(c));
+            assert_eq!(
+                class == ByteClass::NameStart,
+                c.is_ascii_alphabetic() || c == '_'
+            );
+            assert_eq!(class == ByteClass::Whitespace, is_whitespace_assimilated(c));
+            if let ByteClass::Punctuation(kind) = class {
+                let input = c.to_string();
+                let (tokens, errors) = Lexer::new(&input).lex();
+                assert_eq!(errors, []);
+                assert_eq!(tokens[0].kind(), kind);
+            }
+        }
+    }
 }
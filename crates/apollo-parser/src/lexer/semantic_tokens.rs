@@ -0,0 +1,125 @@
+use crate::lexer::cook_string_value;
+use crate::Error;
+use crate::Lexer;
+use crate::TokenKind;
+use alloc::borrow::Cow;
+
+/// A token that is significant to the meaning of a GraphQL document,
+/// returned by [`Lexer::semantic_tokens`].
+///
+/// Unlike [`Token`][crate::Token], this includes the token’s source range
+/// and can compute its cooked value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    index: usize,
+}
+
+/// An iterator of [`SemanticToken`]s, created by [`Lexer::semantic_tokens`].
+///
+/// Whitespace, comments, commas, and the end-of-file token are skipped.
+/// Lexing errors are yielded in between tokens, like with [`Lexer`].
+#[derive(Clone, Debug)]
+pub struct SemanticTokens<'a> {
+    lexer: Lexer<'a>,
+}
+
+impl<'a> Lexer<'a> {
+    /// Returns an iterator of tokens significant to the meaning of the document.
+    ///
+    /// This does not build a syntax tree,
+    /// making it suitable for high-throughput scanners such as operation hashing,
+    /// minification, or extracting GraphQL embedded in other files.
+    ///
+    /// ```rust
+    /// use apollo_parser::Lexer;
+    /// use apollo_parser::TokenKind;
+    ///
+    /// let query = r#"{ greet(name: "Hello\nWorld") , # comment
+    /// }"#;
+    /// let tokens = Lexer::new(query)
+    ///     .semantic_tokens()
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// let kinds: Vec<_> = tokens.iter().map(|token| token.kind()).collect();
+    /// assert_eq!(kinds, [
+    ///     TokenKind::LCurly,
+    ///     TokenKind::Name,
+    ///     TokenKind::LParen,
+    ///     TokenKind::Name,
+    ///     TokenKind::Colon,
+    ///     TokenKind::StringValue,
+    ///     TokenKind::RParen,
+    ///     TokenKind::RCurly,
+    /// ]);
+    /// assert_eq!(tokens[5].text(), r#""Hello\nWorld""#);
+    /// assert_eq!(tokens[5].cooked_value(), "Hello\nWorld");
+    /// ```
+    pub fn semantic_tokens(self) -> SemanticTokens<'a> {
+        SemanticTokens { lexer: self }
+    }
+}
+
+impl<'a> Iterator for SemanticTokens<'a> {
+    type Item = Result<SemanticToken<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let token = match self.lexer.next()? {
+                Ok(token) => token,
+                Err(err) => return Some(Err(err)),
+            };
+            match token.kind() {
+                TokenKind::Whitespace | TokenKind::Comment | TokenKind::Comma | TokenKind::Eof => {
+                    continue
+                }
+                kind => {
+                    return Some(Ok(SemanticToken {
+                        kind,
+                        text: token.data(),
+                        index: token.index(),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+impl<'a> SemanticToken<'a> {
+    /// Returns the kind of token.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Returns the source text for this token.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the byte index where this token starts in the source text.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the byte range of this token in the source text.
+    #[cfg(feature = "parser")]
+    pub fn range(&self) -> crate::TextRange {
+        let start = self.index as u32;
+        let end = start + self.text.len() as u32;
+        crate::TextRange::new(start.into(), end.into())
+    }
+
+    /// Returns the value represented by this token.
+    ///
+    /// For string values (including block strings),
+    /// this is the string with quotes removed, escape sequences replaced,
+    /// and block string indentation removed.
+    /// For other tokens this is the source text unchanged.
+    pub fn cooked_value(&self) -> Cow<'a, str> {
+        match self.kind {
+            TokenKind::StringValue => Cow::Owned(cook_string_value(self.text)),
+            _ => Cow::Borrowed(self.text),
+        }
+    }
+}
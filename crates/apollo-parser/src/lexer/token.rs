@@ -1,5 +1,5 @@
 use crate::TokenKind;
-use std::fmt;
+use core::fmt;
 
 /// A token generated by the lexer.
 #[derive(Clone, PartialEq, Eq)]
@@ -83,6 +83,9 @@ impl fmt::Debug for Token<'_> {
             TokenKind::Pipe => {
                 write!(f, "PIPE@{}:{} {:?}", start, end, self.data)
             }
+            TokenKind::Question => {
+                write!(f, "QUESTION@{}:{} {:?}", start, end, self.data)
+            }
             TokenKind::Eof => {
                 write!(f, "EOF@{start}:{start}")
             }
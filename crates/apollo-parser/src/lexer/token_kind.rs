@@ -4,6 +4,8 @@
 ///
 /// Punctuator
 ///   # ! $ & ... , : = @ ( ) [ ] { } |
+///
+/// `?` is only produced with the `experimental-client-controlled-nullability` feature.
 /// Name
 /// IntValue
 /// FloatValue
@@ -32,6 +34,7 @@ pub enum TokenKind {
     LCurly,     // {
     RCurly,     // }
     Pipe,       // |
+    Question,   // ?
     Eof,
 
     // composite nodes
@@ -59,6 +62,7 @@ macro_rules! T {
     ['{'] => { $ crate :: TokenKind :: LCurly } ;
     ['}'] => { $ crate :: TokenKind :: RCurly } ;
     [|] => { $ crate :: TokenKind :: Pipe } ;
+    [?] => { $ crate :: TokenKind :: Question } ;
 
     // composite nodes
     [name] => { $ crate :: TokenKind :: Name } ;
@@ -1,25 +1,73 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 mod lexer;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "conformance")]
+pub mod conformance;
+
+#[cfg(feature = "parser")]
 pub mod cst;
 mod error;
+#[cfg(feature = "parser")]
+mod format;
+#[cfg(feature = "parser")]
+mod invariants;
 mod limit;
+mod line_index;
+#[cfg(feature = "parser")]
 mod parser;
+mod source_files;
 
 pub use crate::error::Error;
+pub use crate::error::ErrorKind;
+#[cfg(feature = "parser")]
+pub use crate::format::Formatter;
+#[cfg(feature = "parser")]
+pub use crate::invariants::check_invariants;
+#[cfg(feature = "parser")]
+pub use crate::invariants::InvariantCheck;
 pub use crate::lexer::Lexer;
+pub use crate::lexer::SemanticToken;
+pub use crate::lexer::SemanticTokens;
 pub use crate::lexer::Token;
 pub use crate::lexer::TokenKind;
 pub use crate::limit::LimitTracker;
+pub use crate::line_index::LineCol;
+pub use crate::line_index::LineIndex;
+pub use crate::line_index::PositionEncoding;
+pub use crate::line_index::SourceOffset;
+#[cfg(feature = "parser")]
+pub use crate::parser::ExtensionParser;
+#[cfg(feature = "parser")]
+pub use crate::parser::ParseEventHandler;
+#[cfg(feature = "parser")]
 pub use crate::parser::Parser;
+#[cfg(feature = "parser")]
+pub use crate::parser::Recovery;
+#[cfg(feature = "parser")]
 pub use crate::parser::SyntaxElement;
+#[cfg(feature = "parser")]
+pub use crate::parser::SyntaxExtension;
+#[cfg(feature = "parser")]
 pub use crate::parser::SyntaxKind;
+#[cfg(feature = "parser")]
 pub use crate::parser::SyntaxNode;
+#[cfg(feature = "parser")]
 pub(crate) use crate::parser::SyntaxNodeChildren;
+#[cfg(feature = "parser")]
 pub use crate::parser::SyntaxToken;
+#[cfg(feature = "parser")]
 pub use crate::parser::SyntaxTree;
+#[cfg(feature = "parser")]
+pub use crate::parser::TextEdit;
+#[cfg(feature = "parser")]
 pub(crate) use crate::parser::TokenText;
+pub use crate::source_files::SourceFile;
+pub use crate::source_files::SourceFiles;
+#[cfg(feature = "parser")]
 pub use rowan::TextRange;
@@ -1,4 +1,4 @@
-use std::fmt;
+use core::fmt;
 
 /// A LimitTracker enforces a particular limit within the parser. It keeps
 /// track of utilization so that we can report how close to a limit we
@@ -38,6 +38,7 @@ use std::fmt;
 /// // ... continue
 /// ```
 #[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LimitTracker {
     pub(crate) current: usize,
     /// High Water mark for this limit
@@ -0,0 +1,330 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Converts between byte offsets in a source text and line/column positions,
+/// with columns counted in UTF-8, UTF-16, or UTF-32 code units.
+///
+/// Building the index scans the text once. Each conversion then takes
+/// logarithmic time in the number of lines, plus linear time in the number of
+/// non-ASCII characters on that line, instead of scanning the text from the start.
+/// This is intended for language servers: the Language Server Protocol
+/// uses UTF-16 columns by default.
+///
+/// Lines and columns are zero-based, like in the Language Server Protocol.
+/// Line terminators are `\n`, `\r\n`, and `\r`, as in the GraphQL specification.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::LineCol;
+/// use apollo_parser::LineIndex;
+/// use apollo_parser::PositionEncoding;
+///
+/// let text = "type Query {\n  \"🦀\" crab: String\n}";
+/// let index = LineIndex::new(text);
+/// let offset = text.find("crab").unwrap();
+///
+/// let position = LineCol { line: 1, col: 9 };
+/// assert_eq!(index.position(offset, PositionEncoding::Utf8), Some(position));
+/// let position = LineCol { line: 1, col: 7 };
+/// assert_eq!(index.position(offset, PositionEncoding::Utf16), Some(position));
+/// assert_eq!(index.offset(position, PositionEncoding::Utf16), Some(offset));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Byte offset of the start of each line. The first is always 0.
+    line_starts: Vec<u32>,
+    /// Byte offset of the end of each line, before its line terminator
+    line_ends: Vec<u32>,
+    /// Non-ASCII characters of each line, as (byte offset in the line, UTF-8 length)
+    wide_chars: Vec<Vec<(u32, u8)>>,
+    len: u32,
+}
+
+/// A zero-based line and column in a source text, as returned by [`LineIndex::position`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: u32,
+    /// In the code units of the [`PositionEncoding`] used
+    pub col: u32,
+}
+
+/// Which code units [`LineIndex`] counts in columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PositionEncoding {
+    /// Bytes
+    Utf8,
+    /// 16-bit code units, where characters outside the Basic Multilingual Plane count twice.
+    /// This is the default in the Language Server Protocol.
+    Utf16,
+    /// Unicode scalar values (Rust `char`s)
+    Utf32,
+}
+
+/// Where a source text starts in a larger host file, such as GraphQL in a string literal
+/// of a Rust or JavaScript file. Configured with [`Parser::with_source_offset`].
+///
+/// Like [`LineCol`], the line and column are zero-based.
+///
+/// [`Parser::with_source_offset`]: crate::Parser::with_source_offset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceOffset {
+    pub line: u32,
+    /// In the same code units as the positions passed to [`host_position`][Self::host_position]
+    pub column: u32,
+    /// Byte offset
+    pub byte: usize,
+}
+
+impl SourceOffset {
+    /// Convert a position in the source text, such as from a [`LineIndex`] of that text,
+    /// to a position in the host file.
+    ///
+    /// Only columns on the first line are shifted, as the other lines
+    /// start at the beginning of a line of the host file.
+    pub fn host_position(&self, position: LineCol) -> LineCol {
+        LineCol {
+            line: self.line + position.line,
+            col: if position.line == 0 {
+                self.column + position.col
+            } else {
+                position.col
+            },
+        }
+    }
+}
+
+impl PositionEncoding {
+    fn len(self, utf8_len: u8) -> u32 {
+        match self {
+            PositionEncoding::Utf8 => utf8_len.into(),
+            PositionEncoding::Utf16 => {
+                if utf8_len == 4 {
+                    2
+                } else {
+                    1
+                }
+            }
+            PositionEncoding::Utf32 => 1,
+        }
+    }
+}
+
+impl LineIndex {
+    /// Index the lines of `text`.
+    ///
+    /// # Panics
+    ///
+    /// If `text` is 4 GiB or longer.
+    pub fn new(text: &str) -> Self {
+        let len = u32::try_from(text.len()).expect("text too long for a line index");
+        let mut line_starts = Vec::from([0]);
+        let mut line_ends = Vec::new();
+        let mut wide_chars = Vec::from([Vec::new()]);
+        let mut chars = text.char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            let line_start = *line_starts.last().unwrap();
+            match c {
+                '\r' if chars.peek().is_some_and(|&(_, next)| next == '\n') => {}
+                '\n' | '\r' => {
+                    let terminator_start = if c == '\n' && text[..offset].ends_with('\r') {
+                        offset - 1
+                    } else {
+                        offset
+                    };
+                    line_ends.push(terminator_start as u32);
+                    line_starts.push(offset as u32 + 1);
+                    wide_chars.push(Vec::new());
+                }
+                c if !c.is_ascii() => wide_chars
+                    .last_mut()
+                    .unwrap()
+                    .push((offset as u32 - line_start, c.len_utf8() as u8)),
+                _ => {}
+            }
+        }
+        line_ends.push(len);
+        Self {
+            line_starts,
+            line_ends,
+            wide_chars,
+            len,
+        }
+    }
+
+    /// Returns the number of lines. This is one more than the number of line terminators.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the byte offset range of the zero-based line `line`,
+    /// including its line terminator
+    pub fn line_range(&self, line: u32) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .copied()
+            .unwrap_or(self.len);
+        Some(start as usize..end as usize)
+    }
+
+    /// Convert a byte offset to a line and column.
+    ///
+    /// Returns `None` if `offset` is past the end of the text
+    /// or not at a character boundary.
+    pub fn position(&self, offset: usize, encoding: PositionEncoding) -> Option<LineCol> {
+        let offset = u32::try_from(offset)
+            .ok()
+            .filter(|&offset| offset <= self.len)?;
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let byte_col = offset - self.line_starts[line];
+        let mut col = byte_col;
+        for &(char_offset, utf8_len) in &self.wide_chars[line] {
+            if char_offset >= byte_col {
+                break;
+            }
+            if byte_col < char_offset + u32::from(utf8_len) {
+                // Inside a multi-byte character
+                return None;
+            }
+            col = col - u32::from(utf8_len) + encoding.len(utf8_len);
+        }
+        Some(LineCol {
+            line: line as u32,
+            col,
+        })
+    }
+
+    /// Convert a line and column to a byte offset.
+    ///
+    /// Returns `None` if the line does not exist, if the column is past the end of the line
+    /// (excluding its line terminator), or inside a character.
+    pub fn offset(&self, position: LineCol, encoding: PositionEncoding) -> Option<usize> {
+        let line = position.line as usize;
+        let line_start = *self.line_starts.get(line)?;
+        // Byte column and `encoding` column after the last wide character before `position`
+        let mut byte_col = 0;
+        let mut col = 0;
+        for &(char_offset, utf8_len) in &self.wide_chars[line] {
+            let char_col = col + (char_offset - byte_col);
+            if position.col <= char_col {
+                break;
+            }
+            let len = encoding.len(utf8_len);
+            if position.col < char_col + len {
+                // Inside a character
+                return None;
+            }
+            byte_col = char_offset + u32::from(utf8_len);
+            col = char_col + len;
+        }
+        let offset = line_start.checked_add(byte_col + (position.col - col))?;
+        (offset <= self.line_ends[line]).then_some(offset as usize)
+    }
+
+    /// Convert a byte offset range to a range of positions.
+    ///
+    /// Returns `None` if either end is not a valid offset for [`position`][Self::position].
+    pub fn range(&self, range: Range<usize>, encoding: PositionEncoding) -> Option<Range<LineCol>> {
+        Some(self.position(range.start, encoding)?..self.position(range.end, encoding)?)
+    }
+
+    /// Convert a [`TextRange`][crate::TextRange], such as from a syntax node,
+    /// to a range of positions
+    #[cfg(feature = "parser")]
+    pub fn text_range(
+        &self,
+        range: crate::TextRange,
+        encoding: PositionEncoding,
+    ) -> Option<Range<LineCol>> {
+        self.range(range.start().into()..range.end().into(), encoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: u32, col: u32) -> LineCol {
+        LineCol { line, col }
+    }
+
+    #[test]
+    fn line_terminators() {
+        let text = "a\nbc\r\nd\re";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_range(1), Some(2..6));
+        assert_eq!(index.line_range(3), Some(8..9));
+        assert_eq!(index.line_range(4), None);
+        for (offset, expected) in [
+            (0, pos(0, 0)),
+            (1, pos(0, 1)),
+            (2, pos(1, 0)),
+            (4, pos(1, 2)),
+            (5, pos(1, 3)),
+            (6, pos(2, 0)),
+            (8, pos(3, 0)),
+            (9, pos(3, 1)),
+        ] {
+            assert_eq!(
+                index.position(offset, PositionEncoding::Utf8),
+                Some(expected)
+            );
+        }
+        assert_eq!(index.position(10, PositionEncoding::Utf8), None);
+        assert_eq!(index.offset(pos(1, 2), PositionEncoding::Utf8), Some(4));
+        // Past the end of the line content
+        assert_eq!(index.offset(pos(1, 3), PositionEncoding::Utf8), None);
+        assert_eq!(index.offset(pos(3, 1), PositionEncoding::Utf8), Some(9));
+        assert_eq!(index.offset(pos(3, 2), PositionEncoding::Utf8), None);
+        assert_eq!(index.offset(pos(4, 0), PositionEncoding::Utf8), None);
+    }
+
+    #[test]
+    fn encodings() {
+        // é is 2 bytes and 1 UTF-16 unit, 🦀 is 4 bytes and 2 UTF-16 units
+        let text = "x\n\"é🦀\" y";
+        let index = LineIndex::new(text);
+        let y = text.find('y').unwrap();
+        let crab = text.find('🦀').unwrap();
+        for (encoding, y_col, crab_col) in [
+            (PositionEncoding::Utf8, 9, 3),
+            (PositionEncoding::Utf16, 6, 2),
+            (PositionEncoding::Utf32, 5, 2),
+        ] {
+            assert_eq!(index.position(y, encoding), Some(pos(1, y_col)));
+            assert_eq!(index.offset(pos(1, y_col), encoding), Some(y));
+            assert_eq!(index.position(crab, encoding), Some(pos(1, crab_col)));
+            assert_eq!(index.offset(pos(1, crab_col), encoding), Some(crab));
+        }
+        // Inside characters
+        assert_eq!(index.position(crab + 1, PositionEncoding::Utf8), None);
+        assert_eq!(index.offset(pos(1, 3), PositionEncoding::Utf16), None);
+        assert_eq!(
+            index.range(crab..y, PositionEncoding::Utf16),
+            Some(pos(1, 2)..pos(1, 6))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "query {\r\n  a(s: \"ünï🦀cødé\")\n  b\r}";
+        let index = LineIndex::new(text);
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            for (offset, _) in text.char_indices() {
+                let position = index.position(offset, encoding).unwrap();
+                let is_terminator =
+                    matches!(text.as_bytes()[offset], b'\n') && text[..offset].ends_with('\r');
+                if !is_terminator {
+                    assert_eq!(index.offset(position, encoding), Some(offset));
+                }
+            }
+        }
+    }
+}
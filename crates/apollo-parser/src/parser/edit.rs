@@ -0,0 +1,372 @@
+use crate::cst;
+use crate::cst::CstNode;
+use crate::parser::syntax_tree::green_depth;
+use crate::SyntaxKind;
+use crate::SyntaxNode;
+use crate::SyntaxTree;
+use rowan::GreenNode;
+use rowan::GreenToken;
+use rowan::NodeOrToken;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+type GreenElement = NodeOrToken<GreenNode, GreenToken>;
+
+/// Editing a syntax tree.
+///
+/// Syntax trees are immutable: each edit returns a new tree
+/// that shares all unchanged nodes with `self`.
+/// Nodes to insert are taken from other trees, typically from parsing a snippet of GraphQL.
+/// Whitespace and comments that are not part of an edited node are kept as-is,
+/// so the new tree serializes back to the original source text with only the edit applied.
+///
+/// Syntax errors of `self` inside removed or replaced nodes are dropped,
+/// and the others are moved to their new position.
+/// Errors in inserted nodes are not reported: check the tree they come from.
+///
+/// ## Panics
+///
+/// All methods panic if a node to edit is not part of `self`.
+/// After an edit, nodes of the new tree need to be looked up again from its root.
+///
+/// ## Example
+///
+/// ```rust
+/// use apollo_parser::cst;
+/// use apollo_parser::cst::CstNode;
+/// use apollo_parser::Parser;
+///
+/// let tree = Parser::new("query {\n  a\n  b\n}\n\nfragment F on T { c }\n").parse();
+/// let fragment = tree.document().definitions().nth(1).unwrap();
+/// let tree = tree.remove(fragment.syntax());
+///
+/// // Nodes of the previous tree are not part of the new one, look them up again
+/// let Some(cst::Definition::OperationDefinition(operation)) = tree.document().definitions().next()
+/// else {
+///     panic!()
+/// };
+/// let snippet = Parser::new("{ c }").parse_selection_set();
+/// let new_field = snippet.field_set().selections().next().unwrap();
+/// let tree = tree.push_selection(&operation.selection_set().unwrap(), new_field.syntax());
+/// assert_eq!(tree.document().syntax().to_string(), "query {\n  a\n  b\n  c\n}\n");
+/// ```
+impl<T: CstNode> SyntaxTree<T> {
+    /// Returns a tree where `node` is replaced with `replacement`
+    pub fn replace(&self, node: &SyntaxNode, replacement: &SyntaxNode) -> Self {
+        self.check_contains(node);
+        let green = replacement.green().into_owned();
+        match node.parent() {
+            Some(parent) => {
+                let index = node.index();
+                self.splice(&parent, index..index + 1, vec![NodeOrToken::Node(green)])
+            }
+            None => {
+                let depth = green_depth(&green);
+                self.with_green(
+                    green,
+                    depth,
+                    node.text().len().into(),
+                    replacement.text().len().into(),
+                    0,
+                )
+            }
+        }
+    }
+
+    /// Returns a tree where `node` is removed,
+    /// together with the whitespace and commas that follow it.
+    /// If `node` is the last one in its parent, the whitespace before it is removed instead.
+    pub fn remove(&self, node: &SyntaxNode) -> Self {
+        self.check_contains(node);
+        let Some(parent) = node.parent() else {
+            return self.with_green(
+                GreenNode::new(node.green().kind(), []),
+                1,
+                node.text().len().into(),
+                0,
+                0,
+            );
+        };
+        let children: Vec<_> = parent.children_with_tokens().collect();
+        let index = node.index();
+        let mut end = index + 1;
+        while end < children.len() && is_separator(children[end].kind()) {
+            end += 1;
+        }
+        let mut start = index;
+        if end == children.len() || children[end].as_node().is_none() {
+            // Nothing follows in this parent but tokens like `}`, keep the separator after
+            // `node` and remove the one before it instead.
+            end = index + 1;
+            while start > 0 && is_separator(children[start - 1].kind()) {
+                start -= 1;
+            }
+        }
+        self.splice(&parent, start..end, Vec::new())
+    }
+
+    /// Returns a tree where `node` is inserted right after `anchor`, in the same parent.
+    ///
+    /// They are separated by a copy of the whitespace before `anchor`,
+    /// so that the new node is on its own line with the same indentation if `anchor` is.
+    pub fn insert_after(&self, anchor: &SyntaxNode, node: &SyntaxNode) -> Self {
+        self.check_contains(anchor);
+        let parent = anchor
+            .parent()
+            .expect("cannot insert next to the root node");
+        let index = anchor.index() + 1;
+        let insert = vec![
+            separator_before(anchor),
+            NodeOrToken::Node(node.green().into_owned()),
+        ];
+        self.splice(&parent, index..index, insert)
+    }
+
+    /// Returns a tree where `node` is inserted right before `anchor`, in the same parent.
+    ///
+    /// They are separated by a copy of the whitespace before `anchor`.
+    pub fn insert_before(&self, anchor: &SyntaxNode, node: &SyntaxNode) -> Self {
+        self.check_contains(anchor);
+        let parent = anchor
+            .parent()
+            .expect("cannot insert next to the root node");
+        let index = anchor.index();
+        let insert = vec![
+            NodeOrToken::Node(node.green().into_owned()),
+            separator_before(anchor),
+        ];
+        self.splice(&parent, index..index, insert)
+    }
+
+    /// Returns a tree where `selection` is added at the end of `selection_set`
+    ///
+    /// `selection` should be a field, fragment spread, or inline fragment node.
+    pub fn push_selection(
+        &self,
+        selection_set: &cst::SelectionSet,
+        selection: &SyntaxNode,
+    ) -> Self {
+        if let Some(last) = selection_set.selections().last() {
+            return self.insert_after(last.syntax(), selection);
+        }
+        let set = selection_set.syntax();
+        self.check_contains(set);
+        let index = set
+            .children_with_tokens()
+            .find(|child| child.kind() == SyntaxKind::R_CURLY)
+            .map_or(set.children_with_tokens().count(), |r_curly| {
+                r_curly.index()
+            });
+        let insert = vec![
+            whitespace(" "),
+            NodeOrToken::Node(selection.green().into_owned()),
+            whitespace(" "),
+        ];
+        self.splice(set, index..index, insert)
+    }
+
+    fn check_contains(&self, node: &SyntaxNode) {
+        let root = node.ancestors().last().unwrap_or_else(|| node.clone());
+        assert!(
+            std::ptr::eq(&*root.green(), &*self.green),
+            "node is not part of this syntax tree"
+        );
+    }
+
+    /// Replace `range` of the children of `parent` with `insert`
+    fn splice(&self, parent: &SyntaxNode, range: Range<usize>, insert: Vec<GreenElement>) -> Self {
+        let children: Vec<_> = parent.children_with_tokens().collect();
+        let start = match children.get(range.start) {
+            Some(child) => child.text_range().start(),
+            None => parent.text_range().end(),
+        };
+        let removed: usize = children[range.clone()]
+            .iter()
+            .map(|child| usize::from(child.text_range().len()))
+            .sum();
+        let inserted = insert
+            .iter()
+            .map(|child| usize::from(child.text_len()))
+            .sum();
+        // Inserted nodes are children of `parent`, one level below its ancestors
+        let inserted_depth = insert
+            .iter()
+            .filter_map(|child| child.as_node())
+            .map(|child| parent.ancestors().count() + green_depth(child))
+            .max()
+            .unwrap_or(0);
+        let new_parent = parent.green().splice_children(range, insert);
+        self.with_green(
+            parent.replace_with(new_parent),
+            self.depth.max(inserted_depth),
+            removed,
+            inserted,
+            start.into(),
+        )
+    }
+
+    /// Returns a tree with the given root and `depth`,
+    /// where `removed` bytes at `offset` were replaced with `inserted` bytes
+    fn with_green(
+        &self,
+        green: GreenNode,
+        depth: usize,
+        removed: usize,
+        inserted: usize,
+        offset: usize,
+    ) -> Self {
+        // Error indices include the source offset
+        let offset = offset + self.source_offset.byte;
+        let errors = self
+            .errors
+            .iter()
+            .filter(|err| err.index < offset || err.index >= offset + removed)
+            .map(|err| {
+                let mut err = err.clone();
+                if err.index >= offset + removed {
+                    err.index = err.index - removed + inserted;
+                }
+                err
+            })
+            .collect();
+        SyntaxTree {
+            green,
+            errors,
+            recursion_limit: self.recursion_limit,
+            token_limit: self.token_limit,
+            node_limit: self.node_limit,
+            source_offset: self.source_offset,
+            depth,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+fn is_separator(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::WHITESPACE | SyntaxKind::COMMA)
+}
+
+fn whitespace(text: &str) -> GreenElement {
+    NodeOrToken::Token(GreenToken::new(
+        rowan::SyntaxKind(SyntaxKind::WHITESPACE as u16),
+        text,
+    ))
+}
+
+/// A copy of the whitespace just before `node`, or a single space
+fn separator_before(node: &SyntaxNode) -> GreenElement {
+    match node.prev_sibling_or_token() {
+        Some(NodeOrToken::Token(token)) if token.kind() == SyntaxKind::WHITESPACE => {
+            whitespace(token.text())
+        }
+        _ => whitespace(" "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cst;
+    use crate::cst::CstNode;
+    use crate::Parser;
+
+    fn definitions(tree: &crate::SyntaxTree) -> Vec<cst::Definition> {
+        tree.document().definitions().collect()
+    }
+
+    #[test]
+    fn replace() {
+        let tree = Parser::new("type A { a: Int }\n# comment\ntype B { b: Int }").parse();
+        let snippet = Parser::new("type C { c: [C!] }").parse();
+        let new = tree.replace(
+            definitions(&tree)[0].syntax(),
+            definitions(&snippet)[0].syntax(),
+        );
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "type C { c: [C!] }\n# comment\ntype B { b: Int }"
+        );
+        // The unchanged definition is shared
+        assert!(std::ptr::eq(
+            &*definitions(&tree)[1].syntax().green(),
+            &*definitions(&new)[1].syntax().green(),
+        ));
+    }
+
+    #[test]
+    fn remove() {
+        let tree = Parser::new("type A { a: Int, b: Int }\n\ntype B { b: Int }\n").parse();
+        let cst::Definition::ObjectTypeDefinition(a) = &definitions(&tree)[0] else {
+            panic!()
+        };
+        let fields: Vec<_> = a.fields_definition().unwrap().field_definitions().collect();
+
+        let new = tree.remove(fields[0].syntax());
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "type A { b: Int }\n\ntype B { b: Int }\n"
+        );
+        let new = tree.remove(fields[1].syntax());
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "type A { a: Int }\n\ntype B { b: Int }\n"
+        );
+        let new = tree.remove(definitions(&tree)[0].syntax());
+        assert_eq!(new.document().syntax().to_string(), "type B { b: Int }\n");
+        let new = tree.remove(definitions(&tree)[1].syntax());
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "type A { a: Int, b: Int }\n"
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let tree = Parser::new("query {\n  a\n}\n\n{ }").parse();
+        let snippet = Parser::new("{ b ...F }").parse_selection_set();
+        let selections: Vec<_> = snippet.field_set().selections().collect();
+        let sets: Vec<_> = definitions(&tree)
+            .into_iter()
+            .map(|def| match def {
+                cst::Definition::OperationDefinition(op) => op.selection_set().unwrap(),
+                _ => panic!(),
+            })
+            .collect();
+
+        let new = tree.push_selection(&sets[0], selections[0].syntax());
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "query {\n  a\n  b\n}\n\n{ }"
+        );
+        let new = tree.push_selection(&sets[1], selections[1].syntax());
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "query {\n  a\n}\n\n{  ...F }"
+        );
+        let a = sets[0].selections().next().unwrap();
+        let new = tree.insert_before(a.syntax(), selections[0].syntax());
+        assert_eq!(
+            new.document().syntax().to_string(),
+            "query {\n  b\n  a\n}\n\n{ }"
+        );
+        assert_eq!(new.errors().len(), 1);
+    }
+
+    #[test]
+    fn shifts_errors() {
+        let tree = Parser::new("type A { a: Int }\ntype B { b: }\ntype C { c: }").parse();
+        assert_eq!(tree.errors().len(), 2);
+        let new = tree.remove(definitions(&tree)[1].syntax());
+        let errors: Vec<_> = new.errors().collect();
+        assert_eq!(errors.len(), 1);
+        let source = new.document().syntax().to_string();
+        assert_eq!(&source[errors[0].index()..], "}");
+    }
+
+    #[test]
+    #[should_panic = "node is not part of this syntax tree"]
+    fn foreign_node() {
+        let tree = Parser::new("type A { a: Int }").parse();
+        let other = Parser::new("type A { a: Int }").parse();
+        tree.remove(definitions(&other)[0].syntax());
+    }
+}
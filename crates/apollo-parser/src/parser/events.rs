@@ -0,0 +1,185 @@
+use crate::SyntaxKind;
+
+/// Receives the nodes and tokens of a document from
+/// [`Parser::parse_with_events`][crate::Parser::parse_with_events],
+/// in place of a syntax tree.
+///
+/// Nodes are reported with a `start_node` call, then calls for their children,
+/// then a matching `finish_node` call. Tokens include whitespace, comments, and commas,
+/// so the text of all tokens together is the parsed input.
+/// Every method does nothing by default.
+pub trait ParseEventHandler {
+    /// Called when a node starts, before its children
+    fn start_node(&mut self, kind: SyntaxKind) {
+        let _ = kind;
+    }
+
+    /// Called when a node ends, after its children
+    fn finish_node(&mut self, kind: SyntaxKind) {
+        let _ = kind;
+    }
+
+    /// Called for each token, with its source text
+    fn token(&mut self, kind: SyntaxKind, text: &str) {
+        let _ = (kind, text);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum Event {
+    Start(SyntaxKind),
+    Finish,
+    Token {
+        kind: SyntaxKind,
+        start: usize,
+        end: usize,
+    },
+}
+
+pub(crate) fn replay(input: &str, events: Vec<Event>, handler: &mut impl ParseEventHandler) {
+    let mut open_nodes = Vec::new();
+    for event in events {
+        match event {
+            Event::Start(kind) => {
+                open_nodes.push(kind);
+                handler.start_node(kind)
+            }
+            Event::Finish => {
+                if let Some(kind) = open_nodes.pop() {
+                    handler.finish_node(kind)
+                }
+            }
+            Event::Token { kind, start, end } => handler.token(kind, &input[start..end]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ParseEventHandler;
+    use crate::Parser;
+    use crate::SyntaxKind;
+    use crate::SyntaxTree;
+    use expect_test::expect;
+    use std::fmt::Write;
+
+    /// Prints events like the `Debug` impl of `SyntaxTree`, without text ranges
+    #[derive(Default)]
+    struct Printer {
+        output: String,
+        indent: usize,
+        text: String,
+    }
+
+    impl ParseEventHandler for Printer {
+        fn start_node(&mut self, kind: SyntaxKind) {
+            writeln!(
+                self.output,
+                "{:indent$}- {kind:?}",
+                "",
+                indent = self.indent
+            )
+            .unwrap();
+            self.indent += 4;
+        }
+
+        fn finish_node(&mut self, _kind: SyntaxKind) {
+            self.indent -= 4;
+        }
+
+        fn token(&mut self, kind: SyntaxKind, text: &str) {
+            writeln!(
+                self.output,
+                "{:indent$}- {kind:?} {text:?}",
+                "",
+                indent = self.indent
+            )
+            .unwrap();
+            self.text.push_str(text);
+        }
+    }
+
+    /// Same format as `Printer`, from a syntax tree
+    fn print_tree(tree: &SyntaxTree) -> String {
+        let mut output = String::new();
+        let mut indent = 0;
+        for event in tree.document().syntax.preorder_with_tokens() {
+            match event {
+                rowan::WalkEvent::Enter(rowan::NodeOrToken::Node(node)) => {
+                    writeln!(output, "{:indent$}- {:?}", "", node.kind()).unwrap();
+                    indent += 4;
+                }
+                rowan::WalkEvent::Leave(rowan::NodeOrToken::Node(_)) => indent -= 4,
+                rowan::WalkEvent::Enter(rowan::NodeOrToken::Token(token)) => {
+                    writeln!(
+                        output,
+                        "{:indent$}- {:?} {:?}",
+                        "",
+                        token.kind(),
+                        token.text()
+                    )
+                    .unwrap();
+                }
+                rowan::WalkEvent::Leave(rowan::NodeOrToken::Token(_)) => {}
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn events_match_tree() {
+        let inputs = [
+            "query Q($v: [Int!]! = [1]) { a(arg: $v) @skip(if: false) { ...F } }",
+            "fragment F on T { b c: d }",
+            "type T implements I & J { f(x: String = \"s\"): [T!] } # comment",
+            "{ unclosed(arg: ",
+            "uasdf21230jkdw { a }",
+        ];
+        for input in inputs {
+            let mut printer = Printer::default();
+            let errors = Parser::new(input).parse_with_events(&mut printer);
+            let tree = Parser::new(input).parse();
+            assert_eq!(printer.output, print_tree(&tree), "{input}");
+            assert_eq!(printer.text, input);
+            assert_eq!(errors, tree.errors().cloned().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn events() {
+        let mut printer = Printer::default();
+        let errors = Parser::new("query($v: Int!) { a }").parse_with_events(&mut printer);
+        assert!(errors.is_empty());
+        expect![[r#"
+            - DOCUMENT
+                - OPERATION_DEFINITION
+                    - OPERATION_TYPE
+                        - query_KW "query"
+                    - VARIABLE_DEFINITIONS
+                        - L_PAREN "("
+                        - VARIABLE_DEFINITION
+                            - VARIABLE
+                                - DOLLAR "$"
+                                - NAME
+                                    - IDENT "v"
+                            - COLON ":"
+                            - WHITESPACE " "
+                            - NON_NULL_TYPE
+                                - NAMED_TYPE
+                                    - NAME
+                                        - IDENT "Int"
+                                - BANG "!"
+                        - R_PAREN ")"
+                    - WHITESPACE " "
+                    - SELECTION_SET
+                        - L_CURLY "{"
+                        - WHITESPACE " "
+                        - FIELD
+                            - NAME
+                                - IDENT "a"
+                        - WHITESPACE " "
+                        - R_CURLY "}"
+        "#]]
+        .assert_eq(&printer.output);
+    }
+}
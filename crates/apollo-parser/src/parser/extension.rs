@@ -0,0 +1,387 @@
+use crate::parser::grammar::argument;
+use crate::parser::grammar::description;
+use crate::parser::grammar::directive;
+use crate::parser::grammar::field;
+use crate::parser::grammar::input;
+use crate::parser::grammar::name;
+use crate::parser::grammar::object;
+use crate::parser::grammar::selection;
+use crate::parser::grammar::ty;
+use crate::parser::grammar::value;
+use crate::parser::grammar::value::Constness;
+use crate::Parser;
+use crate::SyntaxKind;
+use crate::TokenKind;
+use crate::S;
+use crate::T;
+use std::ops::ControlFlow;
+
+/// Parses experimental top-level definitions that are not part of GraphQL,
+/// such as prototypes of new keywords, while reusing the lexer, the parser for
+/// standard syntax within them, and syntax error reporting.
+///
+/// Register an extension with [`Parser::syntax_extension`].
+/// Without one, a definition that starts with an unknown keyword is a syntax error.
+///
+/// A custom definition is a [`CustomDefinition`][crate::cst::CustomDefinition] node
+/// of kind [`SyntaxKind::CUSTOM_DEFINITION`] containing an optional description,
+/// the keyword as a `NAME` node, then whatever [`parse_definition`][Self::parse_definition]
+/// parses through [`ExtensionParser`].
+///
+/// ## Example
+///
+/// ```rust
+/// use apollo_parser::cst;
+/// use apollo_parser::cst::CstNode;
+/// use apollo_parser::ExtensionParser;
+/// use apollo_parser::Parser;
+/// use apollo_parser::SyntaxExtension;
+///
+/// /// `entity User { id: ID! }`
+/// struct Entities;
+///
+/// impl SyntaxExtension for Entities {
+///     fn handles_keyword(&self, keyword: &str) -> bool {
+///         keyword == "entity"
+///     }
+///
+///     fn parse_definition(&self, _keyword: &str, p: &mut ExtensionParser<'_, '_>) {
+///         p.name();
+///         p.const_directives();
+///         p.fields_definition();
+///     }
+/// }
+///
+/// let cst = Parser::new("entity User @key(fields: \"id\") { id: ID! }")
+///     .syntax_extension(&Entities)
+///     .parse();
+/// assert_eq!(cst.errors().len(), 0);
+/// let cst::Definition::CustomDefinition(entity) = cst.document().definitions().next().unwrap()
+/// else {
+///     panic!("expected a custom definition")
+/// };
+/// assert_eq!(entity.keyword().unwrap().text(), "entity");
+/// let fields = entity.syntax().children().find_map(cst::FieldsDefinition::cast).unwrap();
+/// assert_eq!(fields.field_definitions().count(), 1);
+/// ```
+pub trait SyntaxExtension {
+    /// Returns whether this extension parses a top-level definition starting with `keyword`,
+    /// after an optional description.
+    ///
+    /// Only called for names that do not start a GraphQL definition.
+    fn handles_keyword(&self, keyword: &str) -> bool;
+
+    /// Parses the rest of a definition after its keyword,
+    /// which was accepted by [`handles_keyword`][Self::handles_keyword].
+    fn parse_definition(&self, keyword: &str, p: &mut ExtensionParser<'_, '_>);
+}
+
+/// The parser state given to [`SyntaxExtension::parse_definition`].
+///
+/// Methods that parse standard syntax, such as [`selection_set`][Self::selection_set],
+/// produce the same nodes as in standard definitions, so the usual typed
+/// [`cst`][crate::cst] API can read them.
+/// Each of them only parses if the next token can start that syntax,
+/// and otherwise records a syntax error without consuming anything.
+pub struct ExtensionParser<'p, 'input> {
+    pub(crate) parser: &'p mut Parser<'input>,
+}
+
+impl ExtensionParser<'_, '_> {
+    /// Returns the kind of the next significant token
+    pub fn peek(&mut self) -> Option<TokenKind> {
+        self.parser.peek()
+    }
+
+    /// Returns the source text of the next significant token
+    pub fn peek_data(&mut self) -> Option<&str> {
+        self.parser.peek_data()
+    }
+
+    /// Returns whether the next token is the name `keyword`
+    pub fn at_keyword(&mut self, keyword: &str) -> bool {
+        self.peek() == Some(TokenKind::Name) && self.peek_data() == Some(keyword)
+    }
+
+    /// Consumes the next token as a token of the given `kind` in the current node
+    pub fn bump(&mut self, kind: SyntaxKind) {
+        self.parser.bump(kind)
+    }
+
+    /// Records a syntax error at the next token, without consuming it
+    pub fn err(&mut self, message: &str) {
+        self.parser.err(message)
+    }
+
+    /// Records a syntax error at the next token, and consumes it
+    pub fn err_and_pop(&mut self, message: &str) {
+        self.parser.err_and_pop(message)
+    }
+
+    /// Parses the name `keyword` as a `NAME` node, or records a syntax error
+    pub fn keyword(&mut self, keyword: &str) {
+        if self.at_keyword(keyword) {
+            name::name(self.parser)
+        } else {
+            self.err(&format!("expected `{keyword}`"))
+        }
+    }
+
+    /// Parses a `NAME` node
+    pub fn name(&mut self) {
+        name::name(self.parser)
+    }
+
+    /// Parses a `DESCRIPTION` node
+    pub fn description(&mut self) {
+        if self.peek() == Some(TokenKind::StringValue) {
+            description::description(self.parser)
+        } else {
+            self.err("expected a Description")
+        }
+    }
+
+    /// Parses a `NAMED_TYPE` node
+    pub fn named_type(&mut self) {
+        if self.peek() == Some(TokenKind::Name) {
+            ty::named_type(self.parser)
+        } else {
+            self.err("expected a Name")
+        }
+    }
+
+    /// Parses a `TYPE` node such as `[String!]`
+    pub fn ty(&mut self) {
+        ty::ty(self.parser)
+    }
+
+    /// Parses a `VALUE` node, which may contain variables
+    pub fn value(&mut self) {
+        value::value(self.parser, Constness::NotConst, true)
+    }
+
+    /// Parses a `VALUE` node without variables
+    pub fn const_value(&mut self) {
+        value::value(self.parser, Constness::Const, true)
+    }
+
+    /// Parses an optional `DIRECTIVES` node whose arguments may contain variables
+    pub fn directives(&mut self) {
+        if self.peek() == Some(T![@]) {
+            directive::directives(self.parser, Constness::NotConst)
+        }
+    }
+
+    /// Parses an optional `DIRECTIVES` node without variables
+    pub fn const_directives(&mut self) {
+        if self.peek() == Some(T![@]) {
+            directive::directives(self.parser, Constness::Const)
+        }
+    }
+
+    /// Parses an optional `ARGUMENTS` node whose values may contain variables
+    pub fn arguments(&mut self) {
+        if self.peek() == Some(T!['(']) {
+            argument::arguments(self.parser, Constness::NotConst)
+        }
+    }
+
+    /// Parses an optional `ARGUMENTS` node without variables
+    pub fn const_arguments(&mut self) {
+        if self.peek() == Some(T!['(']) {
+            argument::arguments(self.parser, Constness::Const)
+        }
+    }
+
+    /// Parses an optional `ARGUMENTS_DEFINITION` node
+    pub fn arguments_definition(&mut self) {
+        if self.peek() == Some(T!['(']) {
+            argument::arguments_definition(self.parser)
+        }
+    }
+
+    /// Parses an optional `IMPLEMENTS_INTERFACES` node
+    pub fn implements_interfaces(&mut self) {
+        if self.at_keyword("implements") {
+            object::implements_interfaces(self.parser)
+        }
+    }
+
+    /// Parses a `FIELDS_DEFINITION` node such as `{ id: ID! }`
+    pub fn fields_definition(&mut self) {
+        if self.peek() == Some(T!['{']) {
+            field::fields_definition(self.parser)
+        } else {
+            self.err("expected a Fields Definition")
+        }
+    }
+
+    /// Parses an `INPUT_FIELDS_DEFINITION` node such as `{ id: ID! }`
+    pub fn input_fields_definition(&mut self) {
+        if self.peek() == Some(T!['{']) {
+            input::input_fields_definition(self.parser)
+        } else {
+            self.err("expected an Input Fields Definition")
+        }
+    }
+
+    /// Parses a `SELECTION_SET` node such as `{ id name }`
+    pub fn selection_set(&mut self) {
+        if self.peek() == Some(T!['{']) {
+            selection::selection_set(self.parser)
+        } else {
+            self.err("expected a Selection Set")
+        }
+    }
+
+    /// Parses a block delimited by curly braces, for syntax like directives with blocks.
+    /// `item` is called until the closing brace and must consume at least one token,
+    /// otherwise the next token is reported as an error and skipped.
+    pub fn block(&mut self, mut item: impl FnMut(&mut ExtensionParser<'_, '_>)) {
+        if self.peek() != Some(T!['{']) {
+            self.err("expected {");
+            return;
+        }
+        if self.parser.recursion_limit.check_and_increment() {
+            self.parser.limit_err("parser recursion limit reached");
+            return;
+        }
+        self.bump(S!['{']);
+        self.parser.peek_while(|p, kind| match kind {
+            T!['}'] | TokenKind::Eof => ControlFlow::Break(()),
+            _ => {
+                let before = p.current_token.clone();
+                item(&mut ExtensionParser { parser: p });
+                if before == p.current_token {
+                    p.err_and_pop("unexpected token in block");
+                }
+                ControlFlow::Continue(())
+            }
+        });
+        self.parser.expect(T!['}'], S!['}']);
+        self.parser.recursion_limit.decrement();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cst;
+    use crate::cst::CstNode;
+    use expect_test::expect;
+
+    /// `policy Name { allow @role(name: "admin") deny { ... } }`
+    struct Policies;
+
+    impl SyntaxExtension for Policies {
+        fn handles_keyword(&self, keyword: &str) -> bool {
+            keyword == "policy"
+        }
+
+        fn parse_definition(&self, keyword: &str, p: &mut ExtensionParser<'_, '_>) {
+            assert_eq!(keyword, "policy");
+            p.name();
+            p.block(|p| {
+                if p.at_keyword("allow") {
+                    p.keyword("allow");
+                    p.const_directives();
+                } else if p.at_keyword("deny") {
+                    p.keyword("deny");
+                    p.selection_set();
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn custom_definition() {
+        let input = r#"
+"Who can see what"
+policy Admin {
+  allow @role(name: "admin")
+  deny { secrets }
+}
+type Query { a: Int }
+"#;
+        let cst = Parser::new(input).syntax_extension(&Policies).parse();
+        assert_eq!(cst.errors().len(), 0);
+        assert_eq!(cst.document().source_string(), input);
+        let definitions: Vec<_> = cst.document().definitions().collect();
+        assert_eq!(definitions.len(), 2);
+        let cst::Definition::CustomDefinition(policy) = &definitions[0] else {
+            panic!("expected a custom definition")
+        };
+        assert_eq!(policy.keyword().unwrap().text(), "policy");
+        assert!(policy.description().is_some());
+        expect![[r#"
+            CUSTOM_DEFINITION@1..84
+              DESCRIPTION@1..19
+                STRING_VALUE@1..19
+                  STRING@1..19 "\"Who can see what\""
+              WHITESPACE@19..20 "\n"
+              NAME@20..26
+                IDENT@20..26 "policy"
+              WHITESPACE@26..27 " "
+              NAME@27..32
+                IDENT@27..32 "Admin"
+              WHITESPACE@32..33 " "
+              L_CURLY@33..34 "{"
+              WHITESPACE@34..37 "\n  "
+              NAME@37..42
+                IDENT@37..42 "allow"
+              WHITESPACE@42..43 " "
+              DIRECTIVES@43..63
+                DIRECTIVE@43..63
+                  AT@43..44 "@"
+                  NAME@44..48
+                    IDENT@44..48 "role"
+                  ARGUMENTS@48..63
+                    L_PAREN@48..49 "("
+                    ARGUMENT@49..62
+                      NAME@49..53
+                        IDENT@49..53 "name"
+                      COLON@53..54 ":"
+                      WHITESPACE@54..55 " "
+                      STRING_VALUE@55..62
+                        STRING@55..62 "\"admin\""
+                    R_PAREN@62..63 ")"
+              WHITESPACE@63..66 "\n  "
+              NAME@66..70
+                IDENT@66..70 "deny"
+              WHITESPACE@70..71 " "
+              SELECTION_SET@71..82
+                L_CURLY@71..72 "{"
+                WHITESPACE@72..73 " "
+                FIELD@73..80
+                  NAME@73..80
+                    IDENT@73..80 "secrets"
+                WHITESPACE@80..81 " "
+                R_CURLY@81..82 "}"
+              WHITESPACE@82..83 "\n"
+              R_CURLY@83..84 "}"
+        "#]]
+        .assert_eq(&format!("{:#?}", policy.syntax()));
+    }
+
+    #[test]
+    fn custom_definition_errors() {
+        // Without the extension
+        let cst = Parser::new("policy Admin { allow }").parse();
+        assert_eq!(
+            cst.errors().next().unwrap().message(),
+            "expected definition"
+        );
+
+        let cst = Parser::new("policy Admin { allow 1 }")
+            .syntax_extension(&Policies)
+            .parse();
+        let errors: Vec<_> = cst.errors().map(|e| e.message()).collect();
+        assert_eq!(errors, ["unexpected token in block"]);
+        // Parsing continues after the custom definition
+        let cst = Parser::new("policy Admin { ] } type Query { a: Int }")
+            .syntax_extension(&Policies)
+            .parse();
+        assert_eq!(cst.errors().len(), 1);
+        assert_eq!(cst.document().definitions().count(), 2);
+    }
+}
@@ -1,15 +1,21 @@
+use crate::parser::grammar::description;
 use crate::parser::grammar::directive;
 use crate::parser::grammar::enum_;
 use crate::parser::grammar::extensions;
 use crate::parser::grammar::fragment;
 use crate::parser::grammar::input;
 use crate::parser::grammar::interface;
+use crate::parser::grammar::name;
 use crate::parser::grammar::object;
 use crate::parser::grammar::operation;
 use crate::parser::grammar::scalar;
 use crate::parser::grammar::schema;
 use crate::parser::grammar::union_;
+use crate::parser::ExtensionRef;
+use crate::parser::Recovery;
+use crate::ExtensionParser;
 use crate::Parser;
+use crate::SyntaxExtension;
 use crate::SyntaxKind;
 use crate::TokenKind;
 use std::ops::ControlFlow;
@@ -25,40 +31,71 @@ pub(crate) fn document(p: &mut Parser) {
         p.err("Unexpected <EOF>.");
     }
 
-    p.peek_while(|p, kind| {
-        assert_eq!(
-            p.recursion_limit.current, 0,
-            "unbalanced limit increment / decrement"
-        );
-
-        match kind {
-            TokenKind::StringValue => {
-                if let Some(def) = p.peek_data_n(2) {
+    loop {
+        p.peek_while(|p, kind| {
+            assert_eq!(
+                p.recursion_limit.current, 0,
+                "unbalanced limit increment / decrement"
+            );
+
+            match kind {
+                TokenKind::StringValue => {
+                    if let Some(def) = p.peek_data_n(2) {
+                        select_definition(def, p);
+                    } else {
+                        p.err_and_pop("expected a definition after this StringValue");
+                    }
+                }
+                TokenKind::Name => {
+                    let def = p.peek_data().unwrap();
                     select_definition(def, p);
-                } else {
-                    p.err_and_pop("expected a definition after this StringValue");
                 }
+                TokenKind::LCurly => {
+                    let def = p.peek_data().unwrap();
+                    select_definition(def, p);
+                }
+                TokenKind::Eof => return ControlFlow::Break(()),
+                _ => p.err_and_pop("expected a StringValue, Name or OperationDefinition"),
             }
-            TokenKind::Name => {
-                let def = p.peek_data().unwrap();
-                select_definition(def, p);
-            }
-            TokenKind::LCurly => {
-                let def = p.peek_data().unwrap();
-                select_definition(def, p);
-            }
-            TokenKind::Eof => return ControlFlow::Break(()),
-            _ => p.err_and_pop("expected a StringValue, Name or OperationDefinition"),
-        }
 
-        ControlFlow::Continue(())
-    });
+            ControlFlow::Continue(())
+        });
+
+        if !p.recovering {
+            break;
+        }
+        // Skip the rest of the definition with an error, or the rest of the input
+        p.recovering = false;
+        let resume = p.options.recovery == Recovery::Definition;
+        p.skip_until(|p| resume && at_definition(p));
+    }
 
     p.push_ignored();
 
     doc.finish_node();
 }
 
+/// Whether the next token starts a definition, ignoring `{` which also starts
+/// selection sets and other blocks within definitions
+fn at_definition(p: &mut Parser) -> bool {
+    let keyword = match p.peek() {
+        Some(TokenKind::Name) => p.peek_data(),
+        Some(TokenKind::StringValue) => p.peek_data_n(2),
+        _ => None,
+    };
+    match keyword {
+        Some(
+            "directive" | "enum" | "extend" | "fragment" | "input" | "interface" | "type" | "query"
+            | "mutation" | "subscription" | "scalar" | "schema" | "union",
+        ) => true,
+        Some(keyword) => matches!(
+            p.options.syntax_extension,
+            Some(ExtensionRef(extension)) if extension.handles_keyword(keyword)
+        ),
+        None => false,
+    }
+}
+
 fn select_definition(def: &str, p: &mut Parser) {
     match def {
         "directive" => directive::directive_definition(p),
@@ -72,8 +109,26 @@ fn select_definition(def: &str, p: &mut Parser) {
         "scalar" => scalar::scalar_type_definition(p),
         "schema" => schema::schema_definition(p),
         "union" => union_::union_type_definition(p),
-        _ => p.err_and_pop("expected definition"),
+        _ => match p.options.syntax_extension {
+            Some(ExtensionRef(extension)) if extension.handles_keyword(def) => {
+                custom_definition(p, extension, def)
+            }
+            _ => p.err_and_pop("expected definition"),
+        },
+    }
+}
+
+/// A definition parsed by a [`SyntaxExtension`][crate::SyntaxExtension]
+///
+/// *CustomDefinition*:
+///     Description? Name ...
+fn custom_definition(p: &mut Parser, extension: &dyn SyntaxExtension, keyword: &str) {
+    let _g = p.start_node(SyntaxKind::CUSTOM_DEFINITION);
+    if let Some(TokenKind::StringValue) = p.peek() {
+        description::description(p);
     }
+    name::name(p);
+    extension.parse_definition(keyword, &mut ExtensionParser { parser: p });
 }
 
 #[cfg(test)]
@@ -4,9 +4,9 @@ use crate::parser::grammar::argument;
 use crate::parser::grammar::description;
 use crate::parser::grammar::directive;
 use crate::parser::grammar::name;
-use crate::parser::grammar::selection;
 use crate::parser::grammar::ty;
 use crate::parser::grammar::value::Constness;
+use crate::parser::NodeGuard;
 use crate::Parser;
 use crate::SyntaxKind;
 use crate::TokenKind;
@@ -17,9 +17,14 @@ use std::ops::ControlFlow;
 /// See: https://spec.graphql.org/October2021/#Field
 ///
 /// *Field*:
-///     Alias? Name Arguments? Directives? SelectionSet?
-pub(crate) fn field(p: &mut Parser) {
-    let _guard = p.start_node(SyntaxKind::FIELD);
+///     Alias? Name Arguments? Nullability? Directives? SelectionSet?
+///
+/// *Nullability* is only parsed with the `experimental-client-controlled-nullability` feature.
+///
+/// Parses the field up to its selection set, which is left to the caller.
+/// The returned guard finishes the FIELD node when dropped.
+pub(crate) fn field(p: &mut Parser) -> NodeGuard {
+    let guard = p.start_node(SyntaxKind::FIELD);
 
     if let Some(TokenKind::Name) = p.peek() {
         if let Some(T![:]) = p.peek_n(2) {
@@ -34,12 +39,75 @@ pub(crate) fn field(p: &mut Parser) {
         argument::arguments(p, Constness::NotConst);
     }
 
+    #[cfg(feature = "experimental-client-controlled-nullability")]
+    if let Some(T![!] | T![?] | T!['[']) = p.peek() {
+        nullability(p);
+    }
+
     if let Some(T![@]) = p.peek() {
         directive::directives(p, Constness::NotConst);
     }
 
-    if let Some(T!['{']) = p.peek() {
-        selection::selection_set(p);
+    guard
+}
+
+/// From the client-controlled nullability RFC, which is not part of the GraphQL spec yet.
+///
+/// *Nullability*:
+///     ListNullability? NullabilityDesignator?
+///
+/// *ListNullability*:
+///     **[** Nullability? **]**
+///
+/// *NullabilityDesignator*: one of
+///     **!** **?**
+///
+/// Nested list nullability is tracked on an explicit stack like nested selection sets:
+/// nodes are started down to the innermost one, then finished in reverse order.
+#[cfg(feature = "experimental-client-controlled-nullability")]
+fn nullability(p: &mut Parser) {
+    let mut open = vec![(
+        SyntaxKind::NULLABILITY,
+        p.start_node(SyntaxKind::NULLABILITY),
+    )];
+    while let Some(T!['[']) = p.peek() {
+        let list_guard = p.start_node(SyntaxKind::LIST_NULLABILITY);
+        p.bump(S!['[']);
+
+        // We need to enforce recursion limits to prevent
+        // excessive resource consumption.
+        if p.recursion_limit.check_and_increment() {
+            p.limit_err("parser recursion limit reached");
+            break;
+        }
+        open.push((SyntaxKind::LIST_NULLABILITY, list_guard));
+        if let Some(T![!] | T![?] | T!['[']) = p.peek() {
+            open.push((
+                SyntaxKind::NULLABILITY,
+                p.start_node(SyntaxKind::NULLABILITY),
+            ));
+        } else {
+            break;
+        }
+    }
+
+    while let Some((kind, _guard)) = open.pop() {
+        if kind == SyntaxKind::LIST_NULLABILITY {
+            p.recursion_limit.decrement();
+            p.expect(T![']'], S![']']);
+        } else {
+            match p.peek() {
+                Some(T![!]) => {
+                    let _guard = p.start_node(SyntaxKind::NULLABILITY_DESIGNATOR);
+                    p.bump(S![!]);
+                }
+                Some(T![?]) => {
+                    let _guard = p.start_node(SyntaxKind::NULLABILITY_DESIGNATOR);
+                    p.bump(S![?]);
+                }
+                _ => {}
+            }
+        }
     }
 }
 
@@ -103,3 +171,94 @@ pub(crate) fn field_definition(p: &mut Parser) {
         p.err("expected a type");
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "experimental-client-controlled-nullability")]
+mod test {
+    use crate::cst;
+    use crate::cst::CstNode;
+    use crate::Parser;
+
+    fn first_field(input: &str) -> cst::Field {
+        let cst = Parser::new(input).parse();
+        assert_eq!(
+            cst.errors().len(),
+            0,
+            "{:?}",
+            cst.errors().collect::<Vec<_>>()
+        );
+        let Some(cst::Definition::OperationDefinition(op)) = cst.document().definitions().next()
+        else {
+            panic!("expected an operation")
+        };
+        match op.selection_set().unwrap().selections().next() {
+            Some(cst::Selection::Field(field)) => field,
+            _ => panic!("expected a field"),
+        }
+    }
+
+    #[test]
+    fn nullability_designators() {
+        let field = first_field("{ user(id: 4)! @skip(if: false) { name? } }");
+        let nullability = field.nullability().unwrap();
+        assert_eq!(nullability.source_string(), "!");
+        assert!(nullability.list_nullability().is_none());
+        let designator = nullability.nullability_designator().unwrap();
+        assert!(designator.excl_token().is_some());
+        assert!(field.directives().is_some());
+
+        let field = first_field("{ name? }");
+        let designator = field
+            .nullability()
+            .unwrap()
+            .nullability_designator()
+            .unwrap();
+        assert!(designator.question_token().is_some());
+    }
+
+    #[test]
+    fn list_nullability() {
+        let field = first_field("{ matrix[[!]?]! }");
+        let nullability = field.nullability().unwrap();
+        assert_eq!(nullability.source_string(), "[[!]?]!");
+        let outer = nullability.list_nullability().unwrap();
+        let inner = outer.nullability().unwrap();
+        assert!(inner
+            .nullability_designator()
+            .unwrap()
+            .question_token()
+            .is_some());
+        let innermost = inner.list_nullability().unwrap().nullability().unwrap();
+        assert!(innermost.list_nullability().is_none());
+        assert!(innermost
+            .nullability_designator()
+            .unwrap()
+            .excl_token()
+            .is_some());
+    }
+
+    #[test]
+    fn deep_list_nullability() {
+        let input = format!("{{ list{}!{} }}", "[".repeat(1_000), "]".repeat(1_000));
+        let cst = Parser::new(&input).recursion_limit(100).parse();
+        assert_eq!(cst.errors().len(), 1);
+        assert_eq!(
+            cst.errors().next().unwrap().message(),
+            "parser recursion limit reached"
+        );
+
+        let cst = Parser::new(&input).recursion_limit(2_000).parse();
+        assert_eq!(cst.errors().len(), 0);
+        assert_eq!(cst.document().source_string(), input);
+    }
+
+    #[test]
+    fn unclosed_list_nullability() {
+        let cst = Parser::new("{ list[! }").parse();
+        assert_eq!(cst.errors().len(), 1);
+        assert_eq!(
+            cst.errors().next().unwrap().message(),
+            "expected R_BRACK, got }"
+        );
+    }
+}
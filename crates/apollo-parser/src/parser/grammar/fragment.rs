@@ -3,6 +3,7 @@ use crate::parser::grammar::name;
 use crate::parser::grammar::selection;
 use crate::parser::grammar::ty;
 use crate::parser::grammar::value::Constness;
+use crate::parser::NodeGuard;
 use crate::Parser;
 use crate::SyntaxKind;
 use crate::TokenKind;
@@ -75,8 +76,11 @@ pub(crate) fn type_condition(p: &mut Parser) {
 ///
 /// *InlineFragment*:
 ///     **...** TypeCondition? Directives? SelectionSet
-pub(crate) fn inline_fragment(p: &mut Parser) {
-    let _g = p.start_node(SyntaxKind::INLINE_FRAGMENT);
+///
+/// Parses the inline fragment up to its selection set, which is left to the caller.
+/// The returned guard finishes the INLINE_FRAGMENT node when dropped.
+pub(crate) fn inline_fragment(p: &mut Parser) -> NodeGuard {
+    let guard = p.start_node(SyntaxKind::INLINE_FRAGMENT);
     p.bump(S![...]);
 
     if let Some(TokenKind::Name) = p.peek() {
@@ -87,10 +91,7 @@ pub(crate) fn inline_fragment(p: &mut Parser) {
         directive::directives(p, Constness::NotConst);
     }
 
-    match p.peek() {
-        Some(T!['{']) => selection::selection_set(p),
-        _ => p.err("expected Selection Set"),
-    }
+    guard
 }
 
 /// See: https://spec.graphql.org/October2021/#FragmentSpread
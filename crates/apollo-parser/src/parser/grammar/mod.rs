@@ -1,21 +1,21 @@
+pub(crate) mod argument;
+pub(crate) mod description;
+pub(crate) mod directive;
 pub(crate) mod document;
+pub(crate) mod field;
+pub(crate) mod input;
+pub(crate) mod name;
+pub(crate) mod object;
 pub(crate) mod selection;
 pub(crate) mod ty;
+pub(crate) mod value;
 
-mod argument;
-mod description;
-mod directive;
 mod enum_;
 mod extensions;
-mod field;
 mod fragment;
-mod input;
 mod interface;
-mod name;
-mod object;
 mod operation;
 mod scalar;
 mod schema;
 mod union_;
-mod value;
 mod variable;
@@ -1,32 +1,25 @@
 use crate::parser::grammar::field;
 use crate::parser::grammar::fragment;
+use crate::parser::NodeGuard;
 use crate::Parser;
 use crate::SyntaxKind;
 use crate::TokenKind;
 use crate::S;
 use crate::T;
-use std::ops::ControlFlow;
 
 /// See: https://spec.graphql.org/October2021/#SelectionSet
 ///
 /// *SelectionSet*:
 ///     **{** Selection* **}**
+///
+/// Nested selection sets are tracked on an explicit stack rather than
+/// with recursive calls, so that their depth is bounded by the parser's recursion limit
+/// and not by the size of the thread's call stack.
 pub(crate) fn selection_set(p: &mut Parser) {
     if let Some(T!['{']) = p.peek() {
-        let _g = p.start_node(SyntaxKind::SELECTION_SET);
-        p.bump(S!['{']);
-
-        // We need to enforce recursion limits to prevent
-        // excessive resource consumption or (more seriously)
-        // stack overflows.
-        if p.recursion_limit.check_and_increment() {
-            p.limit_err("parser recursion limit reached");
-            return;
+        if let Some(root) = open_selection_set(p, None) {
+            selections(p, root);
         }
-        selection(p);
-        p.recursion_limit.decrement();
-
-        p.expect(T!['}'], S!['}']);
     }
 }
 
@@ -34,16 +27,74 @@ pub(crate) fn field_set(p: &mut Parser) {
     if let Some(T!['{']) = p.peek() {
         selection_set(p)
     } else {
-        let _g = p.start_node(SyntaxKind::SELECTION_SET);
+        let guard = p.start_node(SyntaxKind::SELECTION_SET);
         // We need to enforce recursion limits to prevent
-        // excessive resource consumption or (more seriously)
-        // stack overflows.
+        // excessive resource consumption.
         if p.recursion_limit.check_and_increment() {
             p.limit_err("parser recursion limit reached");
             return;
         }
-        selection(p);
+        let root = OpenSelectionSet {
+            braced: false,
+            has_selection: false,
+            _guard: guard,
+            _parent: None,
+        };
+        selections(p, root);
+    }
+}
+
+/// A selection set whose end has not been parsed yet.
+/// Dropping it finishes its node, then the node of the field or inline fragment it belongs to.
+struct OpenSelectionSet {
+    /// Whether the selection set started with `{` and should end with `}`
+    braced: bool,
+    has_selection: bool,
+    _guard: NodeGuard,
+    _parent: Option<NodeGuard>,
+}
+
+/// Start a selection set at a `{` token,
+/// as part of the field or inline fragment node `parent` if any.
+///
+/// Returns `None` if the recursion limit is reached.
+fn open_selection_set(p: &mut Parser, parent: Option<NodeGuard>) -> Option<OpenSelectionSet> {
+    let guard = p.start_node(SyntaxKind::SELECTION_SET);
+    p.bump(S!['{']);
+
+    // We need to enforce recursion limits to prevent
+    // excessive resource consumption.
+    if p.recursion_limit.check_and_increment() {
+        p.limit_err("parser recursion limit reached");
+        return None;
+    }
+    Some(OpenSelectionSet {
+        braced: true,
+        has_selection: false,
+        _guard: guard,
+        _parent: parent,
+    })
+}
+
+/// Parse the selections of `root` and of any selection set nested in it.
+fn selections(p: &mut Parser, root: OpenSelectionSet) {
+    let mut open = vec![root];
+    while let Some(innermost) = open.last_mut() {
+        if let Some(parent) = selection(p, &mut innermost.has_selection) {
+            if let Some(nested) = open_selection_set(p, Some(parent)) {
+                open.push(nested);
+            }
+            continue;
+        }
+
+        let innermost = open.pop().unwrap();
+        if !innermost.has_selection {
+            p.err("expected at least one Selection in Selection Set");
+        }
         p.recursion_limit.decrement();
+        if innermost.braced {
+            p.expect(T!['}'], S!['}']);
+        }
     }
 }
 
@@ -53,46 +104,51 @@ pub(crate) fn field_set(p: &mut Parser) {
 ///     Field
 ///     FragmentSpread
 ///     InlineFragment
-pub(crate) fn selection(p: &mut Parser) {
-    let mut has_selection = false;
-
-    p.peek_while(|p, kind| match kind {
-        T![...] => {
-            let next_token = p.peek_token_n(2);
-            match next_token {
-                Some(next_token) => {
-                    if next_token.kind() == TokenKind::Name && next_token.data() != "on" {
-                        fragment::fragment_spread(p);
-                    } else if matches!(
-                        next_token.kind(),
-                        TokenKind::At | TokenKind::Name | TokenKind::LCurly
-                    ) {
-                        fragment::inline_fragment(p);
-                    } else {
-                        p.err("expected an Inline Fragment or a Fragment Spread");
-                        p.bump(S![...]);
+///
+/// Parses selections until the end of the current selection set,
+/// or until a field or inline fragment that has a nested selection set.
+/// In the latter case, its node is returned and the next token is `{`.
+fn selection(p: &mut Parser, has_selection: &mut bool) -> Option<NodeGuard> {
+    while let Some(kind) = p.peek() {
+        match kind {
+            T![...] => {
+                let next_token = p.peek_token_n(2);
+                match next_token {
+                    Some(next_token) => {
+                        *has_selection = true;
+                        if next_token.kind() == TokenKind::Name && next_token.data() != "on" {
+                            fragment::fragment_spread(p);
+                        } else if matches!(
+                            next_token.kind(),
+                            TokenKind::At | TokenKind::Name | TokenKind::LCurly
+                        ) {
+                            let inline_fragment = fragment::inline_fragment(p);
+                            match p.peek() {
+                                Some(T!['{']) => return Some(inline_fragment),
+                                _ => p.err("expected Selection Set"),
+                            }
+                        } else {
+                            p.err("expected an Inline Fragment or a Fragment Spread");
+                            p.bump(S![...]);
+                        }
+                    }
+                    None => {
+                        p.err_and_pop("expected an Inline Fragment or a Fragment Spread");
+                        break;
                     }
-                    has_selection = true;
-                    ControlFlow::Continue(())
                 }
-                None => {
-                    p.err_and_pop("expected an Inline Fragment or a Fragment Spread");
-                    ControlFlow::Break(())
+            }
+            TokenKind::Name => {
+                *has_selection = true;
+                let field = field::field(p);
+                if let Some(T!['{']) = p.peek() {
+                    return Some(field);
                 }
             }
+            _ => break,
         }
-        T!['{'] => ControlFlow::Break(()),
-        TokenKind::Name => {
-            field::field(p);
-            has_selection = true;
-            ControlFlow::Continue(())
-        }
-        _ => ControlFlow::Break(()),
-    });
-
-    if !has_selection {
-        p.err("expected at least one Selection in Selection Set");
     }
+    None
 }
 
 #[cfg(test)]
@@ -75,6 +75,7 @@ fn parse<'a>(p: &mut Parser<'a>) -> Result<(), Option<Token<'a>>> {
 
     // Deal with nullable types
     if let Some(T![!]) = p.peek() {
+        p.count_node();
         let _guard = checkpoint.wrap_node(SyntaxKind::NON_NULL_TYPE);
 
         p.eat(S![!]);
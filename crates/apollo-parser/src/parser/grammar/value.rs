@@ -1,11 +1,11 @@
 use crate::parser::grammar::name;
 use crate::parser::grammar::variable;
+use crate::parser::NodeGuard;
 use crate::Parser;
 use crate::SyntaxKind;
 use crate::TokenKind;
 use crate::S;
 use crate::T;
-use std::ops::ControlFlow;
 
 #[derive(Clone, Copy)]
 pub(crate) enum Constness {
@@ -25,7 +25,92 @@ pub(crate) enum Constness {
 ///     EnumValue
 ///     ListValue[?Const]
 ///     ObjectValue[?Const]
+///
+/// Nested list and object values are tracked on an explicit stack rather than
+/// with recursive calls, so that their depth is bounded by the parser's recursion limit
+/// and not by the size of the thread's call stack.
 pub(crate) fn value(p: &mut Parser, constness: Constness, pop_on_error: bool) {
+    let mut open: Vec<OpenValue> = Vec::new();
+    let mut next_value = Some(pop_on_error);
+
+    loop {
+        if let Some(pop_on_error) = next_value.take() {
+            match p.peek() {
+                Some(T!['[']) => open.push(list_value(p)),
+                Some(T!['{']) => open.push(object_value(p)),
+                _ => {
+                    scalar_value(p, constness, pop_on_error);
+                    finish_nested_value(p, &open);
+                }
+            }
+        }
+
+        let Some(innermost) = open.last() else {
+            return;
+        };
+        match innermost.kind {
+            OpenValueKind::List => match p.peek() {
+                Some(T![']']) => {
+                    p.bump(S![']']);
+                    close_value(p, &mut open);
+                }
+                None | Some(TokenKind::Eof) => close_value(p, &mut open),
+                Some(_) if p.recursion_limit.check_and_increment() => {
+                    p.limit_err("parser recursion limit reached");
+                    close_value(p, &mut open);
+                }
+                Some(_) => next_value = Some(true),
+            },
+            OpenValueKind::Object => match p.peek() {
+                Some(TokenKind::Name) => {
+                    if let Some(field) = object_field(p) {
+                        open.push(field);
+                        next_value = Some(true);
+                    }
+                }
+                _ => {
+                    p.expect(T!['}'], S!['}']);
+                    close_value(p, &mut open);
+                }
+            },
+            OpenValueKind::ObjectField => {
+                // The field's value was parsed, finish the OBJECT_FIELD node
+                open.pop();
+            }
+        }
+    }
+}
+
+/// A list value, object value, or object field whose end has not been parsed yet.
+/// Dropping it finishes the corresponding node.
+struct OpenValue {
+    kind: OpenValueKind,
+    _guard: NodeGuard,
+}
+
+#[derive(Clone, Copy)]
+enum OpenValueKind {
+    List,
+    Object,
+    ObjectField,
+}
+
+/// Finish the innermost list or object value.
+fn close_value(p: &mut Parser, open: &mut Vec<OpenValue>) {
+    open.pop();
+    finish_nested_value(p, open);
+}
+
+/// A value inside a list or object field was fully parsed:
+/// balance the recursion limit increment done before parsing it.
+fn finish_nested_value(p: &mut Parser, open: &[OpenValue]) {
+    if !open.is_empty() {
+        p.recursion_limit.decrement();
+    }
+}
+
+/// Any value other than a list or object value.
+fn scalar_value(p: &mut Parser, constness: Constness, pop_on_error: bool) {
     match p.peek() {
         Some(T![$]) => {
             if let Constness::Const = constness {
@@ -69,8 +154,6 @@ pub(crate) fn value(p: &mut Parser, constness: Constness, pop_on_error: bool) {
                 _ => enum_value(p),
             }
         }
-        Some(T!['[']) => list_value(p, constness),
-        Some(T!['{']) => object_value(p, constness),
         _ => {
             let error_message = "expected a valid Value";
             if pop_on_error {
@@ -81,6 +164,7 @@ pub(crate) fn value(p: &mut Parser, constness: Constness, pop_on_error: bool) {
         }
     }
 }
+
 /// See: https://spec.graphql.org/October2021/#EnumValue
 ///
 /// *EnumValue*:
@@ -105,25 +189,15 @@ pub(crate) fn enum_value(p: &mut Parser) {
 /// *ListValue[Const]*:
 ///     **[** **]**
 ///     **[** Value[?Const]* **]**
-pub(crate) fn list_value(p: &mut Parser, constness: Constness) {
-    let _g = p.start_node(SyntaxKind::LIST_VALUE);
+///
+/// Starts the list value. Items and the closing bracket are parsed by [`value`].
+fn list_value(p: &mut Parser) -> OpenValue {
+    let _guard = p.start_node(SyntaxKind::LIST_VALUE);
     p.bump(S!['[']);
-
-    p.peek_while(|p, node| {
-        if node == T![']'] {
-            p.bump(S![']']);
-            ControlFlow::Break(())
-        } else if node == TokenKind::Eof {
-            ControlFlow::Break(())
-        } else if p.recursion_limit.check_and_increment() {
-            p.limit_err("parser recursion limit reached");
-            ControlFlow::Break(())
-        } else {
-            value(p, constness, true);
-            p.recursion_limit.decrement();
-            ControlFlow::Continue(())
-        }
-    });
+    OpenValue {
+        kind: OpenValueKind::List,
+        _guard,
+    }
 }
 
 /// See: https://spec.graphql.org/October2021/#ObjectValue
@@ -131,22 +205,24 @@ pub(crate) fn list_value(p: &mut Parser, constness: Constness) {
 /// *ObjectValue[Const]*:
 ///     **{** **}**
 ///     **{** ObjectField[?Const]* **}**
-pub(crate) fn object_value(p: &mut Parser, constness: Constness) {
-    let _g = p.start_node(SyntaxKind::OBJECT_VALUE);
+///
+/// Starts the object value. Fields and the closing brace are parsed by [`value`].
+fn object_value(p: &mut Parser) -> OpenValue {
+    let _guard = p.start_node(SyntaxKind::OBJECT_VALUE);
     p.bump(S!['{']);
-
-    p.peek_while_kind(TokenKind::Name, |p| {
-        object_field(p, constness);
-    });
-
-    p.expect(T!['}'], S!['}']);
+    OpenValue {
+        kind: OpenValueKind::Object,
+        _guard,
+    }
 }
 
 /// See: https://spec.graphql.org/October2021/#ObjectField
 ///
 /// *ObjectField[Const]*:
 ///     Name **:** Value[?Const]
-pub(crate) fn object_field(p: &mut Parser, constness: Constness) {
+///
+/// Returns the open field if its value should be parsed next by [`value`].
+fn object_field(p: &mut Parser) -> Option<OpenValue> {
     let _guard = p.start_node(SyntaxKind::OBJECT_FIELD);
     name::name(p);
 
@@ -154,11 +230,14 @@ pub(crate) fn object_field(p: &mut Parser, constness: Constness) {
         p.bump(S![:]);
         if p.recursion_limit.check_and_increment() {
             p.limit_err("parser recursion limit reached");
-            return;
+            return None;
         }
-        value(p, constness, true);
-        p.recursion_limit.decrement()
+        return Some(OpenValue {
+            kind: OpenValueKind::ObjectField,
+            _guard,
+        });
     }
+    None
 }
 
 /// See: https://spec.graphql.org/October2021/#DefaultValue
@@ -0,0 +1,448 @@
+use crate::cst;
+use crate::Error;
+use crate::Lexer;
+use crate::LimitTracker;
+use crate::Parser;
+use crate::Recovery;
+use crate::SourceOffset;
+use crate::SyntaxKind;
+use crate::SyntaxNode;
+use crate::SyntaxTree;
+use crate::TextRange;
+use rowan::GreenNodeData;
+use rowan::NodeOrToken;
+use rowan::TextSize;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// A change to the source text of a document: the text in `range` is replaced with `replacement`.
+///
+/// Used with [`Parser::reparse`] to update a previously parsed [`SyntaxTree`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    /// The range of the replaced text, in the source text before the edit.
+    pub range: TextRange,
+    /// The text inserted in place of `range`.
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Replace the text in `range` with `replacement`.
+    pub fn replace(range: TextRange, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Insert `text` at `offset`.
+    pub fn insert(offset: TextSize, text: impl Into<String>) -> Self {
+        Self::replace(TextRange::empty(offset), text)
+    }
+
+    /// Remove the text in `range`.
+    pub fn delete(range: TextRange) -> Self {
+        Self::replace(range, String::new())
+    }
+
+    /// Apply the edit to `text`, which must be the source text before the edit.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `range` is out of bounds of `text` or not on a character boundary.
+    pub fn apply(&self, text: &mut String) {
+        text.replace_range(
+            std::ops::Range::<usize>::from(self.range),
+            &self.replacement,
+        );
+    }
+
+    /// Returns whether `new_text` is the result of applying this edit to a text of `old_len` bytes.
+    fn matches(&self, old_len: TextSize, new_text: &str) -> bool {
+        let start = usize::from(self.range.start());
+        let inserted_end = start + self.replacement.len();
+        self.range.end() <= old_len
+            && u32::from(old_len) as usize - usize::from(self.range.len()) + self.replacement.len()
+                == new_text.len()
+            && new_text.get(start..inserted_end) == Some(self.replacement.as_str())
+    }
+}
+
+/// Try to update `old` for `edit` by only reparsing the top-level definition that contains it.
+///
+/// Returns `None` when this can not be guaranteed to produce the same tree as a full parse of
+/// `parser`'s input, and the caller should fall back to that.
+///
+/// The replaced definition must end with a `}` both before and after the edit,
+/// as must the definition before it (if any).
+/// Those definitions can not be extended by the tokens that follow,
+/// so the edit can not change where the neighbouring definitions start or end.
+pub(crate) fn reparse_definition(
+    parser: &Parser<'_>,
+    old: &SyntaxTree<cst::Document>,
+    edit: &TextEdit,
+) -> Option<SyntaxTree<cst::Document>> {
+    let old_root: &GreenNodeData = &old.green;
+    if old.errors.iter().any(Error::is_limit)
+        || old.source_offset != parser.options.source_offset
+        || !parser.options.trivia
+        || !edit.matches(old_root.text_len(), parser.input)
+    {
+        return None;
+    }
+
+    // Find the top-level definition that contains the edit.
+    let mut offset = TextSize::from(0);
+    let mut previous_closed = true;
+    let mut found = None;
+    for (index, child) in old_root.children().enumerate() {
+        let range = TextRange::at(offset, child.text_len());
+        offset += child.text_len();
+        match child {
+            NodeOrToken::Node(node) => {
+                if range.contains_range(edit.range) {
+                    found = Some((index, node, range));
+                    break;
+                }
+                previous_closed = ends_with_r_curly(node);
+            }
+            NodeOrToken::Token(token) => {
+                if range.end() > edit.range.start() {
+                    // The edit touches top-level trivia or an error token.
+                    return None;
+                }
+                if !is_trivia(SyntaxKind::from(token.kind().0)) {
+                    previous_closed = false;
+                }
+            }
+        }
+    }
+    let (index, old_definition, old_range) = found?;
+    if !previous_closed || !ends_with_r_curly(old_definition) {
+        return None;
+    }
+
+    let new_end =
+        usize::from(old_range.end()) + edit.replacement.len() - usize::from(edit.range.len());
+    let start = usize::from(old_range.start());
+    let text = parser.input.get(start..new_end)?;
+
+    // Shift error indices to the full input, and to the host file if any
+    let byte = parser.options.source_offset.byte;
+    let mut options = parser.options;
+    options.source_offset = SourceOffset {
+        line: 0,
+        column: 0,
+        byte: byte + start,
+    };
+    let region = Parser::with_options(text, options).parse();
+    if region.errors.iter().any(Error::is_limit) {
+        return None;
+    }
+    // Other recovery modes skip past the end of the definition after an error,
+    // or stop parsing altogether
+    if options.recovery != Recovery::Token && (!old.errors.is_empty() || !region.errors.is_empty())
+    {
+        return None;
+    }
+
+    // The reparsed text must still be exactly one definition that can not be extended.
+    let mut children = region.green.children();
+    let new_definition = match (children.next(), children.next()) {
+        (Some(NodeOrToken::Node(node)), None) => node,
+        _ => return None,
+    };
+    if !ends_with_r_curly(new_definition) {
+        return None;
+    }
+
+    // Both counts include an EOF token, which cancel out.
+    let old_tokens = Lexer::new(&old_definition.to_string()).count();
+    let tokens = old.token_limit.high - old_tokens + region.token_limit.high;
+    let token_limit = parser.lexer.limit_tracker.limit;
+    if tokens > token_limit {
+        return None;
+    }
+
+    // The region's count includes its own document node.
+    let old_nodes = SyntaxNode::new_root(old_definition.to_owned())
+        .descendants()
+        .count();
+    let nodes = old.node_limit.high - old_nodes - 1 + region.node_limit.high;
+    let node_limit = parser.node_limit.limit;
+    if nodes > node_limit {
+        return None;
+    }
+
+    let green = old_root.replace_child(index, NodeOrToken::Node(new_definition.to_owned()));
+    let errors = splice_errors(
+        &old.errors,
+        byte + start..byte + usize::from(old_range.end()),
+        region.errors.clone(),
+        byte + new_end,
+    );
+
+    Some(SyntaxTree {
+        green,
+        errors,
+        recursion_limit: LimitTracker {
+            current: 0,
+            high: old.recursion_limit.high.max(region.recursion_limit.high),
+            limit: parser.recursion_limit.limit,
+        },
+        token_limit: LimitTracker {
+            current: 0,
+            high: tokens,
+            limit: token_limit,
+        },
+        node_limit: LimitTracker {
+            current: 0,
+            high: nodes,
+            limit: node_limit,
+        },
+        source_offset: parser.options.source_offset,
+        // Both have a document root with definitions as children
+        depth: old.depth.max(region.depth),
+        _phantom: PhantomData,
+    })
+}
+
+/// Replace errors reported in `old_range` with `region_errors`,
+/// and shift errors after the range to its new end.
+fn splice_errors(
+    old_errors: &[Error],
+    old_range: Range<usize>,
+    region_errors: Vec<Error>,
+    new_end: usize,
+) -> Vec<Error> {
+    let Range {
+        start,
+        end: old_end,
+    } = old_range;
+    let mut errors: Vec<Error> = old_errors
+        .iter()
+        .filter(|err| err.index < start)
+        .cloned()
+        .collect();
+    errors.extend(region_errors);
+    errors.extend(
+        old_errors
+            .iter()
+            .filter(|err| err.index >= old_end)
+            .map(|err| {
+                let mut err = err.clone();
+                err.index = err.index - old_end + new_end;
+                err
+            }),
+    );
+    errors
+}
+
+fn ends_with_r_curly(node: &GreenNodeData) -> bool {
+    let mut node = node;
+    loop {
+        let last = node
+            .children()
+            .rev()
+            .find(|child| !is_trivia(SyntaxKind::from(child.kind().0)));
+        match last {
+            Some(NodeOrToken::Node(child)) => node = child,
+            Some(NodeOrToken::Token(token)) => {
+                return SyntaxKind::from(token.kind().0) == SyntaxKind::R_CURLY
+            }
+            None => return false,
+        }
+    }
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE | SyntaxKind::COMMENT | SyntaxKind::COMMA
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextEdit;
+    use crate::cst;
+    use crate::cst::CstNode;
+    use crate::Parser;
+    use crate::Recovery;
+    use crate::SyntaxKind;
+    use crate::SyntaxTree;
+    use crate::TextRange;
+
+    /// Apply `edit` to `source` and check that reparsing gives the same result as a full parse.
+    fn check(source: &str, edit: TextEdit, expect_incremental: bool) -> SyntaxTree<cst::Document> {
+        check_with(source, edit, expect_incremental, |input| Parser::new(input))
+    }
+
+    /// Like `check`, with parsers configured by `new_parser`
+    fn check_with(
+        source: &str,
+        edit: TextEdit,
+        expect_incremental: bool,
+        new_parser: fn(&str) -> Parser<'_>,
+    ) -> SyntaxTree<cst::Document> {
+        let old = new_parser(source).parse();
+        let mut new_source = source.to_string();
+        edit.apply(&mut new_source);
+
+        let full = new_parser(&new_source).parse();
+        let incremental = super::reparse_definition(&new_parser(&new_source), &old, &edit);
+        assert_eq!(incremental.is_some(), expect_incremental);
+
+        let reparsed = new_parser(&new_source).reparse(&old, &edit);
+        assert_eq!(format!("{reparsed:?}"), format!("{full:?}"));
+        assert_eq!(reparsed.token_limit().high, full.token_limit().high);
+        assert_eq!(reparsed.node_limit().high, full.node_limit().high);
+        reparsed
+    }
+
+    fn find(source: &str, text: &str) -> TextRange {
+        let start = source.find(text).unwrap();
+        TextRange::new((start as u32).into(), ((start + text.len()) as u32).into())
+    }
+
+    #[test]
+    fn reparses_edited_definition() {
+        let source = "type Query { a: Int }\n\ntype Pet { name: String }\n\n{ a }\n";
+        let edit = TextEdit::replace(find(source, "name"), "nickname");
+        let reparsed = check(source, edit.clone(), true);
+        assert_eq!(reparsed.errors().len(), 0);
+
+        let old = Parser::new(source).parse();
+        let mut new_source = source.to_string();
+        edit.apply(&mut new_source);
+        let reparsed = Parser::new(&new_source).reparse(&old, &edit);
+        let old_query = old.document().definitions().next().unwrap();
+        let new_query = reparsed.document().definitions().next().unwrap();
+        // Unchanged definitions share the same green node.
+        assert!(std::ptr::eq(
+            &*old_query.syntax().green(),
+            &*new_query.syntax().green(),
+        ));
+    }
+
+    #[test]
+    fn shifts_errors() {
+        let source = "type A { a: }\ntype B { b: Int }\ntype C { c: }\n";
+        let reparsed = check(
+            source,
+            TextEdit::insert(find(source, "b: Int").start(), "bb: Int "),
+            true,
+        );
+        assert_eq!(reparsed.errors().len(), 2);
+
+        let reparsed = check(
+            source,
+            TextEdit::replace(find(source, "c: "), "c: String"),
+            true,
+        );
+        assert_eq!(reparsed.errors().len(), 1);
+    }
+
+    #[test]
+    fn falls_back_to_full_parse() {
+        let source = "type Query { a: Int }\nscalar X\ntype Pet { name: String }\n";
+        // Edits in top-level whitespace
+        check(
+            source,
+            TextEdit::insert(find(source, "\nscalar").start(), "\n"),
+            false,
+        );
+        // The previous definition could absorb tokens from the edited definition
+        check(
+            source,
+            TextEdit::replace(find(source, "name"), "nickname"),
+            false,
+        );
+        // Edits that no longer end the definition with a `}`
+        check(
+            source,
+            TextEdit::insert(find(source, " }").start(), " \"\"\""),
+            false,
+        );
+        // Edits that split the definition in two
+        check(
+            source,
+            TextEdit::insert(find(source, " a: Int").start(), " } type B {"),
+            false,
+        );
+    }
+
+    #[test]
+    fn strict_recovery() {
+        let strict: fn(&str) -> Parser<'_> = |input| Parser::new(input).recovery(Recovery::Strict);
+        let source = "type A { a: Int }\ntype B { b: Int }\ntype C { c: Int }\n";
+        check_with(
+            source,
+            TextEdit::replace(find(source, "b: Int"), "bb: Int"),
+            true,
+            strict,
+        );
+        // Later definitions become `ERROR` tokens after the first error
+        let reparsed = check_with(
+            source,
+            TextEdit::delete(find(source, "Int }\ntype C")),
+            false,
+            strict,
+        );
+        assert_eq!(reparsed.errors().len(), 1);
+        let source = "type A { a: Int }\ntype B { b: }\ntype C { c: Int }\n";
+        check_with(
+            source,
+            TextEdit::insert(find(source, " }\ntype C").start(), "Int"),
+            false,
+            strict,
+        );
+    }
+
+    #[test]
+    fn definition_recovery() {
+        let definition: fn(&str) -> Parser<'_> =
+            |input| Parser::new(input).recovery(Recovery::Definition);
+        let source = "type A { a: Int }\ntype B { b: Int c: Int }\ntype C { c: Int }\n";
+        check_with(
+            source,
+            TextEdit::replace(find(source, "b: Int"), "b: Int!"),
+            true,
+            definition,
+        );
+        check_with(
+            source,
+            TextEdit::delete(find(source, "Int c")),
+            false,
+            definition,
+        );
+    }
+
+    #[test]
+    fn missing_nodes() {
+        let missing: fn(&str) -> Parser<'_> = |input| Parser::new(input).missing_nodes(true);
+        let source = "type A { a: Int }\ntype B { b: Int }\n{ c(x: 1) }\n";
+        let reparsed = check_with(
+            source,
+            TextEdit::replace(find(source, "b: Int"), "b: [Int"),
+            true,
+            missing,
+        );
+        assert!(reparsed
+            .document()
+            .syntax()
+            .descendants()
+            .any(|node| node.kind() == SyntaxKind::MISSING));
+        check_with(source, TextEdit::delete(find(source, "1")), true, missing);
+    }
+
+    #[test]
+    fn mismatched_edit() {
+        let source = "type Query { a: Int }";
+        let old = Parser::new(source).parse();
+        let edit = TextEdit::replace(find(source, "a"), "b");
+        let tree = Parser::new("type Query { c: Int }").reparse(&old, &edit);
+        let query = tree.document().definitions().next().unwrap();
+        assert_eq!(query.syntax().to_string(), "type Query { c: Int }");
+    }
+}
@@ -1,5 +1,11 @@
+mod edit;
+mod events;
+mod extension;
 mod generated;
+mod incremental;
 mod language;
+#[cfg(feature = "serde")]
+mod serde_impls;
 mod syntax_tree;
 mod token_text;
 
@@ -8,12 +14,19 @@ pub(crate) mod grammar;
 use crate::cst::Document;
 use crate::cst::SelectionSet;
 use crate::cst::Type;
+use crate::cst::Value;
 use crate::lexer::Lexer;
 use crate::Error;
+use crate::ErrorKind;
 use crate::LimitTracker;
+use crate::SourceOffset;
 use crate::Token;
 use crate::TokenKind;
+pub use events::ParseEventHandler;
+pub use extension::ExtensionParser;
+pub use extension::SyntaxExtension;
 pub use generated::syntax_kind::SyntaxKind;
+pub use incremental::TextEdit;
 pub use language::SyntaxElement;
 pub use language::SyntaxNode;
 pub use language::SyntaxNodeChildren;
@@ -22,8 +35,9 @@ pub use language::SyntaxToken;
 use std::cell::RefCell;
 use std::ops::ControlFlow;
 use std::rc::Rc;
-pub use syntax_tree::SyntaxTree;
 // pub(crate) use language::GraphQLLanguage;
+pub(crate) use syntax_tree::BuilderCheckpoint;
+pub use syntax_tree::SyntaxTree;
 pub(crate) use syntax_tree::SyntaxTreeBuilder;
 pub(crate) use token_text::TokenText;
 
@@ -81,6 +95,7 @@ pub(crate) use token_text::TokenText;
 /// ```
 #[derive(Debug)]
 pub struct Parser<'input> {
+    input: &'input str,
     lexer: Lexer<'input>,
     /// Store one lookahead token so we don't need to reparse things as much.
     current_token: Option<Token<'input>>,
@@ -92,8 +107,85 @@ pub struct Parser<'input> {
     errors: Vec<crate::Error>,
     /// The limit to apply to parsing.
     recursion_limit: LimitTracker,
+    /// The limit on the number of nodes in the tree.
+    node_limit: LimitTracker,
     /// Accept parsing errors?
     accept_errors: bool,
+    /// Skipping tokens after an error, as configured by `options.recovery`?
+    /// While set, the parser sees the end of the input.
+    recovering: bool,
+    /// Configuration set with the builder methods
+    options: ParserOptions<'input>,
+}
+
+/// Configuration of a [`Parser`], set with its builder methods.
+///
+/// Kept in one value so that parsers for part of the input,
+/// like the definition reparsed by [`Parser::reparse`], get the same configuration.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ParserOptions<'input> {
+    recursion_limit: usize,
+    token_limit: usize,
+    node_limit: usize,
+    /// How to continue after a syntax error
+    pub(crate) recovery: Recovery,
+    /// Insert `MISSING` nodes for expected tokens?
+    missing_nodes: bool,
+    /// Add whitespace and comment tokens to the tree?
+    pub(crate) trivia: bool,
+    /// Parses custom definitions
+    pub(crate) syntax_extension: Option<ExtensionRef<'input>>,
+    /// Where the input starts in a host file
+    pub(crate) source_offset: SourceOffset,
+}
+
+impl Default for ParserOptions<'_> {
+    fn default() -> Self {
+        Self {
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            token_limit: usize::MAX,
+            node_limit: usize::MAX,
+            recovery: Recovery::default(),
+            missing_nodes: false,
+            trivia: true,
+            syntax_extension: None,
+            source_offset: SourceOffset::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ExtensionRef<'input>(pub(crate) &'input dyn SyntaxExtension);
+
+impl std::fmt::Debug for ExtensionRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SyntaxExtension")
+    }
+}
+
+/// How the parser continues after a syntax error, configured with [`Parser::recovery`].
+///
+/// In all cases, the syntax tree contains all of the input text:
+/// tokens skipped after an error are kept as `ERROR` tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Recovery {
+    /// Resume parsing at the next token that fits, such as the next field
+    /// of a selection set. Each error is reported.
+    ///
+    /// This gives the most complete tree, for editors and language servers.
+    #[default]
+    Token,
+    /// Skip the rest of the definition that contains an error, and resume parsing at
+    /// the next token that starts a definition, such as the `type` keyword.
+    /// Only the first error of each definition is reported.
+    ///
+    /// This avoids cascading errors, at the cost of an incomplete tree for that definition.
+    Definition,
+    /// Stop parsing at the first error, which is the only error reported.
+    ///
+    /// This suits validators in CI, which reject the input anyway.
+    Strict,
 }
 
 /// Chosen experimentally with:
@@ -112,21 +204,30 @@ const DEFAULT_RECURSION_LIMIT: usize = 500;
 impl<'input> Parser<'input> {
     /// Create a new instance of a parser given an input string.
     pub fn new(input: &'input str) -> Self {
-        let lexer = Lexer::new(input);
+        Self::with_options(input, ParserOptions::default())
+    }
+
+    pub(crate) fn with_options(input: &'input str, options: ParserOptions<'input>) -> Self {
+        let lexer = Lexer::new(input).with_limit(options.token_limit);
 
         Self {
+            input,
             lexer,
             current_token: None,
             builder: Rc::new(RefCell::new(SyntaxTreeBuilder::new())),
             ignored: vec![],
             errors: Vec::new(),
-            recursion_limit: LimitTracker::new(DEFAULT_RECURSION_LIMIT),
+            recursion_limit: LimitTracker::new(options.recursion_limit),
+            node_limit: LimitTracker::new(options.node_limit),
             accept_errors: true,
+            recovering: false,
+            options,
         }
     }
 
     /// Configure the recursion limit to use while parsing.
     pub fn recursion_limit(mut self, recursion_limit: usize) -> Self {
+        self.options.recursion_limit = recursion_limit;
         self.recursion_limit = LimitTracker::new(recursion_limit);
         self
     }
@@ -136,10 +237,117 @@ impl<'input> Parser<'input> {
     ///
     /// By default, there is no limit.
     pub fn token_limit(mut self, token_limit: usize) -> Self {
+        self.options.token_limit = token_limit;
         self.lexer = self.lexer.with_limit(token_limit);
         self
     }
 
+    /// Configure the limit on the number of nodes in the syntax tree. If a document
+    /// would produce more nodes, parsing will be aborted.
+    ///
+    /// The token limit alone does not bound the size of the tree, as a single token
+    /// can start several nested nodes, like a field name in a selection set.
+    /// This limit bounds the memory used for the tree when parsing untrusted input.
+    ///
+    /// By default, there is no limit.
+    pub fn node_limit(mut self, node_limit: usize) -> Self {
+        self.options.node_limit = node_limit;
+        self.node_limit = LimitTracker::new(node_limit);
+        self
+    }
+
+    /// Configure how the parser continues after a syntax error.
+    ///
+    /// By default, the parser recovers as close to the error as it can
+    /// ([`Recovery::Token`]), which gives editors the most complete tree.
+    pub fn recovery(mut self, recovery: Recovery) -> Self {
+        self.options.recovery = recovery;
+        self
+    }
+
+    /// Configure whether to insert an empty [`SyntaxKind::MISSING`] node where
+    /// an expected token, such as a closing `}`, is missing.
+    ///
+    /// Editors can use these to position completions and quick fixes.
+    /// By default, no such nodes are inserted.
+    pub fn missing_nodes(mut self, missing_nodes: bool) -> Self {
+        self.options.missing_nodes = missing_nodes;
+        self
+    }
+
+    /// Configure whether whitespace and comment tokens are added to the syntax tree.
+    ///
+    /// Without them, the tree uses significantly less memory, which matters for
+    /// services that only validate documents, or for large SDL files generated
+    /// from introspection. But the tree is no longer lossless: printing it does not
+    /// reproduce the input, and the text ranges of syntax nodes no longer match
+    /// offsets in the input. [Error indices](Error::index) are still offsets in the input.
+    /// Commas are kept.
+    ///
+    /// By default, whitespace and comments are part of the tree.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use apollo_parser::cst::CstNode;
+    /// use apollo_parser::Parser;
+    ///
+    /// let input = "# A comment\n{ a  b }";
+    /// let tree = Parser::new(input).trivia(false).parse();
+    /// assert_eq!(tree.errors().len(), 0);
+    /// assert_eq!(tree.document().syntax().to_string(), "{ab}");
+    /// ```
+    pub fn trivia(mut self, trivia: bool) -> Self {
+        self.options.trivia = trivia;
+        self
+    }
+
+    /// Configure an extension that parses top-level definitions
+    /// starting with keywords that are not part of GraphQL.
+    ///
+    /// See [`SyntaxExtension`].
+    pub fn syntax_extension(mut self, extension: &'input dyn SyntaxExtension) -> Self {
+        self.options.syntax_extension = Some(ExtensionRef(extension));
+        self
+    }
+
+    /// Configure where the input starts in a host file, for GraphQL embedded
+    /// in a string literal of another language. `line` and `column` are zero-based.
+    ///
+    /// [Error indices](Error::index) are shifted by `byte`,
+    /// so that diagnostics point at the location in the host file.
+    /// Syntax nodes still have text ranges relative to the input.
+    /// Use [`SyntaxTree::source_offset`] to convert them,
+    /// and [`SourceOffset::host_position`] for lines and columns.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use apollo_parser::LineCol;
+    /// use apollo_parser::LineIndex;
+    /// use apollo_parser::Parser;
+    /// use apollo_parser::PositionEncoding;
+    ///
+    /// let host = "let query = gql!(\"{ user(id: ) }\");";
+    /// let start = host.find('{').unwrap();
+    /// let snippet = &host[start..host.rfind('"').unwrap()];
+    ///
+    /// let tree = Parser::new(snippet)
+    ///     .with_source_offset(0, start as u32, start)
+    ///     .parse();
+    /// let error = tree.errors().next().unwrap();
+    /// assert_eq!(&host[error.index()..][..1], ")");
+    ///
+    /// let offset = error.index() - tree.source_offset().byte;
+    /// let position = LineIndex::new(snippet).position(offset, PositionEncoding::Utf8).unwrap();
+    /// let position = tree.source_offset().host_position(position);
+    /// assert_eq!(position, LineCol { line: 0, col: 29 });
+    /// ```
+    pub fn with_source_offset(mut self, line: u32, column: u32, byte: usize) -> Self {
+        self.options.source_offset = SourceOffset { line, column, byte };
+        self
+    }
+
     /// Parse the current tokens.
     pub fn parse(mut self) -> SyntaxTree<Document> {
         grammar::document::document(&mut self);
@@ -147,18 +355,123 @@ impl<'input> Parser<'input> {
         let builder = Rc::try_unwrap(self.builder)
             .expect("More than one reference to builder left")
             .into_inner();
-        let builder =
-            builder.finish_document(self.errors, self.recursion_limit, self.lexer.limit_tracker);
+        let builder = builder.finish_document(
+            self.errors,
+            self.recursion_limit,
+            self.lexer.limit_tracker,
+            self.node_limit,
+            self.options.source_offset,
+        );
 
         match builder {
             syntax_tree::SyntaxTreeWrapper::Document(tree) => tree,
             syntax_tree::SyntaxTreeWrapper::Type(_)
-            | syntax_tree::SyntaxTreeWrapper::FieldSet(_) => {
+            | syntax_tree::SyntaxTreeWrapper::FieldSet(_)
+            | syntax_tree::SyntaxTreeWrapper::Value(_) => {
                 unreachable!("parse constructor can only construct a document")
             }
         }
     }
 
+    /// Parse a document after a change to its source text,
+    /// reusing the parts of `old` that are not affected by `edit`.
+    ///
+    /// `old` is the tree for the source text before the edit,
+    /// and this parser's input is the source text after it.
+    /// When the edit is inside a top-level definition that is delimited by braces,
+    /// like a type definition or an operation, only that definition is reparsed
+    /// and the other definitions share their syntax nodes with `old`.
+    /// Otherwise, or if the input does not match `edit`, this parses the whole input
+    /// like [`parse`][Self::parse]. With a [`recovery`][Self::recovery] other than
+    /// [`Recovery::Token`], the whole input is also parsed when `old` or the reparsed definition
+    /// has errors. Either way, the result has the same nodes and errors
+    /// as a full parse, but the recursion limit's high water mark may be that of `old`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use apollo_parser::cst::CstNode;
+    /// use apollo_parser::Parser;
+    /// use apollo_parser::TextEdit;
+    ///
+    /// let mut source = "type Query { a: Int }\ntype Pet { name: String }".to_string();
+    /// let tree = Parser::new(&source).parse();
+    ///
+    /// let edit = TextEdit::insert(33.into(), "nick");
+    /// edit.apply(&mut source);
+    /// let tree = Parser::new(&source).reparse(&tree, &edit);
+    /// assert_eq!(tree.errors().len(), 0);
+    /// assert_eq!(
+    ///     tree.document().syntax().to_string(),
+    ///     "type Query { a: Int }\ntype Pet { nickname: String }",
+    /// );
+    /// ```
+    pub fn reparse(self, old: &SyntaxTree<Document>, edit: &TextEdit) -> SyntaxTree<Document> {
+        match incremental::reparse_definition(&self, old, edit) {
+            Some(tree) => tree,
+            None => self.parse(),
+        }
+    }
+
+    /// Parse a document without building a syntax tree,
+    /// and report its structure to `handler` instead.
+    ///
+    /// `handler` receives the same nodes and tokens that [`parse`][Self::parse]
+    /// would put in the tree, in document order.
+    /// This is cheaper when only some information about the document is needed,
+    /// like counting operations or hashing a normalized form of the source.
+    /// Returns the syntax errors, which are not reported to `handler`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use apollo_parser::ParseEventHandler;
+    /// use apollo_parser::Parser;
+    /// use apollo_parser::SyntaxKind;
+    ///
+    /// #[derive(Default)]
+    /// struct FieldCounter {
+    ///     fields: usize,
+    ///     max_depth: usize,
+    ///     depth: usize,
+    /// }
+    ///
+    /// impl ParseEventHandler for FieldCounter {
+    ///     fn start_node(&mut self, kind: SyntaxKind) {
+    ///         match kind {
+    ///             SyntaxKind::FIELD => self.fields += 1,
+    ///             SyntaxKind::SELECTION_SET => {
+    ///                 self.depth += 1;
+    ///                 self.max_depth = self.max_depth.max(self.depth);
+    ///             }
+    ///             _ => {}
+    ///         }
+    ///     }
+    ///
+    ///     fn finish_node(&mut self, kind: SyntaxKind) {
+    ///         if kind == SyntaxKind::SELECTION_SET {
+    ///             self.depth -= 1;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut counter = FieldCounter::default();
+    /// let errors = Parser::new("{ a { b c } d }").parse_with_events(&mut counter);
+    /// assert!(errors.is_empty());
+    /// assert_eq!(counter.fields, 4);
+    /// assert_eq!(counter.max_depth, 2);
+    /// ```
+    pub fn parse_with_events(mut self, handler: &mut impl ParseEventHandler) -> Vec<Error> {
+        self.builder = Rc::new(RefCell::new(SyntaxTreeBuilder::events()));
+        grammar::document::document(&mut self);
+
+        let builder = Rc::try_unwrap(self.builder)
+            .expect("More than one reference to builder left")
+            .into_inner();
+        events::replay(self.input, builder.finish_events(), handler);
+        self.errors
+    }
+
     /// Parse a selection set with optional outer braces.
     /// This is the expected format of the string value of the `fields` argument of some directives
     /// like [`@requires`](https://www.apollographql.com/docs/federation/federated-types/federated-directives/#requires).
@@ -172,12 +485,15 @@ impl<'input> Parser<'input> {
             self.errors,
             self.recursion_limit,
             self.lexer.limit_tracker,
+            self.node_limit,
+            self.options.source_offset,
         );
 
         match builder {
             syntax_tree::SyntaxTreeWrapper::FieldSet(tree) => tree,
             syntax_tree::SyntaxTreeWrapper::Document(_)
-            | syntax_tree::SyntaxTreeWrapper::Type(_) => {
+            | syntax_tree::SyntaxTreeWrapper::Type(_)
+            | syntax_tree::SyntaxTreeWrapper::Value(_) => {
                 unreachable!("parse_selection_set constructor can only construct a selection set")
             }
         }
@@ -186,24 +502,100 @@ impl<'input> Parser<'input> {
     /// Parse a GraphQL type.
     /// This is the expected format of the string value of the `type` argument
     /// of some directives like [`@field`](https://specs.apollo.dev/join/v0.3/#@field).
+    ///
+    /// Unexpected tokens after the type are kept in the type node as errors.
+    /// [`SyntaxTree::ty`] panics if the input does not start with a type,
+    /// check for [errors][SyntaxTree::errors] first.
     pub fn parse_type(mut self) -> SyntaxTree<Type> {
+        let root = self.start_node(SyntaxKind::ERROR);
         grammar::ty::ty(&mut self);
+        self.finish_standalone(root, "expected the end of the type");
 
         let builder = Rc::try_unwrap(self.builder)
             .expect("More than one reference to builder left")
             .into_inner();
-        let builder =
-            builder.finish_type(self.errors, self.recursion_limit, self.lexer.limit_tracker);
+        let builder = builder.finish_type(
+            self.errors,
+            self.recursion_limit,
+            self.lexer.limit_tracker,
+            self.node_limit,
+            self.options.source_offset,
+        );
 
         match builder {
             syntax_tree::SyntaxTreeWrapper::Type(tree) => tree,
             syntax_tree::SyntaxTreeWrapper::FieldSet(_)
-            | syntax_tree::SyntaxTreeWrapper::Document(_) => {
+            | syntax_tree::SyntaxTreeWrapper::Document(_)
+            | syntax_tree::SyntaxTreeWrapper::Value(_) => {
                 unreachable!("parse_type constructor can only construct a type")
             }
         }
     }
 
+    /// Parse a GraphQL value, which may contain variables.
+    /// This is useful for tools that edit values on their own,
+    /// like the arguments of a directive application.
+    ///
+    /// Unexpected tokens after the value are kept in the value node as errors.
+    /// Input that does not start with a value is kept in an error node,
+    /// for which [`SyntaxTree::value`] returns `None`.
+    pub fn parse_value(mut self) -> SyntaxTree<Value> {
+        let root = self.start_node(SyntaxKind::ERROR);
+        if matches!(
+            self.peek(),
+            Some(
+                TokenKind::Dollar
+                    | TokenKind::Int
+                    | TokenKind::Float
+                    | TokenKind::StringValue
+                    | TokenKind::Name
+                    | TokenKind::LBracket
+                    | TokenKind::LCurly
+            )
+        ) {
+            grammar::value::value(&mut self, grammar::value::Constness::NotConst, false);
+        } else {
+            self.err("expected a valid Value");
+        }
+        self.finish_standalone(root, "expected the end of the value");
+
+        let builder = Rc::try_unwrap(self.builder)
+            .expect("More than one reference to builder left")
+            .into_inner();
+        let builder = builder.finish_value(
+            self.errors,
+            self.recursion_limit,
+            self.lexer.limit_tracker,
+            self.node_limit,
+            self.options.source_offset,
+        );
+
+        match builder {
+            syntax_tree::SyntaxTreeWrapper::Value(tree) => tree,
+            syntax_tree::SyntaxTreeWrapper::FieldSet(_)
+            | syntax_tree::SyntaxTreeWrapper::Document(_)
+            | syntax_tree::SyntaxTreeWrapper::Type(_) => {
+                unreachable!("parse_value constructor can only construct a value")
+            }
+        }
+    }
+
+    /// Finish parsing a standalone type or value, whose node was started after `root`.
+    ///
+    /// `root` is a temporary node for the surrounding whitespace and unexpected tokens,
+    /// which the syntax tree builder then moves into the type or value node.
+    fn finish_standalone(&mut self, root: NodeGuard, message: &str) {
+        if self.recovering {
+            self.recovering = false;
+            self.skip_until(|_| false);
+        }
+        while !matches!(self.peek(), None | Some(TokenKind::Eof)) {
+            self.err_and_pop(message);
+        }
+        self.push_ignored();
+        drop(root);
+    }
+
     /// Check if the current token is `kind`.
     pub(crate) fn at(&mut self, token: TokenKind) -> bool {
         if let Some(t) = self.peek() {
@@ -236,6 +628,7 @@ impl<'input> Parser<'input> {
         let tokens = std::mem::take(&mut self.ignored);
         for token in tokens {
             let syntax_kind = match token.kind {
+                TokenKind::Comment | TokenKind::Whitespace if !self.options.trivia => continue,
                 TokenKind::Comment => SyntaxKind::COMMENT,
                 TokenKind::Whitespace => SyntaxKind::WHITESPACE,
                 TokenKind::Comma => SyntaxKind::COMMA,
@@ -272,7 +665,7 @@ impl<'input> Parser<'input> {
             return;
         };
         // this needs to be the computed location
-        let err = Error::limit(message, current.index());
+        let err = Error::limit(message, current.index()).with_kind(ErrorKind::RecursionLimit);
         self.push_err(err);
         self.accept_errors = false;
     }
@@ -350,12 +743,20 @@ impl<'input> Parser<'input> {
             let message = format!("expected {kind:?}, got {data}");
             Error::with_loc(message, data.to_string(), index)
         };
+        let err = err.with_kind(ErrorKind::ExpectedToken(kind));
 
         self.push_err(err);
+        if self.options.missing_nodes {
+            // Before any whitespace, right after the previous token
+            self.count_node();
+            let mut builder = self.builder.borrow_mut();
+            builder.start_node(SyntaxKind::MISSING);
+            builder.finish_node();
+        }
     }
 
     /// Push an error to parser's error Vec.
-    pub(crate) fn push_err(&mut self, err: crate::error::Error) {
+    pub(crate) fn push_err(&mut self, mut err: crate::error::Error) {
         // If the parser has reached a limit, self.accept_errors will
         // be set to false so that we do not push any more errors.
         //
@@ -363,8 +764,21 @@ impl<'input> Parser<'input> {
         // in an early termination which will cause the parser to
         // report "errors" which aren't really errors and thus
         // must be ignored.
-        if self.accept_errors {
+        if self.accept_errors && !self.recovering {
+            err.index += self.options.source_offset.byte;
             self.errors.push(err);
+            self.recovering = self.options.recovery != Recovery::Token;
+        }
+    }
+
+    /// Consume tokens into `ERROR` tokens, without reporting errors,
+    /// until `stop` returns true or the input ends.
+    pub(crate) fn skip_until(&mut self, mut stop: impl FnMut(&mut Self) -> bool) {
+        while !matches!(self.peek(), None | Some(TokenKind::Eof)) && !stop(self) {
+            self.push_ignored();
+            let token = self.pop();
+            self.push_token(SyntaxKind::ERROR, token);
+            self.skip_ignored();
         }
     }
 
@@ -372,10 +786,11 @@ impl<'input> Parser<'input> {
     fn next_token(&mut self) -> Option<Token<'input>> {
         for res in &mut self.lexer {
             match res {
-                Err(err) => {
+                Err(mut err) => {
                     if err.is_limit() {
                         self.accept_errors = false;
                     }
+                    err.index += self.options.source_offset.byte;
                     self.errors.push(err);
                 }
                 Ok(token) => {
@@ -399,7 +814,7 @@ impl<'input> Parser<'input> {
 
     /// Insert a token into the syntax tree.
     pub(crate) fn push_token(&mut self, kind: SyntaxKind, token: Token) {
-        self.builder.borrow_mut().token(kind, token.data())
+        self.builder.borrow_mut().token(kind, &token)
     }
 
     /// Start a node and make it current.
@@ -410,6 +825,7 @@ impl<'input> Parser<'input> {
     /// tokens.
     pub(crate) fn start_node(&mut self, kind: SyntaxKind) -> NodeGuard {
         self.push_ignored();
+        self.count_node();
 
         self.builder.borrow_mut().start_node(kind);
         let guard = NodeGuard::new(self.builder.clone());
@@ -418,6 +834,23 @@ impl<'input> Parser<'input> {
         guard
     }
 
+    /// Count a node towards the node limit.
+    ///
+    /// When the limit is reached, no more tokens are read so that parsing finishes early.
+    pub(crate) fn count_node(&mut self) {
+        if self.node_limit.check_and_increment() {
+            let index = self
+                .current_token
+                .as_ref()
+                .map_or(self.input.len(), |token| token.index());
+            let err = Error::limit("node limit reached, aborting parsing", index)
+                .with_kind(ErrorKind::NodeLimit);
+            self.push_err(err);
+            self.accept_errors = false;
+            self.lexer.abort();
+        }
+    }
+
     /// Set a checkpoint for *maybe* wrapping the following parse tree in some
     /// other node.
     pub(crate) fn checkpoint_node(&mut self) -> Checkpoint {
@@ -493,6 +926,9 @@ impl<'input> Parser<'input> {
 
     /// Peek the next Token and return it.
     pub(crate) fn peek_token(&mut self) -> Option<&Token<'input>> {
+        if self.recovering {
+            return None;
+        }
         if self.current_token.is_none() {
             self.current_token = self.next_token();
         }
@@ -560,11 +996,11 @@ impl Drop for NodeGuard {
 /// A rowan Checkpoint that can self-close the new wrapper node if required.
 pub(crate) struct Checkpoint {
     builder: Rc<RefCell<SyntaxTreeBuilder>>,
-    checkpoint: rowan::Checkpoint,
+    checkpoint: BuilderCheckpoint,
 }
 
 impl Checkpoint {
-    fn new(builder: Rc<RefCell<SyntaxTreeBuilder>>, checkpoint: rowan::Checkpoint) -> Self {
+    fn new(builder: Rc<RefCell<SyntaxTreeBuilder>>, checkpoint: BuilderCheckpoint) -> Self {
         Self {
             builder,
             checkpoint,
@@ -584,9 +1020,14 @@ impl Checkpoint {
 mod tests {
     use super::DEFAULT_RECURSION_LIMIT;
     use crate::cst;
+    use crate::cst::CstNode;
     use crate::Error;
+    use crate::ErrorKind;
     use crate::Parser;
+    use crate::Recovery;
+    use crate::SyntaxKind;
     use crate::SyntaxTree;
+    use crate::TextRange;
     use expect_test::expect;
 
     #[test]
@@ -635,7 +1076,8 @@ mod tests {
         let errors = cst.errors().collect::<Vec<_>>();
         assert_eq!(
             errors,
-            &[&Error::limit("parser recursion limit reached", 121),]
+            &[&Error::limit("parser recursion limit reached", 121)
+                .with_kind(ErrorKind::RecursionLimit),]
         );
     }
 
@@ -738,6 +1180,97 @@ mod tests {
         assert_eq!(cst.token_limit().high, 26);
     }
 
+    #[test]
+    fn recovery_strategies() {
+        let source = "type A { a: Int b: } type B { c: String }\nquery { x(: 1) y }\n{ z }";
+        let parse = |recovery| {
+            let tree = Parser::new(source).recovery(recovery).parse();
+            assert_eq!(tree.document().source_string(), source);
+            let errors: Vec<_> = tree.errors().map(|err| err.message().to_owned()).collect();
+            let definitions: Vec<_> = tree
+                .document()
+                .definitions()
+                .map(|def| def.source_string())
+                .collect();
+            (errors, definitions)
+        };
+
+        let (errors, definitions) = parse(Recovery::Token);
+        assert_eq!(
+            errors,
+            [
+                "expected a Type",
+                "expected an Argument",
+                "expected R_PAREN, got :",
+                "expected R_CURLY, got :",
+                "expected a StringValue, Name or OperationDefinition",
+                "expected a StringValue, Name or OperationDefinition",
+                "expected a StringValue, Name or OperationDefinition",
+                "expected definition",
+                "expected a StringValue, Name or OperationDefinition",
+            ]
+        );
+        assert_eq!(definitions.len(), 4);
+
+        // The tokens of the operation after `x(` are skipped up to `{`, which is not
+        // a definition keyword
+        let (errors, definitions) = parse(Recovery::Definition);
+        assert_eq!(errors, ["expected a Type", "expected an Argument"]);
+        assert_eq!(
+            definitions,
+            ["type A { a: Int b:", "type B { c: String }", "query { x("]
+        );
+
+        let (errors, definitions) = parse(Recovery::Strict);
+        assert_eq!(errors, ["expected a Type"]);
+        assert_eq!(definitions, ["type A { a: Int b:"]);
+    }
+
+    #[test]
+    fn missing_nodes() {
+        let source = "type A { a: [Int }";
+        let tree = Parser::new(source).missing_nodes(true).parse();
+        assert_eq!(tree.errors().len(), 1);
+        let missing: Vec<_> = tree
+            .document()
+            .syntax()
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::MISSING)
+            .map(|node| (node.parent().unwrap().kind(), node.text_range()))
+            .collect();
+        assert_eq!(
+            missing,
+            [(SyntaxKind::LIST_TYPE, TextRange::empty(16.into()))]
+        );
+
+        let tree = Parser::new(source).parse();
+        assert!(!tree
+            .document()
+            .syntax()
+            .descendants()
+            .any(|node| node.kind() == SyntaxKind::MISSING));
+    }
+
+    #[test]
+    fn node_limit() {
+        let source = "query ($v: [Int!]) { a { b } c: d(arg: $v) }";
+        let cst = Parser::new(source).parse();
+        assert_eq!(cst.errors().len(), 0);
+        let nodes = cst.document().syntax().descendants().count();
+        assert_eq!(cst.node_limit().high, nodes);
+
+        let cst = Parser::new(source).node_limit(nodes).parse();
+        assert_eq!(cst.errors().len(), 0);
+
+        let cst = Parser::new(source).node_limit(10).parse();
+        let errors: Vec<_> = cst.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), ErrorKind::NodeLimit);
+        assert_eq!(errors[0].message(), "node limit reached, aborting parsing");
+        assert!(cst.document().syntax().descendants().count() < nodes);
+        assert_eq!(cst.node_limit().high, 11);
+    }
+
     #[test]
     // single char v.s. multiple is less important than consistency between consecutive calls:
     #[allow(clippy::single_char_add_str)]
@@ -760,9 +1293,6 @@ mod tests {
             assert_eq!(ast.errors.len(), 0, "{:?}", ast.errors[0])
         });
         deep(DEFAULT_RECURSION_LIMIT * SMASH_THE_STACK_FACTOR, |ast| {
-            // Parsing nested structures without recursion on the call stack
-            // is possible but not as easy as it would require an explicit stack.
-
             // The recursion limit triggered and protected against stack overflow.
             assert_eq!(ast.errors.len(), 1);
             assert!(ast.errors[0].message.contains("recursion limit reached"));
@@ -906,6 +1436,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deep_selection_sets_and_values_with_high_recursion_limit() {
+        // Selection sets and values are parsed with an explicit stack
+        // instead of recursion, so their depth is only bounded by the configured limit.
+        const DEPTH: usize = DEFAULT_RECURSION_LIMIT * 40;
+        let check = |input: String| {
+            let ast = Parser::new(&input).recursion_limit(DEPTH + 1).parse();
+            assert_eq!(ast.errors, []);
+            let ast = Parser::new(&input).recursion_limit(DEPTH - 1).parse();
+            assert_eq!(ast.errors.len(), 1);
+            assert!(ast.errors[0].message.contains("recursion limit reached"));
+        };
+
+        // Nested selection set, alternating fields and inline fragments
+        let mut doc = String::new();
+        doc.push_str("query { ");
+        doc.push_str(&"f { ... { ".repeat(DEPTH / 2));
+        doc.push_str("f ");
+        doc.push_str(&"} } ".repeat(DEPTH / 2));
+        doc.push('}');
+        check(doc);
+
+        // Nested list value
+        let mut doc = String::new();
+        doc.push_str("query { f(arg: ");
+        doc.push_str(&"[".repeat(DEPTH));
+        doc.push('0');
+        doc.push_str(&"]".repeat(DEPTH));
+        doc.push_str(") }");
+        check(doc);
+
+        // Nested object value
+        let mut doc = String::new();
+        doc.push_str("query { f(arg: ");
+        doc.push_str(&"{f: ".repeat(DEPTH));
+        doc.push('0');
+        doc.push_str(&"}".repeat(DEPTH));
+        doc.push_str(") }");
+        check(doc);
+    }
+
     #[test]
     fn parse_field_set() {
         let source = r#"{ a }"#;
@@ -941,10 +1512,167 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parse_type() {
+        let cst = Parser::new(" [Int!]! ").parse_type();
+        assert_eq!(cst.errors().len(), 0);
+        assert!(matches!(cst.ty(), cst::Type::NonNullType(_)));
+        assert_eq!(cst.syntax_node().to_string(), " [Int!]! ");
+
+        let cst = Parser::new("Int String").parse_type();
+        assert_eq!(cst.errors().len(), 1);
+        assert!(matches!(cst.ty(), cst::Type::NamedType(_)));
+        assert_eq!(cst.syntax_node().to_string(), "Int String");
+    }
+
+    #[test]
+    fn parse_value() {
+        let cst = Parser::new(r#"{ a: [1, $b], c: "d" }"#).parse_value();
+        assert_eq!(cst.errors().len(), 0);
+        let Some(cst::Value::ObjectValue(object)) = cst.value() else {
+            panic!("expected an object value")
+        };
+        let fields: Vec<_> = object.object_fields().collect();
+        assert_eq!(fields.len(), 2);
+        assert!(matches!(fields[0].value(), Some(cst::Value::ListValue(_))));
+
+        let cst = Parser::new(" true ").parse_value();
+        assert_eq!(cst.errors().len(), 0);
+        assert_eq!(cst.syntax_node().to_string(), " true ");
+        assert!(matches!(cst.value(), Some(cst::Value::BooleanValue(_))));
+
+        let cst = Parser::new("1 2").parse_value();
+        assert_eq!(cst.errors().len(), 1);
+        assert!(matches!(cst.value(), Some(cst::Value::IntValue(_))));
+        assert_eq!(cst.syntax_node().to_string(), "1 2");
+
+        let cst = Parser::new("").parse_value();
+        assert_eq!(cst.errors().len(), 1);
+        assert!(cst.value().is_none());
+
+        let cst = Parser::new("@a").parse_value();
+        assert_eq!(cst.errors().len(), 3);
+        assert_eq!(cst.syntax_node().to_string(), "@a");
+        assert!(cst.value().is_none());
+    }
+
     #[test]
     fn no_infinite_loop() {
         let source = r#"{ ..."#;
         let parser = Parser::new(source).token_limit(3);
         let _cst = parser.parse();
     }
+
+    #[test]
+    fn error_kinds() {
+        let kinds = |source: &str| -> Vec<ErrorKind> {
+            Parser::new(source)
+                .recursion_limit(3)
+                .token_limit(30)
+                .parse()
+                .errors()
+                .map(|err| err.kind())
+                .collect()
+        };
+        assert_eq!(kinds("{ a % }")[0], ErrorKind::UnexpectedCharacter);
+        assert_eq!(
+            kinds(r#"{ a(b: "c\x") }"#)[0],
+            ErrorKind::InvalidEscapeSequence
+        );
+        assert_eq!(kinds("{ a(b: 01) }")[0], ErrorKind::InvalidNumber);
+        assert_eq!(kinds("{ a .. }")[0], ErrorKind::UnterminatedSpread);
+        assert_eq!(
+            kinds("{ a(b: 1 }"),
+            [ErrorKind::ExpectedToken(SyntaxKind::R_PAREN)]
+        );
+        assert_eq!(kinds("type Query { a: }")[0], ErrorKind::UnexpectedToken);
+        assert_eq!(
+            kinds("{ a { b { c { d } } } }")[0],
+            ErrorKind::RecursionLimit
+        );
+        assert_eq!(kinds(&"{ a }".repeat(10))[0], ErrorKind::TokenLimit);
+
+        let cst = Parser::new("{ a(b: \"c) }").parse();
+        let err = cst.errors().next().unwrap();
+        assert_eq!(err.kind(), ErrorKind::UnterminatedString);
+        assert_eq!(err.message(), "unterminated string value");
+    }
+
+    #[test]
+    fn source_offset() {
+        let host = "const a = 1;\nconst query = gql`\n  { a(b: %) }\n  type T {\n`;";
+        let start = host.find('`').unwrap() + 1;
+        let end = host.rfind('`').unwrap();
+        let snippet = &host[start..end];
+        let parse = |text| Parser::new(text).with_source_offset(1, 19, start);
+
+        let tree = parse(snippet).parse();
+        assert_eq!(tree.source_offset().byte, start);
+        let at: Vec<_> = tree.errors().map(|err| &host[err.index()..]).collect();
+        // A lexer error, then parser errors
+        assert_eq!(at.len(), 4);
+        assert!(at[0].starts_with("%)"));
+        assert!(at[1].starts_with(")"));
+        assert!(at[2].starts_with("`"));
+
+        // Reparsing keeps errors in host coordinates
+        let mut edited = snippet.to_string();
+        let edit = crate::TextEdit::replace(TextRange::at(10.into(), 1.into()), "1");
+        edit.apply(&mut edited);
+        let reparsed = parse(&edited).reparse(&tree, &edit);
+        let at: Vec<_> = reparsed.errors().map(|err| &host[err.index()..]).collect();
+        assert_eq!(at, [&host[end..], &host[end..]]);
+        assert_eq!(reparsed.source_offset(), tree.source_offset());
+    }
+
+    #[test]
+    fn without_trivia() {
+        let input = r#"
+            # The query type
+            type Query {
+                user(id: ID!, name: String): User @deprecated
+            }
+
+            query($id: ID!) { user(id: $id) { name } }
+        "#;
+        let with_trivia = Parser::new(input).parse();
+        let without_trivia = Parser::new(input).trivia(false).parse();
+        assert_eq!(without_trivia.errors().len(), 0);
+        let syntax = without_trivia.document().syntax().clone();
+        assert!(syntax.descendants_with_tokens().all(|element| !matches!(
+            element.kind(),
+            SyntaxKind::WHITESPACE | SyntaxKind::COMMENT
+        )));
+        assert_eq!(
+            syntax.to_string(),
+            "typeQuery{user(id:ID!,name:String):User@deprecated}query($id:ID!){user(id:$id){name}}"
+        );
+
+        // Same nodes, without the trivia tokens
+        let kinds = |tree: &SyntaxTree| {
+            tree.document()
+                .syntax()
+                .descendants()
+                .map(|node| node.kind())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kinds(&without_trivia), kinds(&with_trivia));
+
+        // Error indices are still offsets in the input
+        let input = "type Query {\n  a: Int\n  b:\n}";
+        let tree = Parser::new(input).trivia(false).parse();
+        let error = tree.errors().next().unwrap();
+        assert_eq!(&input[error.index()..], "}");
+
+        // Incremental reparsing needs offsets, so it reparses everything
+        let mut edited = input.to_string();
+        let edit = crate::TextEdit::replace(TextRange::at(26.into(), 0.into()), " Int");
+        edit.apply(&mut edited);
+        let reparsed = Parser::new(&edited).trivia(false).reparse(&tree, &edit);
+        assert_eq!(reparsed.errors().len(), 0);
+        assert_eq!(
+            reparsed.document().syntax().to_string(),
+            "typeQuery{a:Intb:Int}"
+        );
+    }
 }
@@ -0,0 +1,265 @@
+//! `Serialize` and `Deserialize` for [`SyntaxTree`], to cache parsed documents.
+//!
+//! The green tree is encoded as a flat sequence of events in preorder:
+//! the start of a node, a token with its text, or the end of the current node.
+//! Deserializing replays these events into a tree builder without running the parser,
+//! and without recursion.
+
+use super::language::GraphQLLanguage;
+use super::LimitTracker;
+use crate::cst::CstNode;
+use crate::Error;
+use crate::SourceOffset;
+use crate::SyntaxKind;
+use crate::SyntaxTree;
+use rowan::GreenNode;
+use rowan::GreenNodeBuilder;
+use rowan::GreenNodeData;
+use rowan::NodeOrToken;
+use serde::de;
+use serde::ser::SerializeSeq;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Syntax kinds are only meaningful for the version of apollo-parser that produced them
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize)]
+struct SerializeTree<'a> {
+    version: &'static str,
+    green: Green<'a>,
+    errors: &'a [Error],
+    recursion_limit: LimitTracker,
+    token_limit: LimitTracker,
+    node_limit: LimitTracker,
+    source_offset: SourceOffset,
+}
+
+#[derive(Deserialize)]
+struct DeserializeTree<'a> {
+    #[serde(borrow)]
+    version: Cow<'a, str>,
+    /// The root node and its depth
+    #[serde(deserialize_with = "deserialize_green")]
+    green: (GreenNode, usize),
+    errors: Vec<Error>,
+    recursion_limit: LimitTracker,
+    token_limit: LimitTracker,
+    node_limit: LimitTracker,
+    source_offset: SourceOffset,
+}
+
+struct Green<'a>(&'a GreenNodeData);
+
+#[derive(Serialize, Deserialize)]
+enum Event<'a> {
+    Start(SyntaxKind),
+    Token(SyntaxKind, #[serde(borrow)] Cow<'a, str>),
+    Finish,
+}
+
+impl<T: CstNode> Serialize for SyntaxTree<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeTree {
+            version: VERSION,
+            green: Green(&self.green),
+            errors: &self.errors,
+            recursion_limit: self.recursion_limit,
+            token_limit: self.token_limit,
+            node_limit: self.node_limit,
+            source_offset: self.source_offset,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: CstNode> Deserialize<'de> for SyntaxTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tree = DeserializeTree::deserialize(deserializer)?;
+        if tree.version != VERSION {
+            return Err(de::Error::custom(format_args!(
+                "syntax tree serialized by apollo-parser {}, expected {VERSION}",
+                tree.version
+            )));
+        }
+        let (green, depth) = tree.green;
+        Ok(SyntaxTree {
+            green,
+            errors: tree.errors,
+            recursion_limit: tree.recursion_limit,
+            token_limit: tree.token_limit,
+            node_limit: tree.node_limit,
+            source_offset: tree.source_offset,
+            depth,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl Serialize for Green<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&Event::Start(kind(self.0.kind())))?;
+        // Walk without recursion, as trees can be deep
+        let mut stack = vec![self.0.children()];
+        while let Some(children) = stack.last_mut() {
+            match children.next() {
+                Some(NodeOrToken::Node(node)) => {
+                    seq.serialize_element(&Event::Start(kind(node.kind())))?;
+                    stack.push(node.children());
+                }
+                Some(NodeOrToken::Token(token)) => seq.serialize_element(&Event::Token(
+                    kind(token.kind()),
+                    Cow::Borrowed(token.text()),
+                ))?,
+                None => {
+                    seq.serialize_element(&Event::Finish)?;
+                    stack.pop();
+                }
+            }
+        }
+        seq.end()
+    }
+}
+
+fn kind(raw: rowan::SyntaxKind) -> SyntaxKind {
+    <GraphQLLanguage as rowan::Language>::kind_from_raw(raw)
+}
+
+struct GreenVisitor;
+
+impl<'de> de::Visitor<'de> for GreenVisitor {
+    type Value = (GreenNode, usize);
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence of syntax tree events")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut builder = GreenNodeBuilder::new();
+        let mut depth = 0_usize;
+        let mut max_depth = 0;
+        let mut finished = false;
+        while let Some(event) = seq.next_element::<Event<'de>>()? {
+            if finished {
+                return Err(de::Error::custom("syntax tree events after the root node"));
+            }
+            match event {
+                Event::Start(kind) => {
+                    builder.start_node(rowan::SyntaxKind(kind.into()));
+                    depth += 1;
+                    max_depth = max_depth.max(depth);
+                }
+                Event::Token(_, _) | Event::Finish if depth == 0 => {
+                    return Err(de::Error::custom("syntax tree event outside of a node"));
+                }
+                Event::Token(kind, text) => builder.token(rowan::SyntaxKind(kind.into()), &text),
+                Event::Finish => {
+                    builder.finish_node();
+                    depth -= 1;
+                    finished = depth == 0;
+                }
+            }
+        }
+        if !finished {
+            return Err(de::Error::custom("unfinished syntax tree node"));
+        }
+        Ok((builder.finish(), max_depth))
+    }
+}
+
+// Not `impl Deserialize for GreenNode`, which is a foreign type
+fn deserialize_green<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(GreenNode, usize), D::Error> {
+    deserializer.deserialize_seq(GreenVisitor)
+}
+
+impl Serialize for SyntaxKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16((*self).into())
+    }
+}
+
+impl<'de> Deserialize<'de> for SyntaxKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = u16::deserialize(deserializer)?;
+        if raw >= SyntaxKind::__LAST as u16 {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(raw.into()),
+                &"a syntax kind",
+            ));
+        }
+        Ok(kind(rowan::SyntaxKind(raw)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cst;
+    use crate::cst::CstNode;
+    use crate::Parser;
+    use crate::SyntaxTree;
+
+    #[test]
+    fn round_trip() {
+        let input = r#"
+            # Comments and errors are kept
+            type Query { user(id: ID!): User @deprecated(reason: "é") }
+            query { user(id: ) { name } }
+        "#;
+        let tree = Parser::new(input)
+            .with_source_offset(1, 2, 3)
+            .token_limit(100)
+            .parse();
+        assert_eq!(tree.errors().len(), 1);
+        let json = serde_json::to_string(&tree).unwrap();
+        let deserialized: SyntaxTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, tree);
+        assert_eq!(deserialized.document().syntax().to_string(), input);
+        assert_eq!(deserialized.errors().next(), tree.errors().next());
+        assert_eq!(deserialized.token_limit(), tree.token_limit());
+        assert_eq!(deserialized.source_offset(), tree.source_offset());
+
+        // From a reader, which cannot borrow strings
+        let deserialized: SyntaxTree = serde_json::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(deserialized, tree);
+
+        let tree = Parser::new("{ a b { c } }").parse_selection_set();
+        let json = serde_json::to_string(&tree).unwrap();
+        let deserialized: SyntaxTree<cst::SelectionSet> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, tree);
+        assert_eq!(deserialized.field_set().selections().count(), 2);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let tree = Parser::new("{ a }").parse();
+        let json = serde_json::to_string(&tree).unwrap();
+        let error = |json: &str| {
+            serde_json::from_str::<SyntaxTree>(json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let version = format!(r#""version":"{}""#, env!("CARGO_PKG_VERSION"));
+        let old = json.replace(&version, r#""version":"0.1.0""#);
+        assert!(error(&old).starts_with(&format!(
+            "syntax tree serialized by apollo-parser 0.1.0, expected {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+
+        let start = json.find(r#"{"Start":"#).unwrap() + 9;
+        let end = start + json[start..].find('}').unwrap();
+        let invalid_kind = format!("{}65535{}", &json[..start], &json[end..]);
+        assert!(error(&invalid_kind).starts_with("invalid value: integer `65535`"));
+
+        let unfinished = json.replacen(r#","Finish""#, "", 1);
+        assert!(error(&unfinished).starts_with("unfinished syntax tree node"));
+    }
+}
@@ -1,12 +1,19 @@
+use super::events::Event;
 use super::LimitTracker;
 use crate::cst;
 use crate::cst::CstNode;
 use crate::Error;
+use crate::SourceOffset;
 use crate::SyntaxElement;
 use crate::SyntaxKind;
 use crate::SyntaxNode;
+use crate::SyntaxToken;
+use crate::Token;
 use rowan::GreenNode;
 use rowan::GreenNodeBuilder;
+use rowan::GreenNodeData;
+use rowan::TextSize;
+use rowan::TokenAtOffset;
 use std::fmt;
 use std::marker::PhantomData;
 use std::slice::Iter;
@@ -52,6 +59,7 @@ pub(crate) enum SyntaxTreeWrapper {
     Document(SyntaxTree<cst::Document>),
     FieldSet(SyntaxTree<cst::SelectionSet>),
     Type(SyntaxTree<cst::Type>),
+    Value(SyntaxTree<cst::Value>),
 }
 
 #[derive(PartialEq, Eq, Clone)]
@@ -60,7 +68,11 @@ pub struct SyntaxTree<T: CstNode = cst::Document> {
     pub(crate) errors: Vec<crate::Error>,
     pub(crate) recursion_limit: LimitTracker,
     pub(crate) token_limit: LimitTracker,
-    _phantom: PhantomData<fn() -> T>,
+    pub(crate) node_limit: LimitTracker,
+    pub(crate) source_offset: SourceOffset,
+    /// Number of nested levels of nodes, or an upper bound of it
+    pub(crate) depth: usize,
+    pub(crate) _phantom: PhantomData<fn() -> T>,
 }
 
 const _: () = {
@@ -86,6 +98,17 @@ impl<T: CstNode> SyntaxTree<T> {
         self.token_limit
     }
 
+    /// Get the syntax tree's node limit.
+    pub fn node_limit(&self) -> LimitTracker {
+        self.node_limit
+    }
+
+    /// Get where the parsed text starts in a host file,
+    /// as configured with [`Parser::with_source_offset`][crate::Parser::with_source_offset].
+    pub fn source_offset(&self) -> SourceOffset {
+        self.source_offset
+    }
+
     pub fn green(&self) -> GreenNode {
         self.green.clone()
     }
@@ -93,6 +116,113 @@ impl<T: CstNode> SyntaxTree<T> {
     pub(crate) fn syntax_node(&self) -> SyntaxNode {
         rowan::SyntaxNode::new_root(self.green.clone())
     }
+
+    /// Returns the token at byte `offset` in the source text,
+    /// or `None` if `offset` is past the end of the source text.
+    ///
+    /// At the boundary between two tokens, such as a cursor position in an editor,
+    /// this prefers the token that starts at `offset`,
+    /// unless it is whitespace, a comma, or a comment and the previous token is not.
+    pub fn token_at_offset(&self, offset: usize) -> Option<SyntaxToken> {
+        let root = self.syntax_node();
+        let offset = TextSize::try_from(offset).ok()?;
+        if offset > root.text_range().end() {
+            return None;
+        }
+        match root.token_at_offset(offset) {
+            TokenAtOffset::None => None,
+            TokenAtOffset::Single(token) => Some(token),
+            TokenAtOffset::Between(left, right) => {
+                if is_trivia(right.kind()) && !is_trivia(left.kind()) {
+                    Some(left)
+                } else {
+                    Some(right)
+                }
+            }
+        }
+    }
+
+    /// Returns the innermost node covering byte `offset` in the source text:
+    /// the parent of [`token_at_offset`][Self::token_at_offset].
+    ///
+    /// Use [`SyntaxNode::ancestors`][rowan::SyntaxNode::ancestors] to walk up from there,
+    /// or [`find_node_at_offset`][Self::find_node_at_offset] for a node of a given type.
+    pub fn node_at_offset(&self, offset: usize) -> Option<SyntaxNode> {
+        self.token_at_offset(offset)?.parent()
+    }
+
+    /// Returns the innermost node of type `N` covering byte `offset` in the source text.
+    ///
+    /// ```rust
+    /// use apollo_parser::cst;
+    /// use apollo_parser::cst::CstNode;
+    /// use apollo_parser::Parser;
+    ///
+    /// let source = "query { pet { name } }";
+    /// let tree = Parser::new(source).parse();
+    /// let offset = source.find("name").unwrap();
+    ///
+    /// let field: cst::Field = tree.find_node_at_offset(offset).unwrap();
+    /// assert_eq!(field.name().unwrap().text(), "name");
+    /// let operation: cst::OperationDefinition = tree.find_node_at_offset(offset).unwrap();
+    /// assert_eq!(operation.operation_type().unwrap().source_string(), "query");
+    /// ```
+    pub fn find_node_at_offset<N: CstNode>(&self, offset: usize) -> Option<N> {
+        self.ancestors_at_offset(offset).find_map(N::cast)
+    }
+
+    /// Returns the nodes covering byte `offset` in the source text,
+    /// from the innermost to the root.
+    pub fn ancestors_at_offset(&self, offset: usize) -> impl Iterator<Item = SyntaxNode> {
+        self.node_at_offset(offset)
+            .into_iter()
+            .flat_map(|node| node.ancestors())
+    }
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE | SyntaxKind::COMMA | SyntaxKind::COMMENT
+    )
+}
+
+/// Trees up to this deep are dropped recursively.
+/// This covers documents within the default recursion limit,
+/// and fits in the 2 MiB default stack of spawned threads.
+const RECURSIVE_DROP_MAX_DEPTH: usize = 2048;
+
+impl<T: CstNode> Drop for SyntaxTree<T> {
+    fn drop(&mut self) {
+        // Dropping a green node drops its children recursively, which could
+        // overflow the stack for very deeply nested documents. Keep every
+        // `KEEP_EVERY`-th level of the tree alive, ancestors first, so that
+        // dropping each of them in order only recurses that many levels.
+        const KEEP_EVERY: usize = 64;
+
+        if self.depth <= RECURSIVE_DROP_MAX_DEPTH {
+            return;
+        }
+        let mut kept = Vec::new();
+        let mut stack = vec![(&*self.green, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            for child in node.children() {
+                if let rowan::NodeOrToken::Node(child) = child {
+                    if (depth + 1) % KEEP_EVERY == 0 {
+                        kept.push(child.to_owned());
+                    }
+                    stack.push((child, depth + 1));
+                }
+            }
+        }
+        if kept.is_empty() {
+            return;
+        }
+
+        let placeholder = GreenNode::new(rowan::SyntaxKind(SyntaxKind::DOCUMENT as u16), []);
+        drop(std::mem::replace(&mut self.green, placeholder));
+        drop(kept);
+    }
 }
 
 impl SyntaxTree<cst::Document> {
@@ -133,6 +263,14 @@ impl SyntaxTree<cst::Type> {
     }
 }
 
+impl SyntaxTree<cst::Value> {
+    /// Return the root typed `Value` node,
+    /// or `None` if the input did not start with a value.
+    pub fn value(&self) -> Option<cst::Value> {
+        cst::Value::cast(self.syntax_node())
+    }
+}
+
 impl<T: CstNode> fmt::Debug for SyntaxTree<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fn print(f: &mut fmt::Formatter<'_>, indent: usize, element: SyntaxElement) -> fmt::Result {
@@ -181,40 +319,127 @@ impl<T: CstNode> fmt::Debug for SyntaxTree<T> {
 }
 
 #[derive(Debug)]
-pub(crate) struct SyntaxTreeBuilder {
-    builder: GreenNodeBuilder<'static>,
+pub(crate) enum SyntaxTreeBuilder {
+    Tree {
+        builder: GreenNodeBuilder<'static>,
+        /// For each unfinished node, the largest depth of its finished child nodes
+        open: Vec<usize>,
+        /// Depth of the finished root node
+        depth: usize,
+    },
+    /// Records events for [`Parser::parse_with_events`][crate::Parser::parse_with_events]
+    /// instead of building a tree
+    Events(Vec<Event>),
+}
+
+/// A position to maybe wrap the following nodes and tokens in a new node
+pub(crate) enum BuilderCheckpoint {
+    Tree(rowan::Checkpoint),
+    Events(usize),
 }
 
 impl SyntaxTreeBuilder {
     /// Create a new instance of `SyntaxBuilder`.
     pub(crate) fn new() -> Self {
-        Self {
+        Self::Tree {
             builder: GreenNodeBuilder::new(),
+            open: Vec::new(),
+            depth: 0,
         }
     }
 
-    pub(crate) fn checkpoint(&self) -> rowan::Checkpoint {
-        self.builder.checkpoint()
+    /// Create a builder that only records events.
+    pub(crate) fn events() -> Self {
+        Self::Events(Vec::new())
+    }
+
+    pub(crate) fn checkpoint(&self) -> BuilderCheckpoint {
+        match self {
+            Self::Tree { builder, .. } => BuilderCheckpoint::Tree(builder.checkpoint()),
+            Self::Events(events) => BuilderCheckpoint::Events(events.len()),
+        }
     }
 
     /// Start new node and make it current.
     pub(crate) fn start_node(&mut self, kind: SyntaxKind) {
-        self.builder.start_node(rowan::SyntaxKind(kind as u16));
+        match self {
+            Self::Tree { builder, open, .. } => {
+                builder.start_node(rowan::SyntaxKind(kind as u16));
+                open.push(0);
+            }
+            Self::Events(events) => events.push(Event::Start(kind)),
+        }
     }
 
     /// Finish current branch and restore previous branch as current.
     pub(crate) fn finish_node(&mut self) {
-        self.builder.finish_node();
+        match self {
+            Self::Tree {
+                builder,
+                open,
+                depth,
+            } => {
+                builder.finish_node();
+                let finished = open.pop().unwrap_or(0) + 1;
+                match open.last_mut() {
+                    Some(parent) => *parent = (*parent).max(finished),
+                    None => *depth = finished,
+                }
+            }
+            Self::Events(events) => events.push(Event::Finish),
+        }
     }
 
-    pub(crate) fn wrap_node(&mut self, checkpoint: rowan::Checkpoint, kind: SyntaxKind) {
-        self.builder
-            .start_node_at(checkpoint, rowan::SyntaxKind(kind as u16));
+    pub(crate) fn wrap_node(&mut self, checkpoint: BuilderCheckpoint, kind: SyntaxKind) {
+        match (self, checkpoint) {
+            (
+                Self::Tree {
+                    builder,
+                    open,
+                    depth,
+                },
+                BuilderCheckpoint::Tree(checkpoint),
+            ) => {
+                builder.start_node_at(checkpoint, rowan::SyntaxKind(kind as u16));
+                // The wrapped children are among those of the current node:
+                // their depth is at most the largest so far.
+                open.push(open.last().copied().unwrap_or(*depth));
+            }
+            (Self::Events(events), BuilderCheckpoint::Events(index)) => {
+                events.insert(index, Event::Start(kind))
+            }
+            _ => unreachable!("checkpoint from a different builder"),
+        }
     }
 
     /// Adds new token to the current branch.
-    pub(crate) fn token(&mut self, kind: SyntaxKind, text: &str) {
-        self.builder.token(rowan::SyntaxKind(kind as u16), text);
+    pub(crate) fn token(&mut self, kind: SyntaxKind, token: &Token<'_>) {
+        match self {
+            Self::Tree { builder, .. } => {
+                builder.token(rowan::SyntaxKind(kind as u16), token.data())
+            }
+            Self::Events(events) => events.push(Event::Token {
+                kind,
+                start: token.index(),
+                end: token.index() + token.data().len(),
+            }),
+        }
+    }
+
+    /// Returns the recorded events of a builder created with [`events`][Self::events]
+    pub(crate) fn finish_events(self) -> Vec<Event> {
+        match self {
+            Self::Events(events) => events,
+            Self::Tree { .. } => unreachable!("finish_events called on a tree builder"),
+        }
+    }
+
+    /// Returns the root node and its depth
+    fn finish_green(self) -> (GreenNode, usize) {
+        match self {
+            Self::Tree { builder, depth, .. } => (builder.finish(), depth),
+            Self::Events(_) => unreachable!("cannot build a tree from events"),
+        }
     }
 
     pub(crate) fn finish_document(
@@ -222,14 +447,20 @@ impl SyntaxTreeBuilder {
         errors: Vec<Error>,
         recursion_limit: LimitTracker,
         token_limit: LimitTracker,
+        node_limit: LimitTracker,
+        source_offset: SourceOffset,
     ) -> SyntaxTreeWrapper {
+        let (green, depth) = self.finish_green();
         SyntaxTreeWrapper::Document(SyntaxTree {
-            green: self.builder.finish(),
+            green,
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
             // TODO: keep the recursion and token limits in the builder rather than pass it in here?
             recursion_limit,
             token_limit,
+            node_limit,
+            source_offset,
+            depth,
             _phantom: PhantomData,
         })
     }
@@ -239,14 +470,20 @@ impl SyntaxTreeBuilder {
         errors: Vec<Error>,
         recursion_limit: LimitTracker,
         token_limit: LimitTracker,
+        node_limit: LimitTracker,
+        source_offset: SourceOffset,
     ) -> SyntaxTreeWrapper {
+        let (green, depth) = self.finish_green();
         SyntaxTreeWrapper::FieldSet(SyntaxTree {
-            green: self.builder.finish(),
+            green,
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
             // TODO: keep the recursion and token limits in the builder rather than pass it in here?
             recursion_limit,
             token_limit,
+            node_limit,
+            source_offset,
+            depth,
             _phantom: PhantomData,
         })
     }
@@ -256,23 +493,126 @@ impl SyntaxTreeBuilder {
         errors: Vec<Error>,
         recursion_limit: LimitTracker,
         token_limit: LimitTracker,
+        node_limit: LimitTracker,
+        source_offset: SourceOffset,
     ) -> SyntaxTreeWrapper {
+        let (green, depth) = self.finish_green();
         SyntaxTreeWrapper::Type(SyntaxTree {
-            green: self.builder.finish(),
+            green: unwrap_standalone(green),
+            // TODO: keep the errors in the builder rather than pass it in here?
+            errors,
+            // TODO: keep the recursion and token limits in the builder rather than pass it in here?
+            recursion_limit,
+            token_limit,
+            node_limit,
+            source_offset,
+            depth,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub(crate) fn finish_value(
+        self,
+        errors: Vec<Error>,
+        recursion_limit: LimitTracker,
+        token_limit: LimitTracker,
+        node_limit: LimitTracker,
+        source_offset: SourceOffset,
+    ) -> SyntaxTreeWrapper {
+        let (green, depth) = self.finish_green();
+        SyntaxTreeWrapper::Value(SyntaxTree {
+            green: unwrap_standalone(green),
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
             // TODO: keep the recursion and token limits in the builder rather than pass it in here?
             recursion_limit,
             token_limit,
+            node_limit,
+            source_offset,
+            depth,
             _phantom: PhantomData,
         })
     }
 }
 
+/// Returns the number of nested levels of nodes in `node`, including itself
+pub(crate) fn green_depth(node: &GreenNodeData) -> usize {
+    let mut max = 0;
+    let mut stack = vec![(node, 1)];
+    while let Some((node, depth)) = stack.pop() {
+        max = max.max(depth);
+        stack.extend(
+            node.children()
+                .filter_map(|child| child.into_node())
+                .map(|child| (child, depth + 1)),
+        );
+    }
+    max
+}
+
+/// Move the tokens around a single node of `root` into that node, and return it as the new root.
+///
+/// Used for standalone types and values, which are parsed in a temporary root node.
+/// If there is no such node, `root` is kept.
+fn unwrap_standalone(root: GreenNode) -> GreenNode {
+    let mut nodes = root.children().filter_map(|child| child.into_node());
+    let (Some(node), None) = (nodes.next(), nodes.next()) else {
+        return root;
+    };
+    let index = root
+        .children()
+        .position(|child| child.as_node().is_some())
+        .unwrap();
+    let leading = root.children().take(index).map(|child| child.to_owned());
+    let trailing = root
+        .children()
+        .skip(index + 1)
+        .map(|child| child.to_owned());
+    let len = node.children().len();
+    node.to_owned()
+        .splice_children(len..len, trailing)
+        .splice_children(0..0, leading)
+}
+
 #[cfg(test)]
 mod test {
+    use crate::cst;
+    use crate::cst::CstNode;
     use crate::cst::Definition;
     use crate::Parser;
+    use crate::SyntaxKind;
+
+    #[test]
+    fn depth_tracked_while_parsing() {
+        fn depth<T: CstNode>(tree: &super::SyntaxTree<T>) -> (usize, usize) {
+            (tree.depth, super::green_depth(&tree.green))
+        }
+        let tree = Parser::new("query { a { b(arg: [{ c: 1 }]) } }").parse();
+        let (tracked, actual) = depth(&tree);
+        assert_eq!(tracked, actual);
+        // Wrapping a type in a non-null node makes an upper bound
+        let tree = Parser::new("type T { f(a: [[Int!]!]!): [String!] }").parse();
+        let (tracked, actual) = depth(&tree);
+        assert!(tracked >= actual);
+        assert!(tracked <= actual + 3);
+        let tree = Parser::new("[[Int!]!]!").parse_type();
+        let (tracked, actual) = depth(&tree);
+        assert!(tracked >= actual);
+
+        // Edits keep an upper bound
+        let tree = Parser::new("{ a }").parse();
+        let snippet = Parser::new("{ b { c { d } } }").parse_selection_set();
+        let selection = snippet.field_set().selections().next().unwrap();
+        let Some(cst::Definition::OperationDefinition(operation)) =
+            tree.document().definitions().next()
+        else {
+            panic!()
+        };
+        let edited = tree.push_selection(&operation.selection_set().unwrap(), selection.syntax());
+        let (tracked, actual) = depth(&edited);
+        assert!(tracked >= actual);
+        assert!(actual > depth(&tree).1);
+    }
 
     #[test]
     fn directive_name() {
@@ -311,4 +651,53 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn node_at_offset() {
+        let source = "query Q($id: ID) { pet(id: $id) { name, owner { name } } }";
+        let tree = Parser::new(source).parse();
+        let offset = |needle: &str| source.find(needle).unwrap();
+
+        let token = tree.token_at_offset(offset("owner")).unwrap();
+        assert_eq!(token.text(), "owner");
+        // At the end of a name, prefer the name over the following comma
+        let token = tree.token_at_offset(offset(", owner")).unwrap();
+        assert_eq!(token.text(), "name");
+        // Between two significant tokens, prefer the one that starts there
+        let token = tree.token_at_offset(offset("$id)") + 3).unwrap();
+        assert_eq!(token.kind(), SyntaxKind::R_PAREN);
+
+        let node = tree.node_at_offset(offset("$id)") + 1).unwrap();
+        assert_eq!(node.kind(), SyntaxKind::NAME);
+        let kinds: Vec<_> = tree
+            .ancestors_at_offset(offset("$id)") + 1)
+            .map(|node| node.kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                SyntaxKind::NAME,
+                SyntaxKind::VARIABLE,
+                SyntaxKind::ARGUMENT,
+                SyntaxKind::ARGUMENTS,
+                SyntaxKind::FIELD,
+                SyntaxKind::SELECTION_SET,
+                SyntaxKind::OPERATION_DEFINITION,
+                SyntaxKind::DOCUMENT,
+            ]
+        );
+
+        let field: cst::Field = tree
+            .find_node_at_offset(source.rfind("name").unwrap())
+            .unwrap();
+        assert_eq!(field.source_string(), "name");
+        let field: cst::Field = tree.find_node_at_offset(offset("owner")).unwrap();
+        assert_eq!(field.source_string(), "owner { name }");
+        let variable: Option<cst::VariableDefinition> = tree.find_node_at_offset(offset("pet"));
+        assert!(variable.is_none());
+
+        assert_eq!(tree.token_at_offset(source.len()).unwrap().text(), "}");
+        assert!(tree.token_at_offset(source.len() + 1).is_none());
+        assert!(tree.node_at_offset(source.len() + 1).is_none());
+    }
 }
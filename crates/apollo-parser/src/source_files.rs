@@ -0,0 +1,205 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Several named source files concatenated into one text,
+/// to parse them into a single [`SyntaxTree`](crate::SyntaxTree)
+/// and map offsets in that tree back to each file.
+///
+/// This lets tools work on a schema split across many `.graphql` files,
+/// such as a federated schema, as one document: definitions in one file
+/// can be found from references in another with the usual tree navigation.
+///
+/// Files are separated by a line break, so that a comment at the end of a file
+/// does not extend into the next one. A file that ends in the middle of a definition
+/// is still parsed as continuing into the next file.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::cst;
+/// use apollo_parser::cst::CstNode;
+/// use apollo_parser::Parser;
+/// use apollo_parser::SourceFiles;
+///
+/// let files = SourceFiles::from_iter([
+///     ("query.graphql", "type Query { me: User }"),
+///     ("user.graphql", "type User { name: String }"),
+/// ]);
+/// let tree = Parser::new(files.text()).parse();
+/// assert_eq!(tree.errors().len(), 0);
+///
+/// // Go to the definition of `User` from the `Query` type
+/// let user = tree
+///     .document()
+///     .definitions()
+///     .find_map(|def| match def {
+///         cst::Definition::ObjectTypeDefinition(def)
+///             if def.name().unwrap().text() == "User" => Some(def),
+///         _ => None,
+///     })
+///     .unwrap();
+/// let (file, offset) = files
+///     .to_file_offset(user.syntax().text_range().start().into())
+///     .unwrap();
+/// assert_eq!(file.name(), "user.graphql");
+/// assert_eq!(offset, 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceFiles {
+    text: String,
+    files: Vec<SourceFile>,
+}
+
+/// One file of [`SourceFiles`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    name: String,
+    offset: usize,
+    len: usize,
+}
+
+impl SourceFiles {
+    /// Create an empty set of files
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a file
+    pub fn add(&mut self, name: impl Into<String>, text: &str) -> &mut Self {
+        if !self.files.is_empty() {
+            self.text.push('\n');
+        }
+        self.files.push(SourceFile {
+            name: name.into(),
+            offset: self.text.len(),
+            len: text.len(),
+        });
+        self.text.push_str(text);
+        self
+    }
+
+    /// Returns the concatenated text of all files, to be parsed
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the files in the order they were added
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// Returns the file with the given name
+    pub fn get(&self, name: &str) -> Option<&SourceFile> {
+        self.files.iter().find(|file| file.name == name)
+    }
+
+    /// Returns the text of `file`
+    pub fn file_text(&self, file: &SourceFile) -> &str {
+        &self.text[file.range()]
+    }
+
+    /// Returns the file that contains `offset` in the concatenated text.
+    ///
+    /// The end offset of a file is part of that file, like a cursor at the end of the text.
+    /// Returns `None` if `offset` is past the end of the text.
+    pub fn file_at_offset(&self, offset: usize) -> Option<&SourceFile> {
+        let index = self
+            .files
+            .partition_point(|file| file.offset <= offset)
+            .checked_sub(1)?;
+        let file = &self.files[index];
+        (offset <= file.offset + file.len).then_some(file)
+    }
+
+    /// Convert an offset in the concatenated text, such as from a syntax node
+    /// or [`Error::index`](crate::Error::index), to a file and an offset in that file
+    pub fn to_file_offset(&self, offset: usize) -> Option<(&SourceFile, usize)> {
+        let file = self.file_at_offset(offset)?;
+        Some((file, offset - file.offset))
+    }
+
+    /// Convert an offset in the file named `name` to an offset in the concatenated text
+    pub fn to_offset(&self, name: &str, file_offset: usize) -> Option<usize> {
+        let file = self.get(name)?;
+        (file_offset <= file.len).then_some(file.offset + file_offset)
+    }
+}
+
+impl SourceFile {
+    /// Returns the name of this file, as given to [`SourceFiles::add`]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the offset of the start of this file in the concatenated text
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the byte range of this file in the concatenated text
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+}
+
+impl<N: Into<String>, T: AsRef<str>> FromIterator<(N, T)> for SourceFiles {
+    fn from_iter<I: IntoIterator<Item = (N, T)>>(iter: I) -> Self {
+        let mut files = Self::new();
+        files.extend(iter);
+        files
+    }
+}
+
+impl<N: Into<String>, T: AsRef<str>> Extend<(N, T)> for SourceFiles {
+    fn extend<I: IntoIterator<Item = (N, T)>>(&mut self, iter: I) {
+        for (name, text) in iter {
+            self.add(name, text.as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    #[test]
+    fn offsets() {
+        let files = SourceFiles::from_iter([
+            ("a.graphql", "type A { b: B } # no line break"),
+            ("empty.graphql", ""),
+            ("b.graphql", "type B {\n  f(: Int\n}"),
+        ]);
+        assert_eq!(
+            files.text(),
+            "type A { b: B } # no line break\n\ntype B {\n  f(: Int\n}"
+        );
+        let names: Vec<_> = files.files().iter().map(|file| file.name()).collect();
+        assert_eq!(names, ["a.graphql", "empty.graphql", "b.graphql"]);
+        let b = files.get("b.graphql").unwrap();
+        assert_eq!(b.range(), 33..53);
+        assert_eq!(files.file_text(b), "type B {\n  f(: Int\n}");
+
+        assert_eq!(files.to_file_offset(0).unwrap().0.name(), "a.graphql");
+        assert_eq!(files.to_file_offset(31).unwrap().0.name(), "a.graphql");
+        assert_eq!(files.to_file_offset(32).unwrap().0.name(), "empty.graphql");
+        assert_eq!(files.to_file_offset(36).unwrap(), (b, 3));
+        assert_eq!(files.to_file_offset(53).unwrap(), (b, 20));
+        assert_eq!(files.to_file_offset(54), None);
+        assert_eq!(files.to_offset("b.graphql", 3), Some(36));
+        assert_eq!(files.to_offset("b.graphql", 21), None);
+        assert_eq!(files.to_offset("c.graphql", 0), None);
+
+        // The comment in the first file ends at the separator
+        let tree = Parser::new(files.text()).parse();
+        assert_eq!(tree.document().definitions().count(), 2);
+        let errors: Vec<_> = tree
+            .errors()
+            .map(|err| {
+                let (file, offset) = files.to_file_offset(err.index()).unwrap();
+                (file.name(), offset)
+            })
+            .collect();
+        assert_eq!(errors, [("b.graphql", 13), ("b.graphql", 13)]);
+    }
+}
@@ -52,6 +52,55 @@ fn parser_tests() {
     });
 }
 
+#[test]
+fn invariants_hold_for_test_data() {
+    let paths = ["lexer/ok", "lexer/err", "parser/ok", "parser/err"];
+    for (path, text) in collect_graphql_files(&test_data_dir(), &paths) {
+        let check = crate::check_invariants(&text);
+        assert!(
+            check.violations.is_empty(),
+            "Invariants do not hold for {path:?}: {:#?}",
+            check.violations
+        );
+    }
+}
+
+/// Lists the corpus embedded by the `conformance` feature,
+/// so that the suite includes files added to `test_data`.
+#[test]
+fn conformance_corpus() {
+    let mut generated = String::from(
+        "// Generated by `conformance_corpus` in src/tests.rs, do not edit.\n\
+         // To update: env UPDATE_EXPECT=1 cargo test --package apollo-parser\n\
+         \n\
+         use super::Case;\n\
+         use super::Expected::Accepted;\n\
+         use super::Expected::Rejected;\n\
+         \n\
+         macro_rules! case {\n    \
+             ($name: literal, $expected: expr) => {\n        \
+                 Case {\n            \
+                     name: $name,\n            \
+                     input: include_str!(concat!(\"../../test_data/\", $name, \".graphql\")),\n            \
+                     expected: $expected,\n        \
+                 }\n    \
+             };\n\
+         }\n",
+    );
+    for (constant, prefix) in [("LEXER", "lexer"), ("PARSER", "parser")] {
+        writeln!(generated, "\npub(super) const {constant}: &[Case] = &[").unwrap();
+        for (dir, expected) in [("ok", "Accepted"), ("err", "Rejected")] {
+            let dir = format!("{prefix}/{dir}");
+            for path in graphql_files_in_dir(&test_data_dir().join(&dir)) {
+                let stem = path.file_stem().unwrap().to_str().unwrap();
+                writeln!(generated, "    case!(\"{dir}/{stem}\", {expected}),").unwrap();
+            }
+        }
+        writeln!(generated, "];").unwrap();
+    }
+    expect_file!["conformance/corpus.rs"].assert_eq(&generated);
+}
+
 fn assert_errors_are_present(errors: &[Error], path: &Path) {
     assert!(
         !errors.is_empty(),
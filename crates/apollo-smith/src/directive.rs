@@ -29,6 +29,7 @@ impl From<DirectiveDef> for ast::Definition {
     fn from(dir_def: DirectiveDef) -> Self {
         ast::DirectiveDefinition {
             description: dir_def.description.map(Into::into),
+            leading_comments: Vec::new(),
             name: dir_def.name.into(),
             arguments: dir_def
                 .arguments_definition
@@ -147,6 +147,8 @@ impl TryFrom<apollo_parser::cst::Document> for Document {
                 apollo_parser::cst::Definition::FragmentDefinition(fragment_def) => {
                     fragment_defs.push(FragmentDef::try_from(fragment_def)?)
                 }
+                // Only parsed with a `SyntaxExtension`, which is not configured here
+                apollo_parser::cst::Definition::CustomDefinition(_) => {}
             }
         }
 
@@ -29,6 +29,7 @@ impl From<EnumTypeDef> for ast::Definition {
     fn from(x: EnumTypeDef) -> Self {
         if x.extend {
             ast::EnumTypeExtension {
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
                 values: x
@@ -41,6 +42,7 @@ impl From<EnumTypeDef> for ast::Definition {
         } else {
             ast::EnumTypeDefinition {
                 description: x.description.map(Into::into),
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
                 values: x
@@ -124,6 +126,7 @@ impl From<EnumValueDefinition> for ast::EnumValueDefinition {
     fn from(x: EnumValueDefinition) -> Self {
         Self {
             description: x.description.map(Into::into),
+            leading_comments: Vec::new(),
             value: x.value.into(),
             directives: Directive::to_ast(x.directives),
         }
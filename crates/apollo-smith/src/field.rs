@@ -32,6 +32,7 @@ impl From<FieldDef> for ast::FieldDefinition {
     fn from(x: FieldDef) -> Self {
         Self {
             description: x.description.map(Into::into),
+            leading_comments: Vec::new(),
             name: x.name.into(),
             directives: Directive::to_ast(x.directives),
             arguments: x.arguments_definition.map(Into::into).unwrap_or_default(),
@@ -81,13 +82,13 @@ pub struct Field {
 
 impl From<Field> for ast::Field {
     fn from(x: Field) -> Self {
-        Self {
-            alias: x.alias.map(Into::into),
-            name: x.name.into(),
-            directives: Directive::to_ast(x.directives),
-            arguments: x.args.into_iter().map(|x| Node::new(x.into())).collect(),
-            selection_set: x.selection_set.map(Into::into).unwrap_or_default(),
-        }
+        // Not a struct literal: fields of `ast::Field` depend on apollo-compiler features
+        let mut field = Self::new(x.name.into());
+        field.alias = x.alias.map(Into::into);
+        field.directives = Directive::to_ast(x.directives);
+        field.arguments = x.args.into_iter().map(|x| Node::new(x.into())).collect();
+        field.selection_set = x.selection_set.map(Into::into).unwrap_or_default();
+        field
     }
 }
 
@@ -34,6 +34,7 @@ impl From<InputObjectTypeDef> for ast::Definition {
     fn from(x: InputObjectTypeDef) -> Self {
         if x.extend {
             ast::InputObjectTypeExtension {
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
                 fields: x.fields.into_iter().map(|x| Node::new(x.into())).collect(),
@@ -42,6 +43,7 @@ impl From<InputObjectTypeDef> for ast::Definition {
         } else {
             ast::InputObjectTypeDefinition {
                 description: x.description.map(Into::into),
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
                 fields: x.fields.into_iter().map(|x| Node::new(x.into())).collect(),
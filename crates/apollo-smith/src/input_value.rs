@@ -132,6 +132,7 @@ impl From<InputValueDef> for ast::InputValueDefinition {
     fn from(x: InputValueDef) -> Self {
         Self {
             description: x.description.map(Into::into),
+            leading_comments: Vec::new(),
             name: x.name.into(),
             ty: Node::new(x.ty.into()),
             default_value: x.default_value.map(|x| Node::new(x.into())),
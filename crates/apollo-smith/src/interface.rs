@@ -35,6 +35,7 @@ impl From<InterfaceTypeDef> for ast::Definition {
     fn from(x: InterfaceTypeDef) -> Self {
         if x.extend {
             ast::InterfaceTypeExtension {
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 implements_interfaces: x.interfaces.into_iter().map(Into::into).collect(),
                 directives: Directive::to_ast(x.directives),
@@ -48,6 +49,7 @@ impl From<InterfaceTypeDef> for ast::Definition {
         } else {
             ast::InterfaceTypeDefinition {
                 description: x.description.map(Into::into),
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 implements_interfaces: x.interfaces.into_iter().map(Into::into).collect(),
                 directives: Directive::to_ast(x.directives),
@@ -13,6 +13,7 @@ pub(crate) mod interface;
 pub(crate) mod name;
 pub(crate) mod object;
 pub(crate) mod operation;
+pub mod sample;
 pub(crate) mod scalar;
 pub(crate) mod schema;
 pub(crate) mod selection_set;
@@ -34,6 +34,7 @@ impl From<ObjectTypeDef> for ast::Definition {
     fn from(x: ObjectTypeDef) -> Self {
         if x.extend {
             ast::ObjectTypeExtension {
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 implements_interfaces: x
                     .implements_interfaces
@@ -51,6 +52,7 @@ impl From<ObjectTypeDef> for ast::Definition {
         } else {
             ast::ObjectTypeDefinition {
                 description: x.description.map(Into::into),
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 implements_interfaces: x
                     .implements_interfaces
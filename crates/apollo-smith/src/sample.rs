@@ -0,0 +1,236 @@
+use crate::Document;
+use crate::DocumentBuilder;
+use crate::OperationDef;
+use arbitrary::Result;
+use arbitrary::Unstructured;
+
+/// Input bytes for [`DocumentBuilder`], for property tests outside of a fuzzer.
+///
+/// A `Sample` can be created deterministically from a seed,
+/// so that a failing test case can be reproduced from a printed seed,
+/// and [shrunk][Self::shrink] to a smaller sample that generates a smaller document.
+/// Shrinking bytes works because `DocumentBuilder` makes the smallest choices
+/// (fewest definitions, shortest names) when its input is short or zeroed.
+///
+/// ```rust
+/// use apollo_smith::sample::minimize;
+/// use apollo_smith::sample::Sample;
+///
+/// let has_mutation = |sample: &Sample| {
+///     let document = String::from(sample.document().unwrap());
+///     document.contains("mutation")
+/// };
+/// for seed in 0..100 {
+///     let sample = Sample::from_seed(seed, 1024);
+///     if has_mutation(&sample) {
+///         let minimal = minimize(sample, has_mutation);
+///         assert!(minimal.bytes().len() <= 1024);
+///         break;
+///     }
+/// }
+/// ```
+///
+/// With the `proptest` feature, `Sample` implements
+/// [`proptest::arbitrary::Arbitrary`], so that `any::<Sample>()` generates samples
+/// and proptest shrinks a failing sample, and therefore its document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sample {
+    bytes: Vec<u8>,
+}
+
+impl Sample {
+    /// Create a sample of `len` pseudo-random bytes.
+    /// The same seed and length always create the same sample.
+    pub fn from_seed(seed: u64, len: usize) -> Self {
+        // SplitMix64: https://prng.di.unimi.it/splitmix64.c
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        };
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            let chunk = next().to_le_bytes();
+            let remaining = len - bytes.len();
+            bytes.extend_from_slice(&chunk[..remaining.min(chunk.len())]);
+        }
+        Self { bytes }
+    }
+
+    /// Create a sample from existing bytes, such as from a proptest strategy
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// The input bytes, for example to print a failing sample
+    /// and reproduce it later with [`Sample::from_bytes`]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Generate a document with type system definitions and operations
+    pub fn document(&self) -> Result<Document> {
+        let mut u = Unstructured::new(&self.bytes);
+        Ok(DocumentBuilder::new(&mut u)?.finish())
+    }
+
+    /// Generate an operation that selects from the types of an existing `schema` document,
+    /// for example one from [`Sample::document`] or converted from `apollo-parser`.
+    ///
+    /// Returns `None` if the schema document has no schema definition.
+    pub fn operation(&self, schema: Document) -> Result<Option<OperationDef>> {
+        let mut u = Unstructured::new(&self.bytes);
+        DocumentBuilder::with_document(&mut u, schema)?.operation_definition()
+    }
+
+    /// Returns smaller samples derived from this one, most aggressive first.
+    ///
+    /// Candidates are either shorter, or have the same length and some byte decreased,
+    /// so repeatedly shrinking always terminates.
+    pub fn shrink(&self) -> impl Iterator<Item = Sample> + '_ {
+        let len = self.bytes.len();
+        let chunk_sizes =
+            std::iter::successors(Some(len / 2), |&size| (size > 1).then_some(size / 2))
+                .filter(|&size| size > 0);
+        let removals = chunk_sizes.flat_map(move |size| {
+            (0..len / size).map(move |index| {
+                let start = index * size;
+                let mut bytes = self.bytes.clone();
+                bytes.drain(start..start + size);
+                Sample { bytes }
+            })
+        });
+        let truncate_one = (len > 0).then(|| Sample {
+            bytes: self.bytes[..len - 1].to_vec(),
+        });
+        let reductions = self
+            .bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, &byte)| byte != 0)
+            .flat_map(move |(index, &byte)| {
+                let mut values = vec![0];
+                values.extend([byte / 2, byte - 1].into_iter().filter(|&v| v != 0));
+                values.dedup();
+                values.into_iter().map(move |value| {
+                    let mut bytes = self.bytes.clone();
+                    bytes[index] = value;
+                    Sample { bytes }
+                })
+            });
+        removals.chain(truncate_one).chain(reductions)
+    }
+}
+
+/// Generates samples of up to 1024 bytes with `any::<Sample>()`,
+/// or with a given length range with `any_with::<Sample>(range.into())`.
+///
+/// Proptest shrinks a failing sample by removing bytes and decreasing them towards zero,
+/// like [`Sample::shrink`].
+///
+/// ```rust
+/// use apollo_smith::sample::Sample;
+/// use proptest::prelude::*;
+///
+/// proptest!(|(sample in any::<Sample>())| {
+///     let document = String::from(sample.document().unwrap());
+///     let tree = apollo_parser::Parser::new(&document).parse();
+///     prop_assert_eq!(tree.errors().len(), 0);
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Sample {
+    type Parameters = proptest::collection::SizeRange;
+    type Strategy = proptest::strategy::Map<
+        proptest::collection::VecStrategy<proptest::num::u8::Any>,
+        fn(Vec<u8>) -> Sample,
+    >;
+
+    fn arbitrary() -> Self::Strategy {
+        Self::arbitrary_with((0..=1024).into())
+    }
+
+    fn arbitrary_with(len: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::collection::vec(proptest::num::u8::ANY, len).prop_map(Sample::from_bytes)
+    }
+}
+
+/// Shrink a sample for which `fails` returns true as much as possible
+/// while `fails` still returns true, and return the smallest sample found.
+///
+/// This is a greedy search: it moves to the first [`Sample::shrink`] candidate that still fails
+/// until none of the candidates do.
+pub fn minimize(mut sample: Sample, mut fails: impl FnMut(&Sample) -> bool) -> Sample {
+    loop {
+        let smaller = sample.shrink().find(|candidate| fails(candidate));
+        match smaller {
+            Some(smaller) => sample = smaller,
+            None => return sample,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let a = Sample::from_seed(42, 100);
+        let b = Sample::from_seed(42, 100);
+        assert_eq!(a, b);
+        assert_eq!(a.bytes().len(), 100);
+        assert_ne!(a, Sample::from_seed(43, 100));
+        assert_eq!(Sample::from_seed(42, 10).bytes(), &a.bytes()[..10]);
+        assert_eq!(
+            String::from(a.document().unwrap()),
+            String::from(b.document().unwrap())
+        );
+    }
+
+    #[test]
+    fn shrink_candidates_are_smaller() {
+        let sample = Sample::from_seed(7, 20);
+        let total = |s: &Sample| s.bytes().iter().map(|&b| b as usize).sum::<usize>();
+        for candidate in sample.shrink() {
+            assert!(
+                candidate.bytes().len() < sample.bytes().len()
+                    || (candidate.bytes().len() == sample.bytes().len()
+                        && total(&candidate) < total(&sample))
+            );
+        }
+        assert_eq!(Sample::from_bytes(Vec::new()).shrink().count(), 0);
+    }
+
+    #[test]
+    fn minimize_finds_small_document() {
+        let has_three_scalars = |sample: &Sample| {
+            let document = String::from(sample.document().unwrap());
+            document
+                .lines()
+                .filter(|line| line.starts_with("scalar"))
+                .count()
+                >= 3
+        };
+        let sample = (0..100)
+            .map(|seed| Sample::from_seed(seed, 64))
+            .find(has_three_scalars)
+            .unwrap();
+        let minimal = minimize(sample.clone(), has_three_scalars);
+        assert!(has_three_scalars(&minimal));
+        // The first byte chooses the number of scalar definitions
+        assert_eq!(minimal.bytes(), [2]);
+    }
+
+    #[test]
+    fn operation_for_existing_schema() {
+        let schema = Sample::from_seed(1, 256).document().unwrap();
+        let operation = Sample::from_seed(2, 64).operation(schema).unwrap().unwrap();
+        let operation = String::from(operation);
+        assert!(operation.contains('{'), "{operation}");
+    }
+}
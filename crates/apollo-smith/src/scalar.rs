@@ -26,6 +26,7 @@ impl From<ScalarTypeDef> for ast::Definition {
     fn from(x: ScalarTypeDef) -> Self {
         if x.extend {
             ast::ScalarTypeExtension {
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
             }
@@ -33,6 +34,7 @@ impl From<ScalarTypeDef> for ast::Definition {
         } else {
             ast::ScalarTypeDefinition {
                 description: x.description.map(Into::into),
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
             }
@@ -28,6 +28,7 @@ impl From<UnionTypeDef> for ast::Definition {
     fn from(x: UnionTypeDef) -> Self {
         if x.extend {
             ast::UnionTypeExtension {
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
                 members: x.members.into_iter().map(Into::into).collect(),
@@ -36,6 +37,7 @@ impl From<UnionTypeDef> for ast::Definition {
         } else {
             ast::UnionTypeDefinition {
                 description: x.description.map(Into::into),
+                leading_comments: Vec::new(),
                 name: x.name.into(),
                 directives: Directive::to_ast(x.directives),
                 members: x.members.into_iter().map(Into::into).collect(),
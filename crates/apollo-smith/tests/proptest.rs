@@ -0,0 +1,47 @@
+#![cfg(feature = "proptest")]
+
+use apollo_smith::sample::Sample;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use proptest::test_runner::TestError;
+use proptest::test_runner::TestRunner;
+
+proptest! {
+    #[test]
+    fn generated_documents_parse(sample in any::<Sample>()) {
+        let document = String::from(sample.document().unwrap());
+        let tree = apollo_parser::Parser::new(&document).parse();
+        prop_assert_eq!(tree.errors().len(), 0, "{}", document);
+    }
+
+    #[test]
+    fn respects_length_range(sample in any_with::<Sample>((10..20).into())) {
+        prop_assert!((10..20).contains(&sample.bytes().len()));
+    }
+}
+
+#[test]
+fn shrinks_failing_sample() {
+    let has_three_scalars = |sample: &Sample| {
+        let document = String::from(sample.document().unwrap());
+        document
+            .lines()
+            .filter(|line| line.starts_with("scalar"))
+            .count()
+            >= 3
+    };
+    let mut runner = TestRunner::deterministic();
+    let result = runner.run(&any::<Sample>(), |sample| {
+        if has_three_scalars(&sample) {
+            Err(TestCaseError::fail("three scalars"))
+        } else {
+            Ok(())
+        }
+    });
+    let Err(TestError::Fail(_, minimal)) = result else {
+        panic!("expected a failing sample, got {result:?}")
+    };
+    assert!(has_three_scalars(&minimal));
+    // The first byte chooses the number of scalar definitions
+    assert_eq!(minimal.bytes(), [2]);
+}
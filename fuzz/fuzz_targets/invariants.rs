@@ -0,0 +1,6 @@
+#![no_main]
+use apollo_rs_fuzz::corpus::check_parser;
+use libfuzzer_sys::fuzz_target;
+
+// Crash inputs can be added to `fuzz/regressions/parser` to be replayed by `cargo test`
+fuzz_target!(|data: &[u8]| check_parser(data));
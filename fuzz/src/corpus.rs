@@ -0,0 +1,157 @@
+//! Replaying fuzzer inputs as tests, and distilling a corpus.
+//!
+//! Inputs that made a fuzz target crash can be saved in a directory
+//! and replayed in a regular `#[test]`, for example in CI:
+//!
+//! ```no_run
+//! #[test]
+//! fn parser_regressions() {
+//!     apollo_rs_fuzz::corpus::assert_replay_dir("regressions/parser", apollo_rs_fuzz::corpus::check_parser);
+//! }
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Inputs longer than this are truncated in failure reports
+const MAX_DISPLAYED_INPUT: usize = 500;
+
+/// An input that made a check panic, returned by [`replay_dir`]
+#[derive(Debug, Clone)]
+pub struct ReplayFailure {
+    pub path: PathBuf,
+    pub input: Vec<u8>,
+    /// The panic message
+    pub message: String,
+}
+
+impl fmt::Display for ReplayFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = String::from_utf8_lossy(&self.input);
+        let truncated = input.chars().take(MAX_DISPLAYED_INPUT).collect::<String>();
+        writeln!(
+            f,
+            "--- {} ({} bytes)",
+            self.path.display(),
+            self.input.len()
+        )?;
+        write!(f, "input: {truncated:?}")?;
+        if truncated.len() < input.len() {
+            write!(f, " (truncated)")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "panicked: {}", self.message)
+    }
+}
+
+/// Files of `dir` in sorted order, skipping subdirectories and hidden files
+fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_file() && !hidden {
+            paths.push(path)
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Run `check` on the contents of every file in `dir`, such as crash inputs saved by
+/// `cargo fuzz`, and return the inputs for which it panicked.
+pub fn replay_dir(dir: impl AsRef<Path>, check: impl Fn(&[u8])) -> io::Result<Vec<ReplayFailure>> {
+    let mut failures = Vec::new();
+    for path in files(dir.as_ref())? {
+        let input = fs::read(&path)?;
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| check(&input)));
+        if let Err(payload) = result {
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                message.to_string()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "(non-string panic payload)".to_owned()
+            };
+            failures.push(ReplayFailure {
+                path,
+                input,
+                message,
+            })
+        }
+    }
+    Ok(failures)
+}
+
+/// Like [`replay_dir`], but panics with a report of every failing input if any.
+/// Meant to be called from a `#[test]`.
+pub fn assert_replay_dir(dir: impl AsRef<Path>, check: impl Fn(&[u8])) {
+    let dir = dir.as_ref();
+    let failures = replay_dir(dir, check)
+        .unwrap_or_else(|err| panic!("could not read {}: {err}", dir.display()));
+    if !failures.is_empty() {
+        let mut report = format!("{} input(s) in {} failed:\n", failures.len(), dir.display());
+        for failure in &failures {
+            report.push_str(&failure.to_string());
+        }
+        panic!("{report}")
+    }
+}
+
+/// A check for [`replay_dir`] and fuzz targets: lexes and parses UTF-8 input with apollo-parser,
+/// and panics if any of its [invariants][apollo_parser::check_invariants] do not hold.
+/// Input that is not UTF-8 is ignored.
+pub fn check_parser(input: &[u8]) {
+    let Ok(input) = std::str::from_utf8(input) else {
+        return;
+    };
+    let check = apollo_parser::check_invariants(input);
+    if !check.violations.is_empty() {
+        panic!(
+            "parser invariants do not hold:\n{}",
+            check.violations.join("\n")
+        )
+    }
+}
+
+/// Select a small subset of the files of `dir` that together exercise
+/// every part of the GraphQL grammar that the whole directory exercises,
+/// as measured by the kinds of syntax nodes and tokens in apollo-parser syntax trees.
+///
+/// Files are picked greedily by how many not-yet-covered kinds they add,
+/// preferring shorter files in case of a tie.
+/// Files that are not UTF-8 are skipped.
+/// Returns the selected paths, in sorted order.
+/// Copy them to a new directory to get a distilled corpus.
+pub fn distill_dir(dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let mut candidates = Vec::new();
+    for path in files(dir.as_ref())? {
+        let Ok(input) = String::from_utf8(fs::read(&path)?) else {
+            continue;
+        };
+        let kinds = apollo_parser::check_invariants(&input).syntax_kinds;
+        candidates.push((path, input.len(), kinds));
+    }
+    let mut covered = std::collections::BTreeSet::new();
+    let mut selected = Vec::new();
+    loop {
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, (_, len, kinds))| (i, kinds.difference(&covered).count(), *len))
+            .filter(|&(_, new_kinds, _)| new_kinds > 0)
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)));
+        let Some((i, _, _)) = best else { break };
+        let (path, _, kinds) = candidates.swap_remove(i);
+        covered.extend(kinds);
+        selected.push(path);
+    }
+    selected.sort();
+    Ok(selected)
+}
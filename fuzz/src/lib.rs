@@ -1,3 +1,5 @@
+pub mod corpus;
+
 use apollo_smith::DocumentBuilder;
 use libfuzzer_sys::arbitrary::Result;
 use libfuzzer_sys::arbitrary::Unstructured;
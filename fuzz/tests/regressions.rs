@@ -0,0 +1,43 @@
+use apollo_rs_fuzz::corpus::assert_replay_dir;
+use apollo_rs_fuzz::corpus::check_parser;
+use apollo_rs_fuzz::corpus::distill_dir;
+use apollo_rs_fuzz::corpus::replay_dir;
+use std::path::Path;
+
+fn regressions_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/regressions/parser"))
+}
+
+#[test]
+fn parser_regressions() {
+    assert_replay_dir(regressions_dir(), check_parser);
+}
+
+#[test]
+fn replay_reports_failures() {
+    let failures = replay_dir(regressions_dir(), |input| {
+        assert!(!input.is_empty(), "empty input")
+    })
+    .unwrap();
+    assert_eq!(failures.len(), 1);
+    assert!(failures[0].path.ends_with("empty.graphql"));
+    assert_eq!(
+        failures[0].to_string(),
+        format!(
+            "--- {} (0 bytes)\ninput: \"\"\npanicked: empty input\n",
+            failures[0].path.display()
+        )
+    );
+}
+
+#[test]
+fn distill_keeps_covering_inputs() {
+    let selected = distill_dir(regressions_dir()).unwrap();
+    let names: Vec<_> = selected
+        .iter()
+        .map(|path| path.file_name().unwrap().to_str().unwrap())
+        .collect();
+    // The empty document adds nothing that others do not cover
+    assert!(!names.contains(&"empty.graphql"), "{names:?}");
+    assert!(names.contains(&"deep_list_nesting.graphql"), "{names:?}");
+}
@@ -267,6 +267,7 @@ impl Field {
                     "|" => "pipe",
                     "=" => "eq",
                     ":" => "colon",
+                    "?" => "question",
                     "..." => "dotdotdot",
                     _ => name,
                 };
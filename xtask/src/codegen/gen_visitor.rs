@@ -0,0 +1,67 @@
+use crate::cst_src::CstSrc;
+use crate::reformat;
+use crate::utils::to_lower_snake_case;
+use crate::utils::to_upper_snake_case;
+use anyhow::Result;
+use quote::format_ident;
+use quote::quote;
+
+pub(crate) fn generate_visitor(grammar: &CstSrc) -> Result<String> {
+    let nodes: Vec<_> = grammar
+        .nodes
+        .iter()
+        .map(|node| {
+            let name = format_ident!("{}", node.name);
+            let kind = format_ident!("{}", to_upper_snake_case(&node.name));
+            let snake = to_lower_snake_case(&node.name);
+            let enter = format_ident!("enter_{}", snake);
+            let exit = format_ident!("exit_{}", snake);
+            (name, kind, enter, exit)
+        })
+        .collect();
+
+    let methods = nodes.iter().map(|(name, _, enter, exit)| {
+        quote! {
+            fn #enter(&mut self, _node: &#name) {}
+            fn #exit(&mut self, _node: &#name) {}
+        }
+    });
+    let enter_arms = nodes.iter().map(|(name, kind, enter, _)| {
+        quote! { #kind => visitor.#enter(&#name { syntax: node }), }
+    });
+    let exit_arms = nodes.iter().map(|(name, kind, _, exit)| {
+        quote! { #kind => visitor.#exit(&#name { syntax: node }), }
+    });
+
+    let visitor = quote! {
+        use crate::cst::*;
+        use crate::SyntaxKind::*;
+
+        #[pretty_doc_comment_placeholder_workaround]
+        pub trait Visitor {
+            #(#methods)*
+        }
+
+        pub(crate) fn enter(visitor: &mut (impl Visitor + ?Sized), node: SyntaxNode) {
+            match node.kind() {
+                #(#enter_arms)*
+                _ => {}
+            }
+        }
+
+        pub(crate) fn exit(visitor: &mut (impl Visitor + ?Sized), node: SyntaxNode) {
+            match node.kind() {
+                #(#exit_arms)*
+                _ => {}
+            }
+        }
+    };
+
+    let visitor = visitor.to_string().replace(
+        "# [pretty_doc_comment_placeholder_workaround] ",
+        "/// Callbacks called by [`walk`] when entering and exiting each kind of node.\n\
+         ///\n\
+         /// All methods do nothing by default: implement those for the nodes of interest.\n",
+    );
+    reformat(&visitor)
+}
@@ -1,5 +1,6 @@
 mod gen_syntax_kinds;
 mod gen_syntax_nodes;
+mod gen_visitor;
 
 use crate::cst_src::Cardinality;
 use crate::cst_src::CstEnumSrc;
@@ -16,6 +17,7 @@ use anyhow::Result;
 use clap::Parser;
 use gen_syntax_kinds::generate_kinds;
 use gen_syntax_nodes::generate_nodes;
+use gen_visitor::generate_visitor;
 use std::collections::BTreeSet;
 use ungrammar::Grammar;
 use ungrammar::Rule;
@@ -37,6 +39,10 @@ impl Codegen {
         let cst_nodes_file = project_root().join("crates/apollo-parser/src/cst/generated/nodes.rs");
         let contents = generate_nodes(KINDS_SRC, &cst)?;
         ensure_file_contents(cst_nodes_file.as_path(), &contents)?;
+
+        let visitor_file = project_root().join("crates/apollo-parser/src/cst/generated/visitor.rs");
+        let contents = generate_visitor(&cst)?;
+        ensure_file_contents(visitor_file.as_path(), &contents)?;
         Ok(())
     }
 }
@@ -23,6 +23,7 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         ("...", "SPREAD"),
         ("=", "EQ"),
         (":", "COLON"),
+        ("?", "QUESTION"),
     ],
     keywords: &[
         "query",
@@ -149,6 +150,11 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         "DIRECTIVE_LOCATION",
         "EXECUTABLE_DIRECTIVE_LOCATION",
         "TYPE_SYSTEM_DIRECTIVE_LOCATION",
+        "NULLABILITY",
+        "LIST_NULLABILITY",
+        "NULLABILITY_DESIGNATOR",
+        "CUSTOM_DEFINITION",
+        "MISSING",
     ],
 };
 